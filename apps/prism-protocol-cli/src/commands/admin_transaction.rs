@@ -0,0 +1,214 @@
+use crate::commands::broadcast::{write_signed_transactions, SignedTransactionRecord};
+use crate::error::{program_failure, rpc_failure, CliError, CliResult};
+use prism_protocol_client::PrismProtocolClient;
+use prism_protocol_sdk::{
+    encode_signed_transaction, sign_with_nonce, ComputeBudgetConfig, DurableNonce,
+    OfflineSigningError, PriorityFee,
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// Durable nonce settings of an admin command run with `--sign-only`
+pub struct SignOnly {
+    /// Durable nonce account backing the transaction
+    pub nonce: Pubkey,
+
+    /// Value stored in the nonce account
+    pub blockhash: Hash,
+
+    /// Nonce authority keypair file (defaults to the admin keypair)
+    pub nonce_authority: Option<PathBuf>,
+
+    /// Output file for the signed transaction
+    pub signed_tx_out: PathBuf,
+}
+
+/// Durable nonce settings of a multi-transaction admin command run with `--sign-only`
+pub struct SignOnlyNonces {
+    /// Durable nonce accounts, one per transaction
+    pub nonces: Vec<Pubkey>,
+
+    /// Value stored in each nonce account, in `nonces` order
+    pub blockhashes: Vec<Hash>,
+
+    /// Nonce authority keypair file (defaults to the admin keypair)
+    pub nonce_authority: Option<PathBuf>,
+
+    /// Output file for the signed transactions
+    pub signed_tx_out: PathBuf,
+}
+
+impl SignOnlyNonces {
+    /// Nonce accounts paired with their stored values
+    pub fn pairs(&self) -> CliResult<Vec<(Pubkey, Hash)>> {
+        if self.nonces.len() != self.blockhashes.len() {
+            return Err(CliError::InvalidConfig(format!(
+                "Got {} nonce account(s) but {} blockhash(es); pass one --blockhash per --nonce",
+                self.nonces.len(),
+                self.blockhashes.len()
+            )));
+        }
+        Ok(self
+            .nonces
+            .iter()
+            .copied()
+            .zip(self.blockhashes.iter().copied())
+            .collect())
+    }
+}
+
+/// Parse a hex campaign fingerprint
+pub fn parse_fingerprint(campaign: &str) -> CliResult<[u8; 32]> {
+    parse_hash("campaign fingerprint", campaign)
+}
+
+/// Parse a hex cohort merkle root
+pub fn parse_merkle_root(cohort: &str) -> CliResult<[u8; 32]> {
+    parse_hash("cohort merkle root", cohort)
+}
+
+fn parse_hash(what: &str, value: &str) -> CliResult<[u8; 32]> {
+    let bytes = hex::decode(value.trim())
+        .map_err(|e| CliError::InvalidConfig(format!("Invalid {} '{}': {}", what, value, e)))?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        CliError::InvalidConfig(format!(
+            "The {} must be 32 bytes, got {}",
+            what,
+            bytes.len()
+        ))
+    })
+}
+
+/// Send a single admin instruction, or sign it against a durable nonce for `broadcast`
+///
+/// Online, `priority_fee` defaults to `auto`; offline it defaults to `none`, since
+/// estimating a fee needs RPC access.
+pub fn send_or_sign(
+    description: &str,
+    instruction: Instruction,
+    admin_keypair: &Keypair,
+    rpc_url: &str,
+    priority_fee: Option<String>,
+    sign_only: Option<SignOnly>,
+) -> CliResult<()> {
+    let default_fee = if sign_only.is_some() { "none" } else { "auto" };
+    let priority_fee = PriorityFee::from_str(priority_fee.as_deref().unwrap_or(default_fee))
+        .map_err(CliError::InvalidConfig)?;
+    let budget = ComputeBudgetConfig::with_priority_fee(priority_fee);
+
+    let Some(sign_only) = sign_only else {
+        let rpc_client = Arc::new(RpcClient::new_with_commitment(
+            rpc_url,
+            CommitmentConfig::confirmed(),
+        ));
+        let client = PrismProtocolClient::new(rpc_client.clone());
+        let assembled = client
+            .assemble_transaction(
+                &[instruction],
+                &admin_keypair.pubkey(),
+                &[admin_keypair],
+                &budget,
+            )
+            .map_err(|e| program_failure("Failed to assemble transaction", e))?;
+        let signature = rpc_client
+            .send_and_confirm_transaction(&assembled.transaction)
+            .map_err(|e| rpc_failure("Failed to send transaction", e))?;
+        println!("✅ {}: {}", description, signature);
+        return Ok(());
+    };
+
+    let records = sign_admin_transactions(
+        &[(description.to_string(), vec![instruction])],
+        admin_keypair,
+        &[(sign_only.nonce, sign_only.blockhash)],
+        sign_only.nonce_authority.as_deref(),
+        &budget,
+    )?;
+    write_signed_transactions(&sign_only.signed_tx_out, &records)?;
+
+    println!(
+        "✅ Signed with nonce {}; wrote {}",
+        sign_only.nonce,
+        sign_only.signed_tx_out.display()
+    );
+    println!("   Submit it from an online machine with `prism-protocol broadcast`");
+    Ok(())
+}
+
+/// Sign admin transactions offline, each against the next durable nonce in `nonces`
+///
+/// `transactions` pairs a description with the instructions of each transaction; the
+/// admin pays the fees, and the nonce authority (the admin unless given) co-signs.
+pub fn sign_admin_transactions(
+    transactions: &[(String, Vec<Instruction>)],
+    admin_keypair: &Keypair,
+    nonces: &[(Pubkey, Hash)],
+    nonce_authority: Option<&Path>,
+    budget: &ComputeBudgetConfig,
+) -> CliResult<Vec<SignedTransactionRecord>> {
+    if budget.priority_fee.needs_samples() {
+        return Err(CliError::InvalidConfig(
+            OfflineSigningError::PriorityFeeNeedsRpc(budget.priority_fee.to_string()).to_string(),
+        ));
+    }
+    if transactions.len() > nonces.len() {
+        return Err(CliError::InvalidConfig(format!(
+            "{} transaction(s) to sign but {} nonce account(s); each transaction needs its own nonce",
+            transactions.len(),
+            nonces.len()
+        )));
+    }
+
+    let nonce_authority = match nonce_authority {
+        Some(path) => Some(read_keypair_file(path).map_err(|e| {
+            CliError::InvalidConfig(format!("Failed to read nonce authority keypair: {}", e))
+        })?),
+        None => None,
+    };
+    let authority = nonce_authority
+        .as_ref()
+        .map_or(admin_keypair.pubkey(), |keypair| keypair.pubkey());
+
+    let mut signers: Vec<&Keypair> = vec![admin_keypair];
+    signers.extend(nonce_authority.as_ref());
+
+    let mut records = Vec::with_capacity(transactions.len());
+    for ((description, instructions), (account, blockhash)) in transactions.iter().zip(nonces) {
+        let nonce = DurableNonce {
+            account: *account,
+            authority,
+            blockhash: *blockhash,
+        };
+        let transaction = sign_with_nonce(
+            instructions,
+            &admin_keypair.pubkey(),
+            &signers,
+            &nonce,
+            &[],
+            budget,
+        )
+        .map_err(|e| CliError::InvalidConfig(e.to_string()))?;
+
+        records.push(SignedTransactionRecord {
+            description: description.clone(),
+            signature: transaction.signatures[0].to_string(),
+            nonce_account: account.to_string(),
+            claim: None,
+            ordered: false,
+            transaction: encode_signed_transaction(&transaction)
+                .map_err(|e| CliError::InvalidConfig(e.to_string()))?,
+        });
+    }
+    Ok(records)
+}
@@ -0,0 +1,146 @@
+use crate::error::{program_failure, rpc_failure, CliError, CliResult};
+use prism_protocol_client::PrismProtocolClient;
+use prism_protocol_db::CampaignDatabase;
+use prism_protocol_sdk::decode_signed_transaction;
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::{fs, path::Path, path::PathBuf, str::FromStr, sync::Arc};
+
+/// One transaction in a sign-only output file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignedTransactionRecord {
+    /// What the transaction does, for the operator reviewing the file
+    pub description: String,
+
+    /// Fee payer signature (the transaction id once broadcast)
+    pub signature: String,
+
+    /// Durable nonce account the transaction advances
+    pub nonce_account: String,
+
+    /// Claim to record in the campaign database once the transaction lands
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim: Option<SignedClaim>,
+
+    /// Later transactions depend on this one (a deploy step), so broadcast waits for it
+    /// to confirm and stops if it fails
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ordered: bool,
+
+    /// Base64 wire-format signed transaction
+    pub transaction: String,
+}
+
+/// Claimant and cohort of a signed claim transaction
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignedClaim {
+    pub claimant: String,
+    pub cohort_name: String,
+}
+
+/// Write signed transactions as a JSON file
+pub fn write_signed_transactions(
+    path: &Path,
+    records: &[SignedTransactionRecord],
+) -> CliResult<()> {
+    fs::write(path, serde_json::to_string_pretty(records)?)?;
+    Ok(())
+}
+
+pub fn execute(
+    signed_tx_in: PathBuf,
+    campaign_db_in: Option<PathBuf>,
+    rpc_url: String,
+) -> CliResult<()> {
+    println!("📡 Broadcasting signed transactions...");
+    println!("📄 Input: {}", signed_tx_in.display());
+
+    let records: Vec<SignedTransactionRecord> =
+        serde_json::from_str(&fs::read_to_string(&signed_tx_in)?)?;
+    if records.is_empty() {
+        println!("❌ No transactions in {}", signed_tx_in.display());
+        return Ok(());
+    }
+
+    let mut db = match &campaign_db_in {
+        Some(path) => Some(
+            CampaignDatabase::open(path)
+                .map_err(|e| CliError::InvalidConfig(format!("Failed to open database: {}", e)))?,
+        ),
+        None => None,
+    };
+
+    let rpc_client = Arc::new(RpcClient::new_with_commitment(
+        &rpc_url,
+        CommitmentConfig::confirmed(),
+    ));
+    let client = PrismProtocolClient::new(rpc_client.clone());
+
+    let mut sent = 0;
+    let mut stopped = false;
+    for (i, record) in records.iter().enumerate() {
+        println!(
+            "\n📦 Transaction {} of {}: {}",
+            i + 1,
+            records.len(),
+            record.description
+        );
+        println!("   Nonce account: {}", record.nonce_account);
+
+        let transaction = match decode_signed_transaction(&record.transaction) {
+            Ok(transaction) => transaction,
+            Err(e) => {
+                println!("❌ {}", e);
+                continue;
+            }
+        };
+
+        let sent_signature = if record.ordered {
+            rpc_client
+                .send_and_confirm_transaction(&transaction)
+                .map_err(|e| rpc_failure("Failed to send transaction", e))
+        } else {
+            client
+                .send_transaction(&transaction)
+                .map_err(|e| program_failure("Failed to send transaction", e))
+        };
+        let signature = match sent_signature {
+            Ok(signature) => signature,
+            Err(e) => {
+                // A stale nonce shows up as a blockhash error
+                println!("❌ {}", e);
+                println!("   💡 If the nonce was advanced since signing, sign again with its current value");
+                if record.ordered {
+                    println!(
+                        "⚠️  Stopping: the remaining {} transaction(s) depend on this one",
+                        records.len() - i - 1
+                    );
+                    stopped = true;
+                    break;
+                }
+                continue;
+            }
+        };
+        sent += 1;
+
+        if let (Some(db), Some(claim)) = (db.as_mut(), &record.claim) {
+            let claimant = Pubkey::from_str(&claim.claimant).map_err(|e| {
+                CliError::InvalidConfig(format!("Invalid claimant '{}': {}", claim.claimant, e))
+            })?;
+            if let Err(e) =
+                db.update_claim_status(&claimant, &claim.cohort_name, &signature.to_string())
+            {
+                println!("⚠️  Warning: Failed to update database: {}", e);
+            }
+        }
+    }
+
+    println!("\n🎉 Broadcast completed!");
+    println!("   Sent: {}/{}", sent, records.len());
+    if sent < records.len() && !stopped {
+        println!("⚠️  Some transactions failed; the rest were sent independently.");
+    }
+
+    Ok(())
+}
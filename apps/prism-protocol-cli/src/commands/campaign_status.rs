@@ -1,46 +1,16 @@
 use crate::error::{CliError, CliResult};
 use hex;
-use prism_protocol_client::PrismProtocolClient;
-use prism_protocol_db::CampaignDatabase;
+use prism_protocol_client::{CampaignProgress, CampaignState, CohortState, PrismProtocolClient};
+use prism_protocol_db::{CampaignDatabase, DustReport};
+use prism_protocol_sdk::CampaignStatus;
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use solana_sdk::commitment_config::CommitmentConfig;
 use std::{path::PathBuf, sync::Arc};
 
-#[derive(Debug)]
 struct CampaignStatusReport {
-    campaign: CampaignInfo,
-    cohorts: Vec<CohortStatus>,
-}
-
-#[derive(Debug)]
-struct CampaignInfo {
-    fingerprint: [u8; 32],
-    campaign_address: Pubkey,
-    admin: Pubkey,
-    mint: Pubkey,
-    exists: bool,
-    #[allow(dead_code)]
-    account_data_length: Option<usize>,
-}
-
-#[derive(Debug)]
-struct CohortStatus {
-    name: String,
-    merkle_root: [u8; 32],
-    cohort_address: Pubkey,
-    exists: bool,
-    account_data_length: Option<usize>,
-    vaults: Vec<VaultStatus>,
-}
-
-#[derive(Debug)]
-struct VaultStatus {
-    index: u8,
-    vault_address: Pubkey,
-    exists: bool,
-    token_balance: u64,
-    #[allow(dead_code)]
-    account_data_length: Option<usize>,
+    state: CampaignState,
+    progress: CampaignProgress,
+    dust: Option<DustReport>,
 }
 
 pub fn execute(campaign_db_path: PathBuf, rpc_url: String) -> CliResult<()> {
@@ -67,152 +37,55 @@ fn generate_status_report(
     db: &CampaignDatabase,
     client: &PrismProtocolClient,
 ) -> CliResult<CampaignStatusReport> {
-    // Get campaign info from database
-    let campaign_data = db
-        .read_campaign_info()
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to read campaign: {}", e)))?;
-
-    let (campaign_address, _) = client
-        .address_finder()
-        .find_campaign_v0_address(&campaign_data.admin, &campaign_data.fingerprint);
-
-    // Check if campaign exists on-chain using the client's typed method
-    let campaign_account = client
-        .get_campaign_v0(&campaign_data.fingerprint, &campaign_data.admin)
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to query campaign: {}", e)))?;
-
-    let exists = campaign_account.is_some();
-    let account_data_length = if exists { Some(0) } else { None }; // We don't need raw data length anymore
-
-    let campaign_info = CampaignInfo {
-        fingerprint: campaign_data.fingerprint,
-        campaign_address,
-        admin: campaign_data.admin,
-        mint: campaign_data.mint,
-        exists,
-        account_data_length,
-    };
+    // Fetch campaign, cohorts and vaults in batched getMultipleAccounts calls
+    let state = CampaignState::load(client, db)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to load campaign state: {}", e)))?;
+    let progress = state.progress();
 
-    // Get cohort statuses
-    let cohorts = if exists {
-        generate_cohort_statuses(db, client, &campaign_address)?
-    } else {
-        Vec::new()
-    };
+    // Dust accounting is recorded at compile time (absent in older databases)
+    let dust = db
+        .read_dust_report()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read dust report: {}", e)))?;
 
     Ok(CampaignStatusReport {
-        campaign: campaign_info,
-        cohorts,
+        state,
+        progress,
+        dust,
     })
 }
 
-fn generate_cohort_statuses(
-    db: &CampaignDatabase,
-    client: &PrismProtocolClient,
-    campaign_address: &Pubkey,
-) -> CliResult<Vec<CohortStatus>> {
-    let mut cohorts = Vec::new();
-
-    // Get cohort info from database
-    let cohort_data = db
-        .read_cohorts()
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to read cohorts: {}", e)))?;
-
-    for cohort in cohort_data {
-        let (cohort_address, _) = client
-            .address_finder()
-            .find_cohort_v0_address(campaign_address, &cohort.merkle_root);
-
-        // Check if cohort exists on-chain using the client's typed method
-        let cohort_account = client
-            .get_cohort_v0(campaign_address, &cohort.merkle_root)
-            .map_err(|e| CliError::InvalidConfig(format!("Failed to query cohort: {}", e)))?;
-
-        let exists = cohort_account.is_some();
-        let account_data_length = if exists { Some(0) } else { None }; // We don't need raw data length anymore
-
-        // Get vault statuses for this cohort using the client's typed methods
-        let vaults =
-            generate_vault_statuses_from_addresses(client, &cohort_address, &cohort.vaults)?;
-
-        cohorts.push(CohortStatus {
-            name: cohort.name,
-            merkle_root: cohort.merkle_root,
-            cohort_address,
-            exists,
-            account_data_length,
-            vaults,
-        });
-    }
-
-    Ok(cohorts)
-}
-
-fn generate_vault_statuses_from_addresses(
-    client: &PrismProtocolClient,
-    _cohort_address: &Pubkey,
-    vault_addresses: &[Pubkey],
-) -> CliResult<Vec<VaultStatus>> {
-    let mut vaults = Vec::new();
-
-    for (index, &vault_address) in vault_addresses.iter().enumerate() {
-        // Use the client's typed token account method
-        let token_account = client
-            .get_token_account(&vault_address)
-            .map_err(|e| CliError::InvalidConfig(format!("Failed to query vault: {}", e)))?;
-
-        if let Some(account) = token_account {
-            vaults.push(VaultStatus {
-                index: index as u8,
-                vault_address,
-                exists: true,
-                token_balance: account.amount,
-                account_data_length: Some(0), // We don't need raw data length anymore
-            });
-        } else {
-            vaults.push(VaultStatus {
-                index: index as u8,
-                vault_address,
-                exists: false,
-                token_balance: 0,
-                account_data_length: None,
-            });
-        }
-    }
-
-    Ok(vaults)
-}
-
 fn print_status_report(report: &CampaignStatusReport) {
-    print_campaign_info(&report.campaign);
+    print_campaign_info(&report.state);
 
-    if report.campaign.exists {
-        print_cohort_statuses(&report.cohorts);
+    if report.state.account.is_some() {
+        print_cohort_statuses(&report.state.cohorts);
+        print_progress(&report.progress);
     } else {
         println!("\n⚠️  Campaign not deployed - skipping cohort/vault checks");
         println!("   Run `deploy-campaign` to create on-chain accounts");
     }
+
+    if let Some(dust) = &report.dust {
+        print_dust_report(dust);
+    }
 }
 
-fn print_campaign_info(info: &CampaignInfo) {
+fn print_campaign_info(state: &CampaignState) {
     println!("\n🏛️  Campaign Information:");
-    println!("   Fingerprint: {}", hex::encode(info.fingerprint));
-    println!("   Admin: {}", info.admin);
-    println!("   Mint: {}", info.mint);
-    println!("   Address: {}", info.campaign_address);
+    println!("   Fingerprint: {}", hex::encode(state.fingerprint));
+    println!("   Admin: {}", state.admin);
+    println!("   Mint: {}", state.mint);
+    println!("   Address: {}", state.address);
 
-    if info.exists {
+    if state.account.is_some() {
         println!("   ✅ Status: EXISTS");
-        if let Some(len) = info.account_data_length {
-            println!("   📏 Data length: {} bytes", len);
-        }
     } else {
         println!("   ❌ Status: NOT FOUND");
         println!("   💡 Campaign may not be deployed yet");
     }
 }
 
-fn print_cohort_statuses(cohorts: &[CohortStatus]) {
+fn print_cohort_statuses(cohorts: &[CohortState]) {
     if cohorts.is_empty() {
         println!("\n📂 No cohorts found in campaign database");
         return;
@@ -222,14 +95,11 @@ fn print_cohort_statuses(cohorts: &[CohortStatus]) {
 
     for (i, cohort) in cohorts.iter().enumerate() {
         println!("\n   {}. Cohort: {}", i + 1, cohort.name);
-        println!("      Address: {}", cohort.cohort_address);
+        println!("      Address: {}", cohort.address);
         println!("      Merkle Root: {}", hex::encode(cohort.merkle_root));
 
-        if cohort.exists {
+        if cohort.account.is_some() {
             println!("      ✅ Status: EXISTS");
-            if let Some(len) = cohort.account_data_length {
-                println!("      📏 Data length: {} bytes", len);
-            }
         } else {
             println!("      ❌ Status: NOT FOUND");
         }
@@ -238,18 +108,102 @@ fn print_cohort_statuses(cohorts: &[CohortStatus]) {
         if !cohort.vaults.is_empty() {
             println!("      💰 Vaults:");
             for vault in &cohort.vaults {
-                if vault.exists {
+                if vault.account.is_some() {
                     println!(
-                        "         Vault {}: ✅ EXISTS - {} tokens ({})",
-                        vault.index, vault.token_balance, vault.vault_address
+                        "         Vault {}: ✅ EXISTS - {} / {} tokens ({})",
+                        vault.index,
+                        vault.balance(),
+                        vault.required_tokens,
+                        vault.address
                     );
                 } else {
                     println!(
                         "         Vault {}: ❌ NOT FOUND ({})",
-                        vault.index, vault.vault_address
+                        vault.index, vault.address
                     );
                 }
             }
         }
     }
 }
+
+fn print_progress(progress: &CampaignProgress) {
+    let status = match progress.status {
+        Some(CampaignStatus::Inactive) => "Inactive",
+        Some(CampaignStatus::Active) => "Active",
+        Some(CampaignStatus::Paused) => "Paused",
+        Some(CampaignStatus::PermanentlyHalted) => "Permanently halted",
+        None => "Not deployed",
+    };
+
+    println!("\n📈 Progress:");
+    println!("   Campaign status: {}", status);
+    println!(
+        "   Cohorts: {}/{} initialized, {} activated",
+        progress.cohorts_initialized, progress.cohorts_total, progress.cohorts_activated
+    );
+    println!(
+        "   Vaults: {}/{} initialized, {} activated, {} funded",
+        progress.vaults_initialized,
+        progress.vaults_total,
+        progress.vaults_activated,
+        progress.vaults_funded
+    );
+    println!(
+        "   Funding: {}/{} tokens",
+        progress.tokens_funded, progress.tokens_required
+    );
+
+    if progress.is_fully_deployed() && progress.is_fully_funded() {
+        println!("   ✅ Fully deployed and funded");
+    }
+}
+
+fn print_dust_report(report: &DustReport) {
+    let summary = &report.summary;
+
+    println!("\n🧹 Dust:");
+    println!("   Policy: {}", summary.policy);
+    if let Some(treasury) = summary.treasury {
+        println!("   Treasury: {}", treasury);
+        match &summary.treasury_signature {
+            Some(signature) => println!(
+                "   ✅ Sent {} base units to treasury: {}",
+                summary.treasury_amount, signature
+            ),
+            None => println!(
+                "   ⏳ {} base units owed to treasury (sent by deploy-campaign)",
+                summary.treasury_amount
+            ),
+        }
+    }
+    println!("   Budget: {}", summary.budget);
+    println!("   Total funding: {}", summary.total_funding);
+    println!("   Unallocated budget: {}", summary.unallocated_budget);
+    println!("   Total dust: {}", summary.total_dust);
+
+    for cohort in &report.cohorts {
+        println!("\n   Cohort: {}", cohort.cohort_name);
+        println!("      Budget: {}", cohort.cohort_budget);
+        println!("      Allocated: {}", cohort.allocated);
+        println!("      Allocation dust: {}", cohort.allocation_dust);
+        println!("      Funding dust: {}", cohort.funding_dust);
+        if !cohort.redistributed.is_zero() {
+            println!(
+                "      ♻️  Redistributed to leaves: {}",
+                cohort.redistributed
+            );
+        }
+
+        for vault in report
+            .vaults
+            .iter()
+            .filter(|v| v.cohort_name == cohort.cohort_name)
+        {
+            println!(
+                "         Vault {}: {} required, exact share {}, dust {}",
+                vault.vault_index, vault.required_tokens, vault.exact_share, vault.dust
+            );
+        }
+    }
+}
@@ -1,19 +1,24 @@
-use crate::error::{CliError, CliResult};
+use crate::commands::broadcast::{write_signed_transactions, SignedClaim, SignedTransactionRecord};
+use crate::error::{program_failure, CliError, CliResult};
 use hex;
 use prism_protocol_client::PrismProtocolClient;
-use prism_protocol_db::{CampaignDatabase, EligibilityInfo};
-use prism_protocol_sdk::build_claim_tokens_ix;
+use prism_protocol_db::{CampaignDatabase, EligibilityInfo, StoredProof};
+use prism_protocol_sdk::{
+    build_claim_tokens_ix, build_claim_tokens_v2_ix, campaign_lookup_table_addresses,
+    encode_signed_transaction, sign_with_nonce, AddressFinder, ClaimV1Params, ComputeBudgetConfig,
+    DurableNonce, OfflineSigningError, PriorityFee,
+};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
+    hash::Hash,
     instruction::Instruction,
-    message::Message,
+    message::AddressLookupTableAccount,
     pubkey::Pubkey,
     signature::{read_keypair_file, Keypair, Signer},
-    transaction::Transaction,
 };
 use spl_associated_token_account::get_associated_token_address;
-use std::{path::PathBuf, sync::Arc};
+use std::{path::PathBuf, str::FromStr, sync::Arc};
 
 #[derive(Debug)]
 struct ClaimTransaction {
@@ -27,9 +32,13 @@ pub fn execute(
     claimant_keypair_path: PathBuf,
     rpc_url: String,
     dry_run: bool,
+    priority_fee: String,
 ) -> CliResult<()> {
     println!("🎯 Starting token claim process...");
 
+    let priority_fee = PriorityFee::from_str(&priority_fee).map_err(CliError::InvalidConfig)?;
+    let budget = ComputeBudgetConfig::with_priority_fee(priority_fee);
+
     let mut db = CampaignDatabase::open(&campaign_db_path)
         .map_err(|e| CliError::InvalidConfig(format!("Failed to open database: {}", e)))?;
 
@@ -38,7 +47,7 @@ pub fn execute(
         CommitmentConfig::confirmed(),
     ));
 
-    let client = PrismProtocolClient::new(rpc_client.clone());
+    let client = PrismProtocolClient::new(rpc_client);
 
     // Load claimant keypair
     println!("🔑 Loading claimant keypair...");
@@ -56,6 +65,9 @@ pub fn execute(
     println!("   Mint: {}", campaign_info.mint);
     println!("   Admin: {}", campaign_info.admin);
 
+    // Claims reference the campaign's lookup table when deploy created one
+    let lookup_tables = load_lookup_tables(&db, &client)?;
+
     // Find eligible claims
     println!("🔍 Finding eligible claims...");
     let eligibility_info = db
@@ -100,7 +112,8 @@ pub fn execute(
     println!("🔨 Building claim transactions...");
     let claim_transactions = build_claim_transactions(
         &db,
-        &client,
+        client.address_finder(),
+        Some(&client),
         &campaign_info,
         &claimant_pubkey,
         &claimant_token_account,
@@ -119,8 +132,14 @@ pub fn execute(
         );
         println!("   Cohort: {}", claim_tx.eligibility.cohort_name);
 
-        match execute_claim_transaction(&client, &rpc_client, &claimant_keypair, claim_tx, dry_run)
-        {
+        match execute_claim_transaction(
+            &client,
+            &claimant_keypair,
+            claim_tx,
+            &lookup_tables,
+            &budget,
+            dry_run,
+        ) {
             Ok(signature) => {
                 successful_claims += 1;
                 total_tokens_claimed += claim_tx.expected_tokens;
@@ -172,9 +191,234 @@ pub fn execute(
     Ok(())
 }
 
-fn build_claim_transactions(
+/// The campaign's address lookup table, if the database records one that exists on-chain
+fn load_lookup_tables(
     db: &CampaignDatabase,
     client: &PrismProtocolClient,
+) -> CliResult<Vec<AddressLookupTableAccount>> {
+    let Some(address) = db
+        .read_campaign_lookup_table()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read lookup table: {}", e)))?
+    else {
+        println!("   Lookup table: none (legacy transactions)");
+        return Ok(Vec::new());
+    };
+
+    match client
+        .get_address_lookup_table(&address)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to fetch lookup table: {}", e)))?
+    {
+        Some(table) => {
+            println!(
+                "   Lookup table: {} ({} addresses)",
+                address,
+                table.addresses.len()
+            );
+            Ok(vec![table])
+        }
+        None => {
+            println!(
+                "⚠️  Lookup table {} not found on-chain, falling back to legacy transactions",
+                address
+            );
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Sign claims offline against durable nonces and write them for `broadcast`
+///
+/// Reads only the campaign database: the on-chain receipt check is skipped, and claims
+/// compile against the campaign's lookup table as deploy filled it.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_sign_only(
+    campaign_db_path: PathBuf,
+    claimant_keypair_path: PathBuf,
+    nonce_accounts: Vec<Pubkey>,
+    nonce_blockhashes: Vec<Hash>,
+    nonce_authority_path: Option<PathBuf>,
+    priority_fee: String,
+    signed_tx_out: PathBuf,
+) -> CliResult<()> {
+    println!("✍️  Signing claims offline with durable nonces...");
+
+    if nonce_accounts.len() != nonce_blockhashes.len() {
+        return Err(CliError::InvalidConfig(format!(
+            "Got {} nonce account(s) but {} blockhash(es); pass one --blockhash per --nonce",
+            nonce_accounts.len(),
+            nonce_blockhashes.len()
+        )));
+    }
+
+    let priority_fee = PriorityFee::from_str(&priority_fee).map_err(CliError::InvalidConfig)?;
+    if priority_fee.needs_samples() {
+        return Err(CliError::InvalidConfig(
+            OfflineSigningError::PriorityFeeNeedsRpc(priority_fee.to_string()).to_string(),
+        ));
+    }
+    let budget = ComputeBudgetConfig::with_priority_fee(priority_fee);
+
+    let db = CampaignDatabase::open(&campaign_db_path)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to open database: {}", e)))?;
+
+    println!("🔑 Loading claimant keypair...");
+    let claimant_keypair = read_keypair_file(&claimant_keypair_path)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read keypair: {}", e)))?;
+    let claimant_pubkey = claimant_keypair.pubkey();
+    println!("✅ Claimant: {}", claimant_pubkey);
+
+    let nonce_authority = match &nonce_authority_path {
+        Some(path) => Some(read_keypair_file(path).map_err(|e| {
+            CliError::InvalidConfig(format!("Failed to read nonce authority keypair: {}", e))
+        })?),
+        None => None,
+    };
+    let authority_pubkey = nonce_authority
+        .as_ref()
+        .map_or(claimant_pubkey, |keypair| keypair.pubkey());
+    println!("✅ Nonce authority: {}", authority_pubkey);
+
+    let campaign_info = db
+        .read_campaign_info()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read campaign: {}", e)))?;
+    println!("✅ Campaign: {}", hex::encode(campaign_info.fingerprint));
+    let lookup_tables = offline_lookup_tables(&db, &campaign_info)?;
+
+    let eligibility_info = db
+        .read_claimant_eligibility(&claimant_pubkey)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read eligibility: {}", e)))?;
+    let pending_claims: Vec<&EligibilityInfo> = eligibility_info
+        .iter()
+        .filter(|info| !info.db_claimed)
+        .collect();
+
+    if pending_claims.is_empty() {
+        println!(
+            "❌ No pending claims found for claimant {}",
+            claimant_pubkey
+        );
+        return Ok(());
+    }
+
+    // Each nonce account backs exactly one transaction
+    if pending_claims.len() > nonce_accounts.len() {
+        println!(
+            "⚠️  {} pending claim(s) but {} nonce account(s); signing the first {}",
+            pending_claims.len(),
+            nonce_accounts.len(),
+            nonce_accounts.len()
+        );
+    }
+    let pending_claims = &pending_claims[..pending_claims.len().min(nonce_accounts.len())];
+
+    let claimant_token_account =
+        get_associated_token_address(&claimant_pubkey, &campaign_info.mint);
+    let claim_transactions = build_claim_transactions(
+        &db,
+        &AddressFinder::default(),
+        None,
+        &campaign_info,
+        &claimant_pubkey,
+        &claimant_token_account,
+        pending_claims,
+    )?;
+
+    let mut signers: Vec<&Keypair> = vec![&claimant_keypair];
+    signers.extend(nonce_authority.as_ref());
+
+    let mut records = Vec::with_capacity(claim_transactions.len());
+    for ((claim_tx, account), blockhash) in claim_transactions
+        .iter()
+        .zip(&nonce_accounts)
+        .zip(&nonce_blockhashes)
+    {
+        let nonce = DurableNonce {
+            account: *account,
+            authority: authority_pubkey,
+            blockhash: *blockhash,
+        };
+        let transaction = sign_with_nonce(
+            &[claim_tx.claim_ix.clone()],
+            &claimant_pubkey,
+            &signers,
+            &nonce,
+            &lookup_tables,
+            &budget,
+        )
+        .map_err(|e| CliError::InvalidConfig(e.to_string()))?;
+
+        println!(
+            "✅ Signed claim for cohort {} ({} tokens) with nonce {}",
+            claim_tx.eligibility.cohort_name, claim_tx.expected_tokens, account
+        );
+        records.push(SignedTransactionRecord {
+            description: format!(
+                "Claim {} tokens from cohort {}",
+                claim_tx.expected_tokens, claim_tx.eligibility.cohort_name
+            ),
+            signature: transaction.signatures[0].to_string(),
+            nonce_account: account.to_string(),
+            claim: Some(SignedClaim {
+                claimant: claimant_pubkey.to_string(),
+                cohort_name: claim_tx.eligibility.cohort_name.clone(),
+            }),
+            ordered: false,
+            transaction: encode_signed_transaction(&transaction)
+                .map_err(|e| CliError::InvalidConfig(e.to_string()))?,
+        });
+    }
+
+    write_signed_transactions(&signed_tx_out, &records)?;
+
+    println!(
+        "\n🎉 Wrote {} signed transaction(s) to {}",
+        records.len(),
+        signed_tx_out.display()
+    );
+    println!("   Submit them from an online machine with `prism-protocol broadcast`");
+
+    Ok(())
+}
+
+/// The campaign's lookup table rebuilt from the database, for signing without RPC
+///
+/// Deploy appends [`campaign_lookup_table_addresses`] to the table in order, so the same
+/// list compiles to the indexes the table holds on-chain.
+fn offline_lookup_tables(
+    db: &CampaignDatabase,
+    campaign_info: &prism_protocol_db::CampaignInfo,
+) -> CliResult<Vec<AddressLookupTableAccount>> {
+    let Some(key) = db
+        .read_campaign_lookup_table()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read lookup table: {}", e)))?
+    else {
+        println!("   Lookup table: none (legacy transactions)");
+        return Ok(Vec::new());
+    };
+
+    let cohorts = db
+        .read_cohorts()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read cohorts: {}", e)))?;
+    let addresses = campaign_lookup_table_addresses(
+        &AddressFinder::default(),
+        &campaign_info.admin,
+        &campaign_info.fingerprint,
+        &campaign_info.mint,
+        &cohorts,
+    )
+    .addresses;
+    println!(
+        "   Lookup table: {} ({} addresses, as deployed)",
+        key,
+        addresses.len()
+    );
+    Ok(vec![AddressLookupTableAccount { key, addresses }])
+}
+
+fn build_claim_transactions(
+    db: &CampaignDatabase,
+    address_finder: &AddressFinder,
+    client: Option<&PrismProtocolClient>,
     campaign_info: &prism_protocol_db::CampaignInfo,
     claimant: &Pubkey,
     claimant_token_account: &Pubkey,
@@ -184,29 +428,31 @@ fn build_claim_transactions(
 
     for eligibility in pending_claims {
         // Get merkle proof from database
-        let proof_data = db
-            .read_merkle_proof(claimant, &eligibility.cohort_name)
+        let stored_proof = db
+            .read_claimant_proof(claimant, &eligibility.cohort_name)
             .map_err(|e| CliError::InvalidConfig(format!("Failed to read merkle proof: {}", e)))?;
 
-        // Calculate addresses using the client's address finder
-        let (campaign_address, _) = client
-            .address_finder()
+        // Calculate addresses
+        let (campaign_address, _) = address_finder
             .find_campaign_v0_address(&campaign_info.admin, &campaign_info.fingerprint);
 
-        let (cohort_address, _) = client
-            .address_finder()
+        let (cohort_address, _) = address_finder
             .find_cohort_v0_address(&campaign_address, &eligibility.cohort_merkle_root);
 
-        let (claim_receipt_address, _) = client
-            .address_finder()
-            .find_claim_receipt_v0_address(&cohort_address, claimant);
-
-        // Check if already claimed on-chain using the client's typed method
-        if client
-            .get_claim_receipt_v0(&cohort_address, claimant)
-            .map_err(|e| CliError::InvalidConfig(format!("Failed to check claim receipt: {}", e)))?
-            .is_some()
-        {
+        let (claim_receipt_address, _) =
+            address_finder.find_claim_receipt_v0_address(&cohort_address, claimant);
+
+        // Check if already claimed on-chain (skipped when signing offline)
+        let claimed_on_chain = match client {
+            Some(client) => client
+                .get_claim_receipt_v0(&cohort_address, claimant)
+                .map_err(|e| {
+                    CliError::InvalidConfig(format!("Failed to check claim receipt: {}", e))
+                })?
+                .is_some(),
+            None => false,
+        };
+        if claimed_on_chain {
             println!(
                 "⚠️  Cohort {} already claimed on-chain, skipping",
                 eligibility.cohort_name
@@ -214,20 +460,6 @@ fn build_claim_transactions(
             continue;
         }
 
-        // Parse merkle proof from database format
-        let merkle_proof: Vec<[u8; 32]> = proof_data
-            .merkle_proof
-            .iter()
-            .map(|hex_str| {
-                let bytes = hex::decode(hex_str.trim())
-                    .map_err(|e| CliError::InvalidConfig(format!("Invalid proof hex: {}", e)))?;
-                let hash: [u8; 32] = bytes.try_into().map_err(|_| {
-                    CliError::InvalidConfig("Proof hash must be 32 bytes".to_string())
-                })?;
-                Ok(hash)
-            })
-            .collect::<CliResult<Vec<[u8; 32]>>>()?;
-
         // Get vault assignment from database (the correct, stored assignment)
         let (vault_index, assigned_vault) = db
             .read_claimant_vault_assignment(claimant, &eligibility.cohort_name)
@@ -235,22 +467,47 @@ fn build_claim_transactions(
                 CliError::InvalidConfig(format!("Failed to read vault assignment: {}", e))
             })?;
 
-        // Build claim instruction
-        let (claim_ix, _, _) = build_claim_tokens_ix(
-            campaign_info.admin,
-            *claimant,
-            campaign_address,
-            cohort_address,
-            assigned_vault,
-            campaign_info.mint,
-            *claimant_token_account,
-            claim_receipt_address,
-            campaign_info.fingerprint,
-            eligibility.cohort_merkle_root,
-            merkle_proof,
-            vault_index,
-            eligibility.entitlements,
-        )
+        // Build claim instruction: binary proofs claim V0 cohorts, wide proofs V1 cohorts
+        let claim_ix = match stored_proof {
+            StoredProof::V0(merkle_proof) => build_claim_tokens_ix(
+                campaign_info.admin,
+                *claimant,
+                campaign_address,
+                cohort_address,
+                assigned_vault,
+                campaign_info.mint,
+                *claimant_token_account,
+                claim_receipt_address,
+                campaign_info.fingerprint,
+                eligibility.cohort_merkle_root,
+                merkle_proof,
+                vault_index,
+                eligibility.entitlements,
+            )
+            .map(|(ix, _, _)| ix),
+            StoredProof::V1(merkle_proof) => build_claim_tokens_v2_ix(
+                address_finder,
+                ClaimV1Params {
+                    admin: campaign_info.admin,
+                    claimant: *claimant,
+                    mint: campaign_info.mint,
+                    claimant_token_account: *claimant_token_account,
+                    campaign_fingerprint: campaign_info.fingerprint,
+                    cohort_merkle_root: eligibility.cohort_merkle_root,
+                    merkle_proof,
+                    assigned_vault_index: vault_index,
+                    entitlements: eligibility.entitlements,
+                },
+            )
+            .map(|(ix, _, _)| ix),
+            other => {
+                return Err(CliError::InvalidConfig(format!(
+                    "Cohort {} stores a {} proof, which can't be claimed individually",
+                    eligibility.cohort_name,
+                    other.format_name()
+                )))
+            }
+        }
         .map_err(|e| {
             CliError::InvalidConfig(format!("Failed to build claim instruction: {}", e))
         })?;
@@ -267,9 +524,10 @@ fn build_claim_transactions(
 
 fn execute_claim_transaction(
     client: &PrismProtocolClient,
-    rpc_client: &RpcClient,
     claimant_keypair: &Keypair,
     claim_tx: &ClaimTransaction,
+    lookup_tables: &[AddressLookupTableAccount],
+    budget: &ComputeBudgetConfig,
     dry_run: bool,
 ) -> CliResult<String> {
     if dry_run {
@@ -280,22 +538,29 @@ fn execute_claim_transaction(
         return Ok(String::new());
     }
 
-    // Build transaction using Message API for proper structure
-    let recent_blockhash = rpc_client.get_latest_blockhash()?;
-    let message = Message::new(
-        &[claim_tx.claim_ix.clone()],
-        Some(&claimant_keypair.pubkey()),
-    );
-    let mut transaction = Transaction::new_unsigned(message);
-    transaction.message.recent_blockhash = recent_blockhash;
-
-    // Sign transaction
-    transaction.sign(&[claimant_keypair], recent_blockhash);
+    // Size the CU limit by simulation (large proofs) and add the priority fee; with a
+    // lookup table this is a v0 transaction, leaving more room for the proof
+    let assembled = client
+        .assemble_versioned_transaction(
+            &[claim_tx.claim_ix.clone()],
+            &claimant_keypair.pubkey(),
+            &[claimant_keypair],
+            lookup_tables,
+            budget,
+        )
+        .map_err(|e| program_failure("Failed to assemble transaction", e))?;
+    if let Some(limit) = assembled.compute_unit_limit {
+        println!("   Compute unit limit: {}", limit);
+    }
+    if let Some(price) = assembled.compute_unit_price {
+        println!("   Priority fee: {} micro-lamports/CU", price);
+    }
+    let transaction = assembled.transaction;
 
     // Use the client's transaction sending capabilities
     let signature = client
         .send_transaction(&transaction)
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to send transaction: {}", e)))?;
+        .map_err(|e| program_failure("Failed to send transaction", e))?;
 
     println!("   Transaction signature: {}", signature);
     Ok(signature.to_string())
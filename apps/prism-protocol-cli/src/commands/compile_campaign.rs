@@ -1,6 +1,14 @@
 use crate::error::{CliError, CliResult};
 use prism_protocol_client::PrismProtocolClient;
-use prism_protocol_sdk::{compile_campaign, AddressFinder};
+use prism_protocol_csvs::{read_campaign_csv, read_cohorts_csv};
+use prism_protocol_db::CampaignDatabase;
+use prism_protocol_sdk::{
+    campaign_layout::cohort_sizes, compile_campaign_db_with_options, compile_campaign_streaming,
+    compile_manifest_db, compile_manifest_streaming, load_manifest, open_manifest, plan_layout,
+    AddressFinder, CampaignLayout, CampaignManifest, CampaignSettings, ClaimHashScheme,
+    CompileOptions, CompileProgress, DustPolicy, LayoutConstraints, LeafOrder, StreamingOptions,
+    TreeVersion,
+};
 use rust_decimal::Decimal;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
@@ -11,6 +19,7 @@ use solana_sdk::{
 use std::str::FromStr;
 use std::{path::PathBuf, sync::Arc};
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute(
     campaign_csv_in: PathBuf,
     cohorts_csv_in: PathBuf,
@@ -20,6 +29,13 @@ pub fn execute(
     claimants_per_vault: usize,
     campaign_db_out: PathBuf,
     rpc_url: String,
+    dust_policy: String,
+    leaf_order: String,
+    streaming: bool,
+    work_dir: Option<PathBuf>,
+    auto_layout: bool,
+    tree_arity: Option<usize>,
+    hash_scheme: String,
 ) -> CliResult<()> {
     println!("Starting campaign compilation");
     println!("Campaign CSV: {}", campaign_csv_in.display());
@@ -30,6 +46,24 @@ pub fn execute(
     println!("Claimants per vault: {}", claimants_per_vault);
     println!("Output database: {}", campaign_db_out.display());
     println!("RPC URL: {}", rpc_url);
+    println!("Dust policy: {}", dust_policy);
+    println!("Leaf order: {}", leaf_order);
+    println!("Streaming: {}", streaming);
+    println!("Auto layout: {}", auto_layout);
+    if let Some(arity) = tree_arity {
+        println!("Tree arity: {} (V1 trees)", arity);
+        println!("Hash scheme: {}", hash_scheme);
+    }
+
+    let dust_policy = DustPolicy::from_str(&dust_policy).map_err(CliError::InvalidConfig)?;
+    let leaf_order = LeafOrder::from_str(&leaf_order).map_err(CliError::InvalidConfig)?;
+    let hash_scheme = ClaimHashScheme::from_str(&hash_scheme).map_err(CliError::InvalidConfig)?;
+    let compile_options = CompileOptions {
+        dust_policy,
+        leaf_order,
+        hash_scheme,
+        ..Default::default()
+    };
 
     // Parse budget
     println!("Parsing budget...");
@@ -46,19 +80,7 @@ pub fn execute(
     println!("Admin public key: {}", admin_pubkey);
 
     // Discover mint decimals using our custom RPC client
-    let rpc_client = Arc::new(RpcClient::new_with_commitment(
-        &rpc_url,
-        CommitmentConfig::confirmed(),
-    ));
-
-    let client = PrismProtocolClient::new(rpc_client.clone());
-
-    let mint_info = client
-        .get_mint(&mint)
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to fetch mint {}: {}", mint, e)))?
-        .ok_or_else(|| CliError::InvalidConfig(format!("Mint {} not found", mint)))?;
-
-    let mint_decimals = mint_info.decimals;
+    let mint_decimals = fetch_mint_decimals(&rpc_url, &mint)?;
     println!("Discovered mint decimals: {}", mint_decimals);
 
     // Check if output file exists
@@ -73,25 +95,283 @@ pub fn execute(
     println!("Compiling campaign from CSV files...");
     let address_finder = AddressFinder::default();
 
-    let db = compile_campaign(
-        address_finder,
-        &campaign_csv_in,
-        &cohorts_csv_in,
-        budget_decimal,
-        mint,
-        mint_decimals,
-        admin_pubkey,
-        claimants_per_vault,
-    )
-    .map_err(|e| CliError::InvalidConfig(format!("Campaign compilation failed: {}", e)))?;
-
-    // Save database to file
-    println!("Saving compiled campaign to database file...");
-    db.save_to_file(&campaign_db_out, true)
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to save database: {}", e)))?;
+    let cohorts_rows = read_cohorts_csv(&cohorts_csv_in)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read cohorts CSV: {}", e)))?;
+    let compile_options = CompileOptions {
+        tree_arities: tree_arity
+            .map(|arity| {
+                cohorts_rows
+                    .iter()
+                    .map(|row| (row.cohort.clone(), arity))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ..compile_options
+    };
+
+    if streaming {
+        // Written straight to disk (the database may not fit in memory) and moved into
+        // place once complete
+        let options = StreamingOptions {
+            compile: compile_options,
+            work_dir,
+            // Planned from the cohort sizes counted while sorting
+            layout: auto_layout.then_some(LayoutConstraints {
+                max_claimants_per_vault: claimants_per_vault,
+                tree_arity,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let summary = compile_campaign_streaming(
+            address_finder,
+            &campaign_csv_in,
+            &cohorts_rows,
+            &CampaignSettings {
+                budget: budget_decimal,
+                mint,
+                mint_decimals,
+                admin: admin_pubkey,
+                claimants_per_vault,
+            },
+            &campaign_db_out,
+            &options,
+            print_progress,
+        )
+        .map_err(|e| CliError::InvalidConfig(format!("Campaign compilation failed: {}", e)))?;
+        if let Some(layout) = &summary.layout {
+            print_layout(layout);
+        }
+        let db = CampaignDatabase::open(&campaign_db_out)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to open database: {}", e)))?;
+
+        println!(
+            "📊 {} claimants, {} vaults, {} base units of funding",
+            summary.total_claimants, summary.total_vaults, summary.total_funding
+        );
+        warn_unallocated_budget(&db)?;
+    } else {
+        let campaign_rows = read_campaign_csv(&campaign_csv_in)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to read campaign CSV: {}", e)))?;
+
+        let compile_options = if auto_layout {
+            let layout = plan_layout(
+                &cohort_sizes(&campaign_rows, &cohorts_rows),
+                &LayoutConstraints {
+                    max_claimants_per_vault: claimants_per_vault,
+                    tree_arity,
+                    ..Default::default()
+                },
+            )
+            .map_err(|e| CliError::InvalidConfig(format!("Layout planning failed: {}", e)))?;
+            print_layout(&layout);
+
+            compile_options.with_layout(&layout)
+        } else {
+            compile_options
+        };
+
+        let db = compile_campaign_db_with_options(
+            address_finder,
+            &campaign_rows,
+            &cohorts_rows,
+            &CampaignSettings {
+                budget: budget_decimal,
+                mint,
+                mint_decimals,
+                admin: admin_pubkey,
+                claimants_per_vault,
+            },
+            &compile_options,
+        )
+        .map_err(|e| CliError::InvalidConfig(format!("Campaign compilation failed: {}", e)))?;
+        warn_unallocated_budget(&db)?;
+
+        // Save database to file
+        println!("Saving compiled campaign to database file...");
+        db.save_to_file(&campaign_db_out, true)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to save database: {}", e)))?;
+    }
 
     println!("Campaign compilation completed successfully!");
     println!("Database saved to: {}", campaign_db_out.display());
 
     Ok(())
 }
+
+/// Compile a campaign from a manifest, recording the manifest hash in the database
+pub fn execute_manifest(
+    manifest_path: PathBuf,
+    campaign_db_out: PathBuf,
+    rpc_url: String,
+    streaming: bool,
+    work_dir: Option<PathBuf>,
+) -> CliResult<()> {
+    println!("Starting campaign compilation");
+    println!("📄 Manifest: {}", manifest_path.display());
+    println!("Output database: {}", campaign_db_out.display());
+    println!("Streaming: {}", streaming);
+
+    if streaming {
+        // Claimant files are only hashed here and streamed during compilation
+        let inputs = open_manifest(&manifest_path)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to load manifest: {}", e)))?;
+        let mint_decimals = describe_manifest(&inputs.manifest, &rpc_url)?;
+        println!(
+            "🔑 Manifest hash: {}",
+            hex::encode(inputs.hash(mint_decimals))
+        );
+
+        println!("Compiling campaign from manifest...");
+        let options = StreamingOptions {
+            work_dir,
+            ..Default::default()
+        };
+        let summary = compile_manifest_streaming(
+            AddressFinder::default(),
+            &inputs,
+            mint_decimals,
+            &campaign_db_out,
+            &options,
+            print_progress,
+        )
+        .map_err(|e| CliError::InvalidConfig(format!("Campaign compilation failed: {}", e)))?;
+        let db = CampaignDatabase::open(&campaign_db_out)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to open database: {}", e)))?;
+
+        println!(
+            "📊 {} claimants, {} vaults, {} base units of funding",
+            summary.total_claimants, summary.total_vaults, summary.total_funding
+        );
+        warn_unallocated_budget(&db)?;
+    } else {
+        let loaded = load_manifest(&manifest_path)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to load manifest: {}", e)))?;
+        let mint_decimals = describe_manifest(&loaded.manifest, &rpc_url)?;
+        println!(
+            "🔑 Manifest hash: {}",
+            hex::encode(loaded.hash(mint_decimals))
+        );
+
+        println!("Compiling campaign from manifest...");
+        let db = compile_manifest_db(AddressFinder::default(), &loaded, mint_decimals)
+            .map_err(|e| CliError::InvalidConfig(format!("Campaign compilation failed: {}", e)))?;
+        warn_unallocated_budget(&db)?;
+
+        println!("Saving compiled campaign to database file...");
+        db.save_to_file(&campaign_db_out, true)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to save database: {}", e)))?;
+    }
+
+    println!("Campaign compilation completed successfully!");
+    println!("Database saved to: {}", campaign_db_out.display());
+
+    Ok(())
+}
+
+/// Print a manifest's settings and return the mint decimals to compile with, looking
+/// them up on-chain when the manifest doesn't declare them
+fn describe_manifest(manifest: &CampaignManifest, rpc_url: &str) -> CliResult<u8> {
+    println!("Campaign: {}", manifest.name);
+    println!("Mint: {}", manifest.mint);
+    println!("Admin: {}", manifest.admin);
+    println!("Budget: {}", manifest.budget);
+    println!("Tree version: {:?}", manifest.tree_version);
+    println!("Claimants per vault: {}", manifest.claimants_per_vault);
+    println!("Dust policy: {}", manifest.dust_policy);
+    println!("Leaf order: {}", manifest.leaf_order);
+    if let Some(go_live_slot) = manifest.go_live_slot {
+        println!("Go-live slot: {}", go_live_slot);
+    }
+    for cohort in &manifest.cohorts {
+        println!(
+            "  - {} ({:?}) from {}",
+            cohort.name,
+            cohort.allocation_mode,
+            cohort.claimants_file.display()
+        );
+    }
+
+    match manifest.mint_decimals {
+        Some(mint_decimals) => Ok(mint_decimals),
+        None => {
+            let mint_decimals = fetch_mint_decimals(rpc_url, &manifest.mint)?;
+            println!("Discovered mint decimals: {}", mint_decimals);
+            Ok(mint_decimals)
+        }
+    }
+}
+
+/// Look up a mint's decimals on-chain
+fn fetch_mint_decimals(rpc_url: &str, mint: &Pubkey) -> CliResult<u8> {
+    let rpc_client = Arc::new(RpcClient::new_with_commitment(
+        rpc_url,
+        CommitmentConfig::confirmed(),
+    ));
+
+    let client = PrismProtocolClient::new(rpc_client);
+
+    let mint_info = client
+        .get_mint(mint)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to fetch mint {}: {}", mint, e)))?
+        .ok_or_else(|| CliError::InvalidConfig(format!("Mint {} not found", mint)))?;
+
+    Ok(mint_info.decimals)
+}
+
+/// Warn when part of the budget isn't drawn by any cohort
+fn warn_unallocated_budget(db: &CampaignDatabase) -> CliResult<()> {
+    let report = db
+        .read_dust_report()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read dust report: {}", e)))?;
+    if let Some(report) = report {
+        if report.summary.unallocated_budget > Decimal::ZERO {
+            println!(
+                "⚠️  {} of the {} budget is not allocated to any cohort",
+                report.summary.unallocated_budget, report.summary.budget
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Print the planned layout of every cohort and why it was chosen
+fn print_layout(layout: &CampaignLayout) {
+    println!("🧭 Planned layout:");
+    for cohort in &layout.cohorts {
+        println!(
+            "  - {} ({} claimants): {}",
+            cohort.name, cohort.claimant_count, cohort
+        );
+        match (cohort.tree_version, cohort.v0_limit) {
+            (_, Some(limit)) => println!("      Not a V0 tree: {}", limit),
+            (TreeVersion::V0, None) => {
+                println!("      V0 claims fit the transaction and compute limits")
+            }
+            (TreeVersion::V1, None) => println!("      Tree arity set by --tree-arity"),
+        }
+        println!("      Vaults: {}", cohort.vault_sizing);
+    }
+}
+
+/// Print streaming compilation progress
+fn print_progress(progress: CompileProgress) {
+    match progress {
+        CompileProgress::RowsRead { rows } => println!("  📥 Sorted {} rows", rows),
+        CompileProgress::RunsMerged { runs, rows } => {
+            println!("  🔀 Merged {} runs ({} rows)", runs, rows)
+        }
+        CompileProgress::CohortHashed { cohort, leaves } => {
+            println!("  🌳 Hashed cohort '{}' ({} leaves)", cohort, leaves)
+        }
+        CompileProgress::ProofsWritten {
+            cohort,
+            written,
+            total,
+        } => println!(
+            "  💾 Cohort '{}': {}/{} proofs written",
+            cohort, written, total
+        ),
+    }
+}
@@ -29,11 +29,24 @@ The deploy-campaign command performs these steps in order:
 - ✅ Create all vault token accounts (if not already created)
 - ✅ Fund all vaults with required tokens
 - ✅ Verify vault balances match requirements
+- ✅ Transfer rounding dust to the treasury (`treasury:<pubkey>` dust policy)
 
-### 5. Campaign Activation
+### 5. Address Lookup Table
+- ✅ Create a lookup table holding the campaign, mint, cohorts, vaults and programs
+- ✅ Extend an existing table with any addresses it is missing
+- ✅ Record the table address so claims can use v0 transactions
+
+### 6. Campaign Activation
 - ✅ Enable/activate the campaign after everything is funded
 - ✅ Final verification that campaign is ready for claims
 
+## Offline Deploy (`--sign-only`)
+With `--sign-only` nothing is read from the chain: the deploy is planned up front from the
+database ([`plan_deployment`]) and every transaction is signed against its own durable
+nonce, in the order `broadcast` must submit them. The plan also activates each vault and
+cohort. Activation needs the manifest's `go_live_slot`, since the current slot can't be
+read offline. The lookup table is left out; rerun `deploy-campaign` online to create it.
+
 ## Error Handling
 - Should be idempotent - can be run multiple times safely
 - Should provide clear progress indicators
@@ -46,30 +59,55 @@ The deploy-campaign command performs these steps in order:
 - Record final activation status and timestamp
 */
 
-use crate::error::{CliError, CliResult};
+use crate::commands::admin_transaction::{sign_admin_transactions, SignOnlyNonces};
+use crate::commands::broadcast::write_signed_transactions;
+use crate::error::{program_failure, rpc_failure, CliError, CliResult};
 use hex;
+use prism_protocol::ClaimHashScheme;
 use prism_protocol_client::PrismProtocolClient;
-use prism_protocol_db::CampaignDatabase;
+use prism_protocol_db::{CampaignDatabase, CohortTree, DustSummary};
 use prism_protocol_sdk::{
-    build_create_vault_ix, build_initialize_campaign_ix, build_initialize_cohort_ix,
-    build_set_campaign_active_status_ix,
+    build_activate_campaign_v0_ix, build_create_vault_ix, build_initialize_campaign_ix,
+    build_initialize_cohort_ix, build_initialize_cohort_v1_ix, campaign_lookup_table_addresses,
+    lookup_table::{
+        build_create_lookup_table_ix, build_extend_lookup_table_ixs, missing_lookup_table_addresses,
+    },
+    plan_deployment, AddressFinder, CampaignActivation, CampaignLookupTableAddresses,
+    CampaignStatus, ComputeBudgetConfig, InitializeCohortV1Params, PriorityFee,
 };
 use solana_client::{rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig};
 use solana_sdk::{
     commitment_config::{CommitmentConfig, CommitmentLevel},
+    instruction::Instruction,
     pubkey::Pubkey,
     signature::{read_keypair_file, Signer},
     transaction::Transaction,
 };
-use spl_associated_token_account::get_associated_token_address;
-use std::{path::PathBuf, sync::Arc};
-
-pub fn execute(campaign_db_in: PathBuf, admin_keypair: PathBuf, rpc_url: String) -> CliResult<()> {
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+pub fn execute(
+    campaign_db_in: PathBuf,
+    admin_keypair: PathBuf,
+    rpc_url: String,
+    priority_fee: Option<String>,
+    campaign_db_ipfs_hash: Option<String>,
+) -> CliResult<()> {
     println!("🚀 Deploying campaign on-chain...");
     println!("Database: {}", campaign_db_in.display());
     println!("Admin keypair: {}", admin_keypair.display());
     println!("RPC URL: {}", rpc_url);
 
+    let campaign_db_ipfs_hash = campaign_db_ipfs_hash
+        .map(|hash| parse_ipfs_hash(&hash))
+        .transpose()?;
+    let priority_fee = PriorityFee::from_str(priority_fee.as_deref().unwrap_or("auto"))
+        .map_err(CliError::InvalidConfig)?;
+    let budget = ComputeBudgetConfig::with_priority_fee(priority_fee);
+    println!("Priority fee: {}", priority_fee);
+
     // Step 1: Read admin keypair
     println!("\n🔑 Reading admin keypair...");
     let admin_keypair = read_keypair_file(&admin_keypair)
@@ -179,6 +217,23 @@ pub fn execute(campaign_db_in: PathBuf, admin_keypair: PathBuf, rpc_url: String)
         println!("   (Required: {} + small buffer for fees)", human_amount);
     }
 
+    // Rounding dust still owed to a treasury (`treasury` dust policy)
+    let dust_summary = db
+        .read_dust_report()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read dust report: {}", e)))?
+        .map(|report| report.summary);
+    let treasury_due = match &dust_summary {
+        Some(summary) if summary.treasury_signature.is_none() => summary.treasury_amount,
+        _ => 0,
+    };
+    if treasury_due > 0 {
+        println!(
+            "🏦 Treasury dust: {} base units ({} tokens)",
+            treasury_due,
+            client.format_token_amount(treasury_due, mint_decimals)
+        );
+    }
+
     // Step 6: Pre-flight checks
     println!("\n🔍 Performing pre-flight checks...");
 
@@ -209,7 +264,7 @@ pub fn execute(campaign_db_in: PathBuf, admin_keypair: PathBuf, rpc_url: String)
         &client,
         &admin_keypair,
         &campaign_info,
-        total_tokens_needed,
+        total_tokens_needed + treasury_due,
     )?;
 
     // Step 7: Deploy campaign PDA
@@ -220,6 +275,7 @@ pub fn execute(campaign_db_in: PathBuf, admin_keypair: PathBuf, rpc_url: String)
         &admin_keypair,
         &campaign_info,
         &mut db,
+        &budget,
     )?;
 
     // Step 8: Deploy all cohort PDAs and their vaults
@@ -229,8 +285,14 @@ pub fn execute(campaign_db_in: PathBuf, admin_keypair: PathBuf, rpc_url: String)
 
     for (index, cohort) in cohort_data.iter().enumerate() {
         // Deploy cohort PDA
-        let cohort_signature =
-            deploy_cohort_pda(&rpc_client, &client, &admin_keypair, &campaign_info, cohort)?;
+        let cohort_signature = deploy_cohort_pda(
+            &rpc_client,
+            &client,
+            &admin_keypair,
+            &campaign_info,
+            cohort,
+            &budget,
+        )?;
         if !cohort_signature.is_empty() {
             cohort_signatures.push((cohort.name.clone(), cohort_signature));
         }
@@ -247,6 +309,7 @@ pub fn execute(campaign_db_in: PathBuf, admin_keypair: PathBuf, rpc_url: String)
             &campaign_info,
             cohort,
             &mut db,
+            &budget,
         )?;
 
         if !vault_signatures.is_empty() {
@@ -269,17 +332,53 @@ pub fn execute(campaign_db_in: PathBuf, admin_keypair: PathBuf, rpc_url: String)
         );
     }
 
-    // Step 9: Activate campaign
-    println!("\n🎯 Activating campaign...");
-    activate_campaign(
+    if let Some(summary) = &dust_summary {
+        if summary.treasury.is_some() {
+            println!("\n🏦 Transferring dust to treasury...");
+            transfer_treasury_dust(
+                &rpc_client,
+                &client,
+                &admin_keypair,
+                &campaign_info.mint,
+                summary,
+                &mut db,
+                &budget,
+            )?;
+        }
+    }
+
+    // Step 9: Create the campaign's address lookup table
+    println!("\n🗂️  Creating address lookup table...");
+    let lookup_table = deploy_lookup_table(
         &rpc_client,
         &client,
         &admin_keypair,
         &campaign_info,
+        &cohort_data,
         &mut db,
+        &budget,
     )?;
 
-    // Step 10: Final verification
+    // Step 10: Activate campaign
+    match campaign_db_ipfs_hash {
+        Some(campaign_db_ipfs_hash) => {
+            println!("\n🎯 Activating campaign...");
+            activate_campaign(
+                &rpc_client,
+                &client,
+                &admin_keypair,
+                &campaign_info,
+                &db,
+                campaign_db_ipfs_hash,
+                &budget,
+            )?;
+        }
+        None => {
+            println!("\n⏸️  Skipping activation: rerun with --campaign-db-ipfs-hash once the final database is published");
+        }
+    }
+
+    // Step 11: Final verification
     println!("\n✅ Performing final verification...");
     verify_deployment(&client, &campaign_info, &cohort_data, &vault_requirements)?;
 
@@ -300,6 +399,7 @@ pub fn execute(campaign_db_in: PathBuf, admin_keypair: PathBuf, rpc_url: String)
         println!("  - All cohort PDAs already existed (skipped)");
     }
 
+    println!("  - Address lookup table: {}", lookup_table);
     println!("  - {} vaults created and funded", vault_requirements.len());
     println!("  - {} total tokens distributed", total_tokens_needed);
     println!("  - Campaign activated and ready for claims");
@@ -308,6 +408,112 @@ pub fn execute(campaign_db_in: PathBuf, admin_keypair: PathBuf, rpc_url: String)
     Ok(())
 }
 
+/// Sign every deploy transaction offline against durable nonces, for `broadcast`
+pub fn execute_sign_only(
+    campaign_db_in: PathBuf,
+    admin_keypair: PathBuf,
+    priority_fee: Option<String>,
+    campaign_db_ipfs_hash: Option<String>,
+    sign_only: SignOnlyNonces,
+) -> CliResult<()> {
+    println!("✍️  Signing campaign deployment offline with durable nonces...");
+    println!("Database: {}", campaign_db_in.display());
+
+    let nonces = sign_only.pairs()?;
+    let priority_fee = PriorityFee::from_str(priority_fee.as_deref().unwrap_or("none"))
+        .map_err(CliError::InvalidConfig)?;
+    let budget = ComputeBudgetConfig::with_priority_fee(priority_fee);
+
+    println!("\n🔑 Reading admin keypair...");
+    let admin_keypair = read_keypair_file(&admin_keypair)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read admin keypair: {}", e)))?;
+    println!("✅ Admin public key: {}", admin_keypair.pubkey());
+
+    let db = CampaignDatabase::open(&campaign_db_in)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to open database: {}", e)))?;
+    let campaign_info = db
+        .read_campaign_info()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read campaign info: {}", e)))?;
+    if campaign_info.admin != admin_keypair.pubkey() {
+        return Err(CliError::InvalidConfig(format!(
+            "Campaign was compiled for admin {}, not {}",
+            campaign_info.admin,
+            admin_keypair.pubkey()
+        )));
+    }
+    println!(
+        "✅ Campaign fingerprint: {}",
+        hex::encode(campaign_info.fingerprint)
+    );
+
+    // The current slot can't be read offline, so activation needs the manifest's go-live slot
+    let activation = match campaign_db_ipfs_hash {
+        Some(hash) => {
+            let go_live_slot = db
+                .read_campaign_go_live_slot()
+                .map_err(|e| {
+                    CliError::InvalidConfig(format!("Failed to read go-live slot: {}", e))
+                })?
+                .ok_or_else(|| {
+                    CliError::InvalidConfig(
+                        "Signing the activation offline needs a go_live_slot in the campaign manifest"
+                            .to_string(),
+                    )
+                })?;
+            Some(CampaignActivation {
+                campaign_db_ipfs_hash: parse_ipfs_hash(&hash)?,
+                go_live_slot,
+            })
+        }
+        None => None,
+    };
+
+    let plan = plan_deployment(&AddressFinder::default(), &db, activation)
+        .map_err(|e| CliError::InvalidConfig(e.to_string()))?;
+    println!("\n📋 Deployment takes {} transactions", plan.len());
+
+    let transactions: Vec<(String, Vec<Instruction>)> = plan
+        .into_iter()
+        .map(|tx| (tx.description, tx.instructions))
+        .collect();
+    let mut records = sign_admin_transactions(
+        &transactions,
+        &admin_keypair,
+        &nonces,
+        sign_only.nonce_authority.as_deref(),
+        &budget,
+    )?;
+    for (index, record) in records.iter_mut().enumerate() {
+        record.ordered = true;
+        println!(
+            "  ✅ {}. {} (nonce {})",
+            index + 1,
+            record.description,
+            record.nonce_account
+        );
+    }
+    write_signed_transactions(&sign_only.signed_tx_out, &records)?;
+
+    println!(
+        "\n🎉 Wrote {} signed transaction(s) to {}",
+        records.len(),
+        sign_only.signed_tx_out.display()
+    );
+    println!("   Submit them in order from an online machine with `prism-protocol broadcast`");
+    println!(
+        "   💰 The admin's token account {} must hold the campaign's tokens by then",
+        get_associated_token_address(&campaign_info.admin, &campaign_info.mint)
+    );
+    if activation.is_none() {
+        println!(
+            "   ⏸️  Campaign left inactive: activate it later by rerunning deploy-campaign online with --campaign-db-ipfs-hash"
+        );
+    }
+    println!("   🗂️  No lookup table: rerun deploy-campaign online to create it");
+
+    Ok(())
+}
+
 /// Calculate actual tokens needed for funding (excluding already-funded vaults)
 fn calculate_actual_tokens_needed(
     client: &PrismProtocolClient,
@@ -430,6 +636,7 @@ fn deploy_campaign_pda(
     admin_keypair: &dyn Signer,
     campaign_info: &prism_protocol_db::CampaignInfo,
     db: &mut CampaignDatabase,
+    budget: &ComputeBudgetConfig,
 ) -> CliResult<String> {
     let (campaign_address, _) = client
         .address_finder()
@@ -453,17 +660,9 @@ fn deploy_campaign_pda(
     .map_err(|e| CliError::InvalidConfig(format!("Failed to build campaign instruction: {}", e)))?;
 
     // Create and send transaction with enhanced retry logic
-    println!("  🔄 Getting recent blockhash...");
-    let recent_blockhash = rpc_client
-        .get_latest_blockhash()
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to get blockhash: {}", e)))?;
-
-    let transaction = Transaction::new_signed_with_payer(
-        &[initialize_campaign_ix],
-        Some(&admin_keypair.pubkey()),
-        &[admin_keypair],
-        recent_blockhash,
-    );
+    println!("  🔄 Assembling transaction...");
+    let transaction =
+        assemble_transaction(client, admin_keypair, &[initialize_campaign_ix], budget)?;
 
     println!("  📤 Sending campaign initialization transaction...");
 
@@ -481,7 +680,7 @@ fn deploy_campaign_pda(
             CommitmentConfig::confirmed(),
             config,
         )
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to deploy campaign: {}", e)))?;
+        .map_err(|e| rpc_failure("Failed to deploy campaign", e))?;
 
     println!("  ✅ Campaign PDA deployed! Signature: {}", signature);
 
@@ -499,6 +698,7 @@ fn deploy_cohort_pda(
     admin_keypair: &dyn Signer,
     campaign_info: &prism_protocol_db::CampaignInfo,
     cohort: &prism_protocol_db::CohortInfo,
+    budget: &ComputeBudgetConfig,
 ) -> CliResult<String> {
     let (campaign_address, _) = client
         .address_finder()
@@ -517,29 +717,40 @@ fn deploy_cohort_pda(
         return Ok(String::new());
     }
 
-    // Build initialize cohort instruction
-    let (initialize_cohort_ix, _, _) = build_initialize_cohort_ix(
-        campaign_info.admin,
-        campaign_address,
-        campaign_info.fingerprint,
-        cohort_address,
-        cohort.merkle_root,
-        cohort.amount_per_entitlement,
-        cohort.vault_count as u8,
-    )
+    // Build initialize cohort instruction; V1 trees also commit their arity and hash scheme
+    let initialize_cohort_ix = match cohort.tree {
+        CohortTree::V0 => build_initialize_cohort_ix(
+            campaign_info.admin,
+            campaign_address,
+            campaign_info.fingerprint,
+            cohort_address,
+            cohort.merkle_root,
+            cohort.amount_per_entitlement,
+            cohort.vault_count as u8,
+        )
+        .map(|(ix, _, _)| ix),
+        CohortTree::V1 { arity, hash_scheme } => {
+            let hash_scheme = ClaimHashScheme::from(hash_scheme);
+            println!("    🌳 V1 tree of arity {} ({})", arity, hash_scheme);
+            build_initialize_cohort_v1_ix(
+                client.address_finder(),
+                InitializeCohortV1Params {
+                    admin: campaign_info.admin,
+                    campaign_fingerprint: campaign_info.fingerprint,
+                    merkle_root: cohort.merkle_root,
+                    amount_per_entitlement: cohort.amount_per_entitlement,
+                    expected_vault_count: cohort.vault_count as u8,
+                    merkle_tree_arity: arity,
+                    hash_scheme,
+                },
+            )
+            .map(|(ix, _, _)| ix)
+        }
+    }
     .map_err(|e| CliError::InvalidConfig(format!("Failed to build cohort instruction: {}", e)))?;
 
     // Create and send transaction
-    let recent_blockhash = rpc_client
-        .get_latest_blockhash()
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to get blockhash: {}", e)))?;
-
-    let transaction = Transaction::new_signed_with_payer(
-        &[initialize_cohort_ix],
-        Some(&admin_keypair.pubkey()),
-        &[admin_keypair],
-        recent_blockhash,
-    );
+    let transaction = assemble_transaction(client, admin_keypair, &[initialize_cohort_ix], budget)?;
 
     println!("    📤 Sending cohort initialization transaction...");
 
@@ -557,7 +768,7 @@ fn deploy_cohort_pda(
             CommitmentConfig::confirmed(),
             config,
         )
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to deploy cohort: {}", e)))?;
+        .map_err(|e| rpc_failure("Failed to deploy cohort", e))?;
 
     println!("    ✅ Cohort PDA deployed! Signature: {}", signature);
 
@@ -574,6 +785,7 @@ fn deploy_and_fund_cohort_vaults(
     campaign_info: &prism_protocol_db::CampaignInfo,
     cohort: &prism_protocol_db::CohortInfo,
     db: &mut CampaignDatabase,
+    budget: &ComputeBudgetConfig,
 ) -> CliResult<Vec<String>> {
     let (campaign_address, _) = client
         .address_finder()
@@ -616,6 +828,7 @@ fn deploy_and_fund_cohort_vaults(
             cohort,
             &vault_address,
             vault_index,
+            budget,
         )?;
 
         if !creation_signature.is_empty() {
@@ -634,6 +847,7 @@ fn deploy_and_fund_cohort_vaults(
                 db,
                 &cohort.name,
                 vault_req.vault_index,
+                budget,
             )?;
         }
     }
@@ -649,6 +863,7 @@ fn create_vault_if_needed(
     cohort: &prism_protocol_db::CohortInfo,
     vault_address: &Pubkey,
     vault_index: u8,
+    budget: &ComputeBudgetConfig,
 ) -> CliResult<String> {
     // Check if vault already exists using client
     let vault_exists = rpc_client.get_account(vault_address).is_ok();
@@ -687,16 +902,7 @@ fn create_vault_if_needed(
     })?;
 
     // Create and send transaction
-    let recent_blockhash = rpc_client
-        .get_latest_blockhash()
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to get blockhash: {}", e)))?;
-
-    let transaction = Transaction::new_signed_with_payer(
-        &[create_vault_ix],
-        Some(&admin_keypair.pubkey()),
-        &[admin_keypair],
-        recent_blockhash,
-    );
+    let transaction = assemble_transaction(client, admin_keypair, &[create_vault_ix], budget)?;
 
     let config = RpcSendTransactionConfig {
         skip_preflight: false,
@@ -712,9 +918,7 @@ fn create_vault_if_needed(
             CommitmentConfig::confirmed(),
             config,
         )
-        .map_err(|e| {
-            CliError::InvalidConfig(format!("Failed to create vault {}: {}", vault_index, e))
-        })?;
+        .map_err(|e| rpc_failure(&format!("Failed to create vault {}", vault_index), e))?;
 
     println!(
         "        ✅ Vault {} created! Signature: {}",
@@ -737,6 +941,7 @@ fn fund_vault_if_needed(
     db: &mut CampaignDatabase,
     cohort_name: &str,
     vault_index: usize,
+    budget: &ComputeBudgetConfig,
 ) -> CliResult<()> {
     // Check current vault balance using client
     let current_balance = match client.get_token_account(vault_address) {
@@ -771,20 +976,11 @@ fn fund_vault_if_needed(
     )
     .map_err(|e| CliError::InvalidConfig(format!("Failed to build transfer instruction: {}", e)))?;
 
-    let recent_blockhash = rpc_client
-        .get_latest_blockhash()
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to get blockhash: {}", e)))?;
-
-    let transaction = Transaction::new_signed_with_payer(
-        &[transfer_ix],
-        Some(&admin_keypair.pubkey()),
-        &[admin_keypair],
-        recent_blockhash,
-    );
+    let transaction = assemble_transaction(client, admin_keypair, &[transfer_ix], budget)?;
 
     let signature = rpc_client
         .send_and_confirm_transaction_with_spinner(&transaction)
-        .map_err(|e| CliError::InvalidConfig(format!("Failed to fund vault: {}", e)))?;
+        .map_err(|e| rpc_failure("Failed to fund vault", e))?;
 
     println!(
         "        ✅ Vault {} funded with {} tokens! Signature: {}",
@@ -803,76 +999,308 @@ fn fund_vault_if_needed(
     Ok(())
 }
 
+/// Send the campaign's rounding dust to the treasury's token account, once
+fn transfer_treasury_dust(
+    rpc_client: &RpcClient,
+    client: &PrismProtocolClient,
+    admin_keypair: &dyn Signer,
+    mint: &Pubkey,
+    summary: &DustSummary,
+    db: &mut CampaignDatabase,
+    budget: &ComputeBudgetConfig,
+) -> CliResult<()> {
+    let Some(treasury) = summary.treasury else {
+        return Ok(());
+    };
+
+    if let Some(signature) = &summary.treasury_signature {
+        println!(
+            "  ⚠️  Dust already transferred to treasury ({}), skipping...",
+            signature
+        );
+        return Ok(());
+    }
+    if summary.treasury_amount == 0 {
+        println!("  ⚠️  No dust to transfer");
+        return Ok(());
+    }
+
+    let admin_pubkey = admin_keypair.pubkey();
+    let admin_token_account = get_associated_token_address(&admin_pubkey, mint);
+    let treasury_token_account = get_associated_token_address(&treasury, mint);
+    println!(
+        "  💰 Transferring {} base units to {} (token account {})...",
+        summary.treasury_amount, treasury, treasury_token_account
+    );
+
+    let create_account_ix =
+        create_associated_token_account_idempotent(&admin_pubkey, &treasury, mint, &spl_token::ID);
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &admin_token_account,
+        &treasury_token_account,
+        &admin_pubkey,
+        &[],
+        summary.treasury_amount,
+    )
+    .map_err(|e| CliError::InvalidConfig(format!("Failed to build transfer instruction: {}", e)))?;
+
+    let transaction = assemble_transaction(
+        client,
+        admin_keypair,
+        &[create_account_ix, transfer_ix],
+        budget,
+    )?;
+
+    let signature = rpc_client
+        .send_and_confirm_transaction_with_spinner(&transaction)
+        .map_err(|e| rpc_failure("Failed to transfer dust to treasury", e))?;
+    println!("  ✅ Dust transferred! Signature: {}", signature);
+
+    db.update_treasury_transfer(&signature.to_string())
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to update database: {}", e)))?;
+    println!("  💾 Database updated with treasury transfer");
+
+    Ok(())
+}
+
 fn activate_campaign(
     rpc_client: &RpcClient,
     client: &PrismProtocolClient,
     admin_keypair: &dyn Signer,
     campaign_info: &prism_protocol_db::CampaignInfo,
-    _db: &mut CampaignDatabase,
+    db: &CampaignDatabase,
+    campaign_db_ipfs_hash: [u8; 32],
+    budget: &ComputeBudgetConfig,
 ) -> CliResult<()> {
-    let (campaign_address, _) = client
-        .address_finder()
-        .find_campaign_v0_address(&campaign_info.admin, &campaign_info.fingerprint);
-
     // Check if campaign exists - fix argument order
-    match client.get_campaign_v0(&campaign_info.fingerprint, &campaign_info.admin) {
-        Ok(Some(_)) => {
-            println!("  🎯 Campaign PDA found, activating campaign...");
-
-            // Build activation instruction
-            let (activate_ix, _, _) = build_set_campaign_active_status_ix(
-                campaign_info.admin,
-                campaign_address,
-                campaign_info.fingerprint,
-                true, // Set to active
-            )
-            .map_err(|e| {
-                CliError::InvalidConfig(format!("Failed to build activation instruction: {}", e))
-            })?;
+    let campaign = match client.get_campaign_v0(&campaign_info.fingerprint, &campaign_info.admin) {
+        Ok(Some(campaign)) => campaign,
+        _ => {
+            return Err(CliError::InvalidConfig(
+                "Campaign PDA not found - deployment may have failed".to_string(),
+            ));
+        }
+    };
+    if campaign.status != CampaignStatus::Inactive {
+        println!("  ✅ Campaign already activated");
+        return Ok(());
+    }
 
-            // Send activation transaction
-            let recent_blockhash = rpc_client
-                .get_latest_blockhash()
-                .map_err(|e| CliError::InvalidConfig(format!("Failed to get blockhash: {}", e)))?;
+    // Go live at the slot declared by the manifest, or immediately
+    let current_slot = rpc_client
+        .get_slot()
+        .map_err(|e| rpc_failure("Failed to get current slot", e))?;
+    let go_live_slot = db
+        .read_campaign_go_live_slot()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read go-live slot: {}", e)))?
+        .unwrap_or(current_slot);
+    if go_live_slot < current_slot {
+        return Err(CliError::InvalidConfig(format!(
+            "Go-live slot {} has already passed (current slot {}); recompile with a later go_live_slot",
+            go_live_slot, current_slot
+        )));
+    }
+    println!(
+        "  🎯 Campaign PDA found, activating campaign (go-live slot {})...",
+        go_live_slot
+    );
 
-            let transaction = Transaction::new_signed_with_payer(
-                &[activate_ix],
-                Some(&admin_keypair.pubkey()),
-                &[admin_keypair],
-                recent_blockhash,
-            );
+    let (activate_ix, _, _) = build_activate_campaign_v0_ix(
+        client.address_finder(),
+        campaign_info.admin,
+        campaign_info.fingerprint,
+        campaign_db_ipfs_hash,
+        go_live_slot,
+    )
+    .map_err(|e| {
+        CliError::InvalidConfig(format!("Failed to build activation instruction: {}", e))
+    })?;
+
+    // Send activation transaction
+    let transaction = assemble_transaction(client, admin_keypair, &[activate_ix], budget)?;
+
+    let config = RpcSendTransactionConfig {
+        skip_preflight: false,
+        preflight_commitment: Some(CommitmentLevel::Confirmed),
+        encoding: None,
+        max_retries: Some(5),
+        min_context_slot: None,
+    };
+
+    let signature = rpc_client
+        .send_and_confirm_transaction_with_spinner_and_config(
+            &transaction,
+            CommitmentConfig::confirmed(),
+            config,
+        )
+        .map_err(|e| rpc_failure("Failed to activate campaign", e))?;
 
-            let config = RpcSendTransactionConfig {
-                skip_preflight: false,
-                preflight_commitment: Some(CommitmentLevel::Confirmed),
-                encoding: None,
-                max_retries: Some(5),
-                min_context_slot: None,
-            };
+    println!("  ✅ Campaign activated! Signature: {}", signature);
 
+    Ok(())
+}
+
+/// Parse the hex-encoded 32-byte hash of the published campaign database
+fn parse_ipfs_hash(hash: &str) -> CliResult<[u8; 32]> {
+    let bytes = hex::decode(hash.trim()).map_err(|e| {
+        CliError::InvalidConfig(format!("Invalid campaign database IPFS hash: {}", e))
+    })?;
+    let hash: [u8; 32] = bytes.try_into().map_err(|_| {
+        CliError::InvalidConfig("Campaign database IPFS hash must be 32 bytes".to_string())
+    })?;
+    if hash == [0; 32] {
+        return Err(CliError::InvalidConfig(
+            "Campaign database IPFS hash must not be zero".to_string(),
+        ));
+    }
+    Ok(hash)
+}
+
+/// Create (or top up) the campaign's address lookup table and record it in the database
+fn deploy_lookup_table(
+    rpc_client: &RpcClient,
+    client: &PrismProtocolClient,
+    admin_keypair: &dyn Signer,
+    campaign_info: &prism_protocol_db::CampaignInfo,
+    cohort_data: &[prism_protocol_db::CohortInfo],
+    db: &mut CampaignDatabase,
+    budget: &ComputeBudgetConfig,
+) -> CliResult<Pubkey> {
+    let admin_pubkey = admin_keypair.pubkey();
+    let CampaignLookupTableAddresses {
+        addresses,
+        overflow,
+    } = campaign_lookup_table_addresses(
+        client.address_finder(),
+        &campaign_info.admin,
+        &campaign_info.fingerprint,
+        &campaign_info.mint,
+        cohort_data,
+    );
+    if !overflow.is_empty() {
+        println!(
+            "  ⚠️  {} addresses exceed the lookup table's {} address capacity; claims will carry them inline",
+            overflow.len(),
+            addresses.len()
+        );
+    }
+
+    let existing = db
+        .read_campaign_lookup_table()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read lookup table: {}", e)))?;
+    let existing = match existing {
+        Some(address) => client
+            .get_address_lookup_table(&address)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to fetch lookup table: {}", e)))?,
+        None => None,
+    };
+
+    let config = RpcSendTransactionConfig {
+        skip_preflight: false,
+        preflight_commitment: Some(CommitmentLevel::Confirmed),
+        encoding: None,
+        max_retries: Some(5),
+        min_context_slot: None,
+    };
+
+    let (lookup_table, mut extend_ixs) = match existing {
+        Some(table) => {
+            let missing = missing_lookup_table_addresses(&table, &addresses);
+            println!("  📍 Lookup table: {}", table.key);
+            if missing.is_empty() {
+                println!("  ⚠️  Lookup table already up to date, skipping...");
+                return Ok(table.key);
+            }
+            println!("  🔄 Adding {} missing addresses...", missing.len());
+            let extend_ixs =
+                build_extend_lookup_table_ixs(table.key, admin_pubkey, admin_pubkey, &missing);
+            (table.key, extend_ixs)
+        }
+        None => {
+            let recent_slot = rpc_client
+                .get_slot()
+                .map_err(|e| rpc_failure("Failed to fetch recent slot", e))?;
+            let (create_ix, lookup_table) =
+                build_create_lookup_table_ix(admin_pubkey, admin_pubkey, recent_slot);
+            println!("  📍 Lookup table: {}", lookup_table);
+
+            // Create the table together with its first batch of addresses
+            let mut extend_ixs =
+                build_extend_lookup_table_ixs(lookup_table, admin_pubkey, admin_pubkey, &addresses);
+            let first_extend = extend_ixs.remove(0);
+            let transaction =
+                assemble_transaction(client, admin_keypair, &[create_ix, first_extend], budget)?;
+
+            println!("  📤 Sending lookup table creation transaction...");
             let signature = rpc_client
                 .send_and_confirm_transaction_with_spinner_and_config(
                     &transaction,
                     CommitmentConfig::confirmed(),
                     config,
                 )
+                .map_err(|e| rpc_failure("Failed to create lookup table", e))?;
+            println!("  ✅ Lookup table created! Signature: {}", signature);
+
+            db.update_campaign_lookup_table(&lookup_table, &signature.to_string())
                 .map_err(|e| {
-                    CliError::InvalidConfig(format!("Failed to activate campaign: {}", e))
+                    CliError::InvalidConfig(format!("Failed to update database: {}", e))
                 })?;
+            println!("  💾 Database updated with lookup table address");
 
-            println!("  ✅ Campaign activated! Signature: {}", signature);
-
-            // Skip database update since the method doesn't exist yet
-            println!("  ⚠️  Database campaign activation tracking not yet implemented");
-        }
-        _ => {
-            return Err(CliError::InvalidConfig(
-                "Campaign PDA not found - deployment may have failed".to_string(),
-            ));
+            (lookup_table, extend_ixs)
         }
+    };
+
+    let total = extend_ixs.len();
+    for (index, extend_ix) in extend_ixs.drain(..).enumerate() {
+        let transaction = assemble_transaction(client, admin_keypair, &[extend_ix], budget)?;
+        let signature = rpc_client
+            .send_and_confirm_transaction_with_spinner_and_config(
+                &transaction,
+                CommitmentConfig::confirmed(),
+                config,
+            )
+            .map_err(|e| rpc_failure("Failed to extend lookup table", e))?;
+        println!(
+            "  ✅ Extended lookup table ({}/{}): {}",
+            index + 1,
+            total,
+            signature
+        );
     }
 
-    Ok(())
+    println!(
+        "  ✅ Lookup table holds {} addresses (usable from the next slot)",
+        addresses.len()
+    );
+    Ok(lookup_table)
+}
+
+/// Build a signed admin transaction with a simulated CU limit and the configured priority fee
+fn assemble_transaction(
+    client: &PrismProtocolClient,
+    admin_keypair: &dyn Signer,
+    instructions: &[Instruction],
+    budget: &ComputeBudgetConfig,
+) -> CliResult<Transaction> {
+    let assembled = client
+        .assemble_transaction(
+            instructions,
+            &admin_keypair.pubkey(),
+            &[admin_keypair],
+            budget,
+        )
+        .map_err(|e| program_failure("Failed to assemble transaction", e))?;
+
+    if let Some(limit) = assembled.compute_unit_limit {
+        println!("    ⚙️  Compute unit limit: {}", limit);
+    }
+    if let Some(price) = assembled.compute_unit_price {
+        println!("    ⛽ Priority fee: {} micro-lamports/CU", price);
+    }
+
+    Ok(assembled.transaction)
 }
 
 fn verify_deployment(
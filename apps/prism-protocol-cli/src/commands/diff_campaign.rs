@@ -0,0 +1,172 @@
+use crate::error::{CliError, CliResult};
+use prism_protocol_db::CampaignDatabase;
+use prism_protocol_sdk::{diff_campaigns, CampaignDiff, CohortChange, CohortDiff};
+use std::path::PathBuf;
+
+/// Claimants listed per section before the table is truncated
+const MAX_LISTED_CLAIMANTS: usize = 20;
+
+pub fn execute(old_db_path: PathBuf, new_db_path: PathBuf, json: bool) -> CliResult<()> {
+    let old_db = CampaignDatabase::open(&old_db_path)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to open old database: {}", e)))?;
+    let new_db = CampaignDatabase::open(&new_db_path)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to open new database: {}", e)))?;
+
+    let diff = diff_campaigns(&old_db, &new_db)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to diff campaigns: {}", e)))?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&diff)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to serialize diff: {}", e)))?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    println!("🔍 Comparing campaign databases");
+    println!("   Old: {}", old_db_path.display());
+    println!("   New: {}", new_db_path.display());
+
+    print_campaign_diff(&diff);
+
+    Ok(())
+}
+
+fn print_campaign_diff(diff: &CampaignDiff) {
+    if diff.is_empty() {
+        println!("\n✅ Campaigns are identical");
+        return;
+    }
+
+    println!("\n🏛️  Campaign:");
+    if diff.fingerprint_changed() {
+        println!("   ⚠️  Fingerprint changed (campaign deploys to a new address)");
+        println!("      Old: {}", hex::encode(diff.old_fingerprint));
+        println!("      New: {}", hex::encode(diff.new_fingerprint));
+    } else {
+        println!("   Fingerprint: {}", hex::encode(diff.old_fingerprint));
+    }
+    if diff.old_manifest_hash != diff.new_manifest_hash {
+        println!(
+            "   Manifest hash: {} → {}",
+            optional_hex(&diff.old_manifest_hash),
+            optional_hex(&diff.new_manifest_hash)
+        );
+    }
+    if diff.old_budget != diff.new_budget {
+        println!("   Budget: {} → {}", diff.old_budget, diff.new_budget);
+    }
+    println!(
+        "   Funding: {} → {} ({})",
+        diff.old_total_funding,
+        diff.new_total_funding,
+        signed(diff.funding_delta)
+    );
+
+    println!(
+        "\n📂 Cohorts:\n   {:<20} {:<10} {:>8} {:>8} {:>8} {:>10} {:>16}",
+        "Cohort", "Change", "Added", "Removed", "Changed", "Reassigned", "Funding delta"
+    );
+    for cohort in &diff.cohorts {
+        println!(
+            "   {:<20} {:<10} {:>8} {:>8} {:>8} {:>10} {:>16}",
+            cohort.name,
+            change_label(cohort.change),
+            cohort.added.len(),
+            cohort.removed.len(),
+            cohort.changed.len(),
+            cohort.reassigned,
+            signed(cohort.funding_delta)
+        );
+    }
+
+    for cohort in diff
+        .cohorts
+        .iter()
+        .filter(|c| c.change != CohortChange::Unchanged)
+    {
+        print_cohort_diff(cohort);
+    }
+}
+
+fn print_cohort_diff(cohort: &CohortDiff) {
+    println!(
+        "\n   Cohort: {} ({})",
+        cohort.name,
+        change_label(cohort.change)
+    );
+
+    if cohort.old_amount_per_entitlement != cohort.new_amount_per_entitlement {
+        println!(
+            "      Amount per entitlement: {} → {}",
+            optional_amount(cohort.old_amount_per_entitlement),
+            optional_amount(cohort.new_amount_per_entitlement)
+        );
+    }
+
+    for entry in cohort.added.iter().take(MAX_LISTED_CLAIMANTS) {
+        println!(
+            "      ➕ {} ({} entitlements, vault {})",
+            entry.claimant, entry.entitlements, entry.vault_index
+        );
+    }
+    print_truncated(cohort.added.len());
+
+    for entry in cohort.removed.iter().take(MAX_LISTED_CLAIMANTS) {
+        println!(
+            "      ➖ {} ({} entitlements, vault {})",
+            entry.claimant, entry.entitlements, entry.vault_index
+        );
+    }
+    print_truncated(cohort.removed.len());
+
+    for change in cohort.changed.iter().take(MAX_LISTED_CLAIMANTS) {
+        println!(
+            "      ✏️  {}: {} → {} entitlements",
+            change.claimant, change.old_entitlements, change.new_entitlements
+        );
+    }
+    print_truncated(cohort.changed.len());
+
+    if cohort.reassigned > 0 {
+        println!("      🔀 {} claimants moved vault", cohort.reassigned);
+    }
+
+    for vault in &cohort.vaults {
+        println!(
+            "      💰 Vault {}: {} → {} tokens",
+            vault.vault_index,
+            optional_amount(vault.old_required),
+            optional_amount(vault.new_required)
+        );
+    }
+}
+
+fn print_truncated(len: usize) {
+    if len > MAX_LISTED_CLAIMANTS {
+        println!(
+            "      ... and {} more (use --json for the full list)",
+            len - MAX_LISTED_CLAIMANTS
+        );
+    }
+}
+
+fn change_label(change: CohortChange) -> &'static str {
+    match change {
+        CohortChange::Added => "added",
+        CohortChange::Removed => "removed",
+        CohortChange::Modified => "modified",
+        CohortChange::Unchanged => "unchanged",
+    }
+}
+
+fn signed(delta: i128) -> String {
+    format!("{:+}", delta)
+}
+
+fn optional_amount(amount: Option<u64>) -> String {
+    amount.map_or_else(|| "-".to_string(), |amount| amount.to_string())
+}
+
+fn optional_hex(hash: &Option<[u8; 32]>) -> String {
+    hash.map_or_else(|| "-".to_string(), hex::encode)
+}
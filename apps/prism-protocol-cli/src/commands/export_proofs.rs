@@ -0,0 +1,41 @@
+use crate::error::{CliError, CliResult};
+use prism_protocol_db::CampaignDatabase;
+use prism_protocol_sdk::{export_proofs, AddressFinder, ProofExportOptions};
+use std::path::PathBuf;
+
+pub fn execute(
+    campaign_db_in: PathBuf,
+    out_dir: PathBuf,
+    shard_prefix_len: usize,
+    overwrite: bool,
+) -> CliResult<()> {
+    println!("📦 Exporting claim proofs for static hosting");
+    println!("📊 Database: {}", campaign_db_in.display());
+    println!("📁 Output directory: {}", out_dir.display());
+
+    let db = CampaignDatabase::open(&campaign_db_in)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to open database: {}", e)))?;
+
+    let summary = export_proofs(
+        &AddressFinder::default(),
+        &db,
+        &out_dir,
+        &ProofExportOptions {
+            shard_prefix_len,
+            overwrite,
+        },
+    )
+    .map_err(|e| CliError::InvalidConfig(format!("Failed to export proofs: {}", e)))?;
+
+    println!(
+        "✅ Exported {} claimant bundles in {} shards",
+        summary.claimants, summary.shards
+    );
+    println!(
+        "🔑 Manifest SHA-256: {}",
+        hex::encode(summary.manifest_sha256)
+    );
+    println!("💡 Publish the manifest hash so claim sites can verify the export");
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::error::{CliError, CliResult};
+use hex;
+use prism_protocol_client::{CampaignFilter, PrismProtocolClient};
+use prism_protocol_sdk::{CampaignStatus, CampaignV0};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::sync::Arc;
+
+pub fn execute(admin: Option<Pubkey>, mint: Option<Pubkey>, rpc_url: String) -> CliResult<()> {
+    let filter = match (admin, mint) {
+        (Some(admin), None) => CampaignFilter::Admin(admin),
+        (None, Some(mint)) => CampaignFilter::Mint(mint),
+        _ => {
+            return Err(CliError::InvalidConfig(
+                "Specify exactly one of --admin or --mint".to_string(),
+            ))
+        }
+    };
+
+    match filter {
+        CampaignFilter::Admin(admin) => println!("🔍 Listing campaigns for admin: {}", admin),
+        CampaignFilter::Mint(mint) => println!("🔍 Listing campaigns for mint: {}", mint),
+    }
+
+    let rpc_client = RpcClient::new_with_commitment(&rpc_url, CommitmentConfig::confirmed());
+    let client = PrismProtocolClient::new(Arc::new(rpc_client));
+
+    let campaigns = client
+        .find_campaigns_v0(filter)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to list campaigns: {}", e)))?;
+
+    if campaigns.is_empty() {
+        println!("\n❌ No campaigns found on-chain");
+        return Ok(());
+    }
+
+    println!("\n✅ Found {} campaign(s):", campaigns.len());
+    for (i, (address, campaign)) in campaigns.iter().enumerate() {
+        print_campaign(i + 1, address, campaign);
+    }
+
+    Ok(())
+}
+
+fn print_campaign(number: usize, address: &Pubkey, campaign: &CampaignV0) {
+    let status = match campaign.status {
+        CampaignStatus::Inactive => "Inactive",
+        CampaignStatus::Active => "Active",
+        CampaignStatus::Paused => "Paused",
+        CampaignStatus::PermanentlyHalted => "Permanently halted",
+    };
+
+    println!("\n   {}. Campaign: {}", number, address);
+    println!("      Fingerprint: {}", hex::encode(campaign.fingerprint));
+    println!("      Admin: {}", campaign.admin);
+    println!("      Mint: {}", campaign.mint);
+    println!("      Status: {}", status);
+    println!(
+        "      Cohorts: {}/{} initialized, {} activated",
+        campaign.initialized_cohort_count,
+        campaign.expected_cohort_count,
+        campaign.activated_cohort_count
+    );
+    println!("      Go-live slot: {}", campaign.go_live_slot);
+    if campaign.unstoppable {
+        println!("      🔒 Unstoppable");
+    }
+}
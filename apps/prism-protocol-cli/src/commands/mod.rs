@@ -1,10 +1,17 @@
+pub mod admin_transaction;
+pub mod broadcast;
 pub mod campaign_status;
 pub mod check_eligibility;
 pub mod claim_tokens;
 pub mod compile_campaign;
 pub mod deploy_campaign;
+pub mod diff_campaign;
+pub mod export_proofs;
 pub mod generate_fixtures;
+pub mod list_campaigns;
 pub mod pause_campaign;
+pub mod prove_ineligible;
 pub mod query_claims;
 pub mod reclaim_tokens;
 pub mod resume_campaign;
+pub mod sync_claims;
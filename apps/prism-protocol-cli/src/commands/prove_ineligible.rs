@@ -0,0 +1,204 @@
+use crate::error::{CliError, CliResult};
+use prism_protocol_db::{CampaignDatabase, CohortInfo, CohortTree, HashScheme};
+use prism_protocol_merkle::{
+    ClaimHashScheme, ClaimLeaf, ClaimTreeV0, ClaimTreeV1, ClaimTreeV1Options, ClaimantIndexTree,
+    LeafOrder, NeighbourLeaf, NonMembershipError,
+};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::path::PathBuf;
+
+/// Non-membership proof for one cohort, as written by `--proof-out`
+#[derive(Serialize)]
+struct CohortExclusion {
+    cohort: String,
+    merkle_root: String,
+    claimant_index_root: String,
+    hash_scheme: String,
+    leaf_count: u64,
+    lower: Option<NeighbourJson>,
+    upper: Option<NeighbourJson>,
+}
+
+#[derive(Serialize)]
+struct NeighbourJson {
+    index: u64,
+    claimant: String,
+    assigned_vault_index: u8,
+    entitlements: u64,
+    path: Vec<String>,
+}
+
+impl From<&NeighbourLeaf> for NeighbourJson {
+    fn from(neighbour: &NeighbourLeaf) -> Self {
+        Self {
+            index: neighbour.index,
+            claimant: neighbour.leaf.claimant.to_string(),
+            assigned_vault_index: neighbour.leaf.assigned_vault_index,
+            entitlements: neighbour.leaf.entitlements,
+            path: neighbour.path.iter().map(hex::encode).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExclusionProofFile {
+    claimant: String,
+    campaign_fingerprint: String,
+    cohorts: Vec<CohortExclusion>,
+}
+
+pub fn execute(
+    campaign_db_in: PathBuf,
+    claimant: Pubkey,
+    cohort: Option<String>,
+    proof_out: Option<PathBuf>,
+) -> CliResult<()> {
+    println!("🔍 Proving {} is not eligible", claimant);
+
+    let db = CampaignDatabase::open(&campaign_db_in)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to open database: {}", e)))?;
+    let campaign_info = db
+        .read_campaign_info()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read campaign info: {}", e)))?;
+
+    let mut cohorts = db
+        .read_cohorts()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read cohorts: {}", e)))?;
+    if let Some(name) = &cohort {
+        cohorts.retain(|info| &info.name == name);
+        if cohorts.is_empty() {
+            return Err(CliError::InvalidConfig(format!(
+                "Cohort '{}' not found in campaign",
+                name
+            )));
+        }
+    }
+
+    let mut exclusions = Vec::with_capacity(cohorts.len());
+    for info in &cohorts {
+        let index_root = db
+            .read_cohort_claimant_index_root(&info.name)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to read cohort: {}", e)))?
+            .ok_or_else(|| {
+                CliError::InvalidConfig(format!(
+                    "Cohort '{}' was not compiled with --leaf-order claimant, so exclusion can't be proved",
+                    info.name
+                ))
+            })?;
+        let index = rebuild_claimant_index(&db, info, &index_root)?;
+        let hash_scheme = index.hash_scheme();
+
+        let proof = index.non_membership_proof(&claimant).map_err(|e| match e {
+            NonMembershipError::ClaimantInTree(_) => CliError::InvalidConfig(format!(
+                "{} is eligible in cohort '{}'",
+                claimant, info.name
+            )),
+            e => CliError::InvalidConfig(format!("Failed to build non-membership proof: {}", e)),
+        })?;
+        if !proof.verify_with_scheme(&index_root, hash_scheme) {
+            return Err(CliError::InvalidConfig(format!(
+                "Non-membership proof for cohort '{}' failed verification",
+                info.name
+            )));
+        }
+
+        println!(
+            "✅ Not in cohort '{}' ({} leaves)",
+            info.name, proof.leaf_count
+        );
+        for (side, neighbour) in [("below", &proof.lower), ("above", &proof.upper)] {
+            match neighbour {
+                Some(neighbour) => println!(
+                    "   Leaf {} {}: {}",
+                    neighbour.index, side, neighbour.leaf.claimant
+                ),
+                None => println!("   No leaf {}", side),
+            }
+        }
+
+        exclusions.push(CohortExclusion {
+            cohort: info.name.clone(),
+            merkle_root: hex::encode(info.merkle_root),
+            claimant_index_root: hex::encode(index_root),
+            hash_scheme: hash_scheme.to_string(),
+            leaf_count: proof.leaf_count,
+            lower: proof.lower.as_ref().map(NeighbourJson::from),
+            upper: proof.upper.as_ref().map(NeighbourJson::from),
+        });
+    }
+
+    if let Some(path) = proof_out {
+        let file = ExclusionProofFile {
+            claimant: claimant.to_string(),
+            campaign_fingerprint: hex::encode(campaign_info.fingerprint),
+            cohorts: exclusions,
+        };
+        std::fs::write(&path, serde_json::to_string_pretty(&file)?)?;
+        println!("📁 Proof written to {}", path.display());
+    }
+
+    println!(
+        "🎉 {} is not eligible in {} cohort(s)",
+        claimant,
+        cohorts.len()
+    );
+
+    Ok(())
+}
+
+/// Rebuild a cohort's claimant index from its database records, checking it against
+/// the recorded index root and the same leaves against the cohort's merkle root
+fn rebuild_claimant_index(
+    db: &CampaignDatabase,
+    info: &CohortInfo,
+    index_root: &[u8; 32],
+) -> CliResult<ClaimantIndexTree> {
+    let mut leaves: Vec<ClaimLeaf> = db
+        .read_cohort_claimant_records(&info.name)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read claimants: {}", e)))?
+        .into_iter()
+        .map(|record| ClaimLeaf {
+            claimant: record.claimant,
+            assigned_vault_index: record.assigned_vault_index,
+            entitlements: record.entitlements,
+        })
+        .collect();
+    LeafOrder::Claimant.apply(&mut leaves);
+
+    let merkle_root = match info.tree {
+        CohortTree::V0 => ClaimTreeV0::from_leaves(leaves.clone()).map(|tree| tree.root()),
+        CohortTree::V1 { arity, hash_scheme } => ClaimTreeV1::from_leaves_with_options(
+            leaves.clone(),
+            &ClaimTreeV1Options {
+                arity: arity as usize,
+                leaf_order: LeafOrder::Claimant,
+                hash_scheme: hash_scheme.into(),
+            },
+        )
+        .map(|tree| tree.root()),
+    }
+    .map_err(|e| CliError::InvalidConfig(format!("Failed to build merkle tree: {}", e)))?;
+    if merkle_root != Some(info.merkle_root) {
+        return Err(CliError::InvalidConfig(format!(
+            "Cohort '{}' claimants don't match its merkle root",
+            info.name
+        )));
+    }
+
+    let hash_scheme: ClaimHashScheme = match info.tree {
+        CohortTree::V0 => HashScheme::Sha256,
+        CohortTree::V1 { hash_scheme, .. } => hash_scheme,
+    }
+    .into();
+    let index = ClaimantIndexTree::from_leaves(leaves, hash_scheme)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to index claimants: {}", e)))?;
+    if index.root() != *index_root {
+        return Err(CliError::InvalidConfig(format!(
+            "Cohort '{}' claimants don't match its claimant index root",
+            info.name
+        )));
+    }
+
+    Ok(index)
+}
@@ -1,8 +1,12 @@
 use crate::error::{CliError, CliResult};
 use chrono;
 use hex;
-use prism_protocol_client::PrismProtocolClient;
+use prism_protocol_client::{
+    core::{decode_account, decode_fetched_account},
+    ClaimReceiptFilter, PrismClientCore, PrismProtocolClient,
+};
 use prism_protocol_db::CampaignDatabase;
+use prism_protocol_sdk::ClaimReceiptV0;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
@@ -10,7 +14,7 @@ use solana_sdk::{
     signature::{read_keypair_file, Signer},
 };
 use std::str::FromStr;
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 
 #[derive(Debug)]
 struct ClaimInfo {
@@ -50,9 +54,8 @@ pub fn execute(campaign_db_path: PathBuf, claimant: String, rpc_url: String) ->
     println!("   Admin: {}", campaign_info.admin);
 
     // Calculate campaign address
-    let (campaign_address, _) = client
-        .address_finder()
-        .find_campaign_v0_address(&campaign_info.admin, &campaign_info.fingerprint);
+    let campaign_address =
+        client.campaign_v0_address(&campaign_info.fingerprint, &campaign_info.admin);
     println!("🏛️  Campaign address: {}", campaign_address);
 
     // Query blockchain for actual claim receipts
@@ -65,6 +68,71 @@ pub fn execute(campaign_db_path: PathBuf, claimant: String, rpc_url: String) ->
     Ok(())
 }
 
+/// Find a claimant's receipts across every campaign on-chain (no database required)
+pub fn execute_all_campaigns(claimant: String, rpc_url: String) -> CliResult<()> {
+    println!("⛓️  Querying blockchain for claims across all campaigns...");
+
+    let rpc_client = Arc::new(RpcClient::new_with_commitment(
+        &rpc_url,
+        CommitmentConfig::confirmed(),
+    ));
+    let client = PrismProtocolClient::new(rpc_client);
+
+    let claimant_pubkey = parse_claimant_input(&claimant)?;
+    println!("🔍 Querying claims for: {}", claimant_pubkey);
+
+    // One getProgramAccounts call finds every receipt of this claimant
+    let receipts = client
+        .find_claim_receipts_v0(ClaimReceiptFilter::Claimant(claimant_pubkey))
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to query claim receipts: {}", e)))?;
+
+    if receipts.is_empty() {
+        println!("\n❌ No claims found on-chain");
+        println!("   This claimant hasn't claimed from any campaign.");
+        return Ok(());
+    }
+
+    // Resolve each receipt's cohort to its campaign in one batched fetch
+    let cohort_addresses: Vec<Pubkey> = receipts.iter().map(|(_, r)| r.cohort).collect();
+    let cohorts = client
+        .get_cohorts_v0(&cohort_addresses)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to query cohorts: {}", e)))?;
+
+    let mut by_campaign: BTreeMap<Option<Pubkey>, Vec<(Pubkey, ClaimReceiptV0)>> = BTreeMap::new();
+    for ((address, receipt), cohort) in receipts.into_iter().zip(cohorts) {
+        by_campaign
+            .entry(cohort.map(|cohort| cohort.campaign))
+            .or_default()
+            .push((address, receipt));
+    }
+
+    let total: usize = by_campaign.values().map(|claims| claims.len()).sum();
+    println!(
+        "\n✅ Found {} claim(s) across {} campaign(s):",
+        total,
+        by_campaign.len()
+    );
+
+    for (campaign, claims) in &by_campaign {
+        match campaign {
+            Some(campaign) => println!("\n🏛️  Campaign: {}", campaign),
+            None => println!("\n🏛️  Campaign: unknown (cohort account not found)"),
+        }
+
+        for (address, receipt) in claims {
+            println!("   Claim Receipt: {}", address);
+            println!("      Cohort Address: {}", receipt.cohort);
+            println!("      Assigned Vault: {}", receipt.assigned_vault);
+            println!(
+                "      Claimed At: {}",
+                format_timestamp(receipt.claimed_at_timestamp)
+            );
+        }
+    }
+
+    Ok(())
+}
+
 fn parse_claimant_input(claimant: &str) -> CliResult<Pubkey> {
     if let Ok(pubkey) = Pubkey::from_str(claimant) {
         println!("🔍 Using provided pubkey: {}", pubkey);
@@ -96,24 +164,46 @@ fn query_claim_receipts(
 
     println!("✅ Found {} cohort(s) in database", cohorts.len());
 
+    // Derive every receipt address up front and fetch them in one batched call
+    let receipt_addresses: Vec<(Pubkey, Pubkey)> = cohorts
+        .iter()
+        .map(|cohort| {
+            let cohort_address = client.cohort_v0_address(campaign_address, &cohort.merkle_root);
+            let receipt_address = client.claim_receipt_v0_address(&cohort_address, claimant);
+            (cohort_address, receipt_address)
+        })
+        .collect();
+
+    let addresses: Vec<Pubkey> = receipt_addresses
+        .iter()
+        .map(|(_, receipt_address)| *receipt_address)
+        .collect();
+    let accounts = client
+        .get_multiple_accounts(&addresses)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to query claim receipts: {}", e)))?;
+
     let mut claims = Vec::new();
 
-    for cohort in cohorts {
+    for ((cohort, (cohort_address, claim_receipt_address)), account) in
+        cohorts.iter().zip(receipt_addresses).zip(accounts)
+    {
         println!("🔍 Checking cohort: {}", cohort.name);
 
-        // Calculate cohort address
-        let (cohort_address, _) = client
-            .address_finder()
-            .find_cohort_v0_address(campaign_address, &cohort.merkle_root);
-
-        // Check if claimant has a claim receipt for this cohort
-        match client.get_claim_receipt_v0(&cohort_address, claimant) {
-            Ok(Some(claim_receipt)) => {
-                // Calculate claim receipt address for display
-                let (claim_receipt_address, _) = client
-                    .address_finder()
-                    .find_claim_receipt_v0_address(&cohort_address, claimant);
+        // Only a missing receipt means unclaimed; anything else at the address is an error
+        let receipt = decode_fetched_account::<ClaimReceiptV0>(
+            &claim_receipt_address,
+            account.as_ref(),
+            decode_account,
+        )
+        .map_err(|e| {
+            CliError::InvalidConfig(format!(
+                "Failed to read claim receipt for cohort {}: {}",
+                cohort.name, e
+            ))
+        })?;
 
+        match receipt {
+            Some(claim_receipt) => {
                 claims.push(ClaimInfo {
                     claim_receipt_address,
                     cohort_address,
@@ -125,13 +215,9 @@ fn query_claim_receipts(
 
                 println!("   ✅ Found claim receipt");
             }
-            Ok(None) => {
+            None => {
                 println!("   ❌ No claim receipt found");
             }
-            Err(e) => {
-                println!("   ⚠️  Error checking claim receipt: {}", e);
-                // Continue with other cohorts
-            }
         }
     }
 
@@ -155,11 +241,10 @@ fn display_claims_results(claims: &[ClaimInfo], campaign_fingerprint: &[u8; 32])
         println!("   Claimant: {}", claim.claimant);
         println!("   Assigned Vault: {}", claim.assigned_vault);
 
-        // Format timestamp
-        let datetime = chrono::DateTime::from_timestamp(claim.claimed_at_timestamp, 0)
-            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
-            .unwrap_or_else(|| "Invalid timestamp".to_string());
-        println!("   Claimed At: {}", datetime);
+        println!(
+            "   Claimed At: {}",
+            format_timestamp(claim.claimed_at_timestamp)
+        );
         println!();
     }
 
@@ -171,3 +256,9 @@ fn display_claims_results(claims: &[ClaimInfo], campaign_fingerprint: &[u8; 32])
     println!("\n💡 This shows what has actually been claimed on-chain.");
     println!("   Compare with 'check-eligibility' to see what's still claimable.");
 }
+
+fn format_timestamp(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| "Invalid timestamp".to_string())
+}
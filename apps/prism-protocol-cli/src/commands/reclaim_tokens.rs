@@ -1,16 +1,60 @@
-use crate::error::CliResult;
+use crate::commands::admin_transaction::{
+    parse_fingerprint, parse_merkle_root, send_or_sign, SignOnly,
+};
+use crate::error::{CliError, CliResult};
+use prism_protocol_sdk::{build_reclaim_tokens_v0_ix, AddressFinder};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
 use std::path::PathBuf;
 
+/// Reclaim the tokens left in one vault of a permanently halted campaign
+///
+/// Tokens go to the admin's associated token account for `mint`, which must exist.
+#[allow(clippy::too_many_arguments)]
 pub fn execute(
     campaign: String,
     cohort: String,
+    vault_index: u8,
+    mint: Pubkey,
     keypair: PathBuf,
     rpc_url: String,
+    priority_fee: Option<String>,
+    sign_only: Option<SignOnly>,
 ) -> CliResult<()> {
-    println!("🚧 reclaim-tokens command not yet implemented");
-    println!("Campaign: {}", campaign);
-    println!("Cohort: {}", cohort);
-    println!("Keypair: {}", keypair.display());
-    println!("RPC URL: {}", rpc_url);
-    Ok(())
+    println!(
+        "💸 Reclaim tokens from vault {} of cohort {}",
+        vault_index, cohort
+    );
+    let fingerprint = parse_fingerprint(&campaign)?;
+    let merkle_root = parse_merkle_root(&cohort)?;
+
+    let admin_keypair = read_keypair_file(&keypair)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read keypair: {}", e)))?;
+    let admin = admin_keypair.pubkey();
+    println!("✅ Admin: {}", admin);
+
+    let destination = get_associated_token_address(&admin, &mint);
+    println!("✅ Destination token account: {}", destination);
+
+    let (reclaim_ix, _, _) = build_reclaim_tokens_v0_ix(
+        &AddressFinder::default(),
+        admin,
+        destination,
+        fingerprint,
+        merkle_root,
+        vault_index,
+    )
+    .map_err(|e| CliError::InvalidConfig(format!("Failed to build reclaim instruction: {}", e)))?;
+
+    send_or_sign(
+        &format!("Reclaimed vault {} of cohort {}", vault_index, cohort),
+        reclaim_ix,
+        &admin_keypair,
+        &rpc_url,
+        priority_fee,
+        sign_only,
+    )
 }
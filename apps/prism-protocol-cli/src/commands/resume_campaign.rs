@@ -1,10 +1,36 @@
-use crate::error::CliResult;
+use crate::commands::admin_transaction::{parse_fingerprint, send_or_sign, SignOnly};
+use crate::error::{CliError, CliResult};
+use prism_protocol_sdk::{build_resume_campaign_v0_ix, AddressFinder};
+use solana_sdk::signature::{read_keypair_file, Signer};
 use std::path::PathBuf;
 
-pub fn execute(campaign: String, keypair: PathBuf, rpc_url: String) -> CliResult<()> {
-    println!("🚧 resume-campaign command not yet implemented");
-    println!("Campaign: {}", campaign);
-    println!("Keypair: {}", keypair.display());
-    println!("RPC URL: {}", rpc_url);
-    Ok(())
+pub fn execute(
+    campaign: String,
+    keypair: PathBuf,
+    rpc_url: String,
+    priority_fee: Option<String>,
+    sign_only: Option<SignOnly>,
+) -> CliResult<()> {
+    println!("▶️  Resume campaign {}", campaign);
+    let fingerprint = parse_fingerprint(&campaign)?;
+
+    let admin_keypair = read_keypair_file(&keypair)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read keypair: {}", e)))?;
+    println!("✅ Admin: {}", admin_keypair.pubkey());
+
+    let (resume_ix, _, _) = build_resume_campaign_v0_ix(
+        &AddressFinder::default(),
+        admin_keypair.pubkey(),
+        fingerprint,
+    )
+    .map_err(|e| CliError::InvalidConfig(format!("Failed to build resume instruction: {}", e)))?;
+
+    send_or_sign(
+        "Resumed campaign",
+        resume_ix,
+        &admin_keypair,
+        &rpc_url,
+        priority_fee,
+        sign_only,
+    )
 }
@@ -0,0 +1,148 @@
+use crate::error::{CliError, CliResult};
+use prism_protocol_client::{ClaimReceiptFilter, PrismClientCore, PrismProtocolClient};
+use prism_protocol_db::CampaignDatabase;
+use prism_protocol_sdk::claim_sync::{
+    apply_claims, clear_missing_receipts, ClaimDrift, CohortClaimSync,
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::{path::PathBuf, sync::Arc};
+
+/// Record on-chain claims in the campaign database and report drift between the two.
+///
+/// Each batch commits on its own, so an interrupted sync picks up where it stopped when
+/// run again; a run against an up-to-date database changes nothing.
+pub fn execute(
+    campaign_db_path: PathBuf,
+    rpc_url: String,
+    signatures: bool,
+    clear_missing: bool,
+    batch_size: usize,
+) -> CliResult<()> {
+    if batch_size == 0 {
+        return Err(CliError::InvalidConfig(
+            "--batch-size must be at least 1".to_string(),
+        ));
+    }
+
+    println!("🔄 Syncing claims from chain...");
+
+    let mut db = CampaignDatabase::open(&campaign_db_path)
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to open database: {}", e)))?;
+
+    let rpc_client = Arc::new(RpcClient::new_with_commitment(
+        &rpc_url,
+        CommitmentConfig::confirmed(),
+    ));
+    let client = PrismProtocolClient::new(rpc_client);
+
+    let campaign_info = db
+        .read_campaign_info()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read campaign: {}", e)))?;
+    let campaign_address =
+        client.campaign_v0_address(&campaign_info.fingerprint, &campaign_info.admin);
+    println!("🏛️  Campaign address: {}", campaign_address);
+
+    let cohorts = db
+        .read_cohorts()
+        .map_err(|e| CliError::InvalidConfig(format!("Failed to read cohorts: {}", e)))?;
+
+    let mut total_receipts = 0;
+    let mut total_up_to_date = 0;
+    let mut total_updated = 0;
+    let mut total_cleared = 0;
+    let mut total_unsigned = 0;
+    let mut total_drift = 0;
+
+    for cohort in &cohorts {
+        let cohort_address = client.cohort_v0_address(&campaign_address, &cohort.merkle_root);
+        println!("\n📦 Cohort: {} ({})", cohort.name, cohort_address);
+
+        let receipts = client
+            .find_claim_receipts_v0(ClaimReceiptFilter::Cohort(cohort_address))
+            .map_err(|e| {
+                CliError::InvalidConfig(format!("Failed to query claim receipts: {}", e))
+            })?;
+
+        let sync = CohortClaimSync::plan(&db, &cohort.name, &receipts, signatures)
+            .map_err(|e| CliError::InvalidConfig(format!("Failed to read claimants: {}", e)))?;
+        println!(
+            "   🧾 {} receipt(s), {} already recorded, {} to update",
+            sync.receipts,
+            sync.up_to_date,
+            sync.pending.len()
+        );
+
+        let mut updated = 0;
+        for batch in sync.pending.chunks(batch_size) {
+            let mut batch = batch.to_vec();
+            for claim in batch.iter_mut().filter(|claim| claim.needs_signature) {
+                let signature = client.find_first_signature(&claim.receipt).map_err(|e| {
+                    CliError::InvalidConfig(format!(
+                        "Failed to query signatures for receipt {}: {}",
+                        claim.receipt, e
+                    ))
+                })?;
+                match signature {
+                    Some(signature) => claim.signature = Some(signature.to_string()),
+                    None => total_unsigned += 1,
+                }
+            }
+
+            updated += apply_claims(&mut db, &cohort.name, &batch)
+                .map_err(|e| CliError::InvalidConfig(format!("Failed to record claims: {}", e)))?;
+            println!("   💾 Recorded {}/{}", updated, sync.pending.len());
+        }
+        total_updated += updated;
+
+        if clear_missing {
+            let cleared = clear_missing_receipts(&mut db, &sync).map_err(|e| {
+                CliError::InvalidConfig(format!("Failed to clear claim status: {}", e))
+            })?;
+            if cleared > 0 {
+                println!("   🧹 Cleared {} claim(s) without a receipt", cleared);
+            }
+            total_cleared += cleared;
+        }
+
+        // Cleared claims no longer count as drift
+        let drift: Vec<_> = sync
+            .drift
+            .iter()
+            .filter(|drift| !(clear_missing && matches!(drift, ClaimDrift::MissingReceipt { .. })))
+            .collect();
+        for drift in &drift {
+            println!("   ⚠️  {}", drift);
+        }
+        total_drift += drift.len();
+
+        total_receipts += sync.receipts;
+        total_up_to_date += sync.up_to_date;
+    }
+
+    println!("\n📊 Sync summary:");
+    println!("   Cohorts: {}", cohorts.len());
+    println!("   Receipts on-chain: {}", total_receipts);
+    println!("   Already recorded: {}", total_up_to_date);
+    println!("   Updated: {}", total_updated);
+    if clear_missing {
+        println!("   Cleared: {}", total_cleared);
+    }
+    if total_unsigned > 0 {
+        println!(
+            "   ⚠️  {} claim(s) recorded without a signature (none found on-chain)",
+            total_unsigned
+        );
+    }
+
+    if total_drift == 0 {
+        println!("\n✅ Database matches the chain");
+    } else {
+        println!(
+            "\n⚠️  {} drift issue(s) found; see the cohort reports above",
+            total_drift
+        );
+    }
+
+    Ok(())
+}
@@ -2,56 +2,6 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 use std::path::PathBuf;
 
-/// Campaign configuration file structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CampaignConfig {
-    /// Human-readable campaign name (for organization)
-    pub campaign_name: String,
-
-    /// SPL token mint to be distributed
-    pub mint: Pubkey,
-
-    /// Path to admin keypair file
-    pub admin_keypair_path: PathBuf,
-
-    /// Optional claim deadline (Unix timestamp)
-    pub claim_deadline_timestamp: Option<i64>,
-
-    /// List of cohorts in this campaign
-    pub cohorts: Vec<CohortConfig>,
-}
-
-/// Configuration for a single cohort within a campaign
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CohortConfig {
-    /// Human-readable cohort name (for organization)
-    pub cohort_name: String,
-
-    /// Amount of tokens per entitlement
-    pub amount_per_entitlement: u64,
-
-    /// Path to claimants file (CSV or JSON)
-    pub claimants_file: PathBuf,
-
-    /// Number of vaults to use for this cohort
-    #[serde(default = "default_vault_count")]
-    pub vault_count: usize,
-
-    /// Optional: Pre-specified vault pubkeys (if not provided, will be generated)
-    pub vaults: Option<Vec<Pubkey>>,
-}
-
-/// Claimant data structure for input files
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ClaimantInput {
-    /// Claimant's public key
-    pub claimant: Pubkey,
-
-    /// Number of entitlements for this claimant
-    #[serde(default = "default_entitlements")]
-    pub entitlements: u64,
-}
-
 /// Generated campaign output structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CampaignOutput {
@@ -128,11 +78,3 @@ pub struct ClaimantLookup {
     pub amount_per_entitlement: u64,
     pub total_claimable: u64,
 }
-
-fn default_vault_count() -> usize {
-    5
-}
-
-fn default_entitlements() -> u64 {
-    1
-}
@@ -1,3 +1,4 @@
+use prism_protocol_client::{program_error_hint, ClientError, DecodedProgramError};
 use thiserror::Error;
 
 pub type CliResult<T> = Result<T, CliError>;
@@ -34,3 +35,26 @@ pub enum CliError {
     #[error("Writing keypair to file: {0}")]
     WriteKeypair(String),
 }
+
+/// Wrap a failed client call, explaining program errors (name, message and a hint)
+pub fn program_failure(context: &str, err: ClientError) -> CliError {
+    if let ClientError::Program { code, name, msg } = &err {
+        println!(
+            "   ❌ Program rejected the transaction: {} ({})",
+            name, code
+        );
+        println!("      {}", msg);
+        if let Some(hint) = program_error_hint(name) {
+            println!("   💡 {}", hint);
+        }
+    }
+    CliError::InvalidConfig(format!("{}: {}", context, err))
+}
+
+/// Wrap a failed RPC send, decoding program errors from the preflight result
+pub fn rpc_failure(context: &str, err: solana_client::client_error::ClientError) -> CliError {
+    match DecodedProgramError::from_rpc_error(&err) {
+        Some(decoded) => program_failure(context, decoded.into()),
+        None => CliError::InvalidConfig(format!("{}: {}", context, err)),
+    }
+}
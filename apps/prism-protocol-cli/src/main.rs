@@ -1,5 +1,5 @@
-use clap::{Parser, Subcommand};
-use solana_sdk::pubkey::Pubkey;
+use clap::{Args, Parser, Subcommand};
+use solana_sdk::{hash::Hash, pubkey::Pubkey};
 use std::path::PathBuf;
 
 mod commands;
@@ -17,6 +17,45 @@ struct Cli {
     command: Commands,
 }
 
+/// Durable nonce options of single-transaction admin commands (pause, resume, reclaim)
+#[derive(Args)]
+struct SignOnlyArgs {
+    /// Sign offline against a durable nonce and write the transaction for `broadcast`
+    #[arg(long, requires_all = ["nonce", "blockhash", "signed_tx_out"])]
+    sign_only: bool,
+
+    /// Durable nonce account
+    #[arg(long, requires = "sign_only")]
+    nonce: Option<Pubkey>,
+
+    /// Value stored in the nonce account (see `solana nonce <account>`)
+    #[arg(long, requires = "sign_only")]
+    blockhash: Option<Hash>,
+
+    /// Nonce authority keypair file (defaults to the admin keypair)
+    #[arg(long, requires = "sign_only")]
+    nonce_authority: Option<PathBuf>,
+
+    /// Output file for the signed transaction
+    #[arg(long, requires = "sign_only")]
+    signed_tx_out: Option<PathBuf>,
+}
+
+impl SignOnlyArgs {
+    fn into_sign_only(self) -> Option<commands::admin_transaction::SignOnly> {
+        if !self.sign_only {
+            return None;
+        }
+        // clap requires the rest alongside --sign-only
+        Some(commands::admin_transaction::SignOnly {
+            nonce: self.nonce?,
+            blockhash: self.blockhash?,
+            nonce_authority: self.nonce_authority,
+            signed_tx_out: self.signed_tx_out?,
+        })
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Generate test fixtures for benchmarking and development (on clusters with airdrops)
@@ -62,27 +101,31 @@ enum Commands {
         rpc_url: String,
     },
 
-    /// Compile campaign from CSV files into deployment-ready database
+    /// Compile campaign from a manifest or CSV files into deployment-ready database
     CompileCampaign {
+        /// Campaign manifest (YAML or TOML) declaring every compile input
+        #[arg(long, conflicts_with_all = ["campaign_csv_in", "cohorts_csv_in", "mint", "budget", "admin_keypair", "auto_layout"])]
+        manifest: Option<PathBuf>,
+
         /// Input campaign CSV file path (cohort,claimant,entitlements)
-        #[arg(long)]
-        campaign_csv_in: PathBuf,
+        #[arg(long, required_unless_present = "manifest")]
+        campaign_csv_in: Option<PathBuf>,
 
         /// Input cohorts CSV file path (cohort,share_percentage)
-        #[arg(long)]
-        cohorts_csv_in: PathBuf,
+        #[arg(long, required_unless_present = "manifest")]
+        cohorts_csv_in: Option<PathBuf>,
 
         /// SPL token mint that will be distributed
-        #[arg(long)]
-        mint: Pubkey,
+        #[arg(long, required_unless_present = "manifest")]
+        mint: Option<Pubkey>,
 
         /// Campaign budget in human-readable tokens (e.g., "1000.5" for 1000.5 SOL)
-        #[arg(long)]
-        budget: String,
+        #[arg(long, required_unless_present = "manifest")]
+        budget: Option<String>,
 
         /// Path to admin keypair file
-        #[arg(long)]
-        admin_keypair: PathBuf,
+        #[arg(long, required_unless_present = "manifest")]
+        admin_keypair: Option<PathBuf>,
 
         /// Maximum claimants per vault (affects rent costs, can be reclaimed)
         #[arg(long, default_value = "200000")]
@@ -95,6 +138,37 @@ enum Commands {
         /// Solana RPC URL for mint metadata discovery
         #[arg(long, default_value = "http://127.0.0.1:8899")]
         rpc_url: String,
+
+        /// What to do with rounding dust: retain, first-leaves or treasury:<pubkey>
+        #[arg(long, default_value = "retain")]
+        dust_policy: String,
+
+        /// Leaf order of cohort trees: insertion or claimant (enables prove-ineligible)
+        #[arg(long, default_value = "insertion")]
+        leaf_order: String,
+
+        /// Compile with bounded memory via on-disk sorted runs (for very large campaigns)
+        #[arg(long)]
+        streaming: bool,
+
+        /// Directory for temporary sort files when streaming (defaults to the system temp dir)
+        #[arg(long, requires = "streaming")]
+        work_dir: Option<PathBuf>,
+
+        /// Plan each cohort's tree and vault count from claim size and compute costs
+        /// (--claimants-per-vault becomes an upper bound; with --tree-arity only vault
+        /// counts are planned)
+        #[arg(long)]
+        auto_layout: bool,
+
+        /// Compile every cohort into a V1 tree of this arity (2-256) instead of a binary
+        /// V0 tree; V1 cohorts are claimed with claim_tokens_v2
+        #[arg(long)]
+        tree_arity: Option<usize>,
+
+        /// Hash scheme of the V1 trees: sha256 or keccak256 (binary V0 trees are always SHA-256)
+        #[arg(long, default_value = "sha256", requires = "tree_arity")]
+        hash_scheme: String,
     },
 
     /// Deploy campaign on-chain
@@ -110,6 +184,37 @@ enum Commands {
         /// Solana RPC URL
         #[arg(long, default_value = "http://127.0.0.1:8899")]
         rpc_url: String,
+
+        /// Priority fee: none, auto, auto:<percentile> or micro-lamports per compute unit
+        /// (default: auto, or none with --sign-only)
+        #[arg(long)]
+        priority_fee: Option<String>,
+
+        /// Hex SHA-256 IPFS hash of the published campaign database; activates the
+        /// campaign at the manifest's go-live slot (or immediately) when given
+        #[arg(long)]
+        campaign_db_ipfs_hash: Option<String>,
+
+        /// Sign every deploy transaction offline against durable nonces and write them
+        /// for `broadcast` (activation needs the manifest's go_live_slot)
+        #[arg(long, requires_all = ["nonce", "blockhash", "signed_tx_out"])]
+        sign_only: bool,
+
+        /// Durable nonce account, one per deploy transaction (repeatable)
+        #[arg(long, requires = "sign_only")]
+        nonce: Vec<Pubkey>,
+
+        /// Value stored in each nonce account (see `solana nonce <account>`), in --nonce order
+        #[arg(long, requires = "sign_only")]
+        blockhash: Vec<Hash>,
+
+        /// Nonce authority keypair file (defaults to the admin keypair)
+        #[arg(long, requires = "sign_only")]
+        nonce_authority: Option<PathBuf>,
+
+        /// Output file for the signed transactions
+        #[arg(long, requires = "sign_only")]
+        signed_tx_out: Option<PathBuf>,
     },
 
     /// Pause a campaign
@@ -124,6 +229,14 @@ enum Commands {
         /// Solana RPC URL
         #[arg(short, long, default_value = "http://127.0.0.1:8899")]
         rpc_url: String,
+
+        /// Priority fee: none, auto, auto:<percentile> or micro-lamports per compute unit
+        /// (default: auto, or none with --sign-only)
+        #[arg(long)]
+        priority_fee: Option<String>,
+
+        #[command(flatten)]
+        sign_only: SignOnlyArgs,
     },
 
     /// Resume a campaign
@@ -138,6 +251,14 @@ enum Commands {
         /// Solana RPC URL
         #[arg(short, long, default_value = "http://127.0.0.1:8899")]
         rpc_url: String,
+
+        /// Priority fee: none, auto, auto:<percentile> or micro-lamports per compute unit
+        /// (default: auto, or none with --sign-only)
+        #[arg(long)]
+        priority_fee: Option<String>,
+
+        #[command(flatten)]
+        sign_only: SignOnlyArgs,
     },
 
     /// Reclaim tokens from a cohort
@@ -148,6 +269,14 @@ enum Commands {
         /// Cohort merkle root (hex string)
         cohort: String,
 
+        /// Index of the vault to empty
+        #[arg(long, default_value_t = 0)]
+        vault_index: u8,
+
+        /// Campaign mint; tokens go to the admin's associated token account for it
+        #[arg(long)]
+        mint: Pubkey,
+
         /// Admin keypair file
         #[arg(short, long)]
         keypair: PathBuf,
@@ -155,6 +284,48 @@ enum Commands {
         /// Solana RPC URL
         #[arg(short, long, default_value = "http://127.0.0.1:8899")]
         rpc_url: String,
+
+        /// Priority fee: none, auto, auto:<percentile> or micro-lamports per compute unit
+        /// (default: auto, or none with --sign-only)
+        #[arg(long)]
+        priority_fee: Option<String>,
+
+        #[command(flatten)]
+        sign_only: SignOnlyArgs,
+    },
+
+    /// Compare two compiled campaign databases before redeploying
+    DiffCampaign {
+        /// Previously compiled (or deployed) campaign database
+        #[arg(long)]
+        old_db: PathBuf,
+
+        /// Recompiled campaign database
+        #[arg(long)]
+        new_db: PathBuf,
+
+        /// Print the full diff as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Export per-claimant proof bundles as sharded static JSON
+    ExportProofs {
+        /// Campaign database file (contains campaign fingerprint and merkle trees)
+        #[arg(long)]
+        campaign_db_in: PathBuf,
+
+        /// Output directory for the manifest and shards
+        #[arg(long)]
+        out_dir: PathBuf,
+
+        /// Hex digits of the claimant pubkey used as shard directory name (1-4)
+        #[arg(long, default_value = "2")]
+        shard_prefix_len: usize,
+
+        /// Replace a previous export in the output directory
+        #[arg(long)]
+        overwrite: bool,
     },
 
     /// Get campaign status
@@ -185,6 +356,46 @@ enum Commands {
         /// Optional: dry run mode (simulate transaction without submitting)
         #[arg(long)]
         dry_run: bool,
+
+        /// Priority fee: none, auto, auto:<percentile> or micro-lamports per compute unit
+        /// (default: auto, or none with --sign-only)
+        #[arg(long)]
+        priority_fee: Option<String>,
+
+        /// Sign offline against durable nonces and write the transactions for `broadcast`
+        #[arg(long, requires_all = ["nonce", "blockhash", "signed_tx_out"], conflicts_with = "dry_run")]
+        sign_only: bool,
+
+        /// Durable nonce account, one per claim transaction (repeatable)
+        #[arg(long, requires = "sign_only")]
+        nonce: Vec<Pubkey>,
+
+        /// Value stored in each nonce account (see `solana nonce <account>`), in --nonce order
+        #[arg(long, requires = "sign_only")]
+        blockhash: Vec<Hash>,
+
+        /// Nonce authority keypair file (defaults to the claimant keypair)
+        #[arg(long, requires = "sign_only")]
+        nonce_authority: Option<PathBuf>,
+
+        /// Output file for the signed transactions
+        #[arg(long, requires = "sign_only")]
+        signed_tx_out: Option<PathBuf>,
+    },
+
+    /// Submit transactions signed offline (by `--sign-only`)
+    Broadcast {
+        /// Signed transactions file
+        #[arg(long)]
+        signed_tx_in: PathBuf,
+
+        /// Campaign database to record landed claims in
+        #[arg(long)]
+        campaign_db_in: Option<PathBuf>,
+
+        /// Solana RPC URL
+        #[arg(long, default_value = "http://127.0.0.1:8899")]
+        rpc_url: String,
     },
 
     /// Check what tokens a claimant is eligible for without claiming
@@ -202,16 +413,77 @@ enum Commands {
         rpc_url: String,
     },
 
+    /// Prove a claimant is not eligible, for cohorts compiled with --leaf-order claimant
+    ProveIneligible {
+        /// Campaign database file (contains campaign fingerprint and merkle trees)
+        #[arg(long)]
+        campaign_db_in: PathBuf,
+
+        /// Claimant pubkey to prove absent
+        #[arg(long)]
+        claimant: Pubkey,
+
+        /// Only prove exclusion from this cohort (defaults to every cohort)
+        #[arg(long)]
+        cohort: Option<String>,
+
+        /// Write the non-membership proofs as JSON
+        #[arg(long)]
+        proof_out: Option<PathBuf>,
+    },
+
     /// Query actual claims made by a claimant for this campaign (blockchain-only)
     QueryClaims {
         /// Campaign database file (for campaign fingerprint and filtering)
-        #[arg(long)]
-        campaign_db_in: PathBuf,
+        #[arg(long, required_unless_present = "all_campaigns")]
+        campaign_db_in: Option<PathBuf>,
 
         /// Claimant pubkey or path to keypair file (auto-detected)
         #[arg(long)]
         claimant: String,
 
+        /// Search every campaign on-chain instead of the one in the database
+        #[arg(long, conflicts_with = "campaign_db_in")]
+        all_campaigns: bool,
+
+        /// Solana RPC URL
+        #[arg(long, default_value = "http://127.0.0.1:8899")]
+        rpc_url: String,
+    },
+
+    /// Record on-chain claim receipts in the campaign database and report drift
+    SyncClaims {
+        /// Campaign database file to update
+        #[arg(long)]
+        campaign_db_in: PathBuf,
+
+        /// Solana RPC URL
+        #[arg(long, default_value = "http://127.0.0.1:8899")]
+        rpc_url: String,
+
+        /// Also look up and record each claim's transaction signature
+        #[arg(long)]
+        signatures: bool,
+
+        /// Clear the claim status of claimants recorded as claimed without a receipt
+        #[arg(long)]
+        clear_missing: bool,
+
+        /// Claims recorded per database transaction
+        #[arg(long, default_value = "500")]
+        batch_size: usize,
+    },
+
+    /// List on-chain campaigns by admin or mint (no database required)
+    ListCampaigns {
+        /// Campaign admin pubkey
+        #[arg(long, required_unless_present = "mint", conflicts_with = "mint")]
+        admin: Option<Pubkey>,
+
+        /// Campaign mint pubkey
+        #[arg(long)]
+        mint: Option<Pubkey>,
+
         /// Solana RPC URL
         #[arg(long, default_value = "http://127.0.0.1:8899")]
         rpc_url: String,
@@ -247,6 +519,7 @@ fn main() -> CliResult<()> {
         ),
 
         Commands::CompileCampaign {
+            manifest,
             campaign_csv_in,
             cohorts_csv_in,
             mint,
@@ -255,41 +528,137 @@ fn main() -> CliResult<()> {
             claimants_per_vault,
             campaign_db_out,
             rpc_url,
-        } => commands::compile_campaign::execute(
-            campaign_csv_in,
-            cohorts_csv_in,
-            mint,
-            budget,
-            admin_keypair,
-            claimants_per_vault,
-            campaign_db_out,
-            rpc_url,
-        ),
+            dust_policy,
+            leaf_order,
+            streaming,
+            work_dir,
+            auto_layout,
+            tree_arity,
+            hash_scheme,
+        } => match manifest {
+            Some(manifest) => commands::compile_campaign::execute_manifest(
+                manifest,
+                campaign_db_out,
+                rpc_url,
+                streaming,
+                work_dir,
+            ),
+            // clap requires the CSV inputs whenever --manifest is absent
+            None => commands::compile_campaign::execute(
+                campaign_csv_in.expect("--campaign-csv-in is required"),
+                cohorts_csv_in.expect("--cohorts-csv-in is required"),
+                mint.expect("--mint is required"),
+                budget.expect("--budget is required"),
+                admin_keypair.expect("--admin-keypair is required"),
+                claimants_per_vault,
+                campaign_db_out,
+                rpc_url,
+                dust_policy,
+                leaf_order,
+                streaming,
+                work_dir,
+                auto_layout,
+                tree_arity,
+                hash_scheme,
+            ),
+        },
 
         Commands::DeployCampaign {
             campaign_db_in,
             admin_keypair,
             rpc_url,
-        } => commands::deploy_campaign::execute(campaign_db_in, admin_keypair, rpc_url),
+            priority_fee,
+            campaign_db_ipfs_hash,
+            sign_only,
+            nonce,
+            blockhash,
+            nonce_authority,
+            signed_tx_out,
+        } => {
+            if sign_only {
+                commands::deploy_campaign::execute_sign_only(
+                    campaign_db_in,
+                    admin_keypair,
+                    priority_fee,
+                    campaign_db_ipfs_hash,
+                    commands::admin_transaction::SignOnlyNonces {
+                        nonces: nonce,
+                        blockhashes: blockhash,
+                        nonce_authority,
+                        signed_tx_out: signed_tx_out.expect("--signed-tx-out is required"),
+                    },
+                )
+            } else {
+                commands::deploy_campaign::execute(
+                    campaign_db_in,
+                    admin_keypair,
+                    rpc_url,
+                    priority_fee,
+                    campaign_db_ipfs_hash,
+                )
+            }
+        }
 
         Commands::PauseCampaign {
             campaign,
             keypair,
             rpc_url,
-        } => commands::pause_campaign::execute(campaign, keypair, rpc_url),
+            priority_fee,
+            sign_only,
+        } => commands::pause_campaign::execute(
+            campaign,
+            keypair,
+            rpc_url,
+            priority_fee,
+            sign_only.into_sign_only(),
+        ),
 
         Commands::ResumeCampaign {
             campaign,
             keypair,
             rpc_url,
-        } => commands::resume_campaign::execute(campaign, keypair, rpc_url),
+            priority_fee,
+            sign_only,
+        } => commands::resume_campaign::execute(
+            campaign,
+            keypair,
+            rpc_url,
+            priority_fee,
+            sign_only.into_sign_only(),
+        ),
 
         Commands::ReclaimTokens {
             campaign,
             cohort,
+            vault_index,
+            mint,
             keypair,
             rpc_url,
-        } => commands::reclaim_tokens::execute(campaign, cohort, keypair, rpc_url),
+            priority_fee,
+            sign_only,
+        } => commands::reclaim_tokens::execute(
+            campaign,
+            cohort,
+            vault_index,
+            mint,
+            keypair,
+            rpc_url,
+            priority_fee,
+            sign_only.into_sign_only(),
+        ),
+
+        Commands::DiffCampaign {
+            old_db,
+            new_db,
+            json,
+        } => commands::diff_campaign::execute(old_db, new_db, json),
+
+        Commands::ExportProofs {
+            campaign_db_in,
+            out_dir,
+            shard_prefix_len,
+            overwrite,
+        } => commands::export_proofs::execute(campaign_db_in, out_dir, shard_prefix_len, overwrite),
 
         Commands::CampaignStatus {
             campaign_db_in,
@@ -301,7 +670,39 @@ fn main() -> CliResult<()> {
             claimant_keypair,
             rpc_url,
             dry_run,
-        } => commands::claim_tokens::execute(campaign_db_in, claimant_keypair, rpc_url, dry_run),
+            priority_fee,
+            sign_only,
+            nonce,
+            blockhash,
+            nonce_authority,
+            signed_tx_out,
+        } => {
+            if sign_only {
+                commands::claim_tokens::execute_sign_only(
+                    campaign_db_in,
+                    claimant_keypair,
+                    nonce,
+                    blockhash,
+                    nonce_authority,
+                    priority_fee.unwrap_or_else(|| "none".to_string()),
+                    signed_tx_out.expect("--signed-tx-out is required"),
+                )
+            } else {
+                commands::claim_tokens::execute(
+                    campaign_db_in,
+                    claimant_keypair,
+                    rpc_url,
+                    dry_run,
+                    priority_fee.unwrap_or_else(|| "auto".to_string()),
+                )
+            }
+        }
+
+        Commands::Broadcast {
+            signed_tx_in,
+            campaign_db_in,
+            rpc_url,
+        } => commands::broadcast::execute(signed_tx_in, campaign_db_in, rpc_url),
 
         Commands::CheckEligibility {
             campaign_db_in,
@@ -309,10 +710,43 @@ fn main() -> CliResult<()> {
             rpc_url,
         } => commands::check_eligibility::execute(campaign_db_in, claimant, rpc_url),
 
+        Commands::ProveIneligible {
+            campaign_db_in,
+            claimant,
+            cohort,
+            proof_out,
+        } => commands::prove_ineligible::execute(campaign_db_in, claimant, cohort, proof_out),
+
         Commands::QueryClaims {
             campaign_db_in,
             claimant,
+            all_campaigns,
+            rpc_url,
+        } => match campaign_db_in {
+            Some(campaign_db_in) if !all_campaigns => {
+                commands::query_claims::execute(campaign_db_in, claimant, rpc_url)
+            }
+            _ => commands::query_claims::execute_all_campaigns(claimant, rpc_url),
+        },
+
+        Commands::SyncClaims {
+            campaign_db_in,
+            rpc_url,
+            signatures,
+            clear_missing,
+            batch_size,
+        } => commands::sync_claims::execute(
+            campaign_db_in,
+            rpc_url,
+            signatures,
+            clear_missing,
+            batch_size,
+        ),
+
+        Commands::ListCampaigns {
+            admin,
+            mint,
             rpc_url,
-        } => commands::query_claims::execute(campaign_db_in, claimant, rpc_url),
+        } => commands::list_campaigns::execute(admin, mint, rpc_url),
     }
 }
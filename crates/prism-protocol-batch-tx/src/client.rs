@@ -1,14 +1,19 @@
 use crate::{TxBatchConfig, TxBatchError};
 use backoff::future::retry;
 use futures::future::try_join_all;
-use solana_client::nonblocking::rpc_client::RpcClient;
+use prism_protocol_tx::{
+    compute_budget::{simulation_instructions, writable_accounts},
+    message::{compile_message, transaction_size, unsigned_transaction},
+};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
 use solana_sdk::{
+    hash::Hash,
     instruction::Instruction,
-    message::Message,
+    message::VersionedMessage,
     pubkey::Pubkey,
     signature::{Keypair, Signature},
     signer::Signer,
-    transaction::Transaction,
+    transaction::VersionedTransaction,
 };
 use std::sync::Arc;
 use tracing::{debug, info, warn};
@@ -24,6 +29,13 @@ pub struct CostEstimate {
     pub compute_units_per_tx: Vec<u32>,
 }
 
+/// A packed message (v0 when lookup tables are configured) and the compute unit limit
+/// it requests (if simulated)
+struct PackedMessage {
+    message: VersionedMessage,
+    compute_unit_limit: Option<u32>,
+}
+
 /// High-level client for efficient batch transaction sending
 pub struct BatchTxClient {
     rpc_client: Arc<RpcClient>,
@@ -126,13 +138,14 @@ impl BatchTxClient {
         let mut total_fee = 0u64;
         let mut compute_units = Vec::new();
 
-        for message in &messages {
-            let fee = self.rpc_client.get_fee_for_message(message).await?;
+        for packed in &messages {
+            let fee = self.fee_for_message(&packed.message).await?;
             total_fee += fee;
 
-            // For now, we'll estimate compute units based on instruction count
-            // In a full implementation, we'd simulate each transaction
-            let estimated_cu = message.instructions.len() as u32 * 10_000; // Rough estimate
+            // Simulated limits when available, otherwise a rough per-instruction estimate
+            let estimated_cu = packed
+                .compute_unit_limit
+                .unwrap_or(packed.message.instructions().len() as u32 * 10_000);
             compute_units.push(estimated_cu);
         }
 
@@ -148,36 +161,135 @@ impl BatchTxClient {
         &self,
         instructions: Vec<Instruction>,
         payer: &Pubkey,
-    ) -> Result<Vec<Message>, TxBatchError> {
+    ) -> Result<Vec<PackedMessage>, TxBatchError> {
         let recent_blockhash = self.rpc_client.get_latest_blockhash().await?;
+        let budget = self.config.compute_budget();
         let mut messages = Vec::new();
 
         // Simple chunking based on instruction count
         // TODO: Implement smarter chunking based on transaction size
         for chunk in instructions.chunks(self.config.max_instructions_per_tx) {
-            let message = Message::new_with_blockhash(chunk, Some(payer), &recent_blockhash);
+            let simulated_units = if budget.simulate_compute_units {
+                Some(self.simulate_compute_units(chunk, payer).await?)
+            } else {
+                None
+            };
+
+            let recent_fees = if budget.priority_fee.needs_samples() {
+                self.recent_prioritization_fees(chunk).await?
+            } else {
+                Vec::new()
+            };
+
+            let budgeted = budget.compute_budget_instructions(chunk, simulated_units, &recent_fees);
+            let compute_unit_limit = simulated_units.map(|units| budget.compute_unit_limit(units));
+            debug!(
+                "Packed {} instructions (CU limit: {:?}, priority fee: {:?})",
+                chunk.len(),
+                compute_unit_limit,
+                budget.priority_fee.resolve(&recent_fees)
+            );
 
-            // TODO: Add simulation and compute unit optimization
-            if self.config.simulate_before_send {
-                debug!("Simulation would be performed here");
-                // self.simulate_and_optimize_message(&mut message).await?;
+            let message = self.compile(payer, &budgeted, recent_blockhash)?;
+            let size = transaction_size(&message);
+            if size > self.config.max_transaction_size_bytes {
+                return Err(TxBatchError::TransactionTooLarge {
+                    size,
+                    max: self.config.max_transaction_size_bytes,
+                });
             }
 
-            messages.push(message);
+            messages.push(PackedMessage {
+                message,
+                compute_unit_limit,
+            });
         }
 
         Ok(messages)
     }
 
+    /// Simulate instructions at the maximum CU limit and return the units consumed
+    async fn simulate_compute_units(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+    ) -> Result<u64, TxBatchError> {
+        let transaction = unsigned_transaction(self.compile(
+            payer,
+            &simulation_instructions(instructions),
+            Hash::default(),
+        )?);
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(self.config.confirmation_commitment),
+            ..Default::default()
+        };
+
+        let result = self
+            .rpc_client
+            .simulate_transaction_with_config(&transaction, config)
+            .await?
+            .value;
+        if let Some(err) = result.err {
+            return Err(TxBatchError::SimulationFailed(format!(
+                "{} (logs: {:?})",
+                err,
+                result.logs.unwrap_or_default()
+            )));
+        }
+
+        result.units_consumed.ok_or_else(|| {
+            TxBatchError::SimulationFailed("Simulation did not report compute units".to_string())
+        })
+    }
+
+    /// Compile a message against the configured lookup tables (legacy when there are none)
+    fn compile(
+        &self,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        recent_blockhash: Hash,
+    ) -> Result<VersionedMessage, TxBatchError> {
+        compile_message(
+            payer,
+            instructions,
+            &self.config.address_lookup_tables,
+            recent_blockhash,
+        )
+        .map_err(|e| TxBatchError::Packing(format!("Failed to compile message: {}", e)))
+    }
+
+    /// Fee for a legacy or v0 message
+    async fn fee_for_message(&self, message: &VersionedMessage) -> Result<u64, TxBatchError> {
+        let fee = match message {
+            VersionedMessage::Legacy(message) => self.rpc_client.get_fee_for_message(message).await,
+            VersionedMessage::V0(message) => self.rpc_client.get_fee_for_message(message).await,
+        }?;
+        Ok(fee)
+    }
+
+    /// Recent prioritization fee samples for the instructions' writable accounts
+    async fn recent_prioritization_fees(
+        &self,
+        instructions: &[Instruction],
+    ) -> Result<Vec<u64>, TxBatchError> {
+        let fees = self
+            .rpc_client
+            .get_recent_prioritization_fees(&writable_accounts(instructions))
+            .await?;
+        Ok(fees.into_iter().map(|fee| fee.prioritization_fee).collect())
+    }
+
     /// Verify payer has sufficient balance for all transactions
     async fn verify_payer_balance(
         &self,
-        messages: &[Message],
+        messages: &[PackedMessage],
         payer: &Pubkey,
     ) -> Result<(), TxBatchError> {
         let mut total_fee = 0u64;
-        for message in messages {
-            total_fee += self.rpc_client.get_fee_for_message(message).await?;
+        for packed in messages {
+            total_fee += self.fee_for_message(&packed.message).await?;
         }
 
         let balance = self.rpc_client.get_balance(payer).await?;
@@ -196,14 +308,17 @@ impl BatchTxClient {
     }
 
     /// Send messages in parallel batches with retry logic (using default payer)
-    async fn send_messages(&self, messages: Vec<Message>) -> Result<Vec<Signature>, TxBatchError> {
+    async fn send_messages(
+        &self,
+        messages: Vec<PackedMessage>,
+    ) -> Result<Vec<Signature>, TxBatchError> {
         self.send_messages_with_payer(messages, &self.payer).await
     }
 
     /// Send messages in parallel batches with retry logic (with custom payer)
     async fn send_messages_with_payer(
         &self,
-        messages: Vec<Message>,
+        messages: Vec<PackedMessage>,
         payer: &Keypair,
     ) -> Result<Vec<Signature>, TxBatchError> {
         let mut all_signatures = Vec::new();
@@ -222,9 +337,9 @@ impl BatchTxClient {
             let batch_futures: Vec<_> = batch
                 .iter()
                 .enumerate()
-                .map(|(tx_idx, message)| {
+                .map(|(tx_idx, packed)| {
                     let overall_idx = batch_idx * self.config.max_parallel_sends + tx_idx;
-                    self.send_single_message_with_retry(message.clone(), payer, overall_idx)
+                    self.send_single_message_with_retry(packed.message.clone(), payer, overall_idx)
                 })
                 .collect();
 
@@ -243,7 +358,7 @@ impl BatchTxClient {
     /// Send a single message with retry logic
     async fn send_single_message_with_retry(
         &self,
-        message: Message,
+        message: VersionedMessage,
         payer: &Keypair,
         tx_index: usize,
     ) -> Result<Signature, TxBatchError> {
@@ -260,14 +375,12 @@ impl BatchTxClient {
                 let recent_blockhash = rpc_client
                     .get_latest_blockhash()
                     .await
-                    .map_err(|e| backoff::Error::Permanent(TxBatchError::RpcClient(e)))?;
-                message.recent_blockhash = recent_blockhash;
+                    .map_err(|e| backoff::Error::Permanent(TxBatchError::from(e)))?;
+                message.set_recent_blockhash(recent_blockhash);
 
                 // Create and sign transaction
-                let mut transaction = Transaction::new_unsigned(message);
-                transaction
-                    .try_sign(&[payer], recent_blockhash)
-                    .map_err(|e| {
+                let transaction =
+                    VersionedTransaction::try_new(message, &[payer]).map_err(|e| {
                         backoff::Error::Permanent(TxBatchError::Config(format!(
                             "Failed to sign transaction: {}",
                             e
@@ -294,12 +407,12 @@ impl BatchTxClient {
                         if error_str.contains("blockhash") || error_str.contains("timeout") {
                             // Retryable errors
                             Err(backoff::Error::Transient {
-                                err: TxBatchError::RpcClient(e),
+                                err: TxBatchError::from(e),
                                 retry_after: None,
                             })
                         } else {
                             // Permanent errors
-                            Err(backoff::Error::Permanent(TxBatchError::RpcClient(e)))
+                            Err(backoff::Error::Permanent(TxBatchError::from(e)))
                         }
                     }
                 }
@@ -354,6 +467,59 @@ mod tests {
         // let estimate = batch_client.estimate_cost(instructions).await;
     }
 
+    #[tokio::test]
+    async fn test_lookup_tables_pack_v0_messages() {
+        use solana_sdk::message::AddressLookupTableAccount;
+
+        let payer = Keypair::new();
+        let recipients: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let instructions: Vec<Instruction> = recipients
+            .iter()
+            .map(|recipient| system_instruction::transfer(&payer.pubkey(), recipient, 1))
+            .collect();
+
+        let config = TxBatchConfig {
+            simulate_before_send: false,
+            priority_fee: crate::PriorityFee::None,
+            address_lookup_tables: vec![AddressLookupTableAccount {
+                key: Pubkey::new_unique(),
+                addresses: recipients.clone(),
+            }],
+            ..Default::default()
+        };
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let batch_client = BatchTxClient::with_config(Arc::new(rpc_client), payer, config);
+
+        let packed = batch_client
+            .pack_instructions_to_messages(instructions.clone(), &batch_client.payer_pubkey())
+            .await
+            .unwrap();
+        assert_eq!(packed.len(), 1);
+        let VersionedMessage::V0(message) = &packed[0].message else {
+            panic!("expected a v0 message");
+        };
+        assert_eq!(message.address_table_lookups[0].writable_indexes.len(), 4);
+
+        // Oversized transactions are rejected while packing
+        let config = TxBatchConfig {
+            simulate_before_send: false,
+            priority_fee: crate::PriorityFee::None,
+            max_transaction_size_bytes: 100,
+            ..Default::default()
+        };
+        let batch_client = BatchTxClient::with_config(
+            Arc::new(RpcClient::new_mock("succeeds".to_string())),
+            Keypair::new(),
+            config,
+        );
+        assert!(matches!(
+            batch_client
+                .pack_instructions_to_messages(instructions, &batch_client.payer_pubkey())
+                .await,
+            Err(TxBatchError::TransactionTooLarge { max: 100, .. })
+        ));
+    }
+
     #[test]
     fn test_payer_pubkey() {
         let client = RpcClient::new("http://localhost:8899".to_string());
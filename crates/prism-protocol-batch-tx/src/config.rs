@@ -1,5 +1,8 @@
 use backoff::ExponentialBackoff;
-use solana_sdk::commitment_config::CommitmentConfig;
+use prism_protocol_tx::{
+    compute_budget::DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT, ComputeBudgetConfig, PriorityFee,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, message::AddressLookupTableAccount};
 use std::time::Duration;
 
 /// Configuration for batch transaction operations
@@ -26,6 +29,12 @@ pub struct TxBatchConfig {
     /// Whether to automatically set compute unit limits based on simulation
     pub auto_compute_unit_limit: bool,
 
+    /// Headroom added to simulated compute units, in percent
+    pub compute_unit_margin_percent: u32,
+
+    /// Priority fee (compute unit price) added to every transaction
+    pub priority_fee: PriorityFee,
+
     /// Whether to verify payer balance before sending transactions
     pub verify_balance_before_send: bool,
 
@@ -37,6 +46,9 @@ pub struct TxBatchConfig {
 
     /// Maximum transaction size in bytes (conservative default)
     pub max_transaction_size_bytes: usize,
+
+    /// Lookup tables to compile v0 messages against (empty = legacy transactions)
+    pub address_lookup_tables: Vec<AddressLookupTableAccount>,
 }
 
 impl Default for TxBatchConfig {
@@ -55,10 +67,24 @@ impl Default for TxBatchConfig {
             skip_preflight: false,
             simulate_before_send: true,
             auto_compute_unit_limit: true,
+            compute_unit_margin_percent: DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT,
+            priority_fee: PriorityFee::default(),
             verify_balance_before_send: true,
             max_parallel_sends: 4,
             chunk_instructions_optimally: true,
             max_transaction_size_bytes: 1200, // Conservative, well under 1232 limit
+            address_lookup_tables: Vec::new(),
+        }
+    }
+}
+
+impl TxBatchConfig {
+    /// Compute budget applied to each packed transaction
+    pub fn compute_budget(&self) -> ComputeBudgetConfig {
+        ComputeBudgetConfig {
+            simulate_compute_units: self.simulate_before_send && self.auto_compute_unit_limit,
+            compute_unit_margin_percent: self.compute_unit_margin_percent,
+            priority_fee: self.priority_fee,
         }
     }
 }
@@ -76,4 +102,22 @@ mod tests {
         assert!(config.simulate_before_send);
         assert!(config.verify_balance_before_send);
     }
+
+    #[test]
+    fn test_compute_budget_follows_simulation_flags() {
+        let config = TxBatchConfig::default();
+        assert!(config.compute_budget().simulate_compute_units);
+        assert_eq!(config.compute_budget().priority_fee, PriorityFee::Estimated);
+
+        let config = TxBatchConfig {
+            simulate_before_send: false,
+            priority_fee: PriorityFee::Fixed(1_000),
+            ..Default::default()
+        };
+        assert!(!config.compute_budget().simulate_compute_units);
+        assert_eq!(
+            config.compute_budget().priority_fee,
+            PriorityFee::Fixed(1_000)
+        );
+    }
 }
@@ -4,7 +4,7 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum TxBatchError {
     #[error("RPC client error: {0}")]
-    RpcClient(#[from] solana_client::client_error::ClientError),
+    RpcClient(Box<solana_client::client_error::ClientError>),
 
     #[error("Transaction failed after {retries} retries: {last_error}")]
     RetriesExhausted { retries: usize, last_error: String },
@@ -30,3 +30,9 @@ pub enum TxBatchError {
     #[error("Configuration error: {0}")]
     Config(String),
 }
+
+impl From<solana_client::client_error::ClientError> for TxBatchError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        TxBatchError::RpcClient(Box::new(err))
+    }
+}
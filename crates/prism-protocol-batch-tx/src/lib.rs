@@ -29,7 +29,7 @@ println!("Sent {} transactions successfully", signatures.len());
 ## Custom Configuration
 
 ```rust
-# use prism_protocol_batch_tx::{BatchTxClient, TxBatchConfig};
+# use prism_protocol_batch_tx::{BatchTxClient, PriorityFee, TxBatchConfig};
 # use solana_client::nonblocking::rpc_client::RpcClient;
 # use solana_sdk::signature::Keypair;
 # use std::sync::Arc;
@@ -40,6 +40,8 @@ let config = TxBatchConfig {
     max_parallel_sends: 8,
     simulate_before_send: true,
     max_retries: 5,
+    // Simulated CU limits are on by default; pin the priority fee instead of estimating it
+    priority_fee: PriorityFee::Fixed(5_000),
     ..Default::default()
 };
 
@@ -49,6 +51,27 @@ let client = BatchTxClient::with_config(rpc_client, payer, config);
 # }
 ```
 
+## Versioned Transactions
+
+With `address_lookup_tables` set, every batch is compiled into a v0 message that loads
+accounts through the tables instead of listing them inline:
+
+```rust
+# use prism_protocol_batch_tx::{AddressLookupTableAccount, BatchTxClient, TxBatchConfig};
+# use solana_client::nonblocking::rpc_client::RpcClient;
+# use solana_sdk::signature::Keypair;
+# use std::sync::Arc;
+
+# fn example(lookup_table: AddressLookupTableAccount) {
+# let rpc_client = Arc::new(RpcClient::new("https://api.devnet.solana.com".to_string()));
+let config = TxBatchConfig {
+    address_lookup_tables: vec![lookup_table],
+    ..Default::default()
+};
+let client = BatchTxClient::with_config(rpc_client, Keypair::new(), config);
+# }
+```
+
 ## Multi-Signer Support
 
 ```rust
@@ -78,14 +101,15 @@ mod error;
 pub use client::{BatchTxClient, CostEstimate};
 pub use config::TxBatchConfig;
 pub use error::TxBatchError;
+pub use prism_protocol_tx::{ComputeBudgetConfig, PriorityFee};
 
 // Re-export key Solana types for convenience
 pub use solana_client::nonblocking::rpc_client::RpcClient;
 pub use solana_sdk::{
     hash::Hash,
     instruction::Instruction,
-    message::Message,
+    message::{AddressLookupTableAccount, Message, VersionedMessage},
     pubkey::Pubkey,
     signature::{Keypair, Signature},
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
 };
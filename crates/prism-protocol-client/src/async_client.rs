@@ -0,0 +1,468 @@
+/*!
+# Async Prism Protocol Client
+
+Non-blocking counterpart of [`PrismProtocolClient`](crate::PrismProtocolClient) built on
+`solana_client::nonblocking::rpc_client::RpcClient`, for services running on tokio.
+
+The API mirrors the blocking client method for method; shared logic (PDA derivation,
+account decoding, RPC configuration, simulation handling) lives in [`PrismClientCore`].
+*/
+
+use std::sync::Arc;
+
+use crate::{
+    core::{
+        assemble_transaction, assemble_versioned_transaction, budget_simulation_transaction,
+        budget_simulation_versioned_transaction, check_simulation, decode_account, decode_accounts,
+        decode_lookup_table, decode_unchecked_account, log_signature, simulated_units,
+        PrismClientCore, MAX_MULTIPLE_ACCOUNTS,
+    },
+    discovery::{
+        decode_keyed_accounts, next_signature_cursor, oldest_successful_signature,
+        program_accounts_config, signatures_config, CampaignFilter, ClaimReceiptFilter,
+    },
+    errors::ClientResult,
+    program_error::rpc_error,
+    types::{AssembledTransaction, SimulationResult, TransactionResult},
+};
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::{Mint, TokenAccount};
+use futures::{stream, StreamExt, TryStreamExt};
+use prism_protocol_sdk::{
+    compute_budget::writable_accounts, AddressFinder, CampaignV0, ClaimReceiptV0, CohortV0,
+    ComputeBudgetConfig,
+};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::SerializableTransaction,
+    rpc_filter::RpcFilterType,
+};
+use solana_sdk::{
+    account::Account, commitment_config::CommitmentConfig, hash::Hash, instruction::Instruction,
+    message::AddressLookupTableAccount, pubkey::Pubkey, signature::Signature, signers::Signers,
+    transaction::VersionedTransaction,
+};
+
+/// Maximum number of `getMultipleAccounts` requests in flight at once
+pub const MAX_CONCURRENT_ACCOUNT_REQUESTS: usize = 8;
+
+/// Async client for Prism Protocol RPC operations
+#[derive(Clone)]
+pub struct AsyncPrismProtocolClient {
+    address_finder: Arc<AddressFinder>,
+    rpc_client: Arc<RpcClient>,
+}
+
+impl PrismClientCore for AsyncPrismProtocolClient {
+    fn address_finder(&self) -> &AddressFinder {
+        &self.address_finder
+    }
+
+    fn commitment(&self) -> CommitmentConfig {
+        self.rpc_client.commitment()
+    }
+}
+
+impl AsyncPrismProtocolClient {
+    /// Create new client with RpcClient
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self::new_with_address_finder(rpc_client, AddressFinder::default())
+    }
+
+    /// Create new client with custom AddressFinder (for advanced usage)
+    pub fn new_with_address_finder(
+        rpc_client: Arc<RpcClient>,
+        address_finder: AddressFinder,
+    ) -> Self {
+        Self {
+            rpc_client,
+            address_finder: Arc::new(address_finder),
+        }
+    }
+    // ================================================================================================
+    // Protocol Account Operations (Versioned) - Now much cleaner!
+    // ================================================================================================
+
+    /// Get campaign account (V0)
+    pub async fn get_campaign_v0(
+        &self,
+        fingerprint: &[u8; 32],
+        admin: &Pubkey,
+    ) -> ClientResult<Option<CampaignV0>> {
+        self.fetch_account(&self.campaign_v0_address(fingerprint, admin))
+            .await
+    }
+
+    /// Get cohort account (V0)
+    pub async fn get_cohort_v0(
+        &self,
+        campaign: &Pubkey,
+        merkle_root: &[u8; 32],
+    ) -> ClientResult<Option<CohortV0>> {
+        self.fetch_account(&self.cohort_v0_address(campaign, merkle_root))
+            .await
+    }
+
+    /// Get claim receipt account (V0) - Note: requires cohort address, not campaign
+    pub async fn get_claim_receipt_v0(
+        &self,
+        cohort: &Pubkey,
+        claimant: &Pubkey,
+    ) -> ClientResult<Option<ClaimReceiptV0>> {
+        self.fetch_account(&self.claim_receipt_v0_address(cohort, claimant))
+            .await
+    }
+
+    /// Get vault token account info - Note: requires cohort address and vault index
+    pub async fn get_vault_v0(
+        &self,
+        cohort: &Pubkey,
+        vault_index: u8,
+    ) -> ClientResult<Option<TokenAccount>> {
+        self.get_token_account(&self.vault_v0_address(cohort, vault_index))
+            .await
+    }
+
+    // ================================================================================================
+    // Batched Account Operations (getMultipleAccounts)
+    // ================================================================================================
+
+    /// Get raw accounts, chunked to the `getMultipleAccounts` limit (missing accounts are `None`)
+    ///
+    /// Up to [`MAX_CONCURRENT_ACCOUNT_REQUESTS`] chunks are requested at a time; results
+    /// keep the input order.
+    pub async fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> ClientResult<Vec<Option<Account>>> {
+        let chunks: Vec<_> = stream::iter(addresses.chunks(MAX_MULTIPLE_ACCOUNTS))
+            .map(|chunk| self.rpc_client.get_multiple_accounts(chunk))
+            .buffered(MAX_CONCURRENT_ACCOUNT_REQUESTS)
+            .try_collect()
+            .await?;
+
+        Ok(chunks.into_iter().flatten().collect())
+    }
+
+    /// Get many cohort accounts (V0) by address; missing accounts are `None`
+    pub async fn get_cohorts_v0(&self, cohorts: &[Pubkey]) -> ClientResult<Vec<Option<CohortV0>>> {
+        self.fetch_accounts(cohorts, decode_account).await
+    }
+
+    /// Get claim receipts (V0) for many claimants of one cohort, in claimant order
+    pub async fn get_claim_receipts_v0(
+        &self,
+        cohort: &Pubkey,
+        claimants: &[Pubkey],
+    ) -> ClientResult<Vec<Option<ClaimReceiptV0>>> {
+        let receipts: Vec<Pubkey> = claimants
+            .iter()
+            .map(|claimant| self.claim_receipt_v0_address(cohort, claimant))
+            .collect();
+        self.fetch_accounts(&receipts, decode_account).await
+    }
+
+    /// Get all vault token accounts (V0) of a cohort, indexed by vault index
+    pub async fn get_vaults_v0(
+        &self,
+        cohort: &Pubkey,
+        vault_count: u8,
+    ) -> ClientResult<Vec<Option<TokenAccount>>> {
+        let vaults: Vec<Pubkey> = (0..vault_count)
+            .map(|vault_index| self.vault_v0_address(cohort, vault_index))
+            .collect();
+        self.get_token_accounts(&vaults).await
+    }
+
+    /// Get many token accounts by address; missing accounts are `None`
+    pub async fn get_token_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> ClientResult<Vec<Option<TokenAccount>>> {
+        self.fetch_accounts(addresses, decode_unchecked_account)
+            .await
+    }
+
+    // ================================================================================================
+    // Account Discovery (getProgramAccounts)
+    // ================================================================================================
+
+    /// Find every campaign (V0) of an admin or mint, sorted by campaign address
+    pub async fn find_campaigns_v0(
+        &self,
+        filter: CampaignFilter,
+    ) -> ClientResult<Vec<(Pubkey, CampaignV0)>> {
+        self.find_program_accounts(filter.rpc_filters()).await
+    }
+
+    /// Find every claim receipt (V0) of a claimant or cohort, sorted by receipt address
+    pub async fn find_claim_receipts_v0(
+        &self,
+        filter: ClaimReceiptFilter,
+    ) -> ClientResult<Vec<(Pubkey, ClaimReceiptV0)>> {
+        self.find_program_accounts(filter.rpc_filters()).await
+    }
+
+    /// Find the oldest successful transaction of an account, e.g. the claim behind a receipt
+    ///
+    /// Pages back through the account's whole signature history; `None` if it has no
+    /// successful transaction.
+    pub async fn find_first_signature(&self, address: &Pubkey) -> ClientResult<Option<Signature>> {
+        let mut oldest = None;
+        let mut before = None;
+        loop {
+            let page = self
+                .rpc_client
+                .get_signatures_for_address_with_config(
+                    address,
+                    signatures_config(before, self.commitment()),
+                )
+                .await?;
+            if let Some(signature) = oldest_successful_signature(&page)? {
+                oldest = Some(signature);
+            }
+            match next_signature_cursor(&page)? {
+                Some(cursor) => before = Some(cursor),
+                None => return Ok(oldest),
+            }
+        }
+    }
+
+    // ================================================================================================
+    // SPL Token Operations (Using anchor_spl types)
+    // ================================================================================================
+
+    /// Get mint account using anchor_spl types
+    pub async fn get_mint(&self, mint: &Pubkey) -> ClientResult<Option<Mint>> {
+        self.fetch_unchecked_account(mint).await
+    }
+
+    /// Get token account using anchor_spl types
+    pub async fn get_token_account(&self, address: &Pubkey) -> ClientResult<Option<TokenAccount>> {
+        self.fetch_unchecked_account(address).await
+    }
+
+    /// Get vault balance with proper error handling
+    pub async fn get_vault_balance(&self, cohort: &Pubkey, vault_index: u8) -> ClientResult<u64> {
+        match self.get_vault_v0(cohort, vault_index).await? {
+            Some(vault_account) => Ok(vault_account.amount),
+            None => Ok(0), // Vault doesn't exist = 0 balance
+        }
+    }
+
+    // ================================================================================================
+    // Transaction Management (Simulation + Execution + Logging)
+    // ================================================================================================
+
+    /// Simulate transaction without executing (legacy or versioned)
+    pub async fn simulate_transaction(
+        &self,
+        tx: &impl SerializableTransaction,
+    ) -> ClientResult<SimulationResult> {
+        let result = self
+            .rpc_client
+            .simulate_transaction_with_config(tx, self.simulate_config())
+            .await?;
+        Ok(SimulationResult::from_rpc_result(result.value))
+    }
+
+    /// Send transaction (legacy or versioned) and return signature
+    pub async fn send_transaction(
+        &self,
+        tx: &impl SerializableTransaction,
+    ) -> ClientResult<Signature> {
+        let signature = self
+            .rpc_client
+            .send_transaction_with_config(tx, self.send_config())
+            .await
+            .map_err(rpc_error)?;
+
+        // Generate explorer URL for easy debugging
+        log_signature(&signature);
+
+        Ok(signature)
+    }
+
+    /// Build a signed transaction with a compute budget: simulates to size the CU limit
+    /// and samples `getRecentPrioritizationFees` for the priority fee, as configured
+    pub async fn assemble_transaction<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+        config: &ComputeBudgetConfig,
+    ) -> ClientResult<AssembledTransaction> {
+        let simulation = config
+            .simulate_compute_units
+            .then(|| budget_simulation_transaction(instructions, payer));
+        let (simulated, recent_fees, recent_blockhash) = self
+            .budget_inputs(simulation.as_ref(), instructions, config)
+            .await?;
+
+        assemble_transaction(
+            instructions,
+            payer,
+            signers,
+            recent_blockhash,
+            config,
+            simulated,
+            &recent_fees,
+        )
+    }
+
+    /// Like [`Self::assemble_transaction`], but builds a v0 transaction that references
+    /// accounts through `lookup_tables` (a legacy message when there are none)
+    pub async fn assemble_versioned_transaction<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+        lookup_tables: &[AddressLookupTableAccount],
+        config: &ComputeBudgetConfig,
+    ) -> ClientResult<AssembledTransaction<VersionedTransaction>> {
+        let simulation = if config.simulate_compute_units {
+            Some(budget_simulation_versioned_transaction(
+                instructions,
+                payer,
+                lookup_tables,
+            )?)
+        } else {
+            None
+        };
+        let (simulated, recent_fees, recent_blockhash) = self
+            .budget_inputs(simulation.as_ref(), instructions, config)
+            .await?;
+
+        assemble_versioned_transaction(
+            instructions,
+            payer,
+            signers,
+            lookup_tables,
+            recent_blockhash,
+            config,
+            simulated,
+            &recent_fees,
+        )
+    }
+
+    /// Get an address lookup table; `None` if it doesn't exist (or isn't a lookup table)
+    pub async fn get_address_lookup_table(
+        &self,
+        address: &Pubkey,
+    ) -> ClientResult<Option<AddressLookupTableAccount>> {
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(address, self.commitment())
+            .await?
+            .value;
+        Ok(decode_lookup_table(address, account))
+    }
+
+    /// Simulate and optionally send transaction (supports dry-run)
+    pub async fn simulate_and_send(
+        &self,
+        tx: &impl SerializableTransaction,
+        dry_run: bool,
+    ) -> ClientResult<TransactionResult> {
+        // Always simulate first
+        let sim_result = self.simulate_transaction(tx).await?;
+        if let Some(result) = check_simulation(sim_result, dry_run)? {
+            return Ok(result);
+        }
+
+        // Execute transaction
+        let signature = self.send_transaction(tx).await?;
+        Ok(TransactionResult::Executed(signature))
+    }
+
+    // ================================================================================================
+    // Utility Methods
+    // ================================================================================================
+
+    /// Get the underlying RPC client
+    pub fn rpc_client(&self) -> &Arc<RpcClient> {
+        &self.rpc_client
+    }
+
+    /// Helper method gathering what a compute budget needs: simulated units (when a
+    /// simulation transaction is given), prioritization fee samples and a blockhash
+    async fn budget_inputs(
+        &self,
+        simulation: Option<&impl SerializableTransaction>,
+        instructions: &[Instruction],
+        config: &ComputeBudgetConfig,
+    ) -> ClientResult<(Option<u64>, Vec<u64>, Hash)> {
+        let simulated = match simulation {
+            Some(tx) => {
+                let result = self
+                    .rpc_client
+                    .simulate_transaction_with_config(tx, self.budget_simulate_config())
+                    .await?;
+                Some(simulated_units(SimulationResult::from_rpc_result(
+                    result.value,
+                ))?)
+            }
+            None => None,
+        };
+
+        let recent_fees: Vec<u64> = if config.priority_fee.needs_samples() {
+            self.rpc_client
+                .get_recent_prioritization_fees(&writable_accounts(instructions))
+                .await?
+                .into_iter()
+                .map(|fee| fee.prioritization_fee)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let recent_blockhash = self.rpc_client.get_latest_blockhash().await?;
+        Ok((simulated, recent_fees, recent_blockhash))
+    }
+
+    /// Helper method to fetch and deserialize any account (RPC errors bubble up, deserialization errors become None)
+    async fn fetch_account<T>(&self, address: &Pubkey) -> ClientResult<Option<T>>
+    where
+        T: AccountDeserialize,
+    {
+        let account_data = self.rpc_client.get_account_data(address).await?;
+        Ok(decode_account(&account_data))
+    }
+
+    /// Helper method for SPL token accounts (no discriminators)
+    async fn fetch_unchecked_account<T>(&self, address: &Pubkey) -> ClientResult<Option<T>>
+    where
+        T: AccountDeserialize,
+    {
+        let account_data = self.rpc_client.get_account_data(address).await?;
+        Ok(decode_unchecked_account(&account_data))
+    }
+
+    /// Helper method to run a filtered `getProgramAccounts` query against the program
+    async fn find_program_accounts<T>(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> ClientResult<Vec<(Pubkey, T)>>
+    where
+        T: AccountDeserialize,
+    {
+        let config = program_accounts_config(filters, self.commitment());
+        let accounts = self
+            .rpc_client
+            .get_program_accounts_with_config(self.program_id(), config)
+            .await?;
+        Ok(decode_keyed_accounts(accounts))
+    }
+
+    /// Helper method to fetch and decode many accounts of one type
+    async fn fetch_accounts<T>(
+        &self,
+        addresses: &[Pubkey],
+        decode: fn(&[u8]) -> Option<T>,
+    ) -> ClientResult<Vec<Option<T>>>
+    where
+        T: AccountDeserialize,
+    {
+        let accounts = self.get_multiple_accounts(addresses).await?;
+        decode_accounts(addresses, accounts, decode)
+    }
+}
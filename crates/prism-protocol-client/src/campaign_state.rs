@@ -0,0 +1,562 @@
+/*!
+# Campaign State Loader
+
+Builds a typed, point-in-time snapshot of a campaign's on-chain state.
+
+Every address the campaign can own (campaign, cohorts, vaults and, optionally, one claim
+receipt per claimant) is derived from the campaign database up front and fetched with
+chunked `getMultipleAccounts` calls, so a status check costs a handful of round trips
+instead of one per account.
+
+```rust,no_run
+use prism_protocol_client::{CampaignState, ClientResult, PrismProtocolClient};
+use prism_protocol_db::CampaignDatabase;
+use solana_client::rpc_client::RpcClient;
+use std::{path::Path, sync::Arc};
+
+fn example() -> ClientResult<()> {
+    let client = PrismProtocolClient::new(Arc::new(RpcClient::new(
+        "http://127.0.0.1:8899".to_string(),
+    )));
+    let db = CampaignDatabase::open(Path::new("campaign.db"))?;
+
+    let state = CampaignState::load(&client, &db)?;
+    let progress = state.progress();
+    println!(
+        "{}/{} vaults funded",
+        progress.vaults_funded, progress.vaults_total
+    );
+    Ok(())
+}
+```
+*/
+
+use std::collections::HashMap;
+
+use crate::{
+    core::{decode_account, decode_fetched_account, decode_unchecked_account, PrismClientCore},
+    errors::ClientResult,
+    AsyncPrismProtocolClient, PrismProtocolClient,
+};
+use anchor_spl::token::TokenAccount;
+use prism_protocol_db::CampaignDatabase;
+use prism_protocol_sdk::{CampaignStatus, CampaignV0, ClaimReceiptV0, CohortV0};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+/// Snapshot of a campaign's on-chain accounts
+#[derive(Clone)]
+pub struct CampaignState {
+    pub fingerprint: [u8; 32],
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub address: Pubkey,
+    /// `None` if the campaign account doesn't exist (not deployed yet)
+    pub account: Option<CampaignV0>,
+    pub cohorts: Vec<CohortState>,
+}
+
+/// Snapshot of one cohort and its vaults
+#[derive(Clone)]
+pub struct CohortState {
+    pub name: String,
+    pub merkle_root: [u8; 32],
+    pub address: Pubkey,
+    pub account: Option<CohortV0>,
+    pub vaults: Vec<VaultState>,
+    /// Claim receipts, in database claimant order (only when loaded with receipts)
+    pub receipts: Option<Vec<ClaimantState>>,
+}
+
+/// Snapshot of one vault token account
+#[derive(Clone)]
+pub struct VaultState {
+    pub index: u8,
+    pub address: Pubkey,
+    pub required_tokens: u64,
+    pub account: Option<TokenAccount>,
+}
+
+/// Claim receipt status of one claimant
+#[derive(Clone)]
+pub struct ClaimantState {
+    pub claimant: Pubkey,
+    pub receipt_address: Pubkey,
+    pub receipt: Option<ClaimReceiptV0>,
+}
+
+/// Deployment, funding, activation and claim progress derived from a [`CampaignState`]
+#[derive(Clone, PartialEq, Eq)]
+pub struct CampaignProgress {
+    pub campaign_initialized: bool,
+    pub status: Option<CampaignStatus>,
+    pub cohorts_total: usize,
+    pub cohorts_initialized: usize,
+    pub cohorts_activated: usize,
+    pub vaults_total: usize,
+    pub vaults_initialized: usize,
+    pub vaults_activated: usize,
+    pub vaults_funded: usize,
+    pub tokens_required: u64,
+    /// Vault balances, each capped at that vault's requirement
+    pub tokens_funded: u64,
+    pub claims: Option<ClaimProgress>,
+}
+
+/// Claim progress (only available when receipts were loaded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimProgress {
+    pub claimants: usize,
+    pub claimed: usize,
+}
+
+impl VaultState {
+    /// Token balance (0 if the vault doesn't exist)
+    pub fn balance(&self) -> u64 {
+        self.account.as_ref().map_or(0, |account| account.amount)
+    }
+
+    /// Whether the vault holds at least its required tokens
+    pub fn is_funded(&self) -> bool {
+        self.account.is_some() && self.balance() >= self.required_tokens
+    }
+}
+
+impl CampaignProgress {
+    /// Every cohort and vault account exists on-chain
+    pub fn is_fully_deployed(&self) -> bool {
+        self.campaign_initialized
+            && self.cohorts_initialized == self.cohorts_total
+            && self.vaults_initialized == self.vaults_total
+    }
+
+    /// Every vault holds its required tokens
+    pub fn is_fully_funded(&self) -> bool {
+        self.vaults_funded == self.vaults_total
+    }
+}
+
+impl CampaignState {
+    /// Load the campaign, cohort and vault accounts (no claim receipts)
+    pub fn load(client: &PrismProtocolClient, db: &CampaignDatabase) -> ClientResult<Self> {
+        let plan = LoadPlan::new(client, db, false)?;
+        let accounts = client.get_multiple_accounts(&plan.addresses())?;
+        plan.assemble(accounts)
+    }
+
+    /// Load everything, including one claim receipt per claimant in the database
+    pub fn load_with_receipts(
+        client: &PrismProtocolClient,
+        db: &CampaignDatabase,
+    ) -> ClientResult<Self> {
+        let plan = LoadPlan::new(client, db, true)?;
+        let accounts = client.get_multiple_accounts(&plan.addresses())?;
+        plan.assemble(accounts)
+    }
+
+    /// Async variant of [`CampaignState::load`]
+    pub async fn load_async(
+        client: &AsyncPrismProtocolClient,
+        db: &CampaignDatabase,
+    ) -> ClientResult<Self> {
+        let plan = LoadPlan::new(client, db, false)?;
+        let accounts = client.get_multiple_accounts(&plan.addresses()).await?;
+        plan.assemble(accounts)
+    }
+
+    /// Async variant of [`CampaignState::load_with_receipts`]
+    pub async fn load_with_receipts_async(
+        client: &AsyncPrismProtocolClient,
+        db: &CampaignDatabase,
+    ) -> ClientResult<Self> {
+        let plan = LoadPlan::new(client, db, true)?;
+        let accounts = client.get_multiple_accounts(&plan.addresses()).await?;
+        plan.assemble(accounts)
+    }
+
+    /// Compute deployment/funding/activation (and claim) progress
+    pub fn progress(&self) -> CampaignProgress {
+        let vaults = || self.cohorts.iter().flat_map(|cohort| &cohort.vaults);
+
+        let claims = self
+            .cohorts
+            .iter()
+            .map(|cohort| cohort.receipts.as_ref())
+            .collect::<Option<Vec<_>>>()
+            .map(|receipts| ClaimProgress {
+                claimants: receipts.iter().map(|r| r.len()).sum(),
+                claimed: receipts
+                    .iter()
+                    .flat_map(|r| r.iter())
+                    .filter(|claimant| claimant.receipt.is_some())
+                    .count(),
+            });
+
+        CampaignProgress {
+            campaign_initialized: self.account.is_some(),
+            status: self.account.as_ref().map(|campaign| campaign.status),
+            cohorts_total: self.cohorts.len(),
+            cohorts_initialized: self
+                .cohorts
+                .iter()
+                .filter(|cohort| cohort.account.is_some())
+                .count(),
+            cohorts_activated: self
+                .account
+                .as_ref()
+                .map_or(0, |campaign| campaign.activated_cohort_count as usize),
+            vaults_total: vaults().count(),
+            vaults_initialized: vaults().filter(|vault| vault.account.is_some()).count(),
+            vaults_activated: self
+                .cohorts
+                .iter()
+                .filter_map(|cohort| cohort.account.as_ref())
+                .map(|cohort| cohort.activated_vault_count as usize)
+                .sum(),
+            vaults_funded: vaults().filter(|vault| vault.is_funded()).count(),
+            tokens_required: vaults().map(|vault| vault.required_tokens).sum(),
+            tokens_funded: vaults()
+                .map(|vault| vault.balance().min(vault.required_tokens))
+                .sum(),
+            claims,
+        }
+    }
+}
+
+/// Every address a campaign load needs, in fetch order
+struct LoadPlan {
+    state: CampaignState,
+}
+
+impl LoadPlan {
+    /// Derive all addresses from the database; the returned state has no accounts yet
+    fn new(
+        client: &impl PrismClientCore,
+        db: &CampaignDatabase,
+        include_receipts: bool,
+    ) -> ClientResult<Self> {
+        let campaign = db.read_campaign_info()?;
+        let address = client.campaign_v0_address(&campaign.fingerprint, &campaign.admin);
+
+        let mut required_tokens: HashMap<(String, usize), u64> = HashMap::new();
+        for requirement in db.read_vault_requirements()? {
+            required_tokens.insert(
+                (requirement.cohort_name, requirement.vault_index),
+                requirement.required_tokens,
+            );
+        }
+
+        let mut cohorts = Vec::new();
+        for cohort in db.read_cohorts()? {
+            let cohort_address = client.cohort_v0_address(&address, &cohort.merkle_root);
+
+            let vaults = (0..cohort.vault_count)
+                .map(|index| VaultState {
+                    index: index as u8,
+                    address: client.vault_v0_address(&cohort_address, index as u8),
+                    required_tokens: required_tokens
+                        .get(&(cohort.name.clone(), index))
+                        .copied()
+                        .unwrap_or(0),
+                    account: None,
+                })
+                .collect();
+
+            let receipts = if include_receipts {
+                let claimants = db.read_cohort_claimants(&cohort.name)?;
+                Some(
+                    claimants
+                        .into_iter()
+                        .map(|claimant| ClaimantState {
+                            claimant,
+                            receipt_address: client
+                                .claim_receipt_v0_address(&cohort_address, &claimant),
+                            receipt: None,
+                        })
+                        .collect(),
+                )
+            } else {
+                None
+            };
+
+            cohorts.push(CohortState {
+                name: cohort.name,
+                merkle_root: cohort.merkle_root,
+                address: cohort_address,
+                account: None,
+                vaults,
+                receipts,
+            });
+        }
+
+        Ok(Self {
+            state: CampaignState {
+                fingerprint: campaign.fingerprint,
+                admin: campaign.admin,
+                mint: campaign.mint,
+                address,
+                account: None,
+                cohorts,
+            },
+        })
+    }
+
+    /// Addresses in the order `assemble` consumes them
+    fn addresses(&self) -> Vec<Pubkey> {
+        let state = &self.state;
+        let mut addresses = vec![state.address];
+        addresses.extend(state.cohorts.iter().map(|cohort| cohort.address));
+        for cohort in &state.cohorts {
+            addresses.extend(cohort.vaults.iter().map(|vault| vault.address));
+        }
+        for cohort in &state.cohorts {
+            if let Some(receipts) = &cohort.receipts {
+                addresses.extend(receipts.iter().map(|receipt| receipt.receipt_address));
+            }
+        }
+        addresses
+    }
+
+    /// Fill the state with accounts fetched for `addresses()`
+    ///
+    /// Missing accounts stay `None`; an account that exists but doesn't decode is an error.
+    fn assemble(self, accounts: Vec<Option<Account>>) -> ClientResult<CampaignState> {
+        let mut fetched = self.addresses().into_iter().zip(accounts);
+        let mut state = self.state;
+
+        state.account = next_account(&mut fetched, decode_account)?;
+        for cohort in &mut state.cohorts {
+            cohort.account = next_account(&mut fetched, decode_account)?;
+        }
+        for cohort in &mut state.cohorts {
+            for vault in &mut cohort.vaults {
+                vault.account = next_account(&mut fetched, decode_unchecked_account)?;
+            }
+        }
+        for cohort in &mut state.cohorts {
+            if let Some(receipts) = &mut cohort.receipts {
+                for receipt in receipts {
+                    receipt.receipt = next_account(&mut fetched, decode_account)?;
+                }
+            }
+        }
+
+        Ok(state)
+    }
+}
+
+/// Decode the next fetched account (`None` once the accounts run out)
+fn next_account<T>(
+    fetched: &mut impl Iterator<Item = (Pubkey, Option<Account>)>,
+    decode: fn(&[u8]) -> Option<T>,
+) -> ClientResult<Option<T>> {
+    match fetched.next() {
+        Some((address, account)) => decode_fetched_account(&address, account.as_ref(), decode),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientError;
+    use anchor_lang::AccountSerialize;
+    use prism_protocol_sdk::AddressFinder;
+    use solana_sdk::{commitment_config::CommitmentConfig, program_pack::Pack};
+
+    struct TestCore(AddressFinder);
+
+    impl PrismClientCore for TestCore {
+        fn address_finder(&self) -> &AddressFinder {
+            &self.0
+        }
+
+        fn commitment(&self) -> CommitmentConfig {
+            CommitmentConfig::confirmed()
+        }
+    }
+
+    fn test_db(admin: Pubkey) -> CampaignDatabase {
+        let db = CampaignDatabase::create_in_memory().unwrap();
+        let conn = db.connection();
+        conn.execute(
+            "INSERT INTO campaign (fingerprint, mint, mint_decimals, admin, budget, created_at) VALUES (?, ?, 0, ?, '300', 0)",
+            rusqlite::params![hex::encode([1u8; 32]), Pubkey::new_unique().to_string(), admin.to_string()],
+        )
+        .unwrap();
+
+        for (name, root, vault_count) in [("A", [2u8; 32], 2), ("B", [3u8; 32], 1)] {
+            conn.execute(
+                "INSERT INTO cohorts (cohort_name, merkle_root, amount_per_entitlement, amount_per_entitlement_humane, vault_count, claimant_count, total_tokens_required) VALUES (?, ?, '10', '10', ?, 2, 100)",
+                rusqlite::params![name, hex::encode(root), vault_count],
+            )
+            .unwrap();
+            for index in 0..vault_count {
+                conn.execute(
+                    "INSERT INTO vaults (cohort_name, vault_index, vault_pubkey, required_tokens, assigned_claimants) VALUES (?, ?, ?, 50, 1)",
+                    rusqlite::params![name, index, Pubkey::new_unique().to_string()],
+                )
+                .unwrap();
+            }
+            for _ in 0..2 {
+                conn.execute(
+                    "INSERT INTO claimants (claimant, cohort_name, entitlements, assigned_vault_index, assigned_vault_pubkey, merkle_proof) VALUES (?, ?, 5, 0, '', X'00')",
+                    rusqlite::params![Pubkey::new_unique().to_string(), name],
+                )
+                .unwrap();
+            }
+        }
+
+        db
+    }
+
+    fn account<T: AccountSerialize>(value: &T) -> Option<Account> {
+        let mut data = Vec::new();
+        value.try_serialize(&mut data).unwrap();
+        Some(Account {
+            data,
+            ..Account::default()
+        })
+    }
+
+    fn token_account(amount: u64) -> Option<Account> {
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        Some(Account {
+            data,
+            ..Account::default()
+        })
+    }
+
+    #[test]
+    fn test_load_plan_addresses_and_progress() {
+        let core = TestCore(AddressFinder::default());
+        let admin = Pubkey::new_unique();
+        let db = test_db(admin);
+
+        let plan = LoadPlan::new(&core, &db, true).unwrap();
+        let addresses = plan.addresses();
+
+        // 1 campaign + 2 cohorts + 3 vaults + 4 receipts
+        assert_eq!(addresses.len(), 10);
+        assert_eq!(addresses[0], core.campaign_v0_address(&[1; 32], &admin));
+        assert_eq!(
+            addresses[1],
+            core.cohort_v0_address(&addresses[0], &[2; 32])
+        );
+        assert_eq!(addresses[3], core.vault_v0_address(&addresses[1], 0));
+
+        let campaign = CampaignV0 {
+            admin,
+            mint: Pubkey::new_unique(),
+            fingerprint: [1; 32],
+            campaign_db_ipfs_hash: [0; 32],
+            expected_cohort_count: 2,
+            initialized_cohort_count: 2,
+            activated_cohort_count: 1,
+            status: CampaignStatus::Inactive,
+            unstoppable: false,
+            go_live_slot: 0,
+            bump: 255,
+        };
+        let cohort = |merkle_root| CohortV0 {
+            campaign: addresses[0],
+            merkle_root,
+            amount_per_entitlement: 10,
+            expected_vault_count: 2,
+            initialized_vault_count: 2,
+            activated_vault_count: 2,
+            bump: 255,
+        };
+        let receipt = ClaimReceiptV0 {
+            claimant: Pubkey::new_unique(),
+            cohort: addresses[1],
+            assigned_vault: addresses[3],
+            claimed_at_timestamp: 0,
+            bump: 255,
+        };
+
+        let accounts = vec![
+            account(&campaign),
+            account(&cohort([2; 32])),
+            None, // cohort B not deployed
+            token_account(50),
+            token_account(20),
+            None,
+            account(&receipt),
+            None,
+            None,
+            None,
+        ];
+        let state = plan.assemble(accounts).unwrap();
+
+        assert!(state.account.is_some());
+        assert!(state.cohorts[1].account.is_none());
+        assert_eq!(state.cohorts[0].vaults[1].balance(), 20);
+
+        let progress = state.progress();
+        assert!(
+            progress
+                == CampaignProgress {
+                    campaign_initialized: true,
+                    status: Some(CampaignStatus::Inactive),
+                    cohorts_total: 2,
+                    cohorts_initialized: 1,
+                    cohorts_activated: 1,
+                    vaults_total: 3,
+                    vaults_initialized: 2,
+                    vaults_activated: 2,
+                    vaults_funded: 1,
+                    tokens_required: 150,
+                    tokens_funded: 70,
+                    claims: Some(ClaimProgress {
+                        claimants: 4,
+                        claimed: 1,
+                    }),
+                }
+        );
+        assert!(!progress.is_fully_deployed());
+        assert!(!progress.is_fully_funded());
+    }
+
+    #[test]
+    fn test_undecodable_receipt_is_an_error() {
+        let core = TestCore(AddressFinder::default());
+        let db = test_db(Pubkey::new_unique());
+
+        let plan = LoadPlan::new(&core, &db, true).unwrap();
+        let receipt_address = plan.addresses()[6];
+        let mut accounts = vec![None; 10];
+        accounts[6] = Some(Account {
+            data: vec![0; 16],
+            ..Account::default()
+        });
+
+        // Only a missing receipt means unclaimed
+        match plan.assemble(accounts) {
+            Err(ClientError::InvalidAccountData(message)) => {
+                assert!(message.contains(&receipt_address.to_string()))
+            }
+            other => panic!("Expected InvalidAccountData, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_load_plan_without_receipts() {
+        let core = TestCore(AddressFinder::default());
+        let db = test_db(Pubkey::new_unique());
+
+        let plan = LoadPlan::new(&core, &db, false).unwrap();
+        assert_eq!(plan.addresses().len(), 6);
+
+        let state = plan.assemble(vec![None; 6]).unwrap();
+        let progress = state.progress();
+        assert!(!progress.campaign_initialized);
+        assert_eq!(progress.claims, None);
+        assert_eq!(progress.tokens_funded, 0);
+    }
+}
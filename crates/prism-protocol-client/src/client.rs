@@ -2,22 +2,40 @@
 # Prism Protocol Client Implementation
 
 Main client providing unified access to Prism Protocol operations with proper versioning.
+
+This is the blocking client; see [`AsyncPrismProtocolClient`](crate::AsyncPrismProtocolClient)
+for the tokio-friendly equivalent. Both share their logic through [`PrismClientCore`].
 */
 
 use std::sync::Arc;
 
 use crate::{
-    errors::{ClientError, ClientResult},
-    types::{SimulationResult, TransactionResult},
+    core::{
+        assemble_transaction, assemble_versioned_transaction, budget_simulation_transaction,
+        budget_simulation_versioned_transaction, check_simulation, decode_account, decode_accounts,
+        decode_lookup_table, decode_unchecked_account, log_signature, simulated_units,
+        PrismClientCore, MAX_MULTIPLE_ACCOUNTS,
+    },
+    discovery::{
+        decode_keyed_accounts, next_signature_cursor, oldest_successful_signature,
+        program_accounts_config, signatures_config, CampaignFilter, ClaimReceiptFilter,
+    },
+    errors::ClientResult,
+    program_error::rpc_error,
+    types::{AssembledTransaction, SimulationResult, TransactionResult},
 };
 use anchor_lang::AccountDeserialize;
 use anchor_spl::token::{Mint, TokenAccount};
-use prism_protocol_sdk::AddressFinder;
+use prism_protocol_sdk::{compute_budget::writable_accounts, AddressFinder, ComputeBudgetConfig};
 use solana_client::{
-    rpc_client::RpcClient,
-    rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
+    rpc_client::{RpcClient, SerializableTransaction},
+    rpc_filter::RpcFilterType,
+};
+use solana_sdk::{
+    account::Account, commitment_config::CommitmentConfig, hash::Hash, instruction::Instruction,
+    message::AddressLookupTableAccount, pubkey::Pubkey, signature::Signature, signers::Signers,
+    transaction::VersionedTransaction,
 };
-use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
 
 // Re-export the actual program types via SDK (with versioning)
 pub use prism_protocol_sdk::{CampaignV0, ClaimReceiptV0, CohortV0};
@@ -32,6 +50,16 @@ pub struct PrismProtocolClient {
     rpc_client: Arc<RpcClient>,
 }
 
+impl PrismClientCore for PrismProtocolClient {
+    fn address_finder(&self) -> &AddressFinder {
+        &self.address_finder
+    }
+
+    fn commitment(&self) -> CommitmentConfig {
+        self.rpc_client.commitment()
+    }
+}
+
 impl PrismProtocolClient {
     /// Create new client with RpcClient
     pub fn new(rpc_client: Arc<RpcClient>) -> Self {
@@ -58,11 +86,7 @@ impl PrismProtocolClient {
         fingerprint: &[u8; 32],
         admin: &Pubkey,
     ) -> ClientResult<Option<CampaignV0>> {
-        let (campaign_pda, _) = self
-            .address_finder
-            .find_campaign_v0_address(admin, fingerprint);
-
-        self.fetch_account(&campaign_pda)
+        self.fetch_account(&self.campaign_v0_address(fingerprint, admin))
     }
 
     /// Get cohort account (V0)
@@ -71,11 +95,7 @@ impl PrismProtocolClient {
         campaign: &Pubkey,
         merkle_root: &[u8; 32],
     ) -> ClientResult<Option<CohortV0>> {
-        let (cohort_pda, _) = self
-            .address_finder
-            .find_cohort_v0_address(campaign, merkle_root);
-
-        self.fetch_account(&cohort_pda)
+        self.fetch_account(&self.cohort_v0_address(campaign, merkle_root))
     }
 
     /// Get claim receipt account (V0) - Note: requires cohort address, not campaign
@@ -84,11 +104,7 @@ impl PrismProtocolClient {
         cohort: &Pubkey,
         claimant: &Pubkey,
     ) -> ClientResult<Option<ClaimReceiptV0>> {
-        let (receipt_pda, _) = self
-            .address_finder
-            .find_claim_receipt_v0_address(cohort, claimant);
-
-        self.fetch_account(&receipt_pda)
+        self.fetch_account(&self.claim_receipt_v0_address(cohort, claimant))
     }
 
     /// Get vault token account info - Note: requires cohort address and vault index
@@ -97,10 +113,103 @@ impl PrismProtocolClient {
         cohort: &Pubkey,
         vault_index: u8,
     ) -> ClientResult<Option<TokenAccount>> {
-        let (vault_pda, _) = self
-            .address_finder
-            .find_vault_v0_address(cohort, vault_index);
-        self.get_token_account(&vault_pda)
+        self.get_token_account(&self.vault_v0_address(cohort, vault_index))
+    }
+
+    // ================================================================================================
+    // Batched Account Operations (getMultipleAccounts)
+    // ================================================================================================
+
+    /// Get raw accounts, chunked to the `getMultipleAccounts` limit (missing accounts are `None`)
+    pub fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> ClientResult<Vec<Option<Account>>> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            accounts.extend(self.rpc_client.get_multiple_accounts(chunk)?);
+        }
+        Ok(accounts)
+    }
+
+    /// Get many cohort accounts (V0) by address; missing accounts are `None`
+    pub fn get_cohorts_v0(&self, cohorts: &[Pubkey]) -> ClientResult<Vec<Option<CohortV0>>> {
+        self.fetch_accounts(cohorts, decode_account)
+    }
+
+    /// Get claim receipts (V0) for many claimants of one cohort, in claimant order
+    pub fn get_claim_receipts_v0(
+        &self,
+        cohort: &Pubkey,
+        claimants: &[Pubkey],
+    ) -> ClientResult<Vec<Option<ClaimReceiptV0>>> {
+        let receipts: Vec<Pubkey> = claimants
+            .iter()
+            .map(|claimant| self.claim_receipt_v0_address(cohort, claimant))
+            .collect();
+        self.fetch_accounts(&receipts, decode_account)
+    }
+
+    /// Get all vault token accounts (V0) of a cohort, indexed by vault index
+    pub fn get_vaults_v0(
+        &self,
+        cohort: &Pubkey,
+        vault_count: u8,
+    ) -> ClientResult<Vec<Option<TokenAccount>>> {
+        let vaults: Vec<Pubkey> = (0..vault_count)
+            .map(|vault_index| self.vault_v0_address(cohort, vault_index))
+            .collect();
+        self.get_token_accounts(&vaults)
+    }
+
+    /// Get many token accounts by address; missing accounts are `None`
+    pub fn get_token_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> ClientResult<Vec<Option<TokenAccount>>> {
+        self.fetch_accounts(addresses, decode_unchecked_account)
+    }
+
+    // ================================================================================================
+    // Account Discovery (getProgramAccounts)
+    // ================================================================================================
+
+    /// Find every campaign (V0) of an admin or mint, sorted by campaign address
+    pub fn find_campaigns_v0(
+        &self,
+        filter: CampaignFilter,
+    ) -> ClientResult<Vec<(Pubkey, CampaignV0)>> {
+        self.find_program_accounts(filter.rpc_filters())
+    }
+
+    /// Find every claim receipt (V0) of a claimant or cohort, sorted by receipt address
+    pub fn find_claim_receipts_v0(
+        &self,
+        filter: ClaimReceiptFilter,
+    ) -> ClientResult<Vec<(Pubkey, ClaimReceiptV0)>> {
+        self.find_program_accounts(filter.rpc_filters())
+    }
+
+    /// Find the oldest successful transaction of an account, e.g. the claim behind a receipt
+    ///
+    /// Pages back through the account's whole signature history; `None` if it has no
+    /// successful transaction.
+    pub fn find_first_signature(&self, address: &Pubkey) -> ClientResult<Option<Signature>> {
+        let mut oldest = None;
+        let mut before = None;
+        loop {
+            let page = self.rpc_client.get_signatures_for_address_with_config(
+                address,
+                signatures_config(before, self.commitment()),
+            )?;
+            if let Some(signature) = oldest_successful_signature(&page)? {
+                oldest = Some(signature);
+            }
+            match next_signature_cursor(&page)? {
+                Some(cursor) => before = Some(cursor),
+                None => return Ok(oldest),
+            }
+        }
     }
 
     // ================================================================================================
@@ -119,27 +228,12 @@ impl PrismProtocolClient {
 
     /// Convenience method to check if a mint is WSOL
     pub fn is_wsol_mint(&self, mint: &Pubkey) -> bool {
-        *mint == spl_token::native_mint::id()
+        PrismClientCore::is_wsol_mint(self, mint)
     }
 
     /// Format token amount with proper decimals (for display)
     pub fn format_token_amount(&self, base_units: u64, decimals: u8) -> String {
-        let divisor = 10_u64.pow(decimals as u32);
-        let whole_tokens = base_units / divisor;
-        let fractional_units = base_units % divisor;
-
-        if fractional_units == 0 {
-            format!("{}", whole_tokens)
-        } else {
-            // Format with trailing zeros removed
-            let fractional_str = format!("{:0width$}", fractional_units, width = decimals as usize);
-            let trimmed = fractional_str.trim_end_matches('0');
-            if trimmed.is_empty() {
-                format!("{}", whole_tokens)
-            } else {
-                format!("{}.{}", whole_tokens, trimmed)
-            }
-        }
+        PrismClientCore::format_token_amount(self, base_units, decimals)
     }
 
     /// Get vault balance with proper error handling
@@ -152,76 +246,119 @@ impl PrismProtocolClient {
 
     /// Get or create associated token account address
     pub fn get_associated_token_account_address(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
-        spl_associated_token_account::get_associated_token_address(owner, mint)
+        PrismClientCore::get_associated_token_account_address(self, owner, mint)
     }
 
     // ================================================================================================
     // Transaction Management (Simulation + Execution + Logging)
     // ================================================================================================
 
-    /// Simulate transaction without executing
-    pub fn simulate_transaction(&self, tx: &Transaction) -> ClientResult<SimulationResult> {
-        let config = RpcSimulateTransactionConfig {
-            sig_verify: true,
-            replace_recent_blockhash: true,
-            commitment: Some(self.rpc_client.commitment()),
-            encoding: None,
-            accounts: None,
-            min_context_slot: None,
-            inner_instructions: false,
-        };
-
+    /// Simulate transaction without executing (legacy or versioned)
+    pub fn simulate_transaction(
+        &self,
+        tx: &impl SerializableTransaction,
+    ) -> ClientResult<SimulationResult> {
         let result = self
             .rpc_client
-            .simulate_transaction_with_config(tx, config)?;
+            .simulate_transaction_with_config(tx, self.simulate_config())?;
         Ok(SimulationResult::from_rpc_result(result.value))
     }
 
-    /// Send transaction and return signature
-    pub fn send_transaction(&self, tx: &Transaction) -> ClientResult<Signature> {
-        let config = RpcSendTransactionConfig {
-            skip_preflight: false,
-            preflight_commitment: Some(self.rpc_client.commitment().commitment),
-            encoding: None,
-            max_retries: Some(3),
-            min_context_slot: None,
-        };
-
-        let signature = self.rpc_client.send_transaction_with_config(tx, config)?;
+    /// Send transaction (legacy or versioned) and return signature
+    pub fn send_transaction(&self, tx: &impl SerializableTransaction) -> ClientResult<Signature> {
+        let signature = self
+            .rpc_client
+            .send_transaction_with_config(tx, self.send_config())
+            .map_err(rpc_error)?;
 
         // Generate explorer URL for easy debugging
-        println!(
-            "✅ Transaction: https://explorer.solana.com/tx/{}",
-            signature
-        );
+        log_signature(&signature);
 
         Ok(signature)
     }
 
+    /// Build a signed transaction with a compute budget: simulates to size the CU limit
+    /// and samples `getRecentPrioritizationFees` for the priority fee, as configured
+    pub fn assemble_transaction<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+        config: &ComputeBudgetConfig,
+    ) -> ClientResult<AssembledTransaction> {
+        let simulation = config
+            .simulate_compute_units
+            .then(|| budget_simulation_transaction(instructions, payer));
+        let (simulated, recent_fees, recent_blockhash) =
+            self.budget_inputs(simulation.as_ref(), instructions, config)?;
+
+        assemble_transaction(
+            instructions,
+            payer,
+            signers,
+            recent_blockhash,
+            config,
+            simulated,
+            &recent_fees,
+        )
+    }
+
+    /// Like [`Self::assemble_transaction`], but builds a v0 transaction that references
+    /// accounts through `lookup_tables` (a legacy message when there are none)
+    pub fn assemble_versioned_transaction<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+        lookup_tables: &[AddressLookupTableAccount],
+        config: &ComputeBudgetConfig,
+    ) -> ClientResult<AssembledTransaction<VersionedTransaction>> {
+        let simulation = if config.simulate_compute_units {
+            Some(budget_simulation_versioned_transaction(
+                instructions,
+                payer,
+                lookup_tables,
+            )?)
+        } else {
+            None
+        };
+        let (simulated, recent_fees, recent_blockhash) =
+            self.budget_inputs(simulation.as_ref(), instructions, config)?;
+
+        assemble_versioned_transaction(
+            instructions,
+            payer,
+            signers,
+            lookup_tables,
+            recent_blockhash,
+            config,
+            simulated,
+            &recent_fees,
+        )
+    }
+
+    /// Get an address lookup table; `None` if it doesn't exist (or isn't a lookup table)
+    pub fn get_address_lookup_table(
+        &self,
+        address: &Pubkey,
+    ) -> ClientResult<Option<AddressLookupTableAccount>> {
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(address, self.commitment())?
+            .value;
+        Ok(decode_lookup_table(address, account))
+    }
+
     /// Simulate and optionally send transaction (supports dry-run)
     pub fn simulate_and_send(
         &self,
-        tx: &Transaction,
+        tx: &impl SerializableTransaction,
         dry_run: bool,
     ) -> ClientResult<TransactionResult> {
         // Always simulate first
         let sim_result = self.simulate_transaction(tx)?;
-
-        if !sim_result.success {
-            return Err(ClientError::SimulationFailed(format!(
-                "Transaction simulation failed: {}",
-                sim_result
-                    .error
-                    .unwrap_or_else(|| "Unknown error".to_string())
-            )));
-        }
-
-        if dry_run {
-            println!(
-                "🧪 Dry run successful - transaction would consume {} compute units",
-                sim_result.compute_units.unwrap_or(0)
-            );
-            return Ok(TransactionResult::Simulated(sim_result.raw));
+        if let Some(result) = check_simulation(sim_result, dry_run)? {
+            return Ok(result);
         }
 
         // Execute transaction
@@ -233,6 +370,11 @@ impl PrismProtocolClient {
     // Utility Methods
     // ================================================================================================
 
+    /// Get the underlying RPC client
+    pub fn rpc_client(&self) -> &Arc<RpcClient> {
+        &self.rpc_client
+    }
+
     /// Get the address finder
     pub fn address_finder(&self) -> &AddressFinder {
         &self.address_finder
@@ -240,7 +382,41 @@ impl PrismProtocolClient {
 
     /// Get the program ID
     pub fn program_id(&self) -> &Pubkey {
-        &self.address_finder.program_id
+        PrismClientCore::program_id(self)
+    }
+
+    /// Helper method gathering what a compute budget needs: simulated units (when a
+    /// simulation transaction is given), prioritization fee samples and a blockhash
+    fn budget_inputs(
+        &self,
+        simulation: Option<&impl SerializableTransaction>,
+        instructions: &[Instruction],
+        config: &ComputeBudgetConfig,
+    ) -> ClientResult<(Option<u64>, Vec<u64>, Hash)> {
+        let simulated = match simulation {
+            Some(tx) => {
+                let result = self
+                    .rpc_client
+                    .simulate_transaction_with_config(tx, self.budget_simulate_config())?;
+                Some(simulated_units(SimulationResult::from_rpc_result(
+                    result.value,
+                ))?)
+            }
+            None => None,
+        };
+
+        let recent_fees: Vec<u64> = if config.priority_fee.needs_samples() {
+            self.rpc_client
+                .get_recent_prioritization_fees(&writable_accounts(instructions))?
+                .into_iter()
+                .map(|fee| fee.prioritization_fee)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
+        Ok((simulated, recent_fees, recent_blockhash))
     }
 
     /// Helper method to fetch and deserialize any account (RPC errors bubble up, deserialization errors become None)
@@ -249,7 +425,7 @@ impl PrismProtocolClient {
         T: AccountDeserialize,
     {
         let account_data = self.rpc_client.get_account_data(address)?;
-        Ok(T::try_deserialize(&mut &account_data[..]).ok())
+        Ok(decode_account(&account_data))
     }
 
     /// Helper method for SPL token accounts (no discriminators)
@@ -258,6 +434,246 @@ impl PrismProtocolClient {
         T: AccountDeserialize,
     {
         let account_data = self.rpc_client.get_account_data(address)?;
-        Ok(T::try_deserialize_unchecked(&mut &account_data[..]).ok())
+        Ok(decode_unchecked_account(&account_data))
+    }
+
+    /// Helper method to run a filtered `getProgramAccounts` query against the program
+    fn find_program_accounts<T>(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> ClientResult<Vec<(Pubkey, T)>>
+    where
+        T: AccountDeserialize,
+    {
+        let config = program_accounts_config(filters, self.commitment());
+        let accounts = self
+            .rpc_client
+            .get_program_accounts_with_config(self.program_id(), config)?;
+        Ok(decode_keyed_accounts(accounts))
+    }
+
+    /// Helper method to fetch and decode many accounts of one type
+    fn fetch_accounts<T>(
+        &self,
+        addresses: &[Pubkey],
+        decode: fn(&[u8]) -> Option<T>,
+    ) -> ClientResult<Vec<Option<T>>>
+    where
+        T: AccountDeserialize,
+    {
+        let accounts = self.get_multiple_accounts(addresses)?;
+        decode_accounts(addresses, accounts, decode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{CampaignFilter, ClaimReceiptFilter};
+    use anchor_lang::AccountSerialize;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use solana_client::rpc_request::RpcRequest;
+    use std::collections::HashMap;
+
+    /// Client backed by a mock RPC node that answers `getProgramAccounts` with `accounts`
+    fn mock_client(accounts: Vec<(Pubkey, Vec<u8>)>) -> PrismProtocolClient {
+        let keyed_accounts: Vec<serde_json::Value> = accounts
+            .into_iter()
+            .map(|(pubkey, data)| {
+                serde_json::json!({
+                    "pubkey": pubkey.to_string(),
+                    "account": {
+                        "lamports": 1_000_000,
+                        "data": [STANDARD.encode(&data), "base64"],
+                        "owner": AddressFinder::default().program_id.to_string(),
+                        "executable": false,
+                        "rentEpoch": 0,
+                        "space": data.len(),
+                    },
+                })
+            })
+            .collect();
+
+        let mut mocks = HashMap::new();
+        mocks.insert(
+            RpcRequest::GetProgramAccounts,
+            serde_json::Value::Array(keyed_accounts),
+        );
+        PrismProtocolClient::new(Arc::new(RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            mocks,
+        )))
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_find_campaigns_v0_decodes_and_sorts() {
+        let admin = Pubkey::new_unique();
+        let campaign = |fingerprint| CampaignV0 {
+            admin,
+            mint: Pubkey::new_unique(),
+            fingerprint,
+            campaign_db_ipfs_hash: [0; 32],
+            expected_cohort_count: 1,
+            initialized_cohort_count: 1,
+            activated_cohort_count: 1,
+            status: prism_protocol_sdk::CampaignStatus::Active,
+            unstoppable: false,
+            go_live_slot: 0,
+            bump: 255,
+        };
+
+        let first = Pubkey::new_from_array([1; 32]);
+        let second = Pubkey::new_from_array([2; 32]);
+        let client = mock_client(vec![
+            (second, serialize(&campaign([2; 32]))),
+            (Pubkey::new_unique(), vec![0; 16]), // undecodable accounts are skipped
+            (first, serialize(&campaign([1; 32]))),
+        ]);
+
+        let campaigns = client
+            .find_campaigns_v0(CampaignFilter::Admin(admin))
+            .unwrap();
+        assert_eq!(campaigns.len(), 2);
+        assert_eq!(campaigns[0].0, first);
+        assert_eq!(campaigns[0].1.fingerprint, [1; 32]);
+        assert_eq!(campaigns[1].0, second);
+        assert_eq!(campaigns[1].1.admin, admin);
+    }
+
+    #[test]
+    fn test_find_claim_receipts_v0() {
+        let claimant = Pubkey::new_unique();
+        let receipt = ClaimReceiptV0 {
+            claimant,
+            cohort: Pubkey::new_unique(),
+            assigned_vault: Pubkey::new_unique(),
+            claimed_at_timestamp: 1_700_000_000,
+            bump: 255,
+        };
+        let address = Pubkey::new_unique();
+        let client = mock_client(vec![(address, serialize(&receipt))]);
+
+        let receipts = client
+            .find_claim_receipts_v0(ClaimReceiptFilter::Claimant(claimant))
+            .unwrap();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].0, address);
+        assert_eq!(receipts[0].1.claimed_at_timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_find_first_signature() {
+        // Newest first; the oldest transaction failed
+        let statuses: Vec<serde_json::Value> = (1..=3u8)
+            .map(|i| {
+                serde_json::json!({
+                    "signature": Signature::from([i; 64]).to_string(),
+                    "slot": 10 - i as u64,
+                    "err": (i == 3).then(|| serde_json::json!("AccountNotFound")),
+                    "memo": null,
+                    "blockTime": null,
+                    "confirmationStatus": "finalized",
+                })
+            })
+            .collect();
+        let mut mocks = HashMap::new();
+        mocks.insert(
+            RpcRequest::GetSignaturesForAddress,
+            serde_json::Value::Array(statuses),
+        );
+        let client = PrismProtocolClient::new(Arc::new(RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            mocks,
+        )));
+
+        assert_eq!(
+            client.find_first_signature(&Pubkey::new_unique()).unwrap(),
+            Some(Signature::from([2; 64]))
+        );
+    }
+
+    #[test]
+    fn test_get_address_lookup_table() {
+        use prism_protocol_sdk::lookup_table::decode_lookup_table;
+
+        // Serialized lookup table: 56-byte metadata header followed by the addresses
+        let stored = Pubkey::new_unique();
+        let mut data = vec![1, 0, 0, 0]; // ProgramState::LookupTable
+        data.extend_from_slice(&u64::MAX.to_le_bytes()); // deactivation slot
+        data.extend_from_slice(&0u64.to_le_bytes()); // last extended slot
+        data.push(0); // last extended slot start index
+        data.push(0); // no authority
+        data.resize(56, 0);
+        data.extend_from_slice(stored.as_ref());
+        let address = Pubkey::new_unique();
+        assert_eq!(
+            decode_lookup_table(address, &data).unwrap().addresses,
+            vec![stored]
+        );
+
+        let mut mocks = HashMap::new();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "lamports": 1_000_000,
+                    "data": [STANDARD.encode(&data), "base64"],
+                    "owner": Pubkey::new_unique().to_string(),
+                    "executable": false,
+                    "rentEpoch": 0,
+                    "space": data.len(),
+                },
+            }),
+        );
+        let client = PrismProtocolClient::new(Arc::new(RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            mocks,
+        )));
+
+        let table = client.get_address_lookup_table(&address).unwrap().unwrap();
+        assert_eq!(table.key, address);
+        assert_eq!(table.addresses, vec![stored]);
+    }
+
+    #[test]
+    fn test_assemble_transaction_sizes_budget_from_simulation() {
+        use prism_protocol_sdk::PriorityFee;
+        use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
+
+        let mut mocks = HashMap::new();
+        mocks.insert(
+            RpcRequest::SimulateTransaction,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": { "err": null, "logs": [], "unitsConsumed": 40_000 },
+            }),
+        );
+        let client = PrismProtocolClient::new(Arc::new(RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            mocks,
+        )));
+
+        let payer = Keypair::new();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let assembled = client
+            .assemble_transaction(
+                &[transfer],
+                &payer.pubkey(),
+                &[&payer],
+                &ComputeBudgetConfig::with_priority_fee(PriorityFee::Estimated),
+            )
+            .unwrap();
+
+        // 40k simulated + 10% margin; the mock node reports a single 10k fee sample
+        assert_eq!(assembled.compute_unit_limit, Some(44_000));
+        assert_eq!(assembled.compute_unit_price, Some(10_000));
+        assert_eq!(assembled.transaction.message.instructions.len(), 3);
     }
 }
@@ -0,0 +1,533 @@
+/*!
+# Shared Client Core
+
+Logic shared by the blocking [`PrismProtocolClient`](crate::PrismProtocolClient) and the
+async [`AsyncPrismProtocolClient`](crate::AsyncPrismProtocolClient): PDA derivation,
+account decoding, RPC configuration and simulation handling. The two clients only
+differ in how they talk to the RPC node.
+*/
+
+use crate::{
+    errors::{ClientError, ClientResult},
+    types::{AssembledTransaction, SimulationResult, TransactionResult},
+};
+use anchor_lang::AccountDeserialize;
+use prism_protocol_sdk::{
+    compute_budget::simulation_instructions, lookup_table, AddressFinder, ComputeBudgetConfig,
+};
+use solana_client::rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig};
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    message::{AddressLookupTableAccount, Message},
+    pubkey::Pubkey,
+    signature::Signature,
+    signers::Signers,
+    transaction::{Transaction, VersionedTransaction},
+};
+
+/// Maximum number of accounts fetched per `getMultipleAccounts` request
+pub use solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS;
+
+/// Operations common to the blocking and async Prism Protocol clients
+pub trait PrismClientCore {
+    /// Get the address finder
+    fn address_finder(&self) -> &AddressFinder;
+
+    /// Commitment used for queries, simulation and preflight
+    fn commitment(&self) -> CommitmentConfig;
+
+    /// Get the program ID
+    fn program_id(&self) -> &Pubkey {
+        &self.address_finder().program_id
+    }
+
+    // ================================================================================================
+    // Protocol Addresses (Versioned)
+    // ================================================================================================
+
+    /// Campaign PDA (V0)
+    fn campaign_v0_address(&self, fingerprint: &[u8; 32], admin: &Pubkey) -> Pubkey {
+        self.address_finder()
+            .find_campaign_v0_address(admin, fingerprint)
+            .0
+    }
+
+    /// Cohort PDA (V0)
+    fn cohort_v0_address(&self, campaign: &Pubkey, merkle_root: &[u8; 32]) -> Pubkey {
+        self.address_finder()
+            .find_cohort_v0_address(campaign, merkle_root)
+            .0
+    }
+
+    /// Claim receipt PDA (V0)
+    fn claim_receipt_v0_address(&self, cohort: &Pubkey, claimant: &Pubkey) -> Pubkey {
+        self.address_finder()
+            .find_claim_receipt_v0_address(cohort, claimant)
+            .0
+    }
+
+    /// Vault PDA (V0)
+    fn vault_v0_address(&self, cohort: &Pubkey, vault_index: u8) -> Pubkey {
+        self.address_finder()
+            .find_vault_v0_address(cohort, vault_index)
+            .0
+    }
+
+    // ================================================================================================
+    // SPL Token Helpers
+    // ================================================================================================
+
+    /// Convenience method to check if a mint is WSOL
+    fn is_wsol_mint(&self, mint: &Pubkey) -> bool {
+        *mint == spl_token::native_mint::id()
+    }
+
+    /// Format token amount with proper decimals (for display)
+    fn format_token_amount(&self, base_units: u64, decimals: u8) -> String {
+        let divisor = 10_u64.pow(decimals as u32);
+        let whole_tokens = base_units / divisor;
+        let fractional_units = base_units % divisor;
+
+        if fractional_units == 0 {
+            format!("{}", whole_tokens)
+        } else {
+            // Format with trailing zeros removed
+            let fractional_str = format!("{:0width$}", fractional_units, width = decimals as usize);
+            let trimmed = fractional_str.trim_end_matches('0');
+            if trimmed.is_empty() {
+                format!("{}", whole_tokens)
+            } else {
+                format!("{}.{}", whole_tokens, trimmed)
+            }
+        }
+    }
+
+    /// Get or create associated token account address
+    fn get_associated_token_account_address(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address(owner, mint)
+    }
+
+    // ================================================================================================
+    // Transaction Configuration
+    // ================================================================================================
+
+    /// Simulation settings used by `simulate_transaction`
+    fn simulate_config(&self) -> RpcSimulateTransactionConfig {
+        RpcSimulateTransactionConfig {
+            sig_verify: true,
+            replace_recent_blockhash: true,
+            commitment: Some(self.commitment()),
+            encoding: None,
+            accounts: None,
+            min_context_slot: None,
+            inner_instructions: false,
+        }
+    }
+
+    /// Simulation settings used to size compute budgets (unsigned transactions)
+    fn budget_simulate_config(&self) -> RpcSimulateTransactionConfig {
+        RpcSimulateTransactionConfig {
+            sig_verify: false,
+            ..self.simulate_config()
+        }
+    }
+
+    /// Send settings used by `send_transaction`
+    fn send_config(&self) -> RpcSendTransactionConfig {
+        RpcSendTransactionConfig {
+            skip_preflight: false,
+            preflight_commitment: Some(self.commitment().commitment),
+            encoding: None,
+            max_retries: Some(3),
+            min_context_slot: None,
+        }
+    }
+}
+
+/// Deserialize an Anchor account (discriminator checked); `None` if it doesn't decode
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Option<T> {
+    T::try_deserialize(&mut &data[..]).ok()
+}
+
+/// Deserialize an account without a discriminator (SPL token accounts)
+pub fn decode_unchecked_account<T: AccountDeserialize>(data: &[u8]) -> Option<T> {
+    T::try_deserialize_unchecked(&mut &data[..]).ok()
+}
+
+/// Decode a fetched account: `None` if it doesn't exist, an error if it exists but
+/// doesn't decode as `T`
+pub fn decode_fetched_account<T>(
+    address: &Pubkey,
+    account: Option<&Account>,
+    decode: fn(&[u8]) -> Option<T>,
+) -> ClientResult<Option<T>> {
+    account
+        .map(|account| {
+            decode(&account.data).ok_or_else(|| {
+                let type_name = std::any::type_name::<T>();
+                ClientError::InvalidAccountData(format!(
+                    "Account {} is not a {}",
+                    address,
+                    type_name.rsplit("::").next().unwrap_or(type_name)
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// Decode a `getMultipleAccounts` response, keeping missing accounts as `None`
+pub(crate) fn decode_accounts<T>(
+    addresses: &[Pubkey],
+    accounts: Vec<Option<Account>>,
+    decode: fn(&[u8]) -> Option<T>,
+) -> ClientResult<Vec<Option<T>>> {
+    addresses
+        .iter()
+        .zip(accounts)
+        .map(|(address, account)| decode_fetched_account(address, account.as_ref(), decode))
+        .collect()
+}
+
+/// Turn a simulation into an early result: an error if it failed, the simulation itself
+/// for dry runs, or `None` when the transaction should be sent
+pub(crate) fn check_simulation(
+    sim_result: SimulationResult,
+    dry_run: bool,
+) -> ClientResult<Option<TransactionResult>> {
+    if !sim_result.success {
+        if let Some(program_error) = sim_result.program_error {
+            return Err(program_error.into());
+        }
+        return Err(ClientError::SimulationFailed(format!(
+            "Transaction simulation failed: {}",
+            sim_result
+                .error
+                .unwrap_or_else(|| "Unknown error".to_string())
+        )));
+    }
+
+    if dry_run {
+        println!(
+            "🧪 Dry run successful - transaction would consume {} compute units",
+            sim_result.compute_units.unwrap_or(0)
+        );
+        return Ok(Some(TransactionResult::Simulated(sim_result.raw)));
+    }
+
+    Ok(None)
+}
+
+/// Compute units consumed by a successful simulation
+pub(crate) fn simulated_units(sim_result: SimulationResult) -> ClientResult<u64> {
+    let units = sim_result.compute_units;
+    check_simulation(sim_result, false)?;
+    units.ok_or_else(|| {
+        ClientError::SimulationFailed("Simulation did not report compute units".to_string())
+    })
+}
+
+/// Unsigned transaction used to measure compute units
+pub(crate) fn budget_simulation_transaction(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+) -> Transaction {
+    Transaction::new_unsigned(Message::new(
+        &simulation_instructions(instructions),
+        Some(payer),
+    ))
+}
+
+/// Unsigned v0 transaction used to measure compute units (the blockhash is replaced by the node)
+pub(crate) fn budget_simulation_versioned_transaction(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> ClientResult<VersionedTransaction> {
+    let message = lookup_table::compile_message(
+        payer,
+        &simulation_instructions(instructions),
+        lookup_tables,
+        Hash::default(),
+    )
+    .map_err(|e| ClientError::InvalidConfig(format!("Failed to compile message: {}", e)))?;
+    Ok(lookup_table::unsigned_transaction(message))
+}
+
+/// Apply the compute budget and sign
+pub(crate) fn assemble_transaction<T: Signers + ?Sized>(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &T,
+    recent_blockhash: Hash,
+    config: &ComputeBudgetConfig,
+    simulated_units: Option<u64>,
+    recent_fees: &[u64],
+) -> ClientResult<AssembledTransaction> {
+    let budgeted = config.compute_budget_instructions(instructions, simulated_units, recent_fees);
+    let mut transaction = Transaction::new_unsigned(Message::new_with_blockhash(
+        &budgeted,
+        Some(payer),
+        &recent_blockhash,
+    ));
+    transaction
+        .try_sign(signers, recent_blockhash)
+        .map_err(|e| ClientError::InvalidConfig(format!("Failed to sign transaction: {}", e)))?;
+
+    Ok(AssembledTransaction {
+        transaction,
+        compute_unit_limit: simulated_units.map(|units| config.compute_unit_limit(units)),
+        compute_unit_price: config.priority_fee.resolve(recent_fees),
+    })
+}
+
+/// Apply the compute budget, compile against the lookup tables and sign
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn assemble_versioned_transaction<T: Signers + ?Sized>(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &T,
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+    config: &ComputeBudgetConfig,
+    simulated_units: Option<u64>,
+    recent_fees: &[u64],
+) -> ClientResult<AssembledTransaction<VersionedTransaction>> {
+    let budgeted = config.compute_budget_instructions(instructions, simulated_units, recent_fees);
+    let message = lookup_table::compile_message(payer, &budgeted, lookup_tables, recent_blockhash)
+        .map_err(|e| ClientError::InvalidConfig(format!("Failed to compile message: {}", e)))?;
+    let transaction = VersionedTransaction::try_new(message, signers)
+        .map_err(|e| ClientError::InvalidConfig(format!("Failed to sign transaction: {}", e)))?;
+
+    Ok(AssembledTransaction {
+        transaction,
+        compute_unit_limit: simulated_units.map(|units| config.compute_unit_limit(units)),
+        compute_unit_price: config.priority_fee.resolve(recent_fees),
+    })
+}
+
+/// Decode a fetched lookup table account; `None` if missing or not a lookup table
+pub(crate) fn decode_lookup_table(
+    address: &Pubkey,
+    account: Option<Account>,
+) -> Option<AddressLookupTableAccount> {
+    account.and_then(|account| lookup_table::decode_lookup_table(*address, &account.data).ok())
+}
+
+/// Print an explorer URL for easy debugging
+pub(crate) fn log_signature(signature: &Signature) {
+    println!(
+        "✅ Transaction: https://explorer.solana.com/tx/{}",
+        signature
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    use prism_protocol_sdk::CohortV0;
+    use solana_client::rpc_response::RpcSimulateTransactionResult;
+
+    struct TestCore(AddressFinder);
+
+    impl PrismClientCore for TestCore {
+        fn address_finder(&self) -> &AddressFinder {
+            &self.0
+        }
+
+        fn commitment(&self) -> CommitmentConfig {
+            CommitmentConfig::confirmed()
+        }
+    }
+
+    fn simulation(err: Option<solana_sdk::transaction::TransactionError>) -> SimulationResult {
+        SimulationResult::from_rpc_result(RpcSimulateTransactionResult {
+            err,
+            logs: None,
+            accounts: None,
+            units_consumed: Some(1_000),
+            return_data: None,
+            inner_instructions: None,
+            replacement_blockhash: None,
+        })
+    }
+
+    #[test]
+    fn test_format_token_amount() {
+        let core = TestCore(AddressFinder::default());
+        assert_eq!(core.format_token_amount(1_500_000_000, 9), "1.5");
+        assert_eq!(core.format_token_amount(2_000_000_000, 9), "2");
+        assert_eq!(core.format_token_amount(42, 0), "42");
+        assert_eq!(core.format_token_amount(1, 6), "0.000001");
+    }
+
+    #[test]
+    fn test_decode_accounts_keeps_positions() {
+        let cohort = CohortV0 {
+            campaign: Pubkey::new_unique(),
+            merkle_root: [7; 32],
+            amount_per_entitlement: 10,
+            expected_vault_count: 1,
+            initialized_vault_count: 1,
+            activated_vault_count: 1,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        cohort.try_serialize(&mut data).unwrap();
+
+        let account = |data: Vec<u8>| Account {
+            data,
+            ..Account::default()
+        };
+        let addresses = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let decoded = decode_accounts::<CohortV0>(
+            &addresses,
+            vec![Some(account(data.clone())), None, Some(account(data))],
+            decode_account,
+        )
+        .unwrap();
+
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].as_ref().unwrap().merkle_root, [7; 32]);
+        assert!(decoded[1].is_none());
+        assert_eq!(decoded[2].as_ref().unwrap().merkle_root, [7; 32]);
+
+        // An existing account of the wrong type is an error, not a missing account
+        match decode_accounts::<CohortV0>(
+            &addresses[..1],
+            vec![Some(account(vec![0; 16]))],
+            decode_account,
+        ) {
+            Err(ClientError::InvalidAccountData(message)) => {
+                assert!(message.contains(&addresses[0].to_string()));
+                assert!(message.ends_with("CohortV0"));
+            }
+            other => panic!("Expected InvalidAccountData, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_assemble_transaction_applies_budget() {
+        use prism_protocol_sdk::PriorityFee;
+        use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
+
+        let payer = Keypair::new();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let config = ComputeBudgetConfig::with_priority_fee(PriorityFee::Percentile(50));
+
+        let assembled = assemble_transaction(
+            &[transfer],
+            &payer.pubkey(),
+            &[&payer],
+            Hash::new_unique(),
+            &config,
+            Some(1_000),
+            &[0, 100, 200],
+        )
+        .unwrap();
+
+        assert_eq!(assembled.compute_unit_limit, Some(1_100));
+        assert_eq!(assembled.compute_unit_price, Some(100));
+        assert_eq!(assembled.transaction.message.instructions.len(), 3);
+        assert!(assembled.transaction.is_signed());
+
+        // Signing with the wrong keypair is an error, not a panic
+        assert!(matches!(
+            assemble_transaction(
+                &[],
+                &payer.pubkey(),
+                &[&Keypair::new()],
+                Hash::new_unique(),
+                &config,
+                None,
+                &[],
+            ),
+            Err(ClientError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_assemble_versioned_transaction_uses_lookup_tables() {
+        use prism_protocol_sdk::PriorityFee;
+        use solana_sdk::{message::VersionedMessage, signature::Keypair, signer::Signer};
+
+        let payer = Keypair::new();
+        let shared = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let ix = Instruction::new_with_bytes(
+            program_id,
+            &[],
+            vec![solana_sdk::instruction::AccountMeta::new(shared, false)],
+        );
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![shared],
+        };
+        let config = ComputeBudgetConfig::with_priority_fee(PriorityFee::None);
+
+        let assembled = assemble_versioned_transaction(
+            &[ix],
+            &payer.pubkey(),
+            &[&payer],
+            std::slice::from_ref(&table),
+            Hash::new_unique(),
+            &config,
+            Some(1_000),
+            &[],
+        )
+        .unwrap();
+
+        let VersionedMessage::V0(message) = &assembled.transaction.message else {
+            panic!("expected a v0 message");
+        };
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert_eq!(message.address_table_lookups[0].account_key, table.key);
+        assert!(!message.account_keys.contains(&shared));
+        assert_eq!(assembled.compute_unit_limit, Some(1_100));
+        assert!(assembled
+            .transaction
+            .verify_with_results()
+            .iter()
+            .all(|ok| *ok));
+    }
+
+    #[test]
+    fn test_check_simulation() {
+        assert!(matches!(
+            check_simulation(simulation(None), true),
+            Ok(Some(TransactionResult::Simulated(_)))
+        ));
+        assert!(matches!(
+            check_simulation(simulation(None), false),
+            Ok(None)
+        ));
+        assert!(matches!(
+            check_simulation(
+                simulation(Some(
+                    solana_sdk::transaction::TransactionError::AccountNotFound
+                )),
+                false
+            ),
+            Err(ClientError::SimulationFailed(_))
+        ));
+        assert!(matches!(
+            check_simulation(
+                simulation(Some(
+                    solana_sdk::transaction::TransactionError::InstructionError(
+                        0,
+                        solana_sdk::instruction::InstructionError::Custom(6009)
+                    )
+                )),
+                false
+            ),
+            Err(ClientError::Program { code: 6009, ref name, .. }) if name == "CampaignNotActive"
+        ));
+    }
+}
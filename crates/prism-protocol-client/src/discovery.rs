@@ -0,0 +1,308 @@
+/*!
+# Account Discovery
+
+`getProgramAccounts` filters for finding protocol accounts without a campaign database:
+every campaign of an admin or mint, and every claim receipt of a claimant or cohort.
+
+Each query matches the Anchor discriminator and exact account size, plus a `memcmp` on
+the requested field, so the RPC node only returns accounts of the right type.
+
+`getSignaturesForAddress` paging finds the transaction that created an account, such as
+the claim behind a receipt.
+*/
+
+use anchor_lang::{AccountDeserialize, Discriminator, Space};
+use prism_protocol_sdk::{CampaignV0, ClaimReceiptV0};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+    rpc_response::RpcConfirmedTransactionStatusWithSignature,
+};
+use solana_sdk::{
+    account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature,
+};
+
+use crate::{
+    core::decode_account,
+    errors::{ClientError, ClientResult},
+};
+
+/// Byte offset of the first field after the 8-byte Anchor discriminator
+const FIRST_FIELD_OFFSET: usize = 8;
+
+/// Byte offset of the second (32-byte pubkey) field
+const SECOND_FIELD_OFFSET: usize = FIRST_FIELD_OFFSET + 32;
+
+/// Largest page `getSignaturesForAddress` returns
+pub(crate) const SIGNATURE_PAGE_LIMIT: usize = 1000;
+
+/// Which campaigns to discover
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CampaignFilter {
+    /// Campaigns administered by this pubkey (`CampaignV0.admin`)
+    Admin(Pubkey),
+    /// Campaigns distributing this mint (`CampaignV0.mint`)
+    Mint(Pubkey),
+}
+
+/// Which claim receipts to discover
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimReceiptFilter {
+    /// Receipts of this claimant across every campaign (`ClaimReceiptV0.claimant`)
+    Claimant(Pubkey),
+    /// Receipts of every claimant of this cohort (`ClaimReceiptV0.cohort`)
+    Cohort(Pubkey),
+}
+
+impl CampaignFilter {
+    /// `getProgramAccounts` filters selecting matching `CampaignV0` accounts
+    pub fn rpc_filters(&self) -> Vec<RpcFilterType> {
+        let (offset, key) = match self {
+            CampaignFilter::Admin(admin) => (FIRST_FIELD_OFFSET, admin),
+            CampaignFilter::Mint(mint) => (SECOND_FIELD_OFFSET, mint),
+        };
+        account_filters::<CampaignV0>(offset, key)
+    }
+}
+
+impl ClaimReceiptFilter {
+    /// `getProgramAccounts` filters selecting matching `ClaimReceiptV0` accounts
+    pub fn rpc_filters(&self) -> Vec<RpcFilterType> {
+        let (offset, key) = match self {
+            ClaimReceiptFilter::Claimant(claimant) => (FIRST_FIELD_OFFSET, claimant),
+            ClaimReceiptFilter::Cohort(cohort) => (SECOND_FIELD_OFFSET, cohort),
+        };
+        account_filters::<ClaimReceiptV0>(offset, key)
+    }
+}
+
+/// Discriminator + size + pubkey field filters for an Anchor account type
+fn account_filters<T: Discriminator + Space>(offset: usize, key: &Pubkey) -> Vec<RpcFilterType> {
+    vec![
+        RpcFilterType::DataSize((FIRST_FIELD_OFFSET + T::INIT_SPACE) as u64),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, T::DISCRIMINATOR)),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(offset, key.as_ref())),
+    ]
+}
+
+/// Program accounts query with base64 data (campaign accounts exceed the base58 limit)
+pub(crate) fn program_accounts_config(
+    filters: Vec<RpcFilterType>,
+    commitment: CommitmentConfig,
+) -> RpcProgramAccountsConfig {
+    RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(commitment),
+            ..RpcAccountInfoConfig::default()
+        },
+        with_context: None,
+        sort_results: None,
+    }
+}
+
+/// Decode keyed accounts, dropping any that don't deserialize (sorted by address)
+pub(crate) fn decode_keyed_accounts<T: AccountDeserialize>(
+    accounts: Vec<(Pubkey, Account)>,
+) -> Vec<(Pubkey, T)> {
+    let mut decoded: Vec<(Pubkey, T)> = accounts
+        .into_iter()
+        .filter_map(|(address, account)| {
+            decode_account(&account.data).map(|decoded| (address, decoded))
+        })
+        .collect();
+    decoded.sort_by_key(|(address, _)| *address);
+    decoded
+}
+
+/// Signatures query for the full page preceding `before` (newest first)
+pub(crate) fn signatures_config(
+    before: Option<Signature>,
+    commitment: CommitmentConfig,
+) -> GetConfirmedSignaturesForAddress2Config {
+    GetConfirmedSignaturesForAddress2Config {
+        before,
+        until: None,
+        limit: Some(SIGNATURE_PAGE_LIMIT),
+        commitment: Some(commitment),
+    }
+}
+
+/// Oldest signature of a page whose transaction succeeded
+pub(crate) fn oldest_successful_signature(
+    page: &[RpcConfirmedTransactionStatusWithSignature],
+) -> ClientResult<Option<Signature>> {
+    page.iter()
+        .rev()
+        .find(|status| status.err.is_none())
+        .map(|status| parse_signature(&status.signature))
+        .transpose()
+}
+
+/// Cursor for the page after this one, or `None` if this was the last page
+pub(crate) fn next_signature_cursor(
+    page: &[RpcConfirmedTransactionStatusWithSignature],
+) -> ClientResult<Option<Signature>> {
+    if page.len() < SIGNATURE_PAGE_LIMIT {
+        return Ok(None);
+    }
+    page.last()
+        .map(|status| parse_signature(&status.signature))
+        .transpose()
+}
+
+fn parse_signature(signature: &str) -> ClientResult<Signature> {
+    signature
+        .parse()
+        .map_err(|e| ClientError::Serialization(format!("Invalid signature {}: {}", signature, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    use prism_protocol_sdk::CampaignStatus;
+    use solana_sdk::transaction::TransactionError;
+
+    fn campaign(admin: Pubkey, mint: Pubkey) -> Vec<u8> {
+        let mut data = Vec::new();
+        CampaignV0 {
+            admin,
+            mint,
+            fingerprint: [1; 32],
+            campaign_db_ipfs_hash: [0; 32],
+            expected_cohort_count: 1,
+            initialized_cohort_count: 0,
+            activated_cohort_count: 0,
+            status: CampaignStatus::Inactive,
+            unstoppable: false,
+            go_live_slot: 0,
+            bump: 255,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        data
+    }
+
+    fn receipt(claimant: Pubkey, cohort: Pubkey) -> Vec<u8> {
+        let mut data = Vec::new();
+        ClaimReceiptV0 {
+            claimant,
+            cohort,
+            assigned_vault: Pubkey::new_unique(),
+            claimed_at_timestamp: 0,
+            bump: 255,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        data
+    }
+
+    fn matches(filters: &[RpcFilterType], data: &[u8]) -> bool {
+        filters.iter().all(|filter| match filter {
+            RpcFilterType::DataSize(size) => data.len() as u64 == *size,
+            RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(data),
+            RpcFilterType::TokenAccountState => false,
+        })
+    }
+
+    #[test]
+    fn test_campaign_filters_match_layout() {
+        let admin = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let data = campaign(admin, mint);
+
+        assert!(matches(&CampaignFilter::Admin(admin).rpc_filters(), &data));
+        assert!(matches(&CampaignFilter::Mint(mint).rpc_filters(), &data));
+        assert!(!matches(&CampaignFilter::Admin(mint).rpc_filters(), &data));
+        assert!(!matches(&CampaignFilter::Mint(admin).rpc_filters(), &data));
+
+        // A claim receipt whose first field happens to equal the admin is rejected
+        assert!(!matches(
+            &CampaignFilter::Admin(admin).rpc_filters(),
+            &receipt(admin, mint)
+        ));
+    }
+
+    #[test]
+    fn test_claim_receipt_filters_match_layout() {
+        let claimant = Pubkey::new_unique();
+        let cohort = Pubkey::new_unique();
+        let data = receipt(claimant, cohort);
+
+        assert!(matches(
+            &ClaimReceiptFilter::Claimant(claimant).rpc_filters(),
+            &data
+        ));
+        assert!(matches(
+            &ClaimReceiptFilter::Cohort(cohort).rpc_filters(),
+            &data
+        ));
+        assert!(!matches(
+            &ClaimReceiptFilter::Cohort(claimant).rpc_filters(),
+            &data
+        ));
+    }
+
+    fn signature(index: usize) -> Signature {
+        let mut bytes = [0u8; 64];
+        bytes[..8].copy_from_slice(&(index as u64 + 1).to_le_bytes());
+        Signature::from(bytes)
+    }
+
+    fn signature_page(
+        count: usize,
+        failed: &[usize],
+    ) -> Vec<RpcConfirmedTransactionStatusWithSignature> {
+        (0..count)
+            .map(|i| RpcConfirmedTransactionStatusWithSignature {
+                signature: signature(i).to_string(),
+                slot: (count - i) as u64,
+                err: failed
+                    .contains(&i)
+                    .then_some(TransactionError::AccountNotFound),
+                memo: None,
+                block_time: None,
+                confirmation_status: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_oldest_successful_signature() {
+        assert_eq!(oldest_successful_signature(&[]).unwrap(), None);
+
+        // Newest first: the oldest transaction failed, so the one before it is picked
+        let page = signature_page(3, &[2]);
+        assert_eq!(
+            oldest_successful_signature(&page).unwrap(),
+            Some(signature(1))
+        );
+        assert_eq!(
+            oldest_successful_signature(&signature_page(2, &[0, 1])).unwrap(),
+            None
+        );
+
+        let mut invalid = signature_page(1, &[]);
+        invalid[0].signature = "not-a-signature".to_string();
+        assert!(oldest_successful_signature(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_next_signature_cursor() {
+        assert_eq!(next_signature_cursor(&[]).unwrap(), None);
+        assert_eq!(
+            next_signature_cursor(&signature_page(SIGNATURE_PAGE_LIMIT - 1, &[])).unwrap(),
+            None
+        );
+
+        let full = signature_page(SIGNATURE_PAGE_LIMIT, &[]);
+        assert_eq!(
+            next_signature_cursor(&full).unwrap(),
+            Some(signature(SIGNATURE_PAGE_LIMIT - 1))
+        );
+    }
+}
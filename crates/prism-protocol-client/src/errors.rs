@@ -4,11 +4,17 @@ pub type ClientResult<T> = Result<T, ClientError>;
 
 #[derive(Error, Debug)]
 pub enum ClientError {
+    /// Boxed: the RPC error is several hundred bytes and would otherwise inflate every
+    /// `ClientResult`
     #[error("RPC error: {0}")]
-    Rpc(#[from] solana_client::client_error::ClientError),
+    Rpc(Box<solana_client::client_error::ClientError>),
 
-    #[error("Program error: {0}")]
-    Program(String),
+    #[error("Program error {name} ({code}): {msg}")]
+    Program {
+        code: u32,
+        name: String,
+        msg: String,
+    },
 
     #[error("Account not found: {0}")]
     AccountNotFound(String),
@@ -28,6 +34,15 @@ pub enum ClientError {
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
+    #[error("Database error: {0}")]
+    Database(#[from] prism_protocol_db::DbError),
+
     #[error("Serialization error: {0}")]
     Serialization(String),
 }
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(err))
+    }
+}
@@ -8,15 +8,27 @@ This crate provides a unified client for all Prism Protocol RPC operations and S
 This crate eliminates the technical debt of scattered RPC client code throughout the CLI by providing:
 
 - **Unified RPC Client**: Single, properly configured client with connection management
+- **Async Client**: Non-blocking `AsyncPrismProtocolClient` with the same API for tokio services
+- **Batched Fetching**: Many accounts per round trip via `getMultipleAccounts`
+- **Account Discovery**: Find campaigns by admin/mint and receipts by claimant/cohort
+- **Campaign Snapshots**: `CampaignState` loads a whole campaign and computes its progress
 - **Protocol Operations**: Clean abstractions for campaign, cohort, vault, and receipt operations
 - **SPL Token Management**: Safe token operations using `anchor_spl` types
 - **Transaction Excellence**: Simulation, sending, proper error handling, and explorer links
+- **Compute Budget**: `assemble_transaction` sizes the CU limit by simulation and adds a
+  configured or estimated priority fee
+- **Program Errors**: Failed transactions surface as `ClientError::Program` with the named
+  Prism Protocol or Anchor error instead of a raw `custom program error` code
 
 ## Architecture
 
 The main `PrismProtocolClient` provides all operations needed by CLI commands and the future API server.
 It replaces 19+ scattered database connections and 6+ duplicated RPC client setups with clean, reusable abstractions.
 
+`AsyncPrismProtocolClient` mirrors it on top of the nonblocking `RpcClient`. Both implement
+`PrismClientCore`, which holds everything that doesn't touch the network (PDA derivation,
+account decoding, transaction configuration), so the two clients cannot drift apart.
+
 Uses `anchor_spl` types exclusively for SPL token operations per our architecture decisions.
 
 ## Usage
@@ -46,16 +58,52 @@ fn example() -> ClientResult<()> {
     Ok(())
 }
 ```
+
+The async client has the same surface:
+
+```rust
+use prism_protocol_client::{AsyncPrismProtocolClient, ClientResult, PrismClientCore};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+
+async fn example(cohort: Pubkey, claimants: &[Pubkey]) -> ClientResult<()> {
+    let rpc_client = RpcClient::new("https://api.devnet.solana.com".to_string());
+    let client = AsyncPrismProtocolClient::new(Arc::new(rpc_client));
+
+    // One getMultipleAccounts round trip per 100 claimants
+    let receipts = client.get_claim_receipts_v0(&cohort, claimants).await?;
+    let claimed = receipts.iter().filter(|receipt| receipt.is_some()).count();
+    println!("{} of {} claimants have claimed", claimed, claimants.len());
+
+    // Shared helpers come from PrismClientCore
+    println!("Vault 0: {}", client.vault_v0_address(&cohort, 0));
+
+    Ok(())
+}
+```
 */
 
+pub mod async_client;
+pub mod campaign_state;
 pub mod client;
+pub mod core;
+pub mod discovery;
 pub mod errors;
+pub mod program_error;
 pub mod types;
 
 // Re-export main types for convenience
+pub use async_client::{AsyncPrismProtocolClient, MAX_CONCURRENT_ACCOUNT_REQUESTS};
+pub use campaign_state::{
+    CampaignProgress, CampaignState, ClaimProgress, ClaimantState, CohortState, VaultState,
+};
 pub use client::PrismProtocolClient;
+pub use core::{PrismClientCore, MAX_MULTIPLE_ACCOUNTS};
+pub use discovery::{CampaignFilter, ClaimReceiptFilter};
 pub use errors::{ClientError, ClientResult};
-pub use types::{SimulationResult, TransactionResult};
+pub use program_error::{program_error_hint, DecodedProgramError};
+pub use types::{AssembledTransaction, SimulationResult, TransactionResult};
 
 // Re-export anchor_spl types for external use (following architecture decisions)
 pub use anchor_spl::token::{Mint, TokenAccount};
@@ -0,0 +1,411 @@
+/*!
+# Program Error Decoding
+
+Turns raw transaction failures (`custom program error: 0x1770`) back into named
+Prism Protocol and Anchor errors.
+
+Errors are decoded from two sources:
+
+- **Program logs**: Anchor logs every error it returns as
+  `AnchorError ... Error Code: <name>. Error Number: <code>. Error Message: <msg>.`,
+  including the account that violated a constraint
+- **Error codes**: `InstructionError::Custom` codes are looked up in
+  `prism_protocol::error::ErrorCode` (6000+) and the common Anchor built-ins,
+  for failures that come back without logs
+*/
+
+use anchor_lang::error::{ErrorCode as AnchorErrorCode, ERROR_CODE_OFFSET};
+use prism_protocol::error::ErrorCode as PrismErrorCode;
+use solana_client::{
+    client_error::{ClientError as RpcClientError, ClientErrorKind},
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+use std::fmt;
+
+use crate::errors::ClientError;
+
+/// All Prism Protocol program errors, in declaration order
+const PRISM_ERRORS: &[PrismErrorCode] = &[
+    PrismErrorCode::InvalidMerkleProof,
+    PrismErrorCode::MerkleRootMismatch,
+    PrismErrorCode::NumericOverflow,
+    PrismErrorCode::InvalidEntitlements,
+    PrismErrorCode::TokenAccountOwnerMismatch,
+    PrismErrorCode::CampaignAdminMismatch,
+    PrismErrorCode::CampaignFingerprintMismatch,
+    PrismErrorCode::CohortCampaignMismatch,
+    PrismErrorCode::MintMismatch,
+    PrismErrorCode::CampaignNotActive,
+    PrismErrorCode::CampaignIsActive,
+    PrismErrorCode::CampaignAlreadyActivated,
+    PrismErrorCode::CampaignIsUnstoppable,
+    PrismErrorCode::CampaignNotPaused,
+    PrismErrorCode::CampaignNotPermanentlyHalted,
+    PrismErrorCode::InvalidStatusTransition,
+    PrismErrorCode::GoLiveDateNotReached,
+    PrismErrorCode::InvalidIpfsHash,
+    PrismErrorCode::GoLiveSlotInPast,
+    PrismErrorCode::NoCohortsExpected,
+    PrismErrorCode::NotAllCohortsActivated,
+    PrismErrorCode::NoVaultsExpected,
+    PrismErrorCode::VaultIndexOutOfBounds,
+    PrismErrorCode::TooManyVaults,
+    PrismErrorCode::VaultNotInitialized,
+    PrismErrorCode::IncorrectVaultFunding,
+    PrismErrorCode::NotAllVaultsActivated,
+    PrismErrorCode::AssignedVaultIndexOutOfBounds,
+    PrismErrorCode::VaultMismatch,
+    PrismErrorCode::ClaimReceiptMismatch,
+    PrismErrorCode::EmptyClaimBatch,
+    PrismErrorCode::ClaimBatchAccountsMismatch,
+    PrismErrorCode::InvalidMerkleTreeArity,
+    PrismErrorCode::CohortTreeMismatch,
+];
+
+/// Anchor framework errors our instructions can hit (instruction, constraint,
+/// require and account errors)
+const ANCHOR_ERRORS: &[AnchorErrorCode] = &[
+    AnchorErrorCode::InstructionMissing,
+    AnchorErrorCode::InstructionFallbackNotFound,
+    AnchorErrorCode::InstructionDidNotDeserialize,
+    AnchorErrorCode::InstructionDidNotSerialize,
+    AnchorErrorCode::ConstraintMut,
+    AnchorErrorCode::ConstraintHasOne,
+    AnchorErrorCode::ConstraintSigner,
+    AnchorErrorCode::ConstraintRaw,
+    AnchorErrorCode::ConstraintOwner,
+    AnchorErrorCode::ConstraintRentExempt,
+    AnchorErrorCode::ConstraintSeeds,
+    AnchorErrorCode::ConstraintExecutable,
+    AnchorErrorCode::ConstraintState,
+    AnchorErrorCode::ConstraintAssociated,
+    AnchorErrorCode::ConstraintAssociatedInit,
+    AnchorErrorCode::ConstraintClose,
+    AnchorErrorCode::ConstraintAddress,
+    AnchorErrorCode::ConstraintZero,
+    AnchorErrorCode::ConstraintTokenMint,
+    AnchorErrorCode::ConstraintTokenOwner,
+    AnchorErrorCode::ConstraintMintMintAuthority,
+    AnchorErrorCode::ConstraintMintFreezeAuthority,
+    AnchorErrorCode::ConstraintMintDecimals,
+    AnchorErrorCode::ConstraintSpace,
+    AnchorErrorCode::ConstraintAccountIsNone,
+    AnchorErrorCode::ConstraintTokenTokenProgram,
+    AnchorErrorCode::ConstraintMintTokenProgram,
+    AnchorErrorCode::ConstraintAssociatedTokenTokenProgram,
+    AnchorErrorCode::RequireViolated,
+    AnchorErrorCode::RequireEqViolated,
+    AnchorErrorCode::RequireKeysEqViolated,
+    AnchorErrorCode::RequireNeqViolated,
+    AnchorErrorCode::RequireKeysNeqViolated,
+    AnchorErrorCode::RequireGtViolated,
+    AnchorErrorCode::RequireGteViolated,
+    AnchorErrorCode::AccountDiscriminatorAlreadySet,
+    AnchorErrorCode::AccountDiscriminatorNotFound,
+    AnchorErrorCode::AccountDiscriminatorMismatch,
+    AnchorErrorCode::AccountDidNotDeserialize,
+    AnchorErrorCode::AccountDidNotSerialize,
+    AnchorErrorCode::AccountNotEnoughKeys,
+    AnchorErrorCode::AccountNotMutable,
+    AnchorErrorCode::AccountOwnedByWrongProgram,
+    AnchorErrorCode::InvalidProgramId,
+    AnchorErrorCode::InvalidProgramExecutable,
+    AnchorErrorCode::AccountNotSigner,
+    AnchorErrorCode::AccountNotSystemOwned,
+    AnchorErrorCode::AccountNotInitialized,
+    AnchorErrorCode::AccountNotProgramData,
+    AnchorErrorCode::AccountNotAssociatedTokenAccount,
+    AnchorErrorCode::AccountSysvarMismatch,
+    AnchorErrorCode::DeclaredProgramIdMismatch,
+    AnchorErrorCode::TryingToInitPayerAsProgramAccount,
+    AnchorErrorCode::InvalidNumericConversion,
+];
+
+/// A program error decoded from a failed transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedProgramError {
+    /// Numeric error code (`InstructionError::Custom`)
+    pub code: u32,
+    /// Error variant name, e.g. `InvalidMerkleProof`
+    pub name: String,
+    /// Human-readable message from the program
+    pub msg: String,
+    /// Account that violated a constraint, when Anchor reported one
+    pub account: Option<String>,
+}
+
+impl DecodedProgramError {
+    /// Look up a known Prism Protocol or Anchor error code
+    pub fn from_code(code: u32) -> Option<Self> {
+        let (name, msg) = if code >= ERROR_CODE_OFFSET {
+            PRISM_ERRORS
+                .iter()
+                .find(|error| u32::from(**error) == code)
+                .map(|error| (error.name(), error.to_string()))?
+        } else {
+            ANCHOR_ERRORS
+                .iter()
+                .find(|error| u32::from(**error) == code)
+                .map(|error| (error.name(), error.to_string()))?
+        };
+
+        Some(Self {
+            code,
+            name,
+            msg,
+            account: None,
+        })
+    }
+
+    /// Decode a transaction error, preferring Anchor's log line when logs are available
+    pub fn from_transaction_error(err: &TransactionError, logs: &[String]) -> Option<Self> {
+        let code = match err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => *code,
+            _ => return None,
+        };
+
+        parse_anchor_logs(logs)
+            .into_iter()
+            .rev()
+            .find(|decoded| decoded.code == code)
+            .or_else(|| Self::from_code(code))
+            .or_else(|| {
+                Some(Self {
+                    code,
+                    name: "Unknown".to_string(),
+                    msg: format!("custom program error: {:#x}", code),
+                    account: None,
+                })
+            })
+    }
+
+    /// Decode an RPC error from sending a transaction (preflight failures carry logs)
+    pub fn from_rpc_error(err: &RpcClientError) -> Option<Self> {
+        match err.kind() {
+            ClientErrorKind::TransactionError(tx_err) => Self::from_transaction_error(tx_err, &[]),
+            ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                data: RpcResponseErrorData::SendTransactionPreflightFailure(sim),
+                ..
+            }) => sim.err.as_ref().and_then(|tx_err| {
+                Self::from_transaction_error(tx_err, sim.logs.as_deref().unwrap_or_default())
+            }),
+            _ => None,
+        }
+    }
+
+    /// What the user can do about this error, for known Prism Protocol errors
+    pub fn hint(&self) -> Option<&'static str> {
+        program_error_hint(&self.name)
+    }
+}
+
+impl fmt::Display for DecodedProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.name, self.code, self.msg)?;
+        if let Some(account) = &self.account {
+            write!(f, " [account: {}]", account)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<DecodedProgramError> for ClientError {
+    fn from(decoded: DecodedProgramError) -> Self {
+        let msg = match decoded.account {
+            Some(account) => format!("{} (caused by account: {})", decoded.msg, account),
+            None => decoded.msg,
+        };
+        ClientError::Program {
+            code: decoded.code,
+            name: decoded.name,
+            msg,
+        }
+    }
+}
+
+/// Convert an RPC error, surfacing program failures as [`ClientError::Program`]
+pub(crate) fn rpc_error(err: RpcClientError) -> ClientError {
+    match DecodedProgramError::from_rpc_error(&err) {
+        Some(decoded) => decoded.into(),
+        None => ClientError::Rpc(Box::new(err)),
+    }
+}
+
+/// Actionable advice for a program error name
+pub fn program_error_hint(name: &str) -> Option<&'static str> {
+    let hint = match name {
+        "InvalidMerkleProof" | "MerkleRootMismatch" => {
+            "The campaign database doesn't match the deployed cohort; make sure you're using the database the campaign was deployed from"
+        }
+        "CampaignNotActive" => "The campaign hasn't been activated yet (or was paused/halted); check `campaign-status`",
+        "GoLiveDateNotReached" => "Claims open at the campaign's go-live slot; try again later",
+        "TokenAccountOwnerMismatch" => "The destination token account must be owned by the claimant",
+        "CampaignAdminMismatch" => "Sign with the campaign admin keypair the campaign was compiled for",
+        "MintMismatch" => "The mint doesn't match the campaign's mint; check the campaign database",
+        "CampaignIsActive" => "Active campaigns can't be modified; pause the campaign first",
+        "CampaignAlreadyActivated" => "The campaign is already active; nothing to do",
+        "CampaignIsUnstoppable" => "Unstoppable campaigns can't be paused, halted or modified",
+        "GoLiveSlotInPast" => "Choose a go-live slot at or after the current slot",
+        "NotAllCohortsActivated" => "Activate every cohort before activating the campaign",
+        "NotAllVaultsActivated" => "Activate every vault before activating the cohort",
+        "IncorrectVaultFunding" => "Vault balances must exactly match the compiled requirements; re-run deployment to top up vaults",
+        "VaultNotInitialized" => "Create the vault before activating it",
+        "AssignedVaultIndexOutOfBounds" | "VaultIndexOutOfBounds" => {
+            "The vault index is outside the cohort's vault count; check the campaign database"
+        }
+        "VaultMismatch" => "A batched claim's vault isn't the cohort vault at its assigned index",
+        "ClaimReceiptMismatch" => "A batched claim's receipt isn't the claimant's receipt PDA for the cohort",
+        "EmptyClaimBatch" => "A batched claim must settle at least one claim",
+        "ClaimBatchAccountsMismatch" => "A batched claim needs four remaining accounts per claim: claimant, vault, token account and receipt",
+        "InvalidMerkleTreeArity" => "Cohort trees have 2 to 256 children per node; binary (V0) campaigns use 2",
+        "CohortTreeMismatch" => "The cohort tree account belongs to another cohort; derive it from the cohort address",
+        "ConstraintSeeds" => "An account address doesn't match its expected PDA; check the campaign fingerprint and admin",
+        "AccountNotInitialized" => "A required account doesn't exist on-chain yet; has the campaign been deployed?",
+        "ConstraintSigner" | "AccountNotSigner" => "A required signer is missing from the transaction",
+        _ => return None,
+    };
+    Some(hint)
+}
+
+/// Parse Anchor's `AnchorError ...` log lines
+pub fn parse_anchor_logs(logs: &[String]) -> Vec<DecodedProgramError> {
+    logs.iter()
+        .filter_map(|log| parse_anchor_log(log))
+        .collect()
+}
+
+fn parse_anchor_log(log: &str) -> Option<DecodedProgramError> {
+    let rest = &log[log.find("AnchorError")?..];
+
+    let account = rest
+        .strip_prefix("AnchorError caused by account: ")
+        .and_then(|rest| rest.split_once(". Error Code: "))
+        .map(|(account, _)| account.to_string());
+
+    let (_, rest) = rest.split_once("Error Code: ")?;
+    let (name, rest) = rest.split_once(". Error Number: ")?;
+    let (code, msg) = rest.split_once(". Error Message: ")?;
+
+    Some(DecodedProgramError {
+        code: code.parse().ok()?,
+        name: name.to_string(),
+        msg: msg.strip_suffix('.').unwrap_or(msg).to_string(),
+        account,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fails to compile when a program error is added without updating `PRISM_ERRORS`
+    #[allow(dead_code)]
+    fn prism_errors_are_exhaustive(error: PrismErrorCode) {
+        match error {
+            PrismErrorCode::InvalidMerkleProof
+            | PrismErrorCode::MerkleRootMismatch
+            | PrismErrorCode::NumericOverflow
+            | PrismErrorCode::InvalidEntitlements
+            | PrismErrorCode::TokenAccountOwnerMismatch
+            | PrismErrorCode::CampaignAdminMismatch
+            | PrismErrorCode::CampaignFingerprintMismatch
+            | PrismErrorCode::CohortCampaignMismatch
+            | PrismErrorCode::MintMismatch
+            | PrismErrorCode::CampaignNotActive
+            | PrismErrorCode::CampaignIsActive
+            | PrismErrorCode::CampaignAlreadyActivated
+            | PrismErrorCode::CampaignIsUnstoppable
+            | PrismErrorCode::CampaignNotPaused
+            | PrismErrorCode::CampaignNotPermanentlyHalted
+            | PrismErrorCode::InvalidStatusTransition
+            | PrismErrorCode::GoLiveDateNotReached
+            | PrismErrorCode::InvalidIpfsHash
+            | PrismErrorCode::GoLiveSlotInPast
+            | PrismErrorCode::NoCohortsExpected
+            | PrismErrorCode::NotAllCohortsActivated
+            | PrismErrorCode::NoVaultsExpected
+            | PrismErrorCode::VaultIndexOutOfBounds
+            | PrismErrorCode::TooManyVaults
+            | PrismErrorCode::VaultNotInitialized
+            | PrismErrorCode::IncorrectVaultFunding
+            | PrismErrorCode::NotAllVaultsActivated
+            | PrismErrorCode::AssignedVaultIndexOutOfBounds
+            | PrismErrorCode::VaultMismatch
+            | PrismErrorCode::ClaimReceiptMismatch
+            | PrismErrorCode::EmptyClaimBatch
+            | PrismErrorCode::ClaimBatchAccountsMismatch
+            | PrismErrorCode::InvalidMerkleTreeArity
+            | PrismErrorCode::CohortTreeMismatch => {}
+        }
+    }
+
+    #[test]
+    fn test_prism_errors_table_matches_codes() {
+        for (i, error) in PRISM_ERRORS.iter().enumerate() {
+            assert_eq!(u32::from(*error), ERROR_CODE_OFFSET + i as u32);
+        }
+
+        let decoded = DecodedProgramError::from_code(6000).unwrap();
+        assert_eq!(decoded.name, "InvalidMerkleProof");
+        assert_eq!(decoded.msg, "Invalid Merkle proof provided.");
+        assert!(decoded.hint().is_some());
+
+        assert_eq!(
+            DecodedProgramError::from_code(2006).unwrap().name,
+            "ConstraintSeeds"
+        );
+        assert!(DecodedProgramError::from_code(6000 + PRISM_ERRORS.len() as u32).is_none());
+    }
+
+    #[test]
+    fn test_decode_from_anchor_logs() {
+        let logs = vec![
+            "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+            "Program log: AnchorError caused by account: campaign. Error Code: CampaignAdminMismatch. Error Number: 6005. Error Message: Campaign admin mismatch: signer is not the campaign administrator..".to_string(),
+        ];
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(6005));
+
+        let decoded = DecodedProgramError::from_transaction_error(&err, &logs).unwrap();
+        assert_eq!(decoded.code, 6005);
+        assert_eq!(decoded.name, "CampaignAdminMismatch");
+        assert_eq!(
+            decoded.msg,
+            "Campaign admin mismatch: signer is not the campaign administrator."
+        );
+        assert_eq!(decoded.account.as_deref(), Some("campaign"));
+
+        match ClientError::from(decoded) {
+            ClientError::Program { code, name, msg } => {
+                assert_eq!(code, 6005);
+                assert_eq!(name, "CampaignAdminMismatch");
+                assert!(msg.ends_with("(caused by account: campaign)"));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_without_logs() {
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(0x1770));
+        assert_eq!(
+            DecodedProgramError::from_transaction_error(&err, &[])
+                .unwrap()
+                .name,
+            "InvalidMerkleProof"
+        );
+
+        // Codes we don't know are still surfaced, just without a name
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(42));
+        let decoded = DecodedProgramError::from_transaction_error(&err, &[]).unwrap();
+        assert_eq!(decoded.name, "Unknown");
+        assert_eq!(decoded.msg, "custom program error: 0x2a");
+
+        // Non-program failures aren't program errors
+        assert!(DecodedProgramError::from_transaction_error(
+            &TransactionError::AccountNotFound,
+            &[]
+        )
+        .is_none());
+    }
+}
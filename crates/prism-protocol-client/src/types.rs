@@ -5,7 +5,9 @@ Clean data structures for transaction management and simulation results.
 */
 
 use solana_client::rpc_response::RpcSimulateTransactionResult;
-use solana_sdk::signature::Signature;
+use solana_sdk::{signature::Signature, transaction::Transaction};
+
+use crate::program_error::DecodedProgramError;
 
 /// Result of transaction operations
 #[derive(Debug)]
@@ -16,6 +18,20 @@ pub enum TransactionResult {
     Executed(Signature),
 }
 
+/// A signed transaction with the compute budget it was assembled with
+///
+/// `T` is [`VersionedTransaction`](solana_sdk::transaction::VersionedTransaction) when
+/// assembled against address lookup tables.
+#[derive(Debug)]
+pub struct AssembledTransaction<T = Transaction> {
+    /// The signed transaction, ready to send
+    pub transaction: T,
+    /// Compute unit limit set from simulation (`None` = runtime default)
+    pub compute_unit_limit: Option<u32>,
+    /// Priority fee in micro-lamports per compute unit (`None` = no priority fee)
+    pub compute_unit_price: Option<u64>,
+}
+
 /// Result of transaction simulation
 #[derive(Debug)]
 pub struct SimulationResult {
@@ -25,6 +41,8 @@ pub struct SimulationResult {
     pub compute_units: Option<u64>,
     /// Error message if simulation failed
     pub error: Option<String>,
+    /// Decoded program error if the simulation failed inside a program
+    pub program_error: Option<DecodedProgramError>,
     /// Raw simulation result
     pub raw: RpcSimulateTransactionResult,
 }
@@ -34,11 +52,18 @@ impl SimulationResult {
         let success = result.err.is_none();
         let compute_units = result.units_consumed;
         let error = result.err.as_ref().map(|e| e.to_string());
+        let program_error = result.err.as_ref().and_then(|e| {
+            DecodedProgramError::from_transaction_error(
+                e,
+                result.logs.as_deref().unwrap_or_default(),
+            )
+        });
 
         Self {
             success,
             compute_units,
             error,
+            program_error,
             raw: result,
         }
     }
@@ -25,6 +25,12 @@ Contains cohort configuration with columns:
 - `cohort`: Cohort identifier
 - `share_percentage`: Percentage share of campaign budget (Decimal, must sum to 100%)
 
+Optionally followed by allocation mode columns:
+- `allocation_mode`: `share_percentage` (default), `fixed_per_entitlement`, `cohort_budget`, or `per_claimant`
+- `allocation_value`: Amount per entitlement or absolute cohort budget, depending on mode
+  (`per_claimant` cohorts pay one base unit per entitlement)
+- `min_per_claimant` / `max_per_claimant`: Optional caps on a single claimant's allocation
+
 ## Versioning
 
 All CSV schemas include version metadata to handle evolution:
@@ -55,8 +61,8 @@ pub mod validation;
 
 // Re-export main types for convenience
 pub use errors::{CsvError, CsvResult};
-pub use schemas::{CampaignCsvRow, CohortsCsvRow, CURRENT_SCHEMA_VERSION};
+pub use schemas::{AllocationMode, CampaignCsvRow, CohortsCsvRow, CURRENT_SCHEMA_VERSION};
 pub use validation::{
-    read_campaign_csv, read_cohorts_csv, validate_csv_consistency, write_campaign_csv,
-    write_cohorts_csv,
+    read_campaign_csv, read_cohorts_csv, stream_campaign_csv, validate_allocation_modes,
+    validate_csv_consistency, validate_share_percentages, write_campaign_csv, write_cohorts_csv,
 };
@@ -59,21 +59,72 @@ pub struct CampaignCsvRow {
 /// Expected headers for cohorts CSV in exact order
 pub const COHORTS_CSV_HEADERS: &[&str] = &["cohort", "share_percentage"];
 
+/// Extended headers for cohorts CSV that select an allocation mode per cohort
+///
+/// Files using only [`COHORTS_CSV_HEADERS`] are still accepted; every cohort then
+/// uses [`AllocationMode::SharePercentage`] without claimant caps.
+pub const COHORTS_CSV_EXTENDED_HEADERS: &[&str] = &[
+    "cohort",
+    "share_percentage",
+    "allocation_mode",
+    "allocation_value",
+    "min_per_claimant",
+    "max_per_claimant",
+];
+
+/// How a cohort's token allocation is determined
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationMode {
+    /// Cohort receives `share_percentage` of the budget left after all other modes,
+    /// split evenly per entitlement
+    #[default]
+    SharePercentage,
+
+    /// Every entitlement is worth `allocation_value` tokens; the cohort budget is derived
+    FixedPerEntitlement,
+
+    /// Cohort receives exactly `allocation_value` tokens, split evenly per entitlement
+    CohortBudget,
+
+    /// Each claimant's `entitlements` in campaign.csv is their explicit token amount
+    /// (every entitlement is worth exactly one token unit)
+    PerClaimant,
+}
+
 /// Row structure for cohorts.csv
 ///
 /// **File**: `cohorts.csv`
-/// **Purpose**: Contains cohort share percentages for budget allocation
+/// **Purpose**: Contains cohort allocation settings for budget allocation
 /// **Producer**: `generate-fixtures` command or manual creation
 /// **Consumers**: `compile-campaign` command (with --budget parameter)
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct CohortsCsvRow {
     /// Cohort identifier - must match cohorts referenced in campaign.csv
     pub cohort: String,
 
     /// Percentage share of campaign budget allocated to this cohort
-    /// Must be between 0.0 and 100.0, and all cohorts must sum to 100.0
+    /// Must be between 0.0 and 100.0, and all share-percentage cohorts must sum to 100.0
+    /// Must be 0 for cohorts using any other allocation mode
     /// Uses Decimal for precise calculations (e.g., "60.5", "25.25")
     pub share_percentage: Decimal,
+
+    /// Allocation mode for this cohort (defaults to `share_percentage`)
+    #[serde(default)]
+    pub allocation_mode: AllocationMode,
+
+    /// Mode parameter: amount per entitlement for `fixed_per_entitlement`,
+    /// total tokens for `cohort_budget`; empty for the other modes
+    #[serde(default)]
+    pub allocation_value: Option<Decimal>,
+
+    /// Optional lower bound on any single claimant's allocation (in tokens)
+    #[serde(default)]
+    pub min_per_claimant: Option<Decimal>,
+
+    /// Optional upper bound on any single claimant's allocation (in tokens)
+    #[serde(default)]
+    pub max_per_claimant: Option<Decimal>,
 }
 
 // ================================================================================================
@@ -124,6 +175,29 @@ mod tests {
         assert_eq!(row, deserialized);
     }
 
+    #[test]
+    fn test_cohorts_csv_row_allocation_mode_roundtrip() {
+        let row = CohortsCsvRow {
+            cohort: "partners".to_string(),
+            share_percentage: Decimal::ZERO,
+            allocation_mode: AllocationMode::FixedPerEntitlement,
+            allocation_value: Some(Decimal::from_str("12.5").unwrap()),
+            min_per_claimant: None,
+            max_per_claimant: Some(Decimal::from(500)),
+        };
+
+        let mut wtr = csv::Writer::from_writer(vec![]);
+        wtr.serialize(&row).unwrap();
+        let csv_data = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+        assert!(csv_data.starts_with(&COHORTS_CSV_EXTENDED_HEADERS.join(",")));
+        assert!(csv_data.contains("fixed_per_entitlement,12.5,,500"));
+
+        let mut rdr = csv::Reader::from_reader(csv_data.as_bytes());
+        let deserialized: CohortsCsvRow = rdr.deserialize().next().unwrap().unwrap();
+
+        assert_eq!(row, deserialized);
+    }
+
     #[test]
     fn test_hex_serialization_roundtrip() {
         let original = [
@@ -7,7 +7,10 @@ between `generate-fixtures` and `compile-campaign` operations.
 
 use crate::{
     errors::{CsvError, CsvResult},
-    schemas::{CampaignCsvRow, CohortsCsvRow, CAMPAIGN_CSV_HEADERS, COHORTS_CSV_HEADERS},
+    schemas::{
+        AllocationMode, CampaignCsvRow, CohortsCsvRow, CAMPAIGN_CSV_HEADERS,
+        COHORTS_CSV_EXTENDED_HEADERS, COHORTS_CSV_HEADERS,
+    },
 };
 use csv::{Reader, Writer};
 use rust_decimal::Decimal;
@@ -44,14 +47,40 @@ pub fn read_campaign_csv<P: AsRef<Path>>(path: P) -> CsvResult<Vec<CampaignCsvRo
     Ok(rows)
 }
 
+/// Stream a campaign CSV file row by row, validating headers up front
+///
+/// For campaigns too large to hold in memory; unlike [`read_campaign_csv`] an empty
+/// file yields no rows instead of an error.
+pub fn stream_campaign_csv<P: AsRef<Path>>(
+    path: P,
+) -> CsvResult<impl Iterator<Item = CsvResult<CampaignCsvRow>>> {
+    let file = File::open(path)?;
+    let mut rdr = Reader::from_reader(std::io::BufReader::new(file));
+
+    let headers = rdr.headers()?;
+    validate_headers(headers.iter(), CAMPAIGN_CSV_HEADERS, "campaign.csv")?;
+
+    Ok(rdr
+        .into_deserialize()
+        .map(|result| result.map_err(CsvError::from)))
+}
+
 /// Read and validate a cohorts CSV file
+///
+/// Accepts both the legacy two-column layout and the extended layout with
+/// allocation mode columns.
 pub fn read_cohorts_csv<P: AsRef<Path>>(path: P) -> CsvResult<Vec<CohortsCsvRow>> {
     let file = File::open(path)?;
     let mut rdr = Reader::from_reader(file);
 
     // Validate headers
     let headers = rdr.headers()?;
-    validate_headers(headers.iter(), COHORTS_CSV_HEADERS, "cohorts.csv")?;
+    let expected_headers = if headers.len() == COHORTS_CSV_HEADERS.len() {
+        COHORTS_CSV_HEADERS
+    } else {
+        COHORTS_CSV_EXTENDED_HEADERS
+    };
+    validate_headers(headers.iter(), expected_headers, "cohorts.csv")?;
 
     // Read and deserialize rows
     let mut rows = Vec::new();
@@ -110,6 +139,7 @@ pub fn write_cohorts_csv<P: AsRef<Path>>(path: P, rows: &[CohortsCsvRow]) -> Csv
 /// Ensures:
 /// - All cohorts referenced in campaign.csv exist in cohorts.csv
 /// - No orphaned cohorts (cohorts defined but not used)
+/// - Allocation mode parameters and claimant caps are well-formed
 /// - Share percentages are valid (0.0-100.0) and sum to 100.0
 pub fn validate_csv_consistency(
     campaign_rows: &[CampaignCsvRow],
@@ -148,18 +178,103 @@ pub fn validate_csv_consistency(
         }
     }
 
-    // Check 3: Validate share percentages
+    // Check 3: Validate allocation modes and caps
+    validate_allocation_modes(cohorts_rows)?;
+
+    // Check 4: Validate share percentages
     validate_share_percentages(cohorts_rows)?;
 
     Ok(())
 }
 
+/// Validate per-cohort allocation mode parameters and claimant caps
+///
+/// - `fixed_per_entitlement` and `cohort_budget` require a positive `allocation_value`
+/// - `share_percentage` and `per_claimant` must leave `allocation_value` empty
+/// - Cohorts not using `share_percentage` must have a zero share percentage
+/// - Caps must be non-negative and `min_per_claimant <= max_per_claimant`
+pub fn validate_allocation_modes(cohorts_rows: &[CohortsCsvRow]) -> CsvResult<()> {
+    for row in cohorts_rows {
+        match row.allocation_mode {
+            AllocationMode::FixedPerEntitlement | AllocationMode::CohortBudget => {
+                match row.allocation_value {
+                    Some(value) if value > Decimal::ZERO => {}
+                    Some(value) => {
+                        return Err(CsvError::InvalidFormat(format!(
+                            "Cohort '{}' has invalid allocation value: {}. Must be greater than 0",
+                            row.cohort, value
+                        )));
+                    }
+                    None => {
+                        return Err(CsvError::InvalidFormat(format!(
+                            "Cohort '{}' uses {:?} allocation but has no allocation_value",
+                            row.cohort, row.allocation_mode
+                        )));
+                    }
+                }
+            }
+            AllocationMode::SharePercentage | AllocationMode::PerClaimant => {
+                if row.allocation_value.is_some() {
+                    return Err(CsvError::InvalidFormat(format!(
+                        "Cohort '{}' uses {:?} allocation, which does not take an allocation_value",
+                        row.cohort, row.allocation_mode
+                    )));
+                }
+            }
+        }
+
+        if row.allocation_mode != AllocationMode::SharePercentage
+            && row.share_percentage != Decimal::ZERO
+        {
+            return Err(CsvError::InvalidFormat(format!(
+                "Cohort '{}' uses {:?} allocation but has a non-zero share percentage: {}%",
+                row.cohort, row.allocation_mode, row.share_percentage
+            )));
+        }
+
+        for cap in [row.min_per_claimant, row.max_per_claimant]
+            .into_iter()
+            .flatten()
+        {
+            if cap < Decimal::ZERO {
+                return Err(CsvError::InvalidFormat(format!(
+                    "Cohort '{}' has negative claimant cap: {}",
+                    row.cohort, cap
+                )));
+            }
+        }
+
+        if let (Some(min), Some(max)) = (row.min_per_claimant, row.max_per_claimant) {
+            if min > max {
+                return Err(CsvError::InvalidFormat(format!(
+                    "Cohort '{}' has min_per_claimant {} greater than max_per_claimant {}",
+                    row.cohort, min, max
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Validate that share percentages are valid and sum to 100%
+///
+/// Only cohorts using [`AllocationMode::SharePercentage`] take part in the sum.
+/// A campaign without any share-percentage cohorts passes trivially.
 pub fn validate_share_percentages(cohorts_rows: &[CohortsCsvRow]) -> CsvResult<()> {
     let mut total_percentage = Decimal::ZERO;
     let hundred = Decimal::from(100);
 
-    for row in cohorts_rows {
+    let share_rows: Vec<&CohortsCsvRow> = cohorts_rows
+        .iter()
+        .filter(|row| row.allocation_mode == AllocationMode::SharePercentage)
+        .collect();
+
+    if share_rows.is_empty() {
+        return Ok(());
+    }
+
+    for row in share_rows {
         // Check individual percentage range
         if row.share_percentage < Decimal::ZERO || row.share_percentage > hundred {
             return Err(CsvError::InvalidFormat(format!(
@@ -249,16 +364,41 @@ mod tests {
         assert_eq!(rows, read_rows);
     }
 
+    #[test]
+    fn test_stream_campaign_csv() {
+        let rows: Vec<CampaignCsvRow> = (0..5)
+            .map(|i| CampaignCsvRow {
+                cohort: format!("cohort{}", i % 2),
+                claimant: Pubkey::new_unique(),
+                entitlements: i,
+            })
+            .collect();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        write_campaign_csv(temp_file.path(), &rows).unwrap();
+        let streamed: Vec<CampaignCsvRow> = stream_campaign_csv(temp_file.path())
+            .unwrap()
+            .collect::<CsvResult<_>>()
+            .unwrap();
+
+        assert_eq!(rows, streamed);
+
+        std::fs::write(temp_file.path(), "cohort,wallet,entitlements\n").unwrap();
+        assert!(stream_campaign_csv(temp_file.path()).is_err());
+    }
+
     #[test]
     fn test_write_and_read_cohorts_csv() {
         let rows = vec![
             CohortsCsvRow {
                 cohort: "earlyAdopters".to_string(),
                 share_percentage: Decimal::from(60),
+                ..Default::default()
             },
             CohortsCsvRow {
                 cohort: "powerUsers".to_string(),
                 share_percentage: Decimal::from(40),
+                ..Default::default()
             },
         ];
 
@@ -287,6 +427,7 @@ mod tests {
         let cohort_config_rows = vec![CohortsCsvRow {
             cohort: "earlyAdopters".to_string(),
             share_percentage: Decimal::from(100),
+            ..Default::default()
         }];
 
         // Should pass validation
@@ -297,10 +438,12 @@ mod tests {
             CohortsCsvRow {
                 cohort: "earlyAdopters".to_string(),
                 share_percentage: Decimal::from(60),
+                ..Default::default()
             },
             CohortsCsvRow {
                 cohort: "orphanedCohort".to_string(),
                 share_percentage: Decimal::from(40),
+                ..Default::default()
             },
         ];
 
@@ -318,10 +461,12 @@ mod tests {
             CohortsCsvRow {
                 cohort: "early".to_string(),
                 share_percentage: Decimal::from(70),
+                ..Default::default()
             },
             CohortsCsvRow {
                 cohort: "power".to_string(),
                 share_percentage: Decimal::from(30),
+                ..Default::default()
             },
         ];
 
@@ -333,10 +478,12 @@ mod tests {
             CohortsCsvRow {
                 cohort: "early".to_string(),
                 share_percentage: Decimal::from_str("60.5").unwrap(),
+                ..Default::default()
             },
             CohortsCsvRow {
                 cohort: "power".to_string(),
                 share_percentage: Decimal::from_str("39.5").unwrap(),
+                ..Default::default()
             },
         ];
 
@@ -348,10 +495,12 @@ mod tests {
             CohortsCsvRow {
                 cohort: "early".to_string(),
                 share_percentage: Decimal::from(70),
+                ..Default::default()
             },
             CohortsCsvRow {
                 cohort: "power".to_string(),
                 share_percentage: Decimal::from(40), // Total = 110%
+                ..Default::default()
             },
         ];
 
@@ -366,6 +515,7 @@ mod tests {
         let invalid_range_rows = vec![CohortsCsvRow {
             cohort: "invalid".to_string(),
             share_percentage: Decimal::from(150), // > 100%
+            ..Default::default()
         }];
 
         let result = validate_share_percentages(&invalid_range_rows);
@@ -375,4 +525,92 @@ mod tests {
             .to_string()
             .contains("Must be between 0% and 100%"));
     }
+
+    #[test]
+    fn test_read_legacy_and_extended_cohorts_csv() {
+        let legacy_file = NamedTempFile::new().unwrap();
+        std::fs::write(legacy_file.path(), "cohort,share_percentage\nearly,100\n").unwrap();
+        let legacy_rows = read_cohorts_csv(legacy_file.path()).unwrap();
+        assert_eq!(
+            legacy_rows[0].allocation_mode,
+            AllocationMode::SharePercentage
+        );
+        assert_eq!(legacy_rows[0].allocation_value, None);
+
+        let extended_file = NamedTempFile::new().unwrap();
+        std::fs::write(
+            extended_file.path(),
+            "cohort,share_percentage,allocation_mode,allocation_value,min_per_claimant,max_per_claimant\n\
+             early,100,share_percentage,,1,50\n\
+             partners,0,cohort_budget,2500,,\n",
+        )
+        .unwrap();
+        let extended_rows = read_cohorts_csv(extended_file.path()).unwrap();
+        assert_eq!(extended_rows[0].min_per_claimant, Some(Decimal::from(1)));
+        assert_eq!(extended_rows[0].max_per_claimant, Some(Decimal::from(50)));
+        assert_eq!(
+            extended_rows[1].allocation_mode,
+            AllocationMode::CohortBudget
+        );
+        assert_eq!(extended_rows[1].allocation_value, Some(Decimal::from(2500)));
+
+        validate_allocation_modes(&extended_rows).unwrap();
+        validate_share_percentages(&extended_rows).unwrap();
+    }
+
+    #[test]
+    fn test_allocation_mode_validation() {
+        // Fixed amount without a value
+        let missing_value = vec![CohortsCsvRow {
+            cohort: "fixed".to_string(),
+            allocation_mode: AllocationMode::FixedPerEntitlement,
+            ..Default::default()
+        }];
+        let result = validate_allocation_modes(&missing_value);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("no allocation_value"));
+
+        // Non-share cohort with a share percentage
+        let stray_share = vec![CohortsCsvRow {
+            cohort: "explicit".to_string(),
+            share_percentage: Decimal::from(10),
+            allocation_mode: AllocationMode::PerClaimant,
+            ..Default::default()
+        }];
+        let result = validate_allocation_modes(&stray_share);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("non-zero share percentage"));
+
+        // Inverted caps
+        let inverted_caps = vec![CohortsCsvRow {
+            cohort: "capped".to_string(),
+            share_percentage: Decimal::from(100),
+            min_per_claimant: Some(Decimal::from(10)),
+            max_per_claimant: Some(Decimal::from(5)),
+            ..Default::default()
+        }];
+        let result = validate_allocation_modes(&inverted_caps);
+        assert!(result.unwrap_err().to_string().contains("greater than"));
+
+        // Share percentages only need to sum to 100% across share cohorts
+        let mixed = vec![
+            CohortsCsvRow {
+                cohort: "early".to_string(),
+                share_percentage: Decimal::from(100),
+                ..Default::default()
+            },
+            CohortsCsvRow {
+                cohort: "partners".to_string(),
+                allocation_mode: AllocationMode::CohortBudget,
+                allocation_value: Some(Decimal::from(250)),
+                ..Default::default()
+            },
+        ];
+        validate_allocation_modes(&mixed).unwrap();
+        validate_share_percentages(&mixed).unwrap();
+    }
 }
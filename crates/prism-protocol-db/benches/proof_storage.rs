@@ -0,0 +1,210 @@
+//! Claimants table size, insert and proof lookup timings, comma-separated hex text
+//! proofs (schema version 1) against tagged BLOB proofs, and the in-place migration
+//! between them.
+//!
+//! Databases are built from the version 1 fixture, migrated for the BLOB side, with
+//! binary-tree proofs of the depth a cohort of each size would have. "Text" replays the
+//! version 1 read path: select the hex column and parse it.
+//!
+//! Run with `cargo bench -p prism-protocol-db --bench proof_storage`.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use prism_protocol_db::{CampaignDatabase, ClaimantRecord, StoredProof};
+use rusqlite::{params, Connection};
+use solana_sdk::pubkey::Pubkey;
+use tempfile::TempDir;
+
+const SIZES: [usize; 2] = [100_000, 1_000_000];
+
+/// Random lookups timed per size
+const LOOKUPS: usize = 10_000;
+
+/// Cohort present in the fixture
+const COHORT: &str = "early_users";
+
+const V1_FIXTURE: &str = include_str!("../fixtures/campaign_v1.sql");
+
+fn record(index: usize, depth: usize) -> ClaimantRecord {
+    let mut claimant = [0u8; 32];
+    claimant[..8].copy_from_slice(&(index as u64).to_le_bytes());
+    let proof = (0..depth)
+        .map(|level| {
+            let mut hash = [level as u8; 32];
+            hash[..8].copy_from_slice(&(index as u64).to_le_bytes());
+            hash
+        })
+        .collect();
+
+    ClaimantRecord {
+        claimant: Pubkey::new_from_array(claimant),
+        entitlements: index as u64 + 1,
+        assigned_vault_index: 0,
+        assigned_vault_pubkey: Pubkey::new_from_array([1; 32]),
+        merkle_proof: StoredProof::V0(proof),
+    }
+}
+
+/// Indices of the timed lookups, spread over the table by a fixed LCG
+fn lookup_indices(size: usize) -> Vec<usize> {
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    (0..LOOKUPS)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as usize % size
+        })
+        .collect()
+}
+
+/// Database file holding a fixture with its claimants removed
+fn empty_fixture(dir: &TempDir, name: &str, sql: &str) -> PathBuf {
+    let path = dir.path().join(name);
+    let conn = Connection::open(&path).unwrap();
+    conn.execute_batch(sql).unwrap();
+    conn.execute_batch("DELETE FROM claimants").unwrap();
+    path
+}
+
+fn vacuumed_size(path: &Path) -> u64 {
+    Connection::open(path)
+        .unwrap()
+        .execute_batch("VACUUM")
+        .unwrap();
+    std::fs::metadata(path).unwrap().len()
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn mib(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>5} | {:>10} {:>10} {:>10} | {:>10} {:>10} {:>10} | {:>10} {:>10}",
+        "claimants",
+        "depth",
+        "text size",
+        "text ins",
+        "text get",
+        "blob size",
+        "blob ins",
+        "blob get",
+        "migrate",
+        "migr. size"
+    );
+
+    for size in SIZES {
+        let depth = size.next_power_of_two().trailing_zeros() as usize;
+        let records: Vec<ClaimantRecord> = (0..size).map(|i| record(i, depth)).collect();
+        let lookups = lookup_indices(size);
+        let dir = TempDir::new().unwrap();
+
+        // Text: the version 1 write path
+        let text_path = empty_fixture(&dir, "text.db", V1_FIXTURE);
+        let (_, text_insert) = time(|| {
+            let mut conn = Connection::open(&text_path).unwrap();
+            let tx = conn.transaction().unwrap();
+            {
+                let mut stmt = tx
+                    .prepare("INSERT INTO claimants (claimant, cohort_name, entitlements, assigned_vault_index, assigned_vault_pubkey, merkle_proof) VALUES (?, ?, ?, ?, ?, ?)")
+                    .unwrap();
+                for record in &records {
+                    let proof_hex = record
+                        .merkle_proof
+                        .as_v0()
+                        .unwrap()
+                        .iter()
+                        .map(hex::encode)
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    stmt.execute(params![
+                        record.claimant.to_string(),
+                        COHORT,
+                        record.entitlements,
+                        record.assigned_vault_index,
+                        record.assigned_vault_pubkey.to_string(),
+                        proof_hex
+                    ])
+                    .unwrap();
+                }
+            }
+            tx.commit().unwrap();
+        });
+        let text_size = vacuumed_size(&text_path);
+
+        let (_, text_lookups) = time(|| {
+            let conn = Connection::open(&text_path).unwrap();
+            let mut stmt = conn
+                .prepare(
+                    "SELECT merkle_proof FROM claimants WHERE claimant = ? AND cohort_name = ?",
+                )
+                .unwrap();
+            for &index in &lookups {
+                let text: String = stmt
+                    .query_row(
+                        params![records[index].claimant.to_string(), COHORT],
+                        |row| row.get(0),
+                    )
+                    .unwrap();
+                assert_eq!(
+                    StoredProof::from_legacy_hex(&text).unwrap(),
+                    records[index].merkle_proof
+                );
+            }
+        });
+
+        // Blob: the current write path, into the fixture migrated while empty
+        let blob_path = empty_fixture(&dir, "blob.db", V1_FIXTURE);
+        drop(CampaignDatabase::open(&blob_path).unwrap());
+        let (_, blob_insert) = time(|| {
+            let mut db = CampaignDatabase::open(&blob_path).unwrap();
+            db.insert_claimants(COHORT, &records).unwrap();
+        });
+        let blob_size = vacuumed_size(&blob_path);
+
+        let (_, blob_lookups) = time(|| {
+            let db = CampaignDatabase::open(&blob_path).unwrap();
+            for &index in &lookups {
+                let proof = db
+                    .read_claimant_proof(&records[index].claimant, COHORT)
+                    .unwrap();
+                assert_eq!(proof, records[index].merkle_proof);
+            }
+        });
+
+        // Migration of the text database in place
+        let (_, migrate) = time(|| CampaignDatabase::open(&text_path).unwrap());
+        let migrated_size = vacuumed_size(&text_path);
+        let migrated = CampaignDatabase::open(&text_path).unwrap();
+        for &index in lookups.iter().take(100) {
+            assert_eq!(
+                migrated
+                    .read_claimant_proof(&records[index].claimant, COHORT)
+                    .unwrap(),
+                records[index].merkle_proof
+            );
+        }
+
+        println!(
+            "{:>10} {:>5} | {:>10} {:>10.2?} {:>10.2?} | {:>10} {:>10.2?} {:>10.2?} | {:>10.2?} {:>10}",
+            size,
+            depth,
+            mib(text_size),
+            text_insert,
+            text_lookups / LOOKUPS as u32,
+            mib(blob_size),
+            blob_insert,
+            blob_lookups / LOOKUPS as u32,
+            migrate,
+            mib(migrated_size)
+        );
+    }
+}
@@ -0,0 +1,73 @@
+-- Schema version 1 as first released: campaign, cohorts, claimants and vaults only.
+PRAGMA foreign_keys=OFF;
+BEGIN TRANSACTION;
+CREATE TABLE campaign (
+            fingerprint TEXT PRIMARY KEY,
+            mint TEXT NOT NULL,
+            mint_decimals INTEGER NOT NULL, -- number of decimals for the token (e.g., 9 for SOL)
+            admin TEXT NOT NULL,
+            budget TEXT NOT NULL, -- campaign budget as Decimal string (e.g., "1000.5")
+            created_at INTEGER NOT NULL,
+            deployed_at INTEGER,
+            deployed_signature TEXT, -- transaction signature for campaign deployment
+            activated_at INTEGER, -- timestamp when campaign was activated
+            activation_signature TEXT -- transaction signature for campaign activation
+        );
+INSERT INTO "campaign" VALUES('abababababababababababababababababababababababababababababababab','TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA',6,'ATokenGPvbdGVxr1b2hvZbsiqW5xPWLUBAg2DEpEoAX','1000.5','2025-06-01 12:00:00','2025-06-02 09:30:00','5xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx',NULL,NULL);
+CREATE TABLE claimants (
+            claimant TEXT NOT NULL,
+            cohort_name TEXT NOT NULL,
+            entitlements INTEGER NOT NULL,
+            assigned_vault_index INTEGER NOT NULL, -- index into vaults table
+            assigned_vault_pubkey TEXT NOT NULL, -- hex-encoded pubkey for convenience
+            merkle_proof TEXT NOT NULL, -- hex-encoded proof (comma-separated hashes)
+            claimed_at INTEGER,
+            claimed_signature TEXT, -- transaction signature for claim
+            PRIMARY KEY (claimant, cohort_name),
+            FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
+        );
+INSERT INTO "claimants" VALUES('SysvarRent111111111111111111111111111111111','early_users',1,0,'Stake11111111111111111111111111111111111111','aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa,bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb','2025-06-03 10:00:00','3zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz');
+INSERT INTO "claimants" VALUES('SysvarC1ock11111111111111111111111111111111','early_users',2,1,'Config1111111111111111111111111111111111111','cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc,dddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd',NULL,NULL);
+INSERT INTO "claimants" VALUES('SysvarRent111111111111111111111111111111111','power_users',3,0,'BPFLoaderUpgradeab1e11111111111111111111111','',NULL,NULL);
+CREATE TABLE cohorts (
+            cohort_name TEXT PRIMARY KEY,
+            merkle_root TEXT NOT NULL, -- hex-encoded [u8; 32]
+            amount_per_entitlement TEXT NOT NULL, -- u64 as string to support full range (e.g., "1000000000")
+            amount_per_entitlement_humane TEXT NOT NULL, -- human-readable amount (e.g., "0.001")
+            vault_count INTEGER NOT NULL,
+            claimant_count INTEGER NOT NULL,
+            total_tokens_required INTEGER NOT NULL,
+            deployed_at INTEGER,
+            deployed_signature TEXT -- transaction signature for cohort deployment
+        );
+INSERT INTO "cohorts" VALUES('early_users','1111111111111111111111111111111111111111111111111111111111111111','500000','0.5',2,2,1500000,'2025-06-02 09:31:00','4');
+INSERT INTO "cohorts" VALUES('power_users','2222222222222222222222222222222222222222222222222222222222222222','2000000','2',1,1,6000000,NULL,NULL);
+CREATE TABLE schema_version (
+            version INTEGER PRIMARY KEY,
+            applied_at INTEGER NOT NULL
+        );
+INSERT INTO "schema_version" VALUES(1,'2025-06-01 12:00:00');
+CREATE TABLE vaults (
+            cohort_name TEXT NOT NULL,
+            vault_index INTEGER NOT NULL,
+            vault_pubkey TEXT NOT NULL, -- hex-encoded pubkey
+            vault_keypair_path TEXT, -- optional: path to keypair file if generated
+            required_tokens INTEGER NOT NULL,
+            assigned_claimants INTEGER NOT NULL,
+            created_at INTEGER, -- timestamp when vault PDA was created on-chain
+            created_by_tx TEXT, -- transaction signature for vault creation
+            funded_at INTEGER, -- timestamp when vault was funded with tokens
+            funded_by_tx TEXT, -- transaction signature for vault funding
+            funded_amount INTEGER, -- actual amount funded (for verification)
+            funded_signature TEXT, -- newer field name for consistency
+            PRIMARY KEY (cohort_name, vault_index),
+            FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
+        );
+INSERT INTO "vaults" VALUES('early_users',0,'Stake11111111111111111111111111111111111111',NULL,500000,1,NULL,NULL,'2025-06-02 09:32:00',NULL,500000,NULL);
+INSERT INTO "vaults" VALUES('early_users',1,'Config1111111111111111111111111111111111111',NULL,1000000,1,NULL,NULL,NULL,NULL,NULL,NULL);
+INSERT INTO "vaults" VALUES('power_users',0,'BPFLoaderUpgradeab1e11111111111111111111111',NULL,6000000,1,NULL,NULL,NULL,NULL,NULL,NULL);
+CREATE INDEX idx_claimants_lookup ON claimants(claimant, cohort_name);
+CREATE INDEX idx_vaults_lookup ON vaults(cohort_name, vault_index);
+CREATE INDEX idx_claimants_cohort ON claimants(cohort_name);
+CREATE INDEX idx_vaults_cohort ON vaults(cohort_name);
+COMMIT;
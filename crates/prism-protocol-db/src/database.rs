@@ -6,14 +6,15 @@ Unified database interface for all campaign operations, replacing scattered
 */
 
 use crate::{
-    schema::{check_schema, initialize_database},
-    DbError, DbResult,
+    schema::{check_schema, initialize_database, migrate},
+    DbError, DbResult, StoredProof,
 };
 use hex;
-use rusqlite::{params, Connection};
+use prism_protocol_verify::HashScheme;
+use rusqlite::{params, types::Value, Connection, OptionalExtension};
 use rust_decimal::Decimal;
 use solana_sdk::pubkey::Pubkey;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 /// Essential campaign information from database
@@ -35,6 +36,55 @@ pub struct CohortInfo {
     pub amount_per_entitlement_humane: String, // Human-readable amount (e.g., "0.001")
     pub vaults: Vec<Pubkey>,
     pub vault_count: usize,
+    pub tree: CohortTree,
+}
+
+/// Layout of a cohort's merkle tree, which decides how it is deployed and claimed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CohortTree {
+    /// Binary tree: `initialize_cohort_v0`, claimed with `claim_tokens_v0`
+    #[default]
+    V0,
+    /// Wide tree of a fixed arity and hash scheme: `initialize_cohort_v1`, claimed with
+    /// `claim_tokens_v2`
+    V1 { arity: u16, hash_scheme: HashScheme },
+}
+
+impl CohortTree {
+    /// Stored `(tree_version, merkle_tree_arity, hash_scheme)` columns
+    fn to_columns(self) -> (i64, i64, i64) {
+        match self {
+            CohortTree::V0 => (0, 2, HashScheme::Sha256.tag() as i64),
+            CohortTree::V1 { arity, hash_scheme } => (1, arity as i64, hash_scheme.tag() as i64),
+        }
+    }
+
+    fn from_columns(version: i64, arity: i64, hash_scheme: i64) -> DbResult<Self> {
+        let hash_scheme = u8::try_from(hash_scheme)
+            .ok()
+            .and_then(HashScheme::from_tag)
+            .ok_or_else(|| {
+                DbError::Serialization(format!("Unknown hash scheme: {}", hash_scheme))
+            })?;
+
+        match version {
+            0 if hash_scheme == HashScheme::Sha256 => Ok(CohortTree::V0),
+            0 => Err(DbError::Serialization(format!(
+                "Binary V0 trees are always SHA-256, got hash scheme {}",
+                hash_scheme.tag()
+            ))),
+            1 => Ok(CohortTree::V1 {
+                arity: u16::try_from(arity).map_err(|_| {
+                    DbError::Serialization(format!("Invalid merkle tree arity: {}", arity))
+                })?,
+                hash_scheme,
+            }),
+            other => Err(DbError::Serialization(format!(
+                "Unknown cohort tree version: {}",
+                other
+            ))),
+        }
+    }
 }
 
 /// Claimant eligibility information combining database and calculated data
@@ -51,6 +101,26 @@ pub struct EligibilityInfo {
     pub db_claimed_signature: Option<String>,
 }
 
+/// Recorded claim status of one claimant row
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimStatus {
+    pub claimant: Pubkey,
+    pub assigned_vault_pubkey: Pubkey,
+    /// Whether the row records a claim
+    pub claimed: bool,
+    /// Unix timestamp of the claim (`None` if unclaimed or recorded as text by older releases)
+    pub claimed_at: Option<i64>,
+    pub claimed_signature: Option<String>,
+}
+
+/// New claim status for one claimant row; `None` fields are cleared
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimUpdate {
+    pub claimant: Pubkey,
+    pub claimed_at: Option<i64>,
+    pub claimed_signature: Option<String>,
+}
+
 /// Vault funding requirements
 #[derive(Debug, Clone)]
 pub struct VaultRequirement {
@@ -64,8 +134,79 @@ pub struct VaultRequirement {
 pub struct ClaimProof {
     pub claimant: Pubkey,
     pub cohort_name: String,
-    pub merkle_proof: Vec<String>, // Hex-encoded proof elements
+    pub merkle_proof: Vec<String>, // Hex-encoded V0 proof elements
+    pub entitlements: u64,
+}
+
+/// Claimant row written at compile time
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimantRecord {
+    pub claimant: Pubkey,
     pub entitlements: u64,
+    pub assigned_vault_index: u8,
+    pub assigned_vault_pubkey: Pubkey,
+    pub merkle_proof: StoredProof,
+}
+
+/// Campaign-level dust summary
+#[derive(Debug, Clone, PartialEq)]
+pub struct DustSummary {
+    pub policy: String,
+    pub treasury: Option<Pubkey>,
+    pub budget: Decimal,
+    pub total_funding: Decimal,
+    pub unallocated_budget: Decimal,
+    pub total_dust: Decimal,
+    /// Base units transferred to the treasury at deploy (zero unless policy is "treasury")
+    pub treasury_amount: u64,
+    /// Signature of the treasury transfer, once sent
+    pub treasury_signature: Option<String>,
+}
+
+/// Dust accounting for a single cohort
+#[derive(Debug, Clone, PartialEq)]
+pub struct CohortDust {
+    pub cohort_name: String,
+    pub cohort_budget: Decimal,
+    pub allocated: Decimal,
+    pub allocation_dust: Decimal,
+    pub funding_dust: Decimal,
+    pub redistributed: Decimal,
+}
+
+/// Funding rounding for a single vault
+#[derive(Debug, Clone, PartialEq)]
+pub struct VaultDust {
+    pub cohort_name: String,
+    pub vault_index: usize,
+    pub exact_share: Decimal,
+    pub required_tokens: u64,
+    pub dust: Decimal,
+}
+
+/// Complete dust report produced at compile time
+#[derive(Debug, Clone, PartialEq)]
+pub struct DustReport {
+    pub summary: DustSummary,
+    pub cohorts: Vec<CohortDust>,
+    pub vaults: Vec<VaultDust>,
+}
+
+/// Where a cohort's serialized claim tree lives
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeArtifactSource {
+    /// Artifact file, relative paths resolve against the database directory
+    File(PathBuf),
+    /// Artifact bytes stored in the database
+    Embedded(Vec<u8>),
+}
+
+/// Serialized claim tree recorded for a cohort
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeArtifactRecord {
+    pub cohort_name: String,
+    pub checksum: [u8; 32],
+    pub source: TreeArtifactSource,
 }
 
 /// Unified database interface for campaign operations
@@ -74,7 +215,7 @@ pub struct CampaignDatabase {
 }
 
 impl CampaignDatabase {
-    /// Open an existing database file (read-only operations)
+    /// Open an existing database file, upgrading its schema if it predates this release
     pub fn open(path: &Path) -> DbResult<Self> {
         if !path.exists() {
             return Err(DbError::InvalidConfig(format!(
@@ -87,7 +228,7 @@ impl CampaignDatabase {
             .map_err(|e| DbError::Connection(format!("Failed to open database: {}", e)))?;
 
         // Verify it has the expected schema
-        let db = Self { conn };
+        let mut db = Self { conn };
         if !db.verify_schema()? {
             return Err(DbError::InvalidConfig(format!(
                 "Database file has invalid schema: {}",
@@ -95,6 +236,9 @@ impl CampaignDatabase {
             )));
         }
 
+        // Bring databases from older releases up to date
+        migrate(&mut db.conn)?;
+
         Ok(db)
     }
 
@@ -181,7 +325,7 @@ impl CampaignDatabase {
         let mut stmt = self
             .conn
             .prepare("SELECT fingerprint, mint, mint_decimals, admin, budget FROM campaign LIMIT 1")
-            .map_err(|e| DbError::Database(e))?;
+            .map_err(DbError::Database)?;
 
         let mut rows = stmt
             .query_map([], |row| {
@@ -198,11 +342,11 @@ impl CampaignDatabase {
                     budget_str,
                 ))
             })
-            .map_err(|e| DbError::Database(e))?;
+            .map_err(DbError::Database)?;
 
         if let Some(row) = rows.next() {
             let (fingerprint_hex, mint_str, mint_decimals, admin_str, budget_str) =
-                row.map_err(|e| DbError::Database(e))?;
+                row.map_err(DbError::Database)?;
 
             let fingerprint_bytes = hex::decode(fingerprint_hex)
                 .map_err(|e| DbError::Serialization(format!("Invalid fingerprint hex: {}", e)))?;
@@ -237,8 +381,8 @@ impl CampaignDatabase {
     pub fn read_cohorts(&self) -> DbResult<Vec<CohortInfo>> {
         let mut stmt = self
             .conn
-            .prepare("SELECT cohort_name, merkle_root, amount_per_entitlement, amount_per_entitlement_humane FROM cohorts")
-            .map_err(|e| DbError::Database(e))?;
+            .prepare("SELECT cohort_name, merkle_root, amount_per_entitlement, amount_per_entitlement_humane, tree_version, merkle_tree_arity, hash_scheme FROM cohorts")
+            .map_err(DbError::Database)?;
 
         let cohort_rows = stmt
             .query_map([], |row| {
@@ -246,20 +390,27 @@ impl CampaignDatabase {
                 let merkle_root_hex: String = row.get(1)?;
                 let amount_per_entitlement_str: String = row.get(2)?;
                 let amount_per_entitlement_humane: String = row.get(3)?;
+                let tree: (i64, i64, i64) = (row.get(4)?, row.get(5)?, row.get(6)?);
                 Ok((
                     name,
                     merkle_root_hex,
                     amount_per_entitlement_str,
                     amount_per_entitlement_humane,
+                    tree,
                 ))
             })
-            .map_err(|e| DbError::Database(e))?;
+            .map_err(DbError::Database)?;
 
         let mut cohorts = Vec::new();
 
         for row in cohort_rows {
-            let (name, merkle_root_hex, amount_per_entitlement_str, amount_per_entitlement_humane) =
-                row.map_err(|e| DbError::Database(e))?;
+            let (
+                name,
+                merkle_root_hex,
+                amount_per_entitlement_str,
+                amount_per_entitlement_humane,
+                (tree_version, merkle_tree_arity, hash_scheme),
+            ) = row.map_err(DbError::Database)?;
 
             let merkle_root_bytes = hex::decode(merkle_root_hex)
                 .map_err(|e| DbError::Serialization(format!("Invalid merkle root hex: {}", e)))?;
@@ -279,18 +430,18 @@ impl CampaignDatabase {
                 .prepare(
                     "SELECT vault_pubkey FROM vaults WHERE cohort_name = ? ORDER BY vault_index",
                 )
-                .map_err(|e| DbError::Database(e))?;
+                .map_err(DbError::Database)?;
 
             let vault_rows = vault_stmt
                 .query_map([&name], |row| {
                     let vault_str: String = row.get(0)?;
                     Ok(vault_str)
                 })
-                .map_err(|e| DbError::Database(e))?;
+                .map_err(DbError::Database)?;
 
             let mut vaults = Vec::new();
             for vault_row in vault_rows {
-                let vault_str = vault_row.map_err(|e| DbError::Database(e))?;
+                let vault_str = vault_row.map_err(DbError::Database)?;
                 let vault_pubkey = Pubkey::from_str(&vault_str)
                     .map_err(|e| DbError::InvalidPubkey(format!("Invalid vault pubkey: {}", e)))?;
                 vaults.push(vault_pubkey);
@@ -304,6 +455,7 @@ impl CampaignDatabase {
                 amount_per_entitlement_humane,
                 vaults,
                 vault_count,
+                tree: CohortTree::from_columns(tree_version, merkle_tree_arity, hash_scheme)?,
             });
         }
 
@@ -327,13 +479,13 @@ impl CampaignDatabase {
              WHERE c.claimant = ?
              ORDER BY c.cohort_name",
             )
-            .map_err(|e| DbError::Database(e))?;
+            .map_err(DbError::Database)?;
 
         let rows = stmt
             .query_map([claimant.to_string()], |row| {
                 let cohort_name: String = row.get(0)?;
                 let entitlements: u64 = row.get(1)?;
-                let claimed_at: Option<i64> = row.get(2)?;
+                let claimed_at: Value = row.get(2)?;
                 let claimed_signature: Option<String> = row.get(3)?;
                 let amount_per_entitlement: u64 = row.get(4)?;
                 let merkle_root_hex: String = row.get(5)?;
@@ -347,7 +499,7 @@ impl CampaignDatabase {
                     merkle_root_hex,
                 ))
             })
-            .map_err(|e| DbError::Database(e))?;
+            .map_err(DbError::Database)?;
 
         let mut eligibility = Vec::new();
         for row in rows {
@@ -358,7 +510,7 @@ impl CampaignDatabase {
                 claimed_signature,
                 amount_per_entitlement,
                 merkle_root_hex,
-            ) = row.map_err(|e| DbError::Database(e))?;
+            ) = row.map_err(DbError::Database)?;
 
             // Parse merkle root
             let merkle_root_bytes = hex::decode(merkle_root_hex)
@@ -368,7 +520,7 @@ impl CampaignDatabase {
                 .map_err(|_| DbError::Serialization("Merkle root must be 32 bytes".to_string()))?;
 
             let total_tokens = entitlements * amount_per_entitlement;
-            let already_claimed = claimed_at.is_some();
+            let (already_claimed, claimed_at) = parse_claimed_at(claimed_at);
 
             eligibility.push(EligibilityInfo {
                 cohort_name,
@@ -393,7 +545,7 @@ impl CampaignDatabase {
                 "SELECT cohort_name, vault_index, required_tokens 
              FROM vaults ORDER BY cohort_name, vault_index",
             )
-            .map_err(|e| DbError::Database(e))?;
+            .map_err(DbError::Database)?;
 
         let vault_rows = stmt
             .query_map([], |row| {
@@ -402,12 +554,11 @@ impl CampaignDatabase {
                 let required_tokens: u64 = row.get(2)?;
                 Ok((cohort_name, vault_index, required_tokens))
             })
-            .map_err(|e| DbError::Database(e))?;
+            .map_err(DbError::Database)?;
 
         let mut vault_requirements = Vec::new();
         for row in vault_rows {
-            let (cohort_name, vault_index, required_tokens) =
-                row.map_err(|e| DbError::Database(e))?;
+            let (cohort_name, vault_index, required_tokens) = row.map_err(DbError::Database)?;
 
             vault_requirements.push(VaultRequirement {
                 cohort_name,
@@ -430,24 +581,31 @@ impl CampaignDatabase {
         // Get proof data from claimants table
         let mut stmt = self.conn.prepare(
             "SELECT merkle_proof, entitlements FROM claimants WHERE claimant = ? AND cohort_name = ?"
-        ).map_err(|e| DbError::Database(e))?;
+        ).map_err(DbError::Database)?;
 
         let mut rows = stmt
             .query_map(params![claimant.to_string(), cohort_name], |row| {
-                let proof_hex: String = row.get(0)?;
+                let proof: StoredProof = row.get(0)?;
                 let entitlements: u64 = row.get(1)?;
-                Ok((proof_hex, entitlements))
+                Ok((proof, entitlements))
             })
-            .map_err(|e| DbError::Database(e))?;
+            .map_err(DbError::Database)?;
 
         if let Some(row) = rows.next() {
-            let (proof_hex, entitlements) = row.map_err(|e| DbError::Database(e))?;
-
-            // Parse comma-separated hex proof
-            let merkle_proof: Vec<String> = proof_hex
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
+            let (proof, entitlements) = row.map_err(DbError::Database)?;
+
+            let merkle_proof: Vec<String> = proof
+                .as_v0()
+                .ok_or_else(|| {
+                    DbError::Serialization(format!(
+                        "Claimant {} in cohort {} has a {} proof; read it with read_claimant_proof",
+                        claimant,
+                        cohort_name,
+                        proof.format_name()
+                    ))
+                })?
+                .iter()
+                .map(hex::encode)
                 .collect();
 
             Ok(ClaimProof {
@@ -464,6 +622,74 @@ impl CampaignDatabase {
         }
     }
 
+    /// Read a claimant's stored proof in any format
+    pub fn read_claimant_proof(
+        &self,
+        claimant: &Pubkey,
+        cohort_name: &str,
+    ) -> DbResult<StoredProof> {
+        self.conn
+            .query_row(
+                "SELECT merkle_proof FROM claimants WHERE claimant = ? AND cohort_name = ?",
+                params![claimant.to_string(), cohort_name],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(DbError::Database)?
+            .ok_or_else(|| {
+                DbError::InvalidConfig(format!(
+                    "No merkle proof found for claimant {} in cohort {}",
+                    claimant, cohort_name
+                ))
+            })
+    }
+
+    /// Replace a claimant's stored proof
+    pub fn update_claimant_proof(
+        &mut self,
+        claimant: &Pubkey,
+        cohort_name: &str,
+        proof: &StoredProof,
+    ) -> DbResult<()> {
+        let updated = self
+            .conn
+            .execute(
+                "UPDATE claimants SET merkle_proof = ? WHERE claimant = ? AND cohort_name = ?",
+                params![proof, claimant.to_string(), cohort_name],
+            )
+            .map_err(DbError::Database)?;
+
+        if updated == 0 {
+            return Err(DbError::InvalidConfig(format!(
+                "Claimant {} not found in cohort {}",
+                claimant, cohort_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get all claimants of a cohort (ordered by claimant pubkey string)
+    pub fn read_cohort_claimants(&self, cohort_name: &str) -> DbResult<Vec<Pubkey>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT claimant FROM claimants WHERE cohort_name = ? ORDER BY claimant")
+            .map_err(DbError::Database)?;
+
+        let rows = stmt
+            .query_map([cohort_name], |row| row.get::<_, String>(0))
+            .map_err(DbError::Database)?;
+
+        let mut claimants = Vec::new();
+        for row in rows {
+            let claimant_str = row.map_err(DbError::Database)?;
+            let claimant = Pubkey::from_str(&claimant_str)
+                .map_err(|e| DbError::InvalidPubkey(format!("Invalid claimant pubkey: {}", e)))?;
+            claimants.push(claimant);
+        }
+
+        Ok(claimants)
+    }
+
     /// Get claimant vault assignment
     pub fn read_claimant_vault_assignment(
         &self,
@@ -472,7 +698,7 @@ impl CampaignDatabase {
     ) -> DbResult<(u8, Pubkey)> {
         let mut stmt = self.conn.prepare(
             "SELECT assigned_vault_index, assigned_vault_pubkey FROM claimants WHERE claimant = ? AND cohort_name = ?"
-        ).map_err(|e| DbError::Database(e))?;
+        ).map_err(DbError::Database)?;
 
         let mut rows = stmt
             .query_map(params![claimant.to_string(), cohort_name], |row| {
@@ -480,10 +706,10 @@ impl CampaignDatabase {
                 let vault_pubkey_str: String = row.get(1)?;
                 Ok((vault_index as u8, vault_pubkey_str))
             })
-            .map_err(|e| DbError::Database(e))?;
+            .map_err(DbError::Database)?;
 
         if let Some(row) = rows.next() {
-            let (vault_index, vault_pubkey_str) = row.map_err(|e| DbError::Database(e))?;
+            let (vault_index, vault_pubkey_str) = row.map_err(DbError::Database)?;
 
             let vault_pubkey = Pubkey::from_str(&vault_pubkey_str)
                 .map_err(|e| DbError::InvalidPubkey(format!("Invalid vault pubkey: {}", e)))?;
@@ -497,6 +723,74 @@ impl CampaignDatabase {
         }
     }
 
+    /// Get the compiled rows of all claimants of a cohort (ordered by claimant pubkey string)
+    pub fn read_cohort_claimant_records(&self, cohort_name: &str) -> DbResult<Vec<ClaimantRecord>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT claimant, entitlements, assigned_vault_index, assigned_vault_pubkey, merkle_proof
+             FROM claimants WHERE cohort_name = ? ORDER BY claimant",
+            )
+            .map_err(DbError::Database)?;
+
+        let rows = stmt
+            .query_map([cohort_name], |row| {
+                let claimant_str: String = row.get(0)?;
+                let entitlements: u64 = row.get(1)?;
+                let vault_index: i64 = row.get(2)?;
+                let vault_pubkey_str: String = row.get(3)?;
+                let proof: StoredProof = row.get(4)?;
+                Ok((
+                    claimant_str,
+                    entitlements,
+                    vault_index,
+                    vault_pubkey_str,
+                    proof,
+                ))
+            })
+            .map_err(DbError::Database)?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            let row = row.map_err(DbError::Database)?;
+            records.push(parse_claimant_record(row)?);
+        }
+
+        Ok(records)
+    }
+
+    /// Visit every compiled claimant row, ordered by claimant pubkey string then cohort
+    ///
+    /// Rows are streamed from the claimants primary key, so all cohorts of one claimant
+    /// arrive consecutively without loading the table into memory.
+    pub fn for_each_claimant_record<E: From<DbError>>(
+        &self,
+        mut f: impl FnMut(&str, ClaimantRecord) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT claimant, entitlements, assigned_vault_index, assigned_vault_pubkey, merkle_proof, cohort_name
+             FROM claimants ORDER BY claimant, cohort_name",
+            )
+            .map_err(DbError::Database)?;
+
+        let mut rows = stmt.query([]).map_err(DbError::Database)?;
+        while let Some(row) = rows.next().map_err(DbError::Database)? {
+            let cohort_name: String = row.get(5).map_err(DbError::Database)?;
+            let fields = (
+                row.get(0).map_err(DbError::Database)?,
+                row.get(1).map_err(DbError::Database)?,
+                row.get(2).map_err(DbError::Database)?,
+                row.get(3).map_err(DbError::Database)?,
+                row.get(4).map_err(DbError::Database)?,
+            );
+            f(&cohort_name, parse_claimant_record(fields)?)?;
+        }
+
+        Ok(())
+    }
+
     /// Update campaign deployment status
     pub fn update_campaign_deployment(&mut self, signature: &str) -> DbResult<()> {
         let tx = self
@@ -507,7 +801,30 @@ impl CampaignDatabase {
         tx.execute(
             "UPDATE campaign SET deployed_signature = ?, deployed_at = datetime('now') WHERE rowid = 1",
             params![signature],
-        ).map_err(|e| DbError::Database(e))?;
+        ).map_err(DbError::Database)?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Record the campaign's address lookup table
+    pub fn update_campaign_lookup_table(
+        &mut self,
+        lookup_table: &Pubkey,
+        signature: &str,
+    ) -> DbResult<()> {
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(format!("Failed to start transaction: {}", e)))?;
+
+        tx.execute(
+            "UPDATE campaign SET lookup_table = ?, lookup_table_signature = ? WHERE rowid = 1",
+            params![lookup_table.to_string(), signature],
+        )
+        .map_err(DbError::Database)?;
 
         tx.commit()
             .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
@@ -515,6 +832,78 @@ impl CampaignDatabase {
         Ok(())
     }
 
+    /// Read the campaign's address lookup table, if one was created
+    pub fn read_campaign_lookup_table(&self) -> DbResult<Option<Pubkey>> {
+        let lookup_table: Option<String> = self.read_campaign_column("lookup_table")?;
+
+        lookup_table
+            .map(|address| Pubkey::from_str(&address))
+            .transpose()
+            .map_err(|e| DbError::InvalidPubkey(format!("Invalid lookup table pubkey: {}", e)))
+    }
+
+    /// Record the manifest the campaign was compiled from and its go-live slot
+    pub fn update_campaign_manifest(
+        &mut self,
+        manifest_hash: &[u8; 32],
+        go_live_slot: Option<u64>,
+    ) -> DbResult<()> {
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(format!("Failed to start transaction: {}", e)))?;
+
+        tx.execute(
+            "UPDATE campaign SET manifest_hash = ?, go_live_slot = ? WHERE rowid = 1",
+            params![hex::encode(manifest_hash), go_live_slot],
+        )
+        .map_err(DbError::Database)?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Read the hash of the manifest the campaign was compiled from, if any
+    pub fn read_campaign_manifest_hash(&self) -> DbResult<Option<[u8; 32]>> {
+        let manifest_hash: Option<String> = self.read_campaign_column("manifest_hash")?;
+
+        manifest_hash
+            .map(|hash_hex| {
+                hex::decode(hash_hex)
+                    .map_err(|e| {
+                        DbError::Serialization(format!("Invalid manifest hash hex: {}", e))
+                    })?
+                    .try_into()
+                    .map_err(|_| {
+                        DbError::Serialization("Manifest hash must be 32 bytes".to_string())
+                    })
+            })
+            .transpose()
+    }
+
+    /// Read the slot from which claims are accepted, if the campaign declares one
+    pub fn read_campaign_go_live_slot(&self) -> DbResult<Option<u64>> {
+        self.read_campaign_column("go_live_slot")
+    }
+
+    /// Read a single column of the campaign row
+    fn read_campaign_column<T: rusqlite::types::FromSql>(&self, column: &str) -> DbResult<T> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM campaign LIMIT 1", column),
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    DbError::InvalidConfig("No campaign data found in database".to_string())
+                }
+                e => DbError::Database(e),
+            })
+    }
+
     /// Update vault funding status
     pub fn update_vault_funding(
         &mut self,
@@ -532,7 +921,7 @@ impl CampaignDatabase {
             "UPDATE vaults SET funded_signature = ?, funded_at = datetime('now'), funded_amount = ? 
              WHERE cohort_name = ? AND vault_index = ?",
             params![signature, amount, cohort_name, vault_index as i64],
-        ).map_err(|e| DbError::Database(e))?;
+        ).map_err(DbError::Database)?;
 
         tx.commit()
             .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
@@ -553,11 +942,11 @@ impl CampaignDatabase {
             .map_err(|e| DbError::Transaction(format!("Failed to start transaction: {}", e)))?;
 
         tx.execute(
-            "UPDATE claimants SET claimed_at = datetime('now'), claimed_signature = ? 
+            "UPDATE claimants SET claimed_at = CAST(strftime('%s', 'now') AS INTEGER), claimed_signature = ?
              WHERE claimant = ? AND cohort_name = ?",
             params![signature, claimant.to_string(), cohort_name],
         )
-        .map_err(|e| DbError::Database(e))?;
+        .map_err(DbError::Database)?;
 
         tx.commit()
             .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
@@ -565,6 +954,88 @@ impl CampaignDatabase {
         Ok(())
     }
 
+    /// Read the recorded claim status of every claimant of a cohort (ordered by claimant pubkey string)
+    pub fn read_cohort_claim_statuses(&self, cohort_name: &str) -> DbResult<Vec<ClaimStatus>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT claimant, assigned_vault_pubkey, claimed_at, claimed_signature
+             FROM claimants WHERE cohort_name = ? ORDER BY claimant",
+            )
+            .map_err(DbError::Database)?;
+
+        let rows = stmt
+            .query_map([cohort_name], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Value>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })
+            .map_err(DbError::Database)?;
+
+        let mut statuses = Vec::new();
+        for row in rows {
+            let (claimant_str, vault_str, claimed_at, claimed_signature) =
+                row.map_err(DbError::Database)?;
+            let claimant = Pubkey::from_str(&claimant_str)
+                .map_err(|e| DbError::InvalidPubkey(format!("Invalid claimant pubkey: {}", e)))?;
+            let assigned_vault_pubkey = Pubkey::from_str(&vault_str)
+                .map_err(|e| DbError::InvalidPubkey(format!("Invalid vault pubkey: {}", e)))?;
+            let (claimed, claimed_at) = parse_claimed_at(claimed_at);
+
+            statuses.push(ClaimStatus {
+                claimant,
+                assigned_vault_pubkey,
+                claimed: claimed || claimed_signature.is_some(),
+                claimed_at,
+                claimed_signature,
+            });
+        }
+
+        Ok(statuses)
+    }
+
+    /// Set the claim status of a batch of claimants of one cohort in a single transaction,
+    /// returning the number of rows changed
+    pub fn update_claim_statuses(
+        &mut self,
+        cohort_name: &str,
+        updates: &[ClaimUpdate],
+    ) -> DbResult<usize> {
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(format!("Failed to start transaction: {}", e)))?;
+
+        let mut changed = 0;
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "UPDATE claimants SET claimed_at = ?, claimed_signature = ?
+                 WHERE claimant = ? AND cohort_name = ?",
+                )
+                .map_err(DbError::Database)?;
+
+            for update in updates {
+                changed += stmt
+                    .execute(params![
+                        update.claimed_at,
+                        update.claimed_signature,
+                        update.claimant.to_string(),
+                        cohort_name
+                    ])
+                    .map_err(DbError::Database)?;
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(changed)
+    }
+
     /// Insert campaign data (for use by SDK during compilation)
     pub fn insert_campaign(
         &mut self,
@@ -580,7 +1051,7 @@ impl CampaignDatabase {
             .map_err(|e| DbError::Transaction(format!("Failed to start transaction: {}", e)))?;
 
         tx.execute(
-            "INSERT INTO campaign (fingerprint, mint, mint_decimals, admin, budget) VALUES (?, ?, ?, ?, ?)",
+            "INSERT INTO campaign (fingerprint, mint, mint_decimals, admin, budget, created_at) VALUES (?, ?, ?, ?, ?, datetime('now'))",
             params![
                 hex::encode(fingerprint),
                 mint.to_string(),
@@ -589,7 +1060,7 @@ impl CampaignDatabase {
                 budget.to_string()
             ],
         )
-        .map_err(|e| DbError::Database(e))?;
+        .map_err(DbError::Database)?;
 
         tx.commit()
             .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
@@ -598,27 +1069,39 @@ impl CampaignDatabase {
     }
 
     /// Insert cohort data (for use by SDK during compilation)
+    #[allow(clippy::too_many_arguments)]
     pub fn insert_cohort(
         &mut self,
         name: &str,
         merkle_root: [u8; 32],
         amount_per_entitlement: u64,
         amount_per_entitlement_humane: &str,
+        vault_count: usize,
+        claimant_count: usize,
+        total_tokens_required: u64,
+        tree: CohortTree,
     ) -> DbResult<()> {
+        let (tree_version, merkle_tree_arity, hash_scheme) = tree.to_columns();
         let tx = self
             .conn
             .transaction()
             .map_err(|e| DbError::Transaction(format!("Failed to start transaction: {}", e)))?;
 
         tx.execute(
-            "INSERT INTO cohorts (cohort_name, merkle_root, amount_per_entitlement, amount_per_entitlement_humane) VALUES (?, ?, ?, ?)",
+            "INSERT INTO cohorts (cohort_name, merkle_root, amount_per_entitlement, amount_per_entitlement_humane, vault_count, claimant_count, total_tokens_required, tree_version, merkle_tree_arity, hash_scheme) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             params![
                 name,
                 hex::encode(merkle_root),
                 amount_per_entitlement.to_string(),
-                amount_per_entitlement_humane
+                amount_per_entitlement_humane,
+                vault_count as i64,
+                claimant_count as i64,
+                total_tokens_required,
+                tree_version,
+                merkle_tree_arity,
+                hash_scheme
             ],
-        ).map_err(|e| DbError::Database(e))?;
+        ).map_err(DbError::Database)?;
 
         tx.commit()
             .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
@@ -626,28 +1109,96 @@ impl CampaignDatabase {
         Ok(())
     }
 
+    /// Record the claimant index root of a claimant-ordered cohort
+    pub fn update_cohort_claimant_index_root(
+        &mut self,
+        cohort_name: &str,
+        claimant_index_root: &[u8; 32],
+    ) -> DbResult<()> {
+        let updated = self
+            .conn
+            .execute(
+                "UPDATE cohorts SET claimant_index_root = ? WHERE cohort_name = ?",
+                params![hex::encode(claimant_index_root), cohort_name],
+            )
+            .map_err(DbError::Database)?;
+        if updated == 0 {
+            return Err(DbError::InvalidConfig(format!(
+                "Cohort '{}' not found",
+                cohort_name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Read a cohort's claimant index root, if it was compiled claimant-ordered
+    pub fn read_cohort_claimant_index_root(&self, cohort_name: &str) -> DbResult<Option<[u8; 32]>> {
+        let root: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT claimant_index_root FROM cohorts WHERE cohort_name = ?",
+                [cohort_name],
+                |row| row.get(0),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    DbError::InvalidConfig(format!("Cohort '{}' not found", cohort_name))
+                }
+                e => DbError::Database(e),
+            })?;
+
+        root.map(|root_hex| {
+            hex::decode(root_hex)
+                .map_err(|e| {
+                    DbError::Serialization(format!("Invalid claimant index root hex: {}", e))
+                })?
+                .try_into()
+                .map_err(|_| {
+                    DbError::Serialization("Claimant index root must be 32 bytes".to_string())
+                })
+        })
+        .transpose()
+    }
+
     /// Insert claimant data (for use by SDK during compilation)
-    pub fn insert_claimant(
+    pub fn insert_claimant(&mut self, cohort_name: &str, record: &ClaimantRecord) -> DbResult<()> {
+        self.insert_claimants(cohort_name, std::slice::from_ref(record))
+    }
+
+    /// Insert a batch of claimants of one cohort in a single transaction
+    ///
+    /// Compilation of large campaigns writes proofs in batches of a few thousand rows;
+    /// one transaction per row would dominate compile time.
+    pub fn insert_claimants(
         &mut self,
-        claimant: Pubkey,
         cohort_name: &str,
-        entitlements: u64,
-        merkle_proof: &str,
+        records: &[ClaimantRecord],
     ) -> DbResult<()> {
         let tx = self
             .conn
             .transaction()
             .map_err(|e| DbError::Transaction(format!("Failed to start transaction: {}", e)))?;
 
-        tx.execute(
-            "INSERT INTO claimants (claimant, cohort_name, entitlements, merkle_proof) VALUES (?, ?, ?, ?)",
-            params![
-                claimant.to_string(),
-                cohort_name,
-                entitlements,
-                merkle_proof
-            ],
-        ).map_err(|e| DbError::Database(e))?;
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "INSERT INTO claimants (claimant, cohort_name, entitlements, assigned_vault_index, assigned_vault_pubkey, merkle_proof) VALUES (?, ?, ?, ?, ?, ?)",
+                )
+                .map_err(DbError::Database)?;
+
+            for record in records {
+                stmt.execute(params![
+                    record.claimant.to_string(),
+                    cohort_name,
+                    record.entitlements,
+                    record.assigned_vault_index,
+                    record.assigned_vault_pubkey.to_string(),
+                    record.merkle_proof
+                ])
+                .map_err(DbError::Database)?;
+            }
+        }
 
         tx.commit()
             .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
@@ -662,6 +1213,7 @@ impl CampaignDatabase {
         vault_index: usize,
         vault_pubkey: Pubkey,
         required_tokens: u64,
+        assigned_claimants: usize,
     ) -> DbResult<()> {
         let tx = self
             .conn
@@ -669,18 +1221,350 @@ impl CampaignDatabase {
             .map_err(|e| DbError::Transaction(format!("Failed to start transaction: {}", e)))?;
 
         tx.execute(
-            "INSERT INTO vaults (cohort_name, vault_index, vault_pubkey, required_tokens) VALUES (?, ?, ?, ?)",
+            "INSERT INTO vaults (cohort_name, vault_index, vault_pubkey, required_tokens, assigned_claimants) VALUES (?, ?, ?, ?, ?)",
             params![
                 cohort_name,
                 vault_index as i64,
                 vault_pubkey.to_string(),
-                required_tokens
+                required_tokens,
+                assigned_claimants as i64
+            ],
+        ).map_err(DbError::Database)?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Insert the compile-time dust report (for use by SDK during compilation)
+    pub fn insert_dust_report(&mut self, report: &DustReport) -> DbResult<()> {
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(format!("Failed to start transaction: {}", e)))?;
+
+        let summary = &report.summary;
+        tx.execute(
+            "INSERT INTO dust_summary (id, policy, treasury, budget, total_funding, unallocated_budget, total_dust, treasury_amount, treasury_signature) VALUES (1, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                summary.policy,
+                summary.treasury.map(|t| t.to_string()),
+                summary.budget.to_string(),
+                summary.total_funding.to_string(),
+                summary.unallocated_budget.to_string(),
+                summary.total_dust.to_string(),
+                summary.treasury_amount,
+                summary.treasury_signature
             ],
-        ).map_err(|e| DbError::Database(e))?;
+        )
+        .map_err(DbError::Database)?;
+
+        for cohort in &report.cohorts {
+            tx.execute(
+                "INSERT INTO cohort_dust (cohort_name, cohort_budget, allocated, allocation_dust, funding_dust, redistributed) VALUES (?, ?, ?, ?, ?, ?)",
+                params![
+                    cohort.cohort_name,
+                    cohort.cohort_budget.to_string(),
+                    cohort.allocated.to_string(),
+                    cohort.allocation_dust.to_string(),
+                    cohort.funding_dust.to_string(),
+                    cohort.redistributed.to_string()
+                ],
+            )
+            .map_err(DbError::Database)?;
+        }
+
+        for vault in &report.vaults {
+            tx.execute(
+                "INSERT INTO vault_dust (cohort_name, vault_index, exact_share, required_tokens, dust) VALUES (?, ?, ?, ?, ?)",
+                params![
+                    vault.cohort_name,
+                    vault.vault_index as i64,
+                    vault.exact_share.to_string(),
+                    vault.required_tokens,
+                    vault.dust.to_string()
+                ],
+            )
+            .map_err(DbError::Database)?;
+        }
 
         tx.commit()
             .map_err(|e| DbError::Transaction(format!("Failed to commit transaction: {}", e)))?;
 
         Ok(())
     }
+
+    /// Record the transfer of the campaign's dust to its treasury
+    pub fn update_treasury_transfer(&mut self, signature: &str) -> DbResult<()> {
+        let updated = self
+            .conn
+            .execute(
+                "UPDATE dust_summary SET treasury_signature = ? WHERE id = 1",
+                params![signature],
+            )
+            .map_err(DbError::Database)?;
+
+        if updated == 0 {
+            return Err(DbError::InvalidConfig(
+                "Campaign has no dust report".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Read the compile-time dust report, if one was recorded
+    pub fn read_dust_report(&self) -> DbResult<Option<DustReport>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT policy, treasury, budget, total_funding, unallocated_budget, total_dust, treasury_amount, treasury_signature FROM dust_summary WHERE id = 1")
+            .map_err(DbError::Database)?;
+
+        let mut rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, u64>(6)?,
+                    row.get::<_, Option<String>>(7)?,
+                ))
+            })
+            .map_err(DbError::Database)?;
+
+        let summary = match rows.next() {
+            Some(row) => {
+                let (
+                    policy,
+                    treasury,
+                    budget,
+                    total_funding,
+                    unallocated_budget,
+                    total_dust,
+                    treasury_amount,
+                    treasury_signature,
+                ) = row.map_err(DbError::Database)?;
+
+                let treasury = treasury
+                    .map(|t| Pubkey::from_str(&t))
+                    .transpose()
+                    .map_err(|e| {
+                        DbError::InvalidPubkey(format!("Invalid treasury pubkey: {}", e))
+                    })?;
+
+                DustSummary {
+                    policy,
+                    treasury,
+                    budget: parse_decimal(&budget, "budget")?,
+                    total_funding: parse_decimal(&total_funding, "total funding")?,
+                    unallocated_budget: parse_decimal(&unallocated_budget, "unallocated budget")?,
+                    total_dust: parse_decimal(&total_dust, "total dust")?,
+                    treasury_amount,
+                    treasury_signature,
+                }
+            }
+            None => return Ok(None),
+        };
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT cohort_name, cohort_budget, allocated, allocation_dust, funding_dust, redistributed FROM cohort_dust ORDER BY cohort_name")
+            .map_err(DbError::Database)?;
+
+        let cohort_rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                ))
+            })
+            .map_err(DbError::Database)?;
+
+        let mut cohorts = Vec::new();
+        for row in cohort_rows {
+            let (
+                cohort_name,
+                cohort_budget,
+                allocated,
+                allocation_dust,
+                funding_dust,
+                redistributed,
+            ) = row.map_err(DbError::Database)?;
+
+            cohorts.push(CohortDust {
+                cohort_name,
+                cohort_budget: parse_decimal(&cohort_budget, "cohort budget")?,
+                allocated: parse_decimal(&allocated, "allocated")?,
+                allocation_dust: parse_decimal(&allocation_dust, "allocation dust")?,
+                funding_dust: parse_decimal(&funding_dust, "funding dust")?,
+                redistributed: parse_decimal(&redistributed, "redistributed dust")?,
+            });
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT cohort_name, vault_index, exact_share, required_tokens, dust FROM vault_dust ORDER BY cohort_name, vault_index")
+            .map_err(DbError::Database)?;
+
+        let vault_rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, u64>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })
+            .map_err(DbError::Database)?;
+
+        let mut vaults = Vec::new();
+        for row in vault_rows {
+            let (cohort_name, vault_index, exact_share, required_tokens, dust) =
+                row.map_err(DbError::Database)?;
+
+            vaults.push(VaultDust {
+                cohort_name,
+                vault_index: vault_index as usize,
+                exact_share: parse_decimal(&exact_share, "exact share")?,
+                required_tokens,
+                dust: parse_decimal(&dust, "vault dust")?,
+            });
+        }
+
+        Ok(Some(DustReport {
+            summary,
+            cohorts,
+            vaults,
+        }))
+    }
+
+    /// Record the serialized claim tree of a cohort, replacing any previous one
+    pub fn insert_tree_artifact(&mut self, record: &TreeArtifactRecord) -> DbResult<()> {
+        let (path, artifact) = match &record.source {
+            TreeArtifactSource::File(path) => {
+                let path = path.to_str().ok_or_else(|| {
+                    DbError::InvalidConfig(format!(
+                        "Tree artifact path is not valid UTF-8: {}",
+                        path.display()
+                    ))
+                })?;
+                (Some(path), None)
+            }
+            TreeArtifactSource::Embedded(bytes) => (None, Some(bytes.as_slice())),
+        };
+
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO tree_artifacts (cohort_name, checksum, path, artifact) VALUES (?, ?, ?, ?)",
+                params![
+                    record.cohort_name,
+                    hex::encode(record.checksum),
+                    path,
+                    artifact
+                ],
+            )
+            .map_err(DbError::Database)?;
+
+        Ok(())
+    }
+
+    /// Read the serialized claim tree recorded for a cohort, if any
+    pub fn read_tree_artifact(&self, cohort_name: &str) -> DbResult<Option<TreeArtifactRecord>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT checksum, path, artifact FROM tree_artifacts WHERE cohort_name = ?")
+            .map_err(DbError::Database)?;
+
+        let mut rows = stmt
+            .query_map([cohort_name], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<Vec<u8>>>(2)?,
+                ))
+            })
+            .map_err(DbError::Database)?;
+
+        let (checksum_hex, path, artifact) = match rows.next() {
+            Some(row) => row.map_err(DbError::Database)?,
+            None => return Ok(None),
+        };
+
+        let checksum = hex::decode(checksum_hex)
+            .map_err(|e| {
+                DbError::Serialization(format!("Invalid tree artifact checksum hex: {}", e))
+            })?
+            .try_into()
+            .map_err(|_| {
+                DbError::Serialization("Tree artifact checksum must be 32 bytes".to_string())
+            })?;
+
+        let source = match (path, artifact) {
+            (Some(path), None) => TreeArtifactSource::File(PathBuf::from(path)),
+            (None, Some(bytes)) => TreeArtifactSource::Embedded(bytes),
+            _ => {
+                return Err(DbError::Serialization(format!(
+                    "Tree artifact for cohort {} must have exactly one of path or artifact",
+                    cohort_name
+                )))
+            }
+        };
+
+        Ok(Some(TreeArtifactRecord {
+            cohort_name: cohort_name.to_string(),
+            checksum,
+            source,
+        }))
+    }
+}
+
+/// Parse a Decimal column stored as text
+fn parse_decimal(value: &str, field: &str) -> DbResult<Decimal> {
+    Decimal::from_str(value)
+        .map_err(|e| DbError::Serialization(format!("Invalid {}: {}", field, e)))
+}
+
+/// Interpret a `claimed_at` value as (claimed, unix timestamp)
+///
+/// Older releases recorded claims as `datetime('now')` text, which marks the row claimed
+/// without a usable timestamp.
+fn parse_claimed_at(value: Value) -> (bool, Option<i64>) {
+    match value {
+        Value::Null => (false, None),
+        Value::Integer(timestamp) => (true, Some(timestamp)),
+        _ => (true, None),
+    }
+}
+
+/// Parse a claimants row (claimant, entitlements, vault index, vault pubkey, proof)
+fn parse_claimant_record(
+    (claimant_str, entitlements, vault_index, vault_pubkey_str, merkle_proof): (
+        String,
+        u64,
+        i64,
+        String,
+        StoredProof,
+    ),
+) -> DbResult<ClaimantRecord> {
+    let claimant = Pubkey::from_str(&claimant_str)
+        .map_err(|e| DbError::InvalidPubkey(format!("Invalid claimant pubkey: {}", e)))?;
+    let assigned_vault_pubkey = Pubkey::from_str(&vault_pubkey_str)
+        .map_err(|e| DbError::InvalidPubkey(format!("Invalid vault pubkey: {}", e)))?;
+
+    Ok(ClaimantRecord {
+        claimant,
+        entitlements,
+        assigned_vault_index: vault_index as u8,
+        assigned_vault_pubkey,
+        merkle_proof,
+    })
 }
@@ -24,4 +24,10 @@ pub enum DbError {
 
     #[error("Connection error: {0}")]
     Connection(String),
+
+    #[error("Database schema version {0} is newer than supported version {1}; open it with a newer release")]
+    SchemaTooNew(i32, i32),
+
+    #[error("Migration error: {0}")]
+    Migration(String),
 }
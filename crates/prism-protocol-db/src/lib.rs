@@ -15,6 +15,8 @@ throughout the CLI commands by providing a clean, unified interface.
 - **Transaction Management**: Proper transaction handling for updates
 - **Error Handling**: Consistent error types across all database operations
 - **Type Safety**: Proper Pubkey parsing and hex validation
+- **Schema Migrations**: Databases from older releases are upgraded in place on open
+- **Binary Proofs**: Merkle proofs stored as tagged BLOBs for every tree format
 
 ## Usage
 
@@ -43,17 +45,26 @@ fn example() -> DbResult<()> {
 pub mod database;
 pub mod errors;
 pub mod schema;
+pub mod stored_proof;
 
 // Re-export main types for convenience
 pub use database::{
-    CampaignDatabase, CampaignInfo, ClaimProof, CohortInfo, EligibilityInfo, VaultRequirement,
+    CampaignDatabase, CampaignInfo, ClaimProof, ClaimStatus, ClaimUpdate, ClaimantRecord,
+    CohortDust, CohortInfo, CohortTree, DustReport, DustSummary, EligibilityInfo,
+    TreeArtifactRecord, TreeArtifactSource, VaultDust, VaultRequirement,
 };
 pub use errors::{DbError, DbResult};
-pub use schema::{check_schema, get_schema_version, initialize_database, SCHEMA_VERSION};
+pub use prism_protocol_verify::HashScheme;
+pub use schema::{
+    check_schema, get_schema_version, initialize_database, migrate, Migration, MIGRATIONS,
+    SCHEMA_VERSION,
+};
+pub use stored_proof::{StoredMultiProofNode, StoredProof};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rust_decimal::Decimal;
     use solana_sdk::pubkey::Pubkey;
     use std::str::FromStr;
     use tempfile::NamedTempFile;
@@ -129,6 +140,326 @@ mod tests {
         }
     }
 
+    /// Test dust report round-trip
+    #[test]
+    fn test_dust_report_roundtrip() {
+        let mut db = CampaignDatabase::create_in_memory().unwrap();
+
+        // Freshly created databases have no report
+        assert_eq!(db.read_dust_report().unwrap(), None);
+
+        db.connection()
+            .execute(
+                "INSERT INTO cohorts (cohort_name, merkle_root, amount_per_entitlement, amount_per_entitlement_humane, vault_count, claimant_count, total_tokens_required) VALUES ('Alpha', '00', '1', '1', 2, 2, 1000)",
+                [],
+            )
+            .unwrap();
+
+        let report = DustReport {
+            summary: DustSummary {
+                policy: "treasury".to_string(),
+                treasury: Some(Pubkey::from_str("11111111111111111111111111111112").unwrap()),
+                budget: Decimal::from_str("1000.123456789").unwrap(),
+                total_funding: Decimal::from(1000),
+                unallocated_budget: Decimal::ZERO,
+                total_dust: Decimal::from_str("0.123456789").unwrap(),
+                treasury_amount: 123_456_789,
+                treasury_signature: None,
+            },
+            cohorts: vec![CohortDust {
+                cohort_name: "Alpha".to_string(),
+                cohort_budget: Decimal::from_str("1000.123456789").unwrap(),
+                allocated: Decimal::from_str("1000.123456788").unwrap(),
+                allocation_dust: Decimal::from_str("0.000000001").unwrap(),
+                funding_dust: Decimal::from_str("0.123456788").unwrap(),
+                redistributed: Decimal::ZERO,
+            }],
+            vaults: vec![
+                VaultDust {
+                    cohort_name: "Alpha".to_string(),
+                    vault_index: 0,
+                    exact_share: Decimal::from_str("500.061728394").unwrap(),
+                    required_tokens: 500,
+                    dust: Decimal::from_str("0.061728394").unwrap(),
+                },
+                VaultDust {
+                    cohort_name: "Alpha".to_string(),
+                    vault_index: 1,
+                    exact_share: Decimal::from_str("500.061728394").unwrap(),
+                    required_tokens: 500,
+                    dust: Decimal::from_str("0.061728394").unwrap(),
+                },
+            ],
+        };
+
+        db.insert_dust_report(&report).unwrap();
+        assert_eq!(db.read_dust_report().unwrap(), Some(report));
+    }
+
+    /// Test lookup table address round-trip
+    #[test]
+    fn test_campaign_lookup_table_roundtrip() {
+        let mut db = CampaignDatabase::create_in_memory().unwrap();
+        db.connection()
+            .execute(
+                "INSERT INTO campaign (fingerprint, mint, mint_decimals, admin, budget, created_at) VALUES ('00', 'mint', 6, 'admin', '1000', 0)",
+                [],
+            )
+            .unwrap();
+
+        assert_eq!(db.read_campaign_lookup_table().unwrap(), None);
+
+        let lookup_table = Pubkey::new_unique();
+        db.update_campaign_lookup_table(&lookup_table, "sig")
+            .unwrap();
+        assert_eq!(db.read_campaign_lookup_table().unwrap(), Some(lookup_table));
+    }
+
+    /// Test claimant index root round-trip
+    #[test]
+    fn test_cohort_claimant_index_root_roundtrip() {
+        let mut db = CampaignDatabase::create_in_memory().unwrap();
+        db.insert_cohort("Alpha", [1; 32], 5, "0.000005", 1, 2, 30, CohortTree::V0)
+            .unwrap();
+
+        assert_eq!(db.read_cohort_claimant_index_root("Alpha").unwrap(), None);
+        db.update_cohort_claimant_index_root("Alpha", &[9; 32])
+            .unwrap();
+        assert_eq!(
+            db.read_cohort_claimant_index_root("Alpha").unwrap(),
+            Some([9; 32])
+        );
+
+        assert!(db.read_cohort_claimant_index_root("Beta").is_err());
+        assert!(db
+            .update_cohort_claimant_index_root("Beta", &[9; 32])
+            .is_err());
+    }
+
+    /// Test compile-time inserts populate every required column
+    #[test]
+    fn test_compile_inserts_roundtrip() {
+        let mut db = CampaignDatabase::create_in_memory().unwrap();
+        let mint = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+
+        db.insert_campaign([7; 32], mint, 6, admin, Decimal::from(1000))
+            .unwrap();
+        db.insert_cohort("Alpha", [1; 32], 5, "0.000005", 1, 2, 30, CohortTree::V0)
+            .unwrap();
+        db.insert_vault("Alpha", 0, vault, 30, 2).unwrap();
+
+        let records: Vec<ClaimantRecord> = (0..2)
+            .map(|i| ClaimantRecord {
+                claimant: Pubkey::new_unique(),
+                entitlements: 2 + i,
+                assigned_vault_index: 0,
+                assigned_vault_pubkey: vault,
+                merkle_proof: StoredProof::V0(vec![[i as u8; 32], [9; 32]]),
+            })
+            .collect();
+        db.insert_claimants("Alpha", &records).unwrap();
+
+        assert_eq!(db.read_campaign_info().unwrap().admin, admin);
+        assert_eq!(db.read_cohorts().unwrap()[0].vaults, vec![vault]);
+        assert_eq!(db.calculate_total_funding_required().unwrap(), 30);
+
+        let proof = db.read_merkle_proof(&records[1].claimant, "Alpha").unwrap();
+        assert_eq!(proof.entitlements, 3);
+        assert_eq!(
+            proof.merkle_proof,
+            vec![hex::encode([1u8; 32]), hex::encode([9u8; 32])]
+        );
+        assert_eq!(
+            db.read_claimant_vault_assignment(&records[0].claimant, "Alpha")
+                .unwrap(),
+            (0, vault)
+        );
+
+        let mut sorted = records.clone();
+        sorted.sort_by_key(|r| r.claimant.to_string());
+        assert_eq!(db.read_cohort_claimant_records("Alpha").unwrap(), sorted);
+
+        let mut visited = Vec::new();
+        db.for_each_claimant_record(|cohort_name, record| {
+            assert_eq!(cohort_name, "Alpha");
+            visited.push(record);
+            Ok::<_, DbError>(())
+        })
+        .unwrap();
+        assert_eq!(visited, sorted);
+
+        // A duplicate claimant rolls back the whole batch
+        let fresh = ClaimantRecord {
+            claimant: Pubkey::new_unique(),
+            ..records[0].clone()
+        };
+        assert!(db
+            .insert_claimants("Alpha", &[fresh.clone(), records[0].clone()])
+            .is_err());
+        assert!(db.read_merkle_proof(&fresh.claimant, "Alpha").is_err());
+    }
+
+    #[test]
+    fn test_claimant_proof_formats() {
+        let mut db = CampaignDatabase::create_in_memory().unwrap();
+        let vault = Pubkey::new_unique();
+        db.insert_cohort(
+            "Wide",
+            [1; 32],
+            5,
+            "0.000005",
+            1,
+            1,
+            5,
+            CohortTree::V1 {
+                arity: 4,
+                hash_scheme: HashScheme::Keccak256,
+            },
+        )
+        .unwrap();
+
+        let record = ClaimantRecord {
+            claimant: Pubkey::new_unique(),
+            entitlements: 1,
+            assigned_vault_index: 0,
+            assigned_vault_pubkey: vault,
+            merkle_proof: StoredProof::V1(vec![vec![[1; 32], [2; 32]], vec![[3; 32]]]),
+        };
+        db.insert_claimants("Wide", std::slice::from_ref(&record))
+            .unwrap();
+        assert_eq!(
+            db.read_cohorts().unwrap()[0].tree,
+            CohortTree::V1 {
+                arity: 4,
+                hash_scheme: HashScheme::Keccak256
+            }
+        );
+
+        assert_eq!(
+            db.read_claimant_proof(&record.claimant, "Wide").unwrap(),
+            record.merkle_proof
+        );
+        assert_eq!(
+            db.read_cohort_claimant_records("Wide").unwrap(),
+            vec![record.clone()]
+        );
+        // Hex proofs are only available for V0
+        assert!(db.read_merkle_proof(&record.claimant, "Wide").is_err());
+
+        let multi = StoredProof::MultiV1(vec![vec![StoredMultiProofNode {
+            known_children: 1,
+            siblings: vec![[4; 32]],
+        }]]);
+        db.update_claimant_proof(&record.claimant, "Wide", &multi)
+            .unwrap();
+        assert_eq!(
+            db.read_claimant_proof(&record.claimant, "Wide").unwrap(),
+            multi
+        );
+
+        let stranger = Pubkey::new_unique();
+        assert!(db.read_claimant_proof(&stranger, "Wide").is_err());
+        assert!(db.update_claimant_proof(&stranger, "Wide", &multi).is_err());
+    }
+
+    #[test]
+    fn test_claim_statuses() {
+        let mut db = CampaignDatabase::create_in_memory().unwrap();
+        let vault = Pubkey::new_unique();
+        db.insert_cohort("Alpha", [1; 32], 5, "0.000005", 1, 3, 15, CohortTree::V0)
+            .unwrap();
+
+        let mut records: Vec<ClaimantRecord> = (0..3)
+            .map(|_| ClaimantRecord {
+                claimant: Pubkey::new_unique(),
+                entitlements: 1,
+                assigned_vault_index: 0,
+                assigned_vault_pubkey: vault,
+                merkle_proof: StoredProof::V0(vec![]),
+            })
+            .collect();
+        records.sort_by_key(|r| r.claimant.to_string());
+        db.insert_claimants("Alpha", &records).unwrap();
+
+        // Claims made through the CLI are recorded with a unix timestamp
+        db.update_claim_status(&records[0].claimant, "Alpha", "sig0")
+            .unwrap();
+        let statuses = db.read_cohort_claim_statuses("Alpha").unwrap();
+        assert_eq!(statuses.len(), 3);
+        assert!(statuses[0].claimed);
+        assert!(statuses[0].claimed_at.unwrap() > 1_600_000_000);
+        assert_eq!(statuses[0].claimed_signature.as_deref(), Some("sig0"));
+        assert!(!statuses[1].claimed);
+        assert_eq!(statuses[1].assigned_vault_pubkey, vault);
+
+        let changed = db
+            .update_claim_statuses(
+                "Alpha",
+                &[
+                    ClaimUpdate {
+                        claimant: records[0].claimant,
+                        claimed_at: None,
+                        claimed_signature: None,
+                    },
+                    ClaimUpdate {
+                        claimant: records[2].claimant,
+                        claimed_at: Some(1_700_000_000),
+                        claimed_signature: None,
+                    },
+                    ClaimUpdate {
+                        claimant: Pubkey::new_unique(),
+                        claimed_at: Some(1),
+                        claimed_signature: None,
+                    },
+                ],
+            )
+            .unwrap();
+        assert_eq!(changed, 2);
+
+        let statuses = db.read_cohort_claim_statuses("Alpha").unwrap();
+        assert!(!statuses[0].claimed);
+        assert_eq!(statuses[0].claimed_signature, None);
+        assert_eq!(statuses[2].claimed_at, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn test_tree_artifact_roundtrip() {
+        let mut db = CampaignDatabase::create_in_memory().unwrap();
+        db.insert_cohort("Alpha", [1; 32], 5, "0.000005", 1, 2, 30, CohortTree::V0)
+            .unwrap();
+        db.insert_cohort("Beta", [2; 32], 5, "0.000005", 1, 2, 30, CohortTree::V0)
+            .unwrap();
+        assert_eq!(db.read_tree_artifact("Alpha").unwrap(), None);
+
+        let embedded = TreeArtifactRecord {
+            cohort_name: "Alpha".to_string(),
+            checksum: [7; 32],
+            source: TreeArtifactSource::Embedded(vec![1, 2, 3]),
+        };
+        db.insert_tree_artifact(&embedded).unwrap();
+        assert_eq!(db.read_tree_artifact("Alpha").unwrap(), Some(embedded));
+
+        // Recompiling a cohort replaces its artifact
+        let file = TreeArtifactRecord {
+            cohort_name: "Alpha".to_string(),
+            checksum: [8; 32],
+            source: TreeArtifactSource::File("trees/Alpha.tree".into()),
+        };
+        db.insert_tree_artifact(&file).unwrap();
+        assert_eq!(db.read_tree_artifact("Alpha").unwrap(), Some(file));
+
+        // A row must point at exactly one of a file or embedded bytes
+        assert!(db
+            .connection()
+            .execute(
+                "INSERT INTO tree_artifacts (cohort_name, checksum) VALUES ('Beta', '00')",
+                [],
+            )
+            .is_err());
+    }
+
     /// Test claimant eligibility with properly initialized database
     #[test]
     fn test_empty_eligibility_query_with_schema() {
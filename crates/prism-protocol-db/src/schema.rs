@@ -3,13 +3,45 @@
 
 This module contains the complete database schema for Prism Protocol campaigns
 and provides migration/initialization functionality.
+
+New databases get the complete current schema from [`initialize_database`]. Databases
+written by older releases are upgraded in place by [`migrate`], which applies every
+pending step of [`MIGRATIONS`] in one transaction.
+
+## Adding a migration
+
+1. Change [`initialize_database`] to create the new schema
+2. Bump [`SCHEMA_VERSION`]
+3. Append a [`Migration`] for the new version that brings the previous schema up to it
+4. Add a fixture dump of the previous version under `fixtures/` and cover it in the tests
 */
 
-use crate::{DbError, DbResult};
-use rusqlite::Connection;
+use crate::{DbError, DbResult, StoredProof};
+use rusqlite::{
+    types::{Type, Value},
+    Connection,
+};
 
 /// Current database schema version
-pub const SCHEMA_VERSION: i32 = 1;
+pub const SCHEMA_VERSION: i32 = 2;
+
+/// One step of the schema upgrade path
+pub struct Migration {
+    /// Version the schema is at after this step
+    pub version: i32,
+    /// What the step changes
+    pub description: &'static str,
+    /// Upgrade from `version - 1`; runs inside the migration transaction
+    pub apply: fn(&Connection) -> rusqlite::Result<()>,
+}
+
+/// Every schema upgrade, in version order
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: 2,
+    description:
+        "dust accounting, lookup tables, manifests, tree artifacts, binary proofs and tree layouts",
+    apply: migrate_v1_to_v2,
+}];
 
 /// Initialize database with complete schema
 pub fn initialize_database(conn: &Connection) -> DbResult<()> {
@@ -27,7 +59,11 @@ pub fn initialize_database(conn: &Connection) -> DbResult<()> {
             deployed_at INTEGER,
             deployed_signature TEXT, -- transaction signature for campaign deployment
             activated_at INTEGER, -- timestamp when campaign was activated
-            activation_signature TEXT -- transaction signature for campaign activation
+            activation_signature TEXT, -- transaction signature for campaign activation
+            lookup_table TEXT, -- address lookup table pubkey referenced by v0 claim transactions
+            lookup_table_signature TEXT, -- transaction signature for lookup table creation
+            manifest_hash TEXT, -- hex-encoded SHA-256 of the compile manifest and its claimant files
+            go_live_slot INTEGER -- slot from which claims are accepted (declared by the manifest)
         );
 
         -- Cohort information with merkle trees and deployment tracking
@@ -40,7 +76,11 @@ pub fn initialize_database(conn: &Connection) -> DbResult<()> {
             claimant_count INTEGER NOT NULL,
             total_tokens_required INTEGER NOT NULL,
             deployed_at INTEGER,
-            deployed_signature TEXT -- transaction signature for cohort deployment
+            deployed_signature TEXT, -- transaction signature for cohort deployment
+            tree_version INTEGER NOT NULL DEFAULT 0, -- 0: binary V0 tree, 1: wide V1 tree
+            merkle_tree_arity INTEGER NOT NULL DEFAULT 2, -- children per node (2 for V0 trees)
+            hash_scheme INTEGER NOT NULL DEFAULT 0, -- leaf-encoding tag: 0 SHA-256, 1 Keccak-256 (V1 trees only)
+            claimant_index_root TEXT -- hex-encoded [u8; 32], for claimant-ordered cohorts (non-membership proofs)
         );
 
         -- Individual claimant eligibility and claim tracking
@@ -50,7 +90,7 @@ pub fn initialize_database(conn: &Connection) -> DbResult<()> {
             entitlements INTEGER NOT NULL,
             assigned_vault_index INTEGER NOT NULL, -- index into vaults table
             assigned_vault_pubkey TEXT NOT NULL, -- hex-encoded pubkey for convenience
-            merkle_proof TEXT NOT NULL, -- hex-encoded proof (comma-separated hashes)
+            merkle_proof BLOB NOT NULL, -- tagged binary proof (see stored_proof)
             claimed_at INTEGER,
             claimed_signature TEXT, -- transaction signature for claim
             PRIMARY KEY (claimant, cohort_name),
@@ -75,6 +115,51 @@ pub fn initialize_database(conn: &Connection) -> DbResult<()> {
             FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
         );
 
+        -- Campaign-level dust (rounding remainder) summary, one row per campaign
+        CREATE TABLE dust_summary (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            policy TEXT NOT NULL, -- dust redistribution policy (e.g., "retain", "first-leaves")
+            treasury TEXT, -- treasury pubkey when policy is "treasury"
+            budget TEXT NOT NULL, -- campaign budget as Decimal string
+            total_funding TEXT NOT NULL, -- sum of vault funding as Decimal string
+            unallocated_budget TEXT NOT NULL, -- budget not targeted at any cohort
+            total_dust TEXT NOT NULL, -- budget minus total funding
+            treasury_amount INTEGER NOT NULL DEFAULT 0, -- base units transferred to the treasury at deploy
+            treasury_signature TEXT -- transaction signature for the treasury transfer
+        );
+
+        -- Per-cohort dust accounting
+        CREATE TABLE cohort_dust (
+            cohort_name TEXT PRIMARY KEY,
+            cohort_budget TEXT NOT NULL, -- tokens targeted at this cohort
+            allocated TEXT NOT NULL, -- tokens claimable by this cohort's leaves
+            allocation_dust TEXT NOT NULL, -- lost to per-entitlement rounding
+            funding_dust TEXT NOT NULL, -- lost flooring vault funding to whole units
+            redistributed TEXT NOT NULL, -- dust handed back to leaves by policy
+            FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
+        );
+
+        -- Per-vault funding rounding
+        CREATE TABLE vault_dust (
+            cohort_name TEXT NOT NULL,
+            vault_index INTEGER NOT NULL,
+            exact_share TEXT NOT NULL, -- cohort allocation / vault count, unrounded
+            required_tokens INTEGER NOT NULL,
+            dust TEXT NOT NULL, -- exact_share minus required_tokens (may be negative)
+            PRIMARY KEY (cohort_name, vault_index),
+            FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
+        );
+
+        -- Serialized claim tree per cohort, stored inline or as a file beside the database
+        CREATE TABLE tree_artifacts (
+            cohort_name TEXT PRIMARY KEY,
+            checksum TEXT NOT NULL, -- hex-encoded SHA-256 trailer of the artifact
+            path TEXT, -- artifact file, relative paths resolve against the database directory
+            artifact BLOB, -- embedded artifact bytes
+            CHECK ((path IS NULL) != (artifact IS NULL)),
+            FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
+        );
+
         -- Indexes for efficient lookups
         CREATE INDEX idx_claimants_lookup ON claimants(claimant, cohort_name);
         CREATE INDEX idx_vaults_lookup ON vaults(cohort_name, vault_index);
@@ -86,33 +171,200 @@ pub fn initialize_database(conn: &Connection) -> DbResult<()> {
             version INTEGER PRIMARY KEY,
             applied_at INTEGER NOT NULL
         );
-
-        INSERT INTO schema_version (version, applied_at) 
-        VALUES (?, datetime('now'));
         "#,
     )
-    .map_err(|e| DbError::Database(e))?;
+    .map_err(DbError::Database)?;
 
-    // Insert current schema version
-    conn.execute(
-        "UPDATE schema_version SET applied_at = datetime('now') WHERE version = ?",
-        [SCHEMA_VERSION],
-    )
-    .map_err(|e| DbError::Database(e))?;
+    record_schema_version(conn, SCHEMA_VERSION).map_err(DbError::Database)?;
 
     Ok(())
 }
 
+/// Upgrade a database to [`SCHEMA_VERSION`], returning the version it was at.
+///
+/// Pending migrations run in a single transaction, so a failure leaves the database
+/// untouched. Databases newer than this build are refused rather than opened.
+pub fn migrate(conn: &mut Connection) -> DbResult<i32> {
+    apply_migrations(conn, MIGRATIONS, SCHEMA_VERSION)
+}
+
+fn apply_migrations(
+    conn: &mut Connection,
+    migrations: &[Migration],
+    target_version: i32,
+) -> DbResult<i32> {
+    let version = get_schema_version(conn)?
+        .ok_or_else(|| DbError::InvalidConfig("Database has no schema version".to_string()))?;
+
+    if version > target_version {
+        return Err(DbError::SchemaTooNew(version, target_version));
+    }
+    if version == target_version {
+        return Ok(version);
+    }
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| DbError::Transaction(format!("Failed to start migration: {}", e)))?;
+
+    for migration in migrations.iter().filter(|m| m.version > version) {
+        (migration.apply)(&tx)
+            .and_then(|_| record_schema_version(&tx, migration.version))
+            .map_err(|e| {
+                DbError::Migration(format!(
+                    "Upgrade to schema version {} ({}) failed: {}",
+                    migration.version, migration.description, e
+                ))
+            })?;
+    }
+
+    tx.commit()
+        .map_err(|e| DbError::Transaction(format!("Failed to commit migration: {}", e)))?;
+
+    Ok(version)
+}
+
+fn record_schema_version(conn: &Connection, version: i32) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO schema_version (version, applied_at) VALUES (?, datetime('now'))",
+        [version],
+    )?;
+    Ok(())
+}
+
+// ================================================================================================
+// Migrations
+// ================================================================================================
+
+/// Bring a version 1 database, as first released, up to version 2:
+///
+/// - campaign: lookup table, manifest hash and go-live slot columns
+/// - cohorts: tree version, arity and hash scheme columns; every version 1 cohort is a
+///   binary SHA-256 tree, which the defaults describe. No claimant index root: version 1
+///   cohorts were never claimant-ordered
+/// - dust accounting and tree artifact tables, empty
+/// - claimants: rebuilt with proofs converted from comma-separated hex text to tagged
+///   BLOBs. Rows are copied in primary key order, so the new table is written sequentially.
+fn migrate_v1_to_v2(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE campaign ADD COLUMN lookup_table TEXT;
+        ALTER TABLE campaign ADD COLUMN lookup_table_signature TEXT;
+        ALTER TABLE campaign ADD COLUMN manifest_hash TEXT;
+        ALTER TABLE campaign ADD COLUMN go_live_slot INTEGER;
+
+        ALTER TABLE cohorts ADD COLUMN tree_version INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE cohorts ADD COLUMN merkle_tree_arity INTEGER NOT NULL DEFAULT 2;
+        ALTER TABLE cohorts ADD COLUMN hash_scheme INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE cohorts ADD COLUMN claimant_index_root TEXT;
+
+        CREATE TABLE dust_summary (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            policy TEXT NOT NULL,
+            treasury TEXT,
+            budget TEXT NOT NULL,
+            total_funding TEXT NOT NULL,
+            unallocated_budget TEXT NOT NULL,
+            total_dust TEXT NOT NULL,
+            treasury_amount INTEGER NOT NULL DEFAULT 0,
+            treasury_signature TEXT
+        );
+
+        CREATE TABLE cohort_dust (
+            cohort_name TEXT PRIMARY KEY,
+            cohort_budget TEXT NOT NULL,
+            allocated TEXT NOT NULL,
+            allocation_dust TEXT NOT NULL,
+            funding_dust TEXT NOT NULL,
+            redistributed TEXT NOT NULL,
+            FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
+        );
+
+        CREATE TABLE vault_dust (
+            cohort_name TEXT NOT NULL,
+            vault_index INTEGER NOT NULL,
+            exact_share TEXT NOT NULL,
+            required_tokens INTEGER NOT NULL,
+            dust TEXT NOT NULL,
+            PRIMARY KEY (cohort_name, vault_index),
+            FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
+        );
+
+        CREATE TABLE tree_artifacts (
+            cohort_name TEXT PRIMARY KEY,
+            checksum TEXT NOT NULL,
+            path TEXT,
+            artifact BLOB,
+            CHECK ((path IS NULL) != (artifact IS NULL)),
+            FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
+        );
+
+        CREATE TABLE claimants_v2 (
+            claimant TEXT NOT NULL,
+            cohort_name TEXT NOT NULL,
+            entitlements INTEGER NOT NULL,
+            assigned_vault_index INTEGER NOT NULL,
+            assigned_vault_pubkey TEXT NOT NULL,
+            merkle_proof BLOB NOT NULL,
+            claimed_at INTEGER,
+            claimed_signature TEXT,
+            PRIMARY KEY (claimant, cohort_name),
+            FOREIGN KEY (cohort_name) REFERENCES cohorts(cohort_name)
+        );
+        "#,
+    )?;
+
+    {
+        let mut select = conn.prepare(
+            "SELECT claimant, cohort_name, entitlements, assigned_vault_index, assigned_vault_pubkey, merkle_proof, claimed_at, claimed_signature
+             FROM claimants ORDER BY claimant, cohort_name",
+        )?;
+        let mut insert = conn.prepare(
+            "INSERT INTO claimants_v2 (claimant, cohort_name, entitlements, assigned_vault_index, assigned_vault_pubkey, merkle_proof, claimed_at, claimed_signature)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )?;
+
+        let mut rows = select.query([])?;
+        while let Some(row) = rows.next()? {
+            let proof_hex: String = row.get(5)?;
+            let proof = StoredProof::from_legacy_hex(&proof_hex).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(5, Type::Text, Box::new(e))
+            })?;
+
+            // Other columns are copied as-is, whatever their stored type
+            insert.execute(rusqlite::params![
+                row.get::<_, Value>(0)?,
+                row.get::<_, Value>(1)?,
+                row.get::<_, Value>(2)?,
+                row.get::<_, Value>(3)?,
+                row.get::<_, Value>(4)?,
+                proof,
+                row.get::<_, Value>(6)?,
+                row.get::<_, Value>(7)?,
+            ])?;
+        }
+    }
+
+    conn.execute_batch(
+        r#"
+        DROP TABLE claimants;
+        ALTER TABLE claimants_v2 RENAME TO claimants;
+        CREATE INDEX idx_claimants_lookup ON claimants(claimant, cohort_name);
+        CREATE INDEX idx_claimants_cohort ON claimants(cohort_name);
+        "#,
+    )
+}
+
 /// Check if database is properly initialized
 pub fn check_schema(conn: &Connection) -> DbResult<bool> {
     // Check if campaign table exists
     let mut stmt = conn
         .prepare("SELECT name FROM sqlite_master WHERE type='table' AND name='campaign'")
-        .map_err(|e| DbError::Database(e))?;
+        .map_err(DbError::Database)?;
 
     let mut rows = stmt
         .query_map([], |_row| Ok(()))
-        .map_err(|e| DbError::Database(e))?;
+        .map_err(DbError::Database)?;
 
     Ok(rows.next().is_some())
 }
@@ -134,18 +386,311 @@ pub fn get_schema_version(conn: &Connection) -> DbResult<Option<i32>> {
 
     let mut stmt = conn
         .prepare("SELECT version FROM schema_version ORDER BY version DESC LIMIT 1")
-        .map_err(|e| DbError::Database(e))?;
+        .map_err(DbError::Database)?;
 
     let mut rows = stmt
         .query_map([], |row| {
             let version: i32 = row.get(0)?;
             Ok(version)
         })
-        .map_err(|e| DbError::Database(e))?;
+        .map_err(DbError::Database)?;
 
     if let Some(row) = rows.next() {
-        Ok(Some(row.map_err(|e| DbError::Database(e))?))
+        Ok(Some(row.map_err(DbError::Database)?))
     } else {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CampaignDatabase, HashScheme};
+    use solana_sdk::pubkey::Pubkey;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+    use tempfile::TempDir;
+
+    const V1_FIXTURE: &str = include_str!("../fixtures/campaign_v1.sql");
+
+    /// Materialize a fixture dump as a database file
+    fn fixture_db(sql: &str) -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("campaign.db");
+        Connection::open(&path).unwrap().execute_batch(sql).unwrap();
+        (dir, path)
+    }
+
+    /// Schema objects with their (name, type, not null, primary key) columns
+    type SchemaShape = Vec<(String, Vec<(String, String, bool, i32)>)>;
+
+    fn schema_shape(conn: &Connection) -> SchemaShape {
+        let mut stmt = conn
+            .prepare(
+                "SELECT type, name FROM sqlite_master WHERE name NOT LIKE 'sqlite_%' ORDER BY name",
+            )
+            .unwrap();
+        let objects: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        objects
+            .into_iter()
+            .map(|(kind, name)| {
+                let mut stmt = conn
+                    .prepare("SELECT name, type, \"notnull\", pk FROM pragma_table_info(?)")
+                    .unwrap();
+                let columns = stmt
+                    .query_map([&name], |row| {
+                        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                    })
+                    .unwrap()
+                    .collect::<Result<_, _>>()
+                    .unwrap();
+                (format!("{} {}", kind, name), columns)
+            })
+            .collect()
+    }
+
+    fn current_shape() -> SchemaShape {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        schema_shape(&conn)
+    }
+
+    /// Data common to every fixture survives the upgrade
+    fn assert_fixture_data(db: &CampaignDatabase) {
+        let info = db.read_campaign_info().unwrap();
+        assert_eq!(info.fingerprint, [0xab; 32]);
+        assert_eq!(info.mint_decimals, 6);
+
+        let cohorts = db.read_cohorts().unwrap();
+        assert_eq!(cohorts.len(), 2);
+        assert_eq!(cohorts[0].merkle_root, [0x11; 32]);
+
+        let claimant = Pubkey::from_str("SysvarRent111111111111111111111111111111111").unwrap();
+        let proof = db.read_merkle_proof(&claimant, "early_users").unwrap();
+        assert_eq!(proof.merkle_proof, vec!["aa".repeat(32), "bb".repeat(32)]);
+        assert_eq!(
+            db.read_claimant_proof(&claimant, "early_users").unwrap(),
+            StoredProof::V0(vec![[0xaa; 32], [0xbb; 32]])
+        );
+        // Single-leaf cohorts have empty proofs
+        assert_eq!(
+            db.read_claimant_proof(&claimant, "power_users").unwrap(),
+            StoredProof::V0(vec![])
+        );
+
+        // Claims recorded as text by older releases still read as claimed
+        let statuses = db.read_cohort_claim_statuses("early_users").unwrap();
+        let status = statuses.iter().find(|s| s.claimant == claimant).unwrap();
+        assert!(status.claimed);
+        assert_eq!(status.claimed_at, None);
+        assert!(statuses.iter().any(|s| !s.claimed));
+        assert_eq!(db.calculate_total_funding_required().unwrap(), 7_500_000);
+
+        let claimed: Option<String> = db
+            .connection()
+            .query_row(
+                "SELECT claimed_signature FROM claimants WHERE claimant = ? AND cohort_name = 'early_users'",
+                [claimant.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(claimed.is_some());
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        let versions: Vec<i32> = MIGRATIONS.iter().map(|m| m.version).collect();
+        let expected: Vec<i32> = (2..=SCHEMA_VERSION).collect();
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn test_fresh_database_records_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+
+        let applied_at: Option<String> = conn
+            .query_row(
+                "SELECT applied_at FROM schema_version WHERE version = ?",
+                [SCHEMA_VERSION],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(applied_at.is_some());
+
+        // Nothing to do on a current database
+        assert_eq!(migrate(&mut conn).unwrap(), SCHEMA_VERSION);
+        let rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 1);
+    }
+
+    #[test]
+    fn test_migrates_v1_database() {
+        let (_dir, path) = fixture_db(V1_FIXTURE);
+
+        let mut db = CampaignDatabase::open(&path).unwrap();
+        assert_eq!(
+            get_schema_version(db.connection()).unwrap(),
+            Some(SCHEMA_VERSION)
+        );
+        assert_eq!(schema_shape(db.connection()), current_shape());
+        assert_fixture_data(&db);
+
+        // Storage added since version 1 is usable and empty
+        assert_eq!(db.read_campaign_lookup_table().unwrap(), None);
+        assert_eq!(db.read_campaign_manifest_hash().unwrap(), None);
+        assert!(db.read_dust_report().unwrap().is_none());
+        assert!(db.read_tree_artifact("early_users").unwrap().is_none());
+
+        let lookup_table = Pubkey::new_unique();
+        db.update_campaign_lookup_table(&lookup_table, "sig")
+            .unwrap();
+        assert_eq!(db.read_campaign_lookup_table().unwrap(), Some(lookup_table));
+
+        // Every proof is stored as a BLOB
+        let text_proofs: i64 = db
+            .connection()
+            .query_row(
+                "SELECT COUNT(*) FROM claimants WHERE typeof(merkle_proof) != 'blob'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(text_proofs, 0);
+
+        // Existing cohorts were compiled as binary SHA-256 trees
+        let tree = |db: &CampaignDatabase, name: &str| {
+            db.read_cohorts()
+                .unwrap()
+                .into_iter()
+                .find(|c| c.name == name)
+                .unwrap()
+                .tree
+        };
+        assert_eq!(tree(&db, "early_users"), crate::CohortTree::V0);
+        assert_eq!(tree(&db, "power_users"), crate::CohortTree::V0);
+
+        let keccak = crate::CohortTree::V1 {
+            arity: 4,
+            hash_scheme: HashScheme::Keccak256,
+        };
+        db.insert_cohort("evm_users", [0x44; 32], 1, "0.000001", 1, 1, 1, keccak)
+            .unwrap();
+        assert_eq!(tree(&db, "evm_users"), keccak);
+
+        // Binary trees have no other scheme
+        db.connection()
+            .execute(
+                "UPDATE cohorts SET hash_scheme = 1 WHERE cohort_name = 'early_users'",
+                [],
+            )
+            .unwrap();
+        assert!(matches!(db.read_cohorts(), Err(DbError::Serialization(_))));
+    }
+
+    #[test]
+    fn test_migration_is_persisted() {
+        let (_dir, path) = fixture_db(V1_FIXTURE);
+        drop(CampaignDatabase::open(&path).unwrap());
+
+        let mut conn = Connection::open(&path).unwrap();
+        assert_eq!(get_schema_version(&conn).unwrap(), Some(SCHEMA_VERSION));
+        assert_eq!(migrate(&mut conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_invalid_legacy_proof_aborts_migration() {
+        let (_dir, path) = fixture_db(V1_FIXTURE);
+        let conn = Connection::open(&path).unwrap();
+        conn.execute(
+            "UPDATE claimants SET merkle_proof = 'not-hex' WHERE cohort_name = 'power_users'",
+            [],
+        )
+        .unwrap();
+        let before = schema_shape(&conn);
+        drop(conn);
+
+        assert!(matches!(
+            CampaignDatabase::open(&path),
+            Err(DbError::Migration(_))
+        ));
+
+        let conn = Connection::open(&path).unwrap();
+        assert_eq!(get_schema_version(&conn).unwrap(), Some(1));
+        assert_eq!(schema_shape(&conn), before);
+    }
+
+    #[test]
+    fn test_refuses_newer_schema() {
+        let (_dir, path) = fixture_db(V1_FIXTURE);
+        Connection::open(&path)
+            .unwrap()
+            .execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (?, datetime('now'))",
+                [SCHEMA_VERSION + 1],
+            )
+            .unwrap();
+
+        match CampaignDatabase::open(&path) {
+            Err(DbError::SchemaTooNew(found, supported)) => {
+                assert_eq!(found, SCHEMA_VERSION + 1);
+                assert_eq!(supported, SCHEMA_VERSION);
+            }
+            other => panic!("Expected SchemaTooNew, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_missing_schema_version_is_rejected() {
+        let (_dir, path) = fixture_db(V1_FIXTURE);
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch("DROP TABLE schema_version")
+            .unwrap();
+
+        assert!(matches!(
+            CampaignDatabase::open(&path),
+            Err(DbError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() {
+        fn add_notes(conn: &Connection) -> rusqlite::Result<()> {
+            conn.execute_batch("CREATE TABLE notes (text TEXT)")
+        }
+        fn broken(conn: &Connection) -> rusqlite::Result<()> {
+            conn.execute_batch("ALTER TABLE missing ADD COLUMN text TEXT")
+        }
+        let migrations = [
+            Migration {
+                version: 2,
+                description: "notes",
+                apply: add_notes,
+            },
+            Migration {
+                version: 3,
+                description: "broken",
+                apply: broken,
+            },
+        ];
+
+        let (_dir, path) = fixture_db(V1_FIXTURE);
+        let mut conn = Connection::open(&path).unwrap();
+        let before = schema_shape(&conn);
+
+        let err = apply_migrations(&mut conn, &migrations, 3).unwrap_err();
+        assert!(matches!(err, DbError::Migration(ref message) if message.contains("broken")));
+
+        // Neither step nor its version row survives
+        assert_eq!(get_schema_version(&conn).unwrap(), Some(1));
+        assert_eq!(schema_shape(&conn), before);
+    }
+}
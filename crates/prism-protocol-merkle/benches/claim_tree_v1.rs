@@ -0,0 +1,153 @@
+//! ClaimTreeV1 build and proof generation timings, before and after precomputed levels.
+//!
+//! "Before" replays the previous algorithm, which rebuilt every level for each proof;
+//! its all-proofs time is extrapolated from a sample of proofs since running it to
+//! completion at 1M leaves would take hours.
+//!
+//! Run with `cargo bench -p prism-protocol-merkle --bench claim_tree_v1`.
+
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use prism_protocol_merkle::{
+    claim_tree_constants::BRANCHING_FACTOR, ClaimHasherV1, ClaimLeaf, ClaimTreeV1,
+};
+use rayon::prelude::*;
+
+const SIZES: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+/// Proofs timed per size for the previous algorithm
+const BEFORE_SAMPLE: usize = 20;
+
+fn leaves(count: usize) -> Vec<ClaimLeaf> {
+    (0..count)
+        .map(|i| {
+            let mut claimant = [0u8; 32];
+            claimant[..8].copy_from_slice(&(i as u64).to_le_bytes());
+            ClaimLeaf {
+                claimant: Pubkey::new_from_array(claimant),
+                assigned_vault_index: 0,
+                entitlements: i as u64 + 1,
+            }
+        })
+        .collect()
+}
+
+// ================================================================================================
+// Previous algorithm
+// ================================================================================================
+
+fn before_root(leaf_hashes: &[[u8; 32]]) -> [u8; 32] {
+    if leaf_hashes.len() == 1 {
+        return leaf_hashes[0];
+    }
+    let next: Vec<[u8; 32]> = leaf_hashes
+        .chunks(BRANCHING_FACTOR)
+        .map(ClaimHasherV1::hash_internal_node)
+        .collect();
+    before_root(&next)
+}
+
+fn before_proof(leaf_hashes: &[[u8; 32]], leaf_index: usize) -> Vec<Vec<[u8; 32]>> {
+    let mut proof = Vec::new();
+    let mut current_hashes = leaf_hashes.to_vec();
+    let mut current_index = leaf_index;
+
+    while current_hashes.len() > 1 {
+        let chunk_index = current_index / BRANCHING_FACTOR;
+        let position_in_chunk = current_index % BRANCHING_FACTOR;
+        let chunk = current_hashes
+            .chunks(BRANCHING_FACTOR)
+            .nth(chunk_index)
+            .unwrap();
+
+        proof.push(
+            chunk
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != position_in_chunk)
+                .map(|(_, hash)| *hash)
+                .collect(),
+        );
+
+        current_hashes = current_hashes
+            .chunks(BRANCHING_FACTOR)
+            .map(ClaimHasherV1::hash_internal_node)
+            .collect();
+        current_index = chunk_index;
+    }
+
+    proof
+}
+
+// ================================================================================================
+// Runner
+// ================================================================================================
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn main() {
+    println!(
+        "{:>10} | {:>12} {:>14} {:>16} | {:>12} {:>14} {:>16}",
+        "leaves",
+        "before build",
+        "before proof",
+        "before all (est)",
+        "after build",
+        "after proof",
+        "after all"
+    );
+
+    for size in SIZES {
+        let leaves = leaves(size);
+
+        // Before: serial root, full rebuild per proof
+        let (leaf_hashes, before_build) = time(|| {
+            let leaf_hashes: Vec<[u8; 32]> = leaves.iter().map(|leaf| leaf.to_hash()).collect();
+            std::hint::black_box(before_root(&leaf_hashes));
+            leaf_hashes
+        });
+        let step = size / BEFORE_SAMPLE;
+        let (before_proofs, before_sample) = time(|| {
+            (0..BEFORE_SAMPLE)
+                .map(|i| before_proof(&leaf_hashes, i * step))
+                .collect::<Vec<_>>()
+        });
+        let before_per_proof = before_sample / BEFORE_SAMPLE as u32;
+        let before_all = before_per_proof * size as u32;
+
+        // After: precomputed levels, direct lookup
+        let (tree, after_build) = time(|| ClaimTreeV1::from_leaves(leaves.clone()).unwrap());
+        let (_, after_sample) = time(|| {
+            for i in 0..BEFORE_SAMPLE {
+                std::hint::black_box(tree.proof_for_claimant(&leaves[i * step].claimant).unwrap());
+            }
+        });
+        let after_per_proof = after_sample / BEFORE_SAMPLE as u32;
+        let (proof_count, after_all) = time(|| tree.all_proofs().count());
+        assert_eq!(proof_count, size);
+
+        // Both algorithms must agree
+        for (i, proof) in before_proofs.into_iter().enumerate() {
+            let claimant = leaves[i * step].claimant;
+            assert_eq!(tree.proof_for_claimant(&claimant).unwrap(), proof);
+        }
+
+        println!(
+            "{:>10} | {:>12?} {:>14?} {:>16?} | {:>12?} {:>14?} {:>16?}",
+            size,
+            before_build,
+            before_per_proof,
+            before_all,
+            after_build,
+            after_per_proof,
+            after_all
+        );
+    }
+
+    println!("\nrayon threads: {}", rayon::current_num_threads());
+}
@@ -2,7 +2,12 @@ use anchor_lang::prelude::*;
 use rs_merkle::MerkleTree;
 use std::collections::HashMap;
 
-use crate::{consistent_hash_vault_assignment, ClaimHasherV0, ClaimLeaf};
+use crate::tree_artifact::{binary_levels, write_tree_artifact};
+use crate::{
+    consistent_hash_vault_assignment, ClaimHashScheme, ClaimHasherV0, ClaimLeaf, ClaimMultiProofV0,
+    ClaimantIndexTree, LeafHashTreeV0, LeafOrder, NonMembershipResult, TreeArtifactResult,
+    TreeArtifactVersion,
+};
 
 /// Creates a merkle tree using consistent hashing to assign claimants to vaults.
 ///
@@ -32,11 +37,24 @@ use crate::{consistent_hash_vault_assignment, ClaimHasherV0, ClaimLeaf};
 pub fn create_claim_tree_v0(
     claimant_entitlements: &[(Pubkey, u64)],
     vault_count: usize,
+) -> Result<ClaimTreeV0> {
+    create_claim_tree_v0_with_order(claimant_entitlements, vault_count, LeafOrder::Insertion)
+}
+
+/// Creates a claim tree with its leaves in `order`.
+///
+/// [`LeafOrder::Claimant`] trees have the same root whatever the input order.
+pub fn create_claim_tree_v0_with_order(
+    claimant_entitlements: &[(Pubkey, u64)],
+    vault_count: usize,
+    order: LeafOrder,
 ) -> Result<ClaimTreeV0> {
     require!(!claimant_entitlements.is_empty(), ErrorCode::InvalidInput);
     require!(vault_count > 0, ErrorCode::InvalidInput);
+    // Vault indexes are u8; more vaults would wrap onto indexes already in use
+    require!(vault_count <= u8::MAX as usize, ErrorCode::InvalidInput);
 
-    let leaves: Vec<ClaimLeaf> = claimant_entitlements
+    let mut leaves: Vec<ClaimLeaf> = claimant_entitlements
         .iter()
         .map(|(claimant, entitlements)| {
             // Consistent hashing: hash the claimant pubkey to determine vault assignment
@@ -49,6 +67,7 @@ pub fn create_claim_tree_v0(
             }
         })
         .collect();
+    order.apply(&mut leaves);
 
     ClaimTreeV0::from_leaves(leaves)
 }
@@ -149,6 +168,69 @@ impl ClaimTreeV0 {
 
         Ok(merkle_proof.verify(root, &[*index], &[leaf_hash], self.leaves.len()))
     }
+
+    /// Generate a multiproof for a batch of claimants
+    ///
+    /// Returns the claimants' leaves in tree order, the order [`ClaimMultiProofV0::verify`]
+    /// expects them in, with the multiproof. Rebuilds the node levels, which the
+    /// underlying rs_merkle tree doesn't expose.
+    pub fn multiproof_for_claimants(
+        &self,
+        claimants: &[Pubkey],
+    ) -> Result<(Vec<ClaimLeaf>, ClaimMultiProofV0)> {
+        require!(!claimants.is_empty(), ErrorCode::InvalidInput);
+
+        let mut indices = claimants
+            .iter()
+            .map(|claimant| self.claimant_to_index.get(claimant).copied())
+            .collect::<Option<Vec<usize>>>()
+            .ok_or(ErrorCode::ClaimantNotFound)?;
+        indices.sort_unstable();
+        indices.dedup();
+        require!(
+            indices.len() == claimants.len(),
+            ErrorCode::DuplicateClaimant
+        );
+
+        let proof = LeafHashTreeV0::from_leaves(&self.leaves)
+            .multiproof(&indices)
+            .ok_or(ErrorCode::InvalidIndex)?;
+        let leaves = indices
+            .iter()
+            .map(|&index| self.leaves[index].clone())
+            .collect();
+
+        Ok((leaves, proof))
+    }
+
+    /// Verify a multiproof for `leaves` (in tree order) with the on-chain logic
+    pub fn verify_multiproof(
+        &self,
+        leaves: &[ClaimLeaf],
+        proof: &ClaimMultiProofV0,
+    ) -> Result<bool> {
+        let root = self.root().ok_or(ErrorCode::MissingMerkleRoot)?;
+        Ok(proof.verify(&root, leaves))
+    }
+
+    /// Write the tree as a [`TreeArtifact`](crate::TreeArtifact), returning its checksum
+    pub fn write_artifact<W: std::io::Write>(&self, writer: W) -> TreeArtifactResult<[u8; 32]> {
+        write_tree_artifact(
+            writer,
+            TreeArtifactVersion::V0,
+            2,
+            ClaimHashScheme::Sha256,
+            &self.leaves,
+            &binary_levels(&self.leaves),
+        )
+    }
+
+    /// Claimant index over the leaves, for non-membership proofs
+    ///
+    /// Requires a tree built with [`LeafOrder::Claimant`].
+    pub fn claimant_index(&self) -> NonMembershipResult<ClaimantIndexTree> {
+        ClaimantIndexTree::from_leaves(self.leaves.clone(), ClaimHashScheme::Sha256)
+    }
 }
 
 /// Custom error codes for merkle tree operations
@@ -309,6 +391,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_merkle_tree_rejects_unaddressable_vaults() {
+        let claimant_entitlements = [(Pubkey::new_unique(), 1)];
+        assert!(create_claim_tree_v0(&claimant_entitlements, 0).is_err());
+        assert!(create_claim_tree_v0(&claimant_entitlements, u8::MAX as usize).is_ok());
+        assert!(create_claim_tree_v0(&claimant_entitlements, u8::MAX as usize + 1).is_err());
+    }
+
     #[test]
     fn test_consistent_hash_vault_assignment_deterministic() {
         let claimant = Pubkey::new_unique();
@@ -1,8 +1,52 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::Hasher as SolanaHasher;
+use rayon::prelude::*;
 use std::collections::HashMap;
 
-use crate::{claim_tree_constants, consistent_hash_vault_assignment, ClaimLeaf, ClaimProofV1};
+use crate::multiproof::multiproof_from_levels;
+use crate::tree_artifact::write_tree_artifact;
+use crate::{
+    claim_tree_constants, consistent_hash_vault_assignment, hash_leaves_with_scheme,
+    ClaimHashScheme, ClaimLeaf, ClaimMultiProofType, ClaimMultiProofV1, ClaimProofType,
+    ClaimProofV1, ClaimantIndexTree, LeafOrder, NonMembershipResult, TreeArtifactResult,
+    TreeArtifactVersion,
+};
+
+/// Parent nodes per rayon task when building a level
+const PARALLEL_MIN_NODES: usize = 16;
+
+/// Layout and hashing of a [`ClaimTreeV1`].
+///
+/// The arity and hash scheme must match the ones committed on-chain for the cohort.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimTreeV1Options {
+    /// Maximum children per internal node (2..=256)
+    pub arity: usize,
+    /// Leaf order of the tree
+    pub leaf_order: LeafOrder,
+    /// Hash function and leaf encoding
+    pub hash_scheme: ClaimHashScheme,
+}
+
+impl Default for ClaimTreeV1Options {
+    /// 256-ary SHA-256 tree with leaves in insertion order
+    fn default() -> Self {
+        Self {
+            arity: claim_tree_constants::BRANCHING_FACTOR,
+            leaf_order: LeafOrder::Insertion,
+            hash_scheme: ClaimHashScheme::Sha256,
+        }
+    }
+}
+
+impl ClaimTreeV1Options {
+    /// Default options with `arity` children per internal node
+    pub fn with_arity(arity: usize) -> Self {
+        Self {
+            arity,
+            ..Default::default()
+        }
+    }
+}
 
 /// Creates a clean 256-ary merkle tree for claim leaves.
 ///
@@ -20,9 +64,24 @@ use crate::{claim_tree_constants, consistent_hash_vault_assignment, ClaimLeaf, C
 pub fn create_claim_tree_v1(
     claimant_entitlements: &[(Pubkey, u64)],
     vault_count: usize,
+) -> Result<ClaimTreeV1> {
+    create_claim_tree_v1_with_options(
+        claimant_entitlements,
+        vault_count,
+        &ClaimTreeV1Options::default(),
+    )
+}
+
+/// Creates a claim tree with the arity, leaf order and hash scheme of `options`.
+pub fn create_claim_tree_v1_with_options(
+    claimant_entitlements: &[(Pubkey, u64)],
+    vault_count: usize,
+    options: &ClaimTreeV1Options,
 ) -> Result<ClaimTreeV1> {
     require!(!claimant_entitlements.is_empty(), ErrorCode::EmptyTree);
     require!(vault_count > 0, ErrorCode::InvalidInput);
+    // Vault indexes are u8; more vaults would wrap onto indexes already in use
+    require!(vault_count <= u8::MAX as usize, ErrorCode::InvalidInput);
 
     let claim_leaves: Vec<ClaimLeaf> = claimant_entitlements
         .iter()
@@ -36,18 +95,20 @@ pub fn create_claim_tree_v1(
         })
         .collect();
 
-    ClaimTreeV1::from_leaves(claim_leaves)
+    ClaimTreeV1::from_leaves_with_options(claim_leaves, options)
 }
 
 /// A clean 256-ary merkle tree implementation for claim leaves.
 ///
-/// This tree uses a width of 256 children per internal node, which provides
-/// optimal balance between proof size and tree depth for on-chain verification.
+/// This tree uses a width of 256 children per internal node by default. Narrower
+/// trees ([`ClaimTreeV1Options::arity`]) trade depth for smaller levels,
+/// which usually means smaller proofs and cheaper verification.
 ///
 /// ## Key Properties
 ///
-/// - **Width**: 256 children per internal node
-/// - **Hash function**: SHA256 with domain separation (0x01 prefix for internal nodes)
+/// - **Width**: 256 children per internal node (configurable, 2..=256)
+/// - **Hash function**: SHA256 by default, or Keccak-256 ([`ClaimHashScheme`]), with
+///   domain separation (0x01 prefix for internal nodes)
 /// - **Ordering**: Lexicographic ordering of child hashes for deterministic results
 /// - **Proof format**: Compatible with ClaimProofV1::verify
 ///
@@ -59,112 +120,119 @@ pub fn create_claim_tree_v1(
 ///
 /// ## Performance
 ///
-/// - No unnecessary dependencies (verkle trees removed)
-/// - Efficient proof generation and verification
-/// - Minimal memory footprint
+/// - Every level is built once (in parallel) and kept, so a proof is a direct
+///   lookup of at most `arity - 1` siblings per level: O(log n) per proof
+/// - [`ClaimTreeV1::all_proofs`] generates every proof in parallel
+/// - Memory is the leaf hashes plus ~1/(arity - 1) of that for the internal levels
+#[derive(Clone)]
 pub struct ClaimTreeV1 {
-    /// The root hash of the 256-ary tree
-    root_hash: [u8; 32],
     /// Mapping from claimant pubkey to leaf index for fast lookups
     pub claimant_to_index: HashMap<Pubkey, usize>,
     /// Original claim leaves for verification
     pub leaves: Vec<ClaimLeaf>,
-    /// Leaf hashes in tree order
-    leaf_hashes: Vec<[u8; 32]>,
+    /// Node hashes per level, leaf hashes (in tree order) first and the root last
+    levels: Vec<Vec<[u8; 32]>>,
+    /// Maximum children per internal node
+    arity: usize,
+    /// Hash function and leaf encoding
+    hash_scheme: ClaimHashScheme,
 }
 
 impl ClaimTreeV1 {
     /// Creates a new ClaimTreeV1 from claim leaves.
     ///
-    /// Builds a complete 256-ary tree structure and computes the root hash.
+    /// Builds a complete 256-ary tree structure, keeping every level for proof lookups.
     pub fn from_leaves(claim_leaves: Vec<ClaimLeaf>) -> Result<Self> {
+        Self::from_leaves_with_options(claim_leaves, &ClaimTreeV1Options::default())
+    }
+
+    /// Creates a tree with the arity, leaf order and hash scheme of `options`.
+    ///
+    /// Leaves are reordered as `options.leaf_order` requires.
+    pub fn from_leaves_with_options(
+        mut claim_leaves: Vec<ClaimLeaf>,
+        options: &ClaimTreeV1Options,
+    ) -> Result<Self> {
+        let ClaimTreeV1Options {
+            arity,
+            leaf_order,
+            hash_scheme,
+        } = *options;
         require!(!claim_leaves.is_empty(), ErrorCode::EmptyTree);
+        require!(
+            (claim_tree_constants::MIN_BRANCHING_FACTOR..=claim_tree_constants::BRANCHING_FACTOR)
+                .contains(&arity),
+            ErrorCode::InvalidArity
+        );
+        leaf_order.apply(&mut claim_leaves);
 
-        // Check for duplicate claimants
-        let mut seen_claimants = std::collections::HashSet::new();
-        for leaf in &claim_leaves {
+        // Build claimant mapping for fast lookups, rejecting duplicate claimants
+        let mut claimant_to_index: HashMap<Pubkey, usize> =
+            HashMap::with_capacity(claim_leaves.len());
+        for (index, leaf) in claim_leaves.iter().enumerate() {
             require!(
-                seen_claimants.insert(leaf.claimant),
+                claimant_to_index.insert(leaf.claimant, index).is_none(),
                 ErrorCode::DuplicateClaimant
             );
         }
 
-        // Build leaf hashes
-        let leaf_hashes: Vec<[u8; 32]> = claim_leaves.iter().map(|leaf| leaf.to_hash()).collect();
-
-        // Compute root hash using clean 256-ary tree algorithm
-        let root_hash = if leaf_hashes.len() == 1 {
-            // Single leaf - root is the leaf hash
-            leaf_hashes[0]
-        } else {
-            Self::compute_tree_root(&leaf_hashes)?
-        };
-
-        // Build claimant mapping for fast lookups
-        let claimant_to_index: HashMap<Pubkey, usize> = claim_leaves
-            .iter()
-            .enumerate()
-            .map(|(index, leaf)| (leaf.claimant, index))
-            .collect();
+        let levels = Self::build_levels(
+            hash_leaves_with_scheme(&claim_leaves, hash_scheme),
+            arity,
+            hash_scheme,
+        );
 
         Ok(Self {
-            root_hash,
             claimant_to_index,
             leaves: claim_leaves,
-            leaf_hashes,
+            levels,
+            arity,
+            hash_scheme,
         })
     }
 
-    /// Compute the root hash of a 256-ary tree.
+    /// Build every level of the tree, hashing each level in parallel.
     ///
-    /// This function recursively builds the tree by grouping leaves into chunks
-    /// of up to 256, hashing each chunk to create the next level, and continuing
-    /// until a single root hash remains.
-    fn compute_tree_root(leaf_hashes: &[[u8; 32]]) -> Result<[u8; 32]> {
-        if leaf_hashes.len() == 1 {
-            return Ok(leaf_hashes[0]);
-        }
-
-        if leaf_hashes.len() <= claim_tree_constants::BRANCHING_FACTOR {
-            // All leaves fit in one internal node - compute the parent hash
-            return Self::hash_internal_node(leaf_hashes);
+    /// Leaves are grouped into chunks of up to `arity`, each chunk is hashed into a
+    /// parent, and this repeats until a single root hash remains. A single leaf
+    /// is its own root.
+    pub(crate) fn build_levels(
+        leaf_hashes: Vec<[u8; 32]>,
+        arity: usize,
+        hash_scheme: ClaimHashScheme,
+    ) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![leaf_hashes];
+
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .expect("at least one level")
+                .par_chunks(arity)
+                .with_min_len(PARALLEL_MIN_NODES)
+                .map(|chunk| hash_scheme.hash_internal(&mut chunk.to_vec()))
+                .collect();
+            levels.push(next);
         }
 
-        // Multiple chunks needed - build next level
-        let next_level: Result<Vec<[u8; 32]>> = leaf_hashes
-            .chunks(claim_tree_constants::BRANCHING_FACTOR)
-            .map(|chunk| Self::hash_internal_node(chunk))
-            .collect();
-
-        let next_level = next_level?;
-        Self::compute_tree_root(&next_level)
+        levels
     }
 
-    /// Hash an internal node with its children.
-    ///
-    /// Uses SHA256 with domain separation (0x01 prefix) and lexicographic
-    /// ordering of child hashes for deterministic results.
-    fn hash_internal_node(child_hashes: &[[u8; 32]]) -> Result<[u8; 32]> {
-        // Sort child hashes for deterministic ordering
-        let mut sorted_children = child_hashes.to_vec();
-        sorted_children.sort();
-
-        // Hash with internal node domain separation
-        let mut hasher = SolanaHasher::default();
-        hasher.hash(&[claim_tree_constants::INTERNAL_PREFIX]); // Internal node prefix
-        for child_hash in sorted_children {
-            hasher.hash(&child_hash);
-        }
+    /// Returns the root hash of the tree.
+    pub fn root(&self) -> Option<[u8; 32]> {
+        self.levels.last().and_then(|level| level.first()).copied()
+    }
 
-        Ok(hasher.result().to_bytes())
+    /// Maximum children per internal node, as committed in the cohort account.
+    pub fn arity(&self) -> usize {
+        self.arity
     }
 
-    /// Returns the root hash of the tree.
-    pub fn root(&self) -> Option<[u8; 32]> {
-        Some(self.root_hash)
+    /// Hash function and leaf encoding, as committed in the cohort account.
+    pub fn hash_scheme(&self) -> ClaimHashScheme {
+        self.hash_scheme
     }
 
-    /// Generate a 256-ary merkle proof for a specific claimant.
+    /// Generate a merkle proof for a specific claimant.
     ///
     /// Returns a vector of vectors, where each inner vector contains the sibling
     /// hashes needed at that level of the tree to reconstruct the parent.
@@ -179,55 +247,54 @@ impl ClaimTreeV1 {
 
     /// Generate a proof for a leaf at the given index.
     ///
-    /// This follows the same tree structure as the root computation, collecting
-    /// sibling hashes at each level needed to reconstruct the path to the root.
+    /// Reads the siblings within the leaf's chunk at each stored level, walking up
+    /// to the root.
     fn generate_proof(&self, leaf_index: usize) -> Result<Vec<Vec<[u8; 32]>>> {
-        if self.leaf_hashes.len() == 1 {
-            // Single leaf - empty proof
-            return Ok(vec![]);
-        }
-
-        let mut proof = Vec::new();
-        let mut current_hashes = self.leaf_hashes.clone();
-        let mut current_index = leaf_index;
+        require!(leaf_index < self.leaves.len(), ErrorCode::InvalidIndex);
 
-        while current_hashes.len() > 1 {
-            // Find which chunk contains our current index
-            let chunk_index = current_index / claim_tree_constants::BRANCHING_FACTOR;
-            let position_in_chunk = current_index % claim_tree_constants::BRANCHING_FACTOR;
-
-            // Get all chunks at this level
-            let chunks: Vec<&[[u8; 32]]> = current_hashes
-                .chunks(claim_tree_constants::BRANCHING_FACTOR)
-                .collect();
-
-            if chunk_index >= chunks.len() {
-                return Err(error!(ErrorCode::InvalidIndex));
-            }
+        Ok(Self::proof_from_levels(
+            &self.levels,
+            self.arity,
+            leaf_index,
+        ))
+    }
 
-            // Collect sibling hashes within this chunk
-            let chunk = chunks[chunk_index];
-            let mut siblings = Vec::new();
+    /// Proof of the leaf at `leaf_index`, which must be in range, from stored levels.
+    pub(crate) fn proof_from_levels(
+        levels: &[Vec<[u8; 32]>],
+        arity: usize,
+        leaf_index: usize,
+    ) -> Vec<Vec<[u8; 32]>> {
+        let mut proof = Vec::with_capacity(levels.len() - 1);
+        let mut current_index = leaf_index;
 
-            for (i, &hash) in chunk.iter().enumerate() {
-                if i != position_in_chunk {
-                    siblings.push(hash);
-                }
-            }
+        for level in &levels[..levels.len() - 1] {
+            let chunk_index = current_index / arity;
+            let chunk_start = chunk_index * arity;
+            let chunk_end = (chunk_start + arity).min(level.len());
 
+            // All hashes of the chunk except our own
+            let mut siblings = Vec::with_capacity(chunk_end - chunk_start - 1);
+            siblings.extend_from_slice(&level[chunk_start..current_index]);
+            siblings.extend_from_slice(&level[current_index + 1..chunk_end]);
             proof.push(siblings);
 
-            // Build next level by hashing each chunk
-            let next_level: Result<Vec<[u8; 32]>> = current_hashes
-                .chunks(claim_tree_constants::BRANCHING_FACTOR)
-                .map(|chunk| Self::hash_internal_node(chunk))
-                .collect();
-
-            current_hashes = next_level?;
             current_index = chunk_index;
         }
 
-        Ok(proof)
+        proof
+    }
+
+    /// Proofs for every leaf, generated in parallel, in leaf order.
+    pub fn all_proofs(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = (&ClaimLeaf, Vec<Vec<[u8; 32]>>)> + '_ {
+        self.leaves.par_iter().enumerate().map(|(index, leaf)| {
+            let proof = self
+                .generate_proof(index)
+                .expect("every leaf index has a proof");
+            (leaf, proof)
+        })
     }
 
     /// Generate merkle proofs for multiple claimants.
@@ -255,16 +322,81 @@ impl ClaimTreeV1 {
             .ok_or_else(|| error!(ErrorCode::InvalidIndex))
     }
 
-    /// Verify a 256-ary merkle proof for a given claimant.
+    /// Verify a merkle proof for a given claimant.
     ///
-    /// This uses the existing ClaimProofV1::verify logic for compatibility.
+    /// This uses the on-chain ClaimProofV1 logic, including the arity bound.
     pub fn verify_proof(&self, claimant: &Pubkey, proof: &[Vec<[u8; 32]>]) -> Result<bool> {
         let leaf = self.leaf_for_claimant(claimant)?;
         let root = self.root().ok_or(ErrorCode::MissingMerkleRoot)?;
 
         // Use the ClaimProofV1 verification logic for compatibility
-        let claim_proof = ClaimProofV1::new(proof.to_vec());
-        Ok(claim_proof.verify(&root, leaf))
+        let claim_proof = ClaimProofType::from_wide(proof.to_vec());
+        Ok(claim_proof.verify_with_scheme(&root, leaf, self.arity as u16, self.hash_scheme))
+    }
+
+    /// Generate a multiproof for a batch of claimants.
+    ///
+    /// Returns the claimants' leaves in tree order, the order [`ClaimMultiProofV1::verify`]
+    /// expects them in, with the multiproof. Siblings shared between the claimants' paths
+    /// are carried once, and nodes the batch covers entirely carry none.
+    pub fn multiproof_for_claimants(
+        &self,
+        claimants: &[Pubkey],
+    ) -> Result<(Vec<ClaimLeaf>, ClaimMultiProofV1)> {
+        require!(!claimants.is_empty(), ErrorCode::InvalidInput);
+
+        let mut indices = claimants
+            .iter()
+            .map(|claimant| self.claimant_to_index.get(claimant).copied())
+            .collect::<Option<Vec<usize>>>()
+            .ok_or(ErrorCode::ClaimantNotFound)?;
+        indices.sort_unstable();
+        indices.dedup();
+        require!(
+            indices.len() == claimants.len(),
+            ErrorCode::DuplicateClaimant
+        );
+
+        let proof = multiproof_from_levels(&self.levels, self.arity, &indices);
+        let leaves = indices
+            .iter()
+            .map(|&index| self.leaves[index].clone())
+            .collect();
+
+        Ok((leaves, ClaimMultiProofV1::new(proof)))
+    }
+
+    /// Verify a multiproof for `leaves` (in tree order) with the on-chain logic.
+    pub fn verify_multiproof(
+        &self,
+        leaves: &[ClaimLeaf],
+        proof: &ClaimMultiProofV1,
+    ) -> Result<bool> {
+        let root = self.root().ok_or(ErrorCode::MissingMerkleRoot)?;
+        Ok(ClaimMultiProofType::V1(proof.clone()).verify_with_scheme(
+            &root,
+            leaves,
+            self.arity as u16,
+            self.hash_scheme,
+        ))
+    }
+
+    /// Write the tree as a [`TreeArtifact`](crate::TreeArtifact), returning its checksum.
+    pub fn write_artifact<W: std::io::Write>(&self, writer: W) -> TreeArtifactResult<[u8; 32]> {
+        write_tree_artifact(
+            writer,
+            TreeArtifactVersion::V1,
+            self.arity,
+            self.hash_scheme,
+            &self.leaves,
+            &self.levels,
+        )
+    }
+
+    /// Claimant index over the leaves with this tree's hash scheme, for non-membership
+    /// proofs. Requires a tree built with [`LeafOrder::Claimant`].
+    pub fn claimant_index(&self) -> NonMembershipResult<ClaimantIndexTree> {
+        ClaimantIndexTree::from_leaves(self.leaves.clone(), self.hash_scheme)
     }
 
     /// Creates a ClaimProofV1 for a specific claimant.
@@ -280,21 +412,7 @@ impl ClaimTreeV1 {
 
     /// Returns the depth of the tree (number of levels from leaf to root).
     pub fn depth(&self) -> usize {
-        if self.leaves.len() <= 1 {
-            return 0;
-        }
-
-        // Calculate depth based on 256-ary tree structure
-        let mut remaining = self.leaves.len();
-        let mut depth = 0;
-
-        while remaining > 1 {
-            remaining = (remaining + claim_tree_constants::BRANCHING_FACTOR - 1)
-                / claim_tree_constants::BRANCHING_FACTOR; // Ceiling division
-            depth += 1;
-        }
-
-        depth
+        self.levels.len() - 1
     }
 }
 
@@ -312,11 +430,14 @@ pub enum ErrorCode {
     InvalidIndex,
     #[msg("Missing merkle root")]
     MissingMerkleRoot,
+    #[msg("Tree arity must be between 2 and 256")]
+    InvalidArity,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ClaimHasherV1;
 
     fn create_test_leaf(claimant_seed: u8, entitlements: u64) -> ClaimLeaf {
         let mut claimant_bytes = [0u8; 32];
@@ -972,4 +1093,142 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_levels_match_hasher_build_tree() {
+        for size in [1, 2, 255, 256, 257, 65_537] {
+            let leaves: Vec<ClaimLeaf> = (0..size)
+                .map(|i| create_test_leaf_unique(i, i as u64 + 1))
+                .collect();
+            let tree = ClaimTreeV1::from_leaves(leaves.clone()).unwrap();
+
+            let leaf_hashes = leaves.iter().map(|leaf| leaf.to_hash()).collect();
+            let (root, levels) = ClaimHasherV1::build_tree(leaf_hashes).unwrap();
+            assert_eq!(tree.root(), Some(root), "root mismatch for {} leaves", size);
+            assert_eq!(tree.levels, levels, "levels mismatch for {} leaves", size);
+            assert_eq!(tree.depth(), levels.len() - 1);
+        }
+    }
+
+    #[test]
+    fn test_all_proofs() {
+        let leaves: Vec<ClaimLeaf> = (0..1000)
+            .map(|i| create_test_leaf_unique(i, i as u64 + 1))
+            .collect();
+        let tree = ClaimTreeV1::from_leaves(leaves.clone()).unwrap();
+        let root = tree.root().unwrap();
+
+        let all: Vec<_> = tree.all_proofs().collect();
+        assert_eq!(all.len(), leaves.len());
+
+        for ((leaf, proof), expected) in all.into_iter().zip(&leaves) {
+            assert_eq!(leaf, expected, "proofs should be in leaf order");
+            assert_eq!(proof, tree.proof_for_claimant(&leaf.claimant).unwrap());
+            assert!(ClaimProofV1::new(proof).verify(&root, leaf));
+        }
+    }
+
+    #[test]
+    fn test_configurable_arity() {
+        let leaves: Vec<ClaimLeaf> = (0..1000)
+            .map(|i| create_test_leaf_unique(i, i as u64 + 1))
+            .collect();
+
+        for arity in [2, 3, 16, 255, 256] {
+            let tree = ClaimTreeV1::from_leaves_with_options(
+                leaves.clone(),
+                &ClaimTreeV1Options::with_arity(arity),
+            )
+            .unwrap();
+            let root = tree.root().unwrap();
+            assert_eq!(tree.arity(), arity);
+
+            // Depth is the number of times 1000 must be divided by the arity to reach 1
+            let mut expected_depth = 0;
+            let mut remaining = leaves.len();
+            while remaining > 1 {
+                remaining = remaining.div_ceil(arity);
+                expected_depth += 1;
+            }
+            assert_eq!(tree.depth(), expected_depth, "arity {}", arity);
+
+            for (leaf, proof) in tree.all_proofs().collect::<Vec<_>>() {
+                assert_eq!(proof.len(), expected_depth);
+                assert!(proof.iter().all(|level| level.len() < arity));
+                assert!(tree.verify_proof(&leaf.claimant, &proof).unwrap());
+
+                // The on-chain arity bound rejects the proof for narrower trees
+                let proof = ClaimProofV1::new(proof);
+                assert!(proof.verify_with_arity(&root, leaf, arity as u16));
+                if arity > 2 {
+                    assert!(!proof.verify_with_arity(&root, leaf, proof.max_level_width() as u16));
+                }
+            }
+        }
+
+        // Different arities commit to different roots
+        let binary = ClaimTreeV1::from_leaves_with_options(
+            leaves.clone(),
+            &ClaimTreeV1Options::with_arity(2),
+        )
+        .unwrap();
+        let wide = ClaimTreeV1::from_leaves(leaves.clone()).unwrap();
+        assert_eq!(wide.arity(), claim_tree_constants::BRANCHING_FACTOR);
+        assert_ne!(binary.root(), wide.root());
+
+        assert!(ClaimTreeV1::from_leaves_with_options(
+            leaves.clone(),
+            &ClaimTreeV1Options::with_arity(1)
+        )
+        .is_err());
+        assert!(ClaimTreeV1::from_leaves_with_options(
+            leaves,
+            &ClaimTreeV1Options::with_arity(257)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_keccak256_tree() {
+        let claimants: Vec<(Pubkey, u64)> =
+            (0..500).map(|i| (Pubkey::new_unique(), i + 1)).collect();
+        let sha =
+            create_claim_tree_v1_with_options(&claimants, 2, &ClaimTreeV1Options::with_arity(16))
+                .unwrap();
+        let keccak = create_claim_tree_v1_with_options(
+            &claimants,
+            2,
+            &ClaimTreeV1Options {
+                hash_scheme: ClaimHashScheme::Keccak256,
+                ..ClaimTreeV1Options::with_arity(16)
+            },
+        )
+        .unwrap();
+        assert_eq!(sha.hash_scheme(), ClaimHashScheme::Sha256);
+        assert_eq!(keccak.hash_scheme(), ClaimHashScheme::Keccak256);
+        assert_ne!(sha.root(), keccak.root());
+
+        // Same shape, different hashes; proofs only verify under their own scheme
+        let root = keccak.root().unwrap();
+        for leaf in keccak.leaves.iter().step_by(23) {
+            let proof = keccak.proof_for_claimant(&leaf.claimant).unwrap();
+            assert_eq!(
+                proof.len(),
+                sha.proof_for_claimant(&leaf.claimant).unwrap().len()
+            );
+            assert!(keccak.verify_proof(&leaf.claimant, &proof).unwrap());
+
+            let proof = ClaimProofType::from_wide(proof);
+            assert!(proof.verify_with_scheme(&root, leaf, 16, ClaimHashScheme::Keccak256));
+            assert!(!proof.verify(&root, leaf, 16));
+        }
+
+        let batch: Vec<Pubkey> = keccak.leaves[..8]
+            .iter()
+            .map(|leaf| leaf.claimant)
+            .collect();
+        let (leaves, multiproof) = keccak.multiproof_for_claimants(&batch).unwrap();
+        assert!(keccak.verify_multiproof(&leaves, &multiproof).unwrap());
+        assert!(!multiproof.verify_with_arity(&root, &leaves, 16));
+    }
 }
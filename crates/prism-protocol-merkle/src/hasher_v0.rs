@@ -1,8 +1,5 @@
-use anchor_lang::solana_program::hash::Hasher as SolanaHasher;
 use rs_merkle::Hasher;
 
-use crate::claim_tree_constants;
-
 /// Merkle tree hasher for the Prism Protocol that implements the same hashing logic
 /// as used in the claim_tokens verification. This ensures that merkle trees built with
 /// this hasher will produce proofs that can be verified on-chain.
@@ -42,29 +39,15 @@ impl Hasher for ClaimHasherV0 {
 
     fn hash(data: &[u8]) -> [u8; 32] {
         // This is used for leaf hashing
-        let mut hasher = SolanaHasher::default();
-        hasher.hash(&[claim_tree_constants::LEAF_PREFIX]); // Leaf prefix - prevents leaf/internal node confusion attacks
-        hasher.hash(data);
-        hasher.result().to_bytes()
+        prism_protocol_verify::hash_leaf_data_with::<prism_protocol_verify::Sha256Hasher>(data)
     }
 
     fn concat_and_hash(left: &Self::Hash, right: Option<&Self::Hash>) -> Self::Hash {
         match right {
             Some(right_hash) => {
-                // This is used for internal node hashing
-                let mut hasher = SolanaHasher::default();
-                hasher.hash(&[claim_tree_constants::INTERNAL_PREFIX]); // Internal node prefix - provides domain separation from leaf nodes
-
-                // Order hashes lexicographically (same as in verify_merkle_proof)
-                if left.as_ref() <= right_hash.as_ref() {
-                    hasher.hash(left);
-                    hasher.hash(right_hash);
-                } else {
-                    hasher.hash(right_hash);
-                    hasher.hash(left);
-                }
-
-                hasher.result().to_bytes()
+                // This is used for internal node hashing; the pair is ordered
+                // lexicographically, same as in verify_merkle_proof
+                prism_protocol_verify::hash_internal_node(&[*left, *right_hash])
             }
             None => {
                 // If no right sibling, just propagate the left hash
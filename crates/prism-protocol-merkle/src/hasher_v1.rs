@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::Hasher as SolanaHasher;
 
 use crate::claim_tree_constants;
 
@@ -33,10 +32,7 @@ impl ClaimHasherV1 {
     /// Hash a leaf node using domain separation prefix.
     /// This produces the same result as `ClaimLeaf::to_hash()`.
     pub fn hash_leaf(data: &[u8]) -> [u8; 32] {
-        let mut hasher = SolanaHasher::default();
-        hasher.hash(&[claim_tree_constants::LEAF_PREFIX]); // Leaf prefix - prevents leaf/internal node confusion attacks
-        hasher.hash(data);
-        hasher.result().to_bytes()
+        prism_protocol_verify::hash_leaf_data_with::<prism_protocol_verify::Sha256Hasher>(data)
     }
 
     /// Hash an internal node with up to 256 children.
@@ -49,18 +45,8 @@ impl ClaimHasherV1 {
             claim_tree_constants::BRANCHING_FACTOR
         );
 
-        // Sort children for deterministic ordering
-        let mut sorted_children = children.to_vec();
-        sorted_children.sort();
-
-        let mut hasher = SolanaHasher::default();
-        hasher.hash(&[claim_tree_constants::INTERNAL_PREFIX]); // Internal node prefix - provides domain separation from leaf nodes
-
-        for child_hash in sorted_children {
-            hasher.hash(&child_hash);
-        }
-
-        hasher.result().to_bytes()
+        // Children are sorted for deterministic ordering
+        prism_protocol_verify::hash_internal_node(children)
     }
 
     /// Build a complete 256-ary merkle tree from leaf hashes.
@@ -147,23 +133,9 @@ impl ClaimHasherV1 {
     /// Verify a 256-ary merkle proof.
     /// This is the same logic as in `ClaimProofV1::verify()` but as a standalone function.
     pub fn verify_proof(proof: &[Vec<[u8; 32]>], root: &[u8; 32], leaf_hash: &[u8; 32]) -> bool {
-        let mut computed_hash = *leaf_hash;
-
-        // Process each level of the proof from bottom to top
-        for level_siblings in proof.iter() {
-            // Collect all hashes at this level: current computed hash + all siblings
-            let mut level_hashes = Vec::with_capacity(level_siblings.len() + 1);
-            level_hashes.push(computed_hash);
-            level_hashes.extend_from_slice(level_siblings);
-
-            // Sort all hashes lexicographically for deterministic ordering
-            level_hashes.sort();
-
-            // Hash the sorted children to get the parent node hash
-            computed_hash = Self::hash_internal_node(&level_hashes);
-        }
-
-        computed_hash == *root
+        prism_protocol_verify::fold_proof_v1::<prism_protocol_verify::Sha256Hasher>(
+            *leaf_hash, proof,
+        ) == *root
     }
 }
 
@@ -183,6 +155,7 @@ pub enum ErrorCode {
 mod tests {
     use super::*;
     use crate::ClaimLeaf;
+    use anchor_lang::solana_program::hash::Hasher as SolanaHasher;
 
     fn create_test_leaf(claimant_seed: u8, entitlements: u64) -> ClaimLeaf {
         // Create deterministic pubkey for testing
@@ -0,0 +1,364 @@
+use rayon::prelude::*;
+use rs_merkle::Hasher;
+
+use crate::multiproof::multiproof_from_levels;
+use crate::{
+    claim_tree_constants, ClaimHashScheme, ClaimHasherV0, ClaimLeaf, ClaimMultiProofV0, ClaimTreeV1,
+};
+
+/// Leaves per rayon task when hashing leaves or building a level
+const PARALLEL_CHUNK_SIZE: usize = 4096;
+
+/// V0 claim tree built from leaf hashes alone.
+///
+/// Produces the same root and proofs as [`ClaimTreeV0`](crate::ClaimTreeV0) for the
+/// same leaf order, but keeps no leaves and no claimant index, so memory is just the
+/// node hashes (about 64 bytes per leaf). Used by streaming compilation, where the
+/// leaves themselves stay on disk and are addressed by position.
+#[derive(Clone, Debug)]
+pub struct LeafHashTreeV0 {
+    /// Node hashes per level, leaf hashes first and the root last
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl LeafHashTreeV0 {
+    /// Build the tree from leaf hashes in leaf order, hashing each level in parallel
+    pub fn from_leaf_hashes(leaf_hashes: Vec<[u8; 32]>) -> Self {
+        let mut levels = vec![leaf_hashes];
+
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .expect("at least one level")
+                .par_chunks(2)
+                .with_min_len(PARALLEL_CHUNK_SIZE / 2)
+                .map(|pair| ClaimHasherV0::concat_and_hash(&pair[0], pair.get(1)))
+                .collect();
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    /// Rebuild a tree from levels previously taken from [`levels`](Self::levels)
+    ///
+    /// `None` unless the levels have the shape of a binary tree: at least one leaf,
+    /// each level half the size of the one below (rounded up), ending in a single root.
+    /// Node hashes are not re-checked.
+    pub fn from_levels(levels: Vec<Vec<[u8; 32]>>) -> Option<Self> {
+        let well_formed = levels.first().is_some_and(|leaves| !leaves.is_empty())
+            && levels.last().is_some_and(|root| root.len() == 1)
+            && levels
+                .windows(2)
+                .all(|pair| pair[1].len() == pair[0].len().div_ceil(2) && pair[0].len() > 1);
+        well_formed.then_some(Self { levels })
+    }
+
+    /// Hash leaves in parallel and build the tree
+    pub fn from_leaves(leaves: &[ClaimLeaf]) -> Self {
+        Self::from_leaf_hashes(hash_leaves(leaves))
+    }
+
+    /// Number of leaves
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Whether the tree has no leaves
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// Merkle root, `None` for an empty tree
+    pub fn root(&self) -> Option<[u8; 32]> {
+        self.levels.last().and_then(|level| level.first()).copied()
+    }
+
+    /// Sibling hashes from the leaf at `index` up to the root
+    ///
+    /// A node without a sibling (the last node of an odd level) is promoted
+    /// unchanged and contributes nothing to the proof.
+    pub fn proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let mut proof = Vec::with_capacity(self.levels.len());
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+
+        Some(proof)
+    }
+
+    /// Node hashes per level, leaf hashes first and the root last
+    pub fn levels(&self) -> &[Vec<[u8; 32]>] {
+        &self.levels
+    }
+
+    /// Node hashes per level, leaf hashes first and the root last
+    pub(crate) fn into_levels(self) -> Vec<Vec<[u8; 32]>> {
+        self.levels
+    }
+
+    /// Multiproof for the leaves at `indices`, which must be sorted and unique
+    ///
+    /// `None` if any index is out of range or the indices aren't strictly increasing.
+    pub fn multiproof(&self, indices: &[usize]) -> Option<ClaimMultiProofV0> {
+        let in_order = indices.windows(2).all(|pair| pair[0] < pair[1]);
+        if indices.is_empty() || !in_order || indices[indices.len() - 1] >= self.len() {
+            return None;
+        }
+
+        Some(ClaimMultiProofV0::new(multiproof_from_levels(
+            &self.levels,
+            2,
+            indices,
+        )))
+    }
+}
+
+/// V1 claim tree built from leaf hashes alone.
+///
+/// The wide counterpart of [`LeafHashTreeV0`]: the same root and proofs as
+/// [`ClaimTreeV1`] of the same arity and hash scheme for the same leaf order, keeping
+/// only the node hashes.
+#[derive(Clone, Debug)]
+pub struct LeafHashTreeV1 {
+    /// Node hashes per level, leaf hashes first and the root last
+    levels: Vec<Vec<[u8; 32]>>,
+    /// Maximum children per internal node
+    arity: usize,
+    /// Hash function the leaf hashes were made with
+    hash_scheme: ClaimHashScheme,
+}
+
+impl LeafHashTreeV1 {
+    /// Build the tree from leaf hashes in leaf order, hashing each level in parallel
+    ///
+    /// `None` if `arity` is outside 2..=256.
+    pub fn from_leaf_hashes(
+        leaf_hashes: Vec<[u8; 32]>,
+        arity: usize,
+        hash_scheme: ClaimHashScheme,
+    ) -> Option<Self> {
+        valid_arity(arity).then(|| Self {
+            levels: ClaimTreeV1::build_levels(leaf_hashes, arity, hash_scheme),
+            arity,
+            hash_scheme,
+        })
+    }
+
+    /// Rebuild a tree from levels previously taken from [`levels`](Self::levels)
+    ///
+    /// `None` unless `arity` is valid and the levels have the shape of a tree of that
+    /// arity: at least one leaf, each level the one below divided by `arity` (rounded
+    /// up), ending in a single root. Node hashes are not re-checked.
+    pub fn from_levels(
+        levels: Vec<Vec<[u8; 32]>>,
+        arity: usize,
+        hash_scheme: ClaimHashScheme,
+    ) -> Option<Self> {
+        let well_formed = valid_arity(arity)
+            && levels.first().is_some_and(|leaves| !leaves.is_empty())
+            && levels.last().is_some_and(|root| root.len() == 1)
+            && levels
+                .windows(2)
+                .all(|pair| pair[1].len() == pair[0].len().div_ceil(arity) && pair[0].len() > 1);
+        well_formed.then_some(Self {
+            levels,
+            arity,
+            hash_scheme,
+        })
+    }
+
+    /// Hash leaves in parallel with `hash_scheme` and build the tree
+    pub fn from_leaves(
+        leaves: &[ClaimLeaf],
+        arity: usize,
+        hash_scheme: ClaimHashScheme,
+    ) -> Option<Self> {
+        Self::from_leaf_hashes(
+            hash_leaves_with_scheme(leaves, hash_scheme),
+            arity,
+            hash_scheme,
+        )
+    }
+
+    /// Number of leaves
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Whether the tree has no leaves
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// Merkle root, `None` for an empty tree
+    pub fn root(&self) -> Option<[u8; 32]> {
+        self.levels.last().and_then(|level| level.first()).copied()
+    }
+
+    /// Maximum children per internal node
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Hash function and leaf encoding
+    pub fn hash_scheme(&self) -> ClaimHashScheme {
+        self.hash_scheme
+    }
+
+    /// Sibling hashes of the leaf at `index`, one group per level up to the root
+    pub fn proof(&self, index: usize) -> Option<Vec<Vec<[u8; 32]>>> {
+        (index < self.len())
+            .then(|| ClaimTreeV1::proof_from_levels(&self.levels, self.arity, index))
+    }
+
+    /// Node hashes per level, leaf hashes first and the root last
+    pub fn levels(&self) -> &[Vec<[u8; 32]>] {
+        &self.levels
+    }
+}
+
+fn valid_arity(arity: usize) -> bool {
+    (claim_tree_constants::MIN_BRANCHING_FACTOR..=claim_tree_constants::BRANCHING_FACTOR)
+        .contains(&arity)
+}
+
+/// Hash claim leaves in parallel, preserving order
+pub fn hash_leaves(leaves: &[ClaimLeaf]) -> Vec<[u8; 32]> {
+    hash_leaves_with_scheme(leaves, ClaimHashScheme::Sha256)
+}
+
+/// Hash claim leaves in parallel with the given scheme, preserving order
+pub fn hash_leaves_with_scheme(leaves: &[ClaimLeaf], scheme: ClaimHashScheme) -> Vec<[u8; 32]> {
+    leaves
+        .par_iter()
+        .with_min_len(PARALLEL_CHUNK_SIZE)
+        .map(|leaf| leaf.to_hash_with_scheme(scheme))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        create_claim_tree_v0, create_claim_tree_v1_with_options, ClaimProofType, ClaimProofV0,
+        ClaimTreeV1Options,
+    };
+    use anchor_lang::prelude::Pubkey;
+
+    #[test]
+    fn test_matches_claim_tree_v0() {
+        for leaf_count in [1, 2, 3, 5, 8, 13, 100] {
+            let claimants: Vec<(Pubkey, u64)> = (0..leaf_count)
+                .map(|i| (Pubkey::new_unique(), i as u64 + 1))
+                .collect();
+            let tree = create_claim_tree_v0(&claimants, 3).unwrap();
+            let hash_tree = LeafHashTreeV0::from_leaves(&tree.leaves);
+
+            assert_eq!(hash_tree.len(), leaf_count);
+            assert_eq!(hash_tree.root(), tree.root());
+
+            let root = hash_tree.root().unwrap();
+            for (index, leaf) in tree.leaves.iter().enumerate() {
+                let proof = hash_tree.proof(index).unwrap();
+                assert_eq!(proof, tree.proof_for_claimant(&leaf.claimant).unwrap());
+                assert!(ClaimProofV0::new(proof).verify(&root, leaf));
+            }
+            assert!(hash_tree.proof(leaf_count).is_none());
+        }
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let tree = LeafHashTreeV0::from_leaf_hashes(vec![]);
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), None);
+        assert_eq!(tree.proof(0), None);
+    }
+
+    #[test]
+    fn test_from_levels_roundtrip() {
+        for leaf_count in [1, 2, 5, 8] {
+            let tree = LeafHashTreeV0::from_leaf_hashes(vec![[7; 32]; leaf_count]);
+            let rebuilt = LeafHashTreeV0::from_levels(tree.levels().to_vec()).unwrap();
+            assert_eq!(rebuilt.root(), tree.root());
+            assert_eq!(rebuilt.proof(leaf_count - 1), tree.proof(leaf_count - 1));
+        }
+
+        let mut levels = LeafHashTreeV0::from_leaf_hashes(vec![[7; 32]; 5])
+            .levels()
+            .to_vec();
+        assert!(LeafHashTreeV0::from_levels(vec![]).is_none());
+        assert!(LeafHashTreeV0::from_levels(vec![vec![]]).is_none());
+        levels[1].pop();
+        assert!(LeafHashTreeV0::from_levels(levels).is_none());
+    }
+
+    #[test]
+    fn test_matches_claim_tree_v1() {
+        for hash_scheme in ClaimHashScheme::ALL {
+            for (leaf_count, arity) in [(1, 2), (2, 3), (7, 3), (16, 4), (100, 16), (300, 256)] {
+                let claimants: Vec<(Pubkey, u64)> = (0..leaf_count)
+                    .map(|i| (Pubkey::new_unique(), i as u64 + 1))
+                    .collect();
+                let tree = create_claim_tree_v1_with_options(
+                    &claimants,
+                    3,
+                    &ClaimTreeV1Options {
+                        arity,
+                        hash_scheme,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let hash_tree =
+                    LeafHashTreeV1::from_leaves(&tree.leaves, arity, hash_scheme).unwrap();
+
+                assert_eq!(hash_tree.len(), leaf_count);
+                assert_eq!(hash_tree.root(), tree.root());
+
+                let root = hash_tree.root().unwrap();
+                for (index, leaf) in tree.leaves.iter().enumerate() {
+                    let proof = hash_tree.proof(index).unwrap();
+                    assert_eq!(proof, tree.proof_for_claimant(&leaf.claimant).unwrap());
+                    assert!(ClaimProofType::from_wide(proof).verify_with_scheme(
+                        &root,
+                        leaf,
+                        arity as u16,
+                        hash_scheme
+                    ));
+                }
+                assert!(hash_tree.proof(leaf_count).is_none());
+            }
+        }
+        assert!(
+            LeafHashTreeV1::from_leaf_hashes(vec![[7; 32]], 1, ClaimHashScheme::Sha256).is_none()
+        );
+    }
+
+    #[test]
+    fn test_v1_from_levels_roundtrip() {
+        let tree = LeafHashTreeV1::from_leaf_hashes(vec![[7; 32]; 10], 3, ClaimHashScheme::Sha256)
+            .unwrap();
+        let rebuilt =
+            LeafHashTreeV1::from_levels(tree.levels().to_vec(), 3, ClaimHashScheme::Sha256)
+                .unwrap();
+        assert_eq!(rebuilt.root(), tree.root());
+        assert_eq!(rebuilt.proof(9), tree.proof(9));
+
+        // Levels of another arity don't fit
+        assert!(
+            LeafHashTreeV1::from_levels(tree.levels().to_vec(), 4, ClaimHashScheme::Sha256)
+                .is_none()
+        );
+        assert!(LeafHashTreeV1::from_levels(vec![], 3, ClaimHashScheme::Sha256).is_none());
+    }
+}
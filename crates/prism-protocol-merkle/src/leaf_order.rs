@@ -0,0 +1,112 @@
+//! Leaf order of claim trees.
+//!
+//! Trees keep their leaves in input order by default. [`LeafOrder::Claimant`] sorts
+//! them by claimant pubkey, so a cohort's root doesn't depend on the order of its rows
+//! and its leaves can be indexed for non-membership proofs (see
+//! [`crate::non_membership`]).
+
+use std::{fmt, str::FromStr};
+
+use crate::ClaimLeaf;
+
+/// Order of the leaves in a claim tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LeafOrder {
+    /// Leaves in the order claimants were given (e.g. CSV row order)
+    #[default]
+    Insertion,
+
+    /// Leaves sorted by claimant pubkey bytes, independent of the input order and
+    /// enabling non-membership proofs
+    Claimant,
+}
+
+impl LeafOrder {
+    /// Stable name as used on the command line and in manifests
+    pub fn name(&self) -> &'static str {
+        match self {
+            LeafOrder::Insertion => "insertion",
+            LeafOrder::Claimant => "claimant",
+        }
+    }
+
+    /// Put `leaves` in this order
+    pub fn apply(&self, leaves: &mut [ClaimLeaf]) {
+        if *self == LeafOrder::Claimant {
+            leaves.sort_unstable_by_key(|leaf| leaf.claimant);
+        }
+    }
+}
+
+impl fmt::Display for LeafOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for LeafOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "insertion" => Ok(LeafOrder::Insertion),
+            "claimant" => Ok(LeafOrder::Claimant),
+            _ => Err(format!(
+                "Unknown leaf order '{}' (expected insertion or claimant)",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClaimTreeV1Options;
+    use crate::{create_claim_tree_v0_with_order, create_claim_tree_v1_with_options};
+    use anchor_lang::prelude::Pubkey;
+
+    #[test]
+    fn test_claimant_order_ignores_input_order() {
+        let claimants: Vec<(Pubkey, u64)> =
+            (0..50).map(|i| (Pubkey::new_unique(), i + 1)).collect();
+        let mut reversed = claimants.clone();
+        reversed.reverse();
+
+        let v0 = |claimants: &[(Pubkey, u64)], order| {
+            create_claim_tree_v0_with_order(claimants, 2, order).unwrap()
+        };
+        let sorted = v0(&claimants, LeafOrder::Claimant);
+        assert!(sorted
+            .leaves
+            .windows(2)
+            .all(|pair| pair[0].claimant < pair[1].claimant));
+        assert_eq!(sorted.root(), v0(&reversed, LeafOrder::Claimant).root());
+        assert_ne!(
+            v0(&claimants, LeafOrder::Insertion).root(),
+            v0(&reversed, LeafOrder::Insertion).root()
+        );
+
+        let options = ClaimTreeV1Options {
+            arity: 4,
+            leaf_order: LeafOrder::Claimant,
+            ..Default::default()
+        };
+        assert_eq!(
+            create_claim_tree_v1_with_options(&claimants, 2, &options)
+                .unwrap()
+                .root(),
+            create_claim_tree_v1_with_options(&reversed, 2, &options)
+                .unwrap()
+                .root()
+        );
+    }
+
+    #[test]
+    fn test_leaf_order_names() {
+        for order in [LeafOrder::Insertion, LeafOrder::Claimant] {
+            assert_eq!(order.to_string().parse::<LeafOrder>(), Ok(order));
+        }
+        assert!("sorted".parse::<LeafOrder>().is_err());
+    }
+}
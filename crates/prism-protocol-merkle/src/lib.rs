@@ -2,26 +2,53 @@ pub mod claim_tree_v0;
 pub mod claim_tree_v1;
 pub mod hasher_v0;
 pub mod hasher_v1;
+pub mod leaf_hash_tree;
+pub mod leaf_order;
+pub mod multiproof;
+pub mod non_membership;
 pub mod proof;
+pub mod tree_artifact;
 
-pub use claim_tree_v0::{create_claim_tree_v0, ClaimTreeV0};
-pub use claim_tree_v1::{create_claim_tree_v1, ClaimTreeV1};
+pub use claim_tree_v0::{create_claim_tree_v0, create_claim_tree_v0_with_order, ClaimTreeV0};
+pub use claim_tree_v1::{
+    create_claim_tree_v1, create_claim_tree_v1_with_options, ClaimTreeV1, ClaimTreeV1Options,
+};
 pub use hasher_v0::ClaimHasherV0;
 pub use hasher_v1::ClaimHasherV1;
+pub use leaf_hash_tree::{hash_leaves, hash_leaves_with_scheme, LeafHashTreeV0, LeafHashTreeV1};
+pub use leaf_order::LeafOrder;
+pub use non_membership::{
+    claimant_index_root, ClaimantIndexTree, NeighbourLeaf, NonMembershipError, NonMembershipProof,
+    NonMembershipResult,
+};
 pub use proof::{
     batch_verify_proofs, extract_root_from_proof, generate_proof_for_leaf, verify_claim_proof,
 };
+pub use tree_artifact::{
+    TreeArtifact, TreeArtifactError, TreeArtifactResult, TreeArtifactVersion,
+    TREE_ARTIFACT_FORMAT_VERSION,
+};
 
-// Re-export merkle leaf and proof types from prism protocol
-pub use prism_protocol::{ClaimLeaf, ClaimProofV0, ClaimProofV1};
+// Re-export merkle leaf, hasher, proof and multiproof types from prism protocol
+pub use prism_protocol::{
+    ClaimHashScheme, ClaimHasher, ClaimLeaf, ClaimMultiProofNode, ClaimMultiProofType,
+    ClaimMultiProofV0, ClaimMultiProofV1, ClaimProofType, ClaimProofV0, ClaimProofV1,
+    Keccak256ClaimHasher, Sha256ClaimHasher,
+};
 
 // Re-export key types from rs-merkle for convenience
 pub use rs_merkle::{MerkleProof, MerkleTree};
 
 /// Shared constants for merkle tree implementations
 pub mod claim_tree_constants {
-    /// Number of children per internal node in the 256-ary merkle tree
-    pub const BRANCHING_FACTOR: usize = 256;
+    /// Number of children per internal node in the 256-ary merkle tree (the default and
+    /// widest [`ClaimTreeV1`](crate::ClaimTreeV1) arity)
+    pub const BRANCHING_FACTOR: usize =
+        prism_protocol::claim_tree_constants::MAX_TREE_ARITY as usize;
+
+    /// Narrowest [`ClaimTreeV1`](crate::ClaimTreeV1) arity (a binary tree)
+    pub const MIN_BRANCHING_FACTOR: usize =
+        prism_protocol::claim_tree_constants::MIN_TREE_ARITY as usize;
 
     /// Domain separation prefix for leaf nodes
     pub const LEAF_PREFIX: u8 = prism_protocol::claim_tree_constants::LEAF_PREFIX;
@@ -0,0 +1,202 @@
+use crate::ClaimMultiProofNode;
+
+/// Build multiproof levels for a batch of leaves from a tree's stored levels.
+///
+/// `levels` holds the node hashes per level, leaf hashes first and the root last, with
+/// each level grouped into parents of up to `arity` nodes. `leaf_indices` must be sorted
+/// and unique. At each level, every parent of a known node gets one entry listing how
+/// many of its children are known and the hashes of the others.
+pub(crate) fn multiproof_from_levels(
+    levels: &[Vec<[u8; 32]>],
+    arity: usize,
+    leaf_indices: &[usize],
+) -> Vec<Vec<ClaimMultiProofNode>> {
+    let mut known = leaf_indices.to_vec();
+    let mut proof = Vec::with_capacity(levels.len().saturating_sub(1));
+
+    for level in &levels[..levels.len().saturating_sub(1)] {
+        let mut nodes = Vec::new();
+        let mut parents = Vec::new();
+        let mut next_known = known.iter().peekable();
+
+        while let Some(&first) = next_known.peek() {
+            let parent = first / arity;
+            let chunk_start = parent * arity;
+            let chunk_end = (chunk_start + arity).min(level.len());
+
+            let mut node = ClaimMultiProofNode {
+                known_children: 0,
+                siblings: Vec::new(),
+            };
+            for (position, hash) in (chunk_start..chunk_end).zip(&level[chunk_start..chunk_end]) {
+                if next_known.next_if(|&&index| index == position).is_some() {
+                    node.known_children += 1;
+                } else {
+                    node.siblings.push(*hash);
+                }
+            }
+
+            nodes.push(node);
+            parents.push(parent);
+        }
+
+        proof.push(nodes);
+        known = parents;
+    }
+
+    proof
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        create_claim_tree_v0, create_claim_tree_v1, create_claim_tree_v1_with_options, ClaimLeaf,
+        ClaimTreeV1Options,
+    };
+    use anchor_lang::prelude::*;
+
+    /// Borsh-encoded size of a value, as carried in instruction data
+    fn encoded_size<T: AnchorSerialize>(value: &T) -> usize {
+        value.try_to_vec().expect("Failed to serialize").len()
+    }
+
+    /// A spread-out batch: every `stride`-th claimant
+    fn batch(leaves: &[ClaimLeaf], size: usize, stride: usize) -> Vec<Pubkey> {
+        (0..size)
+            .map(|i| leaves[(i * stride) % leaves.len()].claimant)
+            .collect()
+    }
+
+    #[test]
+    fn test_multiproof_size_savings() {
+        let claimants: Vec<(Pubkey, u64)> =
+            (0..10_000).map(|i| (Pubkey::new_unique(), i + 1)).collect();
+        let tree_v0 = create_claim_tree_v0(&claimants, 3).unwrap();
+        let tree_v1 = create_claim_tree_v1(&claimants, 3).unwrap();
+        let tree_v1_arity_4 =
+            create_claim_tree_v1_with_options(&claimants, 3, &ClaimTreeV1Options::with_arity(4))
+                .unwrap();
+
+        // (batch size, stride, minimum saving in % for V0, V1 and V1 arity 4). Sizes only
+        // depend on leaf positions, so the savings are exact; the bounds leave some slack.
+        let cases: [(usize, usize, [usize; 3]); 6] = [
+            (2, 1, [35, 45, 45]),
+            (8, 1, [85, 85, 85]),
+            (8, 1_249, [5, 10, 15]),
+            (32, 1, [95, 95, 95]),
+            (32, 311, [25, 10, 35]),
+            (128, 78, [40, 70, 50]),
+        ];
+        for (size, stride, min_saved) in cases {
+            let batch = batch(&tree_v0.leaves, size, stride);
+
+            let single_v0: usize = batch
+                .iter()
+                .map(|claimant| encoded_size(&tree_v0.proof_for_claimant(claimant).unwrap()))
+                .sum();
+            let (leaves_v0, multi_v0) = tree_v0.multiproof_for_claimants(&batch).unwrap();
+            assert!(tree_v0.verify_multiproof(&leaves_v0, &multi_v0).unwrap());
+
+            let single_v1 = |tree: &crate::ClaimTreeV1| -> usize {
+                batch
+                    .iter()
+                    .map(|claimant| encoded_size(&tree.proof_for_claimant(claimant).unwrap()))
+                    .sum()
+            };
+            let (leaves_v1, multi_v1) = tree_v1.multiproof_for_claimants(&batch).unwrap();
+            assert!(tree_v1.verify_multiproof(&leaves_v1, &multi_v1).unwrap());
+            let (leaves_v1, multi_v1_arity_4) =
+                tree_v1_arity_4.multiproof_for_claimants(&batch).unwrap();
+            assert!(tree_v1_arity_4
+                .verify_multiproof(&leaves_v1, &multi_v1_arity_4)
+                .unwrap());
+
+            for ((name, single, multi), min_saved) in [
+                ("V0", single_v0, encoded_size(&multi_v0)),
+                ("V1", single_v1(&tree_v1), encoded_size(&multi_v1)),
+                (
+                    "V1 arity 4",
+                    single_v1(&tree_v1_arity_4),
+                    encoded_size(&multi_v1_arity_4),
+                ),
+            ]
+            .into_iter()
+            .zip(min_saved)
+            {
+                let saved = 100 * single.saturating_sub(multi) / single;
+                assert!(
+                    saved >= min_saved,
+                    "{} multiproof for {} leaves (stride {}) saved {}% of {} bytes, expected at least {}%",
+                    name,
+                    size,
+                    stride,
+                    saved,
+                    single,
+                    min_saved
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiproof_rejects_wrong_batch() {
+        let claimants: Vec<(Pubkey, u64)> =
+            (0..300).map(|i| (Pubkey::new_unique(), i + 1)).collect();
+        let tree =
+            create_claim_tree_v1_with_options(&claimants, 2, &ClaimTreeV1Options::with_arity(8))
+                .unwrap();
+        let batch = batch(&tree.leaves, 5, 37);
+        let (leaves, proof) = tree.multiproof_for_claimants(&batch).unwrap();
+        assert!(tree.verify_multiproof(&leaves, &proof).unwrap());
+
+        // Inflated entitlements
+        let mut inflated = leaves.clone();
+        inflated[2].entitlements += 1;
+        assert!(!tree.verify_multiproof(&inflated, &proof).unwrap());
+
+        // Leaves in request order rather than tree order, or a leaf dropped
+        let mut reversed = leaves.clone();
+        reversed.reverse();
+        assert!(!tree.verify_multiproof(&reversed, &proof).unwrap());
+        assert!(!tree.verify_multiproof(&leaves[1..], &proof).unwrap());
+
+        // A narrower cohort arity than the tree was built with
+        let root = tree.root().unwrap();
+        assert!(!proof.verify_with_arity(&root, &leaves, 4));
+
+        // Batches with unknown or repeated claimants are refused
+        assert!(tree.multiproof_for_claimants(&[]).is_err());
+        assert!(tree
+            .multiproof_for_claimants(&[batch[0], Pubkey::new_unique()])
+            .is_err());
+        assert!(tree
+            .multiproof_for_claimants(&[batch[0], batch[0]])
+            .is_err());
+    }
+
+    #[test]
+    fn test_multiproof_matches_single_proof() {
+        // A batch of one carries the same siblings as the single proof
+        let claimants: Vec<(Pubkey, u64)> =
+            (0..1_000).map(|i| (Pubkey::new_unique(), i + 1)).collect();
+        let tree_v0 = create_claim_tree_v0(&claimants, 2).unwrap();
+        let tree_v1 =
+            create_claim_tree_v1_with_options(&claimants, 2, &ClaimTreeV1Options::with_arity(16))
+                .unwrap();
+
+        for leaf in tree_v0.leaves.iter().step_by(97) {
+            let (_, multi) = tree_v0.multiproof_for_claimants(&[leaf.claimant]).unwrap();
+            let single = tree_v0.proof_for_claimant(&leaf.claimant).unwrap();
+            assert_eq!(multi.total_hashes(), single.len());
+
+            let (_, multi) = tree_v1.multiproof_for_claimants(&[leaf.claimant]).unwrap();
+            let single = tree_v1.proof_for_claimant(&leaf.claimant).unwrap();
+            let siblings: Vec<[u8; 32]> = multi
+                .as_slice()
+                .iter()
+                .flat_map(|level| level[0].siblings.clone())
+                .collect();
+            assert_eq!(siblings, single.concat());
+        }
+    }
+}
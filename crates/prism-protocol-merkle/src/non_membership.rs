@@ -0,0 +1,517 @@
+//! Claimant index trees and non-membership proofs.
+//!
+//! A cohort's claim tree can't prove a claimant is *absent*: internal nodes hash their
+//! children sorted by hash, so its root binds which leaves are in the tree but not where
+//! they sit. A [`ClaimantIndexTree`] is a second commitment to the same leaves, built for
+//! exclusion:
+//!
+//! - leaves are the cohort's claim leaf hashes (same hash scheme), in strictly ascending
+//!   claimant order; the builder refuses anything else
+//! - internal nodes hash their children in position order, `H(0x01 || left || right)`,
+//!   and the last node of an odd level is promoted unhashed, as in a V0 tree
+//! - the root commits to the leaf count: `H(0x02 || leaf_count as u64 LE || top node)`
+//!
+//! A [`NonMembershipProof`] carries the leaves either side of the claimant with their
+//! indices and paths. Because the root fixes the leaf count and every path is folded in
+//! the direction its index dictates, each neighbour is proved to sit at exactly its
+//! index, and the verifier can check the two are adjacent (or that the single neighbour
+//! is the first or last leaf) and that the claimant sorts strictly between them.
+//!
+//! ## Trust model
+//!
+//! The root commits to the full ordered sequence of leaves, but no root can show a
+//! sequence is sorted without reading it. Sortedness is enforced when the tree is built,
+//! and anyone holding the published leaves (the campaign database, whose hash the
+//! campaign commits on-chain at activation) can rebuild the tree and check both the
+//! order and that the index root covers the same leaves as the cohort's claim root. A
+//! proof that verifies against an audited index root shows the claimant is not a leaf.
+
+use anchor_lang::prelude::*;
+use prism_protocol_verify::Hasher;
+
+use crate::{
+    claim_tree_constants::INTERNAL_PREFIX, ClaimHashScheme, ClaimLeaf, Keccak256ClaimHasher,
+    Sha256ClaimHasher,
+};
+
+/// Domain separation prefix of a claimant index root
+pub const CLAIMANT_INDEX_ROOT_PREFIX: u8 = 0x02;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum NonMembershipError {
+    #[error("Leaves are not in strictly ascending claimant order at index {0}")]
+    NotSorted(usize),
+
+    #[error("Claimant {0} is in the tree")]
+    ClaimantInTree(Pubkey),
+
+    #[error("Claimant index has no leaves")]
+    Empty,
+
+    #[error("{claimants} claimants for {leaf_hashes} leaf hashes")]
+    LengthMismatch {
+        claimants: usize,
+        leaf_hashes: usize,
+    },
+}
+
+pub type NonMembershipResult<T> = std::result::Result<T, NonMembershipError>;
+
+/// Positional merkle tree over claimant-sorted claim leaves, committing to its leaf count
+#[derive(Clone, Debug)]
+pub struct ClaimantIndexTree {
+    leaves: Vec<ClaimLeaf>,
+    /// Node hashes, leaf hashes first and the top node last
+    levels: Vec<Vec<[u8; 32]>>,
+    hash_scheme: ClaimHashScheme,
+}
+
+impl ClaimantIndexTree {
+    /// Build the index over `leaves`, which must be in strictly ascending claimant order
+    pub fn from_leaves(
+        leaves: Vec<ClaimLeaf>,
+        hash_scheme: ClaimHashScheme,
+    ) -> NonMembershipResult<Self> {
+        check_sorted(leaves.iter().map(|leaf| &leaf.claimant))?;
+        let leaf_hashes = leaves
+            .iter()
+            .map(|leaf| hash_scheme.hash_leaf(leaf))
+            .collect();
+        Ok(Self {
+            leaves,
+            levels: build_levels(leaf_hashes, hash_scheme)?,
+            hash_scheme,
+        })
+    }
+
+    /// Index root, as recorded for the cohort in the campaign database
+    pub fn root(&self) -> [u8; 32] {
+        let top = &self.levels[self.levels.len() - 1][0];
+        commit_root(self.hash_scheme, self.leaves.len() as u64, top)
+    }
+
+    /// Leaves in claimant order
+    pub fn leaves(&self) -> &[ClaimLeaf] {
+        &self.leaves
+    }
+
+    pub fn hash_scheme(&self) -> ClaimHashScheme {
+        self.hash_scheme
+    }
+
+    /// Prove `claimant` is not a leaf with the leaves either side of it
+    pub fn non_membership_proof(
+        &self,
+        claimant: &Pubkey,
+    ) -> NonMembershipResult<NonMembershipProof> {
+        let upper = match self
+            .leaves
+            .binary_search_by(|leaf| leaf.claimant.cmp(claimant))
+        {
+            Ok(_) => return Err(NonMembershipError::ClaimantInTree(*claimant)),
+            Err(upper) => upper,
+        };
+
+        let neighbour = |index: usize| NeighbourLeaf {
+            index: index as u64,
+            leaf: self.leaves[index].clone(),
+            path: self.path(index),
+        };
+
+        Ok(NonMembershipProof {
+            claimant: *claimant,
+            leaf_count: self.leaves.len() as u64,
+            lower: upper.checked_sub(1).map(neighbour),
+            upper: (upper < self.leaves.len()).then(|| neighbour(upper)),
+        })
+    }
+
+    /// Sibling hashes from leaf `index` up to the top node, skipping promoted levels
+    fn path(&self, mut index: usize) -> Vec<[u8; 32]> {
+        let mut path = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                path.push(*sibling);
+            }
+            index /= 2;
+        }
+        path
+    }
+}
+
+/// Index root of claimant-sorted leaves given as claimants and their claim leaf hashes
+///
+/// For compilers that hash leaves in batches and never hold a [`ClaimantIndexTree`].
+pub fn claimant_index_root(
+    claimants: &[Pubkey],
+    leaf_hashes: &[[u8; 32]],
+    hash_scheme: ClaimHashScheme,
+) -> NonMembershipResult<[u8; 32]> {
+    if claimants.len() != leaf_hashes.len() {
+        return Err(NonMembershipError::LengthMismatch {
+            claimants: claimants.len(),
+            leaf_hashes: leaf_hashes.len(),
+        });
+    }
+    check_sorted(claimants.iter())?;
+
+    // Only one level is held at a time
+    let mut level = match leaf_hashes {
+        [] => return Err(NonMembershipError::Empty),
+        [leaf_hash] => vec![*leaf_hash],
+        _ => parent_level(leaf_hashes, hash_scheme),
+    };
+    while level.len() > 1 {
+        level = parent_level(&level, hash_scheme);
+    }
+    Ok(commit_root(
+        hash_scheme,
+        leaf_hashes.len() as u64,
+        &level[0],
+    ))
+}
+
+/// A leaf next to the excluded claimant, with its index and path in the index tree
+#[derive(Clone, Debug, PartialEq)]
+pub struct NeighbourLeaf {
+    pub index: u64,
+    pub leaf: ClaimLeaf,
+    pub path: Vec<[u8; 32]>,
+}
+
+impl NeighbourLeaf {
+    /// Fold the path from this leaf's index up to the top node of a `leaf_count` tree,
+    /// or `None` if the path doesn't have exactly that shape
+    fn top_node(&self, leaf_count: u64, hash_scheme: ClaimHashScheme) -> Option<[u8; 32]> {
+        if self.index >= leaf_count {
+            return None;
+        }
+
+        let mut node = hash_scheme.hash_leaf(&self.leaf);
+        let mut path = self.path.iter();
+        let (mut index, mut width) = (self.index, leaf_count);
+        while width > 1 {
+            if index ^ 1 < width {
+                let sibling = path.next()?;
+                node = if index % 2 == 0 {
+                    hash_pair(hash_scheme, &node, sibling)
+                } else {
+                    hash_pair(hash_scheme, sibling, &node)
+                };
+            }
+            index /= 2;
+            width = width.div_ceil(2);
+        }
+
+        path.next().is_none().then_some(node)
+    }
+}
+
+/// Proof that a claimant is not a leaf of a [`ClaimantIndexTree`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct NonMembershipProof {
+    /// The claimant proved absent
+    pub claimant: Pubkey,
+
+    /// Leaves in the tree
+    pub leaf_count: u64,
+
+    /// Greatest leaf below the claimant (`None` if the claimant sorts first)
+    pub lower: Option<NeighbourLeaf>,
+
+    /// Least leaf above the claimant (`None` if the claimant sorts last)
+    pub upper: Option<NeighbourLeaf>,
+}
+
+impl NonMembershipProof {
+    /// Verify against the index root of a SHA-256 cohort
+    pub fn verify(&self, root: &[u8; 32]) -> bool {
+        self.verify_with_scheme(root, ClaimHashScheme::Sha256)
+    }
+
+    /// Verify against an index root built with `hash_scheme`
+    pub fn verify_with_scheme(&self, root: &[u8; 32], hash_scheme: ClaimHashScheme) -> bool {
+        let proves = |neighbour: &NeighbourLeaf| {
+            neighbour
+                .top_node(self.leaf_count, hash_scheme)
+                .is_some_and(|top| commit_root(hash_scheme, self.leaf_count, &top) == *root)
+        };
+        let below = |lower: &NeighbourLeaf| lower.leaf.claimant < self.claimant && proves(lower);
+        let above = |upper: &NeighbourLeaf| self.claimant < upper.leaf.claimant && proves(upper);
+
+        match (&self.lower, &self.upper) {
+            (Some(lower), Some(upper)) => {
+                upper.index.checked_sub(lower.index) == Some(1) && below(lower) && above(upper)
+            }
+            (Some(lower), None) => lower.index + 1 == self.leaf_count && below(lower),
+            (None, Some(upper)) => upper.index == 0 && above(upper),
+            (None, None) => false,
+        }
+    }
+}
+
+fn check_sorted<'a>(claimants: impl Iterator<Item = &'a Pubkey>) -> NonMembershipResult<()> {
+    let mut previous: Option<&Pubkey> = None;
+    for (index, claimant) in claimants.enumerate() {
+        if previous.is_some_and(|previous| previous >= claimant) {
+            return Err(NonMembershipError::NotSorted(index));
+        }
+        previous = Some(claimant);
+    }
+    Ok(())
+}
+
+fn build_levels(
+    leaf_hashes: Vec<[u8; 32]>,
+    hash_scheme: ClaimHashScheme,
+) -> NonMembershipResult<Vec<Vec<[u8; 32]>>> {
+    if leaf_hashes.is_empty() {
+        return Err(NonMembershipError::Empty);
+    }
+
+    let mut levels = vec![leaf_hashes];
+    while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+        levels.push(parent_level(level, hash_scheme));
+    }
+    Ok(levels)
+}
+
+/// Hash a level's nodes in pairs, promoting the last node of an odd level
+fn parent_level(level: &[[u8; 32]], hash_scheme: ClaimHashScheme) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_pair(hash_scheme, left, right),
+            [promoted] => *promoted,
+            _ => unreachable!("chunks(2) yields one or two nodes"),
+        })
+        .collect()
+}
+
+fn commit_root(hash_scheme: ClaimHashScheme, leaf_count: u64, top: &[u8; 32]) -> [u8; 32] {
+    hashv(
+        hash_scheme,
+        &[
+            &[CLAIMANT_INDEX_ROOT_PREFIX],
+            &leaf_count.to_le_bytes(),
+            top,
+        ],
+    )
+}
+
+fn hash_pair(hash_scheme: ClaimHashScheme, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(hash_scheme, &[&[INTERNAL_PREFIX], left, right])
+}
+
+fn hashv(hash_scheme: ClaimHashScheme, parts: &[&[u8]]) -> [u8; 32] {
+    match hash_scheme {
+        ClaimHashScheme::Sha256 => Sha256ClaimHasher::hashv(parts),
+        ClaimHashScheme::Keccak256 => Keccak256ClaimHasher::hashv(parts),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Leaves with ascending claimants, spaced so every gap has room for another
+    fn sorted_leaves(count: usize) -> Vec<ClaimLeaf> {
+        (0..count)
+            .map(|i| ClaimLeaf {
+                claimant: Pubkey::new_from_array([(i * 2 + 1) as u8; 32]),
+                assigned_vault_index: (i % 3) as u8,
+                entitlements: i as u64 + 1,
+            })
+            .collect()
+    }
+
+    /// A claimant strictly between `a` and `b`, if the two aren't consecutive keys
+    fn between(a: &Pubkey, b: &Pubkey) -> Option<Pubkey> {
+        let mut bytes = a.to_bytes();
+        for byte in bytes.iter_mut().rev() {
+            if *byte < u8::MAX {
+                *byte += 1;
+                break;
+            }
+            *byte = 0;
+        }
+        let candidate = Pubkey::new_from_array(bytes);
+        (candidate < *b).then_some(candidate)
+    }
+
+    #[test]
+    fn test_non_membership_every_gap() {
+        for scheme in ClaimHashScheme::ALL {
+            for count in [1, 2, 3, 5, 8, 13] {
+                let leaves = sorted_leaves(count);
+                let tree = ClaimantIndexTree::from_leaves(leaves.clone(), scheme).unwrap();
+                let root = tree.root();
+
+                let mut absent = vec![Pubkey::default(), Pubkey::new_from_array([0xff; 32])];
+                absent.extend(
+                    leaves
+                        .windows(2)
+                        .filter_map(|pair| between(&pair[0].claimant, &pair[1].claimant)),
+                );
+                assert_eq!(absent.len(), count + 1);
+                for claimant in &absent {
+                    let proof = tree.non_membership_proof(claimant).unwrap();
+                    assert!(proof.verify_with_scheme(&root, scheme));
+                }
+
+                // Compilers that only keep leaf hashes commit to the same root
+                assert_eq!(
+                    claimant_index_root(
+                        &leaves.iter().map(|leaf| leaf.claimant).collect::<Vec<_>>(),
+                        &leaves
+                            .iter()
+                            .map(|leaf| scheme.hash_leaf(leaf))
+                            .collect::<Vec<_>>(),
+                        scheme
+                    ),
+                    Ok(root)
+                );
+
+                for leaf in &leaves {
+                    assert_eq!(
+                        tree.non_membership_proof(&leaf.claimant),
+                        Err(NonMembershipError::ClaimantInTree(leaf.claimant))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_unsorted_leaves() {
+        let mut leaves = sorted_leaves(4);
+        leaves.swap(1, 2);
+        assert_eq!(
+            ClaimantIndexTree::from_leaves(leaves.clone(), ClaimHashScheme::Sha256).unwrap_err(),
+            NonMembershipError::NotSorted(2)
+        );
+
+        leaves.swap(1, 2);
+        leaves[3].claimant = leaves[2].claimant;
+        assert_eq!(
+            ClaimantIndexTree::from_leaves(leaves, ClaimHashScheme::Sha256).unwrap_err(),
+            NonMembershipError::NotSorted(3)
+        );
+    }
+
+    #[test]
+    fn test_rejects_forged_proofs() {
+        let leaves = sorted_leaves(8);
+        let tree = ClaimantIndexTree::from_leaves(leaves.clone(), ClaimHashScheme::Sha256).unwrap();
+        let root = tree.root();
+        let claimant = between(&leaves[3].claimant, &leaves[4].claimant).unwrap();
+        let proof = tree.non_membership_proof(&claimant).unwrap();
+        assert!(proof.verify(&root));
+
+        // Wrong scheme or root
+        assert!(!proof.verify_with_scheme(&root, ClaimHashScheme::Keccak256));
+        assert!(!proof.verify(&[0; 32]));
+
+        // Non-adjacent neighbours with genuine paths: 3 and 5 straddle member 4
+        let member = leaves[4].claimant;
+        let gapped = NonMembershipProof {
+            claimant: member,
+            upper: tree
+                .non_membership_proof(&between(&leaves[4].claimant, &leaves[5].claimant).unwrap())
+                .unwrap()
+                .upper,
+            ..proof.clone()
+        };
+        assert!(!gapped.verify(&root));
+
+        // Neighbours relabelled as adjacent keep paths for their real positions
+        let mut relabelled = gapped.clone();
+        relabelled.upper.as_mut().unwrap().index = 4;
+        assert!(!relabelled.verify(&root));
+
+        // A different leaf count changes the committed root
+        for leaf_count in [7, 9, 16] {
+            let recounted = NonMembershipProof {
+                leaf_count,
+                ..proof.clone()
+            };
+            assert!(!recounted.verify(&root));
+        }
+
+        // Dropping the upper neighbour only works for the last leaf
+        let truncated = NonMembershipProof {
+            upper: None,
+            ..proof.clone()
+        };
+        assert!(!truncated.verify(&root));
+
+        // A claimant outside the neighbours' range
+        let outside = NonMembershipProof {
+            claimant: leaves[6].claimant,
+            ..proof.clone()
+        };
+        assert!(!outside.verify(&root));
+
+        // Tampered neighbour leaf
+        let mut tampered = proof.clone();
+        tampered.lower.as_mut().unwrap().leaf.entitlements += 1;
+        assert!(!tampered.verify(&root));
+
+        // Extra path hashes
+        let mut padded = proof;
+        padded.lower.as_mut().unwrap().path.push([0; 32]);
+        assert!(!padded.verify(&root));
+    }
+
+    #[test]
+    fn test_root_commits_to_leaf_count() {
+        // Two trees sharing a top node but not a leaf count have different roots
+        let leaves = sorted_leaves(3);
+        let tree = ClaimantIndexTree::from_leaves(leaves, ClaimHashScheme::Sha256).unwrap();
+        let top = tree.levels.last().unwrap()[0];
+        assert_ne!(tree.root(), commit_root(ClaimHashScheme::Sha256, 4, &top));
+        assert_eq!(
+            ClaimantIndexTree::from_leaves(vec![], ClaimHashScheme::Sha256).unwrap_err(),
+            NonMembershipError::Empty
+        );
+    }
+
+    #[test]
+    fn test_claim_tree_claimant_index() {
+        use crate::{
+            create_claim_tree_v0_with_order, create_claim_tree_v1_with_options, ClaimTreeV1Options,
+            LeafOrder,
+        };
+
+        let claimants: Vec<(Pubkey, u64)> =
+            (0..20).map(|i| (Pubkey::new_unique(), i + 1)).collect();
+        let absent = Pubkey::new_unique();
+
+        let v0 = create_claim_tree_v0_with_order(&claimants, 3, LeafOrder::Claimant).unwrap();
+        let index = v0.claimant_index().unwrap();
+        assert!(index
+            .non_membership_proof(&absent)
+            .unwrap()
+            .verify(&index.root()));
+
+        let options = ClaimTreeV1Options {
+            arity: 4,
+            leaf_order: LeafOrder::Claimant,
+            hash_scheme: ClaimHashScheme::Keccak256,
+        };
+        let v1 = create_claim_tree_v1_with_options(&claimants, 3, &options).unwrap();
+        let index = v1.claimant_index().unwrap();
+        assert!(index
+            .non_membership_proof(&absent)
+            .unwrap()
+            .verify_with_scheme(&index.root(), ClaimHashScheme::Keccak256));
+
+        // Insertion-ordered trees can't be indexed (new_unique keys ascend, so reverse them)
+        let reversed: Vec<(Pubkey, u64)> = claimants.iter().rev().copied().collect();
+        let unsorted = create_claim_tree_v0_with_order(&reversed, 3, LeafOrder::Insertion).unwrap();
+        assert!(matches!(
+            unsorted.claimant_index(),
+            Err(NonMembershipError::NotSorted(_))
+        ));
+    }
+}
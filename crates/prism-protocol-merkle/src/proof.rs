@@ -44,6 +44,9 @@ pub fn generate_proof_for_leaf(
 }
 
 /// Batch verify multiple proofs against the same root
+///
+/// Each proof is checked on its own. To verify a batch with shared siblings carried once,
+/// use a multiproof (`ClaimTreeV0::multiproof_for_claimants`).
 pub fn batch_verify_proofs(
     proofs: &[(&[[u8; 32]], &ClaimLeaf, usize)], // (proof, leaf, index)
     root: &[u8; 32],
@@ -0,0 +1,812 @@
+//! Serialized claim tree artifact.
+//!
+//! A compiled [`ClaimTreeV0`](crate::ClaimTreeV0) or [`ClaimTreeV1`](crate::ClaimTreeV1)
+//! written once and loaded without rebuilding: the file holds the leaves, a claimant
+//! index and every level of node hashes, so serving a proof is a handful of reads from
+//! a memory map. All integers are little-endian.
+//!
+//! ```text
+//! header      64 bytes
+//!   magic         8   b"PRSMTREE"
+//!   format        2   TREE_ARTIFACT_FORMAT_VERSION
+//!   tree version  1   0 = binary (V0, odd nodes promoted), 1 = wide (V1)
+//!   hash scheme   1   ClaimHashScheme tag (0 = SHA-256, 1 = Keccak-256)
+//!   arity         2   children per internal node (2 for V0)
+//!   reserved      2
+//!   leaf count    8
+//!   index slots   8   power of two, at least twice the leaf count
+//!   root         32
+//! leaves      leaf count × 41   borsh-encoded ClaimLeaf (the leaf hash preimage)
+//! index       index slots × 4   leaf index + 1 by claimant, 0 = empty, linear probing
+//! levels      nodes × 32        leaf hashes first and the root last
+//! checksum    32                SHA-256 of everything above
+//! ```
+//!
+//! The level sizes follow from the leaf count, tree version and arity, so any file
+//! whose length disagrees with its header is rejected before anything is read.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::Hasher as SolanaHasher;
+use memmap2::Mmap;
+use rs_merkle::Hasher;
+use std::fs::File;
+use std::io::{self, Write};
+use std::ops::Deref;
+use std::path::Path;
+
+use crate::{
+    claim_tree_constants, ClaimHashScheme, ClaimHasherV0, ClaimLeaf, ClaimProofType, LeafHashTreeV0,
+};
+
+/// Current artifact format version
+pub const TREE_ARTIFACT_FORMAT_VERSION: u16 = 1;
+
+/// Leading bytes of every artifact
+pub const TREE_ARTIFACT_MAGIC: &[u8; 8] = b"PRSMTREE";
+
+const HEADER_BYTES: usize = 64;
+const LEAF_BYTES: usize = 32 + 1 + 8;
+const INDEX_SLOT_BYTES: usize = 4;
+const HASH_BYTES: usize = 32;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TreeArtifactError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Not a claim tree artifact")]
+    NotAnArtifact,
+
+    #[error("Unsupported tree artifact format version {0}")]
+    UnsupportedFormat(u16),
+
+    #[error("Invalid tree artifact: {0}")]
+    Invalid(String),
+
+    #[error("Tree artifact checksum mismatch")]
+    ChecksumMismatch,
+
+    #[error("Tree artifact root mismatch: expected {expected}, found {found}")]
+    RootMismatch { expected: String, found: String },
+}
+
+pub type TreeArtifactResult<T> = std::result::Result<T, TreeArtifactError>;
+
+/// Shape of the tree stored in an artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeArtifactVersion {
+    /// Binary tree; the last node of an odd level is promoted unhashed
+    V0,
+    /// Tree of up to `arity` children per node; every node is hashed
+    V1,
+}
+
+impl TreeArtifactVersion {
+    fn tag(self) -> u8 {
+        match self {
+            TreeArtifactVersion::V0 => 0,
+            TreeArtifactVersion::V1 => 1,
+        }
+    }
+}
+
+// ================================================================================================
+// Writing
+// ================================================================================================
+
+/// Writer that hashes everything passing through it
+struct ChecksumWriter<W> {
+    inner: W,
+    hasher: SolanaHasher,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.hasher.hash(bytes);
+        self.inner.write_all(bytes)
+    }
+}
+
+/// Write an artifact for `leaves` and the tree `levels` built from them
+///
+/// Returns the checksum, which identifies the artifact (e.g. when a campaign database
+/// references it).
+pub(crate) fn write_tree_artifact<W: Write>(
+    writer: W,
+    version: TreeArtifactVersion,
+    arity: usize,
+    hash_scheme: ClaimHashScheme,
+    leaves: &[ClaimLeaf],
+    levels: &[Vec<[u8; 32]>],
+) -> TreeArtifactResult<[u8; 32]> {
+    let root = levels
+        .last()
+        .and_then(|level| level.first())
+        .ok_or_else(|| TreeArtifactError::Invalid("tree has no leaves".to_string()))?;
+    let index = build_claimant_index(leaves)?;
+
+    let mut writer = ChecksumWriter {
+        inner: writer,
+        hasher: SolanaHasher::default(),
+    };
+
+    let mut header = [0u8; HEADER_BYTES];
+    header[0..8].copy_from_slice(TREE_ARTIFACT_MAGIC);
+    header[8..10].copy_from_slice(&TREE_ARTIFACT_FORMAT_VERSION.to_le_bytes());
+    header[10] = version.tag();
+    header[11] = hash_scheme.tag();
+    header[12..14].copy_from_slice(&(arity as u16).to_le_bytes());
+    header[16..24].copy_from_slice(&(leaves.len() as u64).to_le_bytes());
+    header[24..32].copy_from_slice(&(index.len() as u64).to_le_bytes());
+    header[32..64].copy_from_slice(root);
+    writer.write_all(&header)?;
+
+    for leaf in leaves {
+        writer.write_all(&leaf.try_to_vec()?)?;
+    }
+    for slot in &index {
+        writer.write_all(&slot.to_le_bytes())?;
+    }
+    for node in levels.iter().flatten() {
+        writer.write_all(node)?;
+    }
+
+    let checksum = writer.hasher.result().to_bytes();
+    writer.inner.write_all(&checksum)?;
+    writer.inner.flush()?;
+
+    Ok(checksum)
+}
+
+/// Open-addressing claimant index: slot holds leaf index + 1, 0 when empty
+fn build_claimant_index(leaves: &[ClaimLeaf]) -> TreeArtifactResult<Vec<u32>> {
+    if leaves.len() >= u32::MAX as usize {
+        return Err(TreeArtifactError::Invalid(format!(
+            "{} leaves exceed the index capacity",
+            leaves.len()
+        )));
+    }
+
+    let slots = index_slots(leaves.len())?;
+    let mask = slots - 1;
+    let mut index = vec![0u32; slots];
+    for (leaf_index, leaf) in leaves.iter().enumerate() {
+        let mut slot = index_start(&leaf.claimant) & mask;
+        while index[slot] != 0 {
+            slot = (slot + 1) & mask;
+        }
+        index[slot] = leaf_index as u32 + 1;
+    }
+
+    Ok(index)
+}
+
+/// Index size for `leaf_count` leaves: at most half full, so probes stay short
+fn index_slots(leaf_count: usize) -> TreeArtifactResult<usize> {
+    leaf_count
+        .checked_mul(2)
+        .and_then(usize::checked_next_power_of_two)
+        .ok_or_else(|| TreeArtifactError::Invalid(format!("leaf count {}", leaf_count)))
+}
+
+/// First probe slot for a claimant (pubkeys are uniformly distributed)
+fn index_start(claimant: &Pubkey) -> usize {
+    let bytes = claimant.as_ref();
+    u64::from_le_bytes(bytes[..8].try_into().expect("8 bytes")) as usize
+}
+
+/// Node count of each level, leaves first
+fn level_sizes(version: TreeArtifactVersion, arity: usize, leaf_count: usize) -> Vec<usize> {
+    let width = match version {
+        TreeArtifactVersion::V0 => claim_tree_constants::MIN_BRANCHING_FACTOR,
+        TreeArtifactVersion::V1 => arity,
+    };
+    let mut sizes = vec![leaf_count];
+    while let Some(&size) = sizes.last().filter(|&&size| size > 1) {
+        sizes.push(size.div_ceil(width));
+    }
+    sizes
+}
+
+// ================================================================================================
+// Reading
+// ================================================================================================
+
+/// Artifact bytes, memory-mapped from a file or owned (e.g. read from a database)
+enum ArtifactBytes {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+impl Deref for ArtifactBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            ArtifactBytes::Mapped(map) => map,
+            ArtifactBytes::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// A loaded claim tree artifact serving leaves and proofs straight from its bytes
+pub struct TreeArtifact {
+    bytes: ArtifactBytes,
+    version: TreeArtifactVersion,
+    arity: usize,
+    hash_scheme: ClaimHashScheme,
+    leaf_count: usize,
+    index_slots: usize,
+    root: [u8; 32],
+    /// Offset and node count of each level, leaves first
+    levels: Vec<(usize, usize)>,
+}
+
+impl TreeArtifact {
+    /// Memory-map an artifact file, verifying its layout, checksum and root
+    pub fn open(path: &Path) -> TreeArtifactResult<Self> {
+        let artifact = Self::open_unverified(path)?;
+        artifact.verify_checksum()?;
+        Ok(artifact)
+    }
+
+    /// Memory-map an artifact file, verifying its layout and root but not its checksum
+    ///
+    /// Skips the one pass over the file, for artifacts already verified (e.g. by a
+    /// previous [`TreeArtifact::open`] of the same file).
+    pub fn open_unverified(path: &Path) -> TreeArtifactResult<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only and artifacts are written once and never
+        // modified in place; a file truncated underneath us is the same hazard as
+        // any other memory-mapped reader.
+        let map = unsafe { Mmap::map(&file)? };
+        Self::parse(ArtifactBytes::Mapped(map))
+    }
+
+    /// Load an artifact from bytes, verifying its layout, checksum and root
+    pub fn from_bytes(bytes: Vec<u8>) -> TreeArtifactResult<Self> {
+        let artifact = Self::parse(ArtifactBytes::Owned(bytes))?;
+        artifact.verify_checksum()?;
+        Ok(artifact)
+    }
+
+    fn parse(bytes: ArtifactBytes) -> TreeArtifactResult<Self> {
+        if bytes.len() < HEADER_BYTES + HASH_BYTES || &bytes[0..8] != TREE_ARTIFACT_MAGIC {
+            return Err(TreeArtifactError::NotAnArtifact);
+        }
+
+        let format = u16::from_le_bytes([bytes[8], bytes[9]]);
+        if format != TREE_ARTIFACT_FORMAT_VERSION {
+            return Err(TreeArtifactError::UnsupportedFormat(format));
+        }
+
+        let invalid = |reason: String| TreeArtifactError::Invalid(reason);
+        let arity = u16::from_le_bytes([bytes[12], bytes[13]]) as usize;
+        let version = match bytes[10] {
+            0 if arity == claim_tree_constants::MIN_BRANCHING_FACTOR => TreeArtifactVersion::V0,
+            1 if (claim_tree_constants::MIN_BRANCHING_FACTOR
+                ..=claim_tree_constants::BRANCHING_FACTOR)
+                .contains(&arity) =>
+            {
+                TreeArtifactVersion::V1
+            }
+            tag => {
+                return Err(invalid(format!(
+                    "tree version {} with arity {}",
+                    tag, arity
+                )))
+            }
+        };
+        let hash_scheme = ClaimHashScheme::from_tag(bytes[11])
+            .filter(|scheme| {
+                // Binary trees are built with rs_merkle and are SHA-256 only
+                version == TreeArtifactVersion::V1 || *scheme == ClaimHashScheme::Sha256
+            })
+            .ok_or_else(|| invalid(format!("hash scheme {}", bytes[11])))?;
+
+        let read_u64 = |offset: usize| {
+            u64::from_le_bytes(bytes[offset..offset + 8].try_into().expect("8 bytes"))
+        };
+        let leaf_count = usize::try_from(read_u64(16)).map_err(|e| invalid(e.to_string()))?;
+        let slots = usize::try_from(read_u64(24)).map_err(|e| invalid(e.to_string()))?;
+        if leaf_count == 0 || leaf_count >= u32::MAX as usize {
+            return Err(invalid(format!("leaf count {}", leaf_count)));
+        }
+        if slots != index_slots(leaf_count)? {
+            return Err(invalid(format!(
+                "{} index slots for {} leaves",
+                slots, leaf_count
+            )));
+        }
+        let root: [u8; 32] = bytes[32..64].try_into().expect("32 bytes");
+
+        // Lay out the leaves, index and levels, and check the file is exactly as long as
+        // the header implies. Sizes come from the file, so they may overflow on 32-bit targets.
+        let too_large = || invalid(format!("{} leaves exceed the address space", leaf_count));
+        let table_bytes = slots.checked_mul(INDEX_SLOT_BYTES).ok_or_else(too_large)?;
+        let mut offset = leaf_count
+            .checked_mul(LEAF_BYTES)
+            .and_then(|leaf_bytes| HEADER_BYTES.checked_add(leaf_bytes))
+            .and_then(|leaves_end| leaves_end.checked_add(table_bytes))
+            .ok_or_else(too_large)?;
+        if offset > bytes.len() {
+            return Err(invalid(format!(
+                "{} bytes, too short for {} leaves and {} index slots",
+                bytes.len(),
+                leaf_count,
+                slots
+            )));
+        }
+        let mut levels = Vec::new();
+        for size in level_sizes(version, arity, leaf_count) {
+            levels.push((offset, size));
+            offset = size
+                .checked_mul(HASH_BYTES)
+                .and_then(|level_bytes| offset.checked_add(level_bytes))
+                .ok_or_else(too_large)?;
+        }
+        if offset.checked_add(HASH_BYTES) != Some(bytes.len()) {
+            return Err(invalid(format!(
+                "{} bytes, expected {}",
+                bytes.len(),
+                offset.saturating_add(HASH_BYTES)
+            )));
+        }
+
+        let artifact = Self {
+            bytes,
+            version,
+            arity,
+            hash_scheme,
+            leaf_count,
+            index_slots: slots,
+            root,
+            levels,
+        };
+
+        let stored_root = artifact.node(artifact.levels.len() - 1, 0);
+        if stored_root != root {
+            return Err(TreeArtifactError::RootMismatch {
+                expected: hex::encode(root),
+                found: hex::encode(stored_root),
+            });
+        }
+
+        Ok(artifact)
+    }
+
+    /// Check the trailing SHA-256 over the rest of the artifact
+    fn verify_checksum(&self) -> TreeArtifactResult<()> {
+        let body_len = self.bytes.len() - HASH_BYTES;
+        let mut hasher = SolanaHasher::default();
+        hasher.hash(&self.bytes[..body_len]);
+        if hasher.result().to_bytes() != self.checksum() {
+            return Err(TreeArtifactError::ChecksumMismatch);
+        }
+        Ok(())
+    }
+
+    /// Fail unless the artifact's root is `expected` (e.g. the cohort's on-chain root)
+    pub fn verify_root(&self, expected: &[u8; 32]) -> TreeArtifactResult<()> {
+        if self.root != *expected {
+            return Err(TreeArtifactError::RootMismatch {
+                expected: hex::encode(expected),
+                found: hex::encode(self.root),
+            });
+        }
+        Ok(())
+    }
+
+    /// Rehash every leaf and node and compare with the stored levels
+    ///
+    /// O(n) hashing, for audits; loading only checks the checksum and the root.
+    pub fn verify_tree(&self) -> TreeArtifactResult<()> {
+        let width = match self.version {
+            TreeArtifactVersion::V0 => claim_tree_constants::MIN_BRANCHING_FACTOR,
+            TreeArtifactVersion::V1 => self.arity,
+        };
+        let corrupt = |what: String| TreeArtifactError::Invalid(format!("{} doesn't match", what));
+
+        for index in 0..self.leaf_count {
+            let leaf_hash = self
+                .leaf(index)
+                .map(|leaf| leaf.to_hash_with_scheme(self.hash_scheme));
+            if leaf_hash != Some(self.node(0, index)) {
+                return Err(corrupt(format!("leaf {}", index)));
+            }
+        }
+
+        for level in 1..self.levels.len() {
+            let children = self.level(level - 1);
+            for (index, chunk) in children.chunks(width * HASH_BYTES).enumerate() {
+                let mut chunk: Vec<[u8; 32]> = chunk.as_chunks::<HASH_BYTES>().0.to_vec();
+                let expected = match self.version {
+                    TreeArtifactVersion::V0 => {
+                        ClaimHasherV0::concat_and_hash(&chunk[0], chunk.get(1))
+                    }
+                    TreeArtifactVersion::V1 => self.hash_scheme.hash_internal(&mut chunk),
+                };
+                if self.node(level, index) != expected {
+                    return Err(corrupt(format!("node {} of level {}", index, level)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Tree shape
+    pub fn version(&self) -> TreeArtifactVersion {
+        self.version
+    }
+
+    /// Children per internal node
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Hash function and leaf encoding of the tree
+    pub fn hash_scheme(&self) -> ClaimHashScheme {
+        self.hash_scheme
+    }
+
+    /// Merkle root
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// Trailing SHA-256 checksum identifying the artifact
+    pub fn checksum(&self) -> [u8; 32] {
+        self.bytes[self.bytes.len() - HASH_BYTES..]
+            .try_into()
+            .expect("32 bytes")
+    }
+
+    /// Number of leaves
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// Levels from a leaf to the root
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Leaf at `index`, in tree order
+    pub fn leaf(&self, index: usize) -> Option<ClaimLeaf> {
+        if index >= self.leaf_count {
+            return None;
+        }
+        let offset = HEADER_BYTES + index * LEAF_BYTES;
+        ClaimLeaf::try_from_slice(&self.bytes[offset..offset + LEAF_BYTES]).ok()
+    }
+
+    /// Tree index of a claimant's leaf, in expected O(1)
+    pub fn leaf_index(&self, claimant: &Pubkey) -> Option<usize> {
+        let mask = self.index_slots - 1;
+        let mut slot = index_start(claimant) & mask;
+
+        // At most half the slots of a well-formed index are full, so probing reaches an empty
+        // slot early; a corrupt (unverified) index is probed at most once per slot
+        for _ in 0..self.index_slots {
+            let offset = HEADER_BYTES + self.leaf_count * LEAF_BYTES + slot * INDEX_SLOT_BYTES;
+            let entry = u32::from_le_bytes(
+                self.bytes[offset..offset + INDEX_SLOT_BYTES]
+                    .try_into()
+                    .expect("4 bytes"),
+            ) as usize;
+            if entry == 0 || entry > self.leaf_count {
+                return None;
+            }
+
+            let leaf_offset = HEADER_BYTES + (entry - 1) * LEAF_BYTES;
+            if &self.bytes[leaf_offset..leaf_offset + 32] == claimant.as_ref() {
+                return Some(entry - 1);
+            }
+            slot = (slot + 1) & mask;
+        }
+
+        None
+    }
+
+    /// Proof for the leaf at `index`: binary for V0 artifacts, levelled for V1
+    pub fn proof(&self, index: usize) -> Option<ClaimProofType> {
+        if index >= self.leaf_count {
+            return None;
+        }
+
+        let mut position = index;
+        match self.version {
+            TreeArtifactVersion::V0 => {
+                let mut proof = Vec::with_capacity(self.depth());
+                for (level, &(_, size)) in self.levels[..self.depth()].iter().enumerate() {
+                    // A promoted node has no sibling
+                    if position ^ 1 < size {
+                        proof.push(self.node(level, position ^ 1));
+                    }
+                    position /= 2;
+                }
+                Some(ClaimProofType::from_binary(proof))
+            }
+            TreeArtifactVersion::V1 => {
+                let mut proof = Vec::with_capacity(self.depth());
+                for (level, &(_, size)) in self.levels[..self.depth()].iter().enumerate() {
+                    let chunk_start = position / self.arity * self.arity;
+                    let chunk_end = (chunk_start + self.arity).min(size);
+                    proof.push(
+                        (chunk_start..chunk_end)
+                            .filter(|&sibling| sibling != position)
+                            .map(|sibling| self.node(level, sibling))
+                            .collect(),
+                    );
+                    position /= self.arity;
+                }
+                Some(ClaimProofType::from_wide(proof))
+            }
+        }
+    }
+
+    /// A claimant's leaf and proof
+    pub fn proof_for_claimant(&self, claimant: &Pubkey) -> Option<(ClaimLeaf, ClaimProofType)> {
+        let index = self.leaf_index(claimant)?;
+        Some((self.leaf(index)?, self.proof(index)?))
+    }
+
+    fn level(&self, level: usize) -> &[u8] {
+        let (offset, size) = self.levels[level];
+        &self.bytes[offset..offset + size * HASH_BYTES]
+    }
+
+    fn node(&self, level: usize, index: usize) -> [u8; 32] {
+        let offset = self.levels[level].0 + index * HASH_BYTES;
+        self.bytes[offset..offset + HASH_BYTES]
+            .try_into()
+            .expect("32 bytes")
+    }
+}
+
+/// Levels of a V0 tree over `leaves` (rs_merkle doesn't expose its own)
+pub(crate) fn binary_levels(leaves: &[ClaimLeaf]) -> Vec<Vec<[u8; 32]>> {
+    LeafHashTreeV0::from_leaves(leaves).into_levels()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_claim_tree_v0, create_claim_tree_v1_with_options, ClaimTreeV1Options};
+
+    fn claimants(count: usize) -> Vec<(Pubkey, u64)> {
+        (0..count)
+            .map(|i| (Pubkey::new_unique(), i as u64 + 1))
+            .collect()
+    }
+
+    fn artifact_bytes(write: impl FnOnce(&mut Vec<u8>) -> TreeArtifactResult<[u8; 32]>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let checksum = write(&mut bytes).unwrap();
+        assert_eq!(bytes[bytes.len() - 32..], checksum);
+        bytes
+    }
+
+    #[test]
+    fn test_v0_artifact_matches_tree() {
+        for leaf_count in [1, 2, 3, 5, 8, 13, 100] {
+            let tree = create_claim_tree_v0(&claimants(leaf_count), 3).unwrap();
+            let bytes = artifact_bytes(|bytes| tree.write_artifact(bytes));
+            let artifact = TreeArtifact::from_bytes(bytes).unwrap();
+
+            assert_eq!(artifact.version(), TreeArtifactVersion::V0);
+            assert_eq!(Some(artifact.root()), tree.root());
+            assert_eq!(artifact.leaf_count(), leaf_count);
+            artifact.verify_tree().unwrap();
+
+            for leaf in &tree.leaves {
+                let (served_leaf, proof) = artifact.proof_for_claimant(&leaf.claimant).unwrap();
+                assert_eq!(&served_leaf, leaf);
+                match proof {
+                    ClaimProofType::V0(proof) => assert_eq!(
+                        proof.into_inner(),
+                        tree.proof_for_claimant(&leaf.claimant).unwrap()
+                    ),
+                    ClaimProofType::V1(_) => panic!("V0 artifacts serve binary proofs"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_v1_artifact_matches_tree() {
+        let claimants = claimants(1_000);
+        for arity in [2, 3, 16, 256] {
+            let tree = create_claim_tree_v1_with_options(
+                &claimants,
+                3,
+                &ClaimTreeV1Options::with_arity(arity),
+            )
+            .unwrap();
+            let bytes = artifact_bytes(|bytes| tree.write_artifact(bytes));
+            let artifact = TreeArtifact::from_bytes(bytes).unwrap();
+
+            assert_eq!(artifact.version(), TreeArtifactVersion::V1);
+            assert_eq!(artifact.arity(), arity);
+            assert_eq!(artifact.depth(), tree.depth());
+            artifact.verify_root(&tree.root().unwrap()).unwrap();
+            artifact.verify_tree().unwrap();
+
+            let root = artifact.root();
+            for (index, leaf) in tree.leaves.iter().enumerate() {
+                assert_eq!(artifact.leaf_index(&leaf.claimant), Some(index));
+                let proof = artifact.proof(index).unwrap();
+                assert!(proof.verify(&root, leaf, arity as u16));
+                match proof {
+                    ClaimProofType::V1(proof) => assert_eq!(
+                        proof.into_inner(),
+                        tree.proof_for_claimant(&leaf.claimant).unwrap()
+                    ),
+                    ClaimProofType::V0(_) => panic!("V1 artifacts serve levelled proofs"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_keccak256_artifact() {
+        let tree = create_claim_tree_v1_with_options(
+            &claimants(300),
+            2,
+            &ClaimTreeV1Options {
+                hash_scheme: ClaimHashScheme::Keccak256,
+                ..ClaimTreeV1Options::with_arity(8)
+            },
+        )
+        .unwrap();
+        let bytes = artifact_bytes(|bytes| tree.write_artifact(bytes));
+        let artifact = TreeArtifact::from_bytes(bytes.clone()).unwrap();
+
+        assert_eq!(artifact.hash_scheme(), ClaimHashScheme::Keccak256);
+        artifact.verify_tree().unwrap();
+        let root = artifact.root();
+        for leaf in tree.leaves.iter().step_by(17) {
+            let (_, proof) = artifact.proof_for_claimant(&leaf.claimant).unwrap();
+            assert!(proof.verify_with_scheme(&root, leaf, 8, artifact.hash_scheme()));
+        }
+
+        // SHA-256 artifacts record their scheme too
+        let sha = create_claim_tree_v0(&claimants(10), 2).unwrap();
+        let sha = TreeArtifact::from_bytes(artifact_bytes(|bytes| sha.write_artifact(bytes)));
+        assert_eq!(sha.unwrap().hash_scheme(), ClaimHashScheme::Sha256);
+
+        // Unknown schemes are rejected
+        let mut unknown = bytes;
+        unknown[11] = 7;
+        assert!(matches!(
+            TreeArtifact::from_bytes(unknown),
+            Err(TreeArtifactError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_artifact_lookups_outside_tree() {
+        let tree = create_claim_tree_v1_with_options(
+            &claimants(50),
+            2,
+            &ClaimTreeV1Options::with_arity(4),
+        )
+        .unwrap();
+        let artifact =
+            TreeArtifact::from_bytes(artifact_bytes(|bytes| tree.write_artifact(bytes))).unwrap();
+
+        assert_eq!(artifact.leaf_index(&Pubkey::new_unique()), None);
+        assert!(artifact.proof_for_claimant(&Pubkey::new_unique()).is_none());
+        assert!(artifact.leaf(50).is_none());
+        assert!(artifact.proof(50).is_none());
+        assert!(matches!(
+            artifact.verify_root(&[0; 32]),
+            Err(TreeArtifactError::RootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_memory_mapped_artifact() {
+        let tree = create_claim_tree_v0(&claimants(500), 2).unwrap();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let checksum = tree
+            .write_artifact(std::io::BufWriter::new(file.reopen().unwrap()))
+            .unwrap();
+
+        let artifact = TreeArtifact::open(file.path()).unwrap();
+        assert_eq!(artifact.checksum(), checksum);
+        let leaf = &tree.leaves[123];
+        let (_, proof) = artifact.proof_for_claimant(&leaf.claimant).unwrap();
+        assert!(proof.verify(&tree.root().unwrap(), leaf, 2));
+    }
+
+    #[test]
+    fn test_corrupt_artifacts_rejected() {
+        let tree = create_claim_tree_v1_with_options(
+            &claimants(100),
+            2,
+            &ClaimTreeV1Options::with_arity(8),
+        )
+        .unwrap();
+        let bytes = artifact_bytes(|bytes| tree.write_artifact(bytes));
+
+        // Any flipped byte fails the checksum
+        let mut flipped = bytes.clone();
+        flipped[HEADER_BYTES + 7] ^= 1;
+        assert!(matches!(
+            TreeArtifact::from_bytes(flipped.clone()),
+            Err(TreeArtifactError::ChecksumMismatch)
+        ));
+
+        // A flipped leaf loads unverified, but rehashing the tree catches it
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), &flipped).unwrap();
+        let unverified = TreeArtifact::open_unverified(file.path()).unwrap();
+        assert!(matches!(
+            unverified.verify_tree(),
+            Err(TreeArtifactError::Invalid(_))
+        ));
+
+        // A corrupt index that never reaches an empty slot still terminates lookups
+        let leaf_count = tree.leaves.len();
+        let slots = index_slots(leaf_count).unwrap();
+        let mut full_index = bytes.clone();
+        let table = HEADER_BYTES + leaf_count * LEAF_BYTES;
+        for slot in full_index[table..table + slots * INDEX_SLOT_BYTES].chunks_mut(INDEX_SLOT_BYTES)
+        {
+            slot.copy_from_slice(&1u32.to_le_bytes());
+        }
+        std::fs::write(file.path(), &full_index).unwrap();
+        let unverified = TreeArtifact::open_unverified(file.path()).unwrap();
+        assert_eq!(unverified.leaf_index(&Pubkey::new_unique()), None);
+        assert_eq!(unverified.leaf_index(&tree.leaves[0].claimant), Some(0));
+
+        // Slot counts must match the leaf count, and the header must fit the file
+        let with_header = |leaf_count: u64, slots: u64| {
+            let mut header = bytes.clone();
+            header[16..24].copy_from_slice(&leaf_count.to_le_bytes());
+            header[24..32].copy_from_slice(&slots.to_le_bytes());
+            std::fs::write(file.path(), &header).unwrap();
+            TreeArtifact::open_unverified(file.path())
+        };
+        for (leaf_count, slots) in [
+            (leaf_count as u64, slots as u64 * 2),
+            (leaf_count as u64, u64::MAX),
+            (
+                u32::MAX as u64 - 1,
+                index_slots(u32::MAX as usize - 1).unwrap() as u64,
+            ),
+            (u64::MAX, 0),
+        ] {
+            assert!(matches!(
+                with_header(leaf_count, slots),
+                Err(TreeArtifactError::Invalid(_))
+            ));
+        }
+
+        // Truncated, foreign and future files
+        assert!(matches!(
+            TreeArtifact::from_bytes(bytes[..bytes.len() - 1].to_vec()),
+            Err(TreeArtifactError::Invalid(_))
+        ));
+        assert!(matches!(
+            TreeArtifact::from_bytes(b"SQLite format 3".to_vec()),
+            Err(TreeArtifactError::NotAnArtifact)
+        ));
+        let mut future = bytes.clone();
+        future[8] = 2;
+        assert!(matches!(
+            TreeArtifact::from_bytes(future),
+            Err(TreeArtifactError::UnsupportedFormat(2))
+        ));
+
+        // The header root must match the stored root node
+        let mut wrong_root = bytes;
+        wrong_root[40] ^= 1;
+        assert!(matches!(
+            TreeArtifact::from_bytes(wrong_root),
+            Err(TreeArtifactError::RootMismatch { .. })
+        ));
+    }
+}
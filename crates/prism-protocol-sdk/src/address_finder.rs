@@ -6,8 +6,8 @@ use anchor_spl::{
     associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID, token::ID as TOKEN_PROGRAM_ID,
 };
 use prism_protocol::{
-    CAMPAIGN_V0_SEED_PREFIX, CLAIM_RECEIPT_V0_SEED_PREFIX, COHORT_V0_SEED_PREFIX,
-    ID as PRISM_PROGRAM_ID, VAULT_SEED_PREFIX,
+    CAMPAIGN_V0_SEED_PREFIX, CLAIM_RECEIPT_V0_SEED_PREFIX, COHORT_TREE_V0_SEED_PREFIX,
+    COHORT_V0_SEED_PREFIX, ID as PRISM_PROGRAM_ID, VAULT_SEED_PREFIX,
 };
 
 #[derive(Clone)]
@@ -67,6 +67,13 @@ impl AddressFinder {
         )
     }
 
+    pub fn find_cohort_tree_v0_address(&self, cohort_address: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[COHORT_TREE_V0_SEED_PREFIX, cohort_address.as_ref()],
+            &self.program_id,
+        )
+    }
+
     pub fn find_claim_receipt_v0_address(
         &self,
         cohort_address: &Pubkey,
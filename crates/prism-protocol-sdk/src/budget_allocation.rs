@@ -31,9 +31,25 @@ let result = allocator.calculate_cohort_allocation(
 
 // result.amount_per_entitlement is precise and respects mint decimals
 ```
+
+## Allocation Rules
+
+Besides percentage shares, a cohort can use a fixed amount per entitlement, an
+absolute cohort budget, or explicit per-claimant amounts (see [`AllocationRule`]).
+[`BudgetAllocator::allocate_campaign`] resolves non-percentage cohorts first and
+splits whatever budget remains between the percentage cohorts. Budget that no
+cohort draws from (e.g. a campaign without percentage cohorts) is returned as
+[`CampaignAllocation::unallocated_budget`].
+
+Optional [`ClaimantCaps`] bound any single claimant's allocation. Because every
+claimant in a cohort shares one on-chain `amount_per_entitlement`, a capped cohort
+is re-denominated to one base unit per entitlement and each claimant's entitlements
+become their payout. Budget-based rules water-fill the cohort total: capped
+claimants receive exactly their cap and the rest is split pro rata across the
+uncapped claimants.
 */
 
-use rust_decimal::Decimal;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -54,6 +70,12 @@ pub enum AllocationError {
 
     #[error("Budget allocation failed: {0}")]
     AllocationFailed(String),
+
+    #[error("Amount {amount} is not representable with {decimals} mint decimals")]
+    PrecisionExceeded { amount: Decimal, decimals: u8 },
+
+    #[error("Allocations require {required} tokens but the budget is {budget}")]
+    BudgetExceeded { required: Decimal, budget: Decimal },
 }
 
 pub type AllocationResult<T> = Result<T, AllocationError>;
@@ -74,6 +96,94 @@ pub struct CohortAllocation {
     pub dust_amount: Decimal,
 }
 
+/// How a single cohort's tokens are determined
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AllocationRule {
+    /// Percentage of the budget left over after all non-percentage cohorts
+    SharePercentage(Decimal),
+
+    /// Fixed amount per entitlement; the cohort total is derived from entitlements
+    FixedPerEntitlement(Decimal),
+
+    /// Absolute cohort budget, split evenly per entitlement
+    CohortBudget(Decimal),
+
+    /// Each claimant's entitlements are their explicit amount, one base unit
+    /// (10^-decimals tokens) each
+    PerClaimant,
+}
+
+impl AllocationRule {
+    /// Whether this rule draws from the budget shared by percentage cohorts
+    pub fn is_share_percentage(&self) -> bool {
+        matches!(self, AllocationRule::SharePercentage(_))
+    }
+}
+
+/// Optional bounds on any single claimant's allocation (in budget tokens)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ClaimantCaps {
+    pub min: Option<Decimal>,
+    pub max: Option<Decimal>,
+}
+
+impl ClaimantCaps {
+    pub fn is_empty(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    /// Caps in base units: the minimum rounds up and the maximum rounds down
+    fn in_units(&self, mint_decimals: u8) -> AllocationResult<(Option<Decimal>, Option<Decimal>)> {
+        let scale = Decimal::from(10_u64.pow(mint_decimals as u32));
+        let min = self.min.map(|min| (min * scale).ceil());
+        let max = self.max.map(|max| (max * scale).floor());
+
+        if max.is_some_and(|max| max.is_zero()) {
+            return Err(AllocationError::AllocationFailed(format!(
+                "Maximum per claimant {} is below one base unit",
+                self.max.unwrap_or_default()
+            )));
+        }
+
+        Ok((min, max))
+    }
+}
+
+/// Clamp an amount (in base units) to the caps
+fn clamp_units(amount: Decimal, min: Option<Decimal>, max: Option<Decimal>) -> Decimal {
+    let amount = max.map_or(amount, |max| amount.min(max));
+    min.map_or(amount, |min| amount.max(min))
+}
+
+/// A cohort to allocate as part of a campaign
+#[derive(Debug, Clone, PartialEq)]
+pub struct CohortAllocationRequest {
+    pub rule: AllocationRule,
+    pub caps: ClaimantCaps,
+    /// Entitlements per claimant, in leaf order
+    pub entitlements: Vec<u64>,
+}
+
+/// Cohort allocation together with each claimant's effective entitlements
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedCohortAllocation {
+    pub allocation: CohortAllocation,
+
+    /// Entitlements per claimant after caps (same order as the request)
+    pub entitlements: Vec<u64>,
+}
+
+/// Allocation of every cohort of a campaign
+#[derive(Debug, Clone, PartialEq)]
+pub struct CampaignAllocation {
+    /// Per-cohort allocations, in request order
+    pub cohorts: Vec<ResolvedCohortAllocation>,
+
+    /// Budget not drawn by any cohort (only non-zero without percentage cohorts,
+    /// or when their shares add up to less than 100%)
+    pub unallocated_budget: Decimal,
+}
+
 /// Budget allocator with mint decimal constraints
 pub struct BudgetAllocator {
     total_budget: Decimal,
@@ -117,6 +227,19 @@ impl BudgetAllocator {
         // Calculate cohort's total allocation
         let cohort_total = self.total_budget * (share_percentage / Decimal::from(100));
 
+        self.split_evenly(cohort_total, total_entitlements)
+    }
+
+    /// Split a cohort total evenly across entitlements, rounding down to mint precision
+    fn split_evenly(
+        &self,
+        cohort_total: Decimal,
+        total_entitlements: u64,
+    ) -> AllocationResult<CohortAllocation> {
+        if total_entitlements == 0 {
+            return Err(AllocationError::ZeroEntitlements);
+        }
+
         // Calculate raw amount per entitlement
         let raw_amount_per_entitlement = cohort_total / Decimal::from(total_entitlements);
 
@@ -135,6 +258,298 @@ impl BudgetAllocator {
         })
     }
 
+    /// Allocate a single cohort under any [`AllocationRule`], applying claimant caps
+    ///
+    /// `SharePercentage` is taken against this allocator's budget; use
+    /// [`BudgetAllocator::allocate_campaign`] to mix rules within one campaign.
+    pub fn allocate_cohort(
+        &self,
+        rule: AllocationRule,
+        caps: ClaimantCaps,
+        entitlements: &[u64],
+    ) -> AllocationResult<ResolvedCohortAllocation> {
+        self.resolve_cohort(rule, caps, entitlements.to_vec())
+    }
+
+    /// [`BudgetAllocator::allocate_cohort`], reusing `entitlements` for the result
+    fn resolve_cohort(
+        &self,
+        rule: AllocationRule,
+        caps: ClaimantCaps,
+        entitlements: Vec<u64>,
+    ) -> AllocationResult<ResolvedCohortAllocation> {
+        let total_entitlements = entitlements
+            .iter()
+            .try_fold(0u64, |acc, e| acc.checked_add(*e))
+            .ok_or_else(|| AllocationError::Overflow("Total entitlements".to_string()))?;
+
+        if total_entitlements == 0 {
+            return Err(AllocationError::ZeroEntitlements);
+        }
+
+        let cohort_total = match rule {
+            AllocationRule::SharePercentage(share_percentage) => {
+                if share_percentage < Decimal::ZERO || share_percentage > Decimal::from(100) {
+                    return Err(AllocationError::InvalidPercentage(share_percentage));
+                }
+                self.total_budget * (share_percentage / Decimal::from(100))
+            }
+            AllocationRule::CohortBudget(cohort_budget) => {
+                if cohort_budget < Decimal::ZERO {
+                    return Err(AllocationError::AllocationFailed(format!(
+                        "Cohort budget must not be negative: {}",
+                        cohort_budget
+                    )));
+                }
+                cohort_budget
+            }
+            AllocationRule::FixedPerEntitlement(amount_per_entitlement) => {
+                return self.allocate_fixed(amount_per_entitlement, caps, entitlements);
+            }
+            AllocationRule::PerClaimant => {
+                return self.allocate_fixed(self.decimal_precision, caps, entitlements);
+            }
+        };
+
+        if caps.is_empty() {
+            let allocation = self.split_evenly(cohort_total, total_entitlements)?;
+            return Ok(ResolvedCohortAllocation {
+                allocation,
+                entitlements,
+            });
+        }
+
+        self.allocate_capped(cohort_total, caps, entitlements)
+    }
+
+    /// Allocate every cohort of a campaign
+    ///
+    /// Non-percentage cohorts are resolved first; percentage cohorts then share
+    /// whatever budget remains. Budget left over after every cohort is reported
+    /// rather than dropped. Each request's entitlements are moved into its resolved
+    /// allocation, so a campaign's entitlements are held only once.
+    pub fn allocate_campaign(
+        &self,
+        requests: Vec<CohortAllocationRequest>,
+    ) -> AllocationResult<CampaignAllocation> {
+        let mut results: Vec<Option<ResolvedCohortAllocation>> = vec![None; requests.len()];
+        let mut share_requests = Vec::new();
+        let mut explicit_total = Decimal::ZERO;
+
+        for (index, request) in requests.into_iter().enumerate() {
+            if request.rule.is_share_percentage() {
+                share_requests.push((index, request));
+                continue;
+            }
+            let resolved = self.resolve_cohort(request.rule, request.caps, request.entitlements)?;
+            explicit_total += resolved.allocation.cohort_total;
+            results[index] = Some(resolved);
+        }
+
+        if explicit_total > self.total_budget {
+            return Err(AllocationError::BudgetExceeded {
+                required: explicit_total,
+                budget: self.total_budget,
+            });
+        }
+
+        let share_allocator =
+            BudgetAllocator::new(self.total_budget - explicit_total, self.mint_decimals)?;
+
+        let mut share_total = Decimal::ZERO;
+        for (index, request) in share_requests {
+            let resolved =
+                share_allocator.resolve_cohort(request.rule, request.caps, request.entitlements)?;
+            share_total += resolved.allocation.cohort_total;
+            results[index] = Some(resolved);
+        }
+
+        Ok(CampaignAllocation {
+            cohorts: results.into_iter().flatten().collect(),
+            unallocated_budget: self.total_budget - explicit_total - share_total,
+        })
+    }
+
+    /// Allocate a cohort whose amount per entitlement is known up front
+    fn allocate_fixed(
+        &self,
+        amount_per_entitlement: Decimal,
+        caps: ClaimantCaps,
+        mut entitlements: Vec<u64>,
+    ) -> AllocationResult<ResolvedCohortAllocation> {
+        if amount_per_entitlement <= Decimal::ZERO {
+            return Err(AllocationError::AllocationFailed(format!(
+                "Amount per entitlement must be positive: {}",
+                amount_per_entitlement
+            )));
+        }
+
+        if self.round_to_mint_precision(amount_per_entitlement) != amount_per_entitlement {
+            return Err(AllocationError::PrecisionExceeded {
+                amount: amount_per_entitlement,
+                decimals: self.mint_decimals,
+            });
+        }
+
+        if !caps.is_empty() {
+            // Caps rarely fall on a whole number of entitlements, so pay each claimant
+            // in base units to hold them exactly
+            let (min, max) = caps.in_units(self.mint_decimals)?;
+            let units_per_entitlement = amount_per_entitlement / self.decimal_precision;
+            for entitlement in entitlements.iter_mut() {
+                *entitlement = units_to_u64(clamp_units(
+                    Decimal::from(*entitlement) * units_per_entitlement,
+                    min,
+                    max,
+                ))?;
+            }
+            let cohort_total = self.decimal_precision * sum_entitlements(&entitlements);
+            return self.resolve_payouts(cohort_total, entitlements);
+        }
+
+        let cohort_total = amount_per_entitlement * sum_entitlements(&entitlements);
+
+        Ok(ResolvedCohortAllocation {
+            allocation: CohortAllocation {
+                cohort_total,
+                amount_per_entitlement,
+                amount_per_entitlement_humane: amount_per_entitlement.to_string(),
+                dust_amount: Decimal::ZERO,
+            },
+            entitlements,
+        })
+    }
+
+    /// Split a cohort total across entitlements while honouring claimant caps
+    ///
+    /// Water-fills in base units: claimants are sorted by entitlements, and the
+    /// payout per entitlement `rate` is found such that paying every claimant
+    /// `clamp(entitlements * rate, min, max)` spends the cohort total. Claimants
+    /// whose uncapped share crosses a cap are paid exactly the cap; the others are
+    /// paid their share rounded down, which leaves the rounding as dust.
+    fn allocate_capped(
+        &self,
+        cohort_total: Decimal,
+        caps: ClaimantCaps,
+        mut entitlements: Vec<u64>,
+    ) -> AllocationResult<ResolvedCohortAllocation> {
+        let (min, max) = caps.in_units(self.mint_decimals)?;
+        let total_units = (cohort_total / self.decimal_precision).floor();
+
+        if let Some(min) = caps.min {
+            let required = min * Decimal::from(entitlements.len());
+            if required > cohort_total {
+                return Err(AllocationError::BudgetExceeded {
+                    required,
+                    budget: cohort_total,
+                });
+            }
+        }
+
+        let mut sorted = entitlements.to_vec();
+        sorted.sort_unstable();
+        let mut prefix = Vec::with_capacity(sorted.len() + 1);
+        prefix.push(Decimal::ZERO);
+        for entitlement in &sorted {
+            prefix.push(prefix[prefix.len() - 1] + Decimal::from(*entitlement));
+        }
+
+        // Claimants below `min` form a prefix of the sorted entitlements and claimants
+        // above `max` a suffix; returns (min-capped, first max-capped)
+        let partition = |rate: Decimal| {
+            let below = min.map_or(0, |min| {
+                sorted.partition_point(|e| Decimal::from(*e) * rate < min)
+            });
+            let above = max.map_or(sorted.len(), |max| {
+                sorted.partition_point(|e| Decimal::from(*e) * rate < max)
+            });
+            (below, above.max(below))
+        };
+        let spent = |rate: Decimal| {
+            let (below, above) = partition(rate);
+            min.unwrap_or_default() * Decimal::from(below)
+                + (prefix[above] - prefix[below]) * rate
+                + max.unwrap_or_default() * Decimal::from(sorted.len() - above)
+        };
+
+        // The spend is piecewise linear in the rate, with a breakpoint wherever a
+        // claimant crosses a cap: find the last breakpoint that fits the total and
+        // solve the linear piece after it
+        let mut breakpoints = sorted
+            .iter()
+            .filter(|e| **e > 0)
+            .flat_map(|e| {
+                [min, max]
+                    .into_iter()
+                    .flatten()
+                    .map(|cap| cap / Decimal::from(*e))
+            })
+            .collect::<Vec<_>>();
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+        let fitting = breakpoints.partition_point(|rate| spent(*rate) <= total_units);
+        let floor_rate = fitting
+            .checked_sub(1)
+            .map_or(Decimal::ZERO, |index| breakpoints[index]);
+
+        let (below, above) = partition(floor_rate);
+        let uncapped_entitlements = prefix[above] - prefix[below];
+        let capped_spend = min.unwrap_or_default() * Decimal::from(below)
+            + max.unwrap_or_default() * Decimal::from(sorted.len() - above);
+        drop((sorted, prefix, breakpoints));
+
+        // Pay uncapped claimants `e * remaining / uncapped_entitlements` in exact integer
+        // arithmetic, so shares that divide evenly are not lost to rounding
+        let uncapped_share = if uncapped_entitlements.is_zero() {
+            None
+        } else {
+            Some((
+                to_u128(total_units - capped_spend)?,
+                to_u128(uncapped_entitlements)?,
+            ))
+        };
+        for entitlement in entitlements.iter_mut() {
+            let amount = match uncapped_share {
+                Some((remaining, uncapped_entitlements)) => (*entitlement as u128)
+                    .checked_mul(remaining)
+                    .map(|units| Decimal::from(units / uncapped_entitlements))
+                    .ok_or_else(|| AllocationError::Overflow("Claimant share".to_string()))?,
+                None => Decimal::from(*entitlement) * floor_rate,
+            };
+            *entitlement = units_to_u64(clamp_units(amount, min, max))?;
+        }
+
+        self.resolve_payouts(cohort_total, entitlements)
+    }
+
+    /// Re-denominate a cohort to one base unit per entitlement, paying each claimant
+    /// their `payouts` (in base units)
+    fn resolve_payouts(
+        &self,
+        cohort_total: Decimal,
+        payouts: Vec<u64>,
+    ) -> AllocationResult<ResolvedCohortAllocation> {
+        let allocated = self.decimal_precision * sum_entitlements(&payouts);
+
+        if allocated > cohort_total {
+            return Err(AllocationError::AllocationFailed(format!(
+                "Cohort total {} cannot satisfy claimant caps for {} claimants",
+                cohort_total,
+                payouts.len()
+            )));
+        }
+
+        Ok(ResolvedCohortAllocation {
+            allocation: CohortAllocation {
+                cohort_total,
+                amount_per_entitlement: self.decimal_precision,
+                amount_per_entitlement_humane: self.decimal_precision.to_string(),
+                dust_amount: cohort_total - allocated,
+            },
+            entitlements: payouts,
+        })
+    }
+
     /// Calculate total dust across all cohort allocations
     pub fn calculate_total_dust(
         &self,
@@ -171,6 +586,35 @@ impl BudgetAllocator {
     }
 }
 
+/// Convert a token amount to base units (10^-decimals tokens), rounding down
+pub fn to_base_units(amount: Decimal, mint_decimals: u8) -> AllocationResult<u64> {
+    Decimal::from(10_u64.pow(mint_decimals as u32))
+        .checked_mul(amount)
+        .and_then(|units| units.floor().to_u64())
+        .ok_or_else(|| AllocationError::Overflow(format!("{} tokens in base units", amount)))
+}
+
+/// Convert base units back to a token amount
+pub fn from_base_units(units: u64, mint_decimals: u8) -> Decimal {
+    Decimal::from_i128_with_scale(units as i128, mint_decimals as u32)
+}
+
+fn units_to_u64(units: Decimal) -> AllocationResult<u64> {
+    units
+        .to_u64()
+        .ok_or_else(|| AllocationError::Overflow(format!("Payout of {} units", units)))
+}
+
+fn to_u128(amount: Decimal) -> AllocationResult<u128> {
+    amount
+        .to_u128()
+        .ok_or_else(|| AllocationError::Overflow(format!("{} in base units", amount)))
+}
+
+fn sum_entitlements(entitlements: &[u64]) -> Decimal {
+    entitlements.iter().map(|e| Decimal::from(*e)).sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,4 +767,268 @@ mod tests {
         // Verify the actual dust amount is reasonable for SOL
         assert_eq!(total_dust, Decimal::from_str("0.00000001").unwrap()); // Exactly 10 lamports
     }
+
+    #[test]
+    fn test_base_unit_conversion() {
+        let amount = Decimal::from_str("1.234567891").unwrap();
+        assert_eq!(to_base_units(amount, 9).unwrap(), 1_234_567_891);
+        assert_eq!(from_base_units(1_234_567_891, 9), amount);
+
+        // Sub-unit remainders round down
+        assert_eq!(to_base_units(amount, 6).unwrap(), 1_234_567);
+        assert_eq!(to_base_units(Decimal::from(7), 0).unwrap(), 7);
+
+        assert!(matches!(
+            to_base_units(Decimal::from(u64::MAX), 9),
+            Err(AllocationError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_fixed_per_entitlement_allocation() {
+        let allocator = BudgetAllocator::new(Decimal::from(1000), 6).unwrap();
+
+        let resolved = allocator
+            .allocate_cohort(
+                AllocationRule::FixedPerEntitlement(Decimal::from_str("2.5").unwrap()),
+                ClaimantCaps::default(),
+                &[10, 30],
+            )
+            .unwrap();
+
+        // Budget is derived: 40 entitlements * 2.5 = 100
+        assert_eq!(resolved.allocation.cohort_total, Decimal::from(100));
+        assert_eq!(resolved.allocation.dust_amount, Decimal::ZERO);
+        assert_eq!(resolved.entitlements, vec![10, 30]);
+
+        // Amounts finer than the mint allows are rejected rather than silently rounded
+        let result = allocator.allocate_cohort(
+            AllocationRule::FixedPerEntitlement(Decimal::from_str("0.0000001").unwrap()),
+            ClaimantCaps::default(),
+            &[1],
+        );
+        assert!(matches!(
+            result,
+            Err(AllocationError::PrecisionExceeded { decimals: 6, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cohort_budget_allocation() {
+        let allocator = BudgetAllocator::new(Decimal::from(1000), 0).unwrap();
+
+        let resolved = allocator
+            .allocate_cohort(
+                AllocationRule::CohortBudget(Decimal::from(100)),
+                ClaimantCaps::default(),
+                &[1, 2],
+            )
+            .unwrap();
+
+        // 100 / 3 = 33 whole tokens per entitlement, 1 token of dust
+        assert_eq!(resolved.allocation.cohort_total, Decimal::from(100));
+        assert_eq!(
+            resolved.allocation.amount_per_entitlement,
+            Decimal::from(33)
+        );
+        assert_eq!(resolved.allocation.dust_amount, Decimal::from(1));
+    }
+
+    #[test]
+    fn test_per_claimant_allocation_with_caps() {
+        let allocator = BudgetAllocator::new(Decimal::from(10_000), 2).unwrap();
+
+        // Entitlements are base units: 0.01 tokens each with 2 decimals
+        let resolved = allocator
+            .allocate_cohort(
+                AllocationRule::PerClaimant,
+                ClaimantCaps {
+                    min: Some(Decimal::from_str("0.5").unwrap()),
+                    max: Some(Decimal::from(10)),
+                },
+                &[10, 500, 5000],
+            )
+            .unwrap();
+
+        assert_eq!(
+            resolved.allocation.amount_per_entitlement,
+            Decimal::from_str("0.01").unwrap()
+        );
+        assert_eq!(resolved.entitlements, vec![50, 500, 1000]);
+        assert_eq!(
+            resolved.allocation.cohort_total,
+            Decimal::from_str("15.5").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_max_cap_redistributes_budget() {
+        let allocator = BudgetAllocator::new(Decimal::from(1000), 0).unwrap();
+
+        let resolved = allocator
+            .allocate_cohort(
+                AllocationRule::SharePercentage(Decimal::from(100)),
+                ClaimantCaps {
+                    min: None,
+                    max: Some(Decimal::from(400)),
+                },
+                &[1, 1, 8],
+            )
+            .unwrap();
+
+        // Uncapped: 100 per entitlement would give the third claimant 800.
+        // Capped at 400, the remaining 600 is split across the other 2 entitlements.
+        let allocation = &resolved.allocation;
+        assert_eq!(allocation.amount_per_entitlement, Decimal::ONE);
+        assert_eq!(resolved.entitlements, vec![300, 300, 400]);
+        assert_eq!(allocation.dust_amount, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_capped_claimant_receives_exact_cap() {
+        let allocator = BudgetAllocator::new(Decimal::from(1000), 6).unwrap();
+
+        let resolved = allocator
+            .allocate_cohort(
+                AllocationRule::CohortBudget(Decimal::from(1000)),
+                ClaimantCaps {
+                    min: Some(Decimal::from(30)),
+                    max: Some(Decimal::from_str("333.333333").unwrap()),
+                },
+                &[1, 3, 3, 7, 50, 60],
+            )
+            .unwrap();
+
+        let payouts: Vec<Decimal> = resolved
+            .entitlements
+            .iter()
+            .map(|e| Decimal::from(*e) * resolved.allocation.amount_per_entitlement)
+            .collect();
+
+        // The smallest claimant is lifted to the minimum and the two largest are held
+        // at the maximum, each exactly; the others split the rest pro rata
+        assert_eq!(payouts[0], Decimal::from(30));
+        assert_eq!(payouts[4], Decimal::from_str("333.333333").unwrap());
+        assert_eq!(payouts[5], Decimal::from_str("333.333333").unwrap());
+        assert_eq!(payouts[1], payouts[2]);
+        assert!(payouts[3] > payouts[2] && payouts[3] < payouts[4]);
+
+        let allocated: Decimal = payouts.iter().sum();
+        assert_eq!(
+            allocated + resolved.allocation.dust_amount,
+            Decimal::from(1000)
+        );
+        assert!(resolved.allocation.dust_amount < Decimal::from_str("0.00001").unwrap());
+
+        // Fixed amounts are capped exactly too, even off an entitlement boundary
+        let resolved = allocator
+            .allocate_cohort(
+                AllocationRule::FixedPerEntitlement(Decimal::from_str("2.5").unwrap()),
+                ClaimantCaps {
+                    min: None,
+                    max: Some(Decimal::from(11)),
+                },
+                &[2, 10],
+            )
+            .unwrap();
+        assert_eq!(resolved.entitlements, vec![5_000_000, 11_000_000]);
+        assert_eq!(resolved.allocation.cohort_total, Decimal::from(16));
+    }
+
+    #[test]
+    fn test_min_cap_never_overspends() {
+        let allocator = BudgetAllocator::new(Decimal::from(100), 0).unwrap();
+
+        let resolved = allocator
+            .allocate_cohort(
+                AllocationRule::SharePercentage(Decimal::from(100)),
+                ClaimantCaps {
+                    min: Some(Decimal::from(15)),
+                    max: None,
+                },
+                &[1, 3, 6],
+            )
+            .unwrap();
+
+        let allocation = &resolved.allocation;
+        let allocated = allocation.amount_per_entitlement
+            * Decimal::from(resolved.entitlements.iter().sum::<u64>());
+        assert_eq!(allocated + allocation.dust_amount, Decimal::from(100));
+        for entitlements in &resolved.entitlements {
+            assert!(
+                Decimal::from(*entitlements) * allocation.amount_per_entitlement
+                    >= Decimal::from(15)
+            );
+        }
+
+        // Minimums that cannot all be honoured are an error
+        let result = allocator.allocate_cohort(
+            AllocationRule::SharePercentage(Decimal::from(100)),
+            ClaimantCaps {
+                min: Some(Decimal::from(40)),
+                max: None,
+            },
+            &[1, 1, 1],
+        );
+        assert!(matches!(
+            result,
+            Err(AllocationError::BudgetExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn test_allocate_campaign_mixed_rules() {
+        let allocator = BudgetAllocator::new(Decimal::from(1000), 0).unwrap();
+
+        let requests = vec![
+            CohortAllocationRequest {
+                rule: AllocationRule::SharePercentage(Decimal::from(100)),
+                caps: ClaimantCaps::default(),
+                entitlements: vec![100, 100],
+            },
+            CohortAllocationRequest {
+                rule: AllocationRule::CohortBudget(Decimal::from(300)),
+                caps: ClaimantCaps::default(),
+                entitlements: vec![3],
+            },
+            CohortAllocationRequest {
+                rule: AllocationRule::FixedPerEntitlement(Decimal::from(5)),
+                caps: ClaimantCaps::default(),
+                entitlements: vec![20],
+            },
+        ];
+
+        let allocation = allocator.allocate_campaign(requests.clone()).unwrap();
+        let resolved = &allocation.cohorts;
+
+        // Explicit cohorts take 300 + 100; the share cohort splits the remaining 600
+        assert_eq!(resolved.len(), 3);
+        assert_eq!(allocation.unallocated_budget, Decimal::ZERO);
+        assert_eq!(resolved[0].allocation.cohort_total, Decimal::from(600));
+        assert_eq!(
+            resolved[0].allocation.amount_per_entitlement,
+            Decimal::from(3)
+        );
+        assert_eq!(
+            resolved[1].allocation.amount_per_entitlement,
+            Decimal::from(100)
+        );
+        assert_eq!(resolved[2].allocation.cohort_total, Decimal::from(100));
+
+        // Without a percentage cohort the remainder is reported, not dropped
+        let allocation = allocator.allocate_campaign(requests[1..].to_vec()).unwrap();
+        assert_eq!(allocation.cohorts.len(), 2);
+        assert_eq!(allocation.unallocated_budget, Decimal::from(600));
+
+        // Explicit allocations beyond the budget are rejected
+        let over_budget = vec![CohortAllocationRequest {
+            rule: AllocationRule::FixedPerEntitlement(Decimal::from(11)),
+            caps: ClaimantCaps::default(),
+            entitlements: vec![100],
+        }];
+        assert!(matches!(
+            allocator.allocate_campaign(over_budget),
+            Err(AllocationError::BudgetExceeded { .. })
+        ));
+    }
 }
@@ -10,16 +10,31 @@ from CSV inputs and generating the structured data needed for on-chain deploymen
 - Calculate campaign fingerprint from merkle roots
 - Derive all protocol addresses (campaign, cohorts, vaults)
 - Calculate vault funding requirements
+- Account for (and optionally redistribute) rounding dust
 - Return populated in-memory database ready for use
 */
 
-use crate::budget_allocation::{AllocationError, BudgetAllocator};
+use crate::budget_allocation::{
+    from_base_units, to_base_units, AllocationError, AllocationRule, BudgetAllocator, ClaimantCaps,
+    CohortAllocationRequest,
+};
+use crate::campaign_layout::{CampaignLayout, MAX_VAULTS_PER_COHORT};
+use crate::dust::DustPolicy;
 use crate::AddressFinder;
-use prism_protocol::ClaimLeaf;
-use prism_protocol_csvs::{validate_csv_consistency, CampaignCsvRow, CohortsCsvRow};
-use prism_protocol_db::CampaignDatabase;
-use prism_protocol_merkle::{create_claim_tree_v0, ClaimTreeV0};
-use rust_decimal::{prelude::ToPrimitive, Decimal};
+use prism_protocol::{ClaimHashScheme, ClaimLeaf};
+use prism_protocol_csvs::{
+    validate_csv_consistency, AllocationMode, CampaignCsvRow, CohortsCsvRow,
+};
+use prism_protocol_db::{
+    CampaignDatabase, ClaimantRecord, CohortDust, CohortTree, DustReport, DustSummary, StoredProof,
+    VaultDust,
+};
+use prism_protocol_merkle::{
+    claim_tree_constants::{BRANCHING_FACTOR, MIN_BRANCHING_FACTOR},
+    create_claim_tree_v0_with_order, create_claim_tree_v1_with_options, ClaimTreeV0, ClaimTreeV1,
+    ClaimTreeV1Options, ClaimantIndexTree, LeafOrder, NonMembershipResult, TreeArtifactResult,
+};
+use rust_decimal::Decimal;
 use sha2::{Digest, Sha256};
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
@@ -38,6 +53,12 @@ pub enum CompilerError {
 
     #[error("Budget allocation failed: {0}")]
     BudgetAllocation(#[from] AllocationError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid manifest: {0}")]
+    Manifest(String),
 }
 
 pub type CompilerResult<T> = Result<T, CompilerError>;
@@ -45,6 +66,87 @@ pub type CompilerResult<T> = Result<T, CompilerError>;
 /// Internal representation of claimant data (claimant, entitlements)
 type ClaimantData = (Pubkey, u64);
 
+/// Optional compilation settings
+#[derive(Debug, Clone, Default)]
+pub struct CompileOptions {
+    /// What to do with budget left over after rounding
+    pub dust_policy: DustPolicy,
+    /// Leaf order of every cohort tree (claimant order ignores row order and indexes
+    /// claimants for non-membership proofs)
+    pub leaf_order: LeafOrder,
+    /// Per-cohort vault counts taking precedence over `claimants_per_vault`
+    pub vault_counts: HashMap<String, usize>,
+    /// Per-cohort arities of wide (V1) trees; cohorts not listed get binary V0 trees
+    pub tree_arities: HashMap<String, usize>,
+    /// Hash scheme of every V1 tree; binary V0 trees are always SHA-256
+    pub hash_scheme: ClaimHashScheme,
+}
+
+impl CompileOptions {
+    /// Options compiling cohorts into a planned layout: its vault counts, and V1 trees
+    /// of the planned arity for cohorts planned as V1 (replacing `tree_arities`)
+    pub fn with_layout(mut self, layout: &CampaignLayout) -> Self {
+        self.vault_counts = layout.vault_counts();
+        self.tree_arities = layout.tree_arities();
+        self
+    }
+
+    /// Vault count of a cohort: as planned, or enough for `claimants_per_vault`
+    ///
+    /// Fails when `claimants_per_vault` is zero, and when the cohort would need more
+    /// vaults than a `u8` vault index can address.
+    pub(crate) fn vault_count(
+        &self,
+        cohort: &str,
+        claimant_count: usize,
+        claimants_per_vault: usize,
+    ) -> CompilerResult<usize> {
+        let vault_count = match self.vault_counts.get(cohort) {
+            Some(vault_count) => *vault_count,
+            None if claimants_per_vault == 0 => {
+                return Err(CompilerError::InvalidConfig(
+                    "claimants_per_vault must be at least 1".to_string(),
+                ));
+            }
+            None => claimant_count.div_ceil(claimants_per_vault),
+        };
+
+        if !(1..=MAX_VAULTS_PER_COHORT).contains(&vault_count) {
+            return Err(CompilerError::InvalidConfig(format!(
+                "Cohort '{}' needs {} vaults, but a cohort holds 1..={}",
+                cohort, vault_count, MAX_VAULTS_PER_COHORT
+            )));
+        }
+        Ok(vault_count)
+    }
+
+    /// Tree a cohort compiles into: V1 of its listed arity, or binary V0
+    ///
+    /// Fails for an arity the program rejects, and for a V0 tree under a hash scheme
+    /// other than SHA-256.
+    pub(crate) fn cohort_tree(&self, cohort: &str) -> CompilerResult<CohortTree> {
+        match self.tree_arities.get(cohort).copied() {
+            Some(arity) if !(MIN_BRANCHING_FACTOR..=BRANCHING_FACTOR).contains(&arity) => {
+                Err(CompilerError::InvalidConfig(format!(
+                    "Cohort '{}' tree arity {} is outside {}..={}",
+                    cohort, arity, MIN_BRANCHING_FACTOR, BRANCHING_FACTOR
+                )))
+            }
+            Some(arity) => Ok(CohortTree::V1 {
+                arity: arity as u16,
+                hash_scheme: self.hash_scheme.into(),
+            }),
+            None if self.hash_scheme != ClaimHashScheme::Sha256 => {
+                Err(CompilerError::InvalidConfig(format!(
+                    "Cohort '{}' has a binary V0 tree, which is always SHA-256; give it a tree arity to use {}",
+                    cohort, self.hash_scheme
+                )))
+            }
+            None => Ok(CohortTree::V0),
+        }
+    }
+}
+
 /// Internal cohort data during processing
 #[derive(Debug)]
 struct CohortData {
@@ -53,6 +155,72 @@ struct CohortData {
     amount_per_entitlement_humane: String,
     claimants: Vec<ClaimantData>,
     vault_count: usize,
+    cohort_budget: Decimal,
+    allocation_dust: Decimal,
+    redistributed_dust: Decimal,
+}
+
+/// Merkle tree of a compiled cohort
+#[derive(Clone)]
+pub enum CohortMerkleTree {
+    /// Binary tree, deployed with `initialize_cohort_v0`
+    V0(ClaimTreeV0),
+    /// Wide tree of a fixed arity, deployed with `initialize_cohort_v1`
+    V1(ClaimTreeV1),
+}
+
+impl CohortMerkleTree {
+    /// Leaves in tree order
+    pub fn leaves(&self) -> &[ClaimLeaf] {
+        match self {
+            CohortMerkleTree::V0(tree) => &tree.leaves,
+            CohortMerkleTree::V1(tree) => &tree.leaves,
+        }
+    }
+
+    /// Merkle root, if the tree has leaves
+    pub fn root(&self) -> Option<[u8; 32]> {
+        match self {
+            CohortMerkleTree::V0(tree) => tree.root(),
+            CohortMerkleTree::V1(tree) => tree.root(),
+        }
+    }
+
+    /// Tree layout as recorded in the campaign database
+    pub fn layout(&self) -> CohortTree {
+        match self {
+            CohortMerkleTree::V0(_) => CohortTree::V0,
+            CohortMerkleTree::V1(tree) => CohortTree::V1 {
+                arity: tree.arity() as u16,
+                hash_scheme: tree.hash_scheme().into(),
+            },
+        }
+    }
+
+    /// Proof of a claimant's leaf, in the format stored in the campaign database
+    pub fn stored_proof(&self, claimant: &Pubkey) -> CompilerResult<StoredProof> {
+        match self {
+            CohortMerkleTree::V0(tree) => tree.proof_for_claimant(claimant).map(StoredProof::V0),
+            CohortMerkleTree::V1(tree) => tree.proof_for_claimant(claimant).map(StoredProof::V1),
+        }
+        .map_err(|e| CompilerError::MerkleTree(format!("Failed to generate proof: {}", e)))
+    }
+
+    /// Claimant index over the leaves, for non-membership proofs (claimant order only)
+    pub fn claimant_index(&self) -> NonMembershipResult<ClaimantIndexTree> {
+        match self {
+            CohortMerkleTree::V0(tree) => tree.claimant_index(),
+            CohortMerkleTree::V1(tree) => tree.claimant_index(),
+        }
+    }
+
+    /// Serialize the tree as a [`TreeArtifact`](prism_protocol_merkle::TreeArtifact)
+    pub fn write_artifact<W: std::io::Write>(&self, writer: W) -> TreeArtifactResult<[u8; 32]> {
+        match self {
+            CohortMerkleTree::V0(tree) => tree.write_artifact(writer),
+            CohortMerkleTree::V1(tree) => tree.write_artifact(writer),
+        }
+    }
 }
 
 /// Compiled cohort with all derived data
@@ -62,17 +230,32 @@ pub struct CompiledCohort {
     pub address: Pubkey,
     pub merkle_root: [u8; 32],
     pub amount_per_entitlement: Decimal,
+    /// Amount per entitlement in base units, as committed on-chain
+    pub amount_per_entitlement_u64: u64,
     pub amount_per_entitlement_humane: String,
     pub vault_count: usize,
     pub vaults: Vec<CompiledVault>,
-    pub merkle_tree: ClaimTreeV0,
+    pub merkle_tree: CohortMerkleTree,
+    /// Root of the claimant index, for cohorts compiled with [`LeafOrder::Claimant`]
+    pub claimant_index_root: Option<[u8; 32]>,
+    /// Tokens targeted at this cohort by its allocation rule
+    pub cohort_budget: Decimal,
+    /// Budget lost to per-entitlement rounding (after redistribution)
+    pub allocation_dust: Decimal,
+    /// Dust handed back to leaves by the dust policy
+    pub redistributed_dust: Decimal,
 }
 
 /// Compiled vault with address and required funding
 #[derive(Clone, Debug)]
 pub struct CompiledVault {
     pub address: Pubkey,
+    /// Funding in tokens (a whole number of base units)
     pub required_tokens: Decimal,
+    /// Funding in base units, as transferred on-chain
+    pub required_base_units: u64,
+    /// Unrounded share of the cohort allocation assigned to this vault
+    pub exact_share: Decimal,
 }
 
 /// Complete compilation result ready for database storage
@@ -87,9 +270,57 @@ pub struct CompiledCampaign {
     pub cohorts: Vec<CompiledCohort>,
     pub total_claimants: usize,
     pub total_vaults: usize,
+    pub dust_policy: DustPolicy,
 }
 
 impl CompiledCampaign {
+    /// Budget not covered by vault funding (budget minus total funding required)
+    pub fn total_dust(&self) -> Decimal {
+        self.budget - self.total_funding_required()
+    }
+
+    /// Budget not targeted at any cohort (e.g. explicit-only campaigns under budget)
+    pub fn unallocated_budget(&self) -> Decimal {
+        self.budget
+            - self
+                .cohorts
+                .iter()
+                .map(|cohort| cohort.cohort_budget)
+                .sum::<Decimal>()
+    }
+
+    /// Build the dust report persisted alongside the campaign
+    pub fn dust_report(&self) -> CompilerResult<DustReport> {
+        let cohorts = self
+            .cohorts
+            .iter()
+            .map(|cohort| {
+                cohort_dust(
+                    &cohort.name,
+                    cohort.cohort_budget,
+                    cohort.total_allocated(),
+                    cohort.allocation_dust,
+                    cohort.redistributed_dust,
+                    &cohort.vaults,
+                )
+            })
+            .collect();
+
+        let vaults = self
+            .cohorts
+            .iter()
+            .flat_map(|cohort| vault_dust(&cohort.name, &cohort.vaults))
+            .collect();
+
+        dust_report(
+            self.dust_policy,
+            self.budget,
+            self.mint_decimals,
+            cohorts,
+            vaults,
+        )
+    }
+
     /// Get total funding required across all vaults in all cohorts
     pub fn total_funding_required(&self) -> Decimal {
         self.cohorts
@@ -114,7 +345,7 @@ impl CompiledCampaign {
     ) -> Vec<(CompiledCohort, ClaimLeaf)> {
         let mut results = Vec::new();
         for cohort in &self.cohorts {
-            for leaf in &cohort.merkle_tree.leaves {
+            for leaf in cohort.merkle_tree.leaves() {
                 if leaf.claimant == *claimant {
                     results.push((cohort.clone(), leaf.clone()));
                 }
@@ -131,7 +362,7 @@ impl CompiledCampaign {
         cohort_name: &str,
     ) -> Option<(CompiledCohort, ClaimLeaf)> {
         if let Some(cohort) = self.find_cohort_by_name(cohort_name) {
-            for leaf in &cohort.merkle_tree.leaves {
+            for leaf in cohort.merkle_tree.leaves() {
                 if leaf.claimant == *claimant {
                     return Some((cohort.clone(), leaf.clone()));
                 }
@@ -152,7 +383,7 @@ impl CompiledCampaign {
     pub fn all_claimants(&self) -> Vec<Pubkey> {
         self.cohorts
             .iter()
-            .flat_map(|cohort| &cohort.merkle_tree.leaves)
+            .flat_map(|cohort| cohort.merkle_tree.leaves())
             .map(|leaf| leaf.claimant)
             .collect()
     }
@@ -164,6 +395,20 @@ impl CompiledCohort {
         self.vaults.iter().map(|vault| vault.required_tokens).sum()
     }
 
+    /// Total tokens claimable by this cohort's leaves (before flooring to vault funding)
+    pub fn total_allocated(&self) -> Decimal {
+        self.merkle_tree
+            .leaves()
+            .iter()
+            .map(|leaf| Decimal::from(leaf.entitlements) * self.amount_per_entitlement)
+            .sum()
+    }
+
+    /// Allocation lost when flooring vault funding to whole units
+    pub fn funding_dust(&self) -> Decimal {
+        self.total_allocated() - self.total_funding_required()
+    }
+
     /// Get vault addresses (for compatibility with existing code)
     pub fn vault_addresses(&self) -> Vec<Pubkey> {
         self.vaults.iter().map(|vault| vault.address).collect()
@@ -176,7 +421,7 @@ impl CompiledCohort {
 
     /// Find the vault assigned to a specific claimant
     pub fn find_claimant_vault(&self, claimant: &Pubkey) -> Option<&CompiledVault> {
-        for leaf in &self.merkle_tree.leaves {
+        for leaf in self.merkle_tree.leaves() {
             if leaf.claimant == *claimant {
                 return self.find_vault(leaf.assigned_vault_index);
             }
@@ -187,22 +432,33 @@ impl CompiledCohort {
     /// Find a claimant leaf by pubkey
     pub fn find_claimant(&self, claimant: &Pubkey) -> Option<&ClaimLeaf> {
         self.merkle_tree
-            .leaves
+            .leaves()
             .iter()
             .find(|leaf| leaf.claimant == *claimant)
     }
 
-    /// Generate merkle proof for a claimant (convenience wrapper)
+    /// Generate a binary merkle proof for a claimant (V0 cohorts only)
     pub fn proof_for_claimant(&self, claimant: &Pubkey) -> Result<Vec<[u8; 32]>, String> {
-        self.merkle_tree
-            .proof_for_claimant(claimant)
-            .map_err(|e| format!("Failed to generate proof: {}", e))
+        match &self.merkle_tree {
+            CohortMerkleTree::V0(tree) => tree
+                .proof_for_claimant(claimant)
+                .map_err(|e| format!("Failed to generate proof: {}", e)),
+            CohortMerkleTree::V1(_) => Err(format!(
+                "Cohort '{}' has a V1 tree; use stored_proof_for_claimant",
+                self.name
+            )),
+        }
+    }
+
+    /// Generate a merkle proof for a claimant in the cohort's tree format
+    pub fn stored_proof_for_claimant(&self, claimant: &Pubkey) -> CompilerResult<StoredProof> {
+        self.merkle_tree.stored_proof(claimant)
     }
 
     /// Get all claimant pubkeys in this cohort
     pub fn claimant_pubkeys(&self) -> Vec<Pubkey> {
         self.merkle_tree
-            .leaves
+            .leaves()
             .iter()
             .map(|leaf| leaf.claimant)
             .collect()
@@ -214,20 +470,29 @@ impl CompiledCohort {
             .map(|leaf| self.amount_per_entitlement * Decimal::from(leaf.entitlements))
     }
 
-    /// Calculate expected claim amount as u64 for a specific claimant in this cohort
+    /// Calculate expected claim amount in base units for a specific claimant in this cohort
     pub fn expected_claim_amount_u64(&self, claimant: &Pubkey) -> Option<u64> {
-        self.expected_claim_amount(claimant)
-            .and_then(|amount| amount.floor().to_u64())
+        self.find_claimant(claimant).and_then(|leaf| {
+            leaf.entitlements
+                .checked_mul(self.amount_per_entitlement_u64)
+        })
+    }
+
+    /// Total funding of this cohort's vaults in base units
+    pub fn total_funding_u64(&self) -> CompilerResult<u64> {
+        funding_u64(&self.vaults, &self.name)
     }
 }
 
 impl CompiledVault {
-    /// Convert required tokens to u64 for on-chain use (floors the amount)
-    pub fn required_tokens_u64(&self) -> Result<u64, String> {
-        self.required_tokens
-            .floor()
-            .to_u64()
-            .ok_or_else(|| format!("Token amount overflow: {}", self.required_tokens))
+    /// Required funding in base units for on-chain use
+    pub fn required_tokens_u64(&self) -> u64 {
+        self.required_base_units
+    }
+
+    /// Rounding difference between this vault's exact share and its funding
+    pub fn dust(&self) -> Decimal {
+        self.exact_share - self.required_tokens
     }
 }
 
@@ -236,7 +501,7 @@ impl CompiledVault {
 /// # Arguments
 /// * `address_finder` - For deriving protocol addresses
 /// * `campaign_csv` - Path to campaign.csv (cohort, claimant, entitlements)
-/// * `cohorts_csv` - Path to cohorts.csv (cohort, share_percentage)
+/// * `cohorts_csv` - Path to cohorts.csv (cohort, share_percentage, optional allocation mode columns)
 /// * `budget` - Total campaign budget in human-readable tokens (e.g., "1000.5" SOL)
 /// * `mint` - SPL token mint for the campaign
 /// * `mint_decimals` - Number of decimals for the token mint (e.g., 9 for SOL, 6 for USDC)
@@ -252,6 +517,52 @@ pub fn compile_campaign(
     admin: Pubkey,
     claimants_per_vault: usize,
 ) -> CompilerResult<CompiledCampaign> {
+    compile_campaign_with_options(
+        address_finder,
+        campaign_rows,
+        cohorts_rows,
+        &CampaignSettings {
+            budget,
+            mint,
+            mint_decimals,
+            admin,
+            claimants_per_vault,
+        },
+        &CompileOptions::default(),
+    )
+}
+
+/// Campaign-wide inputs of a compile, besides the campaign and cohort rows
+///
+/// Fields are as described on [`compile_campaign`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CampaignSettings {
+    pub budget: Decimal,
+    pub mint: Pubkey,
+    pub mint_decimals: u8,
+    pub admin: Pubkey,
+    pub claimants_per_vault: usize,
+}
+
+/// Compile campaign with explicit [`CompileOptions`]
+///
+/// Identical to [`compile_campaign`] except for the extra settings, such as the
+/// dust redistribution policy.
+pub fn compile_campaign_with_options(
+    address_finder: AddressFinder,
+    campaign_rows: &[CampaignCsvRow],
+    cohorts_rows: &[CohortsCsvRow],
+    campaign: &CampaignSettings,
+    options: &CompileOptions,
+) -> CompilerResult<CompiledCampaign> {
+    let CampaignSettings {
+        budget,
+        mint,
+        mint_decimals,
+        admin,
+        claimants_per_vault,
+    } = *campaign;
+
     // Step 1: Validate CSV consistency
     validate_csv_consistency(&campaign_rows, &cohorts_rows)?;
 
@@ -262,10 +573,11 @@ pub fn compile_campaign(
         budget,
         mint_decimals,
         claimants_per_vault,
+        options,
     )?;
 
     // Step 3: Generate merkle trees
-    let cohort_merkle_data = generate_merkle_trees(cohort_data)?;
+    let cohort_merkle_data = generate_merkle_trees(cohort_data, options)?;
 
     // Step 4: Calculate campaign fingerprint
     let cohort_roots: Vec<[u8; 32]> = cohort_merkle_data
@@ -285,12 +597,14 @@ pub fn compile_campaign(
         &address_finder, //
         cohort_merkle_data,
         &campaign_address,
+        mint_decimals,
+        options.leaf_order,
     )?;
 
     // Step 6: Calculate totals
     let total_claimants = compiled_cohorts
         .iter()
-        .map(|c| c.merkle_tree.leaves.len())
+        .map(|c| c.merkle_tree.leaves().len())
         .sum();
 
     let total_vaults = compiled_cohorts.iter().map(|c| c.vault_count).sum();
@@ -305,6 +619,7 @@ pub fn compile_campaign(
         cohorts: compiled_cohorts,
         total_claimants,
         total_vaults,
+        dust_policy: options.dust_policy,
     })
 }
 
@@ -319,15 +634,35 @@ pub fn compile_campaign_db(
     admin: Pubkey,
     claimants_per_vault: usize,
 ) -> CompilerResult<CampaignDatabase> {
-    let compiled_campaign = compile_campaign(
+    compile_campaign_db_with_options(
         address_finder,
         campaign_rows,
         cohorts_rows,
-        budget,
-        mint,
-        mint_decimals,
-        admin,
-        claimants_per_vault,
+        &CampaignSettings {
+            budget,
+            mint,
+            mint_decimals,
+            admin,
+            claimants_per_vault,
+        },
+        &CompileOptions::default(),
+    )
+}
+
+/// Compile campaign with explicit [`CompileOptions`] and populate database
+pub fn compile_campaign_db_with_options(
+    address_finder: AddressFinder,
+    campaign_rows: &[CampaignCsvRow],
+    cohorts_rows: &[CohortsCsvRow],
+    campaign: &CampaignSettings,
+    options: &CompileOptions,
+) -> CompilerResult<CampaignDatabase> {
+    let compiled_campaign = compile_campaign_with_options(
+        address_finder,
+        campaign_rows,
+        cohorts_rows,
+        campaign,
+        options,
     )?;
 
     let mut db = CampaignDatabase::create_in_memory()
@@ -338,23 +673,18 @@ pub fn compile_campaign_db(
     Ok(db)
 }
 
-/// Process cohorts: group claimants, calculate vault counts, and convert percentages to token amounts using BudgetAllocator
+/// Process cohorts: group claimants, calculate vault counts, and resolve each cohort's allocation rule to token amounts using BudgetAllocator
 fn process_cohorts(
     campaign_rows: &[CampaignCsvRow],
     cohorts_rows: &[CohortsCsvRow],
     budget: Decimal,
     mint_decimals: u8,
     claimants_per_vault: usize,
+    options: &CompileOptions,
 ) -> CompilerResult<Vec<CohortData>> {
     // Create budget allocator with mint constraints
     let allocator = BudgetAllocator::new(budget, mint_decimals)?;
 
-    // Create config lookup
-    let config_map: HashMap<String, &CohortsCsvRow> = cohorts_rows
-        .iter()
-        .map(|config| (config.cohort.clone(), config))
-        .collect();
-
     // Group claimants by cohort
     let mut cohort_groups: HashMap<String, Vec<ClaimantData>> = HashMap::new();
 
@@ -367,15 +697,15 @@ fn process_cohorts(
             .push(claimant_data);
     }
 
-    // Convert to CohortData with vault counts and calculated token amounts using BudgetAllocator
-    let mut cohort_data = Vec::new();
-    for (cohort_name, claimants) in cohort_groups {
-        let config = config_map.get(&cohort_name).ok_or_else(|| {
-            CompilerError::InvalidConfig(format!("No config found for cohort: {}", cohort_name))
-        })?;
-
-        // Calculate vault count needed
-        let vault_count = (claimants.len() + claimants_per_vault - 1) / claimants_per_vault;
+    // Build one allocation request per cohort, in cohorts.csv order so the
+    // fingerprint (which hashes roots in order) is reproducible
+    let mut cohort_names = Vec::new();
+    let mut requests = Vec::new();
+    for config in cohorts_rows {
+        let cohort_name = &config.cohort;
+        let Some(claimants) = cohort_groups.get(cohort_name) else {
+            continue;
+        };
 
         // Calculate total entitlements for this cohort
         let total_entitlements: u64 = claimants.iter().map(|(_, entitlements)| entitlements).sum();
@@ -387,28 +717,90 @@ fn process_cohorts(
             )));
         }
 
-        // Use BudgetAllocator for safe, precise calculations
-        let allocation = allocator.calculate_cohort_allocation(
-            config.share_percentage, //
-            total_entitlements,
+        cohort_names.push(cohort_name.clone());
+        requests.push(CohortAllocationRequest {
+            rule: allocation_rule(config)?,
+            caps: ClaimantCaps {
+                min: config.min_per_claimant,
+                max: config.max_per_claimant,
+            },
+            entitlements: claimants
+                .iter()
+                .map(|(_, entitlements)| *entitlements)
+                .collect(),
+        });
+    }
+
+    // Use BudgetAllocator for safe, precise calculations across all allocation rules
+    let max_caps: Vec<Option<Decimal>> = requests.iter().map(|request| request.caps.max).collect();
+    let allocations = allocator.allocate_campaign(requests)?.cohorts;
+
+    // Convert to CohortData with vault counts and capped entitlements
+    let mut cohort_data = Vec::new();
+    for ((cohort_name, resolved), max_per_claimant) in
+        cohort_names.into_iter().zip(allocations).zip(max_caps)
+    {
+        let mut claimants: Vec<ClaimantData> = cohort_groups[&cohort_name]
+            .iter()
+            .zip(resolved.entitlements)
+            .map(|((claimant, _), entitlements)| (*claimant, entitlements))
+            .collect();
+
+        let mut allocation = resolved.allocation;
+
+        // Apply the dust policy before the leaves are committed to a merkle tree
+        let redistributed_dust = options.dust_policy.apply(
+            &mut claimants,
+            &mut allocation,
+            max_per_claimant,
+            mint_decimals,
         )?;
 
+        // Calculate vault count needed
+        let vault_count =
+            options.vault_count(&cohort_name, claimants.len(), claimants_per_vault)?;
+
         cohort_data.push(CohortData {
-            name: cohort_name.clone(),
+            name: cohort_name,
             amount_per_entitlement: allocation.amount_per_entitlement,
             amount_per_entitlement_humane: allocation.amount_per_entitlement_humane,
             claimants,
             vault_count,
+            cohort_budget: allocation.cohort_total,
+            allocation_dust: allocation.dust_amount - redistributed_dust,
+            redistributed_dust,
         });
     }
 
     Ok(cohort_data)
 }
 
+/// Translate a cohorts.csv row into the allocation rule it selects
+pub(crate) fn allocation_rule(config: &CohortsCsvRow) -> CompilerResult<AllocationRule> {
+    let allocation_value = || {
+        config.allocation_value.ok_or_else(|| {
+            CompilerError::InvalidConfig(format!(
+                "Cohort '{}' uses {:?} allocation but has no allocation_value",
+                config.cohort, config.allocation_mode
+            ))
+        })
+    };
+
+    Ok(match config.allocation_mode {
+        AllocationMode::SharePercentage => AllocationRule::SharePercentage(config.share_percentage),
+        AllocationMode::FixedPerEntitlement => {
+            AllocationRule::FixedPerEntitlement(allocation_value()?)
+        }
+        AllocationMode::CohortBudget => AllocationRule::CohortBudget(allocation_value()?),
+        AllocationMode::PerClaimant => AllocationRule::PerClaimant,
+    })
+}
+
 /// Generate merkle trees for all cohorts
 fn generate_merkle_trees(
     cohort_data: Vec<CohortData>,
-) -> CompilerResult<Vec<(CohortData, ClaimTreeV0, [u8; 32])>> {
+    options: &CompileOptions,
+) -> CompilerResult<Vec<(CohortData, CohortMerkleTree, [u8; 32])>> {
     let mut cohort_merkle_data = Vec::new();
 
     for cohort in cohort_data {
@@ -417,10 +809,25 @@ fn generate_merkle_trees(
             cohort.claimants.iter().map(|c| c.clone()).collect();
 
         // Create merkle tree with vault count
-        let merkle_tree =
-            create_claim_tree_v0(&claimant_pairs, cohort.vault_count).map_err(|e| {
-                CompilerError::MerkleTree(format!("Failed to create merkle tree: {}", e))
-            })?;
+        let merkle_tree = match options.cohort_tree(&cohort.name)? {
+            CohortTree::V0 => create_claim_tree_v0_with_order(
+                &claimant_pairs,
+                cohort.vault_count,
+                options.leaf_order,
+            )
+            .map(CohortMerkleTree::V0),
+            CohortTree::V1 { arity, hash_scheme } => create_claim_tree_v1_with_options(
+                &claimant_pairs,
+                cohort.vault_count,
+                &ClaimTreeV1Options {
+                    arity: arity as usize,
+                    leaf_order: options.leaf_order,
+                    hash_scheme: hash_scheme.into(),
+                },
+            )
+            .map(CohortMerkleTree::V1),
+        }
+        .map_err(|e| CompilerError::MerkleTree(format!("Failed to create merkle tree: {}", e)))?;
 
         let merkle_root = merkle_tree
             .root()
@@ -433,7 +840,7 @@ fn generate_merkle_trees(
 }
 
 /// Calculate campaign fingerprint from merkle roots
-fn calculate_campaign_fingerprint(cohort_roots: &[[u8; 32]]) -> [u8; 32] {
+pub(crate) fn calculate_campaign_fingerprint(cohort_roots: &[[u8; 32]]) -> [u8; 32] {
     let mut hasher = Sha256::new();
     for root in cohort_roots {
         hasher.update(root);
@@ -444,8 +851,10 @@ fn calculate_campaign_fingerprint(cohort_roots: &[[u8; 32]]) -> [u8; 32] {
 /// Derive addresses and finalize compilation
 fn derive_addresses_and_finalize(
     address_finder: &AddressFinder,
-    cohort_merkle_data: Vec<(CohortData, ClaimTreeV0, [u8; 32])>,
+    cohort_merkle_data: Vec<(CohortData, CohortMerkleTree, [u8; 32])>,
     campaign_address: &Pubkey,
+    mint_decimals: u8,
+    leaf_order: LeafOrder,
 ) -> CompilerResult<Vec<CompiledCohort>> {
     let mut compiled_cohorts = Vec::new();
 
@@ -456,7 +865,7 @@ fn derive_addresses_and_finalize(
 
         // Calculate total tokens needed for this cohort
         let total_tokens_for_cohort_decimal: Decimal = merkle_tree
-            .leaves
+            .leaves()
             .iter()
             .map(|leaf| Decimal::from(leaf.entitlements) * cohort.amount_per_entitlement)
             .sum();
@@ -467,17 +876,39 @@ fn derive_addresses_and_finalize(
             &cohort_address,
             cohort.vault_count,
             total_tokens_for_cohort_decimal,
-        );
+            mint_decimals,
+        )?;
+
+        // Claimant-ordered cohorts are indexed for non-membership proofs
+        let claimant_index_root = match leaf_order {
+            LeafOrder::Insertion => None,
+            LeafOrder::Claimant => Some(
+                merkle_tree
+                    .claimant_index()
+                    .map_err(|e| {
+                        CompilerError::MerkleTree(format!("Failed to index claimants: {}", e))
+                    })?
+                    .root(),
+            ),
+        };
 
         compiled_cohorts.push(CompiledCohort {
             name: cohort.name,
             amount_per_entitlement: cohort.amount_per_entitlement,
+            amount_per_entitlement_u64: to_base_units(
+                cohort.amount_per_entitlement,
+                mint_decimals,
+            )?,
             amount_per_entitlement_humane: cohort.amount_per_entitlement_humane,
             vault_count: cohort.vault_count,
             vaults,
             merkle_tree,
+            claimant_index_root,
             merkle_root,
             address: cohort_address,
+            cohort_budget: cohort.cohort_budget,
+            allocation_dust: cohort.allocation_dust,
+            redistributed_dust: cohort.redistributed_dust,
         });
     }
 
@@ -485,36 +916,41 @@ fn derive_addresses_and_finalize(
 }
 
 /// Calculate vault addresses and funding distribution for a cohort
-fn calculate_vault_funding(
+pub(crate) fn calculate_vault_funding(
     address_finder: &AddressFinder,
     cohort_address: &Pubkey,
     vault_count: usize,
     total_tokens_for_cohort: Decimal,
-) -> Vec<CompiledVault> {
-    // Convert to u64 for distribution (floor to be conservative)
-    let total_tokens_u64 = total_tokens_for_cohort.floor().to_u64().unwrap_or(0);
+    mint_decimals: u8,
+) -> CompilerResult<Vec<CompiledVault>> {
+    // Distribute whole base units (floor to be conservative)
+    let total_units = to_base_units(total_tokens_for_cohort, mint_decimals)?;
+
+    // Unrounded per-vault share, used for dust reporting
+    let exact_share = total_tokens_for_cohort / Decimal::from(vault_count);
 
-    // Distribute tokens across vaults
-    let tokens_per_vault = total_tokens_u64 / vault_count as u64;
-    let remainder = total_tokens_u64 % vault_count as u64;
+    let units_per_vault = total_units / vault_count as u64;
+    let remainder = total_units % vault_count as u64;
 
-    (0..vault_count)
+    Ok((0..vault_count)
         .map(|i| {
             let (vault_address, _) = address_finder.find_vault_v0_address(cohort_address, i as u8);
 
-            // First `remainder` vaults get an extra token
-            let required_tokens = if i < remainder as usize {
-                tokens_per_vault + 1
+            // First `remainder` vaults get an extra base unit
+            let required_base_units = if i < remainder as usize {
+                units_per_vault + 1
             } else {
-                tokens_per_vault
+                units_per_vault
             };
 
             CompiledVault {
                 address: vault_address,
-                required_tokens: Decimal::from(required_tokens),
+                required_tokens: from_base_units(required_base_units, mint_decimals),
+                required_base_units,
+                exact_share,
             }
         })
-        .collect()
+        .collect())
 }
 
 /// Populate database with compilation result
@@ -534,74 +970,185 @@ fn populate_database(
 
     // Insert cohorts and related data
     for cohort in &compilation_result.cohorts {
-        // Convert Decimal to u64 for database storage - fail fast if overflow
-        let amount_per_entitlement_u64 = cohort
-            .amount_per_entitlement
-            .floor()
-            .to_u64()
-            .ok_or_else(|| {
-                CompilerError::InvalidConfig(format!(
-                    "Amount per entitlement overflow for cohort '{}': {}",
-                    cohort.name, cohort.amount_per_entitlement
-                ))
-            })?;
+        let vault_claimant_counts = vault_claimant_counts(
+            cohort
+                .merkle_tree
+                .leaves()
+                .iter()
+                .map(|leaf| leaf.assigned_vault_index),
+            cohort.vault_count,
+        );
 
         // Insert cohort
         db.insert_cohort(
             &cohort.name,
             cohort.merkle_root,
-            amount_per_entitlement_u64,
+            cohort.amount_per_entitlement_u64,
             &cohort.amount_per_entitlement_humane,
+            cohort.vault_count,
+            cohort.merkle_tree.leaves().len(),
+            cohort.total_funding_u64()?,
+            cohort.merkle_tree.layout(),
         )
         .map_err(|e| CompilerError::InvalidConfig(format!("Failed to insert cohort: {}", e)))?;
+        if let Some(root) = &cohort.claimant_index_root {
+            db.update_cohort_claimant_index_root(&cohort.name, root)
+                .map_err(|e| {
+                    CompilerError::InvalidConfig(format!("Failed to record claimant index: {}", e))
+                })?;
+        }
 
         // Insert claimants for this cohort
-        for (index, leaf) in cohort.merkle_tree.leaves.iter().enumerate() {
+        let mut records = Vec::with_capacity(cohort.merkle_tree.leaves().len());
+        for (index, leaf) in cohort.merkle_tree.leaves().iter().enumerate() {
             let proof = cohort
                 .merkle_tree
-                .proof_for_claimant(&leaf.claimant)
-                .map_err(|e| {
-                    CompilerError::MerkleTree(format!(
-                        "Failed to generate proof for claimant {}: {}",
-                        index, e
-                    ))
-                })?;
-
-            let proof_hex = proof.iter().map(hex::encode).collect::<Vec<_>>().join(",");
-
-            db.insert_claimant(leaf.claimant, &cohort.name, leaf.entitlements, &proof_hex)
-                .map_err(|e| {
-                    CompilerError::InvalidConfig(format!("Failed to insert claimant: {}", e))
-                })?;
+                .stored_proof(&leaf.claimant)
+                .map_err(|e| CompilerError::MerkleTree(format!("Claimant {}: {}", index, e)))?;
+
+            records.push(ClaimantRecord {
+                claimant: leaf.claimant,
+                entitlements: leaf.entitlements,
+                assigned_vault_index: leaf.assigned_vault_index,
+                assigned_vault_pubkey: cohort.vaults[leaf.assigned_vault_index as usize].address,
+                merkle_proof: proof,
+            });
         }
 
+        db.insert_claimants(&cohort.name, &records).map_err(|e| {
+            CompilerError::InvalidConfig(format!("Failed to insert claimant: {}", e))
+        })?;
+
         // Insert vault requirements using pre-calculated funding amounts
-        for (vault_index, vault) in cohort.vaults.iter().enumerate() {
-            let required_tokens_u64 = vault.required_tokens.floor().to_u64().ok_or_else(|| {
-                CompilerError::InvalidConfig(format!(
-                    "Vault funding overflow for cohort '{}', vault {}",
-                    cohort.name, vault_index
-                ))
-            })?;
-
-            db.insert_vault(
-                &cohort.name,
-                vault_index,
-                vault.address,
-                required_tokens_u64,
-            )
-            .map_err(|e| CompilerError::InvalidConfig(format!("Failed to insert vault: {}", e)))?;
-        }
+        insert_vaults(db, &cohort.name, &cohort.vaults, &vault_claimant_counts)?;
     }
 
+    // Record dust accounting alongside the cohorts and vaults it describes
+    db.insert_dust_report(&compilation_result.dust_report()?)
+        .map_err(|e| {
+            CompilerError::InvalidConfig(format!("Failed to insert dust report: {}", e))
+        })?;
+
+    Ok(())
+}
+
+/// Insert a cohort's vaults with their funding and claimant counts
+pub(crate) fn insert_vaults(
+    db: &mut CampaignDatabase,
+    cohort_name: &str,
+    vaults: &[CompiledVault],
+    claimant_counts: &[usize],
+) -> CompilerResult<()> {
+    for (vault_index, (vault, &claimant_count)) in vaults.iter().zip(claimant_counts).enumerate() {
+        db.insert_vault(
+            cohort_name,
+            vault_index,
+            vault.address,
+            vault.required_base_units,
+            claimant_count,
+        )
+        .map_err(|e| CompilerError::InvalidConfig(format!("Failed to insert vault: {}", e)))?;
+    }
     Ok(())
 }
 
+/// Dust accounting of one cohort
+pub(crate) fn cohort_dust(
+    cohort_name: &str,
+    cohort_budget: Decimal,
+    allocated: Decimal,
+    allocation_dust: Decimal,
+    redistributed: Decimal,
+    vaults: &[CompiledVault],
+) -> CohortDust {
+    let funding: Decimal = vaults.iter().map(|vault| vault.required_tokens).sum();
+    CohortDust {
+        cohort_name: cohort_name.to_string(),
+        cohort_budget,
+        allocated,
+        allocation_dust,
+        funding_dust: allocated - funding,
+        redistributed,
+    }
+}
+
+/// Dust accounting of each vault of a cohort
+pub(crate) fn vault_dust<'a>(
+    cohort_name: &'a str,
+    vaults: &'a [CompiledVault],
+) -> impl Iterator<Item = VaultDust> + 'a {
+    vaults
+        .iter()
+        .enumerate()
+        .map(move |(vault_index, vault)| VaultDust {
+            cohort_name: cohort_name.to_string(),
+            vault_index,
+            exact_share: vault.exact_share,
+            required_tokens: vault.required_base_units,
+            dust: vault.dust(),
+        })
+}
+
+/// Campaign dust report from per-cohort and per-vault dust
+pub(crate) fn dust_report(
+    policy: DustPolicy,
+    budget: Decimal,
+    mint_decimals: u8,
+    cohorts: Vec<CohortDust>,
+    vaults: Vec<VaultDust>,
+) -> CompilerResult<DustReport> {
+    let targeted: Decimal = cohorts.iter().map(|cohort| cohort.cohort_budget).sum();
+    let total_funding: Decimal = cohorts
+        .iter()
+        .map(|cohort| cohort.allocated - cohort.funding_dust)
+        .sum();
+
+    Ok(DustReport {
+        summary: DustSummary {
+            policy: policy.name().to_string(),
+            treasury: policy.treasury(),
+            budget,
+            total_funding,
+            unallocated_budget: budget - targeted,
+            total_dust: budget - total_funding,
+            treasury_amount: policy.treasury_amount(targeted - total_funding, mint_decimals)?,
+            treasury_signature: None,
+        },
+        cohorts,
+        vaults,
+    })
+}
+
+/// Number of claimants assigned to each vault of a cohort
+pub(crate) fn vault_claimant_counts(
+    assigned_vault_indices: impl Iterator<Item = u8>,
+    vault_count: usize,
+) -> Vec<usize> {
+    let mut counts = vec![0; vault_count];
+    for vault_index in assigned_vault_indices {
+        counts[vault_index as usize] += 1;
+    }
+    counts
+}
+
+/// Total funding of a cohort's vaults in base units
+pub(crate) fn funding_u64(vaults: &[CompiledVault], cohort_name: &str) -> CompilerResult<u64> {
+    vaults
+        .iter()
+        .try_fold(0u64, |total, vault| {
+            total.checked_add(vault.required_base_units)
+        })
+        .ok_or_else(|| {
+            CompilerError::InvalidConfig(format!("Funding overflow for cohort '{}'", cohort_name))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::AddressFinder;
     use prism_protocol_csvs::{CampaignCsvRow, CohortsCsvRow};
+    use prism_protocol_db::HashScheme;
     use rust_decimal::Decimal;
     use sha2::Digest;
     use solana_sdk::pubkey::Pubkey;
@@ -660,10 +1207,12 @@ mod tests {
             CohortsCsvRow {
                 cohort: "Alpha".to_string(),
                 share_percentage: Decimal::from(60), // 60%
+                ..Default::default()
             },
             CohortsCsvRow {
                 cohort: "Beta".to_string(),
                 share_percentage: Decimal::from(40), // 40%
+                ..Default::default()
             },
         ]
     }
@@ -750,6 +1299,7 @@ mod tests {
         let cohorts_rows = vec![CohortsCsvRow {
             cohort: "Test".to_string(),
             share_percentage: Decimal::from(100),
+            ..Default::default()
         }];
 
         let compiled = compile_campaign(
@@ -797,13 +1347,10 @@ mod tests {
         // All amounts should respect USDC precision (6 decimals)
         for cohort in &compiled.cohorts {
             for vault in &cohort.vaults {
-                // Should be expressible in USDC precision
-                let as_u64 = vault.required_tokens_u64().unwrap();
-                let back_to_decimal = Decimal::from(as_u64);
-
-                // The difference should be less than 1 microUSDC (0.000001)
-                let precision_unit = Decimal::from_str("0.000001").unwrap();
-                assert!((vault.required_tokens - back_to_decimal).abs() < precision_unit);
+                // Funding is a whole number of microUSDC (0.000001)
+                let as_u64 = vault.required_tokens_u64();
+                assert_eq!(from_base_units(as_u64, 6), vault.required_tokens);
+                assert_eq!(vault.required_tokens.round_dp(6), vault.required_tokens);
             }
         }
     }
@@ -826,7 +1373,10 @@ mod tests {
         for cohort in &compiled.cohorts {
             for vault in &cohort.vaults {
                 assert_eq!(vault.required_tokens, vault.required_tokens.floor());
-                assert!(vault.required_tokens_u64().is_ok());
+                assert_eq!(
+                    Decimal::from(vault.required_tokens_u64()),
+                    vault.required_tokens
+                );
             }
         }
     }
@@ -847,6 +1397,7 @@ mod tests {
         let cohorts_rows = vec![CohortsCsvRow {
             cohort: "Large".to_string(),
             share_percentage: Decimal::from(100),
+            ..Default::default()
         }];
 
         let compiled = compile_campaign(
@@ -870,6 +1421,195 @@ mod tests {
         assert_eq!(total_funding, Decimal::from(250000));
     }
 
+    #[test]
+    fn test_planned_layout() {
+        use crate::campaign_layout::{cohort_sizes, LayoutConstraints, LayoutPlanner};
+
+        let campaign_rows: Vec<CampaignCsvRow> = (0..25)
+            .map(|i| CampaignCsvRow {
+                cohort: "Large".to_string(),
+                claimant: deterministic_pubkey(&format!("large_claimant_{}", i)),
+                entitlements: 100,
+            })
+            .collect();
+        let cohorts_rows = vec![CohortsCsvRow {
+            cohort: "Large".to_string(),
+            share_percentage: Decimal::from(100),
+            ..Default::default()
+        }];
+
+        let planner = LayoutPlanner::new(LayoutConstraints {
+            max_claimants_per_vault: 5,
+            peak_claims_per_slot: 0,
+            ..Default::default()
+        });
+        let layout = planner
+            .plan(&cohort_sizes(&campaign_rows, &cohorts_rows))
+            .unwrap();
+        let compile = |options: &CompileOptions| {
+            compile_campaign_with_options(
+                test_address_finder(),
+                &campaign_rows,
+                &cohorts_rows,
+                &CampaignSettings {
+                    budget: Decimal::from(250000),
+                    mint: test_mint(),
+                    mint_decimals: 9,
+                    admin: test_admin(),
+                    claimants_per_vault: 10,
+                },
+                options,
+            )
+            .unwrap()
+        };
+
+        // The planned 5 vaults win over 10 claimants per vault
+        let compiled = compile(&CompileOptions::default().with_layout(&layout));
+        assert_eq!(compiled.cohorts[0].vault_count, 5);
+        assert_eq!(compiled.total_vaults, 5);
+        assert!(matches!(
+            compiled.cohorts[0].merkle_tree,
+            CohortMerkleTree::V0(_)
+        ));
+
+        // Cohorts planned as V1 trees compile at the planned arity
+        let v1_layout = LayoutPlanner::new(LayoutConstraints {
+            max_claimants_per_vault: 5,
+            peak_claims_per_slot: 0,
+            tree_arity: Some(4),
+            ..Default::default()
+        })
+        .plan(&cohort_sizes(&campaign_rows, &cohorts_rows))
+        .unwrap();
+        let options = CompileOptions {
+            tree_arities: HashMap::from([("Large".to_string(), 8)]),
+            ..Default::default()
+        };
+        let compiled = compile(&options.with_layout(&v1_layout));
+        assert_eq!(compiled.cohorts[0].vault_count, 5);
+        match &compiled.cohorts[0].merkle_tree {
+            CohortMerkleTree::V1(tree) => assert_eq!(tree.arity(), 4),
+            CohortMerkleTree::V0(_) => panic!("Large should compile into a V1 tree"),
+        }
+    }
+
+    #[test]
+    fn test_tree_arities_compile_v1_cohorts() {
+        let campaign_rows: Vec<CampaignCsvRow> = (0..40)
+            .map(|i| CampaignCsvRow {
+                cohort: if i % 2 == 0 { "Wide" } else { "Binary" }.to_string(),
+                claimant: deterministic_pubkey(&format!("arity_claimant_{}", i)),
+                entitlements: 1 + i % 3,
+            })
+            .collect();
+        let cohorts_rows: Vec<CohortsCsvRow> = ["Wide", "Binary"]
+            .into_iter()
+            .map(|cohort| CohortsCsvRow {
+                cohort: cohort.to_string(),
+                share_percentage: Decimal::from(50),
+                ..Default::default()
+            })
+            .collect();
+        let options = CompileOptions {
+            tree_arities: HashMap::from([("Wide".to_string(), 4)]),
+            ..Default::default()
+        };
+
+        let compile = |options: &CompileOptions| {
+            compile_campaign_db_with_options(
+                test_address_finder(),
+                &campaign_rows,
+                &cohorts_rows,
+                &CampaignSettings {
+                    budget: Decimal::from(1000),
+                    mint: test_mint(),
+                    mint_decimals: 6,
+                    admin: test_admin(),
+                    claimants_per_vault: 10,
+                },
+                options,
+            )
+        };
+        // Stored proofs verify against the committed arity and hash scheme
+        let assert_proofs_verify = |db: &CampaignDatabase| {
+            for cohort in db.read_cohorts().unwrap() {
+                let (arity, hash_scheme) = match cohort.tree {
+                    CohortTree::V0 => (2, ClaimHashScheme::Sha256),
+                    CohortTree::V1 { arity, hash_scheme } => (arity, hash_scheme.into()),
+                };
+                for record in db.read_cohort_claimant_records(&cohort.name).unwrap() {
+                    let leaf = ClaimLeaf {
+                        claimant: record.claimant,
+                        assigned_vault_index: record.assigned_vault_index,
+                        entitlements: record.entitlements,
+                    };
+                    let proof = match (cohort.tree, record.merkle_proof) {
+                        (CohortTree::V0, StoredProof::V0(proof)) => {
+                            prism_protocol::ClaimProofType::from_binary(proof)
+                        }
+                        (CohortTree::V1 { .. }, StoredProof::V1(proof)) => {
+                            prism_protocol::ClaimProofType::from_wide(proof)
+                        }
+                        (tree, proof) => panic!("{:?} cohort stored {:?}", tree, proof),
+                    };
+                    assert!(proof.verify_with_scheme(
+                        &cohort.merkle_root,
+                        &leaf,
+                        arity,
+                        hash_scheme
+                    ));
+                }
+            }
+        };
+
+        let db = compile(&options).unwrap();
+        let cohorts = db.read_cohorts().unwrap();
+        let tree = |name: &str| cohorts.iter().find(|c| c.name == name).unwrap().tree;
+        assert_eq!(
+            tree("Wide"),
+            CohortTree::V1 {
+                arity: 4,
+                hash_scheme: HashScheme::Sha256
+            }
+        );
+        assert_eq!(tree("Binary"), CohortTree::V0);
+        assert_proofs_verify(&db);
+
+        // Other hash schemes need every cohort in a V1 tree
+        let keccak = CompileOptions {
+            hash_scheme: ClaimHashScheme::Keccak256,
+            ..options.clone()
+        };
+        assert!(matches!(
+            compile(&keccak),
+            Err(CompilerError::InvalidConfig(_))
+        ));
+        let keccak = CompileOptions {
+            tree_arities: HashMap::from([("Wide".to_string(), 4), ("Binary".to_string(), 2)]),
+            ..keccak
+        };
+        let db = compile(&keccak).unwrap();
+        for cohort in db.read_cohorts().unwrap() {
+            assert!(matches!(
+                cohort.tree,
+                CohortTree::V1 {
+                    hash_scheme: HashScheme::Keccak256,
+                    ..
+                }
+            ));
+        }
+        assert_proofs_verify(&db);
+
+        let invalid = CompileOptions {
+            tree_arities: HashMap::from([("Wide".to_string(), 1)]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            compile(&invalid),
+            Err(CompilerError::InvalidConfig(_))
+        ));
+    }
+
     #[test]
     fn test_compiled_campaign_convenience_methods() {
         let compiled = compile_campaign(
@@ -910,21 +1650,38 @@ mod tests {
 
     #[test]
     fn test_compiled_vault_u64_conversion() {
-        let vault = CompiledVault {
-            address: deterministic_pubkey("test_vault"),
-            required_tokens: Decimal::from_str("1234.567890123").unwrap(),
-        };
-
-        // Should floor to 1234
-        assert_eq!(vault.required_tokens_u64().unwrap(), 1234);
+        let cohort = deterministic_pubkey("test_cohort");
+        let vaults = calculate_vault_funding(
+            &test_address_finder(),
+            &cohort,
+            2,
+            Decimal::from_str("1234.567891123").unwrap(),
+            6,
+        )
+        .unwrap();
 
-        // Test overflow case
-        let overflow_vault = CompiledVault {
-            address: deterministic_pubkey("test_overflow_vault"),
-            required_tokens: Decimal::from_str("18446744073709551616").unwrap(), // > u64::MAX
-        };
+        // Floors to whole base units, first vault takes the remainder
+        assert_eq!(vaults[0].required_tokens_u64(), 617_283_946);
+        assert_eq!(vaults[1].required_tokens_u64(), 617_283_945);
+        assert_eq!(
+            vaults[0].required_tokens,
+            Decimal::from_str("617.283946").unwrap()
+        );
 
-        assert!(overflow_vault.required_tokens_u64().is_err());
+        // Funding beyond u64 base units is an error, not zero
+        let overflow = calculate_vault_funding(
+            &test_address_finder(),
+            &cohort,
+            1,
+            Decimal::from_str("18446744073709551616").unwrap(), // > u64::MAX
+            0,
+        );
+        assert!(matches!(
+            overflow,
+            Err(CompilerError::BudgetAllocation(AllocationError::Overflow(
+                _
+            )))
+        ));
     }
 
     #[test]
@@ -949,6 +1706,117 @@ mod tests {
         // The difference (dust) should be small - allow up to 1 SOL of dust
         let dust = compiled.budget - total_funding;
         assert!(dust < Decimal::from(1)); // Less than 1 SOL
+        assert_eq!(compiled.total_dust(), dust);
+
+        // The report accounts for every token of the budget
+        let report = compiled.dust_report().unwrap();
+        assert_eq!(report.summary.policy, "retain");
+        assert_eq!(report.summary.total_dust, dust);
+        let cohort_dust: Decimal = report
+            .cohorts
+            .iter()
+            .map(|c| c.allocation_dust + c.funding_dust)
+            .sum();
+        assert_eq!(cohort_dust + report.summary.unallocated_budget, dust);
+        assert_eq!(report.vaults.len(), compiled.total_vaults);
+        for cohort in &report.cohorts {
+            let vault_dust: Decimal = report
+                .vaults
+                .iter()
+                .filter(|v| v.cohort_name == cohort.cohort_name)
+                .map(|v| v.dust)
+                .sum();
+            assert_eq!(vault_dust.round_dp(18), cohort.funding_dust);
+        }
+    }
+
+    #[test]
+    fn test_first_leaves_dust_policy() {
+        let campaign_rows: Vec<CampaignCsvRow> = [1, 1, 10]
+            .iter()
+            .enumerate()
+            .map(|(i, entitlements)| CampaignCsvRow {
+                cohort: "Explicit".to_string(),
+                claimant: deterministic_pubkey(&format!("explicit_{}", i)),
+                entitlements: *entitlements,
+            })
+            .collect();
+
+        let cohorts_rows = vec![CohortsCsvRow {
+            cohort: "Explicit".to_string(),
+            allocation_mode: AllocationMode::CohortBudget,
+            allocation_value: Some(Decimal::from(100)),
+            ..Default::default()
+        }];
+
+        let compile = |dust_policy| {
+            compile_campaign_with_options(
+                test_address_finder(),
+                &campaign_rows,
+                &cohorts_rows,
+                &CampaignSettings {
+                    budget: Decimal::from(100),
+                    mint: test_mint(),
+                    mint_decimals: 0,
+                    admin: test_admin(),
+                    claimants_per_vault: 10,
+                },
+                &CompileOptions {
+                    dust_policy,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        // 100 tokens over 12 entitlements is 8 tokens per entitlement, leaving
+        // 4 tokens of dust
+        let retained = compile(DustPolicy::Retain);
+        assert_eq!(retained.cohorts[0].amount_per_entitlement, Decimal::from(8));
+        assert_eq!(retained.total_dust(), Decimal::from(4));
+        assert_eq!(retained.cohorts[0].allocation_dust, Decimal::from(4));
+        assert_eq!(retained.dust_report().unwrap().summary.treasury_amount, 0);
+
+        // The treasury policy leaves the leaves alone and owes the dust to the treasury
+        let treasury = Pubkey::new_unique();
+        let earmarked = compile(DustPolicy::Treasury(treasury));
+        assert_eq!(earmarked.fingerprint, retained.fingerprint);
+        let summary = earmarked.dust_report().unwrap().summary;
+        assert_eq!(summary.treasury, Some(treasury));
+        assert_eq!(summary.treasury_amount, 4);
+
+        let redistributed = compile(DustPolicy::FirstLeaves);
+        let cohort = &redistributed.cohorts[0];
+        assert_eq!(cohort.redistributed_dust, Decimal::from(4));
+        assert_eq!(cohort.allocation_dust, Decimal::ZERO);
+        assert_eq!(redistributed.total_dust(), Decimal::ZERO);
+        assert_eq!(
+            redistributed.dust_report().unwrap().summary.policy,
+            "first-leaves"
+        );
+        assert_ne!(retained.fingerprint, redistributed.fingerprint);
+
+        // Entitlements are re-denominated to one base unit each
+        assert_eq!(cohort.amount_per_entitlement, Decimal::ONE);
+        assert_eq!(cohort.total_funding_required(), Decimal::from(100));
+
+        // Dust is dealt one base unit per leaf, lowest pubkeys first
+        let mut leaves: Vec<_> = cohort
+            .merkle_tree
+            .leaves()
+            .iter()
+            .map(|leaf| (leaf.claimant, leaf.entitlements))
+            .collect();
+        leaves.sort_by_key(|(claimant, _)| claimant.to_bytes());
+        let original: std::collections::HashMap<_, _> = campaign_rows
+            .iter()
+            .map(|row| (row.claimant, row.entitlements))
+            .collect();
+        let extras: Vec<u64> = leaves
+            .iter()
+            .map(|(claimant, entitlements)| entitlements - original[claimant] * 8)
+            .collect();
+        assert_eq!(extras, vec![2, 1, 1]);
     }
 
     #[test]
@@ -970,6 +1838,7 @@ mod tests {
         let cohorts_rows = vec![CohortsCsvRow {
             cohort: "Test".to_string(),
             share_percentage: Decimal::from(100),
+            ..Default::default()
         }];
 
         let compiled1 = compile_campaign(
@@ -1011,6 +1880,7 @@ mod tests {
         let cohorts_rows = vec![CohortsCsvRow {
             cohort: "Test".to_string(),
             share_percentage: Decimal::from(100),
+            ..Default::default()
         }];
 
         let result = compile_campaign(
@@ -1027,6 +1897,45 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_error_handling_vault_counts() {
+        let campaign_rows: Vec<CampaignCsvRow> = (0..300)
+            .map(|i| CampaignCsvRow {
+                cohort: "Test".to_string(),
+                claimant: deterministic_pubkey(&format!("vault_count_{}", i)),
+                entitlements: 1,
+            })
+            .collect();
+        let cohorts_rows = vec![CohortsCsvRow {
+            cohort: "Test".to_string(),
+            share_percentage: Decimal::from(100),
+            ..Default::default()
+        }];
+        let compile = |claimants_per_vault| {
+            compile_campaign(
+                test_address_finder(),
+                &campaign_rows,
+                &cohorts_rows,
+                Decimal::from(1000),
+                test_mint(),
+                9,
+                test_admin(),
+                claimants_per_vault,
+            )
+        };
+
+        assert!(matches!(
+            compile(0),
+            Err(CompilerError::InvalidConfig(msg)) if msg.contains("claimants_per_vault")
+        ));
+        // 300 vaults can't be addressed by a u8 vault index
+        assert!(matches!(
+            compile(1),
+            Err(CompilerError::InvalidConfig(msg)) if msg.contains("300 vaults")
+        ));
+        assert!(compile(2).is_ok());
+    }
+
     #[test]
     fn test_database_population_uses_precomputed_values() {
         // Test that compilation succeeds and database can be created
@@ -1049,6 +1958,100 @@ mod tests {
         assert!(true); // Pass - the real test is that compilation succeeds with new vault structure
     }
 
+    #[test]
+    fn test_mixed_allocation_modes_with_caps() {
+        let mut campaign_rows = simple_campaign_rows();
+        campaign_rows.push(CampaignCsvRow {
+            cohort: "Partners".to_string(),
+            claimant: deterministic_pubkey("partner_1"),
+            entitlements: 75,
+        });
+        campaign_rows.push(CampaignCsvRow {
+            cohort: "Partners".to_string(),
+            claimant: deterministic_pubkey("partner_2"),
+            entitlements: 400,
+        });
+
+        let mut cohorts_rows = simple_cohorts_rows();
+        cohorts_rows.push(CohortsCsvRow {
+            cohort: "Partners".to_string(),
+            allocation_mode: AllocationMode::PerClaimant,
+            max_per_claimant: Some(Decimal::from(100)),
+            ..Default::default()
+        });
+
+        let compiled = compile_campaign(
+            test_address_finder(),
+            &campaign_rows,
+            &cohorts_rows,
+            Decimal::from(1175),
+            test_mint(),
+            0,
+            test_admin(),
+            10,
+        )
+        .unwrap();
+
+        // Partners take their explicit (capped) amounts: 75 + 100
+        let partners = compiled.find_cohort_by_name("Partners").unwrap();
+        assert_eq!(partners.amount_per_entitlement, Decimal::ONE);
+        assert_eq!(
+            partners.expected_claim_amount(&deterministic_pubkey("partner_2")),
+            Some(Decimal::from(100))
+        );
+        assert_eq!(partners.total_funding_required(), Decimal::from(175));
+
+        // Share cohorts split the remaining 1000 as before
+        let alpha = compiled.find_cohort_by_name("Alpha").unwrap();
+        assert_eq!(alpha.amount_per_entitlement, Decimal::from(2));
+        assert_eq!(compiled.total_funding_required(), Decimal::from(1175));
+    }
+
+    #[test]
+    fn test_per_claimant_entitlements_are_base_units() {
+        let campaign_rows: Vec<CampaignCsvRow> = [1_500_000, 250]
+            .iter()
+            .enumerate()
+            .map(|(i, entitlements)| CampaignCsvRow {
+                cohort: "Partners".to_string(),
+                claimant: deterministic_pubkey(&format!("partner_{}", i)),
+                entitlements: *entitlements,
+            })
+            .collect();
+        let cohorts_rows = vec![CohortsCsvRow {
+            cohort: "Partners".to_string(),
+            allocation_mode: AllocationMode::PerClaimant,
+            ..Default::default()
+        }];
+
+        let db = compile_campaign_db(
+            test_address_finder(),
+            &campaign_rows,
+            &cohorts_rows,
+            Decimal::from(10),
+            test_mint(),
+            6,
+            test_admin(),
+            10,
+        )
+        .unwrap();
+
+        // 1.50025 tokens of a 6-decimal mint, one base unit per entitlement
+        let cohorts = db.read_cohorts().unwrap();
+        assert_eq!(cohorts[0].amount_per_entitlement, 1);
+        assert_eq!(db.calculate_total_funding_required().unwrap(), 1_500_250);
+
+        let report = db.read_dust_report().unwrap().unwrap();
+        assert_eq!(
+            report.summary.total_funding,
+            Decimal::from_str("1.50025").unwrap()
+        );
+        assert_eq!(
+            report.summary.unallocated_budget,
+            Decimal::from_str("8.49975").unwrap()
+        );
+    }
+
     #[test]
     fn test_duplicate_claimant_within_cohort_errors() {
         // Test that duplicate claimants within the same cohort cause compilation to fail
@@ -1069,6 +2072,7 @@ mod tests {
         let cohorts_rows = vec![CohortsCsvRow {
             cohort: "PowerUsers".to_string(),
             share_percentage: Decimal::from(100),
+            ..Default::default()
         }];
 
         let result = compile_campaign(
@@ -0,0 +1,444 @@
+/*!
+# Campaign Diff
+
+Compares two compiled campaign databases, typically the deployed compile and a
+recompile from a corrected claimant list, so the change can be reviewed before
+redeploying.
+
+The diff reports, per cohort, added/removed claimants, claimants whose entitlements
+changed, how many claimants moved to a different vault, `amount_per_entitlement`
+changes and vault funding deltas, plus the campaign-level fingerprint, manifest hash
+and total funding. [`CampaignDiff`] serializes to JSON for tooling.
+*/
+
+use prism_protocol_db::{CampaignDatabase, ClaimantRecord, DbResult, VaultRequirement};
+use rust_decimal::Decimal;
+use serde::{Serialize, Serializer};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Display;
+
+/// Differences between two compiled campaigns
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CampaignDiff {
+    #[serde(serialize_with = "serialize_hex")]
+    pub old_fingerprint: [u8; 32],
+    #[serde(serialize_with = "serialize_hex")]
+    pub new_fingerprint: [u8; 32],
+    #[serde(serialize_with = "serialize_optional_hex")]
+    pub old_manifest_hash: Option<[u8; 32]>,
+    #[serde(serialize_with = "serialize_optional_hex")]
+    pub new_manifest_hash: Option<[u8; 32]>,
+    #[serde(serialize_with = "serialize_display")]
+    pub old_budget: Decimal,
+    #[serde(serialize_with = "serialize_display")]
+    pub new_budget: Decimal,
+    pub old_total_funding: u64,
+    pub new_total_funding: u64,
+    pub funding_delta: i128,
+    /// Every cohort of either campaign, old campaign order first
+    pub cohorts: Vec<CohortDiff>,
+}
+
+impl CampaignDiff {
+    /// Whether the campaigns would deploy to different addresses
+    pub fn fingerprint_changed(&self) -> bool {
+        self.old_fingerprint != self.new_fingerprint
+    }
+
+    /// Whether nothing differs
+    pub fn is_empty(&self) -> bool {
+        !self.fingerprint_changed()
+            && self.old_budget == self.new_budget
+            && self.funding_delta == 0
+            && self
+                .cohorts
+                .iter()
+                .all(|cohort| cohort.change == CohortChange::Unchanged)
+    }
+}
+
+/// How a cohort differs between the two campaigns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CohortChange {
+    Added,
+    Removed,
+    Modified,
+    Unchanged,
+}
+
+/// Differences within one cohort
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CohortDiff {
+    pub name: String,
+    pub change: CohortChange,
+    #[serde(serialize_with = "serialize_optional_hex")]
+    pub old_merkle_root: Option<[u8; 32]>,
+    #[serde(serialize_with = "serialize_optional_hex")]
+    pub new_merkle_root: Option<[u8; 32]>,
+    pub old_amount_per_entitlement: Option<u64>,
+    pub new_amount_per_entitlement: Option<u64>,
+    pub old_funding: u64,
+    pub new_funding: u64,
+    pub funding_delta: i128,
+    /// Claimants only in the new campaign
+    pub added: Vec<ClaimantEntry>,
+    /// Claimants only in the old campaign
+    pub removed: Vec<ClaimantEntry>,
+    /// Claimants in both whose entitlements changed
+    pub changed: Vec<EntitlementChange>,
+    /// Claimants in both assigned to a different vault
+    pub reassigned: usize,
+    /// Vaults whose required funding changed (or that exist in only one campaign)
+    pub vaults: Vec<VaultFundingChange>,
+}
+
+/// A claimant present in only one of the campaigns
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClaimantEntry {
+    #[serde(serialize_with = "serialize_display")]
+    pub claimant: Pubkey,
+    pub entitlements: u64,
+    pub vault_index: u8,
+}
+
+/// A claimant whose entitlements differ between the campaigns
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntitlementChange {
+    #[serde(serialize_with = "serialize_display")]
+    pub claimant: Pubkey,
+    pub old_entitlements: u64,
+    pub new_entitlements: u64,
+}
+
+/// Required funding of one vault in each campaign
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VaultFundingChange {
+    pub vault_index: usize,
+    pub old_required: Option<u64>,
+    pub new_required: Option<u64>,
+}
+
+/// Compare two compiled campaign databases
+pub fn diff_campaigns(old: &CampaignDatabase, new: &CampaignDatabase) -> DbResult<CampaignDiff> {
+    let old_info = old.read_campaign_info()?;
+    let new_info = new.read_campaign_info()?;
+
+    let old_vaults = vault_funding_by_cohort(old.read_vault_requirements()?);
+    let new_vaults = vault_funding_by_cohort(new.read_vault_requirements()?);
+
+    let old_cohorts = old.read_cohorts()?;
+    let new_cohorts = new.read_cohorts()?;
+
+    let mut names: Vec<&str> = old_cohorts.iter().map(|c| c.name.as_str()).collect();
+    for cohort in &new_cohorts {
+        if !names.contains(&cohort.name.as_str()) {
+            names.push(&cohort.name);
+        }
+    }
+
+    let mut cohorts = Vec::with_capacity(names.len());
+    for name in names {
+        let old_cohort = old_cohorts.iter().find(|c| c.name == name);
+        let new_cohort = new_cohorts.iter().find(|c| c.name == name);
+
+        let old_records = match old_cohort {
+            Some(_) => old.read_cohort_claimant_records(name)?,
+            None => Vec::new(),
+        };
+        let new_records = match new_cohort {
+            Some(_) => new.read_cohort_claimant_records(name)?,
+            None => Vec::new(),
+        };
+
+        let empty = BTreeMap::new();
+        let old_cohort_vaults = old_vaults.get(name).unwrap_or(&empty);
+        let new_cohort_vaults = new_vaults.get(name).unwrap_or(&empty);
+
+        let mut diff = diff_claimants(name, &old_records, &new_records);
+        diff.vaults = diff_vault_funding(old_cohort_vaults, new_cohort_vaults);
+        diff.old_merkle_root = old_cohort.map(|c| c.merkle_root);
+        diff.new_merkle_root = new_cohort.map(|c| c.merkle_root);
+        diff.old_amount_per_entitlement = old_cohort.map(|c| c.amount_per_entitlement);
+        diff.new_amount_per_entitlement = new_cohort.map(|c| c.amount_per_entitlement);
+        diff.old_funding = old_cohort_vaults.values().sum();
+        diff.new_funding = new_cohort_vaults.values().sum();
+        diff.funding_delta = diff.new_funding as i128 - diff.old_funding as i128;
+        diff.change = match (old_cohort, new_cohort) {
+            (None, _) => CohortChange::Added,
+            (_, None) => CohortChange::Removed,
+            _ if diff.old_merkle_root != diff.new_merkle_root
+                || diff.old_amount_per_entitlement != diff.new_amount_per_entitlement
+                || !diff.vaults.is_empty() =>
+            {
+                CohortChange::Modified
+            }
+            _ => CohortChange::Unchanged,
+        };
+
+        cohorts.push(diff);
+    }
+
+    let old_total_funding = cohorts.iter().map(|c| c.old_funding).sum::<u64>();
+    let new_total_funding = cohorts.iter().map(|c| c.new_funding).sum::<u64>();
+
+    Ok(CampaignDiff {
+        old_fingerprint: old_info.fingerprint,
+        new_fingerprint: new_info.fingerprint,
+        old_manifest_hash: old.read_campaign_manifest_hash()?,
+        new_manifest_hash: new.read_campaign_manifest_hash()?,
+        old_budget: old_info.budget,
+        new_budget: new_info.budget,
+        old_total_funding,
+        new_total_funding,
+        funding_delta: new_total_funding as i128 - old_total_funding as i128,
+        cohorts,
+    })
+}
+
+/// Claimant-level differences of one cohort (cohort-level fields left empty)
+fn diff_claimants(name: &str, old: &[ClaimantRecord], new: &[ClaimantRecord]) -> CohortDiff {
+    let new_by_claimant: HashMap<Pubkey, &ClaimantRecord> =
+        new.iter().map(|r| (r.claimant, r)).collect();
+    let old_claimants: HashSet<Pubkey> = old.iter().map(|r| r.claimant).collect();
+
+    let mut removed = Vec::new();
+    let mut changed = Vec::new();
+    let mut reassigned = 0;
+    for old_record in old {
+        match new_by_claimant.get(&old_record.claimant) {
+            None => removed.push(ClaimantEntry::from(old_record)),
+            Some(new_record) => {
+                if new_record.entitlements != old_record.entitlements {
+                    changed.push(EntitlementChange {
+                        claimant: old_record.claimant,
+                        old_entitlements: old_record.entitlements,
+                        new_entitlements: new_record.entitlements,
+                    });
+                }
+                if new_record.assigned_vault_index != old_record.assigned_vault_index {
+                    reassigned += 1;
+                }
+            }
+        }
+    }
+
+    let added = new
+        .iter()
+        .filter(|r| !old_claimants.contains(&r.claimant))
+        .map(ClaimantEntry::from)
+        .collect();
+
+    CohortDiff {
+        name: name.to_string(),
+        change: CohortChange::Unchanged,
+        old_merkle_root: None,
+        new_merkle_root: None,
+        old_amount_per_entitlement: None,
+        new_amount_per_entitlement: None,
+        old_funding: 0,
+        new_funding: 0,
+        funding_delta: 0,
+        added,
+        removed,
+        changed,
+        reassigned,
+        vaults: Vec::new(),
+    }
+}
+
+/// Vaults whose required funding differs, in vault index order
+fn diff_vault_funding(
+    old: &BTreeMap<usize, u64>,
+    new: &BTreeMap<usize, u64>,
+) -> Vec<VaultFundingChange> {
+    let indices: std::collections::BTreeSet<usize> =
+        old.keys().chain(new.keys()).copied().collect();
+    indices
+        .into_iter()
+        .map(|vault_index| VaultFundingChange {
+            vault_index,
+            old_required: old.get(&vault_index).copied(),
+            new_required: new.get(&vault_index).copied(),
+        })
+        .filter(|change| change.old_required != change.new_required)
+        .collect()
+}
+
+fn vault_funding_by_cohort(
+    requirements: Vec<VaultRequirement>,
+) -> HashMap<String, BTreeMap<usize, u64>> {
+    let mut by_cohort: HashMap<String, BTreeMap<usize, u64>> = HashMap::new();
+    for requirement in requirements {
+        by_cohort
+            .entry(requirement.cohort_name)
+            .or_default()
+            .insert(requirement.vault_index, requirement.required_tokens);
+    }
+    by_cohort
+}
+
+impl From<&ClaimantRecord> for ClaimantEntry {
+    fn from(record: &ClaimantRecord) -> Self {
+        Self {
+            claimant: record.claimant,
+            entitlements: record.entitlements,
+            vault_index: record.assigned_vault_index,
+        }
+    }
+}
+
+fn serialize_display<T: Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn serialize_hex<S: Serializer>(value: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex::encode(value))
+}
+
+fn serialize_optional_hex<S: Serializer>(
+    value: &Option<[u8; 32]>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(bytes) => serializer.serialize_some(&hex::encode(bytes)),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        compile_campaign_db_with_options, AddressFinder, CampaignSettings, CompileOptions,
+    };
+    use prism_protocol_csvs::{AllocationMode, CampaignCsvRow, CohortsCsvRow};
+    use std::str::FromStr;
+
+    fn compile(rows: &[(&str, Pubkey, u64)]) -> CampaignDatabase {
+        let campaign_rows: Vec<CampaignCsvRow> = rows
+            .iter()
+            .map(|(cohort, claimant, entitlements)| CampaignCsvRow {
+                cohort: cohort.to_string(),
+                claimant: *claimant,
+                entitlements: *entitlements,
+            })
+            .collect();
+
+        let mut names: Vec<&str> = rows.iter().map(|(cohort, _, _)| *cohort).collect();
+        names.dedup();
+        let cohorts_rows: Vec<CohortsCsvRow> = names
+            .iter()
+            .map(|name| CohortsCsvRow {
+                cohort: name.to_string(),
+                share_percentage: Decimal::from(100) / Decimal::from(names.len()),
+                allocation_mode: AllocationMode::SharePercentage,
+                allocation_value: None,
+                min_per_claimant: None,
+                max_per_claimant: None,
+            })
+            .collect();
+
+        compile_campaign_db_with_options(
+            AddressFinder::default(),
+            &campaign_rows,
+            &cohorts_rows,
+            &CampaignSettings {
+                budget: Decimal::from_str("1000").unwrap(),
+                mint: Pubkey::new_from_array([1; 32]),
+                mint_decimals: 6,
+                admin: Pubkey::new_from_array([2; 32]),
+                claimants_per_vault: 1,
+            },
+            &CompileOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_identical_campaigns() {
+        let rows = [
+            ("Alpha", Pubkey::new_unique(), 1),
+            ("Beta", Pubkey::new_unique(), 2),
+        ];
+        let diff = diff_campaigns(&compile(&rows), &compile(&rows)).unwrap();
+
+        assert!(diff.is_empty());
+        assert_eq!(diff.cohorts.len(), 2);
+        assert!(diff.cohorts.iter().all(|c| c.added.is_empty()));
+    }
+
+    #[test]
+    fn test_corrected_claimant_list() {
+        let (a, b, c, d) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let old = compile(&[("Alpha", a, 1), ("Alpha", b, 1), ("Beta", c, 1)]);
+        let new = compile(&[("Alpha", a, 3), ("Alpha", d, 1), ("Beta", c, 1)]);
+
+        let diff = diff_campaigns(&old, &new).unwrap();
+        assert!(diff.fingerprint_changed());
+        assert!(!diff.is_empty());
+
+        let alpha = &diff.cohorts[0];
+        assert_eq!(alpha.change, CohortChange::Modified);
+        assert_eq!(
+            alpha.added.iter().map(|e| e.claimant).collect::<Vec<_>>(),
+            vec![d]
+        );
+        assert_eq!(
+            alpha.removed.iter().map(|e| e.claimant).collect::<Vec<_>>(),
+            vec![b]
+        );
+        assert_eq!(
+            alpha.changed,
+            vec![EntitlementChange {
+                claimant: a,
+                old_entitlements: 1,
+                new_entitlements: 3,
+            }]
+        );
+        assert_ne!(
+            alpha.old_amount_per_entitlement,
+            alpha.new_amount_per_entitlement
+        );
+
+        // Beta keeps its claimant and budget share
+        assert_eq!(diff.cohorts[1].change, CohortChange::Unchanged);
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["cohorts"][0]["change"], "modified");
+        assert_eq!(json["cohorts"][0]["added"][0]["claimant"], d.to_string());
+        assert_eq!(json["old_fingerprint"], hex::encode(old_fingerprint(&old)));
+    }
+
+    #[test]
+    fn test_added_and_removed_cohorts() {
+        let a = Pubkey::new_unique();
+        let old = compile(&[("Alpha", a, 1)]);
+        let new = compile(&[("Beta", a, 1)]);
+
+        let diff = diff_campaigns(&old, &new).unwrap();
+        assert_eq!(diff.cohorts.len(), 2);
+        assert_eq!(diff.cohorts[0].change, CohortChange::Removed);
+        assert_eq!(diff.cohorts[0].removed.len(), 1);
+        assert_eq!(diff.cohorts[0].new_funding, 0);
+        assert_eq!(diff.cohorts[1].change, CohortChange::Added);
+        assert_eq!(diff.cohorts[1].vaults[0].old_required, None);
+        assert_eq!(
+            diff.funding_delta,
+            diff.new_total_funding as i128 - diff.old_total_funding as i128
+        );
+    }
+
+    fn old_fingerprint(db: &CampaignDatabase) -> [u8; 32] {
+        db.read_campaign_info().unwrap().fingerprint
+    }
+}
@@ -0,0 +1,806 @@
+/*!
+# Campaign Layout
+
+Choosing each cohort's tree version, arity and vault count from its claimant count,
+instead of by trial and error against where claims break
+(`test_claim_maximum_merkle_proof_size`, `test_claim_maximum_instruction_data_size`).
+
+[`plan_layout`] sizes the widest claim of every cohort with the [`ClaimCostModel`]:
+
+- **Tree**: a binary V0 tree when its widest claim transaction fits in a packet and its
+  compute units, plus margin, fit under the limit. Otherwise the V1 arity with the
+  fewest compute units among those that fit. Claim transactions carry
+  `SetComputeUnitLimit` and `SetComputeUnitPrice`, leaving room for the compute budget
+  and priority fee, and are sized as v0 messages against the campaign's lookup table
+  (see [`crate::lookup_table`]) unless planning for legacy transactions. V0 cohorts are
+  claimed with `claim_tokens_v0` and V1 cohorts with `claim_tokens_v2`, which also reads
+  the cohort tree account. With [`LayoutConstraints::tree_arity`] every cohort gets a V1
+  tree of that arity instead, and only its vaults are planned.
+- **Vaults**: the vault is the only account every claim of a cohort write-locks, and
+  the runtime caps the compute units a block may spend on one writable account. A
+  cohort gets enough vaults to sustain its peak claims per slot at the planned compute
+  unit limit, and at least enough to keep each vault under `max_claimants_per_vault`.
+*/
+
+use crate::compute_budget::{ComputeBudgetConfig, DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT};
+use crate::manifest::TreeVersion;
+use crate::tree_arity::{ArityEstimate, ClaimCostModel};
+use prism_protocol::claim_tree_constants::{MAX_TREE_ARITY, MIN_TREE_ARITY};
+use prism_protocol_csvs::{CampaignCsvRow, CohortsCsvRow};
+use prism_protocol_tx::message::short_vec_len;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use std::collections::HashMap;
+use std::fmt;
+use thiserror::Error;
+
+/// Compute units a block may spend on transactions write-locking one account
+pub const MAX_WRITABLE_ACCOUNT_UNITS_PER_BLOCK: u64 = 12_000_000;
+
+/// Most vaults a cohort can expect (`expected_vault_count` is a `u8`)
+pub const MAX_VAULTS_PER_COHORT: usize = u8::MAX as usize;
+
+/// Accounts of `claim_tokens_v0`, claiming from binary V0 trees
+const CLAIM_V0_ACCOUNTS: usize = 12;
+
+/// Accounts of `claim_tokens_v2`, claiming from V1 trees: those of `claim_tokens_v0`
+/// and the cohort tree
+const CLAIM_V2_ACCOUNTS: usize = 13;
+
+/// Claim accounts that differ per claimant (claimant, token account and receipt) and so
+/// stay out of the lookup table
+const CLAIMANT_ACCOUNTS: usize = 3;
+
+/// Data of `SetComputeUnitLimit` (tag and u32) and `SetComputeUnitPrice` (tag and u64)
+const COMPUTE_BUDGET_DATA_BYTES: [usize; 2] = [5, 9];
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    #[error("Tree arity {arity} is outside {MIN_TREE_ARITY}..={MAX_TREE_ARITY}")]
+    InvalidArity { arity: usize },
+
+    #[error("Cohort {cohort} ({claimant_count} claimants) has no tree whose claims fit in {max_transaction_bytes} bytes and {max_compute_units} compute units; split it into smaller cohorts")]
+    NoFeasibleTree {
+        cohort: String,
+        claimant_count: usize,
+        max_transaction_bytes: usize,
+        max_compute_units: u32,
+    },
+
+    #[error("Cohort {cohort} needs {vault_count} vaults at {max_claimants_per_vault} claimants per vault, but a cohort holds at most {MAX_VAULTS_PER_COHORT}")]
+    TooManyVaults {
+        cohort: String,
+        vault_count: usize,
+        max_claimants_per_vault: usize,
+    },
+}
+
+pub type LayoutResult<T> = Result<T, LayoutError>;
+
+/// Limits every planned claim has to fit in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutConstraints {
+    /// Largest serialized claim transaction
+    pub max_transaction_bytes: usize,
+
+    /// Largest compute unit limit a claim may request. The 200K default is what a claim
+    /// gets without `SetComputeUnitLimit`, so claims land even from wallets that drop it.
+    pub max_compute_units: u32,
+
+    /// Headroom added to modelled compute units, in percent
+    pub compute_unit_margin_percent: u32,
+
+    /// Most claimants a single vault serves
+    pub max_claimants_per_vault: usize,
+
+    /// Claims per slot each cohort should sustain at its peak
+    pub peak_claims_per_slot: u64,
+
+    /// Whether claims are v0 transactions using the campaign's lookup table
+    pub lookup_table: bool,
+
+    /// Arity of every cohort's V1 tree; `None` lets the planner pick a binary V0 tree or
+    /// the cheapest V1 arity per cohort
+    pub tree_arity: Option<usize>,
+}
+
+impl Default for LayoutConstraints {
+    fn default() -> Self {
+        Self {
+            max_transaction_bytes: PACKET_DATA_SIZE,
+            max_compute_units: 200_000,
+            compute_unit_margin_percent: DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT,
+            max_claimants_per_vault: 200_000,
+            peak_claims_per_slot: 100,
+            lookup_table: true,
+            tree_arity: None,
+        }
+    }
+}
+
+/// Limit that ruled out a binary V0 tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutLimit {
+    /// The widest claim transaction is too large
+    TransactionBytes { bytes: usize, limit: usize },
+
+    /// The widest claim needs too high a compute unit limit
+    ComputeUnits { units: u32, limit: u32 },
+}
+
+impl fmt::Display for LayoutLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutLimit::TransactionBytes { bytes, limit } => write!(
+                f,
+                "its widest claim transaction would be {} bytes (limit {})",
+                bytes, limit
+            ),
+            LayoutLimit::ComputeUnits { units, limit } => write!(
+                f,
+                "its widest claim would need {} compute units (limit {})",
+                units, limit
+            ),
+        }
+    }
+}
+
+/// What set a cohort's vault count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VaultSizing {
+    /// One vault holds every claimant and sustains the peak claim rate
+    Single,
+
+    /// Vaults hold at most this many claimants each
+    ClaimantsPerVault(usize),
+
+    /// Vaults sustain this many claims per slot between them
+    Throughput(u64),
+}
+
+impl fmt::Display for VaultSizing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VaultSizing::Single => f.write_str("one vault holds the cohort at its peak claim rate"),
+            VaultSizing::ClaimantsPerVault(claimants) => {
+                write!(f, "at most {} claimants per vault", claimants)
+            }
+            VaultSizing::Throughput(claims) => write!(
+                f,
+                "{} claims per slot within the per-account block limit of {} compute units",
+                claims, MAX_WRITABLE_ACCOUNT_UNITS_PER_BLOCK
+            ),
+        }
+    }
+}
+
+/// Planned layout of one cohort
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CohortLayout {
+    pub name: String,
+    pub claimant_count: usize,
+    pub tree_version: TreeVersion,
+
+    /// Widest claim in the planned tree; its `arity` is the tree arity to commit
+    pub estimate: ArityEstimate,
+
+    /// Serialized size of the widest claim transaction
+    pub transaction_bytes: usize,
+
+    /// Compute unit limit for claims: modelled units plus margin
+    pub compute_unit_limit: u32,
+
+    pub vault_count: usize,
+    pub vault_sizing: VaultSizing,
+
+    /// Why a binary V0 tree was passed over, unless the arity was fixed
+    pub v0_limit: Option<LayoutLimit>,
+}
+
+impl fmt::Display for CohortLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.tree_version {
+            TreeVersion::V0 => write!(f, "V0 binary tree")?,
+            TreeVersion::V1 => write!(f, "V1 tree of arity {}", self.estimate.arity)?,
+        }
+        write!(
+            f,
+            ", depth {}: claims up to {} bytes and {} compute units, {} vault(s)",
+            self.estimate.depth, self.transaction_bytes, self.compute_unit_limit, self.vault_count
+        )
+    }
+}
+
+/// Planned layout of every cohort of a campaign
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CampaignLayout {
+    pub cohorts: Vec<CohortLayout>,
+}
+
+impl CampaignLayout {
+    pub fn cohort(&self, name: &str) -> Option<&CohortLayout> {
+        self.cohorts.iter().find(|cohort| cohort.name == name)
+    }
+
+    /// Planned vault count of every cohort, by name
+    pub fn vault_counts(&self) -> HashMap<String, usize> {
+        self.cohorts
+            .iter()
+            .map(|cohort| (cohort.name.clone(), cohort.vault_count))
+            .collect()
+    }
+
+    /// Planned arity of every cohort planned as a V1 tree, by name
+    pub fn tree_arities(&self) -> HashMap<String, usize> {
+        self.cohorts
+            .iter()
+            .filter(|cohort| cohort.tree_version == TreeVersion::V1)
+            .map(|cohort| (cohort.name.clone(), cohort.estimate.arity as usize))
+            .collect()
+    }
+}
+
+/// Plans cohort layouts from a cost model and constraints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayoutPlanner {
+    pub model: ClaimCostModel,
+    pub constraints: LayoutConstraints,
+}
+
+impl LayoutPlanner {
+    pub fn new(constraints: LayoutConstraints) -> Self {
+        Self {
+            model: ClaimCostModel::default(),
+            constraints,
+        }
+    }
+
+    /// Layout of a cohort of `claimant_count` claimants
+    pub fn plan_cohort(&self, name: &str, claimant_count: usize) -> LayoutResult<CohortLayout> {
+        let no_feasible_tree = || LayoutError::NoFeasibleTree {
+            cohort: name.to_string(),
+            claimant_count,
+            max_transaction_bytes: self.constraints.max_transaction_bytes,
+            max_compute_units: self.constraints.max_compute_units,
+        };
+        let (tree_version, estimate, transaction_bytes, compute_unit_limit, v0_limit) =
+            if let Some(arity) = self.constraints.tree_arity {
+                let arity = u16::try_from(arity)
+                    .ok()
+                    .filter(|arity| (MIN_TREE_ARITY..=MAX_TREE_ARITY).contains(arity))
+                    .ok_or(LayoutError::InvalidArity { arity })?;
+                let estimate = self.model.estimate(claimant_count, arity);
+                let (bytes, units) = self
+                    .check(TreeVersion::V1, &estimate)
+                    .map_err(|_| no_feasible_tree())?;
+                (TreeVersion::V1, estimate, bytes, units, None)
+            } else {
+                let v0 = self.model.estimate_v0(claimant_count);
+                match self.check(TreeVersion::V0, &v0) {
+                    Ok((bytes, units)) => (TreeVersion::V0, v0, bytes, units, None),
+                    Err(limit) => {
+                        let (estimate, bytes, units) = self
+                            .model
+                            .estimates(claimant_count)
+                            .into_iter()
+                            .filter_map(|estimate| {
+                                let (bytes, units) = self.check(TreeVersion::V1, &estimate).ok()?;
+                                Some((estimate, bytes, units))
+                            })
+                            .min_by_key(|(estimate, bytes, _)| {
+                                (estimate.compute_units, *bytes, estimate.arity)
+                            })
+                            .ok_or_else(no_feasible_tree)?;
+                        (TreeVersion::V1, estimate, bytes, units, Some(limit))
+                    }
+                }
+            };
+
+        let (vault_count, vault_sizing) =
+            self.vault_count(name, claimant_count, compute_unit_limit)?;
+
+        Ok(CohortLayout {
+            name: name.to_string(),
+            claimant_count,
+            tree_version,
+            estimate,
+            transaction_bytes,
+            compute_unit_limit,
+            vault_count,
+            vault_sizing,
+            v0_limit,
+        })
+    }
+
+    /// Layouts of cohorts given as `(name, claimant count)`, in order
+    pub fn plan(&self, cohort_sizes: &[(String, usize)]) -> LayoutResult<CampaignLayout> {
+        let cohorts = cohort_sizes
+            .iter()
+            .map(|(name, claimant_count)| self.plan_cohort(name, *claimant_count))
+            .collect::<LayoutResult<_>>()?;
+        Ok(CampaignLayout { cohorts })
+    }
+
+    /// Largest cohort whose claims fit a binary V0 tree
+    pub fn max_v0_claimants(&self) -> usize {
+        let mut max = 1;
+        while max <= usize::MAX / 2
+            && self
+                .check(TreeVersion::V0, &self.model.estimate_v0(max * 2))
+                .is_ok()
+        {
+            max *= 2;
+        }
+        max
+    }
+
+    /// Claim transaction bytes and compute unit limit, if within the constraints
+    fn check(
+        &self,
+        tree_version: TreeVersion,
+        estimate: &ArityEstimate,
+    ) -> Result<(usize, u32), LayoutLimit> {
+        let bytes = claim_transaction_bytes(
+            tree_version,
+            estimate.instruction_data_bytes,
+            self.constraints.lookup_table,
+        );
+        if bytes > self.constraints.max_transaction_bytes {
+            return Err(LayoutLimit::TransactionBytes {
+                bytes,
+                limit: self.constraints.max_transaction_bytes,
+            });
+        }
+
+        let units = ComputeBudgetConfig {
+            compute_unit_margin_percent: self.constraints.compute_unit_margin_percent,
+            ..Default::default()
+        }
+        .compute_unit_limit(estimate.compute_units);
+        if units > self.constraints.max_compute_units {
+            return Err(LayoutLimit::ComputeUnits {
+                units,
+                limit: self.constraints.max_compute_units,
+            });
+        }
+
+        Ok((bytes, units))
+    }
+
+    /// Vaults for a cohort's claimants and its peak claim rate
+    fn vault_count(
+        &self,
+        name: &str,
+        claimant_count: usize,
+        compute_unit_limit: u32,
+    ) -> LayoutResult<(usize, VaultSizing)> {
+        let max_claimants_per_vault = self.constraints.max_claimants_per_vault.max(1);
+        let by_claimants = claimant_count.div_ceil(max_claimants_per_vault);
+        if by_claimants > MAX_VAULTS_PER_COHORT {
+            return Err(LayoutError::TooManyVaults {
+                cohort: name.to_string(),
+                vault_count: by_claimants,
+                max_claimants_per_vault,
+            });
+        }
+
+        // Claims beyond the claimant count never happen
+        let peak_claims = self
+            .constraints
+            .peak_claims_per_slot
+            .min(claimant_count as u64);
+        let by_throughput = (peak_claims * compute_unit_limit as u64)
+            .div_ceil(MAX_WRITABLE_ACCOUNT_UNITS_PER_BLOCK)
+            .min(MAX_VAULTS_PER_COHORT as u64) as usize;
+
+        Ok(if by_claimants <= 1 && by_throughput <= 1 {
+            (1, VaultSizing::Single)
+        } else if by_claimants >= by_throughput {
+            (
+                by_claimants,
+                VaultSizing::ClaimantsPerVault(max_claimants_per_vault),
+            )
+        } else {
+            (by_throughput, VaultSizing::Throughput(peak_claims))
+        })
+    }
+}
+
+/// Layouts of cohorts given as `(name, claimant count)` under the default cost model
+pub fn plan_layout(
+    cohort_sizes: &[(String, usize)],
+    constraints: &LayoutConstraints,
+) -> LayoutResult<CampaignLayout> {
+    LayoutPlanner::new(*constraints).plan(cohort_sizes)
+}
+
+/// Claimants of every cohort with any, in cohorts.csv order
+pub fn cohort_sizes(
+    campaign_rows: &[CampaignCsvRow],
+    cohorts_rows: &[CohortsCsvRow],
+) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for row in campaign_rows {
+        *counts.entry(row.cohort.as_str()).or_default() += 1;
+    }
+
+    cohorts_rows
+        .iter()
+        .filter_map(|config| {
+            let count = counts.get(config.cohort.as_str())?;
+            Some((config.cohort.clone(), *count))
+        })
+        .collect()
+}
+
+/// Serialized size of a claim transaction signed by the claimant, with
+/// `SetComputeUnitLimit` and `SetComputeUnitPrice` ahead of the claim
+///
+/// The claim is `claim_tokens_v0` for a V0 tree and `claim_tokens_v2` for a V1 tree.
+/// With `lookup_table`, a v0 message loading every shared claim account from the
+/// campaign's lookup table; otherwise a legacy message.
+pub fn claim_transaction_bytes(
+    tree_version: TreeVersion,
+    instruction_data_bytes: usize,
+    lookup_table: bool,
+) -> usize {
+    let claim_accounts = match tree_version {
+        TreeVersion::V0 => CLAIM_V0_ACCOUNTS,
+        TreeVersion::V1 => CLAIM_V2_ACCOUNTS,
+    };
+    // Programs are invoked, so they stay static keys even with a lookup table
+    let (static_keys, lookup_bytes) = if lookup_table {
+        // Version prefix, then one table with the vault writable and the rest readonly
+        let loaded = claim_accounts - CLAIMANT_ACCOUNTS;
+        let lookup =
+            short_vec_len(1) + 32 + short_vec_len(1) + 1 + short_vec_len(loaded - 1) + (loaded - 1);
+        (CLAIMANT_ACCOUNTS + 2, 1 + lookup)
+    } else {
+        (claim_accounts + 2, 0)
+    };
+
+    let claim_instruction = 1
+        + short_vec_len(claim_accounts)
+        + claim_accounts
+        + short_vec_len(instruction_data_bytes)
+        + instruction_data_bytes;
+    // Program index, empty account list and data length, then data
+    let budget_instructions: usize = COMPUTE_BUDGET_DATA_BYTES
+        .iter()
+        .map(|data_bytes| 3 + data_bytes)
+        .sum();
+
+    short_vec_len(1)
+        + 64
+        + 3 // message header
+        + short_vec_len(static_keys)
+        + 32 * static_keys
+        + 32 // recent blockhash
+        + short_vec_len(1 + COMPUTE_BUDGET_DATA_BYTES.len())
+        + claim_instruction
+        + budget_instructions
+        + lookup_bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lookup_table::{campaign_lookup_table_addresses, compile_message, transaction_size};
+    use crate::{build_claim_tokens_v0_ix, build_claim_tokens_v2_ix, AddressFinder, ClaimV1Params};
+    use prism_protocol_csvs::AllocationMode;
+    use prism_protocol_db::{CohortInfo, CohortTree, HashScheme};
+    use rust_decimal::Decimal;
+    use solana_sdk::compute_budget::ComputeBudgetInstruction;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::message::AddressLookupTableAccount;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn sizes(cohorts: &[(&str, usize)]) -> Vec<(String, usize)> {
+        cohorts
+            .iter()
+            .map(|(name, count)| (name.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn test_claim_transaction_bytes_match_real_transactions() {
+        let address_finder = AddressFinder::default();
+        let admin = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let fingerprint = [1; 32];
+        let (v0_root, v1_root) = ([2; 32], [4; 32]);
+        let cohort = |merkle_root, tree| CohortInfo {
+            name: "cohort".to_string(),
+            merkle_root,
+            amount_per_entitlement: 1,
+            amount_per_entitlement_humane: "1".to_string(),
+            vaults: vec![],
+            vault_count: 1,
+            tree,
+        };
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: campaign_lookup_table_addresses(
+                &address_finder,
+                &admin,
+                &fingerprint,
+                &mint,
+                &[
+                    cohort(v0_root, CohortTree::V0),
+                    cohort(
+                        v1_root,
+                        CohortTree::V1 {
+                            arity: 3,
+                            hash_scheme: HashScheme::Sha256,
+                        },
+                    ),
+                ],
+            )
+            .addresses,
+        };
+        let budget = [
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            ComputeBudgetInstruction::set_compute_unit_price(1_000),
+        ];
+
+        for depth in [0usize, 3, 17, 30] {
+            let (v0, _, _) = build_claim_tokens_v0_ix(
+                &address_finder,
+                admin,
+                claimant,
+                mint,
+                Pubkey::new_unique(),
+                fingerprint,
+                v0_root,
+                vec![[3; 32]; depth],
+                0,
+                1,
+            )
+            .unwrap();
+            let (v2, _, _) = build_claim_tokens_v2_ix(
+                &address_finder,
+                ClaimV1Params {
+                    admin,
+                    claimant,
+                    mint,
+                    claimant_token_account: Pubkey::new_unique(),
+                    campaign_fingerprint: fingerprint,
+                    cohort_merkle_root: v1_root,
+                    merkle_proof: vec![vec![[3; 32]; 2]; depth],
+                    assigned_vault_index: 0,
+                    entitlements: 1,
+                },
+            )
+            .unwrap();
+
+            for (tree_version, ix) in [(TreeVersion::V0, v0), (TreeVersion::V1, v2)] {
+                let data_bytes = ix.data.len();
+                let instructions = [budget[0].clone(), budget[1].clone(), ix];
+
+                let legacy =
+                    compile_message(&claimant, &instructions, &[], Hash::default()).unwrap();
+                assert_eq!(
+                    transaction_size(&legacy),
+                    claim_transaction_bytes(tree_version, data_bytes, false),
+                    "depth {}",
+                    depth
+                );
+
+                let versioned = compile_message(
+                    &claimant,
+                    &instructions,
+                    std::slice::from_ref(&table),
+                    Hash::default(),
+                )
+                .unwrap();
+                assert_eq!(
+                    transaction_size(&versioned),
+                    claim_transaction_bytes(tree_version, data_bytes, true),
+                    "depth {}",
+                    depth
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_small_cohorts_use_v0() {
+        let layout = plan_layout(
+            &sizes(&[("solo", 1), ("small", 1_000), ("medium", 4_096)]),
+            &LayoutConstraints::default(),
+        )
+        .unwrap();
+
+        for cohort in &layout.cohorts {
+            assert_eq!(cohort.tree_version, TreeVersion::V0, "{}", cohort.name);
+            assert_eq!(cohort.estimate.arity, 2);
+            assert_eq!(cohort.v0_limit, None);
+            assert!(cohort.transaction_bytes <= PACKET_DATA_SIZE);
+            assert!(cohort.compute_unit_limit <= 200_000);
+        }
+        assert_eq!(layout.cohort("solo").unwrap().vault_count, 1);
+        assert_eq!(
+            layout.cohort("solo").unwrap().vault_sizing,
+            VaultSizing::Single
+        );
+        assert_eq!(layout.cohort("medium").unwrap().estimate.depth, 12);
+    }
+
+    #[test]
+    fn test_large_cohort_uses_cheapest_fitting_v1_arity() {
+        let planner = LayoutPlanner::default();
+        let layout = planner.plan_cohort("large", 100_000).unwrap();
+
+        assert_eq!(layout.tree_version, TreeVersion::V1);
+        assert!(matches!(
+            layout.v0_limit,
+            Some(LayoutLimit::ComputeUnits { limit: 200_000, .. })
+        ));
+        assert!(layout.compute_unit_limit <= 200_000);
+        assert!(layout.transaction_bytes <= PACKET_DATA_SIZE);
+
+        // Legacy transactions can't carry its proofs
+        let legacy = LayoutPlanner::new(LayoutConstraints {
+            lookup_table: false,
+            ..Default::default()
+        });
+        assert!(matches!(
+            legacy.plan_cohort("large", 100_000),
+            Err(LayoutError::NoFeasibleTree { .. })
+        ));
+
+        // Nothing that fits is cheaper
+        for estimate in planner.model.estimates(100_000) {
+            if planner.check(TreeVersion::V1, &estimate).is_ok() {
+                assert!(estimate.compute_units >= layout.estimate.compute_units);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixed_tree_arity() {
+        let planner = LayoutPlanner::new(LayoutConstraints {
+            tree_arity: Some(4),
+            ..Default::default()
+        });
+        let layout = planner
+            .plan(&sizes(&[("small", 10), ("medium", 4_096)]))
+            .unwrap();
+        for cohort in &layout.cohorts {
+            assert_eq!(cohort.tree_version, TreeVersion::V1, "{}", cohort.name);
+            assert_eq!(cohort.estimate.arity, 4);
+            assert_eq!(cohort.v0_limit, None);
+        }
+        assert_eq!(layout.cohort("medium").unwrap().estimate.depth, 6);
+        assert_eq!(
+            layout.tree_arities(),
+            HashMap::from([("small".to_string(), 4), ("medium".to_string(), 4)])
+        );
+
+        // Planned V0 cohorts keep binary trees
+        let layout = LayoutPlanner::default()
+            .plan(&sizes(&[("small", 10), ("large", 100_000)]))
+            .unwrap();
+        let arities = layout.tree_arities();
+        assert!(!arities.contains_key("small"));
+        assert_eq!(
+            arities["large"],
+            layout.cohort("large").unwrap().estimate.arity as usize
+        );
+
+        for arity in [0, 1, 257] {
+            let planner = LayoutPlanner::new(LayoutConstraints {
+                tree_arity: Some(arity),
+                ..Default::default()
+            });
+            assert_eq!(
+                planner.plan_cohort("cohort", 10),
+                Err(LayoutError::InvalidArity { arity })
+            );
+        }
+        let wide = LayoutPlanner::new(LayoutConstraints {
+            tree_arity: Some(256),
+            ..Default::default()
+        });
+        assert!(matches!(
+            wide.plan_cohort("large", 100_000),
+            Err(LayoutError::NoFeasibleTree { .. })
+        ));
+    }
+
+    #[test]
+    fn test_v0_boundary() {
+        // At the default limit, compute caps V0 trees at 13 levels
+        let default = LayoutPlanner::default();
+        let max_v0 = default.max_v0_claimants();
+        assert_eq!(max_v0, 8_192);
+        assert_eq!(
+            default.plan_cohort("edge", max_v0).unwrap().tree_version,
+            TreeVersion::V0
+        );
+        let layout = default.plan_cohort("edge", max_v0 + 1).unwrap();
+        assert_eq!(layout.tree_version, TreeVersion::V1);
+        assert!(matches!(
+            layout.v0_limit,
+            Some(LayoutLimit::ComputeUnits { .. })
+        ));
+
+        // With the full compute budget available, transaction size is the bound
+        let generous = LayoutPlanner::new(LayoutConstraints {
+            max_compute_units: 1_400_000,
+            lookup_table: false,
+            ..Default::default()
+        });
+        let max_v0 = generous.max_v0_claimants();
+        assert_eq!(max_v0, 1 << 17);
+        assert!(generous
+            .check(TreeVersion::V0, &generous.model.estimate_v0(max_v0 + 1))
+            .is_err_and(|limit| matches!(limit, LayoutLimit::TransactionBytes { .. })));
+    }
+
+    #[test]
+    fn test_vault_sizing() {
+        // Throughput: 100 peak claims per slot at ~170K CU don't fit one vault's block budget
+        let layout = LayoutPlanner::default().plan_cohort("busy", 4_096).unwrap();
+        let expected = (100 * layout.compute_unit_limit as u64)
+            .div_ceil(MAX_WRITABLE_ACCOUNT_UNITS_PER_BLOCK) as usize;
+        assert!(expected > 1);
+        assert_eq!(layout.vault_count, expected);
+        assert_eq!(layout.vault_sizing, VaultSizing::Throughput(100));
+
+        // Claimants per vault
+        let planner = LayoutPlanner::new(LayoutConstraints {
+            max_claimants_per_vault: 100,
+            ..Default::default()
+        });
+        let layout = planner.plan_cohort("crowded", 1_000).unwrap();
+        assert_eq!(layout.vault_count, 10);
+        assert_eq!(layout.vault_sizing, VaultSizing::ClaimantsPerVault(100));
+
+        assert_eq!(
+            planner.plan_cohort("huge", 100 * MAX_VAULTS_PER_COHORT + 1),
+            Err(LayoutError::TooManyVaults {
+                cohort: "huge".to_string(),
+                vault_count: MAX_VAULTS_PER_COHORT + 1,
+                max_claimants_per_vault: 100,
+            })
+        );
+    }
+
+    #[test]
+    fn test_no_feasible_tree() {
+        let planner = LayoutPlanner::new(LayoutConstraints {
+            max_compute_units: 80_000,
+            ..Default::default()
+        });
+        assert!(planner.plan_cohort("tiny", 2).is_ok());
+        assert!(matches!(
+            planner.plan_cohort("big", 1_000_000),
+            Err(LayoutError::NoFeasibleTree { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cohort_sizes_follow_cohorts_csv() {
+        let row = |cohort: &str| CampaignCsvRow {
+            cohort: cohort.to_string(),
+            claimant: Pubkey::new_unique(),
+            entitlements: 1,
+        };
+        let config = |cohort: &str| CohortsCsvRow {
+            cohort: cohort.to_string(),
+            share_percentage: Decimal::from(50),
+            allocation_mode: AllocationMode::SharePercentage,
+            allocation_value: None,
+            min_per_claimant: None,
+            max_per_claimant: None,
+        };
+
+        let campaign_rows = vec![row("b"), row("a"), row("b")];
+        let cohorts_rows = vec![config("b"), config("empty"), config("a")];
+        assert_eq!(
+            cohort_sizes(&campaign_rows, &cohorts_rows),
+            sizes(&[("b", 2), ("a", 1)])
+        );
+    }
+}
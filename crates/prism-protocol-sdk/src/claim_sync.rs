@@ -0,0 +1,396 @@
+/*!
+# Claim Sync
+
+Reconciles the claim status recorded in a campaign database with the `ClaimReceiptV0`
+accounts on-chain, so `claimed_at`/`claimed_signature` reflect every claim, not only
+those made through the CLI.
+
+Syncing a cohort is split in two steps so the RPC work stays with the caller:
+
+1. [`CohortClaimSync::plan`] compares the cohort's receipts with its claimant rows,
+   collecting rows to update and drift the database can't explain
+2. [`apply_claims`] writes a batch of planned claims in one transaction
+
+The database is its own cursor: a row is only planned while it differs from the chain,
+so re-running a sync is idempotent and an interrupted sync resumes where its last
+committed batch ended.
+*/
+
+use std::{collections::HashMap, fmt};
+
+use prism_protocol::state::ClaimReceiptV0;
+use prism_protocol_db::{CampaignDatabase, ClaimUpdate, DbResult};
+use solana_sdk::pubkey::Pubkey;
+
+/// Disagreement between the database and the chain that syncing doesn't resolve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClaimDrift {
+    /// Receipt in the cohort for a claimant the database doesn't list
+    UnknownClaimant { claimant: Pubkey, receipt: Pubkey },
+    /// Database records a claim that has no receipt on-chain
+    MissingReceipt { claimant: Pubkey },
+    /// Receipt paid from a different vault than the database assigns
+    VaultMismatch {
+        claimant: Pubkey,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+}
+
+impl fmt::Display for ClaimDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClaimDrift::UnknownClaimant { claimant, receipt } => {
+                write!(f, "receipt {} for unknown claimant {}", receipt, claimant)
+            }
+            ClaimDrift::MissingReceipt { claimant } => {
+                write!(f, "{} is recorded as claimed but has no receipt", claimant)
+            }
+            ClaimDrift::VaultMismatch {
+                claimant,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} claimed from vault {} instead of {}",
+                claimant, actual, expected
+            ),
+        }
+    }
+}
+
+/// A claim found on-chain that the database doesn't record yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingClaim {
+    pub claimant: Pubkey,
+    pub receipt: Pubkey,
+    /// Receipt timestamp, written as `claimed_at`
+    pub claimed_at: i64,
+    /// Signature to record; starts as the one already in the database, if any
+    pub signature: Option<String>,
+    /// The claim's signature was requested but isn't known yet
+    pub needs_signature: bool,
+}
+
+/// Sync plan for one cohort
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CohortClaimSync {
+    pub cohort_name: String,
+    /// Receipts found on-chain
+    pub receipts: usize,
+    /// Claims the database already records as on-chain
+    pub up_to_date: usize,
+    pub pending: Vec<PendingClaim>,
+    pub drift: Vec<ClaimDrift>,
+}
+
+impl CohortClaimSync {
+    /// Compare a cohort's receipts, as `(receipt address, receipt)`, with its claimant rows.
+    ///
+    /// With `with_signatures`, claims whose signature isn't recorded stay pending until
+    /// one is found.
+    pub fn plan(
+        db: &CampaignDatabase,
+        cohort_name: &str,
+        receipts: &[(Pubkey, ClaimReceiptV0)],
+        with_signatures: bool,
+    ) -> DbResult<Self> {
+        let mut statuses: HashMap<Pubkey, _> = db
+            .read_cohort_claim_statuses(cohort_name)?
+            .into_iter()
+            .map(|status| (status.claimant, status))
+            .collect();
+
+        let mut sync = Self {
+            cohort_name: cohort_name.to_string(),
+            receipts: receipts.len(),
+            up_to_date: 0,
+            pending: Vec::new(),
+            drift: Vec::new(),
+        };
+
+        for (address, receipt) in receipts {
+            let Some(status) = statuses.remove(&receipt.claimant) else {
+                sync.drift.push(ClaimDrift::UnknownClaimant {
+                    claimant: receipt.claimant,
+                    receipt: *address,
+                });
+                continue;
+            };
+
+            if status.assigned_vault_pubkey != receipt.assigned_vault {
+                sync.drift.push(ClaimDrift::VaultMismatch {
+                    claimant: receipt.claimant,
+                    expected: status.assigned_vault_pubkey,
+                    actual: receipt.assigned_vault,
+                });
+            }
+
+            let needs_signature = with_signatures && status.claimed_signature.is_none();
+            if status.claimed_at == Some(receipt.claimed_at_timestamp) && !needs_signature {
+                sync.up_to_date += 1;
+                continue;
+            }
+
+            sync.pending.push(PendingClaim {
+                claimant: receipt.claimant,
+                receipt: *address,
+                claimed_at: receipt.claimed_at_timestamp,
+                signature: status.claimed_signature,
+                needs_signature,
+            });
+        }
+
+        // Whatever is left has no receipt
+        let mut missing: Vec<Pubkey> = statuses
+            .into_values()
+            .filter(|status| status.claimed)
+            .map(|status| status.claimant)
+            .collect();
+        missing.sort_by_key(|claimant| claimant.to_string());
+        sync.drift.extend(
+            missing
+                .into_iter()
+                .map(|claimant| ClaimDrift::MissingReceipt { claimant }),
+        );
+
+        Ok(sync)
+    }
+
+    /// Claimants recorded as claimed without a receipt
+    pub fn missing_receipts(&self) -> impl Iterator<Item = Pubkey> + '_ {
+        self.drift.iter().filter_map(|drift| match drift {
+            ClaimDrift::MissingReceipt { claimant } => Some(*claimant),
+            _ => None,
+        })
+    }
+
+    /// Whether the database already matches the chain
+    pub fn is_in_sync(&self) -> bool {
+        self.pending.is_empty() && self.drift.is_empty()
+    }
+}
+
+/// Record a batch of pending claims in one transaction, returning the rows changed
+pub fn apply_claims(
+    db: &mut CampaignDatabase,
+    cohort_name: &str,
+    claims: &[PendingClaim],
+) -> DbResult<usize> {
+    let updates: Vec<ClaimUpdate> = claims
+        .iter()
+        .map(|claim| ClaimUpdate {
+            claimant: claim.claimant,
+            claimed_at: Some(claim.claimed_at),
+            claimed_signature: claim.signature.clone(),
+        })
+        .collect();
+    db.update_claim_statuses(cohort_name, &updates)
+}
+
+/// Clear the claim status of claimants recorded as claimed without a receipt
+pub fn clear_missing_receipts(
+    db: &mut CampaignDatabase,
+    sync: &CohortClaimSync,
+) -> DbResult<usize> {
+    let updates: Vec<ClaimUpdate> = sync
+        .missing_receipts()
+        .map(|claimant| ClaimUpdate {
+            claimant,
+            claimed_at: None,
+            claimed_signature: None,
+        })
+        .collect();
+    db.update_claim_statuses(&sync.cohort_name, &updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prism_protocol_db::{ClaimantRecord, CohortTree, StoredProof};
+
+    struct Fixture {
+        db: CampaignDatabase,
+        claimants: Vec<Pubkey>,
+        vault: Pubkey,
+    }
+
+    fn fixture(count: usize) -> Fixture {
+        let mut db = CampaignDatabase::create_in_memory().unwrap();
+        let vault = Pubkey::new_unique();
+        db.insert_cohort(
+            "Alpha",
+            [1; 32],
+            5,
+            "0.000005",
+            1,
+            count,
+            5 * count as u64,
+            CohortTree::V0,
+        )
+        .unwrap();
+
+        let records: Vec<ClaimantRecord> = (0..count)
+            .map(|_| ClaimantRecord {
+                claimant: Pubkey::new_unique(),
+                entitlements: 1,
+                assigned_vault_index: 0,
+                assigned_vault_pubkey: vault,
+                merkle_proof: StoredProof::V0(vec![]),
+            })
+            .collect();
+        db.insert_claimants("Alpha", &records).unwrap();
+
+        Fixture {
+            db,
+            claimants: records.iter().map(|r| r.claimant).collect(),
+            vault,
+        }
+    }
+
+    fn receipt(claimant: Pubkey, vault: Pubkey, claimed_at: i64) -> (Pubkey, ClaimReceiptV0) {
+        (
+            Pubkey::new_unique(),
+            ClaimReceiptV0 {
+                claimant,
+                cohort: Pubkey::new_unique(),
+                assigned_vault: vault,
+                claimed_at_timestamp: claimed_at,
+                bump: 255,
+            },
+        )
+    }
+
+    #[test]
+    fn test_sync_is_idempotent() {
+        let Fixture {
+            mut db,
+            claimants,
+            vault,
+        } = fixture(4);
+        let receipts: Vec<_> = claimants[..3]
+            .iter()
+            .enumerate()
+            .map(|(i, claimant)| receipt(*claimant, vault, 1_700_000_000 + i as i64))
+            .collect();
+
+        let sync = CohortClaimSync::plan(&db, "Alpha", &receipts, false).unwrap();
+        assert_eq!(sync.receipts, 3);
+        assert_eq!(sync.up_to_date, 0);
+        assert_eq!(sync.pending.len(), 3);
+        assert!(sync.drift.is_empty());
+        assert_eq!(apply_claims(&mut db, "Alpha", &sync.pending).unwrap(), 3);
+
+        let statuses = db.read_cohort_claim_statuses("Alpha").unwrap();
+        let claimed_at: HashMap<Pubkey, Option<i64>> = statuses
+            .iter()
+            .map(|status| (status.claimant, status.claimed_at))
+            .collect();
+        assert_eq!(claimed_at[&claimants[1]], Some(1_700_000_001));
+        assert_eq!(claimed_at[&claimants[3]], None);
+
+        // Nothing left to do on a second run
+        let again = CohortClaimSync::plan(&db, "Alpha", &receipts, false).unwrap();
+        assert!(again.is_in_sync());
+        assert_eq!(again.up_to_date, 3);
+    }
+
+    #[test]
+    fn test_sync_resumes_after_partial_apply() {
+        let Fixture {
+            mut db,
+            claimants,
+            vault,
+        } = fixture(10);
+        let receipts: Vec<_> = claimants
+            .iter()
+            .map(|claimant| receipt(*claimant, vault, 1_700_000_000))
+            .collect();
+
+        // Interrupted after the first batch
+        let sync = CohortClaimSync::plan(&db, "Alpha", &receipts, false).unwrap();
+        apply_claims(&mut db, "Alpha", &sync.pending[..4]).unwrap();
+
+        let resumed = CohortClaimSync::plan(&db, "Alpha", &receipts, false).unwrap();
+        assert_eq!(resumed.up_to_date, 4);
+        assert_eq!(resumed.pending.len(), 6);
+        assert_eq!(resumed.pending[..], sync.pending[4..]);
+    }
+
+    #[test]
+    fn test_sync_with_signatures() {
+        let Fixture {
+            mut db,
+            claimants,
+            vault,
+        } = fixture(2);
+        let receipts = vec![
+            receipt(claimants[0], vault, 1_700_000_000),
+            receipt(claimants[1], vault, 1_700_000_000),
+        ];
+
+        // Timestamps synced without signatures first
+        let sync = CohortClaimSync::plan(&db, "Alpha", &receipts, false).unwrap();
+        apply_claims(&mut db, "Alpha", &sync.pending).unwrap();
+
+        let mut sync = CohortClaimSync::plan(&db, "Alpha", &receipts, true).unwrap();
+        assert_eq!(sync.pending.len(), 2);
+        assert!(sync.pending.iter().all(|claim| claim.needs_signature));
+
+        // Only one signature found; the other stays pending
+        sync.pending[0].signature = Some("sig".to_string());
+        apply_claims(&mut db, "Alpha", &sync.pending).unwrap();
+
+        let again = CohortClaimSync::plan(&db, "Alpha", &receipts, true).unwrap();
+        assert_eq!(again.up_to_date, 1);
+        assert_eq!(again.pending.len(), 1);
+        assert_eq!(again.pending[0].claimant, sync.pending[1].claimant);
+    }
+
+    #[test]
+    fn test_sync_reports_drift() {
+        let Fixture {
+            mut db,
+            claimants,
+            vault,
+        } = fixture(3);
+        db.update_claim_status(&claimants[2], "Alpha", "cli-sig")
+            .unwrap();
+
+        let stranger = Pubkey::new_unique();
+        let other_vault = Pubkey::new_unique();
+        let receipts = vec![
+            receipt(claimants[0], vault, 1_700_000_000),
+            receipt(claimants[1], other_vault, 1_700_000_000),
+            receipt(stranger, vault, 1_700_000_000),
+        ];
+
+        let sync = CohortClaimSync::plan(&db, "Alpha", &receipts, false).unwrap();
+        assert_eq!(sync.pending.len(), 2);
+        assert_eq!(
+            sync.drift,
+            vec![
+                ClaimDrift::VaultMismatch {
+                    claimant: claimants[1],
+                    expected: vault,
+                    actual: other_vault,
+                },
+                ClaimDrift::UnknownClaimant {
+                    claimant: stranger,
+                    receipt: receipts[2].0,
+                },
+                ClaimDrift::MissingReceipt {
+                    claimant: claimants[2],
+                },
+            ]
+        );
+
+        // Clearing claims without receipts resolves that drift for good
+        assert_eq!(clear_missing_receipts(&mut db, &sync).unwrap(), 1);
+        apply_claims(&mut db, "Alpha", &sync.pending).unwrap();
+        let again = CohortClaimSync::plan(&db, "Alpha", &receipts, false).unwrap();
+        assert_eq!(again.missing_receipts().count(), 0);
+        assert!(again.pending.is_empty());
+        assert_eq!(again.drift.len(), 2);
+    }
+}
@@ -0,0 +1,397 @@
+/*!
+# Deploy Plan
+
+Every transaction of a campaign deployment, built from the compiled campaign database
+without RPC access.
+
+`deploy-campaign` checks on-chain state as it goes and skips whatever already exists.
+Signing offline rules that out: each transaction is signed against its own durable nonce
+before any of them land, so the plan assumes nothing is deployed yet and lists the
+transactions in the order they must land:
+
+1. `initialize_campaign_v0`
+2. per cohort: `initialize_cohort_v0` (or `_v1`), then per vault `initialize_vault_v0`,
+   a transfer of its tokens from the admin's token account and `activate_vault_v0`,
+   then `activate_cohort_v0`
+3. the rounding dust transfer to the treasury (`treasury:<pubkey>` dust policy)
+4. `activate_campaign_v0`, when a [`CampaignActivation`] is given
+
+The address lookup table is not planned: creating one needs a recent slot, and a durable
+nonce transaction can land any number of slots after it was signed.
+*/
+
+use crate::{
+    build_activate_campaign_v0_ix, build_activate_cohort_v0_ix, build_activate_vault_v0_ix,
+    build_initialize_campaign_v0_ix, build_initialize_cohort_v0_ix, build_initialize_cohort_v1_ix,
+    build_initialize_vault_v0_ix, AddressFinder, InitializeCohortV1Params,
+};
+use prism_protocol::ClaimHashScheme;
+use prism_protocol_db::{CampaignDatabase, CohortTree, DbError};
+use solana_sdk::instruction::Instruction;
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use thiserror::Error;
+
+/// Errors from planning a deployment
+#[derive(Error, Debug)]
+pub enum DeployPlanError {
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+
+    #[error("Failed to build {0} instruction: {1}")]
+    Instruction(&'static str, String),
+
+    #[error("{0}")]
+    InvalidCampaign(String),
+}
+
+/// Arguments of the final `activate_campaign_v0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CampaignActivation {
+    /// SHA-256 IPFS hash of the published campaign database
+    pub campaign_db_ipfs_hash: [u8; 32],
+
+    /// Slot claims open at; must not have passed when the activation lands
+    pub go_live_slot: u64,
+}
+
+/// One transaction of a deployment
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeployTransaction {
+    /// What the transaction does, for the operator
+    pub description: String,
+
+    /// Instructions, without compute budget or nonce instructions
+    pub instructions: Vec<Instruction>,
+}
+
+/// Plan the transactions deploying the campaign in `db`, in the order they must land
+///
+/// Vaults are funded by transfer from the admin's associated token account, which must
+/// hold the campaign's tokens (plus any treasury dust) by the time the plan is broadcast.
+pub fn plan_deployment(
+    address_finder: &AddressFinder,
+    db: &CampaignDatabase,
+    activation: Option<CampaignActivation>,
+) -> Result<Vec<DeployTransaction>, DeployPlanError> {
+    let campaign = db.read_campaign_info()?;
+    let cohorts = db.read_cohorts()?;
+    let vault_requirements = db.read_vault_requirements()?;
+    let dust_summary = db.read_dust_report()?.map(|report| report.summary);
+
+    let admin = campaign.admin;
+    let fingerprint = campaign.fingerprint;
+    let token_program = address_finder.token_program_id;
+    let admin_token_account =
+        get_associated_token_address_with_program_id(&admin, &campaign.mint, &token_program);
+
+    let cohort_count = u8::try_from(cohorts.len()).map_err(|_| {
+        DeployPlanError::InvalidCampaign(format!("{} cohorts exceed 255", cohorts.len()))
+    })?;
+    let (initialize_campaign_ix, _, _) = build_initialize_campaign_v0_ix(
+        address_finder,
+        admin,
+        fingerprint,
+        campaign.mint,
+        cohort_count,
+    )
+    .map_err(|e| DeployPlanError::Instruction("initialize campaign", e.to_string()))?;
+
+    let mut plan = vec![DeployTransaction {
+        description: "Initialize campaign".to_string(),
+        instructions: vec![initialize_campaign_ix],
+    }];
+
+    for cohort in &cohorts {
+        let vault_count = u8::try_from(cohort.vault_count).map_err(|_| {
+            DeployPlanError::InvalidCampaign(format!(
+                "Cohort {} has {} vaults, more than 255",
+                cohort.name, cohort.vault_count
+            ))
+        })?;
+
+        let initialize_cohort_ix = match cohort.tree {
+            CohortTree::V0 => build_initialize_cohort_v0_ix(
+                address_finder,
+                admin,
+                fingerprint,
+                cohort.merkle_root,
+                cohort.amount_per_entitlement,
+                vault_count,
+            )
+            .map(|(ix, _, _)| ix),
+            CohortTree::V1 { arity, hash_scheme } => build_initialize_cohort_v1_ix(
+                address_finder,
+                InitializeCohortV1Params {
+                    admin,
+                    campaign_fingerprint: fingerprint,
+                    merkle_root: cohort.merkle_root,
+                    amount_per_entitlement: cohort.amount_per_entitlement,
+                    expected_vault_count: vault_count,
+                    merkle_tree_arity: arity,
+                    hash_scheme: ClaimHashScheme::from(hash_scheme),
+                },
+            )
+            .map(|(ix, _, _)| ix),
+        }
+        .map_err(|e| DeployPlanError::Instruction("initialize cohort", e.to_string()))?;
+        plan.push(DeployTransaction {
+            description: format!("Initialize cohort {}", cohort.name),
+            instructions: vec![initialize_cohort_ix],
+        });
+
+        for vault_index in 0..vault_count {
+            let required_tokens = vault_requirements
+                .iter()
+                .find(|v| v.cohort_name == cohort.name && v.vault_index == vault_index as usize)
+                .map_or(0, |v| v.required_tokens);
+
+            let (initialize_vault_ix, ix_accounts, _) = build_initialize_vault_v0_ix(
+                address_finder,
+                admin,
+                fingerprint,
+                cohort.merkle_root,
+                campaign.mint,
+                vault_index,
+            )
+            .map_err(|e| DeployPlanError::Instruction("initialize vault", e.to_string()))?;
+            plan.push(DeployTransaction {
+                description: format!("Initialize vault {} of cohort {}", vault_index, cohort.name),
+                instructions: vec![initialize_vault_ix],
+            });
+
+            // Activation checks the balance, so the funding transfer has to land first
+            let mut instructions = Vec::with_capacity(2);
+            if required_tokens > 0 {
+                instructions.push(
+                    spl_token::instruction::transfer(
+                        &token_program,
+                        &admin_token_account,
+                        &ix_accounts.vault,
+                        &admin,
+                        &[],
+                        required_tokens,
+                    )
+                    .map_err(|e| DeployPlanError::Instruction("transfer", e.to_string()))?,
+                );
+            }
+            let (activate_vault_ix, _, _) = build_activate_vault_v0_ix(
+                address_finder,
+                admin,
+                fingerprint,
+                cohort.merkle_root,
+                vault_index,
+                required_tokens,
+            )
+            .map_err(|e| DeployPlanError::Instruction("activate vault", e.to_string()))?;
+            instructions.push(activate_vault_ix);
+            plan.push(DeployTransaction {
+                description: format!(
+                    "Fund vault {} of cohort {} with {} base units and activate it",
+                    vault_index, cohort.name, required_tokens
+                ),
+                instructions,
+            });
+        }
+
+        let (activate_cohort_ix, _, _) =
+            build_activate_cohort_v0_ix(address_finder, admin, fingerprint, cohort.merkle_root)
+                .map_err(|e| DeployPlanError::Instruction("activate cohort", e.to_string()))?;
+        plan.push(DeployTransaction {
+            description: format!("Activate cohort {}", cohort.name),
+            instructions: vec![activate_cohort_ix],
+        });
+    }
+
+    if let Some(summary) = dust_summary {
+        if let (Some(treasury), None) = (summary.treasury, &summary.treasury_signature) {
+            if summary.treasury_amount > 0 {
+                let treasury_token_account = get_associated_token_address_with_program_id(
+                    &treasury,
+                    &campaign.mint,
+                    &token_program,
+                );
+                let transfer_ix = spl_token::instruction::transfer(
+                    &token_program,
+                    &admin_token_account,
+                    &treasury_token_account,
+                    &admin,
+                    &[],
+                    summary.treasury_amount,
+                )
+                .map_err(|e| DeployPlanError::Instruction("transfer", e.to_string()))?;
+                plan.push(DeployTransaction {
+                    description: format!(
+                        "Transfer {} base units of dust to treasury {}",
+                        summary.treasury_amount, treasury
+                    ),
+                    instructions: vec![
+                        create_associated_token_account_idempotent(
+                            &admin,
+                            &treasury,
+                            &campaign.mint,
+                            &token_program,
+                        ),
+                        transfer_ix,
+                    ],
+                });
+            }
+        }
+    }
+
+    if let Some(activation) = activation {
+        let (activate_campaign_ix, _, _) = build_activate_campaign_v0_ix(
+            address_finder,
+            admin,
+            fingerprint,
+            activation.campaign_db_ipfs_hash,
+            activation.go_live_slot,
+        )
+        .map_err(|e| DeployPlanError::Instruction("activate campaign", e.to_string()))?;
+        plan.push(DeployTransaction {
+            description: format!(
+                "Activate campaign (go-live slot {})",
+                activation.go_live_slot
+            ),
+            instructions: vec![activate_campaign_ix],
+        });
+    }
+
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        compile_campaign_db_with_options, decode_signed_transaction, encode_signed_transaction,
+        sign_with_nonce, CampaignSettings, CompileOptions, ComputeBudgetConfig, DurableNonce,
+        PriorityFee,
+    };
+    use prism_protocol_csvs::{AllocationMode, CampaignCsvRow, CohortsCsvRow};
+    use rust_decimal::Decimal;
+    use solana_sdk::{
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_program,
+    };
+
+    fn compile(admin: Pubkey) -> CampaignDatabase {
+        let campaign_rows: Vec<CampaignCsvRow> = [("Alpha", 1), ("Alpha", 2), ("Beta", 3)]
+            .iter()
+            .map(|(cohort, entitlements)| CampaignCsvRow {
+                cohort: cohort.to_string(),
+                claimant: Pubkey::new_unique(),
+                entitlements: *entitlements,
+            })
+            .collect();
+        let cohorts_rows: Vec<CohortsCsvRow> = ["Alpha", "Beta"]
+            .iter()
+            .map(|name| CohortsCsvRow {
+                cohort: name.to_string(),
+                share_percentage: Decimal::from(50),
+                allocation_mode: AllocationMode::SharePercentage,
+                allocation_value: None,
+                min_per_claimant: None,
+                max_per_claimant: None,
+            })
+            .collect();
+
+        compile_campaign_db_with_options(
+            AddressFinder::default(),
+            &campaign_rows,
+            &cohorts_rows,
+            &CampaignSettings {
+                budget: Decimal::from(1000),
+                mint: Pubkey::new_from_array([1; 32]),
+                mint_decimals: 6,
+                admin,
+                claimants_per_vault: 1,
+            },
+            &CompileOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_plan_order() {
+        let db = compile(Pubkey::new_unique());
+        let activation = CampaignActivation {
+            campaign_db_ipfs_hash: [7; 32],
+            go_live_slot: 1_000,
+        };
+        let plan = plan_deployment(&AddressFinder::default(), &db, Some(activation)).unwrap();
+
+        let descriptions: Vec<&str> = plan.iter().map(|tx| tx.description.as_str()).collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Initialize campaign",
+                "Initialize cohort Alpha",
+                "Initialize vault 0 of cohort Alpha",
+                descriptions[3],
+                "Initialize vault 1 of cohort Alpha",
+                descriptions[5],
+                "Activate cohort Alpha",
+                "Initialize cohort Beta",
+                "Initialize vault 0 of cohort Beta",
+                descriptions[9],
+                "Activate cohort Beta",
+                "Activate campaign (go-live slot 1000)",
+            ]
+        );
+
+        // Funding transactions transfer first, then activate the vault
+        for tx in [&plan[3], &plan[5], &plan[9]] {
+            assert!(tx.description.starts_with("Fund vault"));
+            assert_eq!(tx.instructions.len(), 2);
+            assert_eq!(tx.instructions[0].program_id, spl_token::ID);
+            assert_eq!(
+                tx.instructions[1].program_id,
+                AddressFinder::default().program_id
+            );
+        }
+
+        // Without activation arguments the campaign stays inactive
+        let plan = plan_deployment(&AddressFinder::default(), &db, None).unwrap();
+        assert_eq!(plan.last().unwrap().description, "Activate cohort Beta");
+    }
+
+    #[test]
+    fn test_plan_signs_offline() {
+        let admin = Keypair::new();
+        let db = compile(admin.pubkey());
+        let plan = plan_deployment(&AddressFinder::default(), &db, None).unwrap();
+        let budget = ComputeBudgetConfig::with_priority_fee(PriorityFee::None);
+
+        for (i, tx) in plan.iter().enumerate() {
+            let nonce = DurableNonce {
+                account: Pubkey::new_unique(),
+                authority: admin.pubkey(),
+                blockhash: Hash::new_from_array([i as u8; 32]),
+            };
+            let signed = sign_with_nonce(
+                &tx.instructions,
+                &admin.pubkey(),
+                &[&admin],
+                &nonce,
+                &[],
+                &budget,
+            )
+            .unwrap();
+
+            let decoded =
+                decode_signed_transaction(&encode_signed_transaction(&signed).unwrap()).unwrap();
+            let message = &decoded.message;
+            assert_eq!(*message.recent_blockhash(), nonce.blockhash);
+            let keys = message.static_account_keys();
+            let advance = &message.instructions()[0];
+            assert_eq!(keys[advance.program_id_index as usize], system_program::ID);
+            assert_eq!(keys[advance.accounts[0] as usize], nonce.account);
+            assert_eq!(message.instructions().len(), tx.instructions.len() + 1);
+        }
+    }
+}
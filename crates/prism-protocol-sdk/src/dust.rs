@@ -0,0 +1,325 @@
+/*!
+# Dust Policies
+
+Budget allocation always rounds down, so a compiled campaign usually leaves a
+small remainder ("dust") of the budget unallocated. This module defines what
+happens to that remainder.
+
+## Policies
+
+- **`retain`** (default): dust stays with the admin and is only reported
+- **`treasury:<pubkey>`**: dust is transferred to the treasury's token account at deploy
+- **`first-leaves`**: dust is handed back to claimants one base unit at a time, in
+  ascending claimant pubkey order
+
+Every cohort shares a single on-chain `amount_per_entitlement`, so `first-leaves`
+re-denominates a cohort before redistributing: each leaf's entitlements become its
+amount in base units and the cohort pays one base unit per entitlement. Only dust
+smaller than one base unit per leaf (or blocked by `max_per_claimant`) remains as
+reported dust.
+*/
+
+use crate::budget_allocation::{
+    from_base_units, to_base_units, AllocationError, AllocationResult, CohortAllocation,
+};
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::{fmt, str::FromStr};
+
+/// What to do with budget left over after rounding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DustPolicy {
+    /// Keep dust with the admin; report only
+    #[default]
+    Retain,
+
+    /// Transfer dust to the given treasury account at deploy
+    Treasury(Pubkey),
+
+    /// Give one extra base unit to each leaf in turn (by claimant pubkey)
+    FirstLeaves,
+}
+
+impl DustPolicy {
+    /// Stable policy name as persisted in the campaign database
+    pub fn name(&self) -> &'static str {
+        match self {
+            DustPolicy::Retain => "retain",
+            DustPolicy::Treasury(_) => "treasury",
+            DustPolicy::FirstLeaves => "first-leaves",
+        }
+    }
+
+    /// Treasury account, if this policy has one
+    pub fn treasury(&self) -> Option<Pubkey> {
+        match self {
+            DustPolicy::Treasury(treasury) => Some(*treasury),
+            _ => None,
+        }
+    }
+
+    /// Base units transferred to the treasury at deploy
+    ///
+    /// `rounding_dust` is the cohort budgets minus vault funding; budget not targeted
+    /// at any cohort stays with the admin. Zero for every other policy.
+    pub fn treasury_amount(
+        &self,
+        rounding_dust: Decimal,
+        mint_decimals: u8,
+    ) -> AllocationResult<u64> {
+        match self {
+            DustPolicy::Treasury(_) => to_base_units(rounding_dust, mint_decimals),
+            DustPolicy::Retain | DustPolicy::FirstLeaves => Ok(0),
+        }
+    }
+
+    /// Apply the policy to a cohort's leaves before they are committed to a merkle tree
+    ///
+    /// Rewrites `claimants` and the allocation's amount per entitlement when leaves are
+    /// re-denominated, and returns the tokens handed back to leaves.
+    pub fn apply(
+        &self,
+        claimants: &mut [(Pubkey, u64)],
+        allocation: &mut CohortAllocation,
+        max_per_claimant: Option<Decimal>,
+        mint_decimals: u8,
+    ) -> AllocationResult<Decimal> {
+        match self {
+            DustPolicy::FirstLeaves => {
+                let outcome = apply_first_leaves(
+                    claimants,
+                    allocation.amount_per_entitlement,
+                    allocation.dust_amount,
+                    max_per_claimant,
+                    mint_decimals,
+                )?;
+                allocation.amount_per_entitlement = outcome.amount_per_entitlement;
+                allocation.amount_per_entitlement_humane =
+                    outcome.amount_per_entitlement.to_string();
+                Ok(outcome.redistributed)
+            }
+            DustPolicy::Retain | DustPolicy::Treasury(_) => Ok(Decimal::ZERO),
+        }
+    }
+}
+
+impl fmt::Display for DustPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DustPolicy::Treasury(treasury) => write!(f, "treasury:{}", treasury),
+            other => f.write_str(other.name()),
+        }
+    }
+}
+
+impl FromStr for DustPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "retain" => Ok(DustPolicy::Retain),
+            "first-leaves" => Ok(DustPolicy::FirstLeaves),
+            _ => match s.strip_prefix("treasury:") {
+                Some(treasury) => Pubkey::from_str(treasury)
+                    .map(DustPolicy::Treasury)
+                    .map_err(|e| format!("Invalid treasury pubkey '{}': {}", treasury, e)),
+                None => Err(format!(
+                    "Unknown dust policy '{}' (expected retain, first-leaves or treasury:<pubkey>)",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+/// Outcome of applying the `first-leaves` policy to a cohort
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FirstLeavesOutcome {
+    /// Amount per entitlement the cohort is committed with
+    pub amount_per_entitlement: Decimal,
+    /// Tokens handed back to leaves
+    pub redistributed: Decimal,
+}
+
+/// Apply the `first-leaves` policy to a cohort's claimants
+///
+/// Entitlements are rewritten in base units and the returned amount per entitlement
+/// is one base unit. Cohorts whose amount per entitlement rounds to zero base units
+/// are left untouched.
+pub fn apply_first_leaves(
+    claimants: &mut [(Pubkey, u64)],
+    amount_per_entitlement: Decimal,
+    dust: Decimal,
+    max_per_claimant: Option<Decimal>,
+    mint_decimals: u8,
+) -> AllocationResult<FirstLeavesOutcome> {
+    let unit_amount = to_base_units(amount_per_entitlement, mint_decimals)?;
+    if unit_amount == 0 {
+        return Ok(FirstLeavesOutcome {
+            amount_per_entitlement,
+            redistributed: Decimal::ZERO,
+        });
+    }
+
+    for (claimant, entitlements) in claimants.iter_mut() {
+        *entitlements = entitlements.checked_mul(unit_amount).ok_or_else(|| {
+            AllocationError::Overflow(format!("entitlements of {} in base units", claimant))
+        })?;
+    }
+
+    let max_units = max_per_claimant
+        .map(|max| to_base_units(max, mint_decimals))
+        .transpose()?;
+    let redistributed =
+        redistribute_to_first_leaves(claimants, to_base_units(dust, mint_decimals)?, max_units);
+
+    Ok(FirstLeavesOutcome {
+        amount_per_entitlement: from_base_units(1, mint_decimals),
+        redistributed: from_base_units(redistributed, mint_decimals),
+    })
+}
+
+/// Hand dust back to claimants one base unit at a time
+///
+/// `claimants` hold amounts in base units. Each round gives one base unit to every
+/// claimant below `max_units`, in ascending pubkey order so the outcome does not
+/// depend on CSV row order, until the dust runs out. Returns the base units
+/// redistributed.
+pub fn redistribute_to_first_leaves(
+    claimants: &mut [(Pubkey, u64)],
+    dust_units: u64,
+    max_units: Option<u64>,
+) -> u64 {
+    let headroom: Vec<u64> = claimants
+        .iter()
+        .map(|(_, amount)| max_units.map_or(u64::MAX, |max| max.saturating_sub(*amount)))
+        .collect();
+
+    // Find the number of full rounds: everyone gets min(headroom, rounds)
+    let mut by_headroom: Vec<usize> = (0..claimants.len()).collect();
+    by_headroom.sort_by_key(|&i| headroom[i]);
+
+    let mut remaining = dust_units;
+    let mut rounds = 0u64;
+    let mut open = claimants.len() as u64;
+    for &i in &by_headroom {
+        let cost = (headroom[i] - rounds) as u128 * open as u128;
+        if cost > remaining as u128 {
+            break;
+        }
+        remaining -= cost as u64;
+        rounds = headroom[i];
+        open -= 1;
+    }
+    if let Some(more_rounds) = remaining.checked_div(open) {
+        rounds += more_rounds;
+        remaining %= open;
+    }
+
+    // The partial last round goes to the lowest pubkeys that still have headroom
+    let mut order: Vec<usize> = (0..claimants.len()).collect();
+    order.sort_by_key(|&i| claimants[i].0.to_bytes());
+
+    let mut redistributed = 0u64;
+    for i in order {
+        let mut extra = headroom[i].min(rounds);
+        if remaining > 0 && headroom[i] > rounds {
+            extra += 1;
+            remaining -= 1;
+        }
+        claimants[i].1 += extra;
+        redistributed += extra;
+    }
+
+    redistributed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dust_policy_parse_roundtrip() {
+        let treasury = Pubkey::new_unique();
+        for policy in [
+            DustPolicy::Retain,
+            DustPolicy::FirstLeaves,
+            DustPolicy::Treasury(treasury),
+        ] {
+            assert_eq!(DustPolicy::from_str(&policy.to_string()).unwrap(), policy);
+        }
+
+        assert!(DustPolicy::from_str("treasury:not-a-pubkey").is_err());
+        assert!(DustPolicy::from_str("burn").is_err());
+    }
+
+    #[test]
+    fn test_only_treasury_policy_transfers_dust() {
+        let dust = Decimal::new(12_345, 4);
+        let treasury = DustPolicy::Treasury(Pubkey::new_unique());
+
+        assert_eq!(treasury.treasury_amount(dust, 3).unwrap(), 1_234);
+        assert_eq!(DustPolicy::Retain.treasury_amount(dust, 3).unwrap(), 0);
+        assert_eq!(DustPolicy::FirstLeaves.treasury_amount(dust, 3).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_redistribute_to_first_leaves_is_order_independent() {
+        let a = Pubkey::new_from_array([1; 32]);
+        let b = Pubkey::new_from_array([2; 32]);
+        let c = Pubkey::new_from_array([3; 32]);
+
+        let mut forward = vec![(a, 1), (b, 1), (c, 1)];
+        let mut reverse = vec![(c, 1), (b, 1), (a, 1)];
+
+        let redistributed = redistribute_to_first_leaves(&mut forward, 5, None);
+        redistribute_to_first_leaves(&mut reverse, 5, None);
+
+        // One full round, then the two lowest pubkeys get one more base unit
+        assert_eq!(redistributed, 5);
+        assert_eq!(forward, vec![(a, 3), (b, 3), (c, 2)]);
+        assert_eq!(reverse, vec![(c, 2), (b, 3), (a, 3)]);
+    }
+
+    #[test]
+    fn test_redistribute_respects_max_cap() {
+        let a = Pubkey::new_from_array([1; 32]);
+        let b = Pubkey::new_from_array([2; 32]);
+        let c = Pubkey::new_from_array([3; 32]);
+
+        let mut claimants = vec![(a, 5), (b, 1), (c, 4)];
+        let redistributed = redistribute_to_first_leaves(&mut claimants, 6, Some(5));
+
+        // `a` is already at the cap and `c` fills up after one unit
+        assert_eq!(redistributed, 5);
+        assert_eq!(claimants, vec![(a, 5), (b, 5), (c, 5)]);
+    }
+
+    #[test]
+    fn test_apply_first_leaves_redenominates_in_base_units() {
+        let a = Pubkey::new_from_array([1; 32]);
+        let b = Pubkey::new_from_array([2; 32]);
+
+        // 0.05 tokens per entitlement with 2 decimals is 5 base units
+        let mut claimants = vec![(b, 2), (a, 1)];
+        let outcome = apply_first_leaves(
+            &mut claimants,
+            Decimal::new(5, 2),
+            Decimal::new(3, 2),
+            None,
+            2,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.amount_per_entitlement, Decimal::new(1, 2));
+        assert_eq!(outcome.redistributed, Decimal::new(3, 2));
+        assert_eq!(claimants, vec![(b, 11), (a, 7)]);
+
+        // Nothing to re-denominate when an entitlement is worth less than a base unit
+        let mut claimants = vec![(a, 1)];
+        let outcome =
+            apply_first_leaves(&mut claimants, Decimal::ZERO, Decimal::ONE, None, 2).unwrap();
+        assert_eq!(outcome.redistributed, Decimal::ZERO);
+        assert_eq!(claimants, vec![(a, 1)]);
+    }
+}
@@ -209,6 +209,68 @@ pub fn build_initialize_cohort_v0_ix(
     Ok((ix, ix_accounts, ix_data))
 }
 
+/// A cohort committing its tree arity and hash scheme, for [`build_initialize_cohort_v1_ix`]
+#[derive(Debug, Clone)]
+pub struct InitializeCohortV1Params {
+    pub admin: Pubkey,
+    pub campaign_fingerprint: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub amount_per_entitlement: u64,
+    pub expected_vault_count: u8,
+    pub merkle_tree_arity: u16,
+    pub hash_scheme: prism_protocol::ClaimHashScheme,
+}
+
+pub fn build_initialize_cohort_v1_ix(
+    address_finder: &AddressFinder,
+    params: InitializeCohortV1Params,
+) -> Result<(
+    Instruction,
+    prism_protocol::accounts::InitializeCohortV1,
+    prism_protocol::instruction::InitializeCohortV1,
+)> {
+    let InitializeCohortV1Params {
+        admin,
+        campaign_fingerprint,
+        merkle_root,
+        amount_per_entitlement,
+        expected_vault_count,
+        merkle_tree_arity,
+        hash_scheme,
+    } = params;
+
+    let (campaign, _) = address_finder.find_campaign_v0_address(&admin, &campaign_fingerprint);
+
+    let (cohort, _) = address_finder.find_cohort_v0_address(&campaign, &merkle_root);
+
+    let (cohort_tree, _) = address_finder.find_cohort_tree_v0_address(&cohort);
+
+    let ix_accounts = prism_protocol::accounts::InitializeCohortV1 {
+        admin,
+        campaign,
+        cohort,
+        cohort_tree,
+        system_program: address_finder.system_program_id,
+    };
+
+    let ix_data = prism_protocol::instruction::InitializeCohortV1 {
+        campaign_fingerprint,
+        merkle_root,
+        amount_per_entitlement,
+        expected_vault_count,
+        merkle_tree_arity,
+        hash_scheme,
+    };
+
+    let ix = Instruction {
+        program_id: address_finder.program_id,
+        accounts: ix_accounts.to_account_metas(None),
+        data: ix_data.data(),
+    };
+
+    Ok((ix, ix_accounts, ix_data))
+}
+
 pub fn build_activate_cohort_v0_ix(
     address_finder: &AddressFinder,
     admin: Pubkey,
@@ -383,6 +445,293 @@ pub fn build_claim_tokens_v0_ix(
     Ok((ix, ix_accounts, ix_data))
 }
 
+/// A claim with a wide proof against a V1 tree, for [`build_claim_tokens_v1_ix`] and
+/// [`build_claim_tokens_v2_ix`]
+#[derive(Debug, Clone)]
+pub struct ClaimV1Params {
+    pub admin: Pubkey,
+    pub claimant: Pubkey,
+    pub mint: Pubkey,
+    pub claimant_token_account: Pubkey,
+    pub campaign_fingerprint: [u8; 32],
+    pub cohort_merkle_root: [u8; 32],
+    pub merkle_proof: Vec<Vec<[u8; 32]>>,
+    pub assigned_vault_index: u8,
+    pub entitlements: u64,
+}
+
+pub fn build_claim_tokens_v1_ix(
+    address_finder: &AddressFinder,
+    params: ClaimV1Params,
+) -> Result<(
+    Instruction,
+    prism_protocol::accounts::ClaimTokensV1,
+    prism_protocol::instruction::ClaimTokensV1,
+)> {
+    let ClaimV1Params {
+        admin,
+        claimant,
+        mint,
+        claimant_token_account,
+        campaign_fingerprint,
+        cohort_merkle_root,
+        merkle_proof,
+        assigned_vault_index,
+        entitlements,
+    } = params;
+
+    let (campaign, _) = address_finder.find_campaign_v0_address(&admin, &campaign_fingerprint);
+
+    let (cohort, _) = address_finder.find_cohort_v0_address(&campaign, &cohort_merkle_root);
+
+    let (vault, _) = address_finder.find_vault_v0_address(&cohort, assigned_vault_index);
+
+    let (claim_receipt, _) = address_finder.find_claim_receipt_v0_address(&cohort, &claimant);
+
+    let ix_accounts = prism_protocol::accounts::ClaimTokensV1 {
+        admin,
+        claimant,
+        campaign,
+        cohort,
+        vault,
+        mint,
+        claimant_token_account,
+        claim_receipt,
+        token_program: address_finder.token_program_id,
+        associated_token_program: address_finder.associated_token_program_id,
+        system_program: address_finder.system_program_id,
+        rent: address_finder.rent_id,
+    };
+
+    let ix_data = prism_protocol::instruction::ClaimTokensV1 {
+        campaign_fingerprint,
+        cohort_merkle_root,
+        merkle_proof,
+        assigned_vault_index,
+        entitlements,
+    };
+
+    let ix = Instruction {
+        program_id: address_finder.program_id,
+        accounts: ix_accounts.to_account_metas(None),
+        data: ix_data.data(),
+    };
+
+    Ok((ix, ix_accounts, ix_data))
+}
+
+pub fn build_claim_tokens_v2_ix(
+    address_finder: &AddressFinder,
+    params: ClaimV1Params,
+) -> Result<(
+    Instruction,
+    prism_protocol::accounts::ClaimTokensV2,
+    prism_protocol::instruction::ClaimTokensV2,
+)> {
+    let ClaimV1Params {
+        admin,
+        claimant,
+        mint,
+        claimant_token_account,
+        campaign_fingerprint,
+        cohort_merkle_root,
+        merkle_proof,
+        assigned_vault_index,
+        entitlements,
+    } = params;
+
+    let (campaign, _) = address_finder.find_campaign_v0_address(&admin, &campaign_fingerprint);
+
+    let (cohort, _) = address_finder.find_cohort_v0_address(&campaign, &cohort_merkle_root);
+
+    let (cohort_tree, _) = address_finder.find_cohort_tree_v0_address(&cohort);
+
+    let (vault, _) = address_finder.find_vault_v0_address(&cohort, assigned_vault_index);
+
+    let (claim_receipt, _) = address_finder.find_claim_receipt_v0_address(&cohort, &claimant);
+
+    let ix_accounts = prism_protocol::accounts::ClaimTokensV2 {
+        admin,
+        claimant,
+        campaign,
+        cohort,
+        cohort_tree,
+        vault,
+        mint,
+        claimant_token_account,
+        claim_receipt,
+        token_program: address_finder.token_program_id,
+        associated_token_program: address_finder.associated_token_program_id,
+        system_program: address_finder.system_program_id,
+        rent: address_finder.rent_id,
+    };
+
+    let ix_data = prism_protocol::instruction::ClaimTokensV2 {
+        campaign_fingerprint,
+        cohort_merkle_root,
+        merkle_proof,
+        assigned_vault_index,
+        entitlements,
+    };
+
+    let ix = Instruction {
+        program_id: address_finder.program_id,
+        accounts: ix_accounts.to_account_metas(None),
+        data: ix_data.data(),
+    };
+
+    Ok((ix, ix_accounts, ix_data))
+}
+
+/// Remaining accounts of a batched claim, one group per claim in `claims` order
+fn claim_batch_remaining_accounts(
+    address_finder: &AddressFinder,
+    cohort: &Pubkey,
+    claims: &[(prism_protocol::ClaimLeaf, Pubkey)],
+) -> Vec<AccountMeta> {
+    claims
+        .iter()
+        .flat_map(|(leaf, claimant_token_account)| {
+            let (vault, _) =
+                address_finder.find_vault_v0_address(cohort, leaf.assigned_vault_index);
+            let (claim_receipt, _) =
+                address_finder.find_claim_receipt_v0_address(cohort, &leaf.claimant);
+            [
+                AccountMeta::new_readonly(leaf.claimant, true),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(*claimant_token_account, false),
+                AccountMeta::new(claim_receipt, false),
+            ]
+        })
+        .collect()
+}
+
+fn claim_batch_entries(
+    claims: &[(prism_protocol::ClaimLeaf, Pubkey)],
+) -> Vec<prism_protocol::ClaimBatchEntry> {
+    claims
+        .iter()
+        .map(|(leaf, _)| prism_protocol::ClaimBatchEntry {
+            assigned_vault_index: leaf.assigned_vault_index,
+            entitlements: leaf.entitlements,
+        })
+        .collect()
+}
+
+/// Build a batched claim from a binary (V0) cohort.
+///
+/// `claims` pairs each claim leaf with the claimant's token account, in tree order (as
+/// returned by `ClaimTreeV0::multiproof_for_claimants`). Every claimant must sign.
+#[allow(clippy::too_many_arguments)]
+pub fn build_claim_tokens_batch_v0_ix(
+    address_finder: &AddressFinder,
+    admin: Pubkey,
+    payer: Pubkey,
+    mint: Pubkey,
+    campaign_fingerprint: [u8; 32],
+    cohort_merkle_root: [u8; 32],
+    multiproof: prism_protocol::ClaimMultiProofV0,
+    claims: &[(prism_protocol::ClaimLeaf, Pubkey)],
+) -> Result<(
+    Instruction,
+    prism_protocol::accounts::ClaimTokensBatchV0,
+    prism_protocol::instruction::ClaimTokensBatchV0,
+)> {
+    let (campaign, _) = address_finder.find_campaign_v0_address(&admin, &campaign_fingerprint);
+
+    let (cohort, _) = address_finder.find_cohort_v0_address(&campaign, &cohort_merkle_root);
+
+    let ix_accounts = prism_protocol::accounts::ClaimTokensBatchV0 {
+        admin,
+        payer,
+        campaign,
+        cohort,
+        mint,
+        token_program: address_finder.token_program_id,
+        system_program: address_finder.system_program_id,
+    };
+
+    let ix_data = prism_protocol::instruction::ClaimTokensBatchV0 {
+        campaign_fingerprint,
+        cohort_merkle_root,
+        multiproof,
+        claims: claim_batch_entries(claims),
+    };
+
+    let mut accounts = ix_accounts.to_account_metas(None);
+    accounts.extend(claim_batch_remaining_accounts(
+        address_finder,
+        &cohort,
+        claims,
+    ));
+
+    let ix = Instruction {
+        program_id: address_finder.program_id,
+        accounts,
+        data: ix_data.data(),
+    };
+
+    Ok((ix, ix_accounts, ix_data))
+}
+
+/// Build a batched claim from a cohort initialized with `initialize_cohort_v1`.
+///
+/// `claims` is laid out as for [`build_claim_tokens_batch_v0_ix`].
+#[allow(clippy::too_many_arguments)]
+pub fn build_claim_tokens_batch_v1_ix(
+    address_finder: &AddressFinder,
+    admin: Pubkey,
+    payer: Pubkey,
+    mint: Pubkey,
+    campaign_fingerprint: [u8; 32],
+    cohort_merkle_root: [u8; 32],
+    multiproof: prism_protocol::ClaimMultiProofV1,
+    claims: &[(prism_protocol::ClaimLeaf, Pubkey)],
+) -> Result<(
+    Instruction,
+    prism_protocol::accounts::ClaimTokensBatchV1,
+    prism_protocol::instruction::ClaimTokensBatchV1,
+)> {
+    let (campaign, _) = address_finder.find_campaign_v0_address(&admin, &campaign_fingerprint);
+
+    let (cohort, _) = address_finder.find_cohort_v0_address(&campaign, &cohort_merkle_root);
+
+    let (cohort_tree, _) = address_finder.find_cohort_tree_v0_address(&cohort);
+
+    let ix_accounts = prism_protocol::accounts::ClaimTokensBatchV1 {
+        admin,
+        payer,
+        campaign,
+        cohort,
+        cohort_tree,
+        mint,
+        token_program: address_finder.token_program_id,
+        system_program: address_finder.system_program_id,
+    };
+
+    let ix_data = prism_protocol::instruction::ClaimTokensBatchV1 {
+        campaign_fingerprint,
+        cohort_merkle_root,
+        multiproof,
+        claims: claim_batch_entries(claims),
+    };
+
+    let mut accounts = ix_accounts.to_account_metas(None);
+    accounts.extend(claim_batch_remaining_accounts(
+        address_finder,
+        &cohort,
+        claims,
+    ));
+
+    let ix = Instruction {
+        program_id: address_finder.program_id,
+        accounts,
+        data: ix_data.data(),
+    };
+
+    Ok((ix, ix_accounts, ix_data))
+}
+
 pub fn build_reclaim_tokens_v0_ix(
     address_finder: &AddressFinder,
     admin: Pubkey,
@@ -0,0 +1,1000 @@
+/*!
+# Instruction Decoder
+
+Recognises Prism Protocol instructions in arbitrary transactions, for indexers.
+
+Instructions are matched by program id and Anchor discriminator, then decoded into
+[`PrismInstruction`], which pairs the typed instruction arguments from
+`prism_protocol::instruction` with the named accounts from `prism_protocol::accounts`.
+Inner (CPI) instructions are decoded the same way as top-level ones. Each Prism
+instruction in a transaction gets its own decode result, so one malformed instruction
+doesn't hide the others.
+
+Claims can be re-verified offline with [`PrismInstruction::verify_claim`]: the merkle
+proof is checked against the root in the instruction, and every PDA the program would
+have derived is re-derived and compared. Batched claims decode with their named accounts
+and one [`BatchClaimAccounts`] per claim, taken from the remaining accounts; they aren't
+re-verified.
+*/
+
+use crate::AddressFinder;
+use anchor_lang::{prelude::*, Discriminator, InstructionData};
+use prism_protocol::claim_tree_constants::{BINARY_TREE_ARITY, MAX_TREE_ARITY};
+use prism_protocol::{ClaimHashScheme, ClaimLeaf, ClaimProofType, CLAIM_BATCH_ACCOUNTS_PER_CLAIM};
+use solana_sdk::{
+    instruction::{CompiledInstruction, Instruction},
+    message::{v0::LoadedAddresses, Message, VersionedMessage},
+};
+use thiserror::Error;
+
+/// Anchor instruction discriminator length
+const DISCRIMINATOR_LEN: usize = 8;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum InstructionDecodeError {
+    #[error("Instruction is for program {0}, not Prism Protocol")]
+    WrongProgram(Pubkey),
+
+    #[error("Instruction data is shorter than an Anchor discriminator")]
+    MissingDiscriminator,
+
+    #[error("Unknown instruction discriminator: {0}")]
+    UnknownDiscriminator(String),
+
+    #[error("Invalid {instruction} arguments: {reason}")]
+    InvalidArguments {
+        instruction: &'static str,
+        reason: String,
+    },
+
+    #[error("{instruction} expects more accounts than the {actual} provided")]
+    NotEnoughAccounts {
+        instruction: &'static str,
+        actual: usize,
+    },
+
+    #[error("{instruction} has {claims} claims but {accounts} remaining accounts")]
+    BatchAccountMismatch {
+        instruction: &'static str,
+        claims: usize,
+        accounts: usize,
+    },
+
+    #[error("Account index {0} is out of bounds for the message's account keys")]
+    AccountIndexOutOfBounds(u8),
+}
+
+pub type DecodeResult<T> = std::result::Result<T, InstructionDecodeError>;
+
+/// Hands out instruction accounts in declaration order
+struct AccountCursor<'a> {
+    instruction: &'static str,
+    accounts: &'a [Pubkey],
+    next: usize,
+}
+
+impl AccountCursor<'_> {
+    fn next(&mut self) -> DecodeResult<Pubkey> {
+        let account = self.accounts.get(self.next).copied().ok_or(
+            InstructionDecodeError::NotEnoughAccounts {
+                instruction: self.instruction,
+                actual: self.accounts.len(),
+            },
+        )?;
+        self.next += 1;
+        Ok(account)
+    }
+
+    /// The per-claim remaining accounts of a batched claim
+    fn batch_claims(&mut self, claim_count: usize) -> DecodeResult<Vec<BatchClaimAccounts>> {
+        let remaining = self.accounts.len().saturating_sub(self.next);
+        if remaining != claim_count * CLAIM_BATCH_ACCOUNTS_PER_CLAIM {
+            return Err(InstructionDecodeError::BatchAccountMismatch {
+                instruction: self.instruction,
+                claims: claim_count,
+                accounts: remaining,
+            });
+        }
+
+        (0..claim_count)
+            .map(|_| {
+                Ok(BatchClaimAccounts {
+                    claimant: self.next()?,
+                    vault: self.next()?,
+                    claimant_token_account: self.next()?,
+                    claim_receipt: self.next()?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Accounts of one claim in a batched claim, in remaining-account order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchClaimAccounts {
+    pub claimant: Pubkey,
+    pub vault: Pubkey,
+    pub claimant_token_account: Pubkey,
+    pub claim_receipt: Pubkey,
+}
+
+/// Declares [`PrismInstruction`] and its decoder from one list, so the two can't drift.
+/// Accounts must be listed in the order of the program's `#[derive(Accounts)]` struct;
+/// batched claims add `+ claims` to decode their per-claim remaining accounts.
+macro_rules! prism_instructions {
+    ($($variant:ident [$($account:ident),* $(,)?] $(+ $claims:ident)?),* $(,)?) => {
+        /// A decoded Prism Protocol instruction
+        pub enum PrismInstruction {
+            $(
+                $variant {
+                    args: prism_protocol::instruction::$variant,
+                    accounts: prism_protocol::accounts::$variant,
+                    $($claims: Vec<BatchClaimAccounts>,)?
+                },
+            )*
+        }
+
+        impl PrismInstruction {
+            /// Instruction name, e.g. `ClaimTokensV0`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(PrismInstruction::$variant { .. } => stringify!($variant),)*
+                }
+            }
+
+            /// Decode raw instruction data and its account keys (in instruction order)
+            pub fn decode(data: &[u8], accounts: &[Pubkey]) -> DecodeResult<Self> {
+                if data.len() < DISCRIMINATOR_LEN {
+                    return Err(InstructionDecodeError::MissingDiscriminator);
+                }
+                let (discriminator, mut args) = data.split_at(DISCRIMINATOR_LEN);
+
+                $(
+                    if discriminator == prism_protocol::instruction::$variant::DISCRIMINATOR {
+                        let instruction = stringify!($variant);
+                        let invalid = |reason: String| InstructionDecodeError::InvalidArguments {
+                            instruction,
+                            reason,
+                        };
+                        let decoded: prism_protocol::instruction::$variant =
+                            AnchorDeserialize::deserialize(&mut args)
+                                .map_err(|e| invalid(e.to_string()))?;
+                        if !args.is_empty() {
+                            return Err(invalid(format!("{} trailing bytes", args.len())));
+                        }
+
+                        let mut cursor = AccountCursor { instruction, accounts, next: 0 };
+                        let accounts = prism_protocol::accounts::$variant {
+                            $($account: cursor.next()?,)*
+                        };
+                        $(let $claims = cursor.batch_claims(decoded.claims.len())?;)?
+                        return Ok(PrismInstruction::$variant {
+                            args: decoded,
+                            accounts,
+                            $($claims,)?
+                        });
+                    }
+                )*
+
+                Err(InstructionDecodeError::UnknownDiscriminator(hex::encode(discriminator)))
+            }
+        }
+
+        /// Anchor's generated argument and account types don't implement `Debug`, so
+        /// arguments are shown as their serialized bytes
+        impl std::fmt::Debug for PrismInstruction {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(
+                        PrismInstruction::$variant { args, accounts, $($claims)? } => f
+                            .debug_struct(stringify!($variant))
+                            .field("args", &hex::encode(&args.data()[DISCRIMINATOR_LEN..]))
+                            $(.field(stringify!($account), &accounts.$account))*
+                            $(.field(stringify!($claims), $claims))?
+                            .finish(),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+prism_instructions! {
+    InitializeCampaignV0 [admin, campaign, system_program],
+    ActivateCampaignV0 [admin, campaign],
+    MakeCampaignUnstoppableV0 [admin, campaign],
+    PauseCampaignV0 [admin, campaign],
+    ResumeCampaignV0 [admin, campaign],
+    PermanentlyHaltCampaignV0 [admin, campaign],
+    InitializeCohortV0 [admin, campaign, cohort, system_program],
+    InitializeCohortV1 [admin, campaign, cohort, cohort_tree, system_program],
+    ActivateCohortV0 [admin, campaign, cohort],
+    InitializeVaultV0 [admin, campaign, cohort, mint, vault, token_program, system_program],
+    ActivateVaultV0 [admin, campaign, cohort, vault],
+    ClaimTokensV0 [
+        admin,
+        claimant,
+        campaign,
+        cohort,
+        vault,
+        mint,
+        claimant_token_account,
+        claim_receipt,
+        token_program,
+        associated_token_program,
+        system_program,
+        rent,
+    ],
+    ClaimTokensV1 [
+        admin,
+        claimant,
+        campaign,
+        cohort,
+        vault,
+        mint,
+        claimant_token_account,
+        claim_receipt,
+        token_program,
+        associated_token_program,
+        system_program,
+        rent,
+    ],
+    ClaimTokensV2 [
+        admin,
+        claimant,
+        campaign,
+        cohort,
+        cohort_tree,
+        vault,
+        mint,
+        claimant_token_account,
+        claim_receipt,
+        token_program,
+        associated_token_program,
+        system_program,
+        rent,
+    ],
+    ClaimTokensBatchV0 [admin, payer, campaign, cohort, mint, token_program, system_program]
+        + claims,
+    ClaimTokensBatchV1 [
+        admin,
+        payer,
+        campaign,
+        cohort,
+        cohort_tree,
+        mint,
+        token_program,
+        system_program,
+    ] + claims,
+    ReclaimTokensV0 [
+        admin,
+        campaign,
+        cohort,
+        vault,
+        destination_token_account,
+        token_program,
+    ],
+}
+
+/// Where a decoded instruction sits in its transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstructionLocation {
+    /// Index of the top-level instruction
+    pub outer: usize,
+    /// Index within that instruction's inner (CPI) instructions, if any
+    pub inner: Option<usize>,
+}
+
+/// A Prism instruction found in a transaction
+#[derive(Debug)]
+pub struct DecodedInstruction {
+    pub location: InstructionLocation,
+    /// The decoded instruction, or why this Prism instruction couldn't be decoded
+    pub instruction: DecodeResult<PrismInstruction>,
+}
+
+/// Result of re-verifying a claim instruction offline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimVerification {
+    /// The merkle proof proves the claim leaf against the instruction's root
+    pub proof_valid: bool,
+    /// The campaign account is the PDA of the admin and fingerprint
+    pub campaign_matches: bool,
+    /// The cohort account is the PDA of the campaign and merkle root
+    pub cohort_matches: bool,
+    /// The cohort tree account, for claims that pass one, is the PDA of the cohort
+    pub cohort_tree_matches: bool,
+    /// The vault account is the PDA of the cohort and assigned vault index
+    pub vault_matches: bool,
+    /// The claim receipt is the PDA of the cohort and claimant
+    pub receipt_matches: bool,
+}
+
+impl ClaimVerification {
+    /// Every check passed
+    pub fn is_valid(&self) -> bool {
+        self.proof_valid
+            && self.campaign_matches
+            && self.cohort_matches
+            && self.cohort_tree_matches
+            && self.vault_matches
+            && self.receipt_matches
+    }
+}
+
+impl PrismInstruction {
+    /// Decode an instruction, checking it targets the Prism program
+    pub fn from_instruction(program_id: &Pubkey, ix: &Instruction) -> DecodeResult<Self> {
+        if ix.program_id != *program_id {
+            return Err(InstructionDecodeError::WrongProgram(ix.program_id));
+        }
+        let accounts: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        Self::decode(&ix.data, &accounts)
+    }
+
+    /// Decode a compiled instruction against its message's account keys
+    ///
+    /// For v0 messages `account_keys` must include the addresses loaded from lookup
+    /// tables (static keys, then loaded writable, then loaded readonly).
+    pub fn from_compiled(
+        program_id: &Pubkey,
+        account_keys: &[Pubkey],
+        ix: &CompiledInstruction,
+    ) -> DecodeResult<Self> {
+        let key = |index: u8| {
+            account_keys
+                .get(index as usize)
+                .copied()
+                .ok_or(InstructionDecodeError::AccountIndexOutOfBounds(index))
+        };
+
+        let ix_program_id = key(ix.program_id_index)?;
+        if ix_program_id != *program_id {
+            return Err(InstructionDecodeError::WrongProgram(ix_program_id));
+        }
+        let accounts = ix
+            .accounts
+            .iter()
+            .map(|index| key(*index))
+            .collect::<DecodeResult<Vec<_>>>()?;
+        Self::decode(&ix.data, &accounts)
+    }
+
+    /// The claim leaf this instruction proves, for single-claim instructions
+    pub fn claim_leaf(&self) -> Option<ClaimLeaf> {
+        let (claimant, assigned_vault_index, entitlements) = match self {
+            PrismInstruction::ClaimTokensV0 { args, accounts } => (
+                accounts.claimant,
+                args.assigned_vault_index,
+                args.entitlements,
+            ),
+            PrismInstruction::ClaimTokensV1 { args, accounts } => (
+                accounts.claimant,
+                args.assigned_vault_index,
+                args.entitlements,
+            ),
+            PrismInstruction::ClaimTokensV2 { args, accounts } => (
+                accounts.claimant,
+                args.assigned_vault_index,
+                args.entitlements,
+            ),
+            _ => return None,
+        };
+
+        Some(ClaimLeaf {
+            claimant,
+            assigned_vault_index,
+            entitlements,
+        })
+    }
+
+    /// Re-verify a claim instruction offline; `None` for other instructions, including batched claims
+    ///
+    /// This checks everything the program checks that doesn't need account state. An
+    /// indexer should still compare the cohort's on-chain merkle root, tree arity and hash
+    /// scheme if it has them; wide proofs are checked against the widest tree a cohort can
+    /// commit to, and proofs are accepted under any hash scheme.
+    pub fn verify_claim(&self, address_finder: &AddressFinder) -> Option<ClaimVerification> {
+        let leaf = self.claim_leaf()?;
+        let mut cohort_tree = None;
+        let (proof, arity, fingerprint, merkle_root, accounts) = match self {
+            PrismInstruction::ClaimTokensV0 { args, accounts } => (
+                ClaimProofType::from_binary(args.merkle_proof.clone()),
+                BINARY_TREE_ARITY,
+                args.campaign_fingerprint,
+                args.cohort_merkle_root,
+                (
+                    accounts.admin,
+                    accounts.campaign,
+                    accounts.cohort,
+                    accounts.vault,
+                    accounts.claim_receipt,
+                ),
+            ),
+            PrismInstruction::ClaimTokensV1 { args, accounts } => (
+                ClaimProofType::from_wide(args.merkle_proof.clone()),
+                MAX_TREE_ARITY,
+                args.campaign_fingerprint,
+                args.cohort_merkle_root,
+                (
+                    accounts.admin,
+                    accounts.campaign,
+                    accounts.cohort,
+                    accounts.vault,
+                    accounts.claim_receipt,
+                ),
+            ),
+            PrismInstruction::ClaimTokensV2 { args, accounts } => {
+                cohort_tree = Some(accounts.cohort_tree);
+                (
+                    ClaimProofType::from_wide(args.merkle_proof.clone()),
+                    MAX_TREE_ARITY,
+                    args.campaign_fingerprint,
+                    args.cohort_merkle_root,
+                    (
+                        accounts.admin,
+                        accounts.campaign,
+                        accounts.cohort,
+                        accounts.vault,
+                        accounts.claim_receipt,
+                    ),
+                )
+            }
+            _ => return None,
+        };
+        let (admin, campaign, cohort, vault, claim_receipt) = accounts;
+
+        let (expected_campaign, _) = address_finder.find_campaign_v0_address(&admin, &fingerprint);
+        let (expected_cohort, _) = address_finder.find_cohort_v0_address(&campaign, &merkle_root);
+        let (expected_vault, _) =
+            address_finder.find_vault_v0_address(&cohort, leaf.assigned_vault_index);
+        let (expected_receipt, _) =
+            address_finder.find_claim_receipt_v0_address(&cohort, &leaf.claimant);
+
+        Some(ClaimVerification {
+            proof_valid: ClaimHashScheme::ALL
+                .into_iter()
+                .any(|scheme| proof.verify_with_scheme(&merkle_root, &leaf, arity, scheme)),
+            campaign_matches: campaign == expected_campaign,
+            cohort_matches: cohort == expected_cohort,
+            cohort_tree_matches: cohort_tree.is_none_or(|cohort_tree| {
+                cohort_tree == address_finder.find_cohort_tree_v0_address(&cohort).0
+            }),
+            vault_matches: vault == expected_vault,
+            receipt_matches: claim_receipt == expected_receipt,
+        })
+    }
+}
+
+/// Find every Prism instruction in a transaction, top-level and inner
+///
+/// `inner_instructions` pairs a top-level instruction index with the instructions it
+/// invoked, as returned in a transaction's status meta. Instructions for other
+/// programs are skipped; a Prism instruction that fails to decode is reported in its
+/// own [`DecodedInstruction`] without affecting the rest.
+pub fn decode_transaction(
+    program_id: &Pubkey,
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    inner_instructions: &[(u8, Vec<CompiledInstruction>)],
+) -> Vec<DecodedInstruction> {
+    let mut decoded = Vec::new();
+    let mut push = |location: InstructionLocation, ix: &CompiledInstruction| {
+        match PrismInstruction::from_compiled(program_id, account_keys, ix) {
+            Err(InstructionDecodeError::WrongProgram(_)) => {}
+            instruction => decoded.push(DecodedInstruction {
+                location,
+                instruction,
+            }),
+        }
+    };
+
+    for (outer, ix) in instructions.iter().enumerate() {
+        push(InstructionLocation { outer, inner: None }, ix);
+
+        for (index, inner) in inner_instructions {
+            if *index as usize != outer {
+                continue;
+            }
+            for (inner_index, ix) in inner.iter().enumerate() {
+                push(
+                    InstructionLocation {
+                        outer,
+                        inner: Some(inner_index),
+                    },
+                    ix,
+                );
+            }
+        }
+    }
+
+    decoded
+}
+
+/// Find every Prism instruction in a legacy message and its inner instructions
+pub fn decode_message(
+    program_id: &Pubkey,
+    message: &Message,
+    inner_instructions: &[(u8, Vec<CompiledInstruction>)],
+) -> Vec<DecodedInstruction> {
+    decode_transaction(
+        program_id,
+        &message.account_keys,
+        &message.instructions,
+        inner_instructions,
+    )
+}
+
+/// Find every Prism instruction in a legacy or v0 message and its inner instructions
+///
+/// `loaded_addresses` are the addresses the v0 message loaded from lookup tables, as
+/// returned in a transaction's status meta; they are ignored for legacy messages.
+pub fn decode_versioned_message(
+    program_id: &Pubkey,
+    message: &VersionedMessage,
+    loaded_addresses: &LoadedAddresses,
+    inner_instructions: &[(u8, Vec<CompiledInstruction>)],
+) -> Vec<DecodedInstruction> {
+    let mut account_keys = message.static_account_keys().to_vec();
+    if let VersionedMessage::V0(_) = message {
+        account_keys.extend(&loaded_addresses.writable);
+        account_keys.extend(&loaded_addresses.readonly);
+    }
+    decode_transaction(
+        program_id,
+        &account_keys,
+        message.instructions(),
+        inner_instructions,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        build_claim_tokens_batch_v0_ix, build_claim_tokens_batch_v1_ix, build_claim_tokens_v0_ix,
+        build_claim_tokens_v2_ix, build_initialize_campaign_v0_ix, ClaimV1Params,
+    };
+    use prism_protocol_merkle::{ClaimTreeV0, ClaimTreeV1, ClaimTreeV1Options};
+    use solana_sdk::{
+        hash::Hash,
+        message::{v0, AddressLookupTableAccount},
+    };
+
+    fn claim_fixture() -> (AddressFinder, Instruction) {
+        let address_finder = AddressFinder::default();
+        let claimant = Pubkey::new_unique();
+        let leaves = vec![
+            ClaimLeaf {
+                claimant,
+                assigned_vault_index: 0,
+                entitlements: 3,
+            },
+            ClaimLeaf {
+                claimant: Pubkey::new_unique(),
+                assigned_vault_index: 0,
+                entitlements: 1,
+            },
+        ];
+        let tree = ClaimTreeV0::from_leaves(leaves).unwrap();
+
+        let (ix, _, _) = build_claim_tokens_v0_ix(
+            &address_finder,
+            Pubkey::new_unique(),
+            claimant,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            [9; 32],
+            tree.root().unwrap(),
+            tree.proof_for_claimant(&claimant).unwrap(),
+            0,
+            3,
+        )
+        .unwrap();
+
+        (address_finder, ix)
+    }
+
+    #[test]
+    fn test_decode_roundtrips_builder_output() {
+        let address_finder = AddressFinder::default();
+        let admin = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (ix, accounts, _) =
+            build_initialize_campaign_v0_ix(&address_finder, admin, [1; 32], mint, 2).unwrap();
+
+        match PrismInstruction::from_instruction(&address_finder.program_id, &ix).unwrap() {
+            PrismInstruction::InitializeCampaignV0 {
+                args,
+                accounts: decoded,
+            } => {
+                assert_eq!(args.campaign_fingerprint, [1; 32]);
+                assert_eq!(args.mint, mint);
+                assert_eq!(args.expected_cohort_count, 2);
+                assert_eq!(
+                    decoded.to_account_metas(None),
+                    accounts.to_account_metas(None)
+                );
+            }
+            other => panic!("decoded as {}", other.name()),
+        }
+
+        let (_, claim_ix) = claim_fixture();
+        let decoded =
+            PrismInstruction::from_instruction(&address_finder.program_id, &claim_ix).unwrap();
+        assert_eq!(decoded.name(), "ClaimTokensV0");
+        assert_eq!(
+            PrismInstruction::from_instruction(&Pubkey::new_unique(), &claim_ix).err(),
+            Some(InstructionDecodeError::WrongProgram(
+                address_finder.program_id
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decode_batch_claims() {
+        let address_finder = AddressFinder::default();
+        let leaves: Vec<ClaimLeaf> = (0..10)
+            .map(|i| ClaimLeaf {
+                claimant: Pubkey::new_unique(),
+                assigned_vault_index: i % 2,
+                entitlements: i as u64 + 1,
+            })
+            .collect();
+        let batch: Vec<Pubkey> = leaves.iter().step_by(3).map(|l| l.claimant).collect();
+        let tree = ClaimTreeV1::from_leaves_with_options(
+            leaves.clone(),
+            &ClaimTreeV1Options::with_arity(4),
+        )
+        .unwrap();
+        let (batch_leaves, multiproof) = tree.multiproof_for_claimants(&batch).unwrap();
+        let claims: Vec<(ClaimLeaf, Pubkey)> = batch_leaves
+            .iter()
+            .map(|leaf| (leaf.clone(), Pubkey::new_unique()))
+            .collect();
+
+        let (ix, accounts, _) = build_claim_tokens_batch_v1_ix(
+            &address_finder,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            [9; 32],
+            tree.root().unwrap(),
+            multiproof,
+            &claims,
+        )
+        .unwrap();
+
+        // Named accounts, then one signing claimant, vault, token account and receipt per claim
+        let named = accounts.to_account_metas(None).len();
+        assert_eq!(ix.accounts.len(), named + claims.len() * 4);
+        for ((leaf, token_account), metas) in claims.iter().zip(ix.accounts[named..].chunks(4)) {
+            assert_eq!(metas[0].pubkey, leaf.claimant);
+            assert!(metas[0].is_signer && !metas[0].is_writable);
+            assert_eq!(
+                metas[1].pubkey,
+                address_finder
+                    .find_vault_v0_address(&accounts.cohort, leaf.assigned_vault_index)
+                    .0
+            );
+            assert_eq!(metas[2].pubkey, *token_account);
+            assert_eq!(
+                metas[3].pubkey,
+                address_finder
+                    .find_claim_receipt_v0_address(&accounts.cohort, &leaf.claimant)
+                    .0
+            );
+            assert!(metas[1..].iter().all(|meta| meta.is_writable));
+        }
+
+        match PrismInstruction::from_instruction(&address_finder.program_id, &ix).unwrap() {
+            PrismInstruction::ClaimTokensBatchV1 {
+                args,
+                accounts: decoded,
+                claims: decoded_claims,
+            } => {
+                assert_eq!(decoded_claims.len(), claims.len());
+                assert_eq!(decoded.cohort_tree, accounts.cohort_tree);
+                assert_eq!(args.claims.len(), claims.len());
+                for (entry, (leaf, _)) in args.claims.iter().zip(&claims) {
+                    assert_eq!(entry.assigned_vault_index, leaf.assigned_vault_index);
+                    assert_eq!(entry.entitlements, leaf.entitlements);
+                }
+                assert!(args
+                    .multiproof
+                    .verify_with_arity(&tree.root().unwrap(), &batch_leaves, 4));
+            }
+            other => panic!("decoded as {}", other.name()),
+        }
+
+        // Batched claims aren't re-verified as single claims
+        let decoded = PrismInstruction::from_instruction(&address_finder.program_id, &ix).unwrap();
+        assert!(decoded.verify_claim(&address_finder).is_none());
+    }
+
+    #[test]
+    fn test_decode_batch_claim_accounts() {
+        let address_finder = AddressFinder::default();
+        let leaves: Vec<ClaimLeaf> = (0..4)
+            .map(|i| ClaimLeaf {
+                claimant: Pubkey::new_unique(),
+                assigned_vault_index: i % 2,
+                entitlements: i as u64 + 1,
+            })
+            .collect();
+        let batch = [leaves[1].claimant, leaves[2].claimant];
+        let tree = ClaimTreeV0::from_leaves(leaves).unwrap();
+        let (batch_leaves, multiproof) = tree.multiproof_for_claimants(&batch).unwrap();
+        let claims: Vec<(ClaimLeaf, Pubkey)> = batch_leaves
+            .into_iter()
+            .map(|leaf| (leaf, Pubkey::new_unique()))
+            .collect();
+        let (ix, accounts, _) = build_claim_tokens_batch_v0_ix(
+            &address_finder,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            [9; 32],
+            tree.root().unwrap(),
+            multiproof,
+            &claims,
+        )
+        .unwrap();
+
+        // An indexer can tell who was paid, from which vault, into which account
+        match PrismInstruction::from_instruction(&address_finder.program_id, &ix).unwrap() {
+            PrismInstruction::ClaimTokensBatchV0 {
+                claims: decoded, ..
+            } => {
+                let expected: Vec<BatchClaimAccounts> = claims
+                    .iter()
+                    .map(|(leaf, token_account)| BatchClaimAccounts {
+                        claimant: leaf.claimant,
+                        vault: address_finder
+                            .find_vault_v0_address(&accounts.cohort, leaf.assigned_vault_index)
+                            .0,
+                        claimant_token_account: *token_account,
+                        claim_receipt: address_finder
+                            .find_claim_receipt_v0_address(&accounts.cohort, &leaf.claimant)
+                            .0,
+                    })
+                    .collect();
+                assert_eq!(decoded.len(), 2);
+                assert_eq!(decoded, expected);
+            }
+            other => panic!("decoded as {}", other.name()),
+        }
+
+        // Remaining accounts must come in whole groups, one per claim
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(
+            PrismInstruction::decode(&ix.data, &keys[..keys.len() - 1]).err(),
+            Some(InstructionDecodeError::BatchAccountMismatch {
+                instruction: "ClaimTokensBatchV0",
+                claims: 2,
+                accounts: 7,
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_data() {
+        assert_eq!(
+            PrismInstruction::decode(&[1, 2, 3], &[]).err(),
+            Some(InstructionDecodeError::MissingDiscriminator)
+        );
+        assert!(matches!(
+            PrismInstruction::decode(&[0; 8], &[]),
+            Err(InstructionDecodeError::UnknownDiscriminator(_))
+        ));
+
+        let (_, ix) = claim_fixture();
+        let mut trailing = ix.data.clone();
+        trailing.push(0);
+        assert!(matches!(
+            PrismInstruction::decode(
+                &trailing,
+                &ix.accounts.iter().map(|m| m.pubkey).collect::<Vec<_>>()
+            ),
+            Err(InstructionDecodeError::InvalidArguments {
+                instruction: "ClaimTokensV0",
+                ..
+            })
+        ));
+        assert!(matches!(
+            PrismInstruction::decode(&ix.data, &[Pubkey::new_unique()]),
+            Err(InstructionDecodeError::NotEnoughAccounts {
+                instruction: "ClaimTokensV0",
+                actual: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decode_transaction_finds_inner_instructions() {
+        let (address_finder, claim_ix) = claim_fixture();
+        let payer = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+
+        // A router program that CPIs into Prism: the claim only appears as an inner instruction
+        let router_ix = Instruction {
+            program_id: other_program,
+            accounts: claim_ix.accounts.clone(),
+            data: vec![7],
+        };
+        let message = Message::new(&[router_ix, claim_ix.clone()], Some(&payer));
+        let inner_claim = message.instructions[1].clone();
+
+        let decoded = decode_message(
+            &address_finder.program_id,
+            &message,
+            &[(0, vec![inner_claim])],
+        );
+
+        let locations: Vec<_> = decoded.iter().map(|d| d.location).collect();
+        assert_eq!(
+            locations,
+            vec![
+                InstructionLocation {
+                    outer: 0,
+                    inner: Some(0)
+                },
+                InstructionLocation {
+                    outer: 1,
+                    inner: None
+                },
+            ]
+        );
+        assert!(decoded
+            .iter()
+            .all(|d| d.instruction.as_ref().unwrap().name() == "ClaimTokensV0"));
+    }
+
+    #[test]
+    fn test_decode_transaction_reports_each_instruction() {
+        let (address_finder, claim_ix) = claim_fixture();
+        let mut corrupt_ix = claim_ix.clone();
+        corrupt_ix.data.truncate(DISCRIMINATOR_LEN + 4);
+
+        let message = Message::new(&[corrupt_ix, claim_ix], Some(&Pubkey::new_unique()));
+        let decoded = decode_message(&address_finder.program_id, &message, &[]);
+
+        assert_eq!(decoded.len(), 2);
+        assert!(matches!(
+            decoded[0].instruction,
+            Err(InstructionDecodeError::InvalidArguments { .. })
+        ));
+        assert_eq!(
+            decoded[1].instruction.as_ref().unwrap().name(),
+            "ClaimTokensV0"
+        );
+    }
+
+    #[test]
+    fn test_decode_versioned_message_resolves_lookup_tables() {
+        let (address_finder, claim_ix) = claim_fixture();
+        let payer = Pubkey::new_unique();
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![
+                address_finder.token_program_id,
+                address_finder.associated_token_program_id,
+                address_finder.system_program_id,
+                address_finder.rent_id,
+            ],
+        };
+        let message = v0::Message::try_compile(
+            &payer,
+            std::slice::from_ref(&claim_ix),
+            std::slice::from_ref(&table),
+            Hash::new_unique(),
+        )
+        .unwrap();
+        assert_eq!(message.address_table_lookups.len(), 1);
+
+        // Lookup table indexes resolve in lookup order: writable, then readonly
+        let lookup = &message.address_table_lookups[0];
+        let loaded_addresses = LoadedAddresses {
+            writable: lookup
+                .writable_indexes
+                .iter()
+                .map(|i| table.addresses[*i as usize])
+                .collect(),
+            readonly: lookup
+                .readonly_indexes
+                .iter()
+                .map(|i| table.addresses[*i as usize])
+                .collect(),
+        };
+
+        let decoded = decode_versioned_message(
+            &address_finder.program_id,
+            &VersionedMessage::V0(message),
+            &loaded_addresses,
+            &[],
+        );
+        assert_eq!(decoded.len(), 1);
+        let instruction = decoded[0].instruction.as_ref().unwrap();
+        let expected =
+            PrismInstruction::from_instruction(&address_finder.program_id, &claim_ix).unwrap();
+        assert_eq!(format!("{instruction:?}"), format!("{expected:?}"));
+        assert!(instruction
+            .verify_claim(&address_finder)
+            .unwrap()
+            .is_valid());
+    }
+
+    #[test]
+    fn test_verify_claim_offline() {
+        let (address_finder, ix) = claim_fixture();
+        let decoded = PrismInstruction::from_instruction(&address_finder.program_id, &ix).unwrap();
+
+        let verification = decoded.verify_claim(&address_finder).unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(decoded.claim_leaf().unwrap().entitlements, 3);
+
+        // Claiming more entitlements than the leaf grants breaks the proof
+        let mut tampered = ix.clone();
+        let entitlements_offset = tampered.data.len() - 8;
+        tampered.data[entitlements_offset..].copy_from_slice(&4u64.to_le_bytes());
+        let tampered =
+            PrismInstruction::from_instruction(&address_finder.program_id, &tampered).unwrap();
+        let verification = tampered.verify_claim(&address_finder).unwrap();
+        assert!(!verification.proof_valid);
+        assert!(verification.cohort_matches);
+        assert!(!verification.is_valid());
+    }
+
+    #[test]
+    fn test_verify_claim_v2_checks_cohort_tree() {
+        let address_finder = AddressFinder::default();
+        let claimant = Pubkey::new_unique();
+        let mut leaves: Vec<ClaimLeaf> = (0..6)
+            .map(|_| ClaimLeaf {
+                claimant: Pubkey::new_unique(),
+                assigned_vault_index: 0,
+                entitlements: 1,
+            })
+            .collect();
+        leaves[0].claimant = claimant;
+        let tree =
+            ClaimTreeV1::from_leaves_with_options(leaves, &ClaimTreeV1Options::with_arity(4))
+                .unwrap();
+
+        let (ix, accounts, _) = build_claim_tokens_v2_ix(
+            &address_finder,
+            ClaimV1Params {
+                admin: Pubkey::new_unique(),
+                claimant,
+                mint: Pubkey::new_unique(),
+                claimant_token_account: Pubkey::new_unique(),
+                campaign_fingerprint: [9; 32],
+                cohort_merkle_root: tree.root().unwrap(),
+                merkle_proof: tree.proof_for_claimant(&claimant).unwrap(),
+                assigned_vault_index: 0,
+                entitlements: 1,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            accounts.cohort_tree,
+            address_finder
+                .find_cohort_tree_v0_address(&accounts.cohort)
+                .0
+        );
+
+        let decoded = PrismInstruction::from_instruction(&address_finder.program_id, &ix).unwrap();
+        assert_eq!(decoded.name(), "ClaimTokensV2");
+        assert!(decoded.verify_claim(&address_finder).unwrap().is_valid());
+
+        // A cohort tree account for another cohort is rejected
+        let mut wrong_tree = ix.clone();
+        wrong_tree.accounts[4].pubkey = Pubkey::new_unique();
+        let wrong_tree =
+            PrismInstruction::from_instruction(&address_finder.program_id, &wrong_tree).unwrap();
+        let verification = wrong_tree.verify_claim(&address_finder).unwrap();
+        assert!(verification.proof_valid);
+        assert!(!verification.cohort_tree_matches);
+        assert!(!verification.is_valid());
+    }
+}
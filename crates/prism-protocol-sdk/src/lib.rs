@@ -4,36 +4,126 @@
 This crate provides the core SDK functionality for Prism Protocol, including:
 
 - **Campaign Compilation**: Convert CSV data into deployable campaign databases
+- **Campaign Layout**: Tree version, arity and vault count planned from claim costs
+- **Campaign Diffs**: Review what a recompile changes before redeploying
+- **Claim Sync**: Reconcile recorded claims with on-chain claim receipts
+- **Deploy Planning**: Every deploy transaction up front, for signing offline
+- **Campaign Manifests**: One YAML/TOML file as the reproducible compile input
+- **Streaming Compilation**: Memory-bounded compilation of multi-million-claimant campaigns
+- **Proof Export**: Sharded static JSON proof bundles for claim sites
 - **Budget Allocation**: Precise token distribution calculations with safety checks
+- **Dust Accounting**: Report and optionally redistribute rounding remainders
 - **Address Management**: Derive all protocol PDAs and addresses
+- **Compute Budget**: Size CU limits from simulation and set priority fees
+- **Instruction Decoding**: Recognise Prism instructions in transactions and re-verify claims
+- **Versioned Transactions**: Per-campaign address lookup tables and v0 message compilation
+- **Offline Signing**: Durable nonce transactions signed without RPC access
+- **Tree Arity**: Pick the merkle tree arity that minimises claim size or compute
+- **Tree Artifacts**: Serialized cohort trees that serve proofs without a rebuild
 
 ## Key Modules
 
 - `campaign_compiler`: Main compilation logic
+- `campaign_layout`: Per-cohort layout planner over the claim cost model
+- `campaign_diff`: Comparison of two compiled campaign databases
+- `claim_sync`: Claim receipt reconciliation and drift reporting
+- `deploy_plan`: Ordered deploy transactions built from a campaign database
+- `manifest`: Campaign manifest parsing and hashing
+- `streaming_compiler`: External-sort compilation straight into a database
+- `proof_export`: Per-claimant proof bundles with a content-hashed manifest
 - `budget_allocation`: Isolated budget→token math with thorough testing
+- `dust`: Dust policies applied during compilation
 - `address_finder`: PDA derivation and address management
+- `compute_budget`: Compute budget instructions (re-exported from `prism-protocol-tx`)
+- `instruction_decoder`: Typed decoding of Prism instructions for indexers
+- `lookup_table`: Address lookup tables for v0 claim and deploy transactions
+- `offline_signing`: Durable nonce signing and signed transaction encoding
+- `tree_arity`: Claim cost model and arity optimizer
+- `tree_artifacts`: Storing and opening memory-mappable cohort tree artifacts
 */
 
 mod address_finder;
 pub mod budget_allocation;
 pub mod campaign_compiler;
+pub mod campaign_diff;
+pub mod campaign_layout;
+pub mod claim_sync;
+pub mod deploy_plan;
+pub mod dust;
 mod instruction_builders;
+pub mod instruction_decoder;
+pub mod lookup_table;
+pub mod manifest;
+pub mod offline_signing;
+pub mod proof_export;
+pub mod streaming_compiler;
+pub mod tree_arity;
+pub mod tree_artifacts;
+
+pub use prism_protocol_tx::compute_budget;
 
 // Re-export main types
 pub use address_finder::AddressFinder;
-pub use budget_allocation::{AllocationError, AllocationResult, BudgetAllocator};
+pub use budget_allocation::{
+    from_base_units, to_base_units, AllocationError, AllocationResult, AllocationRule,
+    BudgetAllocator, CampaignAllocation, ClaimantCaps, CohortAllocationRequest,
+    ResolvedCohortAllocation,
+};
 pub use campaign_compiler::{
-    compile_campaign, CompiledCampaign, CompiledCohort, CompilerError, CompilerResult,
+    compile_campaign, compile_campaign_db_with_options, compile_campaign_with_options,
+    CampaignSettings, CohortMerkleTree, CompileOptions, CompiledCampaign, CompiledCohort,
+    CompilerError, CompilerResult,
+};
+pub use campaign_diff::{diff_campaigns, CampaignDiff, CohortChange, CohortDiff};
+pub use campaign_layout::{
+    plan_layout, CampaignLayout, CohortLayout, LayoutConstraints, LayoutError, LayoutPlanner,
 };
+pub use claim_sync::{
+    apply_claims, clear_missing_receipts, ClaimDrift, CohortClaimSync, PendingClaim,
+};
+pub use compute_budget::{ComputeBudgetConfig, PriorityFee};
+pub use deploy_plan::{plan_deployment, CampaignActivation, DeployPlanError, DeployTransaction};
+pub use dust::DustPolicy;
 pub use instruction_builders::*;
+pub use instruction_decoder::{
+    decode_message, decode_transaction, decode_versioned_message, BatchClaimAccounts,
+    ClaimVerification, DecodedInstruction, InstructionDecodeError, InstructionLocation,
+    PrismInstruction,
+};
+pub use lookup_table::{
+    campaign_lookup_table_addresses, compile_message, CampaignLookupTableAddresses,
+};
+pub use manifest::{
+    compile_manifest_db, compile_manifest_streaming, load_manifest, open_manifest,
+    CampaignManifest, LoadedManifest, ManifestInputs, TreeVersion,
+};
+pub use offline_signing::{
+    decode_signed_transaction, encode_signed_transaction, sign_with_nonce, DurableNonce,
+    OfflineSigningError,
+};
 pub use prism_protocol::state::*;
-pub use prism_protocol::ClaimLeaf;
-pub use prism_protocol_merkle::ClaimTreeV0;
+pub use prism_protocol::{ClaimHashScheme, ClaimLeaf};
+pub use prism_protocol_merkle::{
+    ClaimTreeV0, ClaimantIndexTree, LeafOrder, NonMembershipProof, TreeArtifact,
+};
+pub use proof_export::{
+    export_proofs, verify_claimant_bundle, ClaimantBundle, ProofExportError, ProofExportOptions,
+    ProofExportSummary, ProofManifest,
+};
+pub use streaming_compiler::{
+    compile_campaign_streaming, CompileProgress, StreamingCompileSummary, StreamingOptions,
+};
+pub use tree_arity::{optimal_arity, ArityEstimate, ArityObjective, ClaimCostModel};
+pub use tree_artifacts::{
+    open_cohort_tree_artifact, store_tree_artifacts, TreeArtifactStorage, TreeArtifactStoreError,
+};
 
 // Re-export csv types
-pub use prism_protocol_csvs::{CampaignCsvRow, CohortsCsvRow};
+pub use prism_protocol_csvs::{AllocationMode, CampaignCsvRow, CohortsCsvRow};
 
 // Re-export database types
 pub use prism_protocol_db::{
-    CampaignDatabase, CampaignInfo, ClaimProof, CohortInfo, EligibilityInfo, VaultRequirement,
+    CampaignDatabase, CampaignInfo, ClaimProof, CohortDust, CohortInfo, DustReport, DustSummary,
+    EligibilityInfo, StoredMultiProofNode, StoredProof, TreeArtifactRecord, TreeArtifactSource,
+    VaultDust, VaultRequirement,
 };
@@ -0,0 +1,300 @@
+/*!
+# Address Lookup Tables
+
+Versioned (v0) transaction support for claims and deploys.
+
+A claim references 12 accounts (13 for V1 cohorts) plus its merkle proof, which caps
+the proof depth a legacy transaction can carry. Every account that is the same for all
+claimants of a campaign (campaign, mint, admin, cohorts, cohort trees, vaults and
+programs) goes into a per-campaign address lookup table, so a v0 claim only carries the
+claimant-specific keys inline.
+
+Deploy creates the table with [`build_create_lookup_table_ix`], fills it with
+[`build_extend_lookup_table_ixs`] and records its address in the campaign database.
+Claims fetch it back and compile against it with [`compile_message`].
+*/
+
+use crate::AddressFinder;
+use prism_protocol_db::{CohortInfo, CohortTree};
+pub use prism_protocol_tx::message::{compile_message, transaction_size, unsigned_transaction};
+use solana_address_lookup_table_interface::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::{AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES},
+};
+use solana_sdk::{
+    clock::Slot,
+    compute_budget,
+    instruction::{Instruction, InstructionError},
+    message::AddressLookupTableAccount,
+    pubkey::Pubkey,
+};
+
+/// Addresses appended per extend instruction (keeps each extend transaction under the packet limit)
+pub const LOOKUP_TABLE_EXTEND_CHUNK_SIZE: usize = 20;
+
+/// Campaign addresses split at the capacity of one lookup table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CampaignLookupTableAddresses {
+    /// Addresses for the campaign's table, at most [`LOOKUP_TABLE_MAX_ADDRESSES`]
+    pub addresses: Vec<Pubkey>,
+    /// Addresses that don't fit; put them in a second table, or transactions carry
+    /// them inline
+    pub overflow: Vec<Pubkey>,
+}
+
+/// Accounts shared by every claim of a campaign, programs first
+///
+/// Addresses past the table capacity are returned in
+/// [`CampaignLookupTableAddresses::overflow`] rather than dropped.
+pub fn campaign_lookup_table_addresses(
+    address_finder: &AddressFinder,
+    admin: &Pubkey,
+    fingerprint: &[u8; 32],
+    mint: &Pubkey,
+    cohorts: &[CohortInfo],
+) -> CampaignLookupTableAddresses {
+    let (campaign, _) = address_finder.find_campaign_v0_address(admin, fingerprint);
+
+    let mut addresses = vec![
+        address_finder.program_id,
+        address_finder.token_program_id,
+        address_finder.associated_token_program_id,
+        address_finder.system_program_id,
+        address_finder.rent_id,
+        compute_budget::id(),
+        campaign,
+        *mint,
+        *admin,
+    ];
+
+    for cohort_info in cohorts {
+        let (cohort, _) =
+            address_finder.find_cohort_v0_address(&campaign, &cohort_info.merkle_root);
+        addresses.push(cohort);
+        if let CohortTree::V1 { .. } = cohort_info.tree {
+            addresses.push(address_finder.find_cohort_tree_v0_address(&cohort).0);
+        }
+        for vault_index in 0..cohort_info.vault_count {
+            let (vault, _) = address_finder.find_vault_v0_address(&cohort, vault_index as u8);
+            addresses.push(vault);
+        }
+    }
+
+    dedup_addresses(&mut addresses);
+    let overflow = addresses.split_off(addresses.len().min(LOOKUP_TABLE_MAX_ADDRESSES));
+    CampaignLookupTableAddresses {
+        addresses,
+        overflow,
+    }
+}
+
+/// Instruction creating a lookup table owned by `authority`, and the table's address
+pub fn build_create_lookup_table_ix(
+    authority: Pubkey,
+    payer: Pubkey,
+    recent_slot: Slot,
+) -> (Instruction, Pubkey) {
+    create_lookup_table(authority, payer, recent_slot)
+}
+
+/// Instructions appending `addresses` to a lookup table, one per chunk
+pub fn build_extend_lookup_table_ixs(
+    lookup_table: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    addresses
+        .chunks(LOOKUP_TABLE_EXTEND_CHUNK_SIZE)
+        .map(|chunk| extend_lookup_table(lookup_table, authority, Some(payer), chunk.to_vec()))
+        .collect()
+}
+
+/// Addresses not yet stored in the table (in order), for resuming a partial deploy
+pub fn missing_lookup_table_addresses(
+    lookup_table: &AddressLookupTableAccount,
+    addresses: &[Pubkey],
+) -> Vec<Pubkey> {
+    addresses
+        .iter()
+        .filter(|address| !lookup_table.addresses.contains(address))
+        .copied()
+        .collect()
+}
+
+/// Decode lookup table account data
+pub fn decode_lookup_table(
+    address: Pubkey,
+    data: &[u8],
+) -> Result<AddressLookupTableAccount, InstructionError> {
+    let table = AddressLookupTable::deserialize(data)?;
+    Ok(AddressLookupTableAccount {
+        key: address,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+/// Remove duplicates, keeping first occurrences in order
+fn dedup_addresses(addresses: &mut Vec<Pubkey>) {
+    let mut seen = std::collections::HashSet::new();
+    addresses.retain(|address| seen.insert(*address));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_claim_tokens_v0_ix;
+    use solana_sdk::{hash::Hash, message::VersionedMessage, packet::PACKET_DATA_SIZE};
+
+    fn cohort(merkle_root: [u8; 32], vault_count: usize) -> CohortInfo {
+        CohortInfo {
+            name: hex::encode(&merkle_root[..4]),
+            merkle_root,
+            amount_per_entitlement: 1,
+            amount_per_entitlement_humane: "1".to_string(),
+            vaults: vec![],
+            vault_count,
+            tree: CohortTree::V0,
+        }
+    }
+
+    #[test]
+    fn test_campaign_lookup_table_addresses() {
+        let address_finder = AddressFinder::default();
+        let admin = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let cohorts = vec![cohort([1; 32], 2), cohort([2; 32], 3)];
+
+        let CampaignLookupTableAddresses {
+            addresses,
+            overflow,
+        } = campaign_lookup_table_addresses(&address_finder, &admin, &[7; 32], &mint, &cohorts);
+
+        // 6 programs/sysvars + campaign, mint, admin + 2 cohorts + 5 vaults
+        assert_eq!(addresses.len(), 16);
+        assert!(overflow.is_empty());
+        assert_eq!(addresses[0], address_finder.program_id);
+
+        let (campaign, _) = address_finder.find_campaign_v0_address(&admin, &[7; 32]);
+        let (cohort, _) = address_finder.find_cohort_v0_address(&campaign, &[2; 32]);
+        let (vault, _) = address_finder.find_vault_v0_address(&cohort, 2);
+        assert!(addresses.contains(&campaign));
+        assert!(addresses.contains(&vault));
+    }
+
+    #[test]
+    fn test_campaign_lookup_table_addresses_overflow() {
+        let address_finder = AddressFinder::default();
+        let admin = Pubkey::new_unique();
+        let cohorts: Vec<CohortInfo> = (0..3u8).map(|i| cohort([i; 32], 100)).collect();
+        let CampaignLookupTableAddresses {
+            addresses,
+            overflow,
+        } = campaign_lookup_table_addresses(
+            &address_finder,
+            &admin,
+            &[7; 32],
+            &Pubkey::new_unique(),
+            &cohorts,
+        );
+
+        // 9 shared + 3 cohorts + 300 vaults: nothing is dropped past the table capacity
+        assert_eq!(addresses.len(), LOOKUP_TABLE_MAX_ADDRESSES);
+        assert_eq!(overflow.len(), 312 - LOOKUP_TABLE_MAX_ADDRESSES);
+
+        let (campaign, _) = address_finder.find_campaign_v0_address(&admin, &[7; 32]);
+        let (last_cohort, _) = address_finder.find_cohort_v0_address(&campaign, &[2; 32]);
+        let (last_vault, _) = address_finder.find_vault_v0_address(&last_cohort, 99);
+        assert_eq!(overflow.last(), Some(&last_vault));
+    }
+
+    #[test]
+    fn test_extend_chunks_and_missing_addresses() {
+        let addresses: Vec<Pubkey> = (0..45).map(|_| Pubkey::new_unique()).collect();
+        let ixs = build_extend_lookup_table_ixs(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            &addresses,
+        );
+        assert_eq!(ixs.len(), 3);
+
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: addresses[..40].to_vec(),
+        };
+        assert_eq!(
+            missing_lookup_table_addresses(&table, &addresses),
+            addresses[40..].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_decode_lookup_table() {
+        use solana_address_lookup_table_interface::state::LookupTableMeta;
+        use std::borrow::Cow;
+
+        let key = Pubkey::new_unique();
+        let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let data = AddressLookupTable {
+            meta: LookupTableMeta::new(Pubkey::new_unique()),
+            addresses: Cow::Borrowed(&addresses),
+        }
+        .serialize_for_tests()
+        .unwrap();
+
+        let table = decode_lookup_table(key, &data).unwrap();
+        assert_eq!(table.key, key);
+        assert_eq!(table.addresses, addresses);
+
+        assert!(decode_lookup_table(key, &[0; 8]).is_err());
+    }
+
+    #[test]
+    fn test_v0_claim_fits_deeper_proofs() {
+        let address_finder = AddressFinder::default();
+        let admin = Pubkey::new_unique();
+        let claimant = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let fingerprint = [7; 32];
+        let merkle_root = [1; 32];
+
+        let (claim_ix, _, _) = build_claim_tokens_v0_ix(
+            &address_finder,
+            admin,
+            claimant,
+            mint,
+            Pubkey::new_unique(),
+            fingerprint,
+            merkle_root,
+            vec![[9; 32]; 22],
+            0,
+            1,
+        )
+        .unwrap();
+
+        let legacy = compile_message(
+            &claimant,
+            std::slice::from_ref(&claim_ix),
+            &[],
+            Hash::default(),
+        )
+        .unwrap();
+        assert!(transaction_size(&legacy) > PACKET_DATA_SIZE);
+
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: campaign_lookup_table_addresses(
+                &address_finder,
+                &admin,
+                &fingerprint,
+                &mint,
+                &[cohort(merkle_root, 1)],
+            )
+            .addresses,
+        };
+        let versioned = compile_message(&claimant, &[claim_ix], &[table], Hash::default()).unwrap();
+        assert!(matches!(versioned, VersionedMessage::V0(_)));
+        assert!(transaction_size(&versioned) <= PACKET_DATA_SIZE);
+    }
+}
@@ -0,0 +1,765 @@
+/*!
+# Campaign Manifest
+
+A single YAML or TOML file declaring everything a compiled campaign depends on:
+mint, budget, admin, tree version, hash scheme, vault sizing, dust policy, leaf order,
+go-live slot and, per cohort, its allocation mode and claimant file.
+
+```yaml
+name: launch-airdrop
+mint: So11111111111111111111111111111111111111112
+admin: 9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin
+budget: "1000000"
+mint_decimals: 9
+claimants_per_vault: 200000
+dust_policy: first-leaves
+leaf_order: claimant
+go_live_slot: 310000000
+cohorts:
+  - name: early
+    claimants_file: early.csv
+    share_percentage: 60
+  - name: partners
+    claimants_file: partners.csv
+    allocation_mode: cohort_budget
+    allocation_value: 250000
+```
+
+With `tree_version: v1`, every cohort compiles into a tree of `tree_arity` children per
+node, hashed with `hash_scheme` (sha256 or keccak256); V0 trees are binary and SHA-256.
+
+Claimant files are CSVs with `claimant,entitlements` headers, resolved relative to
+the manifest. The [manifest hash](LoadedManifest::hash) covers the manifest bytes,
+every claimant file and the mint decimals compiled with, and is recorded in the campaign database so a compiled campaign
+can be traced back to (and reproduced from) exactly one input.
+
+Campaigns too large to hold in memory are opened with [`open_manifest`], which only
+hashes the claimant files, and compiled with [`compile_manifest_streaming`].
+*/
+
+use crate::campaign_compiler::{
+    compile_campaign_db_with_options, CampaignSettings, CompileOptions, CompilerError,
+    CompilerResult,
+};
+use crate::dust::DustPolicy;
+use crate::streaming_compiler::{
+    compile_rows_streaming, CompileProgress, StreamedCampaign, StreamingCompileSummary,
+    StreamingOptions,
+};
+use crate::AddressFinder;
+use prism_protocol::ClaimHashScheme;
+use prism_protocol_csvs::{AllocationMode, CampaignCsvRow, CohortsCsvRow};
+use prism_protocol_db::CampaignDatabase;
+use prism_protocol_merkle::LeafOrder;
+use rust_decimal::Decimal;
+use serde::{de, Deserialize, Deserializer};
+use sha2::{Digest, Sha256};
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Expected headers of a per-cohort claimant file
+pub const CLAIMANT_FILE_HEADERS: &[&str] = &["claimant", "entitlements"];
+
+/// Merkle tree layout cohorts are compiled into
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeVersion {
+    /// Binary tree ([`ClaimTreeV0`](prism_protocol_merkle::ClaimTreeV0))
+    #[default]
+    V0,
+    /// Tree of configurable arity ([`ClaimTreeV1`](prism_protocol_merkle::ClaimTreeV1))
+    V1,
+}
+
+/// Campaign manifest file contents
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CampaignManifest {
+    /// Human-readable campaign name (for organization)
+    pub name: String,
+
+    /// SPL token mint to be distributed
+    #[serde(deserialize_with = "from_str")]
+    pub mint: Pubkey,
+
+    /// Campaign admin
+    #[serde(deserialize_with = "from_str")]
+    pub admin: Pubkey,
+
+    /// Total budget in human-readable tokens
+    #[serde(deserialize_with = "decimal")]
+    pub budget: Decimal,
+
+    /// Mint decimals; looked up on-chain when omitted
+    #[serde(default)]
+    pub mint_decimals: Option<u8>,
+
+    /// Tree layout for every cohort
+    #[serde(default)]
+    pub tree_version: TreeVersion,
+
+    /// Children per internal node of every V1 cohort tree (2-256); required for V1
+    #[serde(default)]
+    pub tree_arity: Option<usize>,
+
+    /// Hash scheme of V1 cohort trees: sha256 or keccak256 (V0 trees are always SHA-256)
+    #[serde(default, deserialize_with = "from_str")]
+    pub hash_scheme: ClaimHashScheme,
+
+    /// Maximum claimants per vault
+    #[serde(default = "default_claimants_per_vault")]
+    pub claimants_per_vault: usize,
+
+    /// What to do with rounding dust: retain, first-leaves or treasury:<pubkey>
+    #[serde(default, deserialize_with = "from_str")]
+    pub dust_policy: DustPolicy,
+
+    /// Leaf order of every cohort tree: insertion or claimant
+    #[serde(default, deserialize_with = "from_str")]
+    pub leaf_order: LeafOrder,
+
+    /// Slot from which claims are accepted once the campaign is activated
+    #[serde(default)]
+    pub go_live_slot: Option<u64>,
+
+    /// Cohorts in fingerprint order
+    pub cohorts: Vec<CohortManifest>,
+}
+
+/// A cohort declared in a campaign manifest
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CohortManifest {
+    /// Cohort identifier
+    pub name: String,
+
+    /// CSV of `claimant,entitlements`, relative to the manifest
+    pub claimants_file: PathBuf,
+
+    /// Allocation mode (defaults to `share_percentage`)
+    #[serde(default)]
+    pub allocation_mode: AllocationMode,
+
+    /// Percentage of the remaining budget for `share_percentage` cohorts
+    #[serde(default, deserialize_with = "decimal")]
+    pub share_percentage: Decimal,
+
+    /// Mode parameter for `fixed_per_entitlement` and `cohort_budget`
+    #[serde(default, deserialize_with = "optional_decimal")]
+    pub allocation_value: Option<Decimal>,
+
+    /// Optional lower bound on any single claimant's allocation (in tokens)
+    #[serde(default, deserialize_with = "optional_decimal")]
+    pub min_per_claimant: Option<Decimal>,
+
+    /// Optional upper bound on any single claimant's allocation (in tokens)
+    #[serde(default, deserialize_with = "optional_decimal")]
+    pub max_per_claimant: Option<Decimal>,
+}
+
+/// One row of a per-cohort claimant file
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ClaimantInput {
+    #[serde(deserialize_with = "from_str")]
+    pub claimant: Pubkey,
+    pub entitlements: u64,
+}
+
+/// A manifest with its claimant files read and hashed
+#[derive(Debug, Clone)]
+pub struct LoadedManifest {
+    pub manifest: CampaignManifest,
+    /// SHA-256 over the manifest bytes followed by the SHA-256 of each claimant file
+    pub inputs_hash: [u8; 32],
+    pub campaign_rows: Vec<CampaignCsvRow>,
+    pub cohorts_rows: Vec<CohortsCsvRow>,
+}
+
+/// A manifest with its claimant files hashed but not read, for streaming compilation
+#[derive(Debug, Clone)]
+pub struct ManifestInputs {
+    pub manifest: CampaignManifest,
+    /// Same as [`LoadedManifest::inputs_hash`]
+    pub inputs_hash: [u8; 32],
+    /// Claimant file of each cohort, resolved relative to the manifest
+    pub claimant_files: Vec<PathBuf>,
+}
+
+impl CampaignManifest {
+    /// Parse a manifest, choosing YAML or TOML by file extension
+    pub fn parse(path: &Path, contents: &str) -> CompilerResult<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+
+        match extension {
+            "yaml" | "yml" => serde_yaml::from_str(contents).map_err(|e| {
+                CompilerError::Manifest(format!("Invalid YAML in {}: {}", path.display(), e))
+            }),
+            "toml" => toml::from_str(contents).map_err(|e| {
+                CompilerError::Manifest(format!("Invalid TOML in {}: {}", path.display(), e))
+            }),
+            _ => Err(CompilerError::Manifest(format!(
+                "Unsupported manifest extension '{}' (expected .yaml, .yml or .toml)",
+                extension
+            ))),
+        }
+    }
+
+    /// Campaign-wide settings, compiled for a mint of `mint_decimals`
+    pub fn settings(&self, mint_decimals: u8) -> CampaignSettings {
+        CampaignSettings {
+            budget: self.budget,
+            mint: self.mint,
+            mint_decimals,
+            admin: self.admin,
+            claimants_per_vault: self.claimants_per_vault,
+        }
+    }
+
+    /// Cohort settings in cohorts.csv form
+    pub fn cohorts_rows(&self) -> Vec<CohortsCsvRow> {
+        self.cohorts
+            .iter()
+            .map(|cohort| CohortsCsvRow {
+                cohort: cohort.name.clone(),
+                share_percentage: cohort.share_percentage,
+                allocation_mode: cohort.allocation_mode,
+                allocation_value: cohort.allocation_value,
+                min_per_claimant: cohort.min_per_claimant,
+                max_per_claimant: cohort.max_per_claimant,
+            })
+            .collect()
+    }
+
+    /// Check settings that only make sense together
+    pub fn validate(&self) -> CompilerResult<()> {
+        if self.claimants_per_vault == 0 {
+            return Err(CompilerError::Manifest(
+                "claimants_per_vault must be positive".to_string(),
+            ));
+        }
+
+        match (self.tree_version, self.tree_arity) {
+            (TreeVersion::V0, Some(_)) => Err(CompilerError::Manifest(
+                "tree_arity applies only to tree_version v1".to_string(),
+            )),
+            (TreeVersion::V0, None) if self.hash_scheme != ClaimHashScheme::Sha256 => {
+                Err(CompilerError::Manifest(format!(
+                    "hash_scheme {} needs tree_version v1 (V0 trees are always SHA-256)",
+                    self.hash_scheme
+                )))
+            }
+            (TreeVersion::V1, None) => Err(CompilerError::Manifest(
+                "tree_version v1 needs a tree_arity".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Compilation settings declared by the manifest
+    pub fn compile_options(&self) -> CompileOptions {
+        let tree_arities = match (self.tree_version, self.tree_arity) {
+            (TreeVersion::V1, Some(arity)) => self
+                .cohorts
+                .iter()
+                .map(|cohort| (cohort.name.clone(), arity))
+                .collect(),
+            _ => Default::default(),
+        };
+
+        CompileOptions {
+            dust_policy: self.dust_policy,
+            leaf_order: self.leaf_order,
+            tree_arities,
+            hash_scheme: self.hash_scheme,
+            ..Default::default()
+        }
+    }
+}
+
+/// Read a manifest and hash every claimant file it references
+pub fn open_manifest(path: &Path) -> CompilerResult<ManifestInputs> {
+    let contents = std::fs::read(path)?;
+    let text = std::str::from_utf8(&contents).map_err(|e| {
+        CompilerError::Manifest(format!("Manifest {} is not UTF-8: {}", path.display(), e))
+    })?;
+    let manifest = CampaignManifest::parse(path, text)?;
+    manifest.validate()?;
+
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    let mut hasher = Sha256::new();
+    hasher.update(&contents);
+
+    let mut claimant_files = Vec::with_capacity(manifest.cohorts.len());
+    for cohort in &manifest.cohorts {
+        let claimants_path = base_dir.join(&cohort.claimants_file);
+        let mut file_hasher = Sha256::new();
+        std::io::copy(&mut File::open(&claimants_path)?, &mut file_hasher)?;
+        hasher.update(file_hasher.finalize());
+        claimant_files.push(claimants_path);
+    }
+
+    Ok(ManifestInputs {
+        manifest,
+        inputs_hash: hasher.finalize().into(),
+        claimant_files,
+    })
+}
+
+/// Read a manifest and every claimant file it references
+pub fn load_manifest(path: &Path) -> CompilerResult<LoadedManifest> {
+    let ManifestInputs {
+        manifest,
+        inputs_hash,
+        claimant_files,
+    } = open_manifest(path)?;
+
+    let mut campaign_rows = Vec::new();
+    for (cohort, claimants_path) in manifest.cohorts.iter().zip(&claimant_files) {
+        for claimant in stream_claimant_file(claimants_path)? {
+            let claimant = claimant?;
+            campaign_rows.push(CampaignCsvRow {
+                cohort: cohort.name.clone(),
+                claimant: claimant.claimant,
+                entitlements: claimant.entitlements,
+            });
+        }
+    }
+
+    let cohorts_rows = manifest.cohorts_rows();
+    Ok(LoadedManifest {
+        manifest,
+        inputs_hash,
+        campaign_rows,
+        cohorts_rows,
+    })
+}
+
+impl LoadedManifest {
+    /// Manifest hash recorded in the database: the inputs hash followed by the mint
+    /// decimals, which change every on-chain amount even when looked up rather than declared
+    pub fn hash(&self, mint_decimals: u8) -> [u8; 32] {
+        manifest_hash(&self.inputs_hash, mint_decimals)
+    }
+}
+
+impl ManifestInputs {
+    /// Same as [`LoadedManifest::hash`]
+    pub fn hash(&self, mint_decimals: u8) -> [u8; 32] {
+        manifest_hash(&self.inputs_hash, mint_decimals)
+    }
+}
+
+fn manifest_hash(inputs_hash: &[u8; 32], mint_decimals: u8) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(inputs_hash);
+    hasher.update([mint_decimals]);
+    hasher.finalize().into()
+}
+
+/// Compile a loaded manifest and record its hash and go-live slot in the database
+pub fn compile_manifest_db(
+    address_finder: AddressFinder,
+    loaded: &LoadedManifest,
+    mint_decimals: u8,
+) -> CompilerResult<CampaignDatabase> {
+    let manifest = &loaded.manifest;
+    let mut db = compile_campaign_db_with_options(
+        address_finder,
+        &loaded.campaign_rows,
+        &loaded.cohorts_rows,
+        &manifest.settings(mint_decimals),
+        &manifest.compile_options(),
+    )?;
+
+    db.update_campaign_manifest(&loaded.hash(mint_decimals), manifest.go_live_slot)
+        .map_err(|e| CompilerError::InvalidConfig(format!("Failed to record manifest: {}", e)))?;
+
+    Ok(db)
+}
+
+/// Compile a manifest into a database at `output` with the streaming compiler, recording
+/// its hash and go-live slot
+///
+/// Claimant files are streamed cohort by cohort, so memory stays bounded as in
+/// [`compile_campaign_streaming`](crate::compile_campaign_streaming). The manifest's
+/// settings replace `options.compile`; the result matches [`compile_manifest_db`].
+pub fn compile_manifest_streaming(
+    address_finder: AddressFinder,
+    inputs: &ManifestInputs,
+    mint_decimals: u8,
+    output: &Path,
+    options: &StreamingOptions,
+    progress: impl FnMut(CompileProgress),
+) -> CompilerResult<StreamingCompileSummary> {
+    let manifest = &inputs.manifest;
+    let cohorts_rows = manifest.cohorts_rows();
+
+    let mut cohort_rows = Vec::with_capacity(manifest.cohorts.len());
+    for (cohort, claimants_path) in manifest.cohorts.iter().zip(&inputs.claimant_files) {
+        cohort_rows.push(stream_claimant_file(claimants_path)?.map(move |claimant| {
+            claimant.map(|claimant| CampaignCsvRow {
+                cohort: cohort.name.clone(),
+                claimant: claimant.claimant,
+                entitlements: claimant.entitlements,
+            })
+        }));
+    }
+
+    let options = StreamingOptions {
+        compile: manifest.compile_options(),
+        ..options.clone()
+    };
+    let campaign = StreamedCampaign {
+        cohorts_rows: &cohorts_rows,
+        settings: manifest.settings(mint_decimals),
+        manifest: Some((inputs.hash(mint_decimals), manifest.go_live_slot)),
+    };
+    compile_rows_streaming(
+        address_finder,
+        cohort_rows.into_iter().flatten(),
+        campaign,
+        output,
+        &options,
+        progress,
+    )
+}
+
+/// Stream a claimant file's rows, checking its headers
+fn stream_claimant_file(
+    path: &Path,
+) -> CompilerResult<impl Iterator<Item = CompilerResult<ClaimantInput>>> {
+    let mut rdr = csv::Reader::from_reader(BufReader::new(File::open(path)?));
+    let display = path.display().to_string();
+    let invalid = move |e: csv::Error| {
+        CompilerError::Manifest(format!("Invalid claimant file {}: {}", display, e))
+    };
+
+    let headers = rdr.headers().map_err(&invalid)?;
+    if !headers.iter().eq(CLAIMANT_FILE_HEADERS.iter().copied()) {
+        return Err(CompilerError::Manifest(format!(
+            "Claimant file {} must have headers {:?}, found {:?}",
+            path.display(),
+            CLAIMANT_FILE_HEADERS,
+            headers.iter().collect::<Vec<_>>()
+        )));
+    }
+
+    Ok(rdr
+        .into_deserialize()
+        .map(move |claimant| claimant.map_err(&invalid)))
+}
+
+fn default_claimants_per_vault() -> usize {
+    200_000
+}
+
+/// Deserialize any `FromStr` type from a string
+fn from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let s = String::deserialize(deserializer)?;
+    T::from_str(&s).map_err(de::Error::custom)
+}
+
+/// Deserialize a decimal from a string or a number (`60`, `"60.5"`)
+fn decimal<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct DecimalVisitor;
+
+    impl de::Visitor<'_> for DecimalVisitor {
+        type Value = Decimal;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a decimal number or string")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Decimal, E> {
+            Decimal::from_str(v).map_err(E::custom)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Decimal, E> {
+            Ok(Decimal::from(v))
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Decimal, E> {
+            Ok(Decimal::from(v))
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Decimal, E> {
+            // Round-trip through the shortest representation so 0.1 stays 0.1
+            Decimal::from_str(&v.to_string()).map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_any(DecimalVisitor)
+}
+
+/// Deserialize an optional decimal, accepting an explicit null
+fn optional_decimal<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Field(#[serde(deserialize_with = "decimal")] Decimal);
+
+    Option::<Field>::deserialize(deserializer).map(|field| field.map(|Field(value)| value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prism_protocol_db::{CohortTree, HashScheme};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn write_claimants(dir: &Path, name: &str, claimants: &[(Pubkey, u64)]) {
+        let mut contents = String::from("claimant,entitlements\n");
+        for (claimant, entitlements) in claimants {
+            contents.push_str(&format!("{},{}\n", claimant, entitlements));
+        }
+        std::fs::write(dir.join(name), contents).unwrap();
+    }
+
+    fn yaml_manifest(mint: &Pubkey, admin: &Pubkey) -> String {
+        format!(
+            r#"
+name: test
+mint: {mint}
+admin: {admin}
+budget: 1000.5
+mint_decimals: 6
+claimants_per_vault: 2
+dust_policy: first-leaves
+leaf_order: claimant
+go_live_slot: 42
+cohorts:
+  - name: early
+    claimants_file: early.csv
+    share_percentage: 100
+  - name: partners
+    claimants_file: partners.csv
+    allocation_mode: cohort_budget
+    allocation_value: "200"
+    min_per_claimant:
+    max_per_claimant: 150
+"#
+        )
+    }
+
+    #[test]
+    fn test_yaml_and_toml_manifests_agree() {
+        let mint = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let yaml =
+            CampaignManifest::parse(Path::new("m.yaml"), &yaml_manifest(&mint, &admin)).unwrap();
+
+        let toml = format!(
+            r#"
+name = "test"
+mint = "{mint}"
+admin = "{admin}"
+budget = 1000.5
+mint_decimals = 6
+claimants_per_vault = 2
+dust_policy = "first-leaves"
+leaf_order = "claimant"
+go_live_slot = 42
+
+[[cohorts]]
+name = "early"
+claimants_file = "early.csv"
+share_percentage = 100
+
+[[cohorts]]
+name = "partners"
+claimants_file = "partners.csv"
+allocation_mode = "cohort_budget"
+allocation_value = "200"
+max_per_claimant = 150
+"#
+        );
+        let toml = CampaignManifest::parse(Path::new("m.toml"), &toml).unwrap();
+
+        assert_eq!(yaml, toml);
+        assert_eq!(yaml.budget, Decimal::from_str("1000.5").unwrap());
+        assert_eq!(yaml.dust_policy, DustPolicy::FirstLeaves);
+        assert_eq!(yaml.tree_version, TreeVersion::V0);
+        assert_eq!(yaml.leaf_order, LeafOrder::Claimant);
+        assert_eq!(yaml.hash_scheme, ClaimHashScheme::Sha256);
+        assert_eq!(yaml.cohorts[1].allocation_value, Some(Decimal::from(200)));
+        assert_eq!(yaml.cohorts[0].max_per_claimant, None);
+
+        assert!(CampaignManifest::parse(Path::new("m.json"), "{}").is_err());
+        assert!(CampaignManifest::parse(
+            Path::new("m.yaml"),
+            &yaml_manifest(&mint, &admin).replace("go_live_slot", "go_live")
+        )
+        .is_err());
+
+        let blake = yaml_manifest(&mint, &admin).replacen("name:", "hash_scheme: blake3\nname:", 1);
+        assert!(CampaignManifest::parse(Path::new("m.yaml"), &blake).is_err());
+    }
+
+    #[test]
+    fn test_v1_manifest_settings() {
+        let mint = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let parse = |settings: &str| {
+            let yaml =
+                yaml_manifest(&mint, &admin).replacen("name:", &format!("{}\nname:", settings), 1);
+            CampaignManifest::parse(Path::new("m.yaml"), &yaml).unwrap()
+        };
+
+        let v1 = parse("tree_version: v1\ntree_arity: 4\nhash_scheme: keccak256");
+        v1.validate().unwrap();
+        let options = v1.compile_options();
+        assert_eq!(options.hash_scheme, ClaimHashScheme::Keccak256);
+        assert_eq!(
+            options.tree_arities,
+            HashMap::from([("early".to_string(), 4), ("partners".to_string(), 4)])
+        );
+        assert!(parse("").compile_options().tree_arities.is_empty());
+
+        for (settings, error) in [
+            ("tree_version: v1", "needs a tree_arity"),
+            ("tree_arity: 4", "only to tree_version v1"),
+            ("hash_scheme: keccak256", "needs tree_version v1"),
+        ] {
+            let err = parse(settings).validate().unwrap_err();
+            assert!(err.to_string().contains(error), "{}: {}", settings, err);
+        }
+    }
+
+    #[test]
+    fn test_compile_manifest_records_hash() {
+        let dir = TempDir::new().unwrap();
+        let mint = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let early: Vec<(Pubkey, u64)> = (1..=3).map(|i| (Pubkey::new_unique(), i)).collect();
+        let partners = vec![(Pubkey::new_unique(), 100), (Pubkey::new_unique(), 300)];
+
+        let manifest_path = dir.path().join("campaign.yaml");
+        std::fs::write(&manifest_path, yaml_manifest(&mint, &admin)).unwrap();
+        write_claimants(dir.path(), "early.csv", &early);
+        write_claimants(dir.path(), "partners.csv", &partners);
+
+        let loaded = load_manifest(&manifest_path).unwrap();
+        assert_eq!(loaded.campaign_rows.len(), 5);
+        assert_eq!(loaded.cohorts_rows[1].cohort, "partners");
+
+        let db = compile_manifest_db(AddressFinder::default(), &loaded, 6).unwrap();
+        assert_eq!(
+            db.read_campaign_manifest_hash().unwrap(),
+            Some(loaded.hash(6))
+        );
+        assert_eq!(db.read_campaign_go_live_slot().unwrap(), Some(42));
+        assert_eq!(db.read_campaign_info().unwrap().admin, admin);
+        assert_eq!(db.read_cohorts().unwrap().len(), 2);
+
+        // The streaming compiler produces the same campaign and records the same hash
+        let streamed_path = dir.path().join("streamed.db");
+        let opened = open_manifest(&manifest_path).unwrap();
+        assert_eq!(opened.inputs_hash, loaded.inputs_hash);
+        let summary = compile_manifest_streaming(
+            AddressFinder::default(),
+            &opened,
+            6,
+            &streamed_path,
+            &StreamingOptions::default(),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(
+            summary.fingerprint,
+            db.read_campaign_info().unwrap().fingerprint
+        );
+        let streamed = CampaignDatabase::open(&streamed_path).unwrap();
+        assert_eq!(
+            streamed.read_campaign_manifest_hash().unwrap(),
+            Some(loaded.hash(6))
+        );
+        assert_eq!(streamed.read_campaign_go_live_slot().unwrap(), Some(42));
+
+        // Same inputs, same hash and fingerprint
+        let reloaded = load_manifest(&manifest_path).unwrap();
+        assert_eq!(reloaded.hash(6), loaded.hash(6));
+        let recompiled = compile_manifest_db(AddressFinder::default(), &reloaded, 6).unwrap();
+        assert_eq!(
+            recompiled.read_campaign_info().unwrap().fingerprint,
+            db.read_campaign_info().unwrap().fingerprint
+        );
+
+        // Looked-up decimals are part of the hash
+        assert_ne!(loaded.hash(9), loaded.hash(6));
+
+        // V1 manifests compile every cohort into a tree of their arity and scheme
+        let v1 = yaml_manifest(&mint, &admin).replacen(
+            "name:",
+            "tree_version: v1\ntree_arity: 3\nhash_scheme: keccak256\nname:",
+            1,
+        );
+        std::fs::write(&manifest_path, v1).unwrap();
+        let loaded_v1 = load_manifest(&manifest_path).unwrap();
+        assert_ne!(loaded_v1.hash(6), loaded.hash(6));
+        let db_v1 = compile_manifest_db(AddressFinder::default(), &loaded_v1, 6).unwrap();
+        for cohort in db_v1.read_cohorts().unwrap() {
+            assert_eq!(
+                cohort.tree,
+                CohortTree::V1 {
+                    arity: 3,
+                    hash_scheme: HashScheme::Keccak256
+                }
+            );
+        }
+        std::fs::write(&manifest_path, yaml_manifest(&mint, &admin)).unwrap();
+
+        // Editing a claimant file changes the hash
+        write_claimants(dir.path(), "partners.csv", &partners[..1]);
+        assert_ne!(
+            load_manifest(&manifest_path).unwrap().hash(6),
+            loaded.hash(6)
+        );
+    }
+
+    #[test]
+    fn test_manifest_rejects_bad_claimant_file() {
+        let dir = TempDir::new().unwrap();
+        let manifest_path = dir.path().join("campaign.yaml");
+        std::fs::write(
+            &manifest_path,
+            yaml_manifest(&Pubkey::new_unique(), &Pubkey::new_unique()),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("early.csv"), "wallet,amount\n").unwrap();
+        write_claimants(dir.path(), "partners.csv", &[(Pubkey::new_unique(), 1)]);
+
+        assert!(matches!(
+            load_manifest(&manifest_path),
+            Err(CompilerError::Manifest(msg)) if msg.contains("headers")
+        ));
+        let opened = open_manifest(&manifest_path).unwrap();
+        assert!(matches!(
+            compile_manifest_streaming(
+                AddressFinder::default(),
+                &opened,
+                6,
+                &dir.path().join("streamed.db"),
+                &StreamingOptions::default(),
+                |_| {},
+            ),
+            Err(CompilerError::Manifest(msg)) if msg.contains("headers")
+        ));
+    }
+}
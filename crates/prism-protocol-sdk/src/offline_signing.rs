@@ -0,0 +1,291 @@
+/*!
+# Offline Signing
+
+Durable nonce transactions for keys that live on air-gapped machines.
+
+A regular transaction expires ~150 slots after its recent blockhash, which rules out
+signing on one machine and broadcasting from another. A durable nonce account stores a
+blockhash that stays valid until the nonce is advanced, so a transaction that:
+
+1. uses the stored nonce value as its recent blockhash, and
+2. starts with an `AdvanceNonceAccount` instruction signed by the nonce authority
+
+can be signed without any RPC access and submitted whenever convenient. Each nonce
+account backs one outstanding transaction: broadcasting advances the nonce, which
+invalidates any other transaction signed against the same value.
+
+Nothing here touches the network: the compute budget can't be sized by simulation, so
+the runtime default limit applies and the priority fee must be fixed (or none). Lookup
+tables can't be fetched either; callers pass the table contents they expect on-chain
+(e.g. from [`campaign_lookup_table_addresses`](crate::campaign_lookup_table_addresses))
+to get a v0 transaction. Signed transactions travel as base64 wire-format strings ([`encode_signed_transaction`]).
+*/
+
+use crate::compute_budget::{with_compute_budget, ComputeBudgetConfig};
+use crate::lookup_table::compile_message;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::{
+    hash::Hash, instruction::Instruction, message::AddressLookupTableAccount, pubkey::Pubkey,
+    signers::Signers, system_instruction, transaction::VersionedTransaction,
+};
+use thiserror::Error;
+
+/// Errors from offline signing and signed transaction (de)serialization
+#[derive(Error, Debug)]
+pub enum OfflineSigningError {
+    #[error("Priority fee '{0}' needs RPC fee samples; use none or a fixed micro-lamport price when signing offline")]
+    PriorityFeeNeedsRpc(String),
+
+    #[error("Failed to sign transaction: {0}")]
+    Signing(String),
+
+    #[error("Invalid signed transaction: {0}")]
+    InvalidTransaction(String),
+}
+
+/// A durable nonce account and the value it currently stores
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurableNonce {
+    /// The nonce account
+    pub account: Pubkey,
+
+    /// Authority allowed to advance the nonce (must sign the transaction)
+    pub authority: Pubkey,
+
+    /// The stored nonce value, used as the transaction's recent blockhash
+    pub blockhash: Hash,
+}
+
+impl DurableNonce {
+    /// The `AdvanceNonceAccount` instruction that must lead the transaction
+    pub fn advance_instruction(&self) -> Instruction {
+        system_instruction::advance_nonce_account(&self.account, &self.authority)
+    }
+
+    /// `instructions` with the nonce advance prepended (replacing any existing one)
+    pub fn with_advance(&self, instructions: &[Instruction]) -> Vec<Instruction> {
+        let advance = self.advance_instruction();
+        let mut advanced = Vec::with_capacity(instructions.len() + 1);
+        advanced.push(advance.clone());
+        advanced.extend(instructions.iter().filter(|ix| **ix != advance).cloned());
+        advanced
+    }
+}
+
+/// Build and sign a durable nonce transaction without RPC access
+///
+/// The compute budget only carries the priority fee (fixed or none); the compute unit
+/// limit is left at the runtime default since there is nothing to simulate against.
+/// With `lookup_tables` the transaction is v0, otherwise legacy.
+pub fn sign_with_nonce<T: Signers + ?Sized>(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &T,
+    nonce: &DurableNonce,
+    lookup_tables: &[AddressLookupTableAccount],
+    config: &ComputeBudgetConfig,
+) -> Result<VersionedTransaction, OfflineSigningError> {
+    if config.priority_fee.needs_samples() {
+        return Err(OfflineSigningError::PriorityFeeNeedsRpc(
+            config.priority_fee.to_string(),
+        ));
+    }
+
+    let budgeted = with_compute_budget(instructions, None, config.priority_fee.resolve(&[]));
+    let message = compile_message(
+        payer,
+        &nonce.with_advance(&budgeted),
+        lookup_tables,
+        nonce.blockhash,
+    )
+    .map_err(|e| OfflineSigningError::Signing(e.to_string()))?;
+
+    VersionedTransaction::try_new(message, signers)
+        .map_err(|e| OfflineSigningError::Signing(e.to_string()))
+}
+
+/// Base64 wire-format encoding of a signed transaction
+pub fn encode_signed_transaction(
+    transaction: &VersionedTransaction,
+) -> Result<String, OfflineSigningError> {
+    let bytes = bincode::serde::encode_to_vec(transaction, bincode::config::legacy())
+        .map_err(|e| OfflineSigningError::InvalidTransaction(e.to_string()))?;
+    Ok(STANDARD.encode(bytes))
+}
+
+/// Decode a transaction produced by [`encode_signed_transaction`] and check its signatures
+pub fn decode_signed_transaction(
+    encoded: &str,
+) -> Result<VersionedTransaction, OfflineSigningError> {
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| OfflineSigningError::InvalidTransaction(e.to_string()))?;
+    let (transaction, read): (VersionedTransaction, usize) =
+        bincode::serde::decode_from_slice(&bytes, bincode::config::legacy())
+            .map_err(|e| OfflineSigningError::InvalidTransaction(e.to_string()))?;
+    if read != bytes.len() {
+        return Err(OfflineSigningError::InvalidTransaction(format!(
+            "{} trailing bytes",
+            bytes.len() - read
+        )));
+    }
+
+    if !transaction.verify_with_results().into_iter().all(|ok| ok) {
+        return Err(OfflineSigningError::InvalidTransaction(
+            "missing or invalid signature".to_string(),
+        ));
+    }
+    Ok(transaction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_budget::PriorityFee, lookup_table::transaction_size};
+    use solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        message::VersionedMessage,
+        signature::{Keypair, Signer},
+        system_program,
+    };
+
+    fn nonce(authority: &Keypair) -> DurableNonce {
+        DurableNonce {
+            account: Pubkey::new_unique(),
+            authority: authority.pubkey(),
+            blockhash: Hash::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_sign_with_nonce_leads_with_advance() {
+        let payer = Keypair::new();
+        let nonce = nonce(&payer);
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+
+        let config = ComputeBudgetConfig::with_priority_fee(PriorityFee::Fixed(1_000));
+        let transaction = sign_with_nonce(
+            std::slice::from_ref(&transfer),
+            &payer.pubkey(),
+            &[&payer],
+            &nonce,
+            &[],
+            &config,
+        )
+        .unwrap();
+
+        let message = &transaction.message;
+        assert_eq!(*message.recent_blockhash(), nonce.blockhash);
+
+        let keys = message.static_account_keys();
+        let programs: Vec<Pubkey> = message
+            .instructions()
+            .iter()
+            .map(|ix| keys[ix.program_id_index as usize])
+            .collect();
+        assert_eq!(programs[0], system_program::id());
+        assert_eq!(
+            message.instructions()[0].data,
+            nonce.advance_instruction().data
+        );
+        assert_eq!(
+            message.instructions()[1].data,
+            ComputeBudgetInstruction::set_compute_unit_price(1_000).data
+        );
+        assert_eq!(message.instructions()[2].data, transfer.data);
+        assert!(transaction.verify_with_results().into_iter().all(|ok| ok));
+    }
+
+    #[test]
+    fn test_sign_with_nonce_compiles_against_lookup_tables() {
+        let payer = Keypair::new();
+        let nonce = nonce(&payer);
+        let recipients: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let transfers: Vec<Instruction> = recipients
+            .iter()
+            .map(|recipient| system_instruction::transfer(&payer.pubkey(), recipient, 1))
+            .collect();
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: recipients.clone(),
+        };
+
+        let config = ComputeBudgetConfig::with_priority_fee(PriorityFee::None);
+        let transaction = sign_with_nonce(
+            &transfers,
+            &payer.pubkey(),
+            &[&payer],
+            &nonce,
+            std::slice::from_ref(&table),
+            &config,
+        )
+        .unwrap();
+
+        let VersionedMessage::V0(message) = &transaction.message else {
+            panic!("expected a v0 message");
+        };
+        assert_eq!(message.recent_blockhash, nonce.blockhash);
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert_eq!(
+            message.address_table_lookups[0].writable_indexes,
+            vec![0, 1, 2, 3]
+        );
+        // The nonce advance still leads, with the nonce account inline
+        assert_eq!(
+            message.instructions[0].data,
+            nonce.advance_instruction().data
+        );
+        assert!(message.account_keys.contains(&nonce.account));
+        assert!(transaction.verify_with_results().into_iter().all(|ok| ok));
+    }
+
+    #[test]
+    fn test_sign_with_nonce_rejects_estimated_fee() {
+        let payer = Keypair::new();
+        let result = sign_with_nonce(
+            &[],
+            &payer.pubkey(),
+            &[&payer],
+            &nonce(&payer),
+            &[],
+            &ComputeBudgetConfig::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(OfflineSigningError::PriorityFeeNeedsRpc(_))
+        ));
+    }
+
+    #[test]
+    fn test_encoding_roundtrip() {
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let config = ComputeBudgetConfig::with_priority_fee(PriorityFee::None);
+
+        // A separate nonce authority co-signs
+        let transaction = sign_with_nonce(
+            &[transfer],
+            &payer.pubkey(),
+            &[&payer, &authority],
+            &nonce(&authority),
+            &[],
+            &config,
+        )
+        .unwrap();
+
+        let encoded = encode_signed_transaction(&transaction).unwrap();
+        assert_eq!(
+            STANDARD.decode(&encoded).unwrap().len(),
+            transaction_size(&transaction.message)
+        );
+        assert_eq!(decode_signed_transaction(&encoded).unwrap(), transaction);
+
+        // Tampering invalidates the signatures
+        let mut tampered = transaction.clone();
+        tampered.signatures.swap(0, 1);
+        let encoded = encode_signed_transaction(&tampered).unwrap();
+        assert!(decode_signed_transaction(&encoded).is_err());
+        assert!(decode_signed_transaction("not base64!").is_err());
+    }
+}
@@ -0,0 +1,575 @@
+/*!
+# Proof Export
+
+Writes a compiled campaign's claim proofs as static JSON for claim sites that can't
+query SQLite.
+
+Each claimant gets one bundle holding every cohort they can claim from (leaf, proof,
+cohort root, vault and amounts). Bundles are sharded by the leading hex digits of the
+claimant pubkey bytes, so directory names stay case-insensitive-filesystem safe:
+
+```text
+out/
+  manifest.json                 campaign, cohorts and one entry per shard
+  shards/3f/index.json          claimant -> bundle path and SHA-256
+  shards/3f/<claimant>.json     claimant bundle
+```
+
+A browser client fetches `manifest.json` (publish its hash out of band), then its
+shard index, then its own bundle, checking each file against the SHA-256 recorded by
+the previous one, and finally verifies the proof against the cohort root on-chain.
+Amounts are decimal strings because u64 values don't fit a JavaScript number.
+*/
+
+use crate::AddressFinder;
+use prism_protocol::{ClaimLeaf, ClaimProofV0};
+use prism_protocol_db::{CampaignDatabase, ClaimantRecord, DbError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Version of the manifest and bundle formats
+pub const PROOF_EXPORT_VERSION: u32 = 1;
+
+/// File name of the top-level manifest
+pub const PROOF_MANIFEST_FILE: &str = "manifest.json";
+
+/// Directory holding the shard directories
+const SHARDS_DIR: &str = "shards";
+
+/// File name of each shard's index
+const SHARD_INDEX_FILE: &str = "index.json";
+
+#[derive(Error, Debug)]
+pub enum ProofExportError {
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid export options: {0}")]
+    InvalidOptions(String),
+
+    #[error("Invalid bundle: {0}")]
+    InvalidBundle(String),
+}
+
+pub type ProofExportResult<T> = Result<T, ProofExportError>;
+
+/// Export settings
+#[derive(Debug, Clone)]
+pub struct ProofExportOptions {
+    /// Hex digits of the claimant pubkey used as the shard directory name (1-4)
+    pub shard_prefix_len: usize,
+    /// Replace a previous export in the output directory
+    pub overwrite: bool,
+}
+
+impl Default for ProofExportOptions {
+    fn default() -> Self {
+        Self {
+            shard_prefix_len: 2,
+            overwrite: false,
+        }
+    }
+}
+
+/// Top-level export manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofManifest {
+    pub version: u32,
+    pub campaign: String,
+    pub campaign_fingerprint: String,
+    pub mint: String,
+    pub admin: String,
+    pub shard_prefix_len: usize,
+    pub claimant_count: usize,
+    pub cohorts: Vec<ManifestCohort>,
+    /// Shard prefix -> shard index location
+    pub shards: BTreeMap<String, ShardEntry>,
+}
+
+/// Cohort summary in the manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestCohort {
+    pub name: String,
+    pub address: String,
+    pub merkle_root: String,
+    pub amount_per_entitlement: String,
+}
+
+/// Location and hash of one shard index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShardEntry {
+    pub index: String,
+    pub sha256: String,
+    pub claimants: usize,
+}
+
+/// Location and hash of one claimant bundle (shard index value)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleEntry {
+    pub path: String,
+    pub sha256: String,
+}
+
+/// Everything one claimant needs to claim
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimantBundle {
+    pub version: u32,
+    pub campaign: String,
+    pub claimant: String,
+    pub claims: Vec<BundleClaim>,
+}
+
+/// A claimant's leaf and proof in one cohort
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleClaim {
+    pub cohort: String,
+    pub cohort_address: String,
+    pub merkle_root: String,
+    pub leaf_hash: String,
+    pub proof: Vec<String>,
+    pub assigned_vault_index: u8,
+    pub assigned_vault: String,
+    pub entitlements: String,
+    pub amount_per_entitlement: String,
+    pub total_amount: String,
+}
+
+/// Result of an export
+#[derive(Debug, Clone)]
+pub struct ProofExportSummary {
+    pub claimants: usize,
+    pub shards: usize,
+    pub manifest_sha256: [u8; 32],
+}
+
+/// Cohort data shared by every bundle claim of that cohort
+struct CohortContext {
+    address: Pubkey,
+    merkle_root: [u8; 32],
+    amount_per_entitlement: u64,
+}
+
+/// Write per-claimant proof bundles, shard indexes and the manifest to `out_dir`
+///
+/// Bundles are written while streaming claimants from the database; only the shard
+/// indexes (one small entry per claimant) are held in memory.
+pub fn export_proofs(
+    address_finder: &AddressFinder,
+    db: &CampaignDatabase,
+    out_dir: &Path,
+    options: &ProofExportOptions,
+) -> ProofExportResult<ProofExportSummary> {
+    if !(1..=4).contains(&options.shard_prefix_len) {
+        return Err(ProofExportError::InvalidOptions(format!(
+            "shard prefix length must be between 1 and 4, got {}",
+            options.shard_prefix_len
+        )));
+    }
+    prepare_out_dir(out_dir, options.overwrite)?;
+
+    let campaign_info = db.read_campaign_info()?;
+    let (campaign, _) =
+        address_finder.find_campaign_v0_address(&campaign_info.admin, &campaign_info.fingerprint);
+
+    let cohorts = db.read_cohorts()?;
+    let mut contexts = HashMap::with_capacity(cohorts.len());
+    let mut manifest_cohorts = Vec::with_capacity(cohorts.len());
+    for cohort in &cohorts {
+        let (address, _) = address_finder.find_cohort_v0_address(&campaign, &cohort.merkle_root);
+        manifest_cohorts.push(ManifestCohort {
+            name: cohort.name.clone(),
+            address: address.to_string(),
+            merkle_root: hex::encode(cohort.merkle_root),
+            amount_per_entitlement: cohort.amount_per_entitlement.to_string(),
+        });
+        contexts.insert(
+            cohort.name.clone(),
+            CohortContext {
+                address,
+                merkle_root: cohort.merkle_root,
+                amount_per_entitlement: cohort.amount_per_entitlement,
+            },
+        );
+    }
+
+    let mut shard_indexes: BTreeMap<String, BTreeMap<String, BundleEntry>> = BTreeMap::new();
+    let mut pending: Option<ClaimantBundle> = None;
+
+    let mut flush = |bundle: ClaimantBundle| -> ProofExportResult<()> {
+        let claimant = Pubkey::from_str(&bundle.claimant)
+            .map_err(|e| ProofExportError::InvalidBundle(e.to_string()))?;
+        let shard = shard_prefix(&claimant, options.shard_prefix_len);
+        let path = format!("{}/{}/{}.json", SHARDS_DIR, shard, bundle.claimant);
+        let sha256 = write_json(&out_dir.join(&path), &bundle)?;
+
+        shard_indexes.entry(shard).or_default().insert(
+            bundle.claimant,
+            BundleEntry {
+                path,
+                sha256: hex::encode(sha256),
+            },
+        );
+        Ok(())
+    };
+
+    db.for_each_claimant_record(|cohort_name, record| {
+        let context = contexts.get(cohort_name).ok_or_else(|| {
+            ProofExportError::InvalidBundle(format!("Claimant in unknown cohort {}", cohort_name))
+        })?;
+        let claim = bundle_claim(cohort_name, context, &record)?;
+        let claimant = record.claimant.to_string();
+
+        match pending.as_mut() {
+            Some(bundle) if bundle.claimant == claimant => bundle.claims.push(claim),
+            _ => {
+                if let Some(bundle) = pending.replace(ClaimantBundle {
+                    version: PROOF_EXPORT_VERSION,
+                    campaign: campaign.to_string(),
+                    claimant,
+                    claims: vec![claim],
+                }) {
+                    flush(bundle)?;
+                }
+            }
+        }
+        Ok::<_, ProofExportError>(())
+    })?;
+    if let Some(bundle) = pending.take() {
+        flush(bundle)?;
+    }
+
+    let mut shards = BTreeMap::new();
+    let mut claimant_count = 0;
+    for (shard, index) in &shard_indexes {
+        let path = format!("{}/{}/{}", SHARDS_DIR, shard, SHARD_INDEX_FILE);
+        let sha256 = write_json(&out_dir.join(&path), index)?;
+        claimant_count += index.len();
+        shards.insert(
+            shard.clone(),
+            ShardEntry {
+                index: path,
+                sha256: hex::encode(sha256),
+                claimants: index.len(),
+            },
+        );
+    }
+
+    let manifest = ProofManifest {
+        version: PROOF_EXPORT_VERSION,
+        campaign: campaign.to_string(),
+        campaign_fingerprint: hex::encode(campaign_info.fingerprint),
+        mint: campaign_info.mint.to_string(),
+        admin: campaign_info.admin.to_string(),
+        shard_prefix_len: options.shard_prefix_len,
+        claimant_count,
+        cohorts: manifest_cohorts,
+        shards,
+    };
+    let manifest_sha256 = write_json(&out_dir.join(PROOF_MANIFEST_FILE), &manifest)?;
+
+    Ok(ProofExportSummary {
+        claimants: claimant_count,
+        shards: manifest.shards.len(),
+        manifest_sha256,
+    })
+}
+
+/// Shard directory name for a claimant: leading hex digits of its pubkey bytes
+pub fn shard_prefix(claimant: &Pubkey, shard_prefix_len: usize) -> String {
+    let mut prefix = hex::encode(&claimant.to_bytes()[..shard_prefix_len.div_ceil(2)]);
+    prefix.truncate(shard_prefix_len);
+    prefix
+}
+
+/// Check every claim of a bundle: proof against its root and amount arithmetic
+pub fn verify_claimant_bundle(bundle: &ClaimantBundle) -> ProofExportResult<()> {
+    let claimant = Pubkey::from_str(&bundle.claimant)
+        .map_err(|e| ProofExportError::InvalidBundle(format!("Invalid claimant: {}", e)))?;
+
+    for claim in &bundle.claims {
+        let entitlements = parse_amount(&claim.entitlements)?;
+        let amount_per_entitlement = parse_amount(&claim.amount_per_entitlement)?;
+        let total_amount = parse_amount(&claim.total_amount)?;
+        if entitlements.checked_mul(amount_per_entitlement) != Some(total_amount) {
+            return Err(ProofExportError::InvalidBundle(format!(
+                "Amount mismatch in cohort {}",
+                claim.cohort
+            )));
+        }
+
+        let leaf = ClaimLeaf {
+            claimant,
+            assigned_vault_index: claim.assigned_vault_index,
+            entitlements,
+        };
+        if hex::encode(leaf.to_hash()) != claim.leaf_hash {
+            return Err(ProofExportError::InvalidBundle(format!(
+                "Leaf hash mismatch in cohort {}",
+                claim.cohort
+            )));
+        }
+
+        let merkle_root = decode_hash(&claim.merkle_root)?;
+        let proof = claim
+            .proof
+            .iter()
+            .map(|hash| decode_hash(hash))
+            .collect::<ProofExportResult<Vec<_>>>()?;
+        if !ClaimProofV0::new(proof).verify(&merkle_root, &leaf) {
+            return Err(ProofExportError::InvalidBundle(format!(
+                "Proof does not match root of cohort {}",
+                claim.cohort
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn bundle_claim(
+    cohort_name: &str,
+    context: &CohortContext,
+    record: &ClaimantRecord,
+) -> ProofExportResult<BundleClaim> {
+    let proof = record.merkle_proof.as_v0().ok_or_else(|| {
+        ProofExportError::InvalidBundle(format!(
+            "Claimant {} in cohort {} has a {} proof; only V0 proofs can be exported",
+            record.claimant,
+            cohort_name,
+            record.merkle_proof.format_name()
+        ))
+    })?;
+    let leaf = ClaimLeaf {
+        claimant: record.claimant,
+        assigned_vault_index: record.assigned_vault_index,
+        entitlements: record.entitlements,
+    };
+
+    Ok(BundleClaim {
+        cohort: cohort_name.to_string(),
+        cohort_address: context.address.to_string(),
+        merkle_root: hex::encode(context.merkle_root),
+        leaf_hash: hex::encode(leaf.to_hash()),
+        proof: proof.iter().map(hex::encode).collect(),
+        assigned_vault_index: record.assigned_vault_index,
+        assigned_vault: record.assigned_vault_pubkey.to_string(),
+        entitlements: record.entitlements.to_string(),
+        amount_per_entitlement: context.amount_per_entitlement.to_string(),
+        total_amount: (record.entitlements as u128 * context.amount_per_entitlement as u128)
+            .to_string(),
+    })
+}
+
+/// Refuse to mix exports unless overwriting, in which case only export files are removed
+fn prepare_out_dir(out_dir: &Path, overwrite: bool) -> ProofExportResult<()> {
+    let manifest_path = out_dir.join(PROOF_MANIFEST_FILE);
+    let shards_path: PathBuf = out_dir.join(SHARDS_DIR);
+
+    if manifest_path.exists() || shards_path.exists() {
+        if !overwrite {
+            return Err(ProofExportError::InvalidOptions(format!(
+                "{} already contains an export (use overwrite to replace)",
+                out_dir.display()
+            )));
+        }
+        if manifest_path.exists() {
+            fs::remove_file(&manifest_path)?;
+        }
+        if shards_path.exists() {
+            fs::remove_dir_all(&shards_path)?;
+        }
+    }
+
+    fs::create_dir_all(out_dir)?;
+    Ok(())
+}
+
+/// Write compact JSON, creating parent directories, and return its SHA-256
+fn write_json<T: Serialize>(path: &Path, value: &T) -> ProofExportResult<[u8; 32]> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let bytes = serde_json::to_vec(value)?;
+    fs::write(path, &bytes)?;
+    Ok(Sha256::digest(&bytes).into())
+}
+
+fn parse_amount(value: &str) -> ProofExportResult<u64> {
+    value
+        .parse()
+        .map_err(|e| ProofExportError::InvalidBundle(format!("Invalid amount {}: {}", value, e)))
+}
+
+fn decode_hash(value: &str) -> ProofExportResult<[u8; 32]> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ProofExportError::InvalidBundle(format!("Invalid hash {}", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compile_campaign_db_with_options, CampaignSettings, CompileOptions};
+    use prism_protocol_csvs::{AllocationMode, CampaignCsvRow, CohortsCsvRow};
+    use rust_decimal::Decimal;
+
+    fn compiled_campaign(claimants: &[Pubkey]) -> CampaignDatabase {
+        let campaign_rows: Vec<CampaignCsvRow> = claimants
+            .iter()
+            .enumerate()
+            .flat_map(|(i, claimant)| {
+                // Every third claimant is in both cohorts
+                let cohorts: &[&str] = if i % 3 == 0 {
+                    &["Alpha", "Beta"]
+                } else {
+                    &["Alpha"]
+                };
+                cohorts.iter().map(move |cohort| CampaignCsvRow {
+                    cohort: cohort.to_string(),
+                    claimant: *claimant,
+                    entitlements: i as u64 + 1,
+                })
+            })
+            .collect();
+        let cohorts_rows: Vec<CohortsCsvRow> = ["Alpha", "Beta"]
+            .iter()
+            .map(|name| CohortsCsvRow {
+                cohort: name.to_string(),
+                share_percentage: Decimal::from(50),
+                allocation_mode: AllocationMode::SharePercentage,
+                allocation_value: None,
+                min_per_claimant: None,
+                max_per_claimant: None,
+            })
+            .collect();
+
+        compile_campaign_db_with_options(
+            AddressFinder::default(),
+            &campaign_rows,
+            &cohorts_rows,
+            &CampaignSettings {
+                budget: Decimal::from(1000),
+                mint: Pubkey::new_unique(),
+                mint_decimals: 6,
+                admin: Pubkey::new_unique(),
+                claimants_per_vault: 4,
+            },
+            &CompileOptions::default(),
+        )
+        .unwrap()
+    }
+
+    fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> (T, [u8; 32]) {
+        let bytes = fs::read(path).unwrap();
+        (
+            serde_json::from_slice(&bytes).unwrap(),
+            Sha256::digest(&bytes).into(),
+        )
+    }
+
+    #[test]
+    fn test_export_and_verify_every_bundle() {
+        let claimants: Vec<Pubkey> = (0..30).map(|_| Pubkey::new_unique()).collect();
+        let db = compiled_campaign(&claimants);
+        let out_dir = tempfile::tempdir().unwrap();
+
+        let summary = export_proofs(
+            &AddressFinder::default(),
+            &db,
+            out_dir.path(),
+            &ProofExportOptions {
+                shard_prefix_len: 1,
+                overwrite: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(summary.claimants, claimants.len());
+
+        let (manifest, manifest_sha256): (ProofManifest, _) =
+            read_json(&out_dir.path().join(PROOF_MANIFEST_FILE));
+        assert_eq!(manifest_sha256, summary.manifest_sha256);
+        assert_eq!(manifest.claimant_count, claimants.len());
+        assert_eq!(manifest.shards.len(), summary.shards);
+
+        // Follow the hash chain manifest -> shard index -> bundle for every claimant
+        for (i, claimant) in claimants.iter().enumerate() {
+            let shard = &manifest.shards[&shard_prefix(claimant, 1)];
+            let (index, index_sha256): (BTreeMap<String, BundleEntry>, _) =
+                read_json(&out_dir.path().join(&shard.index));
+            assert_eq!(hex::encode(index_sha256), shard.sha256);
+
+            let entry = &index[&claimant.to_string()];
+            let (bundle, bundle_sha256): (ClaimantBundle, _) =
+                read_json(&out_dir.path().join(&entry.path));
+            assert_eq!(hex::encode(bundle_sha256), entry.sha256);
+
+            assert_eq!(bundle.claims.len(), if i % 3 == 0 { 2 } else { 1 });
+            verify_claimant_bundle(&bundle).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_tampered_bundle_rejected() {
+        let claimants: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let db = compiled_campaign(&claimants);
+        let out_dir = tempfile::tempdir().unwrap();
+        export_proofs(
+            &AddressFinder::default(),
+            &db,
+            out_dir.path(),
+            &ProofExportOptions::default(),
+        )
+        .unwrap();
+
+        let shard = shard_prefix(&claimants[1], 2);
+        let (mut bundle, _): (ClaimantBundle, _) = read_json(
+            &out_dir
+                .path()
+                .join(format!("{}/{}/{}.json", SHARDS_DIR, shard, claimants[1])),
+        );
+        verify_claimant_bundle(&bundle).unwrap();
+
+        bundle.claims[0].entitlements = "1000".to_string();
+        assert!(verify_claimant_bundle(&bundle).is_err());
+    }
+
+    #[test]
+    fn test_existing_export_requires_overwrite() {
+        let db = compiled_campaign(&[Pubkey::new_unique()]);
+        let out_dir = tempfile::tempdir().unwrap();
+        let address_finder = AddressFinder::default();
+
+        let mut options = ProofExportOptions::default();
+        export_proofs(&address_finder, &db, out_dir.path(), &options).unwrap();
+        assert!(export_proofs(&address_finder, &db, out_dir.path(), &options).is_err());
+
+        options.overwrite = true;
+        export_proofs(&address_finder, &db, out_dir.path(), &options).unwrap();
+
+        options.shard_prefix_len = 0;
+        assert!(matches!(
+            export_proofs(&address_finder, &db, out_dir.path(), &options),
+            Err(ProofExportError::InvalidOptions(_))
+        ));
+    }
+
+    #[test]
+    fn test_shard_prefix() {
+        let claimant = Pubkey::new_from_array([0xab; 32]);
+        assert_eq!(shard_prefix(&claimant, 1), "a");
+        assert_eq!(shard_prefix(&claimant, 3), "aba");
+    }
+}
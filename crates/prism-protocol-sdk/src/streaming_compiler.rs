@@ -0,0 +1,1274 @@
+/*!
+# Streaming Campaign Compiler
+
+Memory-bounded compilation for campaigns too large for [`compile_campaign`], which
+holds every row, leaf and tree in memory at once.
+
+## Passes
+1. **Sorted runs**: campaign.csv is streamed and spilled to disk in fixed-size binary
+   runs, each sorted by (cohort, row), or by (cohort, claimant) for claimant-ordered
+   leaves
+2. **Merge**: the runs are k-way merged into one file, grouping each cohort's rows
+   in leaf order
+3. **Allocation**: cohorts are allocated exactly as in memory, from entitlements alone
+4. **Roots**: each cohort's leaves are hashed in parallel and its tree (binary V0, or
+   V1 of the cohort's arity and hash scheme) is built from leaf hashes only, yielding
+   the roots and thus the campaign fingerprint. The tree's levels are spilled to disk
+   so no cohort is hashed twice. Claimant-ordered cohorts also get their claimant index
+   root, for non-membership proofs
+5. **Proofs**: once the vault addresses are known, each cohort's tree is read back
+   and its proofs are written to the database in batched transactions
+
+Leaves, roots, proofs and the fingerprint are identical to [`compile_campaign`].
+Peak memory is about 8 bytes per claimant for the campaign's entitlements plus
+about 100 bytes per claimant of the largest cohort (about 50 more if it is
+claimant-ordered), independent of CSV size.
+
+The database is built next to the output path and renamed into place only once
+compilation succeeds, so a failed compile never leaves a partial database behind.
+
+[`compile_campaign`]: crate::compile_campaign
+*/
+
+use crate::budget_allocation::{
+    to_base_units, BudgetAllocator, ClaimantCaps, CohortAllocationRequest,
+};
+use crate::campaign_compiler::{
+    allocation_rule, calculate_campaign_fingerprint, calculate_vault_funding, cohort_dust,
+    dust_report, funding_u64, insert_vaults, vault_claimant_counts, vault_dust, CampaignSettings,
+    CompileOptions, CompilerError, CompilerResult,
+};
+use crate::campaign_layout::{plan_layout, CampaignLayout, LayoutConstraints};
+use crate::AddressFinder;
+use prism_protocol::ClaimLeaf;
+use prism_protocol_csvs::{
+    stream_campaign_csv, validate_allocation_modes, validate_share_percentages, CampaignCsvRow,
+    CohortsCsvRow, CsvError,
+};
+use prism_protocol_db::{CampaignDatabase, ClaimantRecord, CohortTree, HashScheme, StoredProof};
+use prism_protocol_merkle::{
+    claimant_index_root, consistent_hash_vault_assignment, hash_leaves_with_scheme, LeafHashTreeV0,
+    LeafHashTreeV1, LeafOrder,
+};
+use rust_decimal::Decimal;
+use solana_sdk::pubkey::Pubkey;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Bytes per spilled row: cohort (u32), row (u64), claimant (32), entitlements (u64)
+const RECORD_SIZE: usize = 4 + 8 + 32 + 8;
+
+/// Settings for [`compile_campaign_streaming`]
+#[derive(Debug, Clone)]
+pub struct StreamingOptions {
+    /// Settings shared with in-memory compilation
+    pub compile: CompileOptions,
+    /// Rows sorted in memory before spilling a run to disk (52 bytes each)
+    pub sort_chunk_rows: usize,
+    /// Claimant rows written per database transaction
+    pub proof_batch_size: usize,
+    /// Directory for temporary run files (system temp dir by default)
+    pub work_dir: Option<PathBuf>,
+    /// Plan every cohort's tree and vault count from the cohort sizes counted in the
+    /// first pass; the plan replaces `compile`'s vault counts and tree arities
+    pub layout: Option<LayoutConstraints>,
+}
+
+impl Default for StreamingOptions {
+    fn default() -> Self {
+        Self {
+            compile: CompileOptions::default(),
+            sort_chunk_rows: 1_000_000,
+            proof_batch_size: 10_000,
+            work_dir: None,
+            layout: None,
+        }
+    }
+}
+
+/// Progress reported by [`compile_campaign_streaming`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileProgress {
+    /// A sorted run was spilled; `rows` counts all rows read so far
+    RowsRead { rows: u64 },
+    /// Runs were merged into cohort order
+    RunsMerged { runs: usize, rows: u64 },
+    /// A cohort's merkle root was computed
+    CohortHashed { cohort: String, leaves: usize },
+    /// A batch of a cohort's proofs was committed
+    ProofsWritten {
+        cohort: String,
+        written: usize,
+        total: usize,
+    },
+}
+
+/// Outcome of a streaming compilation (everything else is in the database)
+#[derive(Debug, Clone)]
+pub struct StreamingCompileSummary {
+    pub fingerprint: [u8; 32],
+    pub address: Pubkey,
+    pub total_claimants: usize,
+    pub total_vaults: usize,
+    /// Funding of every vault in base units
+    pub total_funding: u64,
+    /// Layout planned from [`StreamingOptions::layout`], if any
+    pub layout: Option<CampaignLayout>,
+}
+
+/// Campaign inputs shared by every streaming entry point
+#[derive(Clone, Copy)]
+pub(crate) struct StreamedCampaign<'a> {
+    pub cohorts_rows: &'a [CohortsCsvRow],
+    pub settings: CampaignSettings,
+    /// Manifest hash and go-live slot to record, for manifest compiles
+    pub manifest: Option<([u8; 32], Option<u64>)>,
+}
+
+/// One campaign.csv row as spilled to disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct SortRecord {
+    cohort: u32,
+    /// Row in campaign.csv, or 0 for claimant-ordered leaves so records sort by claimant
+    row: u64,
+    claimant: [u8; 32],
+    entitlements: u64,
+}
+
+impl SortRecord {
+    fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writer.write_all(&self.cohort.to_le_bytes())?;
+        writer.write_all(&self.row.to_le_bytes())?;
+        writer.write_all(&self.claimant)?;
+        writer.write_all(&self.entitlements.to_le_bytes())
+    }
+
+    fn read_from(reader: &mut impl Read) -> std::io::Result<Option<Self>> {
+        let mut buf = [0u8; RECORD_SIZE];
+        match reader.read_exact(&mut buf) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+
+        Ok(Some(Self {
+            cohort: u32::from_le_bytes(buf[0..4].try_into().unwrap()),
+            row: u64::from_le_bytes(buf[4..12].try_into().unwrap()),
+            claimant: buf[12..44].try_into().unwrap(),
+            entitlements: u64::from_le_bytes(buf[44..52].try_into().unwrap()),
+        }))
+    }
+}
+
+/// A cohort as it moves through the passes
+struct StreamedCohort<'a> {
+    config: &'a CohortsCsvRow,
+    /// Offset of the cohort's first row in the merged file, in records
+    start: u64,
+    claimant_count: usize,
+    vault_count: usize,
+    tree: CohortTree,
+    /// Effective entitlements in leaf order (after caps and dust policy)
+    entitlements: Vec<u64>,
+    amount_per_entitlement: Decimal,
+    amount_per_entitlement_humane: String,
+    cohort_budget: Decimal,
+    allocation_dust: Decimal,
+    redistributed_dust: Decimal,
+    merkle_root: [u8; 32],
+    /// Root of the claimant index, for claimant-ordered cohorts
+    claimant_index_root: Option<[u8; 32]>,
+}
+
+impl StreamedCohort<'_> {
+    fn total_allocated(&self) -> Decimal {
+        let total_entitlements: u128 = self.entitlements.iter().map(|&e| e as u128).sum();
+        Decimal::from(total_entitlements) * self.amount_per_entitlement
+    }
+}
+
+/// A cohort's tree, built from leaf hashes
+enum StreamedTree {
+    V0(LeafHashTreeV0),
+    V1(LeafHashTreeV1),
+}
+
+impl StreamedTree {
+    fn root(&self) -> Option<[u8; 32]> {
+        match self {
+            StreamedTree::V0(tree) => tree.root(),
+            StreamedTree::V1(tree) => tree.root(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            StreamedTree::V0(tree) => tree.len(),
+            StreamedTree::V1(tree) => tree.len(),
+        }
+    }
+
+    fn levels(&self) -> &[Vec<[u8; 32]>] {
+        match self {
+            StreamedTree::V0(tree) => tree.levels(),
+            StreamedTree::V1(tree) => tree.levels(),
+        }
+    }
+
+    /// Proof of the leaf at `index`, in the format stored in the campaign database
+    fn proof(&self, index: usize) -> Option<StoredProof> {
+        match self {
+            StreamedTree::V0(tree) => tree.proof(index).map(StoredProof::V0),
+            StreamedTree::V1(tree) => tree.proof(index).map(StoredProof::V1),
+        }
+    }
+}
+
+/// Compile a campaign from campaign.csv on disk into a database at `output`, streaming
+/// rows through sorted runs in `options.work_dir`
+///
+/// Takes the same inputs as [`compile_campaign_with_options`](crate::compile_campaign_with_options)
+/// and produces the same database contents; `progress` is called as passes advance.
+/// An existing database at `output` is replaced only if compilation succeeds.
+pub fn compile_campaign_streaming(
+    address_finder: AddressFinder,
+    campaign_csv: &Path,
+    cohorts_rows: &[CohortsCsvRow],
+    campaign: &CampaignSettings,
+    output: &Path,
+    options: &StreamingOptions,
+    progress: impl FnMut(CompileProgress),
+) -> CompilerResult<StreamingCompileSummary> {
+    let rows = stream_campaign_csv(campaign_csv)?.map(|row| row.map_err(CompilerError::from));
+    let campaign = StreamedCampaign {
+        cohorts_rows,
+        settings: *campaign,
+        manifest: None,
+    };
+    compile_rows_streaming(address_finder, rows, campaign, output, options, progress)
+}
+
+/// [`compile_campaign_streaming`] over campaign rows from any source
+pub(crate) fn compile_rows_streaming(
+    address_finder: AddressFinder,
+    rows: impl Iterator<Item = CompilerResult<CampaignCsvRow>>,
+    campaign: StreamedCampaign,
+    output: &Path,
+    options: &StreamingOptions,
+    mut progress: impl FnMut(CompileProgress),
+) -> CompilerResult<StreamingCompileSummary> {
+    let StreamedCampaign {
+        cohorts_rows,
+        settings:
+            CampaignSettings {
+                budget,
+                mint,
+                mint_decimals,
+                admin,
+                claimants_per_vault,
+            },
+        manifest,
+    } = campaign;
+
+    if options.sort_chunk_rows == 0 || options.proof_batch_size == 0 {
+        return Err(CompilerError::InvalidConfig(
+            "sort_chunk_rows and proof_batch_size must be positive".to_string(),
+        ));
+    }
+
+    validate_allocation_modes(cohorts_rows)?;
+    validate_share_percentages(cohorts_rows)?;
+
+    let work_dir = match &options.work_dir {
+        Some(dir) => tempfile::Builder::new()
+            .prefix("prism-compile-")
+            .tempdir_in(dir)?,
+        None => tempfile::Builder::new()
+            .prefix("prism-compile-")
+            .tempdir()?,
+    };
+
+    // Stage the database beside the output so the final rename stays on one filesystem
+    let output_dir = match output.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let staging_dir = tempfile::Builder::new()
+        .prefix(".prism-compile-")
+        .tempdir_in(output_dir)?;
+    let staged_path = staging_dir.path().join("campaign.db");
+
+    // Pass 1: spill sorted runs
+    let (runs, row_counts) = write_sorted_runs(
+        rows,
+        cohorts_rows,
+        work_dir.path(),
+        options.sort_chunk_rows,
+        options.compile.leaf_order,
+        &mut progress,
+    )?;
+
+    let layout = options
+        .layout
+        .map(|constraints| {
+            let cohort_sizes: Vec<(String, usize)> = cohorts_rows
+                .iter()
+                .zip(&row_counts)
+                .filter(|(_, &count)| count > 0)
+                .map(|(row, &count)| (row.cohort.clone(), count as usize))
+                .collect();
+            plan_layout(&cohort_sizes, &constraints)
+        })
+        .transpose()
+        .map_err(|e| CompilerError::InvalidConfig(format!("Layout planning failed: {}", e)))?;
+    let compile_options = match &layout {
+        Some(layout) => options.compile.clone().with_layout(layout),
+        None => options.compile.clone(),
+    };
+
+    // Pass 2: merge runs into cohort order
+    let merged_path = work_dir.path().join("merged.bin");
+    let total_rows = merge_runs(&runs, &merged_path)?;
+    for run in &runs {
+        std::fs::remove_file(run)?;
+    }
+    progress(CompileProgress::RunsMerged {
+        runs: runs.len(),
+        rows: total_rows,
+    });
+
+    // Pass 3: allocate budget across cohorts
+    let mut cohorts = allocate_cohorts(
+        &merged_path,
+        cohorts_rows,
+        &row_counts,
+        budget,
+        mint_decimals,
+        claimants_per_vault,
+        &compile_options,
+    )?;
+
+    // Pass 4: merkle roots and campaign fingerprint, keeping each tree on disk for pass 5
+    let mut tree_paths = Vec::with_capacity(cohorts.len());
+    for (index, cohort) in cohorts.iter_mut().enumerate() {
+        let tree = build_cohort_tree(&merged_path, cohort, compile_options.leaf_order)?;
+        cohort.merkle_root = tree
+            .root()
+            .ok_or_else(|| CompilerError::MerkleTree("Failed to get merkle root".to_string()))?;
+
+        let tree_path = work_dir.path().join(format!("cohort-{}.tree", index));
+        write_tree_levels(&tree, &tree_path)?;
+        tree_paths.push(tree_path);
+
+        progress(CompileProgress::CohortHashed {
+            cohort: cohort.config.cohort.clone(),
+            leaves: cohort.claimant_count,
+        });
+    }
+
+    let roots: Vec<[u8; 32]> = cohorts.iter().map(|cohort| cohort.merkle_root).collect();
+    let fingerprint = calculate_campaign_fingerprint(&roots);
+    let (campaign_address, _) = address_finder.find_campaign_v0_address(&admin, &fingerprint);
+
+    let mut db = CampaignDatabase::create_file(&staged_path, true)
+        .map_err(|e| CompilerError::InvalidConfig(format!("Failed to create database: {}", e)))?;
+    db.insert_campaign(fingerprint, mint, mint_decimals, admin, budget)
+        .map_err(|e| CompilerError::InvalidConfig(format!("Failed to insert campaign: {}", e)))?;
+    if let Some((manifest_hash, go_live_slot)) = manifest {
+        db.update_campaign_manifest(&manifest_hash, go_live_slot)
+            .map_err(|e| {
+                CompilerError::InvalidConfig(format!("Failed to record manifest: {}", e))
+            })?;
+    }
+
+    // Pass 5: proofs, cohorts and vaults
+    let mut total_funding = 0u64;
+    let mut cohort_dusts = Vec::with_capacity(cohorts.len());
+    let mut vault_dusts = Vec::new();
+    for (cohort, tree_path) in cohorts.iter().zip(&tree_paths) {
+        let name = &cohort.config.cohort;
+        let (cohort_address, _) =
+            address_finder.find_cohort_v0_address(&campaign_address, &cohort.merkle_root);
+        let allocated = cohort.total_allocated();
+        let vaults = calculate_vault_funding(
+            &address_finder,
+            &cohort_address,
+            cohort.vault_count,
+            allocated,
+            mint_decimals,
+        )?;
+        let cohort_funding = funding_u64(&vaults, name)?;
+        total_funding = total_funding
+            .checked_add(cohort_funding)
+            .ok_or_else(|| CompilerError::InvalidConfig("Campaign funding overflow".to_string()))?;
+
+        db.insert_cohort(
+            name,
+            cohort.merkle_root,
+            to_base_units(cohort.amount_per_entitlement, mint_decimals)?,
+            &cohort.amount_per_entitlement_humane,
+            cohort.vault_count,
+            cohort.claimant_count,
+            cohort_funding,
+            cohort.tree,
+        )
+        .map_err(|e| CompilerError::InvalidConfig(format!("Failed to insert cohort: {}", e)))?;
+        if let Some(root) = &cohort.claimant_index_root {
+            db.update_cohort_claimant_index_root(name, root)
+                .map_err(|e| {
+                    CompilerError::InvalidConfig(format!("Failed to record claimant index: {}", e))
+                })?;
+        }
+
+        let tree = read_tree_levels(tree_path, cohort.tree)?;
+        std::fs::remove_file(tree_path)?;
+        let vault_addresses: Vec<Pubkey> = vaults.iter().map(|vault| vault.address).collect();
+        let assigned_vaults = write_cohort_proofs(
+            &merged_path,
+            cohort,
+            &tree,
+            &vault_addresses,
+            &mut db,
+            options.proof_batch_size,
+            &mut progress,
+        )?;
+        let vault_claimant_counts =
+            vault_claimant_counts(assigned_vaults.into_iter(), cohort.vault_count);
+        insert_vaults(&mut db, name, &vaults, &vault_claimant_counts)?;
+
+        cohort_dusts.push(cohort_dust(
+            name,
+            cohort.cohort_budget,
+            allocated,
+            cohort.allocation_dust,
+            cohort.redistributed_dust,
+            &vaults,
+        ));
+        vault_dusts.extend(vault_dust(name, &vaults));
+    }
+
+    db.insert_dust_report(&dust_report(
+        compile_options.dust_policy,
+        budget,
+        mint_decimals,
+        cohort_dusts,
+        vault_dusts,
+    )?)
+    .map_err(|e| CompilerError::InvalidConfig(format!("Failed to insert dust report: {}", e)))?;
+
+    // Close the connection before moving the finished database into place
+    drop(db);
+    std::fs::rename(&staged_path, output)?;
+
+    Ok(StreamingCompileSummary {
+        fingerprint,
+        address: campaign_address,
+        total_claimants: total_rows as usize,
+        total_vaults: cohorts.iter().map(|cohort| cohort.vault_count).sum(),
+        total_funding,
+        layout,
+    })
+}
+
+/// Stream campaign rows into sorted run files, returning them with per-cohort row counts
+fn write_sorted_runs(
+    rows: impl Iterator<Item = CompilerResult<CampaignCsvRow>>,
+    cohorts_rows: &[CohortsCsvRow],
+    work_dir: &Path,
+    sort_chunk_rows: usize,
+    leaf_order: LeafOrder,
+    progress: &mut impl FnMut(CompileProgress),
+) -> CompilerResult<(Vec<PathBuf>, Vec<u64>)> {
+    let cohort_ids: HashMap<&str, u32> = cohorts_rows
+        .iter()
+        .enumerate()
+        .map(|(id, row)| (row.cohort.as_str(), id as u32))
+        .collect();
+
+    let mut row_counts = vec![0u64; cohorts_rows.len()];
+    let mut runs = Vec::new();
+    let mut chunk = Vec::with_capacity(sort_chunk_rows.min(1 << 20));
+    let mut row_count = 0u64;
+
+    for row in rows {
+        let row = row?;
+        let cohort = *cohort_ids.get(row.cohort.as_str()).ok_or_else(|| {
+            CsvError::DataInconsistency(format!(
+                "Cohort '{}' referenced in campaign.csv but not defined in cohorts.csv",
+                row.cohort
+            ))
+        })?;
+
+        row_counts[cohort as usize] += 1;
+        chunk.push(SortRecord {
+            cohort,
+            row: match leaf_order {
+                LeafOrder::Insertion => row_count,
+                LeafOrder::Claimant => 0,
+            },
+            claimant: row.claimant.to_bytes(),
+            entitlements: row.entitlements,
+        });
+        row_count += 1;
+
+        if chunk.len() == sort_chunk_rows {
+            runs.push(spill_run(&mut chunk, work_dir, runs.len())?);
+            progress(CompileProgress::RowsRead { rows: row_count });
+        }
+    }
+
+    if !chunk.is_empty() {
+        runs.push(spill_run(&mut chunk, work_dir, runs.len())?);
+        progress(CompileProgress::RowsRead { rows: row_count });
+    }
+
+    if row_count == 0 {
+        return Err(CsvError::SchemaValidation("Campaign CSV file is empty".to_string()).into());
+    }
+
+    if let Some((row, _)) = cohorts_rows
+        .iter()
+        .zip(&row_counts)
+        .find(|(_, &count)| count == 0)
+    {
+        return Err(CsvError::DataInconsistency(format!(
+            "Cohort '{}' defined in cohorts.csv but has no claimants in campaign.csv",
+            row.cohort
+        ))
+        .into());
+    }
+
+    Ok((runs, row_counts))
+}
+
+/// Sort a chunk of rows and write it out as run number `index`
+fn spill_run(
+    chunk: &mut Vec<SortRecord>,
+    work_dir: &Path,
+    index: usize,
+) -> CompilerResult<PathBuf> {
+    chunk.sort_unstable();
+
+    let path = work_dir.join(format!("run-{}.bin", index));
+    let mut writer = BufWriter::new(File::create(&path)?);
+    for record in chunk.drain(..) {
+        record.write_to(&mut writer)?;
+    }
+    writer.flush()?;
+
+    Ok(path)
+}
+
+/// K-way merge of sorted runs into `output`, returning the number of rows
+fn merge_runs(runs: &[PathBuf], output: &Path) -> CompilerResult<u64> {
+    let mut readers = runs
+        .iter()
+        .map(|run| File::open(run).map(BufReader::new))
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    let mut heap = BinaryHeap::new();
+    for (run, reader) in readers.iter_mut().enumerate() {
+        if let Some(record) = SortRecord::read_from(reader)? {
+            heap.push(Reverse((record, run)));
+        }
+    }
+
+    let mut writer = BufWriter::new(File::create(output)?);
+    let mut rows = 0;
+    while let Some(Reverse((record, run))) = heap.pop() {
+        record.write_to(&mut writer)?;
+        rows += 1;
+        if let Some(next) = SortRecord::read_from(&mut readers[run])? {
+            heap.push(Reverse((next, run)));
+        }
+    }
+    writer.flush()?;
+
+    Ok(rows)
+}
+
+/// Read `count` records starting at record `start` of the merged file
+fn read_cohort_records(
+    merged_path: &Path,
+    start: u64,
+    count: usize,
+) -> CompilerResult<impl Iterator<Item = CompilerResult<SortRecord>>> {
+    let mut reader = BufReader::new(File::open(merged_path)?);
+    reader.seek(SeekFrom::Start(start * RECORD_SIZE as u64))?;
+
+    Ok((0..count).map(move |_| {
+        SortRecord::read_from(&mut reader)?.ok_or_else(|| {
+            CompilerError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "merged run ended early",
+            ))
+        })
+    }))
+}
+
+/// Allocate the budget across cohorts, mirroring in-memory compilation
+fn allocate_cohorts<'a>(
+    merged_path: &Path,
+    cohorts_rows: &'a [CohortsCsvRow],
+    row_counts: &[u64],
+    budget: Decimal,
+    mint_decimals: u8,
+    claimants_per_vault: usize,
+    options: &CompileOptions,
+) -> CompilerResult<Vec<StreamedCohort<'a>>> {
+    let allocator = BudgetAllocator::new(budget, mint_decimals)?;
+
+    let mut requests = Vec::with_capacity(cohorts_rows.len());
+    let mut start = 0u64;
+    for (config, &count) in cohorts_rows.iter().zip(row_counts) {
+        let mut entitlements = Vec::with_capacity(count as usize);
+        let mut claimants = Vec::with_capacity(count as usize);
+        for record in read_cohort_records(merged_path, start, count as usize)? {
+            let record = record?;
+            entitlements.push(record.entitlements);
+            claimants.push(record.claimant);
+        }
+        start += count;
+
+        // Same failure as building the in-memory tree with a repeated claimant
+        claimants.sort_unstable();
+        if let Some(pair) = claimants.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(CompilerError::MerkleTree(format!(
+                "Failed to create merkle tree: duplicate claimant {} in cohort '{}'",
+                Pubkey::new_from_array(pair[0]),
+                config.cohort
+            )));
+        }
+        drop(claimants);
+
+        if entitlements.iter().all(|&e| e == 0) {
+            return Err(CompilerError::InvalidConfig(format!(
+                "Cohort '{}' has zero total entitlements",
+                config.cohort
+            )));
+        }
+
+        requests.push(CohortAllocationRequest {
+            rule: allocation_rule(config)?,
+            caps: ClaimantCaps {
+                min: config.min_per_claimant,
+                max: config.max_per_claimant,
+            },
+            entitlements,
+        });
+    }
+
+    let max_caps: Vec<Option<Decimal>> = requests.iter().map(|request| request.caps.max).collect();
+    let allocations = allocator.allocate_campaign(requests)?.cohorts;
+
+    let mut cohorts = Vec::with_capacity(cohorts_rows.len());
+    let mut start = 0u64;
+    for (((config, resolved), max_per_claimant), &count) in cohorts_rows
+        .iter()
+        .zip(allocations)
+        .zip(max_caps)
+        .zip(row_counts)
+    {
+        let claimant_count = count as usize;
+        let mut allocation = resolved.allocation;
+
+        // Apply the dust policy before the leaves are committed to a merkle tree
+        let mut claimants = read_cohort_records(merged_path, start, claimant_count)?
+            .zip(resolved.entitlements)
+            .map(|(record, entitlements)| {
+                record.map(|record| (Pubkey::new_from_array(record.claimant), entitlements))
+            })
+            .collect::<CompilerResult<Vec<_>>>()?;
+        let redistributed_dust = options.dust_policy.apply(
+            &mut claimants,
+            &mut allocation,
+            max_per_claimant,
+            mint_decimals,
+        )?;
+        let entitlements = claimants.into_iter().map(|(_, e)| e).collect();
+
+        cohorts.push(StreamedCohort {
+            config,
+            start,
+            claimant_count,
+            vault_count: options.vault_count(
+                &config.cohort,
+                claimant_count,
+                claimants_per_vault,
+            )?,
+            tree: options.cohort_tree(&config.cohort)?,
+            entitlements,
+            amount_per_entitlement: allocation.amount_per_entitlement,
+            amount_per_entitlement_humane: allocation.amount_per_entitlement_humane,
+            cohort_budget: allocation.cohort_total,
+            allocation_dust: allocation.dust_amount - redistributed_dust,
+            redistributed_dust,
+            merkle_root: [0; 32],
+            claimant_index_root: None,
+        });
+        start += claimant_count as u64;
+    }
+
+    Ok(cohorts)
+}
+
+/// Read a cohort's leaves in order, `batch_size` at a time
+fn for_each_leaf_batch(
+    merged_path: &Path,
+    cohort: &StreamedCohort,
+    batch_size: usize,
+    mut f: impl FnMut(&[ClaimLeaf]) -> CompilerResult<()>,
+) -> CompilerResult<()> {
+    let mut batch = Vec::with_capacity(batch_size.min(cohort.claimant_count));
+    let records = read_cohort_records(merged_path, cohort.start, cohort.claimant_count)?;
+    for (record, &entitlements) in records.zip(&cohort.entitlements) {
+        let claimant = Pubkey::new_from_array(record?.claimant);
+        batch.push(ClaimLeaf {
+            claimant,
+            assigned_vault_index: consistent_hash_vault_assignment(&claimant, cohort.vault_count)
+                as u8,
+            entitlements,
+        });
+
+        if batch.len() == batch_size {
+            f(&batch)?;
+            batch.clear();
+        }
+    }
+
+    if !batch.is_empty() {
+        f(&batch)?;
+    }
+
+    Ok(())
+}
+
+/// Build a cohort's merkle tree from its leaf hashes
+fn build_cohort_tree(
+    merged_path: &Path,
+    cohort: &mut StreamedCohort,
+    leaf_order: LeafOrder,
+) -> CompilerResult<StreamedTree> {
+    let hash_scheme = match cohort.tree {
+        CohortTree::V0 => HashScheme::Sha256,
+        CohortTree::V1 { hash_scheme, .. } => hash_scheme,
+    };
+    let indexed = leaf_order == LeafOrder::Claimant;
+    let mut leaf_hashes = Vec::with_capacity(cohort.claimant_count);
+    let mut claimants = Vec::with_capacity(if indexed { cohort.claimant_count } else { 0 });
+    for_each_leaf_batch(merged_path, cohort, 1 << 16, |leaves| {
+        leaf_hashes.extend(hash_leaves_with_scheme(leaves, hash_scheme.into()));
+        if indexed {
+            claimants.extend(leaves.iter().map(|leaf| leaf.claimant));
+        }
+        Ok(())
+    })?;
+
+    if indexed {
+        let root =
+            claimant_index_root(&claimants, &leaf_hashes, hash_scheme.into()).map_err(|e| {
+                CompilerError::MerkleTree(format!(
+                    "Failed to index cohort '{}' claimants: {}",
+                    cohort.config.cohort, e
+                ))
+            })?;
+        cohort.claimant_index_root = Some(root);
+    }
+
+    match cohort.tree {
+        CohortTree::V0 => Ok(StreamedTree::V0(LeafHashTreeV0::from_leaf_hashes(
+            leaf_hashes,
+        ))),
+        CohortTree::V1 { arity, hash_scheme } => {
+            LeafHashTreeV1::from_leaf_hashes(leaf_hashes, arity as usize, hash_scheme.into())
+                .map(StreamedTree::V1)
+                .ok_or_else(|| {
+                    CompilerError::MerkleTree(format!(
+                        "Cohort '{}' tree arity {} is invalid",
+                        cohort.config.cohort, arity
+                    ))
+                })
+        }
+    }
+}
+
+/// Spill a cohort tree's levels to disk: level count, then each level's length and hashes
+fn write_tree_levels(tree: &StreamedTree, path: &Path) -> CompilerResult<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&(tree.levels().len() as u64).to_le_bytes())?;
+    for level in tree.levels() {
+        writer.write_all(&(level.len() as u64).to_le_bytes())?;
+        for hash in level {
+            writer.write_all(hash)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read back a tree of layout `tree` spilled by [`write_tree_levels`]
+fn read_tree_levels(path: &Path, tree: CohortTree) -> CompilerResult<StreamedTree> {
+    fn read_u64(reader: &mut impl Read) -> std::io::Result<u64> {
+        let mut buf = [0u8; 8];
+        reader.read_exact(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let level_count = read_u64(&mut reader)?;
+    let mut levels = Vec::new();
+    for _ in 0..level_count {
+        let len = read_u64(&mut reader)? as usize;
+        let mut level = vec![[0u8; 32]; len];
+        for hash in &mut level {
+            reader.read_exact(hash)?;
+        }
+        levels.push(level);
+    }
+
+    match tree {
+        CohortTree::V0 => LeafHashTreeV0::from_levels(levels).map(StreamedTree::V0),
+        CohortTree::V1 { arity, hash_scheme } => {
+            LeafHashTreeV1::from_levels(levels, arity as usize, hash_scheme.into())
+                .map(StreamedTree::V1)
+        }
+    }
+    .ok_or_else(|| {
+        CompilerError::MerkleTree(format!("Spilled tree {} is malformed", path.display()))
+    })
+}
+
+/// Write a cohort's claimants with their proofs, returning each leaf's vault index
+fn write_cohort_proofs(
+    merged_path: &Path,
+    cohort: &StreamedCohort,
+    tree: &StreamedTree,
+    vault_addresses: &[Pubkey],
+    db: &mut CampaignDatabase,
+    batch_size: usize,
+    progress: &mut impl FnMut(CompileProgress),
+) -> CompilerResult<Vec<u8>> {
+    let mut assigned_vaults = Vec::with_capacity(cohort.claimant_count);
+
+    for_each_leaf_batch(merged_path, cohort, batch_size, |leaves| {
+        let offset = assigned_vaults.len();
+        let records = leaves
+            .iter()
+            .enumerate()
+            .map(|(i, leaf)| {
+                let proof = tree.proof(offset + i).ok_or_else(|| {
+                    CompilerError::MerkleTree(format!(
+                        "Leaf {} is outside the {}-leaf tree of cohort '{}'",
+                        offset + i,
+                        tree.len(),
+                        cohort.config.cohort
+                    ))
+                })?;
+                Ok(ClaimantRecord {
+                    claimant: leaf.claimant,
+                    entitlements: leaf.entitlements,
+                    assigned_vault_index: leaf.assigned_vault_index,
+                    assigned_vault_pubkey: vault_addresses[leaf.assigned_vault_index as usize],
+                    merkle_proof: proof,
+                })
+            })
+            .collect::<CompilerResult<Vec<_>>>()?;
+
+        db.insert_claimants(&cohort.config.cohort, &records)
+            .map_err(|e| {
+                CompilerError::InvalidConfig(format!("Failed to insert claimant: {}", e))
+            })?;
+
+        assigned_vaults.extend(leaves.iter().map(|leaf| leaf.assigned_vault_index));
+        progress(CompileProgress::ProofsWritten {
+            cohort: cohort.config.cohort.clone(),
+            written: assigned_vaults.len(),
+            total: cohort.claimant_count,
+        });
+        Ok(())
+    })?;
+
+    Ok(assigned_vaults)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::campaign_compiler::compile_campaign_db_with_options;
+    use crate::campaign_layout::cohort_sizes;
+    use crate::dust::DustPolicy;
+    use prism_protocol::ClaimHashScheme;
+    use prism_protocol_csvs::{write_campaign_csv, AllocationMode, CampaignCsvRow};
+    use tempfile::NamedTempFile;
+
+    fn campaign_rows(count: usize) -> Vec<CampaignCsvRow> {
+        let cohorts = ["Alpha", "Beta", "Partners"];
+        (0..count)
+            .map(|i| CampaignCsvRow {
+                cohort: cohorts[i % 7 % 3].to_string(),
+                claimant: Pubkey::new_unique(),
+                entitlements: (i % 11) as u64 + 1,
+            })
+            .collect()
+    }
+
+    fn cohorts_rows() -> Vec<CohortsCsvRow> {
+        vec![
+            CohortsCsvRow {
+                cohort: "Alpha".to_string(),
+                share_percentage: Decimal::from(70),
+                ..Default::default()
+            },
+            CohortsCsvRow {
+                cohort: "Beta".to_string(),
+                share_percentage: Decimal::from(30),
+                ..Default::default()
+            },
+            CohortsCsvRow {
+                cohort: "Partners".to_string(),
+                allocation_mode: AllocationMode::PerClaimant,
+                max_per_claimant: Some(Decimal::from(8)),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn small_options(compile: CompileOptions) -> StreamingOptions {
+        StreamingOptions {
+            compile,
+            sort_chunk_rows: 37,
+            proof_batch_size: 16,
+            work_dir: None,
+            layout: None,
+        }
+    }
+
+    fn compile_streaming(
+        rows: &[CampaignCsvRow],
+        options: &StreamingOptions,
+        progress: impl FnMut(CompileProgress),
+    ) -> CompilerResult<(CampaignDatabase, StreamingCompileSummary)> {
+        let output_dir = tempfile::tempdir().unwrap();
+        let output = output_dir.path().join("campaign.db");
+        let summary = compile_streaming_to(rows, &output, options, progress)?;
+        Ok((CampaignDatabase::open(&output).unwrap(), summary))
+    }
+
+    fn compile_streaming_to(
+        rows: &[CampaignCsvRow],
+        output: &Path,
+        options: &StreamingOptions,
+        progress: impl FnMut(CompileProgress),
+    ) -> CompilerResult<StreamingCompileSummary> {
+        let csv = NamedTempFile::new().unwrap();
+        write_campaign_csv(csv.path(), rows).unwrap();
+
+        compile_campaign_streaming(
+            AddressFinder::default(),
+            csv.path(),
+            &cohorts_rows(),
+            &CampaignSettings {
+                budget: Decimal::from(10_000),
+                mint: Pubkey::new_from_array([1; 32]),
+                mint_decimals: 3,
+                admin: Pubkey::new_from_array([2; 32]),
+                claimants_per_vault: 25,
+            },
+            output,
+            options,
+            progress,
+        )
+    }
+
+    #[test]
+    fn test_streaming_matches_in_memory() {
+        let rows = campaign_rows(250);
+
+        let arities = |cohorts: &[(&str, usize)]| -> HashMap<String, usize> {
+            cohorts
+                .iter()
+                .map(|(cohort, arity)| (cohort.to_string(), *arity))
+                .collect()
+        };
+        for options in [
+            CompileOptions {
+                dust_policy: DustPolicy::Retain,
+                ..Default::default()
+            },
+            CompileOptions {
+                dust_policy: DustPolicy::FirstLeaves,
+                ..Default::default()
+            },
+            CompileOptions {
+                dust_policy: DustPolicy::FirstLeaves,
+                leaf_order: LeafOrder::Claimant,
+                ..Default::default()
+            },
+            // V1 trees beside V0 ones
+            CompileOptions {
+                tree_arities: arities(&[("Alpha", 4), ("Partners", 3)]),
+                ..Default::default()
+            },
+            CompileOptions {
+                leaf_order: LeafOrder::Claimant,
+                tree_arities: arities(&[("Alpha", 16), ("Beta", 2), ("Partners", 5)]),
+                hash_scheme: ClaimHashScheme::Keccak256,
+                ..Default::default()
+            },
+        ] {
+            let (streamed, summary) =
+                compile_streaming(&rows, &small_options(options.clone()), |_| {}).unwrap();
+            let in_memory = compile_campaign_db_with_options(
+                AddressFinder::default(),
+                &rows,
+                &cohorts_rows(),
+                &CampaignSettings {
+                    budget: Decimal::from(10_000),
+                    mint: Pubkey::new_from_array([1; 32]),
+                    mint_decimals: 3,
+                    admin: Pubkey::new_from_array([2; 32]),
+                    claimants_per_vault: 25,
+                },
+                &options,
+            )
+            .unwrap();
+
+            let campaign = in_memory.read_campaign_info().unwrap();
+            assert_eq!(summary.fingerprint, campaign.fingerprint);
+            assert_eq!(
+                streamed.read_campaign_info().unwrap().fingerprint,
+                campaign.fingerprint
+            );
+            assert_eq!(summary.total_claimants, rows.len());
+
+            let cohorts = in_memory.read_cohorts().unwrap();
+            let streamed_cohorts = streamed.read_cohorts().unwrap();
+            assert_eq!(streamed_cohorts.len(), cohorts.len());
+            for (streamed_cohort, cohort) in streamed_cohorts.iter().zip(&cohorts) {
+                assert_eq!(streamed_cohort.name, cohort.name);
+                assert_eq!(streamed_cohort.merkle_root, cohort.merkle_root);
+                assert_eq!(streamed_cohort.tree, cohort.tree);
+                assert_eq!(streamed_cohort.vaults, cohort.vaults);
+
+                // Claimant-ordered cohorts, and only those, carry the same claimant index
+                let index_root = in_memory
+                    .read_cohort_claimant_index_root(&cohort.name)
+                    .unwrap();
+                assert_eq!(
+                    index_root.is_some(),
+                    options.leaf_order == LeafOrder::Claimant
+                );
+                assert_eq!(
+                    streamed
+                        .read_cohort_claimant_index_root(&cohort.name)
+                        .unwrap(),
+                    index_root
+                );
+
+                // Same claimants with the same entitlements, vaults and proofs
+                assert_eq!(
+                    streamed.read_cohort_claimant_records(&cohort.name).unwrap(),
+                    in_memory
+                        .read_cohort_claimant_records(&cohort.name)
+                        .unwrap()
+                );
+            }
+            let streamed_claimants: usize = streamed_cohorts
+                .iter()
+                .map(|cohort| {
+                    streamed
+                        .read_cohort_claimant_records(&cohort.name)
+                        .unwrap()
+                        .len()
+                })
+                .sum();
+            assert_eq!(streamed_claimants, rows.len());
+
+            let funding = in_memory.calculate_total_funding_required().unwrap();
+            assert_eq!(
+                streamed.calculate_total_funding_required().unwrap(),
+                funding
+            );
+            assert_eq!(summary.total_funding, funding);
+            assert_eq!(
+                streamed.read_dust_report().unwrap(),
+                in_memory.read_dust_report().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_streaming_auto_layout() {
+        let rows = campaign_rows(250);
+        let constraints = LayoutConstraints {
+            max_claimants_per_vault: 40,
+            tree_arity: Some(4),
+            ..Default::default()
+        };
+        let options = StreamingOptions {
+            layout: Some(constraints),
+            ..small_options(CompileOptions::default())
+        };
+        let (streamed, summary) = compile_streaming(&rows, &options, |_| {}).unwrap();
+
+        let layout = plan_layout(&cohort_sizes(&rows, &cohorts_rows()), &constraints).unwrap();
+        assert_eq!(summary.layout.as_ref(), Some(&layout));
+        let in_memory = compile_campaign_db_with_options(
+            AddressFinder::default(),
+            &rows,
+            &cohorts_rows(),
+            &CampaignSettings {
+                budget: Decimal::from(10_000),
+                mint: Pubkey::new_from_array([1; 32]),
+                mint_decimals: 3,
+                admin: Pubkey::new_from_array([2; 32]),
+                claimants_per_vault: 25,
+            },
+            &CompileOptions::default().with_layout(&layout),
+        )
+        .unwrap();
+        assert_eq!(
+            summary.fingerprint,
+            in_memory.read_campaign_info().unwrap().fingerprint
+        );
+        for cohort in streamed.read_cohorts().unwrap() {
+            let planned = layout.cohort(&cohort.name).unwrap();
+            assert_eq!(cohort.vaults.len(), planned.vault_count);
+        }
+    }
+
+    #[test]
+    fn test_streaming_progress() {
+        let rows = campaign_rows(100);
+        let mut events = Vec::new();
+        compile_streaming(&rows, &small_options(CompileOptions::default()), |event| {
+            events.push(event)
+        })
+        .unwrap();
+
+        let spills = events
+            .iter()
+            .filter(|event| matches!(event, CompileProgress::RowsRead { .. }))
+            .count();
+        assert_eq!(spills, 3);
+        assert!(events.contains(&CompileProgress::RunsMerged { runs: 3, rows: 100 }));
+
+        let hashed = events
+            .iter()
+            .filter(|event| matches!(event, CompileProgress::CohortHashed { .. }))
+            .count();
+        assert_eq!(hashed, 3);
+
+        let written: usize = events
+            .iter()
+            .filter_map(|event| match event {
+                CompileProgress::ProofsWritten { written, total, .. } if written == total => {
+                    Some(*total)
+                }
+                _ => None,
+            })
+            .sum();
+        assert_eq!(written, rows.len());
+    }
+
+    #[test]
+    fn test_streaming_rejects_bad_input() {
+        let mut rows = campaign_rows(40);
+        rows[30].claimant = rows[9].claimant;
+        rows[30].cohort = rows[9].cohort.clone();
+        let result = compile_streaming(&rows, &small_options(CompileOptions::default()), |_| {});
+        assert!(matches!(result, Err(CompilerError::MerkleTree(msg)) if msg.contains("duplicate")));
+
+        let mut rows = campaign_rows(40);
+        rows[5].cohort = "Unknown".to_string();
+        let result = compile_streaming(&rows, &small_options(CompileOptions::default()), |_| {});
+        assert!(matches!(result, Err(CompilerError::Csv(_))));
+
+        let rows: Vec<CampaignCsvRow> = campaign_rows(40)
+            .into_iter()
+            .filter(|row| row.cohort != "Beta")
+            .collect();
+        let result = compile_streaming(&rows, &small_options(CompileOptions::default()), |_| {});
+        assert!(matches!(result, Err(CompilerError::Csv(_))));
+    }
+
+    #[test]
+    fn test_streaming_rejects_bad_trees() {
+        let rows = campaign_rows(40);
+        let options = CompileOptions {
+            tree_arities: HashMap::from([("Alpha".to_string(), 1)]),
+            ..Default::default()
+        };
+        let result = compile_streaming(&rows, &small_options(options), |_| {});
+        assert!(matches!(result, Err(CompilerError::InvalidConfig(_))));
+
+        // Binary V0 trees are SHA-256 only
+        let options = CompileOptions {
+            tree_arities: HashMap::from([("Alpha".to_string(), 4)]),
+            hash_scheme: ClaimHashScheme::Keccak256,
+            ..Default::default()
+        };
+        let result = compile_streaming(&rows, &small_options(options), |_| {});
+        assert!(matches!(result, Err(CompilerError::InvalidConfig(msg)) if msg.contains("Beta")));
+    }
+
+    #[test]
+    fn test_streaming_rejects_bad_vault_counts() {
+        let csv = NamedTempFile::new().unwrap();
+        write_campaign_csv(csv.path(), &campaign_rows(800)).unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        let compile = |claimants_per_vault| {
+            compile_campaign_streaming(
+                AddressFinder::default(),
+                csv.path(),
+                &cohorts_rows(),
+                &CampaignSettings {
+                    budget: Decimal::from(10_000),
+                    mint: Pubkey::new_from_array([1; 32]),
+                    mint_decimals: 3,
+                    admin: Pubkey::new_from_array([2; 32]),
+                    claimants_per_vault,
+                },
+                &output_dir.path().join("campaign.db"),
+                &small_options(CompileOptions::default()),
+                |_| {},
+            )
+        };
+
+        let result = compile(0);
+        assert!(
+            matches!(result, Err(CompilerError::InvalidConfig(msg)) if msg.contains("claimants_per_vault"))
+        );
+
+        // ~340 Alpha claimants at one per vault would wrap the u8 vault index
+        let result = compile(1);
+        assert!(matches!(result, Err(CompilerError::InvalidConfig(msg)) if msg.contains("Alpha")));
+        assert!(!output_dir.path().join("campaign.db").exists());
+    }
+
+    #[test]
+    fn test_streaming_replaces_output_only_on_success() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let output = output_dir.path().join("campaign.db");
+        let options = small_options(CompileOptions::default());
+
+        let summary = compile_streaming_to(&campaign_rows(60), &output, &options, |_| {}).unwrap();
+
+        // A failing recompile leaves the previous database and no staging files
+        let mut rows = campaign_rows(60);
+        rows[40].claimant = rows[19].claimant;
+        rows[40].cohort = rows[19].cohort.clone();
+        assert!(compile_streaming_to(&rows, &output, &options, |_| {}).is_err());
+
+        let db = CampaignDatabase::open(&output).unwrap();
+        assert_eq!(
+            db.read_campaign_info().unwrap().fingerprint,
+            summary.fingerprint
+        );
+        assert_eq!(std::fs::read_dir(output_dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,315 @@
+/*!
+# Tree Arity
+
+Choosing how many children each internal node of a cohort's merkle tree has.
+
+The arity is committed in the cohort tree account (`CohortTreeV0::merkle_tree_arity`,
+written by `initialize_cohort_v1`) and bounds every level of a `claim_tokens_v2` proof.
+It trades depth for width: the worst-case proof for `n` leaves has `⌈logₐ n⌉` levels of
+up to `a - 1` sibling hashes. Proof bytes grow with
+`(a - 1) / log a`, so binary trees carry the smallest proofs, while hashing work grows
+with `a / log a` and bottoms out around arity 3 or 4. Which one wins depends on what
+is scarce for a claim ([`ArityObjective`]).
+
+[`ClaimCostModel::estimate`] models the worst-case `claim_tokens_v1` of a cohort
+([`ClaimCostModel::estimate_v0`] the `claim_tokens_v0` of a binary V0 tree) and
+[`optimal_arity`] scans every arity the program accepts.
+
+The program hashes with the software SHA-256 `Hasher`, so compute is modelled per
+64-byte block hashed plus per-level and per-sibling overhead. The default coefficients
+follow the LiteSVM limits recorded in `test_claim_maximum_merkle_proof_size` (~62K CU
+of claim overhead including ATA and receipt creation; binary proofs exhaust the 200K
+default limit at ~15 levels). `test_claim_v1_arity_compute_units` measures claims of
+every proof shape in LiteSVM and fails when the model drifts, printing the
+measurements to recalibrate from.
+*/
+
+use prism_protocol::claim_tree_constants::{BINARY_TREE_ARITY, MAX_TREE_ARITY, MIN_TREE_ARITY};
+
+/// Anchor instruction discriminator
+const DISCRIMINATOR_BYTES: usize = 8;
+
+/// Borsh length prefix of a `Vec`
+const VEC_LEN_BYTES: usize = 4;
+
+/// `claim_tokens_v0`/`claim_tokens_v1` arguments besides the proof: fingerprint, merkle root,
+/// assigned vault index and entitlements
+const CLAIM_ARGS_BYTES: usize = 32 + 32 + 1 + 8;
+
+/// What an arity should minimise
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArityObjective {
+    /// Claim instruction bytes (the proof has to fit in one transaction with the accounts
+    /// and compute budget instructions)
+    TransactionSize,
+
+    /// Compute units consumed by a claim
+    ComputeUnits,
+}
+
+/// Linear compute unit model of `claim_tokens_v1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimCostModel {
+    /// Claim with an empty proof: account checks, leaf hash, receipt and transfer
+    pub base_compute_units: u64,
+
+    /// Per proof level: allocation, hasher setup and finalisation
+    pub per_level_compute_units: u64,
+
+    /// Per 64-byte SHA-256 block hashed
+    pub per_hash_block_compute_units: u64,
+
+    /// Per sibling hash: deserialisation, copy and sort
+    pub per_sibling_compute_units: u64,
+}
+
+impl Default for ClaimCostModel {
+    fn default() -> Self {
+        Self {
+            base_compute_units: 62_000,
+            per_level_compute_units: 300,
+            per_hash_block_compute_units: 4_400,
+            per_sibling_compute_units: 100,
+        }
+    }
+}
+
+/// Worst-case claim cost for a cohort tree of a given arity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArityEstimate {
+    pub arity: u16,
+
+    /// Proof levels (every leaf sits at the same depth)
+    pub depth: usize,
+
+    /// Sibling hashes in the widest proof
+    pub proof_hashes: usize,
+
+    /// Borsh-encoded size of the widest proof
+    pub proof_bytes: usize,
+
+    /// `claim_tokens_v1` instruction data size with the widest proof
+    pub instruction_data_bytes: usize,
+
+    /// Modelled compute units of the claim with the widest proof
+    pub compute_units: u64,
+}
+
+impl ClaimCostModel {
+    /// Compute units to verify a proof with the given siblings per level (base excluded)
+    pub fn verification_compute_units(&self, siblings_per_level: &[usize]) -> u64 {
+        siblings_per_level
+            .iter()
+            .map(|&siblings| {
+                // Internal node preimage: prefix byte and every child hash
+                let preimage_bytes = 1 + 32 * (siblings + 1);
+                self.per_level_compute_units
+                    + self.per_hash_block_compute_units * sha256_blocks(preimage_bytes)
+                    + self.per_sibling_compute_units * siblings as u64
+            })
+            .sum()
+    }
+
+    /// Cost of the widest claim proof in a tree of `leaf_count` leaves
+    ///
+    /// The first leaf's chunk is full at every level, so its proof is the widest.
+    pub fn estimate(&self, leaf_count: usize, arity: u16) -> ArityEstimate {
+        let width = arity as usize;
+        let mut siblings_per_level = Vec::new();
+        let mut level_size = leaf_count.max(1);
+        while level_size > 1 {
+            siblings_per_level.push(level_size.min(width) - 1);
+            level_size = level_size.div_ceil(width);
+        }
+
+        let proof_hashes: usize = siblings_per_level.iter().sum();
+        let proof_bytes =
+            VEC_LEN_BYTES + VEC_LEN_BYTES * siblings_per_level.len() + 32 * proof_hashes;
+        ArityEstimate {
+            arity,
+            depth: siblings_per_level.len(),
+            proof_hashes,
+            proof_bytes,
+            instruction_data_bytes: DISCRIMINATOR_BYTES + CLAIM_ARGS_BYTES + proof_bytes,
+            compute_units: self.base_compute_units
+                + self.verification_compute_units(&siblings_per_level),
+        }
+    }
+
+    /// Cost of the widest `claim_tokens_v0` proof in a binary tree of `leaf_count` leaves
+    ///
+    /// V0 proofs hold one sibling per level with no per-level length prefix, and are
+    /// verified with the same hashing as a binary V1 proof.
+    pub fn estimate_v0(&self, leaf_count: usize) -> ArityEstimate {
+        let mut estimate = self.estimate(leaf_count, BINARY_TREE_ARITY);
+        estimate.proof_bytes -= VEC_LEN_BYTES * estimate.depth;
+        estimate.instruction_data_bytes -= VEC_LEN_BYTES * estimate.depth;
+        estimate
+    }
+
+    /// Estimates for every arity the program accepts, narrowest first
+    pub fn estimates(&self, leaf_count: usize) -> Vec<ArityEstimate> {
+        (MIN_TREE_ARITY..=MAX_TREE_ARITY)
+            .map(|arity| self.estimate(leaf_count, arity))
+            .collect()
+    }
+
+    /// The arity minimising `objective`, breaking ties on the other cost and then
+    /// preferring the narrower tree
+    pub fn optimal_arity(&self, leaf_count: usize, objective: ArityObjective) -> ArityEstimate {
+        self.estimates(leaf_count)
+            .into_iter()
+            .min_by_key(|estimate| match objective {
+                ArityObjective::TransactionSize => (
+                    estimate.instruction_data_bytes as u64,
+                    estimate.compute_units,
+                    estimate.arity,
+                ),
+                ArityObjective::ComputeUnits => (
+                    estimate.compute_units,
+                    estimate.instruction_data_bytes as u64,
+                    estimate.arity,
+                ),
+            })
+            .expect("at least one arity")
+    }
+}
+
+/// Best arity for a cohort of `leaf_count` claimants under the default cost model
+pub fn optimal_arity(leaf_count: usize, objective: ArityObjective) -> ArityEstimate {
+    ClaimCostModel::default().optimal_arity(leaf_count, objective)
+}
+
+/// SHA-256 compression blocks for a message (with its padding and length suffix)
+fn sha256_blocks(message_bytes: usize) -> u64 {
+    (message_bytes + 9).div_ceil(64) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_claim_tokens_v0_ix, build_claim_tokens_v1_ix, AddressFinder, ClaimV1Params};
+    use prism_protocol_merkle::{
+        create_claim_tree_v0, create_claim_tree_v1_with_options, ClaimTreeV1Options,
+    };
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_estimate_matches_tree_and_instruction() {
+        let claimants: Vec<(Pubkey, u64)> =
+            (0..5_000).map(|i| (Pubkey::new_unique(), i + 1)).collect();
+        let address_finder = AddressFinder::default();
+        let model = ClaimCostModel::default();
+
+        for arity in [2u16, 3, 7, 64, 256] {
+            let tree = create_claim_tree_v1_with_options(
+                &claimants,
+                2,
+                &ClaimTreeV1Options::with_arity(arity as usize),
+            )
+            .unwrap();
+            let estimate = model.estimate(claimants.len(), arity);
+            assert_eq!(estimate.depth, tree.depth());
+
+            let widest = tree
+                .leaves
+                .iter()
+                .map(|leaf| {
+                    let proof = tree.proof_for_claimant(&leaf.claimant).unwrap();
+                    proof.iter().map(Vec::len).sum::<usize>()
+                })
+                .max()
+                .unwrap();
+            assert_eq!(estimate.proof_hashes, widest, "arity {}", arity);
+
+            let leaf = &tree.leaves[0];
+            let proof = tree.proof_for_claimant(&leaf.claimant).unwrap();
+            let (ix, _, _) = build_claim_tokens_v1_ix(
+                &address_finder,
+                ClaimV1Params {
+                    admin: Pubkey::new_unique(),
+                    claimant: leaf.claimant,
+                    mint: Pubkey::new_unique(),
+                    claimant_token_account: Pubkey::new_unique(),
+                    campaign_fingerprint: [1; 32],
+                    cohort_merkle_root: tree.root().unwrap(),
+                    merkle_proof: proof,
+                    assigned_vault_index: leaf.assigned_vault_index,
+                    entitlements: leaf.entitlements,
+                },
+            )
+            .unwrap();
+            assert_eq!(estimate.instruction_data_bytes, ix.data.len());
+        }
+    }
+
+    #[test]
+    fn test_estimate_v0_matches_tree_and_instruction() {
+        let address_finder = AddressFinder::default();
+        let model = ClaimCostModel::default();
+
+        for leaf_count in [1usize, 2, 5, 1_000, 4_097] {
+            let claimants: Vec<(Pubkey, u64)> = (0..leaf_count)
+                .map(|i| (Pubkey::new_unique(), i as u64 + 1))
+                .collect();
+            let tree = create_claim_tree_v0(&claimants, 2).unwrap();
+            let estimate = model.estimate_v0(leaf_count);
+
+            let widest = tree
+                .leaves
+                .iter()
+                .map(|leaf| tree.proof_for_claimant(&leaf.claimant).unwrap())
+                .max_by_key(Vec::len)
+                .unwrap();
+            assert_eq!(estimate.depth, widest.len(), "{} leaves", leaf_count);
+            assert_eq!(estimate.proof_hashes, widest.len());
+
+            let (ix, _, _) = build_claim_tokens_v0_ix(
+                &address_finder,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                [1; 32],
+                tree.root().unwrap(),
+                widest,
+                0,
+                1,
+            )
+            .unwrap();
+            assert_eq!(estimate.instruction_data_bytes, ix.data.len());
+
+            // Same hashing as a binary V1 proof, smaller instruction
+            let v1 = model.estimate(leaf_count, BINARY_TREE_ARITY);
+            assert_eq!(estimate.compute_units, v1.compute_units);
+            assert!(estimate.instruction_data_bytes <= v1.instruction_data_bytes);
+        }
+    }
+
+    #[test]
+    fn test_single_leaf_has_no_proof() {
+        let estimate = ClaimCostModel::default().estimate(1, 256);
+        assert_eq!(estimate.depth, 0);
+        assert_eq!(estimate.proof_bytes, VEC_LEN_BYTES);
+        assert_eq!(
+            estimate.compute_units,
+            ClaimCostModel::default().base_compute_units
+        );
+    }
+
+    #[test]
+    fn test_optimal_arity() {
+        // Binary proofs are always the smallest
+        for leaf_count in [2, 1_000, 1_000_000] {
+            let best = optimal_arity(leaf_count, ArityObjective::TransactionSize);
+            assert_eq!(best.arity, 2, "{} leaves", leaf_count);
+        }
+
+        // Hashing favours slightly wider trees, and never the 256 default for big cohorts
+        let best = optimal_arity(1_000_000, ArityObjective::ComputeUnits);
+        assert!((3..=8).contains(&best.arity), "got arity {}", best.arity);
+        let widest = ClaimCostModel::default().estimate(1_000_000, 256);
+        assert!(best.compute_units < widest.compute_units);
+        assert!(best.instruction_data_bytes < widest.instruction_data_bytes);
+    }
+}
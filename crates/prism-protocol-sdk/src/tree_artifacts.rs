@@ -0,0 +1,263 @@
+/*!
+# Tree Artifacts
+
+Stores each compiled cohort's claim tree as a serialized artifact
+(`prism_protocol_merkle::TreeArtifact`) so proof servers can open it and serve proofs
+without reloading claimants and rebuilding the tree.
+
+Artifacts are either embedded in the campaign database or written to a directory as
+`<merkle root hex>.tree`, with the database recording the path and the artifact's
+SHA-256 checksum. Opening an artifact checks it against both the recorded checksum and
+the cohort's merkle root, so a stale or swapped file is refused rather than served.
+*/
+
+use crate::CompiledCampaign;
+use prism_protocol_db::{CampaignDatabase, DbError, TreeArtifactRecord, TreeArtifactSource};
+use prism_protocol_merkle::{TreeArtifact, TreeArtifactError};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File extension of artifacts written to a directory
+pub const TREE_ARTIFACT_EXTENSION: &str = "tree";
+
+#[derive(Error, Debug)]
+pub enum TreeArtifactStoreError {
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Tree artifact error: {0}")]
+    Artifact(#[from] TreeArtifactError),
+
+    #[error("Cohort not found: {0}")]
+    UnknownCohort(String),
+
+    #[error("No tree artifact recorded for cohort {0}")]
+    MissingArtifact(String),
+
+    #[error("Tree artifact for cohort {0} does not match the checksum recorded in the database")]
+    ChecksumMismatch(String),
+}
+
+pub type TreeArtifactStoreResult<T> = Result<T, TreeArtifactStoreError>;
+
+/// Where [`store_tree_artifacts`] puts each cohort's artifact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeArtifactStorage {
+    /// Inside the campaign database
+    Embedded,
+
+    /// As files in a directory; the database records their paths as given
+    Directory(PathBuf),
+}
+
+/// Serialize every cohort tree of a compiled campaign and record it in the database
+pub fn store_tree_artifacts(
+    db: &mut CampaignDatabase,
+    campaign: &CompiledCampaign,
+    storage: &TreeArtifactStorage,
+) -> TreeArtifactStoreResult<()> {
+    if let TreeArtifactStorage::Directory(dir) = storage {
+        fs::create_dir_all(dir)?;
+    }
+
+    for cohort in &campaign.cohorts {
+        let (checksum, source) = match storage {
+            TreeArtifactStorage::Embedded => {
+                let mut bytes = Vec::new();
+                let checksum = cohort.merkle_tree.write_artifact(&mut bytes)?;
+                (checksum, TreeArtifactSource::Embedded(bytes))
+            }
+            TreeArtifactStorage::Directory(dir) => {
+                let path = dir
+                    .join(hex::encode(cohort.merkle_root))
+                    .with_extension(TREE_ARTIFACT_EXTENSION);
+                let checksum = cohort
+                    .merkle_tree
+                    .write_artifact(BufWriter::new(File::create(&path)?))?;
+                (checksum, TreeArtifactSource::File(path))
+            }
+        };
+
+        db.insert_tree_artifact(&TreeArtifactRecord {
+            cohort_name: cohort.name.clone(),
+            checksum,
+            source,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Open the tree artifact recorded for a cohort
+///
+/// Relative artifact paths resolve against `base_dir` (usually the database's
+/// directory). The artifact must match the recorded checksum and the cohort's merkle
+/// root.
+pub fn open_cohort_tree_artifact(
+    db: &CampaignDatabase,
+    cohort_name: &str,
+    base_dir: &Path,
+) -> TreeArtifactStoreResult<TreeArtifact> {
+    let merkle_root = db
+        .read_cohorts()?
+        .into_iter()
+        .find(|cohort| cohort.name == cohort_name)
+        .ok_or_else(|| TreeArtifactStoreError::UnknownCohort(cohort_name.to_string()))?
+        .merkle_root;
+
+    let record = db
+        .read_tree_artifact(cohort_name)?
+        .ok_or_else(|| TreeArtifactStoreError::MissingArtifact(cohort_name.to_string()))?;
+
+    let artifact = match record.source {
+        TreeArtifactSource::File(path) => TreeArtifact::open(&base_dir.join(path))?,
+        TreeArtifactSource::Embedded(bytes) => TreeArtifact::from_bytes(bytes)?,
+    };
+
+    if artifact.checksum() != record.checksum {
+        return Err(TreeArtifactStoreError::ChecksumMismatch(
+            cohort_name.to_string(),
+        ));
+    }
+    artifact.verify_root(&merkle_root)?;
+
+    Ok(artifact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compile_campaign, AddressFinder};
+    use prism_protocol_csvs::{AllocationMode, CampaignCsvRow, CohortsCsvRow};
+    use rust_decimal::Decimal;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn compiled_campaign() -> (CampaignDatabase, CompiledCampaign) {
+        let campaign_rows: Vec<CampaignCsvRow> = (0..40)
+            .map(|i| CampaignCsvRow {
+                cohort: if i % 4 == 0 { "Alpha" } else { "Beta" }.to_string(),
+                claimant: Pubkey::new_unique(),
+                entitlements: i + 1,
+            })
+            .collect();
+        let cohorts_rows: Vec<CohortsCsvRow> = ["Alpha", "Beta"]
+            .iter()
+            .map(|name| CohortsCsvRow {
+                cohort: name.to_string(),
+                share_percentage: Decimal::from(50),
+                allocation_mode: AllocationMode::SharePercentage,
+                allocation_value: None,
+                min_per_claimant: None,
+                max_per_claimant: None,
+            })
+            .collect();
+
+        let campaign = compile_campaign(
+            AddressFinder::default(),
+            &campaign_rows,
+            &cohorts_rows,
+            Decimal::from(1000),
+            Pubkey::new_unique(),
+            6,
+            Pubkey::new_unique(),
+            4,
+        )
+        .unwrap();
+
+        let mut db = CampaignDatabase::create_in_memory().unwrap();
+        db.insert_campaign(
+            campaign.fingerprint,
+            campaign.mint,
+            campaign.mint_decimals,
+            campaign.admin,
+            campaign.budget,
+        )
+        .unwrap();
+        for cohort in &campaign.cohorts {
+            db.insert_cohort(
+                &cohort.name,
+                cohort.merkle_root,
+                1,
+                &cohort.amount_per_entitlement_humane,
+                cohort.vault_count,
+                cohort.merkle_tree.leaves().len(),
+                0,
+                cohort.merkle_tree.layout(),
+            )
+            .unwrap();
+        }
+
+        (db, campaign)
+    }
+
+    fn assert_serves_proofs(db: &CampaignDatabase, campaign: &CompiledCampaign, base_dir: &Path) {
+        for cohort in &campaign.cohorts {
+            let artifact = open_cohort_tree_artifact(db, &cohort.name, base_dir).unwrap();
+            for leaf in cohort.merkle_tree.leaves() {
+                let (served_leaf, proof) = artifact.proof_for_claimant(&leaf.claimant).unwrap();
+                assert_eq!(&served_leaf, leaf);
+                assert!(proof.verify(&cohort.merkle_root, leaf, 2));
+            }
+        }
+    }
+
+    #[test]
+    fn test_embedded_tree_artifacts() {
+        let (mut db, campaign) = compiled_campaign();
+        assert!(matches!(
+            open_cohort_tree_artifact(&db, "Alpha", Path::new(".")),
+            Err(TreeArtifactStoreError::MissingArtifact(_))
+        ));
+
+        store_tree_artifacts(&mut db, &campaign, &TreeArtifactStorage::Embedded).unwrap();
+        assert_serves_proofs(&db, &campaign, Path::new("."));
+
+        assert!(matches!(
+            open_cohort_tree_artifact(&db, "Gamma", Path::new(".")),
+            Err(TreeArtifactStoreError::UnknownCohort(_))
+        ));
+    }
+
+    #[test]
+    fn test_directory_tree_artifacts() {
+        let (mut db, campaign) = compiled_campaign();
+        let dir = tempfile::tempdir().unwrap();
+
+        store_tree_artifacts(
+            &mut db,
+            &campaign,
+            &TreeArtifactStorage::Directory(dir.path().join("trees")),
+        )
+        .unwrap();
+        assert_serves_proofs(&db, &campaign, Path::new("."));
+
+        // Relative paths resolve against the base directory
+        for cohort in &campaign.cohorts {
+            let mut record = db.read_tree_artifact(&cohort.name).unwrap().unwrap();
+            if let TreeArtifactSource::File(path) = &record.source {
+                let relative = path.strip_prefix(dir.path()).unwrap().to_path_buf();
+                record.source = TreeArtifactSource::File(relative);
+            }
+            db.insert_tree_artifact(&record).unwrap();
+        }
+        assert_serves_proofs(&db, &campaign, dir.path());
+
+        // A file swapped for another cohort's tree is refused
+        let [alpha, beta] = [&campaign.cohorts[0], &campaign.cohorts[1]].map(|cohort| {
+            dir.path()
+                .join("trees")
+                .join(hex::encode(cohort.merkle_root))
+                .with_extension(TREE_ARTIFACT_EXTENSION)
+        });
+        fs::copy(&beta, &alpha).unwrap();
+        assert!(matches!(
+            open_cohort_tree_artifact(&db, &campaign.cohorts[0].name, dir.path()),
+            Err(TreeArtifactStoreError::ChecksumMismatch(_))
+        ));
+    }
+}
@@ -1,7 +1,9 @@
 use {
     crate::{deterministic_keypair, deterministic_pubkey, FixtureStage},
     prism_protocol_sdk::{
-        compile_campaign, AddressFinder, CampaignCsvRow, CohortsCsvRow, CompiledCampaign,
+        compile_campaign_db_with_options, compile_campaign_with_options, AddressFinder,
+        CampaignCsvRow, CampaignDatabase, CampaignSettings, CohortsCsvRow, CompileOptions,
+        CompiledCampaign,
     },
     rust_decimal::Decimal,
     solana_keypair::Keypair,
@@ -21,20 +23,24 @@ pub struct FixtureState {
 
 impl Default for FixtureState {
     fn default() -> Self {
+        Self::with_compile_options(&CompileOptions::default())
+    }
+}
+
+impl FixtureState {
+    /// The default campaign compiled with `options` (e.g. V1 tree arities and hash scheme)
+    pub fn with_compile_options(options: &CompileOptions) -> Self {
         let address_finder = AddressFinder::default();
 
         let admin_keypair = default_admin_keypair();
         let mint_keypair = default_mint_keypair();
 
-        let campaign = compile_campaign(
+        let campaign = compile_campaign_with_options(
             address_finder.clone(),
             &default_campaign_csv_rows(),
             &default_cohorts_csv_rows(),
-            DEFAULT_BUDGET,
-            mint_keypair.pubkey(),
-            DEFAULT_MINT_DECIMALS,
-            admin_keypair.pubkey(),
-            DEFAULT_CLAIMANTS_PER_VAULT,
+            &default_campaign_settings(),
+            options,
         )
         .expect("Failed to compile default campaign");
 
@@ -46,6 +52,28 @@ impl Default for FixtureState {
             stage: FixtureStage::default(),
         }
     }
+
+    /// The default campaign compiled into a database, as `compile-campaign` writes it
+    pub fn default_campaign_database() -> CampaignDatabase {
+        compile_campaign_db_with_options(
+            AddressFinder::default(),
+            &default_campaign_csv_rows(),
+            &default_cohorts_csv_rows(),
+            &default_campaign_settings(),
+            &CompileOptions::default(),
+        )
+        .expect("Failed to compile default campaign database")
+    }
+}
+
+fn default_campaign_settings() -> CampaignSettings {
+    CampaignSettings {
+        budget: DEFAULT_BUDGET,
+        mint: default_mint_pubkey(),
+        mint_decimals: DEFAULT_MINT_DECIMALS,
+        admin: default_admin_pubkey(),
+        claimants_per_vault: DEFAULT_CLAIMANTS_PER_VAULT,
+    }
 }
 
 pub const DEFAULT_BUDGET: Decimal = rust_decimal::dec!(1_000_000_000);
@@ -134,18 +162,22 @@ pub fn default_cohorts_csv_rows() -> Vec<CohortsCsvRow> {
         CohortsCsvRow {
             cohort: "EarlyAdopters".to_string(),
             share_percentage: Decimal::from(5),
+            ..Default::default()
         },
         CohortsCsvRow {
             cohort: "Investors".to_string(),
             share_percentage: Decimal::from(10),
+            ..Default::default()
         },
         CohortsCsvRow {
             cohort: "PowerUsers".to_string(),
             share_percentage: Decimal::from(10),
+            ..Default::default()
         },
         CohortsCsvRow {
             cohort: "Team".to_string(),
             share_percentage: Decimal::from(75),
+            ..Default::default()
         },
     ]
 }
@@ -6,23 +6,30 @@ use {
         LiteSVM,
     },
     litesvm_token::spl_token::solana_program::native_token::LAMPORTS_PER_SOL,
-    prism_protocol::{CampaignV0, ClaimLeaf, ClaimReceiptV0, CohortV0},
+    prism_protocol::{CampaignV0, ClaimLeaf, ClaimReceiptV0, CohortTreeV0, CohortV0},
     prism_protocol_sdk::{
         build_activate_campaign_v0_ix, build_activate_cohort_v0_ix, build_activate_vault_v0_ix,
-        build_claim_tokens_v0_ix, build_initialize_campaign_v0_ix, build_initialize_cohort_v0_ix,
-        build_initialize_vault_v0_ix, build_make_campaign_unstoppable_v0_ix,
-        build_pause_campaign_v0_ix, build_permanently_halt_campaign_v0_ix,
-        build_reclaim_tokens_v0_ix, build_resume_campaign_v0_ix, CompiledCohort,
+        build_claim_tokens_v0_ix, build_claim_tokens_v2_ix, build_initialize_campaign_v0_ix,
+        build_initialize_cohort_v0_ix, build_initialize_cohort_v1_ix, build_initialize_vault_v0_ix,
+        build_make_campaign_unstoppable_v0_ix, build_pause_campaign_v0_ix,
+        build_permanently_halt_campaign_v0_ix, build_reclaim_tokens_v0_ix,
+        build_resume_campaign_v0_ix, ClaimV1Params, CohortMerkleTree, CompiledCohort,
+        InitializeCohortV1Params, StoredProof,
     },
-    rust_decimal::prelude::ToPrimitive,
     solana_account::Account,
+    solana_hash::Hash,
     solana_instruction::Instruction,
     solana_keypair::Keypair,
     solana_message::Message,
     solana_pubkey::Pubkey,
+    solana_sdk::{
+        account_utils::StateMut as _,
+        nonce::state::{State as NonceState, Versions},
+    },
     solana_signer::Signer as _,
+    solana_system_interface::instruction::create_nonce_account,
     solana_sysvar::clock::Clock,
-    solana_transaction::Transaction,
+    solana_transaction::{versioned::VersionedTransaction, Transaction},
     spl_associated_token_account::get_associated_token_address,
     std::collections::HashMap,
 };
@@ -61,7 +68,7 @@ impl TestFixture {
             .unwrap_or_else(|e| panic!("Failed to airdrop to {amount} {to}: {e:?}"));
     }
 
-    pub fn latest_blockhash(&self) -> solana_hash::Hash {
+    pub fn latest_blockhash(&self) -> Hash {
         self.svm.latest_blockhash()
     }
 
@@ -100,6 +107,11 @@ impl TestFixture {
 
     /// Send a transaction and optionally print logs based on the logging_enabled setting
     pub fn send_transaction(&mut self, tx: Transaction) -> TransactionResult {
+        self.send_versioned_transaction(tx.into())
+    }
+
+    /// Send a legacy or v0 transaction, e.g. one signed offline against a durable nonce
+    pub fn send_versioned_transaction(&mut self, tx: VersionedTransaction) -> TransactionResult {
         let result = self.svm.send_transaction(tx);
 
         if self.log_send_transaction_results {
@@ -135,6 +147,42 @@ impl TestFixture {
         Ok(())
     }
 
+    /// Create a durable nonce account advanced by `authority`, returning its address
+    /// and stored nonce value
+    pub fn create_nonce_account(&mut self, authority: &Pubkey) -> (Pubkey, Hash) {
+        let nonce_keypair = Keypair::new();
+        let lamports = self
+            .svm
+            .minimum_balance_for_rent_exemption(NonceState::size());
+        let ixs = create_nonce_account(
+            &self.state.admin_keypair.pubkey(),
+            &nonce_keypair.pubkey(),
+            authority,
+            lamports,
+        );
+        let tx = Transaction::new(
+            &[&self.state.admin_keypair, &nonce_keypair],
+            Message::new(&ixs, Some(&self.state.admin_keypair.pubkey())),
+            self.latest_blockhash(),
+        );
+        self.send_transaction(tx)
+            .unwrap_or_else(|e| panic!("Failed to create nonce account: {:?}", e));
+
+        let account = self
+            .fetch_account(&nonce_keypair.pubkey())
+            .expect("Nonce account missing");
+        let versions: Versions = account.state().expect("Invalid nonce account");
+        match versions.state() {
+            NonceState::Initialized(data) => (nonce_keypair.pubkey(), data.blockhash()),
+            NonceState::Uninitialized => panic!("Nonce account not initialized"),
+        }
+    }
+
+    /// Move to a new blockhash, expiring transactions signed with the current one
+    pub fn expire_blockhash(&mut self) {
+        self.svm.expire_blockhash();
+    }
+
     pub fn jump_to(&mut self, target_stage: FixtureStage) {
         // step all stages:
         // - greater than campaign compiled (initial state)
@@ -204,26 +252,44 @@ impl TestFixture {
         let mut txs = Vec::new();
 
         for cohort in &self.state.compiled_campaign.cohorts {
-            let amount_per_entitlement = cohort
-                .amount_per_entitlement
-                .floor()
-                .to_u64()
-                .expect("Amount too large");
+            let amount_per_entitlement = cohort.amount_per_entitlement_u64;
 
             let expected_vault_count = cohort
                 .vault_count
                 .try_into()
                 .expect("Vault count too large");
 
-            let (ix, _, _) = build_initialize_cohort_v0_ix(
-                &self.state.address_finder,
-                self.state.compiled_campaign.admin,
-                self.state.compiled_campaign.fingerprint,
-                cohort.merkle_root,
-                amount_per_entitlement,
-                expected_vault_count,
-            )
-            .expect("Failed to build initialize cohort v0 ix");
+            // V1 trees commit their arity and hash scheme in a cohort tree account
+            let ix = match &cohort.merkle_tree {
+                CohortMerkleTree::V0(_) => {
+                    build_initialize_cohort_v0_ix(
+                        &self.state.address_finder,
+                        self.state.compiled_campaign.admin,
+                        self.state.compiled_campaign.fingerprint,
+                        cohort.merkle_root,
+                        amount_per_entitlement,
+                        expected_vault_count,
+                    )
+                    .expect("Failed to build initialize cohort v0 ix")
+                    .0
+                }
+                CohortMerkleTree::V1(tree) => {
+                    build_initialize_cohort_v1_ix(
+                        &self.state.address_finder,
+                        InitializeCohortV1Params {
+                            admin: self.state.compiled_campaign.admin,
+                            campaign_fingerprint: self.state.compiled_campaign.fingerprint,
+                            merkle_root: cohort.merkle_root,
+                            amount_per_entitlement,
+                            expected_vault_count,
+                            merkle_tree_arity: tree.arity() as u16,
+                            hash_scheme: tree.hash_scheme(),
+                        },
+                    )
+                    .expect("Failed to build initialize cohort v1 ix")
+                    .0
+                }
+            };
 
             txs.push(Transaction::new(
                 &[&self.state.admin_keypair],
@@ -266,9 +332,7 @@ impl TestFixture {
 
         for cohort in &self.state.compiled_campaign.cohorts {
             for vault in cohort.vaults.iter() {
-                let expected_balance = vault
-                    .required_tokens_u64()
-                    .expect("Required tokens too large");
+                let expected_balance = vault.required_tokens_u64();
 
                 let ix = spl_token::instruction::mint_to(
                     &self.state.address_finder.token_program_id,
@@ -330,15 +394,10 @@ impl TestFixture {
         for cohort in &self.state.compiled_campaign.cohorts {
             for vault in cohort.vaults.iter() {
                 // Use custom amount if specified, otherwise fall back to compiled campaign amount
-                let funding_amount =
-                    custom_amounts
-                        .get(&vault.address)
-                        .copied()
-                        .unwrap_or_else(|| {
-                            vault
-                                .required_tokens_u64()
-                                .expect("Required tokens too large")
-                        });
+                let funding_amount = custom_amounts
+                    .get(&vault.address)
+                    .copied()
+                    .unwrap_or_else(|| vault.required_tokens_u64());
 
                 let ix = spl_token::instruction::mint_to(
                     &self.state.address_finder.token_program_id,
@@ -366,9 +425,7 @@ impl TestFixture {
 
         for cohort in &self.state.compiled_campaign.cohorts {
             for (vault_index, vault) in cohort.vaults.iter().enumerate() {
-                let expected_balance = vault
-                    .required_tokens_u64()
-                    .expect("Required tokens too large");
+                let expected_balance = vault.required_tokens_u64();
 
                 let (ix, _, _) = build_activate_vault_v0_ix(
                     &self.state.address_finder,
@@ -437,11 +494,7 @@ impl TestFixture {
                 let expected_balance = custom_expected_balance
                     .get(&vault.address)
                     .copied()
-                    .unwrap_or_else(|| {
-                        vault
-                            .required_tokens_u64()
-                            .expect("Required tokens too large")
-                    });
+                    .unwrap_or_else(|| vault.required_tokens_u64());
 
                 let (ix, _, _) = build_activate_vault_v0_ix(
                     &self.state.address_finder,
@@ -618,22 +671,47 @@ impl TestFixture {
 
         for (cohort, leaf) in cohorts {
             let merkle_proof = cohort
-                .proof_for_claimant(&claimant.pubkey())
+                .stored_proof_for_claimant(&claimant.pubkey())
                 .expect("Should be able to generate proof");
 
-            let (ix, _, _) = build_claim_tokens_v0_ix(
-                &self.state.address_finder,
-                self.state.compiled_campaign.admin,
-                claimant.pubkey(),
-                self.state.compiled_campaign.mint,
-                claimant_token_account,
-                self.state.compiled_campaign.fingerprint,
-                cohort.merkle_root,
-                merkle_proof,
-                leaf.assigned_vault_index,
-                leaf.entitlements,
-            )
-            .expect("Failed to build claim tokens v0 ix");
+            // V1 cohorts are claimed against their committed tree layout
+            let ix = match merkle_proof {
+                StoredProof::V0(merkle_proof) => {
+                    build_claim_tokens_v0_ix(
+                        &self.state.address_finder,
+                        self.state.compiled_campaign.admin,
+                        claimant.pubkey(),
+                        self.state.compiled_campaign.mint,
+                        claimant_token_account,
+                        self.state.compiled_campaign.fingerprint,
+                        cohort.merkle_root,
+                        merkle_proof,
+                        leaf.assigned_vault_index,
+                        leaf.entitlements,
+                    )
+                    .expect("Failed to build claim tokens v0 ix")
+                    .0
+                }
+                StoredProof::V1(merkle_proof) => {
+                    build_claim_tokens_v2_ix(
+                        &self.state.address_finder,
+                        ClaimV1Params {
+                            admin: self.state.compiled_campaign.admin,
+                            claimant: claimant.pubkey(),
+                            mint: self.state.compiled_campaign.mint,
+                            claimant_token_account,
+                            campaign_fingerprint: self.state.compiled_campaign.fingerprint,
+                            cohort_merkle_root: cohort.merkle_root,
+                            merkle_proof,
+                            assigned_vault_index: leaf.assigned_vault_index,
+                            entitlements: leaf.entitlements,
+                        },
+                    )
+                    .expect("Failed to build claim tokens v2 ix")
+                    .0
+                }
+                proof => panic!("Compiled cohorts don't store {:?}", proof),
+            };
 
             // vault_balances_before.insert(
             //     cohort.vaults[leaf.assigned_vault_index as usize].address,
@@ -705,6 +783,11 @@ impl TestFixture {
             .and_then(|a| CohortV0::try_deserialize(&mut &a.data[..]).ok())
     }
 
+    pub fn fetch_cohort_tree(&self, cohort_tree: &Pubkey) -> Option<CohortTreeV0> {
+        self.fetch_account(cohort_tree)
+            .and_then(|a| CohortTreeV0::try_deserialize(&mut &a.data[..]).ok())
+    }
+
     /// Check if an account exists
     pub fn account_exists(&self, address: &Pubkey) -> bool {
         self.svm.get_account(address).is_some()
@@ -1,6 +1,5 @@
 use prism_protocol_sdk::build_claim_tokens_v0_ix;
 use prism_protocol_testing::{deterministic_keypair, FixtureStage, TestFixture};
-use rust_decimal::prelude::ToPrimitive;
 use solana_message::Message;
 use solana_signer::Signer;
 use solana_transaction::Transaction;
@@ -81,15 +80,14 @@ fn test_claim_amount_exceeds_vault_capacity() {
             .expect("early_adopter_1 should be in EarlyAdopters cohort");
 
         (
-            cohort.amount_per_entitlement,
+            cohort.amount_per_entitlement_u64,
             cohort.vaults[assigned_vault_index as usize].address,
             leaf.entitlements,
         )
     };
 
     let normal_claim_amount = amount_per_entitlement
-        .checked_mul(rust_decimal::Decimal::from(entitlements))
-        .and_then(|d| d.to_u64())
+        .checked_mul(entitlements)
         .expect("Normal claim amount calculation should not overflow");
 
     println!("💰 Normal claim amount: {} tokens", normal_claim_amount);
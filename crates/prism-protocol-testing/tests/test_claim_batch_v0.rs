@@ -0,0 +1,189 @@
+use prism_protocol::error::ErrorCode as PrismError;
+use prism_protocol::ClaimLeaf;
+use prism_protocol_sdk::{build_claim_tokens_batch_v0_ix, CohortMerkleTree, CompiledCohort};
+use prism_protocol_testing::{
+    demand_prism_error, deterministic_keypair, FixtureStage, TestFixture,
+};
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+
+/// Test settling several claims in one transaction with claim_tokens_batch_v0
+///
+/// A relayer pays for the claim receipts while every claimant signs:
+/// - Inflated entitlements → InvalidMerkleProof
+/// - A claim missing its receipt account → ClaimBatchAccountsMismatch
+/// - The batch pays every claimant from their assigned vault and creates their receipts
+/// - Claiming again in a new batch fails, leaving balances unchanged
+#[test]
+fn test_claim_batch_v0() {
+    let mut test = TestFixture::default();
+    test.jump_to(FixtureStage::CampaignActivated);
+    test.advance_slot_by(20);
+
+    let relayer = Keypair::new();
+    test.airdrop(&relayer.pubkey(), 1_000_000_000);
+    let claimants = [
+        deterministic_keypair("team_member_1"),
+        deterministic_keypair("team_member_3"),
+    ];
+    let mint = test.state.compiled_campaign.mint;
+
+    // Batched claims pay into existing token accounts
+    let create_token_accounts: Vec<Instruction> = claimants
+        .iter()
+        .map(|claimant| {
+            create_associated_token_account(
+                &relayer.pubkey(),
+                &claimant.pubkey(),
+                &mint,
+                &test.state.address_finder.token_program_id,
+            )
+        })
+        .collect();
+    test.send_transaction(Transaction::new(
+        &[&relayer],
+        Message::new(&create_token_accounts, Some(&relayer.pubkey())),
+        test.latest_blockhash(),
+    ))
+    .expect("Failed to create claimant token accounts");
+
+    let cohort = test
+        .state
+        .compiled_campaign
+        .find_cohort_by_name("Team")
+        .expect("Team cohort should exist");
+    let claimant_pubkeys: Vec<Pubkey> = claimants.iter().map(|c| c.pubkey()).collect();
+    let (leaves, _) = batch_multiproof(&cohort, &claimant_pubkeys);
+    let claims: Vec<(ClaimLeaf, Pubkey)> = leaves
+        .into_iter()
+        .map(|leaf| {
+            let token_account = get_associated_token_address(&leaf.claimant, &mint);
+            (leaf, token_account)
+        })
+        .collect();
+
+    // 1. Inflated entitlements don't verify
+    let mut inflated = claims.clone();
+    inflated[0].0.entitlements += 1;
+    let ix = build_batch_ix(&test, &cohort, &claims, &inflated, &relayer);
+    demand_prism_error(
+        send_batch(&mut test, ix, &relayer, &claimants),
+        PrismError::InvalidMerkleProof as u32,
+        "InvalidMerkleProof",
+    );
+
+    // 2. Every claim needs all four of its accounts
+    let mut ix = build_batch_ix(&test, &cohort, &claims, &claims, &relayer);
+    ix.accounts.pop();
+    demand_prism_error(
+        send_batch(&mut test, ix, &relayer, &claimants),
+        PrismError::ClaimBatchAccountsMismatch as u32,
+        "ClaimBatchAccountsMismatch",
+    );
+
+    // 3. The batch settles every claim
+    let ix = build_batch_ix(&test, &cohort, &claims, &claims, &relayer);
+    send_batch(&mut test, ix, &relayer, &claimants).expect("Batched claim should succeed");
+
+    for (leaf, token_account) in &claims {
+        let balance = test
+            .get_token_account_balance(token_account)
+            .expect("Claimant token account should exist");
+        assert_eq!(
+            balance,
+            cohort.amount_per_entitlement_u64 * leaf.entitlements,
+            "Claimant should receive their entitlements"
+        );
+
+        let (claim_receipt, _) = test
+            .state
+            .address_finder
+            .find_claim_receipt_v0_address(&cohort.address, &leaf.claimant);
+        let receipt = test
+            .fetch_claim_receipt(&claim_receipt)
+            .expect("ClaimReceipt should exist");
+        assert_eq!(receipt.claimant, leaf.claimant);
+        assert_eq!(receipt.cohort, cohort.address);
+        assert_eq!(
+            receipt.assigned_vault,
+            cohort.vaults[leaf.assigned_vault_index as usize].address
+        );
+    }
+    println!("✅ One transaction settled {} claims", claims.len());
+
+    // 4. A claimant can't be paid twice
+    let first = &claims[..1];
+    let ix = build_batch_ix(&test, &cohort, first, first, &relayer);
+    assert!(
+        send_batch(&mut test, ix, &relayer, &claimants[..1]).is_err(),
+        "Claiming again should fail"
+    );
+    let balance = test
+        .get_token_account_balance(&first[0].1)
+        .expect("Claimant token account should exist");
+    assert_eq!(
+        balance,
+        cohort.amount_per_entitlement_u64 * first[0].0.entitlements
+    );
+
+    println!("✅ Repeated batched claim rejected");
+}
+
+fn batch_multiproof(
+    cohort: &CompiledCohort,
+    claimants: &[Pubkey],
+) -> (Vec<ClaimLeaf>, prism_protocol::ClaimMultiProofV0) {
+    let CohortMerkleTree::V0(tree) = &cohort.merkle_tree else {
+        panic!("The default campaign compiles binary trees");
+    };
+    tree.multiproof_for_claimants(claimants)
+        .expect("Failed to build multiproof")
+}
+
+/// Build a batch proving `proven` claims but claiming `claims`
+fn build_batch_ix(
+    test: &TestFixture,
+    cohort: &CompiledCohort,
+    proven: &[(ClaimLeaf, Pubkey)],
+    claims: &[(ClaimLeaf, Pubkey)],
+    relayer: &Keypair,
+) -> Instruction {
+    let claimants: Vec<Pubkey> = proven.iter().map(|(leaf, _)| leaf.claimant).collect();
+    let (_, multiproof) = batch_multiproof(cohort, &claimants);
+
+    let (ix, _, _) = build_claim_tokens_batch_v0_ix(
+        &test.state.address_finder,
+        test.state.compiled_campaign.admin,
+        relayer.pubkey(),
+        test.state.compiled_campaign.mint,
+        test.state.compiled_campaign.fingerprint,
+        cohort.merkle_root,
+        multiproof,
+        claims,
+    )
+    .expect("Failed to build claim tokens batch v0 ix");
+    ix
+}
+
+fn send_batch(
+    test: &mut TestFixture,
+    ix: Instruction,
+    relayer: &Keypair,
+    claimants: &[Keypair],
+) -> litesvm::types::TransactionResult {
+    let mut signers = vec![relayer];
+    signers.extend(claimants);
+    let tx = Transaction::new(
+        &signers,
+        Message::new(&[ix], Some(&relayer.pubkey())),
+        test.latest_blockhash(),
+    );
+    test.send_transaction(tx)
+}
@@ -4,7 +4,6 @@ use prism_protocol_testing::{
     demand_prism_error, deterministic_keypair, AccountChange, CampaignSnapshot, FixtureStage,
     TestFixture,
 };
-use rust_decimal::prelude::ToPrimitive;
 use solana_message::Message;
 use solana_sdk::compute_budget::ComputeBudgetInstruction;
 use solana_signer::Signer as _;
@@ -230,8 +229,7 @@ fn test_claim_before_go_live() {
     let state_after_claim = CampaignSnapshot::capture_with_claimants(&test, &[claimant_pubkey]);
 
     // Calculate expected claim amount
-    let expected_claim_amount =
-        cohort.amount_per_entitlement.floor().to_u64().unwrap() * leaf.entitlements;
+    let expected_claim_amount = cohort.amount_per_entitlement_u64 * leaf.entitlements;
 
     println!("🔬 Performing surgical verification of claim operation:");
     println!("   Expected claim amount: {}", expected_claim_amount);
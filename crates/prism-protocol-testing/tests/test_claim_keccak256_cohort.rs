@@ -0,0 +1,133 @@
+use prism_protocol::error::ErrorCode as PrismError;
+use prism_protocol::ClaimHashScheme;
+use prism_protocol_sdk::{build_claim_tokens_v2_ix, ClaimV1Params, CompileOptions, StoredProof};
+use prism_protocol_testing::{
+    demand_prism_error, deterministic_keypair, FixtureStage, FixtureState, TestFixture,
+};
+use solana_message::Message;
+use solana_signer::Signer as _;
+use solana_transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address;
+
+/// Test that claims are verified with the hash scheme committed in the cohort tree
+///
+/// The default campaign is compiled into Keccak-256 V1 trees:
+/// - Verify every cohort tree committed its arity and Keccak256
+/// - Claim with a SHA-256 proof of the same leaf → InvalidMerkleProof, no ClaimReceipt
+/// - Claim with the compiled Keccak-256 proof → tokens transferred, ClaimReceipt created
+#[test]
+fn test_claim_keccak256_cohort() {
+    let cohort_names: Vec<String> = FixtureState::default()
+        .compiled_campaign
+        .cohorts
+        .iter()
+        .map(|cohort| cohort.name.clone())
+        .collect();
+    let v1_options = |hash_scheme| CompileOptions {
+        tree_arities: cohort_names.iter().map(|name| (name.clone(), 4)).collect(),
+        hash_scheme,
+        ..Default::default()
+    };
+    let mut test = TestFixture::new(
+        FixtureState::with_compile_options(&v1_options(ClaimHashScheme::Keccak256)),
+        litesvm::LiteSVM::new(),
+    )
+    .expect("Failed to create Keccak-256 test fixture");
+    test.jump_to(FixtureStage::CampaignActivated);
+    test.advance_slot_by(20);
+
+    let (campaign, _) = test.state.address_finder.find_campaign_v0_address(
+        &test.state.compiled_campaign.admin,
+        &test.state.compiled_campaign.fingerprint,
+    );
+    for cohort in &test.state.compiled_campaign.cohorts {
+        let (cohort_address, _) = test
+            .state
+            .address_finder
+            .find_cohort_v0_address(&campaign, &cohort.merkle_root);
+        let (cohort_tree, _) = test
+            .state
+            .address_finder
+            .find_cohort_tree_v0_address(&cohort_address);
+        let account = test
+            .fetch_cohort_tree(&cohort_tree)
+            .expect("Cohort tree should exist");
+        assert_eq!(account.merkle_tree_arity, 4);
+        assert_eq!(account.hash_scheme, ClaimHashScheme::Keccak256);
+    }
+    println!("✅ Cohort trees committed the Keccak-256 hash scheme");
+
+    let claimant_keypair = deterministic_keypair("early_adopter_1");
+    let claimant = claimant_keypair.pubkey();
+    test.airdrop(&claimant, 1_000_000_000);
+
+    let (cohort, leaf) = test
+        .state
+        .compiled_campaign
+        .find_claimant_in_cohort(&claimant, "EarlyAdopters")
+        .expect("early_adopter_1 should be in EarlyAdopters cohort");
+    let (cohort_address, _) = test
+        .state
+        .address_finder
+        .find_cohort_v0_address(&campaign, &cohort.merkle_root);
+    let (claim_receipt, _) = test
+        .state
+        .address_finder
+        .find_claim_receipt_v0_address(&cohort_address, &claimant);
+
+    // The same campaign compiled with SHA-256 has the same leaves but other hashes
+    let sha256_campaign =
+        FixtureState::with_compile_options(&v1_options(ClaimHashScheme::Sha256)).compiled_campaign;
+    let (sha256_cohort, sha256_leaf) = sha256_campaign
+        .find_claimant_in_cohort(&claimant, "EarlyAdopters")
+        .expect("early_adopter_1 should be in EarlyAdopters cohort");
+    assert_eq!(sha256_leaf, leaf);
+    let StoredProof::V1(sha256_proof) = sha256_cohort
+        .stored_proof_for_claimant(&claimant)
+        .expect("Failed to build SHA-256 proof")
+    else {
+        panic!("EarlyAdopters should compile into a V1 tree");
+    };
+
+    let token_account = get_associated_token_address(&claimant, &test.state.compiled_campaign.mint);
+    let (ix, _, _) = build_claim_tokens_v2_ix(
+        &test.state.address_finder,
+        ClaimV1Params {
+            admin: test.state.compiled_campaign.admin,
+            claimant,
+            mint: test.state.compiled_campaign.mint,
+            claimant_token_account: token_account,
+            campaign_fingerprint: test.state.compiled_campaign.fingerprint,
+            cohort_merkle_root: cohort.merkle_root,
+            merkle_proof: sha256_proof,
+            assigned_vault_index: leaf.assigned_vault_index,
+            entitlements: leaf.entitlements,
+        },
+    )
+    .expect("Failed to build claim tokens v2 ix");
+    let tx = Transaction::new(
+        &[&claimant_keypair],
+        Message::new(&[ix], Some(&claimant)),
+        test.latest_blockhash(),
+    );
+    demand_prism_error(
+        test.send_transaction(tx),
+        PrismError::InvalidMerkleProof as u32,
+        "InvalidMerkleProof",
+    );
+    assert!(!test.account_exists(&claim_receipt));
+    println!("✅ SHA-256 proof rejected by a Keccak-256 cohort");
+
+    test.try_claim_tokens(&claimant_keypair)
+        .expect("Keccak-256 claim should succeed");
+    let balance = test
+        .get_token_account_balance(&token_account)
+        .expect("Claimant token account should exist");
+    assert_eq!(
+        balance,
+        cohort.amount_per_entitlement_u64 * leaf.entitlements
+    );
+    assert!(test.account_exists(&claim_receipt));
+
+    println!("✅ Keccak-256 proof claimed with claim_tokens_v2");
+}
@@ -150,6 +150,7 @@ fn create_extreme_value_test_fixture() -> TestFixture {
     let cohorts_rows = vec![CohortsCsvRow {
         cohort: "ExtremeValueCohort".to_string(),
         share_percentage: Decimal::from(100), // 100% of budget
+        ..Default::default()
     }];
 
     let compiled_campaign = compile_campaign(
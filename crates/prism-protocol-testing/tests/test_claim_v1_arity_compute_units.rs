@@ -0,0 +1,206 @@
+use prism_protocol::error::ErrorCode as PrismError;
+use prism_protocol_sdk::{build_claim_tokens_v1_ix, ClaimCostModel, ClaimV1Params};
+use prism_protocol_testing::{
+    demand_prism_error, deterministic_keypair, FixtureStage, TestFixture,
+};
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address;
+
+/// Largest relative error tolerated between the cost model and LiteSVM
+const TOLERANCE: f64 = 0.25;
+
+/// Proof shapes measured: (siblings per level, levels). All fit in one transaction.
+const SHAPES: &[(usize, usize)] = &[(1, 1), (1, 8), (1, 16), (3, 4), (7, 3), (15, 1), (23, 1)];
+
+/// Test claim_tokens_v1 compute units against the SDK tree arity cost model
+///
+/// The arity optimizer (`prism_protocol_sdk::tree_arity`) picks cohort tree layouts
+/// from a linear CU model. This measures the real program in LiteSVM:
+/// 1. Verification cost per proof shape, as the difference between a claim with a
+///    proof of that shape and one with an empty proof (both fail at the root check,
+///    so everything else cancels out); `claim_tokens_v1` accepts up to 255 siblings per level
+/// 2. A complete successful claim through `claim_tokens_v1` in a binary cohort
+///
+/// Both must be within 25% of the model. The measurements are printed so the
+/// `ClaimCostModel` defaults can be recalibrated when the claim path changes.
+#[test]
+fn test_claim_v1_arity_compute_units() {
+    let model = ClaimCostModel::default();
+    let claimant_keypair = deterministic_keypair("early_adopter_1");
+
+    // 1. Verification cost per proof shape
+    let mut test = TestFixture::default();
+    test.jump_to(FixtureStage::CampaignActivated);
+    test.advance_slot_by(20);
+    test.disable_send_transaction_logging();
+    test.airdrop(&claimant_keypair.pubkey(), 1_000_000_000);
+
+    let baseline = measure_failed_claim(&mut test, &claimant_keypair, vec![]);
+    println!("📊 Empty proof (failed claim): {} CU", baseline);
+    println!("\n  siblings × levels | measured CU | model CU | error");
+
+    for (index, &(siblings, levels)) in SHAPES.iter().enumerate() {
+        let proof: Vec<Vec<[u8; 32]>> = (0..levels)
+            .map(|level| {
+                (0..siblings)
+                    .map(|sibling| [index as u8, level as u8, sibling as u8, 0xAA].repeat(8))
+                    .map(|bytes| bytes.try_into().unwrap())
+                    .collect()
+            })
+            .collect();
+
+        let measured = measure_failed_claim(&mut test, &claimant_keypair, proof) - baseline;
+        let modelled = model.verification_compute_units(&vec![siblings; levels]);
+        let error = relative_error(measured, modelled);
+        println!(
+            "  {:>8} × {:<6} | {:>11} | {:>8} | {:>5.1}%",
+            siblings,
+            levels,
+            measured,
+            modelled,
+            error * 100.0
+        );
+        assert!(
+            error <= TOLERANCE,
+            "Model is off by {:.1}% for {} siblings × {} levels; recalibrate ClaimCostModel",
+            error * 100.0,
+            siblings,
+            levels
+        );
+    }
+
+    // 2. A complete claim through claim_tokens_v1 in a binary cohort
+    let mut test = TestFixture::default();
+    test.jump_to(FixtureStage::CampaignActivated);
+    test.advance_slot_by(20);
+    test.airdrop(&claimant_keypair.pubkey(), 1_000_000_000);
+
+    let claimant = claimant_keypair.pubkey();
+    let (cohort, leaf) = test
+        .state
+        .compiled_campaign
+        .find_claimant_in_cohort(&claimant, "EarlyAdopters")
+        .expect("early_adopter_1 should be in EarlyAdopters cohort");
+    let binary_proof = cohort
+        .proof_for_claimant(&claimant)
+        .expect("Should be able to generate proof");
+
+    // A binary proof is a wide proof with one sibling per level
+    let levels = binary_proof.len();
+    let proof = binary_proof
+        .into_iter()
+        .map(|sibling| vec![sibling])
+        .collect();
+    let (ix, _, _) = build_claim_tokens_v1_ix(
+        &test.state.address_finder,
+        ClaimV1Params {
+            admin: test.state.compiled_campaign.admin,
+            claimant,
+            mint: test.state.compiled_campaign.mint,
+            claimant_token_account: get_associated_token_address(
+                &claimant,
+                &test.state.compiled_campaign.mint,
+            ),
+            campaign_fingerprint: test.state.compiled_campaign.fingerprint,
+            cohort_merkle_root: cohort.merkle_root,
+            merkle_proof: proof,
+            assigned_vault_index: leaf.assigned_vault_index,
+            entitlements: leaf.entitlements,
+        },
+    )
+    .expect("Failed to build claim tokens v1 ix");
+
+    let tx = Transaction::new(
+        &[&claimant_keypair],
+        Message::new(&[ix], Some(&claimant)),
+        test.latest_blockhash(),
+    );
+    let measured = test
+        .send_transaction(tx)
+        .expect("Binary proof should claim through claim_tokens_v1")
+        .compute_units_consumed;
+    let modelled = model.base_compute_units + model.verification_compute_units(&vec![1; levels]);
+    let error = relative_error(measured, modelled);
+    println!(
+        "\n📊 Successful claim ({} levels): measured {} CU, model {} CU, error {:.1}%",
+        levels,
+        measured,
+        modelled,
+        error * 100.0
+    );
+    assert!(
+        error <= TOLERANCE,
+        "Model base cost is off by {:.1}%; recalibrate ClaimCostModel",
+        error * 100.0
+    );
+
+    println!(
+        "\n🎉 Claim cost model matches LiteSVM within {}%",
+        TOLERANCE * 100.0
+    );
+}
+
+/// Compute units consumed by a claim that fails proof verification
+fn measure_failed_claim(
+    test: &mut TestFixture,
+    claimant_keypair: &Keypair,
+    proof: Vec<Vec<[u8; 32]>>,
+) -> u64 {
+    let claimant = claimant_keypair.pubkey();
+    let (cohort, leaf) = test
+        .state
+        .compiled_campaign
+        .find_claimant_in_cohort(&claimant, "EarlyAdopters")
+        .expect("early_adopter_1 should be in EarlyAdopters cohort");
+
+    let (ix, _, _) = build_claim_tokens_v1_ix(
+        &test.state.address_finder,
+        ClaimV1Params {
+            admin: test.state.compiled_campaign.admin,
+            claimant,
+            mint: test.state.compiled_campaign.mint,
+            claimant_token_account: get_associated_token_address(
+                &claimant,
+                &test.state.compiled_campaign.mint,
+            ),
+            campaign_fingerprint: test.state.compiled_campaign.fingerprint,
+            cohort_merkle_root: cohort.merkle_root,
+            merkle_proof: proof,
+            assigned_vault_index: leaf.assigned_vault_index,
+            entitlements: leaf.entitlements,
+        },
+    )
+    .expect("Failed to build claim tokens v1 ix");
+
+    let tx = Transaction::new(
+        &[claimant_keypair],
+        Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                ix,
+            ],
+            Some(&claimant),
+        ),
+        test.latest_blockhash(),
+    );
+
+    let result = test.send_transaction(tx);
+    let consumed = match &result {
+        Ok(_) => panic!("A made-up proof should not verify"),
+        Err(failed) => failed.meta.compute_units_consumed,
+    };
+    demand_prism_error(
+        result,
+        PrismError::InvalidMerkleProof as u32,
+        "InvalidMerkleProof",
+    );
+    consumed
+}
+
+fn relative_error(measured: u64, modelled: u64) -> f64 {
+    (measured as f64 - modelled as f64).abs() / measured as f64
+}
@@ -1,6 +1,5 @@
 use prism_protocol_sdk::build_claim_tokens_v0_ix;
 use prism_protocol_testing::{deterministic_keypair, FixtureStage, TestFixture};
-use rust_decimal::prelude::ToPrimitive;
 use solana_message::Message;
 use solana_signer::Signer;
 use solana_transaction::Transaction;
@@ -77,14 +76,13 @@ fn test_claim_vault_balance_insufficient() {
             .expect("early_adopter_1 should be in EarlyAdopters cohort");
 
         (
-            cohort.amount_per_entitlement,
+            cohort.amount_per_entitlement_u64,
             cohort.vaults[assigned_vault_index as usize].address,
         )
     };
 
     let expected_claim_amount = amount_per_entitlement
-        .checked_mul(rust_decimal::Decimal::from(entitlements))
-        .and_then(|d| d.to_u64())
+        .checked_mul(entitlements)
         .expect("Claim amount calculation overflow");
     println!("💰 Expected claim amount: {} tokens", expected_claim_amount);
     println!("🎯 Target vault: {}", vault_address);
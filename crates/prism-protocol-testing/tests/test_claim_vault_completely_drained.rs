@@ -1,6 +1,5 @@
 use prism_protocol_sdk::build_claim_tokens_v0_ix;
 use prism_protocol_testing::{deterministic_keypair, FixtureStage, TestFixture};
-use rust_decimal::prelude::ToPrimitive;
 use solana_message::Message;
 use solana_signer::Signer;
 use solana_transaction::Transaction;
@@ -94,9 +93,8 @@ fn test_claim_vault_completely_drained() {
             .expect("early_adopter_1 should be in EarlyAdopters cohort");
 
         cohort
-            .amount_per_entitlement
-            .checked_mul(rust_decimal::Decimal::from(entitlements))
-            .and_then(|d| d.to_u64())
+            .amount_per_entitlement_u64
+            .checked_mul(entitlements)
             .expect("Claim amount calculation overflow")
     };
 
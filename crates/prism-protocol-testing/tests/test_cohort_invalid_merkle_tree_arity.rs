@@ -0,0 +1,85 @@
+use prism_protocol::claim_tree_constants::{MAX_TREE_ARITY, MIN_TREE_ARITY};
+use prism_protocol::error::ErrorCode as PrismError;
+use prism_protocol::ClaimHashScheme;
+use prism_protocol_sdk::{build_initialize_cohort_v1_ix, InitializeCohortV1Params};
+use prism_protocol_testing::{demand_prism_error, FixtureStage, TestFixture};
+use solana_message::Message;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+/// Test cohort initialization with an out-of-range merkle tree arity → InvalidMerkleTreeArity
+///
+/// `initialize_cohort_v1` commits the arity in the cohort tree account, where it bounds
+/// every `claim_tokens_v2` proof level, so it must be a tree the program can verify:
+/// 2 (binary) to 256 children per node.
+#[test]
+fn test_cohort_invalid_merkle_tree_arity() {
+    let mut test = TestFixture::default();
+    test.jump_to(FixtureStage::CampaignInitialized);
+
+    let cohort = &test.state.compiled_campaign.cohorts[0];
+    let merkle_root = cohort.merkle_root;
+    let amount_per_entitlement = cohort.amount_per_entitlement_u64;
+    let expected_vault_count = cohort
+        .vault_count
+        .try_into()
+        .expect("Vault count too large");
+
+    let initialize_cohort = |test: &TestFixture, merkle_tree_arity: u16| {
+        let (ix, _, _) = build_initialize_cohort_v1_ix(
+            &test.state.address_finder,
+            InitializeCohortV1Params {
+                admin: test.state.compiled_campaign.admin,
+                campaign_fingerprint: test.state.compiled_campaign.fingerprint,
+                merkle_root,
+                amount_per_entitlement,
+                expected_vault_count,
+                merkle_tree_arity,
+                hash_scheme: ClaimHashScheme::Sha256,
+            },
+        )
+        .expect("Failed to build initialize cohort v1 ix");
+
+        Transaction::new(
+            &[&test.state.admin_keypair],
+            Message::new(&[ix], Some(&test.state.admin_keypair.pubkey())),
+            test.latest_blockhash(),
+        )
+    };
+
+    for arity in [0, MIN_TREE_ARITY - 1, MAX_TREE_ARITY + 1, u16::MAX] {
+        println!("🧪 Initializing cohort with arity {}...", arity);
+        demand_prism_error(
+            test.send_transaction(initialize_cohort(&test, arity)),
+            PrismError::InvalidMerkleTreeArity as u32,
+            "InvalidMerkleTreeArity",
+        );
+    }
+
+    // The widest valid arity is accepted and committed
+    test.send_transaction(initialize_cohort(&test, MAX_TREE_ARITY))
+        .expect("Arity 256 should be accepted");
+
+    let (campaign, _) = test.state.address_finder.find_campaign_v0_address(
+        &test.state.compiled_campaign.admin,
+        &test.state.compiled_campaign.fingerprint,
+    );
+    let (cohort_address, _) = test
+        .state
+        .address_finder
+        .find_cohort_v0_address(&campaign, &merkle_root);
+    let (cohort_tree_address, _) = test
+        .state
+        .address_finder
+        .find_cohort_tree_v0_address(&cohort_address);
+    let cohort_tree = test
+        .fetch_cohort_tree(&cohort_tree_address)
+        .expect("Cohort tree should exist");
+    assert_eq!(cohort_tree.cohort, cohort_address);
+    assert_eq!(cohort_tree.merkle_tree_arity, MAX_TREE_ARITY);
+
+    println!(
+        "✅ Cohort tree committed merkle tree arity {}",
+        MAX_TREE_ARITY
+    );
+}
@@ -32,9 +32,7 @@ fn test_non_admin_cannot_activate_vault() {
     let cohort_merkle_root = first_cohort.merkle_root;
     let first_vault = &first_cohort.vaults[0];
     let vault_index = 0u8;
-    let expected_balance = first_vault
-        .required_tokens_u64()
-        .expect("Required tokens too large");
+    let expected_balance = first_vault.required_tokens_u64();
     let vault_address = first_vault.address; // Extract address to avoid borrow conflict
 
     // Attacker knows all public parameters and constructs instruction with THEIR key
@@ -1,6 +1,5 @@
 use prism_protocol_sdk::build_initialize_cohort_v0_ix;
 use prism_protocol_testing::{FixtureStage, TestFixture};
-use rust_decimal::prelude::ToPrimitive;
 use solana_instruction::error::InstructionError;
 use solana_keypair::Keypair;
 use solana_message::Message;
@@ -31,11 +30,7 @@ fn test_non_admin_cannot_initialize_cohort() {
     let campaign_fingerprint = test.state.compiled_campaign.fingerprint;
     let first_cohort = &test.state.compiled_campaign.cohorts[0];
     let cohort_merkle_root = first_cohort.merkle_root;
-    let amount_per_entitlement = first_cohort
-        .amount_per_entitlement
-        .floor()
-        .to_u64()
-        .expect("Amount too large");
+    let amount_per_entitlement = first_cohort.amount_per_entitlement_u64;
     let expected_vault_count = first_cohort
         .vault_count
         .try_into()
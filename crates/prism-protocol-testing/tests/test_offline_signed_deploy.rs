@@ -0,0 +1,111 @@
+use {
+    prism_protocol_sdk::{
+        decode_signed_transaction, encode_signed_transaction, plan_deployment, sign_with_nonce,
+        CampaignActivation, CampaignStatus, ComputeBudgetConfig, DurableNonce, PriorityFee,
+    },
+    prism_protocol_testing::{FixtureState, TestFixture},
+    solana_signer::Signer as _,
+    spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account_idempotent,
+    },
+};
+
+/// Test a campaign deploy signed offline against durable nonces, then broadcast
+///
+/// Should test:
+/// - Plan every deploy transaction from the compiled campaign database
+/// - Sign each against its own durable nonce, with no access to the chain
+/// - Broadcast them in order once the blockhash they were signed near has expired
+/// - Verify every vault is funded and the campaign ends up active
+#[test]
+fn test_offline_signed_deploy() {
+    let mut test = TestFixture::default();
+    let db = FixtureState::default_campaign_database();
+    let admin = test.state.admin_keypair.pubkey();
+    let mint = test.state.compiled_campaign.mint;
+
+    // Vaults are funded from the admin's token account
+    let total_tokens: u64 = db
+        .read_vault_requirements()
+        .expect("Failed to read vault requirements")
+        .iter()
+        .map(|v| v.required_tokens)
+        .sum();
+    let admin_token_account = get_associated_token_address(&admin, &mint);
+    test.send_instructions(&[
+        create_associated_token_account_idempotent(&admin, &admin, &mint, &spl_token::ID),
+        spl_token::instruction::mint_to(
+            &spl_token::ID,
+            &mint,
+            &admin_token_account,
+            &admin,
+            &[],
+            total_tokens,
+        )
+        .expect("Failed to build mint_to ix"),
+    ])
+    .expect("Failed to fund admin token account");
+
+    let activation = CampaignActivation {
+        campaign_db_ipfs_hash: [1; 32],
+        go_live_slot: test.current_slot() + 10,
+    };
+    let plan = plan_deployment(&test.state.address_finder, &db, Some(activation))
+        .expect("Failed to plan deployment");
+
+    // Offline: one nonce per transaction, signed without a recent blockhash
+    let nonces: Vec<_> = plan
+        .iter()
+        .map(|_| test.create_nonce_account(&admin))
+        .collect();
+    let budget = ComputeBudgetConfig::with_priority_fee(PriorityFee::None);
+    let signed: Vec<String> = plan
+        .iter()
+        .zip(&nonces)
+        .map(|(tx, (account, blockhash))| {
+            let nonce = DurableNonce {
+                account: *account,
+                authority: admin,
+                blockhash: *blockhash,
+            };
+            let transaction = sign_with_nonce(
+                &tx.instructions,
+                &admin,
+                &[&test.state.admin_keypair],
+                &nonce,
+                &[],
+                &budget,
+            )
+            .expect("Failed to sign offline");
+            encode_signed_transaction(&transaction).expect("Failed to encode transaction")
+        })
+        .collect();
+
+    // Online, later: the blockhash current at signing time has long expired
+    test.expire_blockhash();
+    for (tx, encoded) in plan.iter().zip(&signed) {
+        let transaction = decode_signed_transaction(encoded).expect("Failed to decode");
+        test.send_versioned_transaction(transaction)
+            .unwrap_or_else(|e| panic!("{} failed: {:?}", tx.description, e));
+    }
+
+    for cohort in &test.state.compiled_campaign.cohorts {
+        for vault in &cohort.vaults {
+            assert_eq!(
+                test.get_token_account_balance(&vault.address).unwrap(),
+                vault.required_tokens_u64()
+            );
+        }
+    }
+    assert_eq!(
+        test.get_token_account_balance(&admin_token_account)
+            .unwrap(),
+        0
+    );
+
+    let campaign = test
+        .fetch_campaign_account()
+        .expect("Campaign should exist");
+    assert!(matches!(campaign.status, CampaignStatus::Active));
+    assert_eq!(campaign.go_live_slot, activation.go_live_slot);
+}
@@ -0,0 +1,320 @@
+/*!
+# Compute Budget
+
+Transaction assembly helpers for sizing the compute unit limit and setting a priority fee.
+
+Sizing is a two-step process driven by whoever owns the RPC connection:
+
+1. Simulate [`simulation_instructions`] (the instructions with the maximum CU limit, so
+   large V0 proofs aren't cut off at the default 200k per instruction)
+2. Prepend the real budget with [`ComputeBudgetConfig::compute_budget_instructions`],
+   using the simulated units plus a safety margin and the configured priority fee
+
+Estimated priority fees are a percentile of `getRecentPrioritizationFees` samples for
+the transaction's writable accounts (see [`writable_accounts`]). An estimate of zero
+(no samples, or no recent fees) adds no compute unit price instruction.
+*/
+
+use solana_sdk::{
+    compute_budget::{self, ComputeBudgetInstruction},
+    instruction::Instruction,
+    pubkey::Pubkey,
+};
+use std::{fmt, str::FromStr};
+
+/// Maximum compute units a transaction may request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Default headroom added to simulated compute units
+pub const DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT: u32 = 10;
+
+/// Default percentile of recent prioritization fees used by `auto`
+pub const DEFAULT_PRIORITY_FEE_PERCENTILE: u8 = 75;
+
+/// Maximum accounts accepted by `getRecentPrioritizationFees`
+pub const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// How to choose the compute unit price (micro-lamports per CU)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriorityFee {
+    /// No priority fee
+    None,
+
+    /// A fixed price in micro-lamports per compute unit
+    Fixed(u64),
+
+    /// A percentile of recent prioritization fees for the transaction's writable accounts
+    #[default]
+    Estimated,
+
+    /// Like `Estimated`, with an explicit percentile (0-100)
+    Percentile(u8),
+}
+
+impl PriorityFee {
+    /// Whether this fee needs `getRecentPrioritizationFees` samples
+    pub fn needs_samples(&self) -> bool {
+        matches!(self, PriorityFee::Estimated | PriorityFee::Percentile(_))
+    }
+
+    /// Resolve the compute unit price from recent fee samples; `None` when no price
+    /// instruction should be added
+    pub fn resolve(&self, recent_fees: &[u64]) -> Option<u64> {
+        let estimated = match self {
+            PriorityFee::None => return None,
+            PriorityFee::Fixed(price) => return Some(*price),
+            PriorityFee::Estimated => {
+                estimate_priority_fee(recent_fees, DEFAULT_PRIORITY_FEE_PERCENTILE)
+            }
+            PriorityFee::Percentile(percentile) => estimate_priority_fee(recent_fees, *percentile),
+        };
+        (estimated > 0).then_some(estimated)
+    }
+}
+
+impl fmt::Display for PriorityFee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PriorityFee::None => f.write_str("none"),
+            PriorityFee::Fixed(price) => write!(f, "{}", price),
+            PriorityFee::Estimated => f.write_str("auto"),
+            PriorityFee::Percentile(percentile) => write!(f, "auto:{}", percentile),
+        }
+    }
+}
+
+impl FromStr for PriorityFee {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(PriorityFee::None),
+            "auto" => Ok(PriorityFee::Estimated),
+            _ => match s.strip_prefix("auto:") {
+                Some(percentile) => match percentile.parse::<u8>() {
+                    Ok(percentile) if percentile <= 100 => Ok(PriorityFee::Percentile(percentile)),
+                    _ => Err(format!(
+                        "Invalid priority fee percentile '{}' (expected 0-100)",
+                        percentile
+                    )),
+                },
+                None => s.parse::<u64>().map(PriorityFee::Fixed).map_err(|_| {
+                    format!(
+                        "Unknown priority fee '{}' (expected none, auto, auto:<percentile> or micro-lamports per CU)",
+                        s
+                    )
+                }),
+            },
+        }
+    }
+}
+
+/// Compute budget settings applied when assembling a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputeBudgetConfig {
+    /// Simulate to size the compute unit limit (otherwise the runtime default applies)
+    pub simulate_compute_units: bool,
+
+    /// Headroom added to simulated units, in percent
+    pub compute_unit_margin_percent: u32,
+
+    /// How to choose the compute unit price
+    pub priority_fee: PriorityFee,
+}
+
+impl Default for ComputeBudgetConfig {
+    fn default() -> Self {
+        Self {
+            simulate_compute_units: true,
+            compute_unit_margin_percent: DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT,
+            priority_fee: PriorityFee::default(),
+        }
+    }
+}
+
+impl ComputeBudgetConfig {
+    /// Defaults with the given priority fee
+    pub fn with_priority_fee(priority_fee: PriorityFee) -> Self {
+        Self {
+            priority_fee,
+            ..Self::default()
+        }
+    }
+
+    /// Compute unit limit for a simulated consumption, margin included
+    pub fn compute_unit_limit(&self, simulated_units: u64) -> u32 {
+        let margin = simulated_units.saturating_mul(self.compute_unit_margin_percent as u64) / 100;
+        simulated_units
+            .saturating_add(margin)
+            .min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+    }
+
+    /// The final instruction list: budget instructions, then `instructions` (minus any
+    /// budget instructions they already carried)
+    pub fn compute_budget_instructions(
+        &self,
+        instructions: &[Instruction],
+        simulated_units: Option<u64>,
+        recent_fees: &[u64],
+    ) -> Vec<Instruction> {
+        with_compute_budget(
+            instructions,
+            simulated_units.map(|units| self.compute_unit_limit(units)),
+            self.priority_fee.resolve(recent_fees),
+        )
+    }
+}
+
+/// Prepend compute budget instructions, replacing any the instructions already carry
+pub fn with_compute_budget(
+    instructions: &[Instruction],
+    unit_limit: Option<u32>,
+    unit_price: Option<u64>,
+) -> Vec<Instruction> {
+    let mut budgeted = Vec::with_capacity(instructions.len() + 2);
+    if let Some(limit) = unit_limit {
+        budgeted.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    }
+    if let Some(price) = unit_price {
+        budgeted.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    budgeted.extend(
+        instructions
+            .iter()
+            .filter(|ix| ix.program_id != compute_budget::id())
+            .cloned(),
+    );
+    budgeted
+}
+
+/// Instructions to simulate when sizing the compute unit limit
+pub fn simulation_instructions(instructions: &[Instruction]) -> Vec<Instruction> {
+    with_compute_budget(instructions, Some(MAX_COMPUTE_UNIT_LIMIT), None)
+}
+
+/// Writable accounts to sample prioritization fees for (deduplicated, capped at the RPC limit)
+pub fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = Vec::new();
+    for meta in instructions.iter().flat_map(|ix| &ix.accounts) {
+        if meta.is_writable && !accounts.contains(&meta.pubkey) {
+            accounts.push(meta.pubkey);
+        }
+    }
+    accounts.truncate(MAX_PRIORITIZATION_FEE_ACCOUNTS);
+    accounts
+}
+
+/// Percentile (0-100) of recent prioritization fees; 0 with no samples
+pub fn estimate_priority_fee(recent_fees: &[u64], percentile: u8) -> u64 {
+    if recent_fees.is_empty() {
+        return 0;
+    }
+
+    let mut fees = recent_fees.to_vec();
+    fees.sort_unstable();
+    let rank = (fees.len() - 1) * percentile.min(100) as usize / 100;
+    fees[rank]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    fn ix(accounts: Vec<AccountMeta>) -> Instruction {
+        Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts,
+            data: vec![],
+        }
+    }
+
+    #[test]
+    fn test_priority_fee_parse_roundtrip() {
+        for fee in [
+            PriorityFee::None,
+            PriorityFee::Fixed(5_000),
+            PriorityFee::Estimated,
+            PriorityFee::Percentile(90),
+        ] {
+            assert_eq!(PriorityFee::from_str(&fee.to_string()).unwrap(), fee);
+        }
+
+        assert!(PriorityFee::from_str("auto:101").is_err());
+        assert!(PriorityFee::from_str("fast").is_err());
+    }
+
+    #[test]
+    fn test_priority_fee_resolve() {
+        assert_eq!(PriorityFee::None.resolve(&[10]), None);
+        assert_eq!(PriorityFee::Fixed(0).resolve(&[]), Some(0));
+        assert_eq!(PriorityFee::Estimated.resolve(&[]), None);
+        assert_eq!(PriorityFee::Estimated.resolve(&[0, 0]), None);
+        assert_eq!(PriorityFee::Percentile(100).resolve(&[0, 5]), Some(5));
+    }
+
+    #[test]
+    fn test_estimate_priority_fee() {
+        assert_eq!(estimate_priority_fee(&[], 75), 0);
+        assert_eq!(estimate_priority_fee(&[10, 0, 30, 20, 40], 50), 20);
+        assert_eq!(estimate_priority_fee(&[10, 0, 30, 20, 40], 100), 40);
+        assert_eq!(estimate_priority_fee(&[10, 0, 30, 20, 40], 0), 0);
+    }
+
+    #[test]
+    fn test_compute_unit_limit_applies_margin_and_cap() {
+        let config = ComputeBudgetConfig::default();
+        assert_eq!(config.compute_unit_limit(100_000), 110_000);
+        assert_eq!(config.compute_unit_limit(1_390_000), MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn test_compute_budget_instructions_replace_existing() {
+        let payload = ix(vec![]);
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            payload.clone(),
+        ];
+
+        let config = ComputeBudgetConfig::with_priority_fee(PriorityFee::Fixed(1_000));
+        let budgeted = config.compute_budget_instructions(&instructions, Some(50_000), &[]);
+
+        assert_eq!(
+            budgeted,
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(55_000),
+                ComputeBudgetInstruction::set_compute_unit_price(1_000),
+                payload.clone(),
+            ]
+        );
+
+        // Without simulation or a fee, only the payload remains
+        let config = ComputeBudgetConfig::with_priority_fee(PriorityFee::None);
+        assert_eq!(
+            config.compute_budget_instructions(&instructions, None, &[]),
+            vec![payload.clone()]
+        );
+
+        // An estimate with no fee samples adds no price instruction
+        let config = ComputeBudgetConfig::default();
+        assert_eq!(
+            config.compute_budget_instructions(&instructions, None, &[]),
+            vec![payload]
+        );
+    }
+
+    #[test]
+    fn test_writable_accounts_dedup() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let instructions = vec![
+            ix(vec![
+                AccountMeta::new(a, true),
+                AccountMeta::new_readonly(b, false),
+            ]),
+            ix(vec![AccountMeta::new(a, false), AccountMeta::new(b, false)]),
+        ];
+
+        assert_eq!(writable_accounts(&instructions), vec![a, b]);
+    }
+}
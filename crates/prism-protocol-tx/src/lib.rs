@@ -0,0 +1,18 @@
+/*!
+# Prism Protocol Transactions
+
+Transaction assembly shared by the SDK, the clients and the batch sender. It only
+depends on `solana-sdk`, so RPC-facing crates can use it without pulling in the
+campaign compiler.
+
+## Key Modules
+
+- `compute_budget`: Compute unit sizing from simulation and priority fees
+- `message`: Legacy/v0 message compilation and transaction sizing
+*/
+
+pub mod compute_budget;
+pub mod message;
+
+pub use compute_budget::{ComputeBudgetConfig, PriorityFee};
+pub use message::{compile_message, transaction_size, unsigned_transaction};
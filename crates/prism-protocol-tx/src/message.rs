@@ -0,0 +1,103 @@
+/*!
+# Messages
+
+Compile instructions into legacy or v0 messages and size the resulting transactions.
+*/
+
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, AddressLookupTableAccount, CompileError, Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+
+/// Compile a v0 message against `lookup_tables`, or a legacy message when there are none
+pub fn compile_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedMessage, CompileError> {
+    if lookup_tables.is_empty() {
+        return Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+            instructions,
+            Some(payer),
+            &recent_blockhash,
+        )));
+    }
+
+    v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+        .map(VersionedMessage::V0)
+}
+
+/// Transaction with placeholder signatures (for simulation with `sig_verify` off)
+pub fn unsigned_transaction(message: VersionedMessage) -> VersionedTransaction {
+    let signature_count = message.header().num_required_signatures as usize;
+    VersionedTransaction {
+        signatures: vec![Signature::default(); signature_count],
+        message,
+    }
+}
+
+/// Serialized size in bytes of a signed transaction carrying `message`
+pub fn transaction_size(message: &VersionedMessage) -> usize {
+    let signature_count = message.header().num_required_signatures as usize;
+    short_vec_len(signature_count) + signature_count * 64 + message.serialize().len()
+}
+
+/// Bytes of a compact-u16 length prefix
+pub fn short_vec_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    #[test]
+    fn test_transaction_size_matches_serialized_transaction() {
+        let payer = Pubkey::new_unique();
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: (0..200).map(|_| Pubkey::new_unique()).collect(),
+        };
+        let ix = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: lookup_table.addresses[..140]
+                .iter()
+                .map(|address| AccountMeta::new_readonly(*address, false))
+                .chain([AccountMeta::new(payer, true)])
+                .collect(),
+            data: vec![1; 16],
+        };
+
+        for tables in [vec![], vec![lookup_table]] {
+            let message = compile_message(
+                &payer,
+                std::slice::from_ref(&ix),
+                &tables,
+                Hash::new_unique(),
+            )
+            .unwrap();
+            assert_eq!(
+                matches!(message, VersionedMessage::V0(_)),
+                !tables.is_empty()
+            );
+
+            let transaction = unsigned_transaction(message);
+            assert_eq!(
+                transaction_size(&transaction.message),
+                bincode::serde::encode_to_vec(&transaction, bincode::config::legacy())
+                    .unwrap()
+                    .len()
+            );
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+use crate::{Hash, LEAF_PREFIX};
+
+/// Hash function of a claim tree.
+///
+/// Implementors only supply `hashv` (and the leaf prefix, for tagged leaf encodings);
+/// the leaf layout, the 0x00/0x01 domain separation and the child ordering live in this
+/// crate, so the on-chain program can plug in its syscall-backed hashers and still
+/// build exactly the trees verified here.
+pub trait Hasher {
+    /// Bytes hashed ahead of the serialized leaf
+    const LEAF_PREFIX: &'static [u8] = &[LEAF_PREFIX];
+
+    /// Hash the concatenation of `parts`
+    fn hashv(parts: &[&[u8]]) -> Hash;
+}
+
+/// Software SHA-256 with the original untagged leaf encoding
+#[derive(Clone, Copy, Debug)]
+pub struct Sha256Hasher;
+
+impl Hasher for Sha256Hasher {
+    fn hashv(parts: &[&[u8]]) -> Hash {
+        let mut hasher = Sha256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Software Keccak-256 with the tagged leaf encoding: KECCAK256(0x00 || 0x01 || leaf)
+#[derive(Clone, Copy, Debug)]
+pub struct Keccak256Hasher;
+
+impl Hasher for Keccak256Hasher {
+    const LEAF_PREFIX: &'static [u8] = &[LEAF_PREFIX, HashScheme::Keccak256.tag()];
+
+    fn hashv(parts: &[&[u8]]) -> Hash {
+        let mut hasher = Keccak256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Hash scheme of a cohort tree, as committed in its on-chain CohortTree account.
+///
+/// The tag is `prism_protocol::ClaimHashScheme`'s leaf-encoding version; cohorts
+/// without a CohortTree account always use [`HashScheme::Sha256`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashScheme {
+    /// SHA-256 with the original untagged leaf encoding
+    #[default]
+    Sha256,
+    /// Keccak-256 with the tagged leaf encoding
+    Keccak256,
+}
+
+impl HashScheme {
+    /// Leaf-encoding version tag
+    pub const fn tag(self) -> u8 {
+        match self {
+            HashScheme::Sha256 => 0,
+            HashScheme::Keccak256 => 1,
+        }
+    }
+
+    /// Scheme for a leaf-encoding version tag
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        [HashScheme::Sha256, HashScheme::Keccak256]
+            .into_iter()
+            .find(|scheme| scheme.tag() == tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hashv;
+
+    #[test]
+    fn test_sha256_matches_solana_hasher() {
+        for parts in [&[][..], &[&b""[..]], &[&b"prism"[..], &[0, 1, 2]]] {
+            assert_eq!(Sha256Hasher::hashv(parts), hashv(parts).to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_keccak256_matches_program_scheme() {
+        use prism_protocol::ClaimHashScheme;
+
+        let children = [[3u8; 32], [1u8; 32], [2u8; 32]];
+        assert_eq!(
+            crate::hash_children::<Keccak256Hasher>(&mut children.clone()),
+            ClaimHashScheme::Keccak256.hash_internal(&mut children.clone())
+        );
+        assert_eq!(
+            Keccak256Hasher::LEAF_PREFIX,
+            ClaimHashScheme::Keccak256.leaf_prefix()
+        );
+
+        // Tags are the program's leaf-encoding versions
+        for scheme in ClaimHashScheme::ALL {
+            assert_eq!(
+                HashScheme::from_tag(scheme.tag()).unwrap().tag(),
+                scheme.tag()
+            );
+        }
+        assert_eq!(HashScheme::from_tag(2), None);
+    }
+}
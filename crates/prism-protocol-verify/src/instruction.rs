@@ -0,0 +1,343 @@
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    find_associated_token_address, find_campaign_address, find_claim_receipt_address,
+    find_cohort_address, find_cohort_tree_address, find_vault_address, Hash, Pubkey,
+    ASSOCIATED_TOKEN_PROGRAM_ID, PRISM_PROGRAM_ID, RENT_SYSVAR_ID, SYSTEM_PROGRAM_ID,
+    TOKEN_PROGRAM_ID,
+};
+
+/// Account reference of an instruction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Program instruction, ready to hand to a transaction builder
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// Inputs shared by the V0, V1 and V2 claim instructions
+#[derive(Clone, Copy, Debug)]
+pub struct ClaimAccounts {
+    pub admin: Pubkey,
+    pub claimant: Pubkey,
+    pub mint: Pubkey,
+    pub campaign_fingerprint: Hash,
+    pub cohort_merkle_root: Hash,
+    pub assigned_vault_index: u8,
+}
+
+/// Anchor instruction discriminator: first 8 bytes of SHA256("global:<name>")
+fn discriminator(name: &str) -> [u8; 8] {
+    let hash: Hash = Sha256::new()
+        .chain_update(b"global:")
+        .chain_update(name.as_bytes())
+        .finalize()
+        .into();
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash[..8]);
+    discriminator
+}
+
+fn push_hashes(data: &mut Vec<u8>, hashes: &[Hash]) {
+    data.extend_from_slice(&(hashes.len() as u32).to_le_bytes());
+    for hash in hashes {
+        data.extend_from_slice(hash);
+    }
+}
+
+/// Serialized `claim_tokens_v0` arguments (Anchor discriminator + borsh)
+pub fn claim_tokens_v0_data(
+    campaign_fingerprint: &Hash,
+    cohort_merkle_root: &Hash,
+    merkle_proof: &[Hash],
+    assigned_vault_index: u8,
+    entitlements: u64,
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(8 + 64 + 4 + merkle_proof.len() * 32 + 9);
+    data.extend_from_slice(&discriminator("claim_tokens_v0"));
+    data.extend_from_slice(campaign_fingerprint);
+    data.extend_from_slice(cohort_merkle_root);
+    push_hashes(&mut data, merkle_proof);
+    data.push(assigned_vault_index);
+    data.extend_from_slice(&entitlements.to_le_bytes());
+    data
+}
+
+/// Serialized `claim_tokens_v1` arguments (Anchor discriminator + borsh)
+pub fn claim_tokens_v1_data(
+    campaign_fingerprint: &Hash,
+    cohort_merkle_root: &Hash,
+    merkle_proof: &[Vec<Hash>],
+    assigned_vault_index: u8,
+    entitlements: u64,
+) -> Vec<u8> {
+    wide_claim_data(
+        "claim_tokens_v1",
+        campaign_fingerprint,
+        cohort_merkle_root,
+        merkle_proof,
+        assigned_vault_index,
+        entitlements,
+    )
+}
+
+/// Serialized `claim_tokens_v2` arguments (Anchor discriminator + borsh)
+pub fn claim_tokens_v2_data(
+    campaign_fingerprint: &Hash,
+    cohort_merkle_root: &Hash,
+    merkle_proof: &[Vec<Hash>],
+    assigned_vault_index: u8,
+    entitlements: u64,
+) -> Vec<u8> {
+    wide_claim_data(
+        "claim_tokens_v2",
+        campaign_fingerprint,
+        cohort_merkle_root,
+        merkle_proof,
+        assigned_vault_index,
+        entitlements,
+    )
+}
+
+fn wide_claim_data(
+    name: &str,
+    campaign_fingerprint: &Hash,
+    cohort_merkle_root: &Hash,
+    merkle_proof: &[Vec<Hash>],
+    assigned_vault_index: u8,
+    entitlements: u64,
+) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&discriminator(name));
+    data.extend_from_slice(campaign_fingerprint);
+    data.extend_from_slice(cohort_merkle_root);
+    data.extend_from_slice(&(merkle_proof.len() as u32).to_le_bytes());
+    for level in merkle_proof {
+        push_hashes(&mut data, level);
+    }
+    data.push(assigned_vault_index);
+    data.extend_from_slice(&entitlements.to_le_bytes());
+    data
+}
+
+/// Accounts of a claim instruction, in program order; V2 claims also pass the cohort tree
+fn claim_account_metas(accounts: &ClaimAccounts, with_cohort_tree: bool) -> Vec<AccountMeta> {
+    let (campaign, _) = find_campaign_address(&accounts.admin, &accounts.campaign_fingerprint);
+    let (cohort, _) = find_cohort_address(&campaign, &accounts.cohort_merkle_root);
+    let (vault, _) = find_vault_address(&cohort, accounts.assigned_vault_index);
+    let (claim_receipt, _) = find_claim_receipt_address(&cohort, &accounts.claimant);
+    let claimant_token_account = find_associated_token_address(&accounts.claimant, &accounts.mint);
+
+    let meta = |pubkey, is_signer, is_writable| AccountMeta {
+        pubkey,
+        is_signer,
+        is_writable,
+    };
+
+    let mut metas = alloc::vec![
+        meta(accounts.admin, false, false),
+        meta(accounts.claimant, true, true),
+        meta(campaign, false, false),
+        meta(cohort, false, false),
+    ];
+    if with_cohort_tree {
+        metas.push(meta(find_cohort_tree_address(&cohort).0, false, false));
+    }
+    metas.extend([
+        meta(vault, false, true),
+        meta(accounts.mint, false, false),
+        meta(claimant_token_account, false, true),
+        meta(claim_receipt, false, true),
+        meta(TOKEN_PROGRAM_ID, false, false),
+        meta(ASSOCIATED_TOKEN_PROGRAM_ID, false, false),
+        meta(SYSTEM_PROGRAM_ID, false, false),
+        meta(RENT_SYSVAR_ID, false, false),
+    ]);
+    metas
+}
+
+/// Complete `claim_tokens_v0` instruction, claiming into the claimant's associated token account
+pub fn claim_tokens_v0_instruction(
+    accounts: &ClaimAccounts,
+    merkle_proof: &[Hash],
+    entitlements: u64,
+) -> Instruction {
+    Instruction {
+        program_id: PRISM_PROGRAM_ID,
+        accounts: claim_account_metas(accounts, false),
+        data: claim_tokens_v0_data(
+            &accounts.campaign_fingerprint,
+            &accounts.cohort_merkle_root,
+            merkle_proof,
+            accounts.assigned_vault_index,
+            entitlements,
+        ),
+    }
+}
+
+/// Complete `claim_tokens_v1` instruction, claiming into the claimant's associated token account
+pub fn claim_tokens_v1_instruction(
+    accounts: &ClaimAccounts,
+    merkle_proof: &[Vec<Hash>],
+    entitlements: u64,
+) -> Instruction {
+    Instruction {
+        program_id: PRISM_PROGRAM_ID,
+        accounts: claim_account_metas(accounts, false),
+        data: claim_tokens_v1_data(
+            &accounts.campaign_fingerprint,
+            &accounts.cohort_merkle_root,
+            merkle_proof,
+            accounts.assigned_vault_index,
+            entitlements,
+        ),
+    }
+}
+
+/// Complete `claim_tokens_v2` instruction, claiming into the claimant's associated token account
+pub fn claim_tokens_v2_instruction(
+    accounts: &ClaimAccounts,
+    merkle_proof: &[Vec<Hash>],
+    entitlements: u64,
+) -> Instruction {
+    Instruction {
+        program_id: PRISM_PROGRAM_ID,
+        accounts: claim_account_metas(accounts, true),
+        data: claim_tokens_v2_data(
+            &accounts.campaign_fingerprint,
+            &accounts.cohort_merkle_root,
+            merkle_proof,
+            accounts.assigned_vault_index,
+            entitlements,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey as AnchorPubkey;
+    use anchor_lang::InstructionData;
+    use anchor_spl::associated_token::get_associated_token_address;
+    use prism_protocol_sdk::{
+        build_claim_tokens_v0_ix, build_claim_tokens_v2_ix, AddressFinder, ClaimV1Params,
+    };
+
+    extern crate std;
+    use std::vec;
+
+    #[test]
+    fn test_claim_v0_instruction_parity() {
+        let admin = AnchorPubkey::new_unique();
+        let claimant = AnchorPubkey::new_unique();
+        let mint = AnchorPubkey::new_unique();
+        let proof = vec![[1; 32], [2; 32], [3; 32]];
+
+        let (reference, _, _) = build_claim_tokens_v0_ix(
+            &AddressFinder::default(),
+            admin,
+            claimant,
+            mint,
+            get_associated_token_address(&claimant, &mint),
+            [7; 32],
+            [8; 32],
+            proof.clone(),
+            2,
+            42,
+        )
+        .unwrap();
+
+        let instruction = claim_tokens_v0_instruction(
+            &ClaimAccounts {
+                admin: admin.to_bytes(),
+                claimant: claimant.to_bytes(),
+                mint: mint.to_bytes(),
+                campaign_fingerprint: [7; 32],
+                cohort_merkle_root: [8; 32],
+                assigned_vault_index: 2,
+            },
+            &proof,
+            42,
+        );
+
+        assert_eq!(instruction.program_id, reference.program_id.to_bytes());
+        assert_eq!(instruction.data, reference.data);
+        assert_eq!(instruction.accounts.len(), reference.accounts.len());
+        for (meta, expected) in instruction.accounts.iter().zip(&reference.accounts) {
+            assert_eq!(meta.pubkey, expected.pubkey.to_bytes());
+            assert_eq!(meta.is_signer, expected.is_signer);
+            assert_eq!(meta.is_writable, expected.is_writable);
+        }
+    }
+
+    #[test]
+    fn test_claim_v1_data_parity() {
+        let proof = vec![vec![[1; 32], [2; 32]], vec![], vec![[3; 32]]];
+        let reference = prism_protocol::instruction::ClaimTokensV1 {
+            campaign_fingerprint: [7; 32],
+            cohort_merkle_root: [8; 32],
+            merkle_proof: proof.clone(),
+            assigned_vault_index: 1,
+            entitlements: u64::MAX,
+        };
+
+        assert_eq!(
+            claim_tokens_v1_data(&[7; 32], &[8; 32], &proof, 1, u64::MAX),
+            reference.data()
+        );
+    }
+
+    #[test]
+    fn test_claim_v2_instruction_parity() {
+        let admin = AnchorPubkey::new_unique();
+        let claimant = AnchorPubkey::new_unique();
+        let mint = AnchorPubkey::new_unique();
+        let proof = vec![vec![[1; 32], [2; 32], [3; 32]], vec![[4; 32]]];
+
+        let (reference, _, _) = build_claim_tokens_v2_ix(
+            &AddressFinder::default(),
+            ClaimV1Params {
+                admin,
+                claimant,
+                mint,
+                claimant_token_account: get_associated_token_address(&claimant, &mint),
+                campaign_fingerprint: [7; 32],
+                cohort_merkle_root: [8; 32],
+                merkle_proof: proof.clone(),
+                assigned_vault_index: 3,
+                entitlements: 9,
+            },
+        )
+        .unwrap();
+
+        let instruction = claim_tokens_v2_instruction(
+            &ClaimAccounts {
+                admin: admin.to_bytes(),
+                claimant: claimant.to_bytes(),
+                mint: mint.to_bytes(),
+                campaign_fingerprint: [7; 32],
+                cohort_merkle_root: [8; 32],
+                assigned_vault_index: 3,
+            },
+            &proof,
+            9,
+        );
+
+        assert_eq!(instruction.data, reference.data);
+        assert_eq!(instruction.accounts.len(), reference.accounts.len());
+        for (meta, expected) in instruction.accounts.iter().zip(&reference.accounts) {
+            assert_eq!(meta.pubkey, expected.pubkey.to_bytes());
+            assert_eq!(meta.is_signer, expected.is_signer);
+            assert_eq!(meta.is_writable, expected.is_writable);
+        }
+    }
+}
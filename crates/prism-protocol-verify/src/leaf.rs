@@ -0,0 +1,81 @@
+use crate::{Hash, HashScheme, Hasher, Keccak256Hasher, Pubkey, Sha256Hasher};
+
+/// Borsh-serialized length of a claim leaf (pubkey, u8, u64)
+pub const LEAF_LEN: usize = 32 + 1 + 8;
+
+/// Claim leaf, field for field the same as `prism_protocol::ClaimLeaf`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Leaf {
+    pub claimant: Pubkey,
+    pub assigned_vault_index: u8,
+    pub entitlements: u64,
+}
+
+/// Borsh layout of a leaf: claimant, vault index, entitlements (little-endian)
+pub fn serialize_leaf(leaf: &Leaf) -> [u8; LEAF_LEN] {
+    let mut bytes = [0u8; LEAF_LEN];
+    bytes[..32].copy_from_slice(&leaf.claimant);
+    bytes[32] = leaf.assigned_vault_index;
+    bytes[33..].copy_from_slice(&leaf.entitlements.to_le_bytes());
+    bytes
+}
+
+/// Leaf hash: SHA256(0x00 || borsh(leaf))
+pub fn hash_leaf(leaf: &Leaf) -> Hash {
+    hash_leaf_with::<Sha256Hasher>(leaf)
+}
+
+/// Leaf hash with `H`: H(leaf prefix || borsh(leaf))
+pub fn hash_leaf_with<H: Hasher>(leaf: &Leaf) -> Hash {
+    hash_leaf_data_with::<H>(&serialize_leaf(leaf))
+}
+
+/// Leaf hash with a cohort's hash scheme
+pub fn hash_leaf_with_scheme(leaf: &Leaf, scheme: HashScheme) -> Hash {
+    match scheme {
+        HashScheme::Sha256 => hash_leaf_with::<Sha256Hasher>(leaf),
+        HashScheme::Keccak256 => hash_leaf_with::<Keccak256Hasher>(leaf),
+    }
+}
+
+/// Leaf hash of already borsh-serialized leaf data
+pub fn hash_leaf_data_with<H: Hasher>(data: &[u8]) -> Hash {
+    H::hashv(&[H::LEAF_PREFIX, data])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey as AnchorPubkey;
+    use anchor_lang::AnchorSerialize;
+    use prism_protocol::ClaimLeaf;
+
+    #[test]
+    fn test_leaf_parity() {
+        for (vault_index, entitlements) in [(0, 0), (3, 1), (255, u64::MAX)] {
+            let claimant = AnchorPubkey::new_unique();
+            let reference = ClaimLeaf {
+                claimant,
+                assigned_vault_index: vault_index,
+                entitlements,
+            };
+            let leaf = Leaf {
+                claimant: claimant.to_bytes(),
+                assigned_vault_index: vault_index,
+                entitlements,
+            };
+
+            assert_eq!(
+                serialize_leaf(&leaf).as_slice(),
+                reference.try_to_vec().unwrap().as_slice()
+            );
+            assert_eq!(hash_leaf(&leaf), reference.to_hash());
+            for scheme in prism_protocol::ClaimHashScheme::ALL {
+                assert_eq!(
+                    hash_leaf_with_scheme(&leaf, HashScheme::from_tag(scheme.tag()).unwrap()),
+                    reference.to_hash_with_scheme(scheme)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+/*!
+# Prism Protocol Verify
+
+Portable claim verification for Prism Protocol: leaf hashing with every cohort hash
+scheme, V0/V1 proof verification, PDA derivation and claim instruction serialization.
+
+The crate is `no_std` (with `alloc`) and depends on neither Anchor nor the Solana
+client crates, so it builds for `wasm32-unknown-unknown`. With the `wasm` feature it
+exposes the same functionality to JavaScript through `wasm-bindgen`, letting claim
+sites reuse the exact Rust logic instead of reimplementing the borsh leaf layout and
+the 0x00/0x01 domain prefixes.
+
+The on-chain program and `prism-protocol-merkle` build their trees with this crate:
+the program plugs its syscall-backed hashers in through [`Hasher`], and the parity
+tests pin the borsh leaf layout and the syscall results to the software hashes here.
+
+## Building for the browser
+
+```text
+cargo build -p prism-protocol-verify --target wasm32-unknown-unknown --features wasm --release
+wasm-bindgen --target web target/wasm32-unknown-unknown/release/prism_protocol_verify.wasm --out-dir pkg
+```
+*/
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod hasher;
+pub mod instruction;
+pub mod leaf;
+pub mod pda;
+pub mod proof;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use hasher::{HashScheme, Hasher, Keccak256Hasher, Sha256Hasher};
+pub use instruction::{
+    claim_tokens_v0_data, claim_tokens_v0_instruction, claim_tokens_v1_data,
+    claim_tokens_v1_instruction, claim_tokens_v2_data, claim_tokens_v2_instruction, AccountMeta,
+    ClaimAccounts, Instruction,
+};
+pub use leaf::{
+    hash_leaf, hash_leaf_data_with, hash_leaf_with, hash_leaf_with_scheme, serialize_leaf, Leaf,
+    LEAF_LEN,
+};
+pub use pda::{
+    find_associated_token_address, find_campaign_address, find_claim_receipt_address,
+    find_cohort_address, find_cohort_tree_address, find_program_address, find_vault_address,
+};
+pub use proof::{
+    fold_proof_v0, fold_proof_v1, hash_children, hash_internal_node, verify_proof_v0,
+    verify_proof_v1, verify_proof_v1_with_scheme,
+};
+
+/// 32-byte public key
+pub type Pubkey = [u8; 32];
+
+/// 32-byte SHA-256 hash
+pub type Hash = [u8; 32];
+
+/// Domain separation prefix for leaf nodes
+pub const LEAF_PREFIX: u8 = 0x00;
+
+/// Domain separation prefix for internal nodes
+pub const INTERNAL_PREFIX: u8 = 0x01;
+
+/// Prism Protocol program id (`PrsmV9Kh8HcJjPSShFidZFJrbWM5NWQ98ST8M2BNdAw`)
+pub const PRISM_PROGRAM_ID: Pubkey = [
+    5, 219, 44, 6, 109, 184, 109, 205, 184, 58, 243, 200, 45, 247, 238, 98, 150, 35, 200, 192, 127,
+    172, 168, 253, 177, 27, 78, 253, 81, 153, 207, 88,
+];
+
+/// SPL Token program id
+pub const TOKEN_PROGRAM_ID: Pubkey = [
+    6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172, 28, 180, 133, 237,
+    95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169,
+];
+
+/// SPL Associated Token Account program id
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = [
+    140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142, 13, 131, 11, 90, 19, 153, 218,
+    255, 16, 132, 4, 142, 123, 216, 219, 233, 248, 89,
+];
+
+/// System program id
+pub const SYSTEM_PROGRAM_ID: Pubkey = [0; 32];
+
+/// Rent sysvar id
+pub const RENT_SYSVAR_ID: Pubkey = [
+    6, 167, 213, 23, 25, 44, 92, 81, 33, 140, 201, 76, 61, 74, 241, 127, 88, 218, 238, 8, 155, 161,
+    253, 68, 227, 219, 217, 138, 0, 0, 0, 0,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey as AnchorPubkey;
+    use std::str::FromStr;
+
+    extern crate std;
+
+    #[test]
+    fn test_constants_match_program() {
+        assert_eq!(
+            LEAF_PREFIX,
+            prism_protocol::claim_tree_constants::LEAF_PREFIX
+        );
+        assert_eq!(
+            INTERNAL_PREFIX,
+            prism_protocol::claim_tree_constants::INTERNAL_PREFIX
+        );
+        assert_eq!(PRISM_PROGRAM_ID, prism_protocol::ID.to_bytes());
+
+        let address_finder = prism_protocol_sdk::AddressFinder::default();
+        assert_eq!(TOKEN_PROGRAM_ID, address_finder.token_program_id.to_bytes());
+        assert_eq!(
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+            address_finder.associated_token_program_id.to_bytes()
+        );
+        assert_eq!(
+            SYSTEM_PROGRAM_ID,
+            address_finder.system_program_id.to_bytes()
+        );
+        assert_eq!(
+            RENT_SYSVAR_ID,
+            AnchorPubkey::from_str("SysvarRent111111111111111111111111111111111")
+                .unwrap()
+                .to_bytes()
+        );
+        assert_eq!(RENT_SYSVAR_ID, address_finder.rent_id.to_bytes());
+    }
+}
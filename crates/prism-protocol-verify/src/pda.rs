@@ -0,0 +1,155 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
+
+use crate::{Hash, Pubkey, ASSOCIATED_TOKEN_PROGRAM_ID, PRISM_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+/// Seed prefix of campaign accounts
+pub const CAMPAIGN_V0_SEED_PREFIX: &[u8] = b"campaign_v0";
+
+/// Seed prefix of cohort accounts
+pub const COHORT_V0_SEED_PREFIX: &[u8] = b"cohort_v0";
+
+/// Seed prefix of cohort tree accounts
+pub const COHORT_TREE_V0_SEED_PREFIX: &[u8] = b"cohort_tree_v0";
+
+/// Seed prefix of claim receipt accounts
+pub const CLAIM_RECEIPT_V0_SEED_PREFIX: &[u8] = b"claim_receipt_v0";
+
+/// Seed prefix of vault token accounts
+pub const VAULT_SEED_PREFIX: &[u8] = b"vault";
+
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// Same search as `Pubkey::find_program_address`: highest bump whose address is off-curve
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    (0..=u8::MAX).rev().find_map(|bump| {
+        let mut hasher = Sha256::new();
+        for seed in seeds {
+            hasher.update(seed);
+        }
+        hasher.update([bump]);
+        hasher.update(program_id);
+        hasher.update(PDA_MARKER);
+        let address: Hash = hasher.finalize().into();
+
+        let on_curve = CompressedEdwardsY(address).decompress().is_some();
+        (!on_curve).then_some((address, bump))
+    })
+}
+
+fn find_prism_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    find_program_address(seeds, &PRISM_PROGRAM_ID).expect("no viable bump for Prism PDA")
+}
+
+/// Campaign account of `admin` for a campaign fingerprint
+pub fn find_campaign_address(admin: &Pubkey, fingerprint: &Hash) -> (Pubkey, u8) {
+    find_prism_address(&[CAMPAIGN_V0_SEED_PREFIX, admin, fingerprint])
+}
+
+/// Cohort account of a campaign for a merkle root
+pub fn find_cohort_address(campaign: &Pubkey, merkle_root: &Hash) -> (Pubkey, u8) {
+    find_prism_address(&[COHORT_V0_SEED_PREFIX, campaign, merkle_root])
+}
+
+/// Tree layout account of a cohort initialized with `initialize_cohort_v1`
+pub fn find_cohort_tree_address(cohort: &Pubkey) -> (Pubkey, u8) {
+    find_prism_address(&[COHORT_TREE_V0_SEED_PREFIX, cohort])
+}
+
+/// Vault token account of a cohort
+pub fn find_vault_address(cohort: &Pubkey, vault_index: u8) -> (Pubkey, u8) {
+    find_prism_address(&[VAULT_SEED_PREFIX, cohort, &[vault_index]])
+}
+
+/// Claim receipt of a claimant in a cohort
+pub fn find_claim_receipt_address(cohort: &Pubkey, claimant: &Pubkey) -> (Pubkey, u8) {
+    find_prism_address(&[CLAIM_RECEIPT_V0_SEED_PREFIX, cohort, claimant])
+}
+
+/// Associated token account of `wallet` for `mint`
+pub fn find_associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    find_program_address(
+        &[wallet, &TOKEN_PROGRAM_ID, mint],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .expect("no viable bump for associated token address")
+    .0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey as AnchorPubkey;
+    use anchor_spl::associated_token::get_associated_token_address;
+    use prism_protocol_sdk::AddressFinder;
+
+    #[test]
+    fn test_seed_prefixes_match_program() {
+        assert_eq!(
+            CAMPAIGN_V0_SEED_PREFIX,
+            prism_protocol::CAMPAIGN_V0_SEED_PREFIX
+        );
+        assert_eq!(COHORT_V0_SEED_PREFIX, prism_protocol::COHORT_V0_SEED_PREFIX);
+        assert_eq!(
+            COHORT_TREE_V0_SEED_PREFIX,
+            prism_protocol::COHORT_TREE_V0_SEED_PREFIX
+        );
+        assert_eq!(
+            CLAIM_RECEIPT_V0_SEED_PREFIX,
+            prism_protocol::CLAIM_RECEIPT_V0_SEED_PREFIX
+        );
+        assert_eq!(VAULT_SEED_PREFIX, prism_protocol::VAULT_SEED_PREFIX);
+    }
+
+    #[test]
+    fn test_pda_parity_with_address_finder() {
+        let address_finder = AddressFinder::default();
+
+        for i in 0..16u8 {
+            let admin = AnchorPubkey::new_unique();
+            let claimant = AnchorPubkey::new_unique();
+            let fingerprint = [i; 32];
+            let merkle_root = [i.wrapping_add(100); 32];
+
+            let (campaign, campaign_bump) =
+                address_finder.find_campaign_v0_address(&admin, &fingerprint);
+            assert_eq!(
+                find_campaign_address(&admin.to_bytes(), &fingerprint),
+                (campaign.to_bytes(), campaign_bump)
+            );
+
+            let (cohort, cohort_bump) =
+                address_finder.find_cohort_v0_address(&campaign, &merkle_root);
+            assert_eq!(
+                find_cohort_address(&campaign.to_bytes(), &merkle_root),
+                (cohort.to_bytes(), cohort_bump)
+            );
+
+            let (cohort_tree, cohort_tree_bump) =
+                address_finder.find_cohort_tree_v0_address(&cohort);
+            assert_eq!(
+                find_cohort_tree_address(&cohort.to_bytes()),
+                (cohort_tree.to_bytes(), cohort_tree_bump)
+            );
+
+            let (vault, vault_bump) = address_finder.find_vault_v0_address(&cohort, i);
+            assert_eq!(
+                find_vault_address(&cohort.to_bytes(), i),
+                (vault.to_bytes(), vault_bump)
+            );
+
+            let (receipt, receipt_bump) =
+                address_finder.find_claim_receipt_v0_address(&cohort, &claimant);
+            assert_eq!(
+                find_claim_receipt_address(&cohort.to_bytes(), &claimant.to_bytes()),
+                (receipt.to_bytes(), receipt_bump)
+            );
+
+            let mint = AnchorPubkey::new_unique();
+            assert_eq!(
+                find_associated_token_address(&claimant.to_bytes(), &mint.to_bytes()),
+                get_associated_token_address(&claimant, &mint).to_bytes()
+            );
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::vec::Vec;
+
+use crate::{
+    hash_leaf, hash_leaf_with_scheme, Hash, HashScheme, Hasher, Keccak256Hasher, Leaf,
+    Sha256Hasher, INTERNAL_PREFIX,
+};
+
+/// Internal node hash: SHA256(0x01 || children sorted lexicographically)
+pub fn hash_internal_node(children: &[Hash]) -> Hash {
+    hash_children::<Sha256Hasher>(&mut children.to_vec())
+}
+
+/// Internal node hash with `H`: H(0x01 || children), sorting `children` in place
+pub fn hash_children<H: Hasher>(children: &mut [Hash]) -> Hash {
+    children.sort_unstable();
+
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(children.len() + 1);
+    parts.push(&[INTERNAL_PREFIX]);
+    parts.extend(children.iter().map(|child| child.as_slice()));
+    H::hashv(&parts)
+}
+
+/// Fold a binary (V0) proof from `leaf_hash` up, returning the computed root
+pub fn fold_proof_v0<H: Hasher>(leaf_hash: Hash, proof: &[Hash]) -> Hash {
+    proof.iter().fold(leaf_hash, |node, sibling| {
+        hash_children::<H>(&mut [node, *sibling])
+    })
+}
+
+/// Fold a wide (V1) proof from `leaf_hash` up, returning the computed root
+pub fn fold_proof_v1<H: Hasher>(leaf_hash: Hash, proof: &[Vec<Hash>]) -> Hash {
+    proof.iter().fold(leaf_hash, |node, siblings| {
+        let mut children = Vec::with_capacity(siblings.len() + 1);
+        children.push(node);
+        children.extend_from_slice(siblings);
+        hash_children::<H>(&mut children)
+    })
+}
+
+/// Verify a binary (V0) proof: one sibling per level, pairs ordered before hashing
+pub fn verify_proof_v0(root: &Hash, leaf: &Leaf, proof: &[Hash]) -> bool {
+    fold_proof_v0::<Sha256Hasher>(hash_leaf(leaf), proof) == *root
+}
+
+/// Verify a 256-ary (V1) proof: all siblings of each level, sorted with the node
+pub fn verify_proof_v1(root: &Hash, leaf: &Leaf, proof: &[Vec<Hash>]) -> bool {
+    fold_proof_v1::<Sha256Hasher>(hash_leaf(leaf), proof) == *root
+}
+
+/// Verify a wide proof of a cohort tree built with `scheme`
+pub fn verify_proof_v1_with_scheme(
+    root: &Hash,
+    leaf: &Leaf,
+    proof: &[Vec<Hash>],
+    scheme: HashScheme,
+) -> bool {
+    let leaf_hash = hash_leaf_with_scheme(leaf, scheme);
+    let computed = match scheme {
+        HashScheme::Sha256 => fold_proof_v1::<Sha256Hasher>(leaf_hash, proof),
+        HashScheme::Keccak256 => fold_proof_v1::<Keccak256Hasher>(leaf_hash, proof),
+    };
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey as AnchorPubkey;
+    use prism_protocol::ClaimHashScheme;
+    use prism_protocol::{ClaimProofV0, ClaimProofV1};
+    use prism_protocol_merkle::{
+        create_claim_tree_v0, create_claim_tree_v1, create_claim_tree_v1_with_options,
+        ClaimTreeV1Options,
+    };
+
+    extern crate std;
+    use std::vec;
+
+    fn leaf(claimant: &AnchorPubkey, vault_index: u8, entitlements: u64) -> Leaf {
+        Leaf {
+            claimant: claimant.to_bytes(),
+            assigned_vault_index: vault_index,
+            entitlements,
+        }
+    }
+
+    #[test]
+    fn test_v0_parity_with_claim_tree() {
+        for leaf_count in [1, 2, 3, 7, 64, 129] {
+            let claimants: Vec<(AnchorPubkey, u64)> = (0..leaf_count)
+                .map(|i| (AnchorPubkey::new_unique(), i as u64 + 1))
+                .collect();
+            let tree = create_claim_tree_v0(&claimants, 4).unwrap();
+            let root = tree.root().unwrap();
+
+            for reference in &tree.leaves {
+                let proof = tree.proof_for_claimant(&reference.claimant).unwrap();
+                let leaf = leaf(
+                    &reference.claimant,
+                    reference.assigned_vault_index,
+                    reference.entitlements,
+                );
+
+                assert!(ClaimProofV0::new(proof.clone()).verify(&root, reference));
+                assert!(verify_proof_v0(&root, &leaf, &proof));
+
+                let tampered = Leaf {
+                    entitlements: leaf.entitlements + 1,
+                    ..leaf
+                };
+                assert!(!verify_proof_v0(&root, &tampered, &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn test_v1_parity_with_claim_tree() {
+        for leaf_count in [1, 2, 300, 700] {
+            let claimants: Vec<(AnchorPubkey, u64)> = (0..leaf_count)
+                .map(|i| (AnchorPubkey::new_unique(), i as u64 + 1))
+                .collect();
+            let tree = create_claim_tree_v1(&claimants, 4).unwrap();
+            let root = tree.root().unwrap();
+
+            for reference in tree.leaves.iter().step_by(37) {
+                let proof = tree.proof_for_claimant(&reference.claimant).unwrap();
+                let leaf = leaf(
+                    &reference.claimant,
+                    reference.assigned_vault_index,
+                    reference.entitlements,
+                );
+
+                assert!(ClaimProofV1::new(proof.clone()).verify(&root, reference));
+                assert!(verify_proof_v1(&root, &leaf, &proof));
+                assert!(!verify_proof_v1(&[0; 32], &leaf, &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn test_scheme_parity_with_claim_tree() {
+        let claimants: Vec<(AnchorPubkey, u64)> = (0..100)
+            .map(|i| (AnchorPubkey::new_unique(), i as u64 + 1))
+            .collect();
+
+        for scheme in ClaimHashScheme::ALL {
+            let portable = HashScheme::from_tag(scheme.tag()).unwrap();
+            let options = ClaimTreeV1Options {
+                arity: 8,
+                hash_scheme: scheme,
+                ..Default::default()
+            };
+            let tree = create_claim_tree_v1_with_options(&claimants, 3, &options).unwrap();
+            let root = tree.root().unwrap();
+
+            for reference in tree.leaves.iter().step_by(13) {
+                let proof = tree.proof_for_claimant(&reference.claimant).unwrap();
+                let leaf = leaf(
+                    &reference.claimant,
+                    reference.assigned_vault_index,
+                    reference.entitlements,
+                );
+
+                assert!(verify_proof_v1_with_scheme(&root, &leaf, &proof, portable));
+                let other = match portable {
+                    HashScheme::Sha256 => HashScheme::Keccak256,
+                    HashScheme::Keccak256 => HashScheme::Sha256,
+                };
+                assert!(!verify_proof_v1_with_scheme(&root, &leaf, &proof, other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_proofs() {
+        let leaf = leaf(&AnchorPubkey::new_unique(), 0, 5);
+        let leaf_hash = hash_leaf(&leaf);
+        assert!(verify_proof_v0(&leaf_hash, &leaf, &[]));
+        assert!(verify_proof_v1(&leaf_hash, &leaf, &[]));
+        assert!(verify_proof_v1(
+            &hash_internal_node(&[leaf_hash]),
+            &leaf,
+            &[vec![]]
+        ));
+    }
+}
@@ -0,0 +1,307 @@
+/*!
+JavaScript bindings.
+
+Keys and hashes cross the boundary as `Uint8Array`s (32 bytes each) and amounts as
+`BigInt`s. Proofs are flattened: V0 proofs are the concatenated sibling hashes, V1
+proofs are the concatenated hashes of every level plus the number of hashes per level.
+*/
+
+use alloc::{format, vec::Vec};
+use wasm_bindgen::prelude::*;
+
+use crate::{Hash, HashScheme, Leaf, Pubkey};
+
+fn bytes32(name: &str, bytes: &[u8]) -> Result<[u8; 32], JsError> {
+    bytes
+        .try_into()
+        .map_err(|_| JsError::new(&format!("{} must be 32 bytes, got {}", name, bytes.len())))
+}
+
+fn hashes(name: &str, bytes: &[u8]) -> Result<Vec<Hash>, JsError> {
+    let (chunks, remainder) = bytes.as_chunks::<32>();
+    if !remainder.is_empty() {
+        return Err(JsError::new(&format!(
+            "{} must be a multiple of 32 bytes, got {}",
+            name,
+            bytes.len()
+        )));
+    }
+    Ok(chunks.to_vec())
+}
+
+fn levels(bytes: &[u8], level_sizes: &[u32]) -> Result<Vec<Vec<Hash>>, JsError> {
+    let all = hashes("proof", bytes)?;
+    if level_sizes.iter().map(|&size| size as usize).sum::<usize>() != all.len() {
+        return Err(JsError::new("level sizes don't add up to the proof length"));
+    }
+
+    let mut rest = all.as_slice();
+    Ok(level_sizes
+        .iter()
+        .map(|&size| {
+            let (level, tail) = rest.split_at(size as usize);
+            rest = tail;
+            level.to_vec()
+        })
+        .collect())
+}
+
+fn leaf(claimant: &[u8], assigned_vault_index: u8, entitlements: u64) -> Result<Leaf, JsError> {
+    Ok(Leaf {
+        claimant: bytes32("claimant", claimant)?,
+        assigned_vault_index,
+        entitlements,
+    })
+}
+
+fn hash_scheme(tag: Option<u8>) -> Result<HashScheme, JsError> {
+    let tag = tag.unwrap_or_default();
+    HashScheme::from_tag(tag).ok_or_else(|| JsError::new(&format!("Unknown hash scheme: {}", tag)))
+}
+
+/// Leaf hash of a claim, with the cohort's hash scheme tag (default 0, SHA-256)
+#[wasm_bindgen(js_name = hashLeaf)]
+pub fn hash_leaf(
+    claimant: &[u8],
+    assigned_vault_index: u8,
+    entitlements: u64,
+    scheme: Option<u8>,
+) -> Result<Vec<u8>, JsError> {
+    Ok(crate::hash_leaf_with_scheme(
+        &leaf(claimant, assigned_vault_index, entitlements)?,
+        hash_scheme(scheme)?,
+    )
+    .to_vec())
+}
+
+/// Verify a binary (V0) proof
+#[wasm_bindgen(js_name = verifyProofV0)]
+pub fn verify_proof_v0(
+    root: &[u8],
+    claimant: &[u8],
+    assigned_vault_index: u8,
+    entitlements: u64,
+    proof: &[u8],
+) -> Result<bool, JsError> {
+    Ok(crate::verify_proof_v0(
+        &bytes32("root", root)?,
+        &leaf(claimant, assigned_vault_index, entitlements)?,
+        &hashes("proof", proof)?,
+    ))
+}
+
+/// Verify a wide (V1) proof, with the cohort's hash scheme tag (default 0, SHA-256)
+#[wasm_bindgen(js_name = verifyProofV1)]
+pub fn verify_proof_v1(
+    root: &[u8],
+    claimant: &[u8],
+    assigned_vault_index: u8,
+    entitlements: u64,
+    proof: &[u8],
+    level_sizes: &[u32],
+    scheme: Option<u8>,
+) -> Result<bool, JsError> {
+    Ok(crate::verify_proof_v1_with_scheme(
+        &bytes32("root", root)?,
+        &leaf(claimant, assigned_vault_index, entitlements)?,
+        &levels(proof, level_sizes)?,
+        hash_scheme(scheme)?,
+    ))
+}
+
+/// Campaign account address
+#[wasm_bindgen(js_name = findCampaignAddress)]
+pub fn find_campaign_address(admin: &[u8], fingerprint: &[u8]) -> Result<Vec<u8>, JsError> {
+    let (address, _) = crate::find_campaign_address(
+        &bytes32("admin", admin)?,
+        &bytes32("fingerprint", fingerprint)?,
+    );
+    Ok(address.to_vec())
+}
+
+/// Cohort account address
+#[wasm_bindgen(js_name = findCohortAddress)]
+pub fn find_cohort_address(campaign: &[u8], merkle_root: &[u8]) -> Result<Vec<u8>, JsError> {
+    let (address, _) = crate::find_cohort_address(
+        &bytes32("campaign", campaign)?,
+        &bytes32("merkle root", merkle_root)?,
+    );
+    Ok(address.to_vec())
+}
+
+/// Cohort tree account address, for cohorts initialized with `initialize_cohort_v1`
+#[wasm_bindgen(js_name = findCohortTreeAddress)]
+pub fn find_cohort_tree_address(cohort: &[u8]) -> Result<Vec<u8>, JsError> {
+    let (address, _) = crate::find_cohort_tree_address(&bytes32("cohort", cohort)?);
+    Ok(address.to_vec())
+}
+
+/// Vault token account address
+#[wasm_bindgen(js_name = findVaultAddress)]
+pub fn find_vault_address(cohort: &[u8], vault_index: u8) -> Result<Vec<u8>, JsError> {
+    let (address, _) = crate::find_vault_address(&bytes32("cohort", cohort)?, vault_index);
+    Ok(address.to_vec())
+}
+
+/// Claim receipt address
+#[wasm_bindgen(js_name = findClaimReceiptAddress)]
+pub fn find_claim_receipt_address(cohort: &[u8], claimant: &[u8]) -> Result<Vec<u8>, JsError> {
+    let (address, _) = crate::find_claim_receipt_address(
+        &bytes32("cohort", cohort)?,
+        &bytes32("claimant", claimant)?,
+    );
+    Ok(address.to_vec())
+}
+
+/// Claim instruction as returned to JavaScript
+#[wasm_bindgen]
+pub struct ClaimInstruction {
+    inner: crate::Instruction,
+}
+
+#[wasm_bindgen]
+impl ClaimInstruction {
+    #[wasm_bindgen(getter, js_name = programId)]
+    pub fn program_id(&self) -> Vec<u8> {
+        self.inner.program_id.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> Vec<u8> {
+        self.inner.data.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = accountCount)]
+    pub fn account_count(&self) -> usize {
+        self.inner.accounts.len()
+    }
+
+    #[wasm_bindgen(js_name = accountPubkey)]
+    pub fn account_pubkey(&self, index: usize) -> Option<Vec<u8>> {
+        self.inner
+            .accounts
+            .get(index)
+            .map(|meta| meta.pubkey.to_vec())
+    }
+
+    #[wasm_bindgen(js_name = accountIsSigner)]
+    pub fn account_is_signer(&self, index: usize) -> Option<bool> {
+        self.inner.accounts.get(index).map(|meta| meta.is_signer)
+    }
+
+    #[wasm_bindgen(js_name = accountIsWritable)]
+    pub fn account_is_writable(&self, index: usize) -> Option<bool> {
+        self.inner.accounts.get(index).map(|meta| meta.is_writable)
+    }
+}
+
+fn claim_accounts(
+    admin: &[u8],
+    claimant: &[u8],
+    mint: &[u8],
+    campaign_fingerprint: &[u8],
+    cohort_merkle_root: &[u8],
+    assigned_vault_index: u8,
+) -> Result<crate::ClaimAccounts, JsError> {
+    let key = |name, bytes| -> Result<Pubkey, JsError> { bytes32(name, bytes) };
+    Ok(crate::ClaimAccounts {
+        admin: key("admin", admin)?,
+        claimant: key("claimant", claimant)?,
+        mint: key("mint", mint)?,
+        campaign_fingerprint: bytes32("campaign fingerprint", campaign_fingerprint)?,
+        cohort_merkle_root: bytes32("cohort merkle root", cohort_merkle_root)?,
+        assigned_vault_index,
+    })
+}
+
+/// `claim_tokens_v0` instruction into the claimant's associated token account
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = claimTokensV0Instruction)]
+pub fn claim_tokens_v0_instruction(
+    admin: &[u8],
+    claimant: &[u8],
+    mint: &[u8],
+    campaign_fingerprint: &[u8],
+    cohort_merkle_root: &[u8],
+    assigned_vault_index: u8,
+    entitlements: u64,
+    proof: &[u8],
+) -> Result<ClaimInstruction, JsError> {
+    let accounts = claim_accounts(
+        admin,
+        claimant,
+        mint,
+        campaign_fingerprint,
+        cohort_merkle_root,
+        assigned_vault_index,
+    )?;
+    Ok(ClaimInstruction {
+        inner: crate::claim_tokens_v0_instruction(
+            &accounts,
+            &hashes("proof", proof)?,
+            entitlements,
+        ),
+    })
+}
+
+/// `claim_tokens_v1` instruction into the claimant's associated token account
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = claimTokensV1Instruction)]
+pub fn claim_tokens_v1_instruction(
+    admin: &[u8],
+    claimant: &[u8],
+    mint: &[u8],
+    campaign_fingerprint: &[u8],
+    cohort_merkle_root: &[u8],
+    assigned_vault_index: u8,
+    entitlements: u64,
+    proof: &[u8],
+    level_sizes: &[u32],
+) -> Result<ClaimInstruction, JsError> {
+    let accounts = claim_accounts(
+        admin,
+        claimant,
+        mint,
+        campaign_fingerprint,
+        cohort_merkle_root,
+        assigned_vault_index,
+    )?;
+    Ok(ClaimInstruction {
+        inner: crate::claim_tokens_v1_instruction(
+            &accounts,
+            &levels(proof, level_sizes)?,
+            entitlements,
+        ),
+    })
+}
+
+/// `claim_tokens_v2` instruction into the claimant's associated token account
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = claimTokensV2Instruction)]
+pub fn claim_tokens_v2_instruction(
+    admin: &[u8],
+    claimant: &[u8],
+    mint: &[u8],
+    campaign_fingerprint: &[u8],
+    cohort_merkle_root: &[u8],
+    assigned_vault_index: u8,
+    entitlements: u64,
+    proof: &[u8],
+    level_sizes: &[u32],
+) -> Result<ClaimInstruction, JsError> {
+    let accounts = claim_accounts(
+        admin,
+        claimant,
+        mint,
+        campaign_fingerprint,
+        cohort_merkle_root,
+        assigned_vault_index,
+    )?;
+    Ok(ClaimInstruction {
+        inner: crate::claim_tokens_v2_instruction(
+            &accounts,
+            &levels(proof, level_sizes)?,
+            entitlements,
+        ),
+    })
+}
@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::Hasher as SolanaHasher;
+use prism_protocol_verify::Hasher;
+use std::{fmt, str::FromStr};
+
+use crate::ClaimLeaf;
+
+/// Hash function and leaf encoding of a cohort's merkle tree.
+///
+/// Committed in the cohort tree account by `initialize_cohort_v1`; claims against the
+/// cohort are verified with this scheme. Cohorts without a cohort tree account use
+/// SHA-256. The variant's tag doubles as the leaf-encoding version: tagged schemes put it
+/// in every leaf preimage, so a leaf can't be replayed against a tree built with another
+/// scheme.
+///
+/// ## ⚠️ Tags are part of the leaf hash and MUST NEVER CHANGE
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub enum ClaimHashScheme {
+    /// SHA-256 with the original untagged leaf encoding: SHA256(0x00 || leaf)
+    #[default]
+    Sha256,
+    /// Keccak-256 with a tagged leaf encoding: KECCAK256(0x00 || 0x01 || leaf), for
+    /// trees that are also verified on EVM chains
+    Keccak256,
+}
+
+impl ClaimHashScheme {
+    /// Every scheme, in tag order
+    pub const ALL: [ClaimHashScheme; 2] = [ClaimHashScheme::Sha256, ClaimHashScheme::Keccak256];
+
+    /// Leaf-encoding version tag
+    pub fn tag(self) -> u8 {
+        match self {
+            ClaimHashScheme::Sha256 => 0,
+            ClaimHashScheme::Keccak256 => 1,
+        }
+    }
+
+    /// Scheme for a leaf-encoding version tag
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|scheme| scheme.tag() == tag)
+    }
+
+    /// Bytes hashed ahead of the borsh-encoded leaf
+    pub fn leaf_prefix(self) -> &'static [u8] {
+        match self {
+            ClaimHashScheme::Sha256 => Sha256ClaimHasher::LEAF_PREFIX,
+            ClaimHashScheme::Keccak256 => Keccak256ClaimHasher::LEAF_PREFIX,
+        }
+    }
+
+    /// Stable name as used on the command line and in manifests
+    pub fn name(self) -> &'static str {
+        match self {
+            ClaimHashScheme::Sha256 => "sha256",
+            ClaimHashScheme::Keccak256 => "keccak256",
+        }
+    }
+
+    /// Hash a leaf with this scheme
+    pub fn hash_leaf(self, leaf: &ClaimLeaf) -> [u8; 32] {
+        match self {
+            ClaimHashScheme::Sha256 => Sha256ClaimHasher::hash_leaf(leaf),
+            ClaimHashScheme::Keccak256 => Keccak256ClaimHasher::hash_leaf(leaf),
+        }
+    }
+
+    /// Hash an internal node with this scheme, sorting `children` in place
+    pub fn hash_internal(self, children: &mut [[u8; 32]]) -> [u8; 32] {
+        match self {
+            ClaimHashScheme::Sha256 => Sha256ClaimHasher::hash_internal(children),
+            ClaimHashScheme::Keccak256 => Keccak256ClaimHasher::hash_internal(children),
+        }
+    }
+}
+
+impl fmt::Display for ClaimHashScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<ClaimHashScheme> for prism_protocol_verify::HashScheme {
+    fn from(scheme: ClaimHashScheme) -> Self {
+        match scheme {
+            ClaimHashScheme::Sha256 => Self::Sha256,
+            ClaimHashScheme::Keccak256 => Self::Keccak256,
+        }
+    }
+}
+
+impl From<prism_protocol_verify::HashScheme> for ClaimHashScheme {
+    fn from(scheme: prism_protocol_verify::HashScheme) -> Self {
+        match scheme {
+            prism_protocol_verify::HashScheme::Sha256 => Self::Sha256,
+            prism_protocol_verify::HashScheme::Keccak256 => Self::Keccak256,
+        }
+    }
+}
+
+impl FromStr for ClaimHashScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|scheme| scheme.name() == s)
+            .ok_or_else(|| format!("Unknown hash scheme '{}' (expected sha256 or keccak256)", s))
+    }
+}
+
+/// Hash function of a claim tree, tied to its on-chain scheme.
+///
+/// Implementors supply a [`Hasher`]; the leaf encoding and the 0x00/0x01 domain
+/// separation come from `prism-protocol-verify`, so every scheme gets the same tree
+/// structure and proofs as the portable verifier.
+pub trait ClaimHasher: Hasher + Sized {
+    /// Scheme this hasher implements
+    const SCHEME: ClaimHashScheme;
+
+    /// Leaf hash: H(leaf prefix || borsh_serialized_leaf_data)
+    fn hash_leaf(leaf: &ClaimLeaf) -> [u8; 32] {
+        prism_protocol_verify::hash_leaf_with::<Self>(&leaf.into())
+    }
+
+    /// Leaf hash of already borsh-serialized leaf data
+    fn hash_leaf_data(data: &[u8]) -> [u8; 32] {
+        prism_protocol_verify::hash_leaf_data_with::<Self>(data)
+    }
+
+    /// Internal node hash: H(0x01 || children sorted lexicographically)
+    fn hash_internal(children: &mut [[u8; 32]]) -> [u8; 32] {
+        prism_protocol_verify::hash_children::<Self>(children)
+    }
+}
+
+/// SHA-256 claim hasher, the scheme of every cohort before hash schemes existed
+#[derive(Clone, Debug)]
+pub struct Sha256ClaimHasher;
+
+impl ClaimHasher for Sha256ClaimHasher {
+    const SCHEME: ClaimHashScheme = ClaimHashScheme::Sha256;
+}
+
+// Predates the tag, so SHA-256 leaves keep the default untagged prefix
+impl Hasher for Sha256ClaimHasher {
+    fn hashv(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = SolanaHasher::default();
+        for part in parts {
+            hasher.hash(part);
+        }
+        hasher.result().to_bytes()
+    }
+}
+
+/// Keccak-256 claim hasher; uses the `sol_keccak256` syscall on-chain
+#[derive(Clone, Debug)]
+pub struct Keccak256ClaimHasher;
+
+impl ClaimHasher for Keccak256ClaimHasher {
+    const SCHEME: ClaimHashScheme = ClaimHashScheme::Keccak256;
+}
+
+impl Hasher for Keccak256ClaimHasher {
+    const LEAF_PREFIX: &'static [u8] = prism_protocol_verify::Keccak256Hasher::LEAF_PREFIX;
+
+    fn hashv(parts: &[&[u8]]) -> [u8; 32] {
+        solana_keccak_hasher::hashv(parts).to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claim_tree_constants;
+
+    fn fixed_leaf() -> ClaimLeaf {
+        ClaimLeaf {
+            claimant: Pubkey::new_from_array([7u8; 32]),
+            assigned_vault_index: 3,
+            entitlements: 1337,
+        }
+    }
+
+    #[test]
+    fn test_sha256_leaf_matches_to_hash() {
+        let leaf = fixed_leaf();
+        assert_eq!(Sha256ClaimHasher::hash_leaf(&leaf), leaf.to_hash());
+        assert_eq!(ClaimHashScheme::Sha256.hash_leaf(&leaf), leaf.to_hash());
+    }
+
+    #[test]
+    fn test_sha256_matches_portable_hasher() {
+        let leaf = fixed_leaf();
+        assert_eq!(
+            Sha256ClaimHasher::hash_leaf(&leaf),
+            prism_protocol_verify::hash_leaf(&(&leaf).into())
+        );
+        assert_eq!(
+            Sha256ClaimHasher::hash_internal(&mut [[2u8; 32], [1u8; 32]]),
+            prism_protocol_verify::hash_internal_node(&[[1u8; 32], [2u8; 32]])
+        );
+    }
+
+    #[test]
+    fn test_keccak256_leaf_encoding() {
+        let leaf = fixed_leaf();
+        let serialized = leaf.try_to_vec().unwrap();
+
+        let mut preimage = vec![claim_tree_constants::LEAF_PREFIX, 1];
+        preimage.extend_from_slice(&serialized);
+        assert_eq!(
+            Keccak256ClaimHasher::hash_leaf(&leaf),
+            solana_keccak_hasher::hash(&preimage).to_bytes()
+        );
+
+        // Same leaf, different scheme, different hash
+        assert_ne!(Keccak256ClaimHasher::hash_leaf(&leaf), leaf.to_hash());
+    }
+
+    #[test]
+    fn test_keccak256_known_hash() {
+        // Keccak-256 of the empty string, as used by EVM chains
+        assert_eq!(
+            hex_bytes(&Keccak256ClaimHasher::hashv(&[])),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn test_internal_nodes_are_sorted() {
+        for scheme in ClaimHashScheme::ALL {
+            let a = scheme.hash_internal(&mut [[1u8; 32], [2u8; 32], [3u8; 32]]);
+            let b = scheme.hash_internal(&mut [[3u8; 32], [1u8; 32], [2u8; 32]]);
+            assert_eq!(a, b, "{}", scheme);
+        }
+        assert_ne!(
+            ClaimHashScheme::Sha256.hash_internal(&mut [[1u8; 32], [2u8; 32]]),
+            ClaimHashScheme::Keccak256.hash_internal(&mut [[1u8; 32], [2u8; 32]])
+        );
+    }
+
+    #[test]
+    fn test_scheme_tags_and_names() {
+        for scheme in ClaimHashScheme::ALL {
+            assert_eq!(ClaimHashScheme::from_tag(scheme.tag()), Some(scheme));
+            assert_eq!(scheme.to_string().parse::<ClaimHashScheme>(), Ok(scheme));
+            // Borsh encodes the scheme as its tag
+            assert_eq!(scheme.try_to_vec().unwrap(), vec![scheme.tag()]);
+            let portable = prism_protocol_verify::HashScheme::from(scheme);
+            assert_eq!(portable.tag(), scheme.tag());
+            assert_eq!(ClaimHashScheme::from(portable), scheme);
+        }
+        assert_eq!(ClaimHashScheme::from_tag(2), None);
+        assert!("blake3".parse::<ClaimHashScheme>().is_err());
+        assert_eq!(ClaimHashScheme::default(), ClaimHashScheme::Sha256);
+    }
+
+    fn hex_bytes(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
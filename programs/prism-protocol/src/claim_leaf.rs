@@ -1,7 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::Hasher;
 
-use crate::claim_tree_constants;
+use crate::{ClaimHashScheme, ClaimHasher, Sha256ClaimHasher};
 
 /// Represents the data that is hashed to form a leaf in the Merkle tree.
 /// Each leaf corresponds to a unique claimant's entitlement within a specific cohort.
@@ -50,28 +49,34 @@ pub struct ClaimLeaf {
     pub entitlements: u64,
 }
 
+impl From<&ClaimLeaf> for prism_protocol_verify::Leaf {
+    fn from(leaf: &ClaimLeaf) -> Self {
+        Self {
+            claimant: leaf.claimant.to_bytes(),
+            assigned_vault_index: leaf.assigned_vault_index,
+            entitlements: leaf.entitlements,
+        }
+    }
+}
+
 impl ClaimLeaf {
     /// Hash this ClaimLeaf to produce a 32-byte hash suitable for Merkle tree construction.
     /// This follows our merkle tree hashing scheme: SHA256(0x00 || borsh_serialized_leaf_data).
     pub fn to_hash(&self) -> [u8; 32] {
-        let mut hasher = Hasher::default();
-
-        // Prepend 0x00 for a leaf node, following common Solana merkle tree patterns
-        hasher.hash(&[claim_tree_constants::LEAF_PREFIX]);
-
-        // Serialize the leaf data using Borsh
-        let serialized_leaf = self.try_to_vec().expect("Failed to serialize ClaimLeaf");
-
-        // Hash the serialized data
-        hasher.hash(&serialized_leaf);
+        Sha256ClaimHasher::hash_leaf(self)
+    }
 
-        hasher.result().to_bytes()
+    /// Hash this ClaimLeaf for a tree built with the given hash scheme.
+    pub fn to_hash_with_scheme(&self, scheme: ClaimHashScheme) -> [u8; 32] {
+        scheme.hash_leaf(self)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::claim_tree_constants;
+    use anchor_lang::solana_program::hash::Hasher;
 
     #[test]
     fn test_hash_claim_leaf_consistent() {
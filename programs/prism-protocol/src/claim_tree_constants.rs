@@ -1,8 +1,17 @@
 /// Domain separation constants for merkle tree hashing
-/// These must match the constants in the merkle crate to ensure compatibility
+/// Defined by `prism-protocol-verify`, which owns the tree layout
 ///
 /// Domain separation prefix for leaf nodes
-pub const LEAF_PREFIX: u8 = 0x00;
+pub const LEAF_PREFIX: u8 = prism_protocol_verify::LEAF_PREFIX;
 
 /// Domain separation prefix for internal nodes
-pub const INTERNAL_PREFIX: u8 = 0x01;
+pub const INTERNAL_PREFIX: u8 = prism_protocol_verify::INTERNAL_PREFIX;
+
+/// Smallest merkle tree arity a cohort can commit to (a binary tree)
+pub const MIN_TREE_ARITY: u16 = 2;
+
+/// Largest merkle tree arity a cohort can commit to
+pub const MAX_TREE_ARITY: u16 = 256;
+
+/// Arity of binary (V0) claim trees
+pub const BINARY_TREE_ARITY: u16 = MIN_TREE_ARITY;
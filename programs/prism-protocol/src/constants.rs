@@ -7,6 +7,9 @@ pub const CAMPAIGN_V0_SEED_PREFIX: &[u8] = b"campaign_v0";
 #[constant]
 pub const COHORT_V0_SEED_PREFIX: &[u8] = b"cohort_v0";
 
+#[constant]
+pub const COHORT_TREE_V0_SEED_PREFIX: &[u8] = b"cohort_tree_v0";
+
 #[constant]
 pub const CLAIM_RECEIPT_V0_SEED_PREFIX: &[u8] = b"claim_receipt_v0";
 
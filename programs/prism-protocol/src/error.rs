@@ -71,4 +71,18 @@ pub enum ErrorCode {
     // Claiming errors
     #[msg("Vault index mismatch: the assigned vault index is out of bounds for this cohort.")]
     AssignedVaultIndexOutOfBounds,
+    #[msg("Vault mismatch: the account is not the cohort vault at the assigned index.")]
+    VaultMismatch,
+    #[msg("Claim receipt mismatch: the account is not the claimant's receipt for this cohort.")]
+    ClaimReceiptMismatch,
+    #[msg("Empty claim batch: a batch must settle at least one claim.")]
+    EmptyClaimBatch,
+    #[msg("Claim batch accounts mismatch: each claim needs a claimant, vault, token account and claim receipt.")]
+    ClaimBatchAccountsMismatch,
+
+    // Merkle tree layout errors
+    #[msg("Invalid merkle tree arity: must be between 2 and 256.")]
+    InvalidMerkleTreeArity,
+    #[msg("Cohort tree mismatch: the cohort tree account does not belong to this cohort.")]
+    CohortTreeMismatch,
 }
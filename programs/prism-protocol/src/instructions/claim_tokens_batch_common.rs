@@ -0,0 +1,254 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, System};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::claim_leaf::ClaimLeaf;
+use crate::constants::VAULT_SEED_PREFIX;
+use crate::error::ErrorCode;
+use crate::proofs::ClaimMultiProofType;
+use crate::state::{CampaignV0, ClaimReceiptV0, CohortV0};
+use crate::{ClaimHashScheme, CLAIM_RECEIPT_V0_SEED_PREFIX, COHORT_V0_SEED_PREFIX};
+
+/// Number of remaining accounts per claim in a batch:
+/// `[claimant (signer), vault (mut), claimant_token_account (mut), claim_receipt (mut)]`
+pub const CLAIM_BATCH_ACCOUNTS_PER_CLAIM: usize = 4;
+
+/// One claim of a batch. The claimant is the matching signer in the remaining accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ClaimBatchEntry {
+    pub assigned_vault_index: u8,
+    pub entitlements: u64,
+}
+
+/// Common implementation for claim_tokens_batch_v0 and claim_tokens_batch_v1.
+///
+/// Claims are given in tree order, the order the multiproof consumes their leaves, and
+/// each takes [`CLAIM_BATCH_ACCOUNTS_PER_CLAIM`] remaining accounts. The payer funds
+/// the claim receipts; every claimant still signs, and receives the tokens in a token
+/// account of their own.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_claim_tokens_batch_common<'info>(
+    payer: &Signer<'info>,
+    campaign: &Account<'info, CampaignV0>,
+    cohort: &Account<'info, CohortV0>,
+    mint: &Account<'info, Mint>,
+    token_program: &Program<'info, Token>,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    cohort_merkle_root: [u8; 32],
+    proof: ClaimMultiProofType,
+    merkle_tree_arity: u16,
+    hash_scheme: ClaimHashScheme,
+    claims: &[ClaimBatchEntry],
+) -> Result<()> {
+    // 0. Basic argument validation
+    require!(!claims.is_empty(), ErrorCode::EmptyClaimBatch);
+    require!(
+        remaining_accounts.len() == claims.len() * CLAIM_BATCH_ACCOUNTS_PER_CLAIM,
+        ErrorCode::ClaimBatchAccountsMismatch
+    );
+
+    // 1. Check campaign go-live slot
+    let current_slot = Clock::get()?.slot;
+    require!(
+        current_slot >= campaign.go_live_slot,
+        ErrorCode::GoLiveDateNotReached
+    );
+
+    // 2. Construct the leaves from the claims and their signing claimants
+    let claim_accounts = |index: usize| {
+        &remaining_accounts
+            [index * CLAIM_BATCH_ACCOUNTS_PER_CLAIM..(index + 1) * CLAIM_BATCH_ACCOUNTS_PER_CLAIM]
+    };
+    let mut leaves = Vec::with_capacity(claims.len());
+    for (index, claim) in claims.iter().enumerate() {
+        require!(claim.entitlements > 0, ErrorCode::InvalidEntitlements);
+        require!(
+            claim.assigned_vault_index < cohort.expected_vault_count,
+            ErrorCode::AssignedVaultIndexOutOfBounds
+        );
+        let claimant = &claim_accounts(index)[0];
+        require!(
+            claimant.is_signer,
+            anchor_lang::error::ErrorCode::AccountNotSigner
+        );
+
+        leaves.push(ClaimLeaf {
+            claimant: claimant.key(),
+            assigned_vault_index: claim.assigned_vault_index,
+            entitlements: claim.entitlements,
+        });
+    }
+
+    // 3. Verify the multiproof with the cohort's hash scheme and tree arity
+    if !proof.verify_with_scheme(&cohort.merkle_root, &leaves, merkle_tree_arity, hash_scheme) {
+        return err!(ErrorCode::InvalidMerkleProof);
+    }
+    msg!(
+        "{} for {} claims ({}) verified successfully.",
+        proof.description(),
+        leaves.len(),
+        hash_scheme
+    );
+
+    // 4. Pay out each claim
+    let campaign_key = campaign.key();
+    let cohort_key = cohort.key();
+    let cohort_seeds = &[
+        COHORT_V0_SEED_PREFIX,
+        campaign_key.as_ref(),
+        cohort_merkle_root.as_ref(),
+        &[cohort.bump],
+    ];
+    let rent = Rent::get()?;
+
+    for (index, leaf) in leaves.iter().enumerate() {
+        let [_, vault, claimant_token_account, claim_receipt] = claim_accounts(index) else {
+            return err!(ErrorCode::ClaimBatchAccountsMismatch);
+        };
+
+        // The vault at the assigned index
+        let (vault_address, _) = Pubkey::find_program_address(
+            &[
+                VAULT_SEED_PREFIX,
+                cohort_key.as_ref(),
+                &leaf.assigned_vault_index.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(vault.key(), vault_address, ErrorCode::VaultMismatch);
+        let vault_account = Account::<TokenAccount>::try_from(vault)?;
+        require_keys_eq!(vault_account.mint, mint.key(), ErrorCode::MintMismatch);
+
+        // A token account of the claimant for the campaign mint
+        let token_account = Account::<TokenAccount>::try_from(claimant_token_account)?;
+        require_keys_eq!(token_account.mint, mint.key(), ErrorCode::MintMismatch);
+        require_keys_eq!(
+            token_account.owner,
+            leaf.claimant,
+            ErrorCode::TokenAccountOwnerMismatch
+        );
+
+        // Create the claim receipt, preventing replays
+        let (receipt_address, receipt_bump) = Pubkey::find_program_address(
+            &[
+                CLAIM_RECEIPT_V0_SEED_PREFIX,
+                cohort_key.as_ref(),
+                leaf.claimant.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            claim_receipt.key(),
+            receipt_address,
+            ErrorCode::ClaimReceiptMismatch
+        );
+        let receipt_seeds = &[
+            CLAIM_RECEIPT_V0_SEED_PREFIX,
+            cohort_key.as_ref(),
+            leaf.claimant.as_ref(),
+            &[receipt_bump],
+        ];
+        create_claim_receipt(
+            payer,
+            claim_receipt,
+            system_program,
+            &rent,
+            &[&receipt_seeds[..]],
+        )?;
+
+        // Transfer the claimed tokens, signed by the cohort (which owns the vaults)
+        let total_amount = cohort
+            .amount_per_entitlement
+            .checked_mul(leaf.entitlements)
+            .ok_or(ErrorCode::NumericOverflow)?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: vault.clone(),
+                    to: claimant_token_account.clone(),
+                    authority: cohort.to_account_info(),
+                },
+                &[&cohort_seeds[..]],
+            ),
+            total_amount,
+        )?;
+
+        let receipt = ClaimReceiptV0 {
+            cohort: cohort_key,
+            claimant: leaf.claimant,
+            assigned_vault: vault.key(),
+            claimed_at_timestamp: Clock::get()?.unix_timestamp,
+            bump: receipt_bump,
+        };
+        receipt.try_serialize(&mut &mut claim_receipt.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
+
+/// Create a claim receipt PDA owned by the program, like Anchor's `init`.
+///
+/// Fails if the receipt already exists, so a claimant can only be paid once.
+fn create_claim_receipt<'info>(
+    payer: &Signer<'info>,
+    claim_receipt: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    rent: &Rent,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let space = 8 + ClaimReceiptV0::INIT_SPACE;
+    let required_lamports = rent.minimum_balance(space);
+    let current_lamports = claim_receipt.lamports();
+
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: claim_receipt.clone(),
+                },
+                signer_seeds,
+            ),
+            required_lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    // The address was pre-funded: top it up, then allocate and assign it
+    if required_lamports > current_lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: claim_receipt.clone(),
+                },
+            ),
+            required_lamports - current_lamports,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: claim_receipt.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Assign {
+                account_to_assign: claim_receipt.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::claim_tree_constants::BINARY_TREE_ARITY;
+use crate::error::ErrorCode;
+use crate::instructions::claim_tokens_batch_common::{
+    handle_claim_tokens_batch_common, ClaimBatchEntry,
+};
+use crate::proofs::{ClaimMultiProofType, ClaimMultiProofV0};
+use crate::state::{CampaignStatus, CampaignV0, CohortV0};
+use crate::{ClaimHashScheme, CAMPAIGN_V0_SEED_PREFIX, COHORT_V0_SEED_PREFIX};
+
+/// Settles a batch of claims from a binary (V0) cohort with one multiproof.
+///
+/// Each claim takes `[claimant (signer), vault (mut), claimant_token_account (mut),
+/// claim_receipt (mut)]` from the remaining accounts, in the order of `claims`.
+#[derive(Accounts)]
+#[instruction(
+    campaign_fingerprint: [u8; 32], // Used to find Campaign PDA
+    merkle_root: [u8; 32], // Used to find Cohort PDA (this is the cohort.merkle_root)
+)]
+pub struct ClaimTokensBatchV0<'info> {
+    /// CHECK: This account is validated through the campaign PDA seeds constraint.
+    /// The admin key is used as a seed for deriving the campaign PDA, ensuring
+    /// that only the correct admin can be used for the specific campaign.
+    #[account()]
+    pub admin: UncheckedAccount<'info>,
+
+    /// Pays for the claim receipts (typically a relayer settling the batch).
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [
+            CAMPAIGN_V0_SEED_PREFIX,
+            admin.key().as_ref(),
+            campaign_fingerprint.as_ref(),
+        ],
+        bump = campaign.bump,
+        constraint = campaign.status == CampaignStatus::Active @ ErrorCode::CampaignNotActive,
+        constraint = campaign.mint == mint.key() @ ErrorCode::MintMismatch,
+        constraint = campaign.fingerprint == campaign_fingerprint @ ErrorCode::CampaignFingerprintMismatch,
+    )]
+    pub campaign: Box<Account<'info, CampaignV0>>,
+
+    #[account(
+        seeds = [
+            COHORT_V0_SEED_PREFIX,
+            campaign.key().as_ref(),
+            merkle_root.as_ref()
+        ],
+        bump = cohort.bump,
+        constraint = cohort.campaign == campaign.key() @ ErrorCode::CohortCampaignMismatch,
+        constraint = cohort.merkle_root == merkle_root @ ErrorCode::MerkleRootMismatch,
+    )]
+    pub cohort: Box<Account<'info, CohortV0>>,
+
+    /// The mint of the token being distributed.
+    #[account(
+        constraint = mint.key() == campaign.mint @ ErrorCode::MintMismatch
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_tokens_batch_v0<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimTokensBatchV0<'info>>,
+    _campaign_fingerprint: [u8; 32], // Consumed by Accounts macro for seed derivation
+    cohort_merkle_root: [u8; 32], // Consumed by Accounts macro for seed derivation, also checked in constraint
+    multiproof: ClaimMultiProofV0,
+    claims: Vec<ClaimBatchEntry>,
+) -> Result<()> {
+    handle_claim_tokens_batch_common(
+        &ctx.accounts.payer,
+        &ctx.accounts.campaign,
+        &ctx.accounts.cohort,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        ctx.remaining_accounts,
+        cohort_merkle_root,
+        ClaimMultiProofType::V0(multiproof),
+        BINARY_TREE_ARITY,
+        ClaimHashScheme::Sha256,
+        &claims,
+    )
+}
@@ -5,26 +5,58 @@ use crate::claim_leaf::ClaimLeaf;
 use crate::error::ErrorCode;
 use crate::proofs::ClaimProofType;
 use crate::state::{CampaignV0, ClaimReceiptV0, CohortV0};
-use crate::COHORT_V0_SEED_PREFIX;
+use crate::{ClaimHashScheme, COHORT_V0_SEED_PREFIX};
 
-/// Common implementation for both claim_tokens_v0 and claim_tokens_v1.
+/// Accounts of a single claim, borrowed from the claim instruction's context
+pub(crate) struct ClaimAccounts<'a, 'info> {
+    pub claimant: &'a Signer<'info>,
+    pub campaign: &'a Account<'info, CampaignV0>,
+    pub cohort: &'a Account<'info, CohortV0>,
+    pub vault: &'a mut Account<'info, TokenAccount>,
+    pub claimant_token_account: &'a mut Account<'info, TokenAccount>,
+    pub claim_receipt: &'a mut Account<'info, ClaimReceiptV0>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+/// The claimed leaf, its proof and the tree shape and hash scheme it is verified against
+pub(crate) struct ClaimParams {
+    pub cohort_merkle_root: [u8; 32],
+    pub proof: ClaimProofType,
+    pub merkle_tree_arity: u16,
+    pub hash_scheme: ClaimHashScheme,
+    pub assigned_vault_index: u8,
+    pub entitlements: u64,
+    pub claim_receipt_bump: u8,
+}
+
+/// Common implementation for claim_tokens_v0, claim_tokens_v1 and claim_tokens_v2.
 ///
-/// This function contains all the shared logic between the two claim handlers,
-/// with the only difference being the proof type verification.
-pub(crate) fn handle_claim_tokens_common<'info>(
-    claimant: &Signer<'info>,
-    campaign: &Account<'info, CampaignV0>,
-    cohort: &Account<'info, CohortV0>,
-    vault: &mut Account<'info, TokenAccount>,
-    claimant_token_account: &mut Account<'info, TokenAccount>,
-    claim_receipt: &mut Account<'info, ClaimReceiptV0>,
-    token_program: &Program<'info, Token>,
-    cohort_merkle_root: [u8; 32],
-    proof: ClaimProofType,
-    assigned_vault_index: u8,
-    entitlements: u64,
-    claim_receipt_bump: u8,
+/// This function contains all the shared logic between the claim handlers,
+/// with the only difference being the proof type and the tree arity and hash scheme it is
+/// verified against.
+pub(crate) fn handle_claim_tokens_common(
+    accounts: ClaimAccounts<'_, '_>,
+    params: ClaimParams,
 ) -> Result<()> {
+    let ClaimAccounts {
+        claimant,
+        campaign,
+        cohort,
+        vault,
+        claimant_token_account,
+        claim_receipt,
+        token_program,
+    } = accounts;
+    let ClaimParams {
+        cohort_merkle_root,
+        proof,
+        merkle_tree_arity,
+        hash_scheme,
+        assigned_vault_index,
+        entitlements,
+        claim_receipt_bump,
+    } = params;
+
     // 0. Basic argument validation
     require!(entitlements > 0, ErrorCode::InvalidEntitlements);
 
@@ -48,11 +80,15 @@ pub(crate) fn handle_claim_tokens_common<'info>(
         entitlements,
     };
 
-    // 4. Verify the Merkle proof using our hashing scheme (SHA256)
-    if !proof.verify(&cohort.merkle_root, &leaf) {
+    // 4. Verify the Merkle proof with the cohort's hash scheme and tree arity
+    if !proof.verify_with_scheme(&cohort.merkle_root, &leaf, merkle_tree_arity, hash_scheme) {
         return err!(ErrorCode::InvalidMerkleProof);
     }
-    msg!("{} verified successfully.", proof.description());
+    msg!(
+        "{} ({}) verified successfully.",
+        proof.description(),
+        hash_scheme
+    );
 
     // 5. Check if already claimed (ClaimReceipt PDA is initialized, so this prevents re-init)
     // The init constraint on ClaimReceipt already handles this.
@@ -1,12 +1,17 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+use crate::claim_tree_constants::MAX_TREE_ARITY;
 use crate::constants::VAULT_SEED_PREFIX;
 use crate::error::ErrorCode;
-use crate::instructions::claim_tokens_common::handle_claim_tokens_common;
+use crate::instructions::claim_tokens_common::{
+    handle_claim_tokens_common, ClaimAccounts, ClaimParams,
+};
 use crate::proofs::ClaimProofType;
 use crate::state::{CampaignStatus, CampaignV0, ClaimReceiptV0, CohortV0};
-use crate::{CAMPAIGN_V0_SEED_PREFIX, CLAIM_RECEIPT_V0_SEED_PREFIX, COHORT_V0_SEED_PREFIX};
+use crate::{
+    ClaimHashScheme, CAMPAIGN_V0_SEED_PREFIX, CLAIM_RECEIPT_V0_SEED_PREFIX, COHORT_V0_SEED_PREFIX,
+};
 
 #[derive(Accounts)]
 #[instruction(
@@ -114,17 +119,23 @@ pub fn handle_claim_tokens_v1(
 
     // Delegate to common handler
     handle_claim_tokens_common(
-        &ctx.accounts.claimant,
-        &ctx.accounts.campaign,
-        &ctx.accounts.cohort,
-        &mut ctx.accounts.vault,
-        &mut ctx.accounts.claimant_token_account,
-        &mut ctx.accounts.claim_receipt,
-        &ctx.accounts.token_program,
-        cohort_merkle_root,
-        proof,
-        assigned_vault_index,
-        entitlements,
-        ctx.bumps.claim_receipt,
+        ClaimAccounts {
+            claimant: &ctx.accounts.claimant,
+            campaign: &ctx.accounts.campaign,
+            cohort: &ctx.accounts.cohort,
+            vault: &mut ctx.accounts.vault,
+            claimant_token_account: &mut ctx.accounts.claimant_token_account,
+            claim_receipt: &mut ctx.accounts.claim_receipt,
+            token_program: &ctx.accounts.token_program,
+        },
+        ClaimParams {
+            cohort_merkle_root,
+            proof,
+            merkle_tree_arity: MAX_TREE_ARITY,
+            hash_scheme: ClaimHashScheme::Sha256,
+            assigned_vault_index,
+            entitlements,
+            claim_receipt_bump: ctx.bumps.claim_receipt,
+        },
     )
 }
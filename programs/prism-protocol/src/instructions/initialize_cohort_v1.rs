@@ -0,0 +1,112 @@
+use crate::claim_tree_constants::{MAX_TREE_ARITY, MIN_TREE_ARITY};
+use crate::error::ErrorCode;
+use crate::state::{CampaignStatus, CampaignV0, CohortTreeV0, CohortV0};
+use crate::{
+    ClaimHashScheme, CAMPAIGN_V0_SEED_PREFIX, COHORT_TREE_V0_SEED_PREFIX, COHORT_V0_SEED_PREFIX,
+};
+use anchor_lang::prelude::*;
+
+/// Initializes a cohort whose merkle tree has a committed arity and hash scheme (claimed with
+/// `claim_tokens_v2`).
+///
+/// The cohort account is the same as with `initialize_cohort_v0`, so vaults, activation and
+/// reclaiming work unchanged; the tree layout lives in a CohortTree account beside it.
+#[derive(Accounts)]
+#[instruction(
+    campaign_fingerprint: [u8; 32],
+    merkle_root: [u8; 32],
+    amount_per_entitlement: u64,
+    expected_vault_count: u8,
+    merkle_tree_arity: u16,
+    hash_scheme: ClaimHashScheme
+)]
+pub struct InitializeCohortV1<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            CAMPAIGN_V0_SEED_PREFIX,
+            admin.key().as_ref(),
+            campaign_fingerprint.as_ref()
+        ],
+        bump = campaign.bump,
+        has_one = admin @ ErrorCode::CampaignAdminMismatch,
+        constraint = campaign.fingerprint == campaign_fingerprint @ ErrorCode::CampaignFingerprintMismatch,
+        constraint = campaign.status == CampaignStatus::Inactive @ ErrorCode::CampaignIsActive,
+    )]
+    pub campaign: Account<'info, CampaignV0>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CohortV0::INIT_SPACE,
+        seeds = [
+            COHORT_V0_SEED_PREFIX,
+            campaign.key().as_ref(),
+            merkle_root.as_ref(),
+        ],
+        bump
+    )]
+    pub cohort: Account<'info, CohortV0>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CohortTreeV0::INIT_SPACE,
+        seeds = [
+            COHORT_TREE_V0_SEED_PREFIX,
+            cohort.key().as_ref(),
+        ],
+        bump
+    )]
+    pub cohort_tree: Account<'info, CohortTreeV0>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_cohort_v1(
+    ctx: Context<InitializeCohortV1>,
+    _campaign_fingerprint: [u8; 32], // consumed in account constraints
+    merkle_root: [u8; 32],
+    amount_per_entitlement: u64,
+    expected_vault_count: u8,
+    merkle_tree_arity: u16,
+    hash_scheme: ClaimHashScheme,
+) -> Result<()> {
+    require!(expected_vault_count > 0, ErrorCode::NoVaultsExpected);
+    require!(amount_per_entitlement > 0, ErrorCode::InvalidEntitlements);
+    require!(
+        (MIN_TREE_ARITY..=MAX_TREE_ARITY).contains(&merkle_tree_arity),
+        ErrorCode::InvalidMerkleTreeArity
+    );
+
+    let cohort = &mut ctx.accounts.cohort;
+    cohort.set_inner(CohortV0 {
+        campaign: ctx.accounts.campaign.key(),
+        merkle_root,
+        amount_per_entitlement,
+        expected_vault_count,       // Set during cohort initialization
+        initialized_vault_count: 0, // Incremented during vault creation
+        activated_vault_count: 0,   // Incremented during vault activation
+        bump: ctx.bumps.cohort,
+    });
+
+    let cohort_tree = &mut ctx.accounts.cohort_tree;
+    cohort_tree.set_inner(CohortTreeV0 {
+        cohort: cohort.key(),
+        merkle_tree_arity,
+        hash_scheme,
+        bump: ctx.bumps.cohort_tree,
+    });
+
+    // Increment campaign's initialized cohort count
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.initialized_cohort_count = campaign
+        .initialized_cohort_count
+        .checked_add(1)
+        .ok_or(ErrorCode::NumericOverflow)?;
+
+    Ok(())
+}
@@ -1,11 +1,16 @@
 pub mod activate_campaign_v0;
 pub mod activate_cohort_v0;
 pub mod activate_vault_v0;
+pub mod claim_tokens_batch_common;
+pub mod claim_tokens_batch_v0;
+pub mod claim_tokens_batch_v1;
 pub mod claim_tokens_common;
 pub mod claim_tokens_v0;
 pub mod claim_tokens_v1;
+pub mod claim_tokens_v2;
 pub mod initialize_campaign_v0;
 pub mod initialize_cohort_v0;
+pub mod initialize_cohort_v1;
 pub mod initialize_vault_v0;
 pub mod make_campaign_unstoppable_v0;
 pub mod pause_campaign_v0;
@@ -16,10 +21,15 @@ pub mod resume_campaign_v0;
 pub use activate_campaign_v0::*;
 pub use activate_cohort_v0::*;
 pub use activate_vault_v0::*;
+pub use claim_tokens_batch_common::{ClaimBatchEntry, CLAIM_BATCH_ACCOUNTS_PER_CLAIM};
+pub use claim_tokens_batch_v0::*;
+pub use claim_tokens_batch_v1::*;
 pub use claim_tokens_v0::*;
 pub use claim_tokens_v1::*;
+pub use claim_tokens_v2::*;
 pub use initialize_campaign_v0::*;
 pub use initialize_cohort_v0::*;
+pub use initialize_cohort_v1::*;
 pub use initialize_vault_v0::*;
 pub use make_campaign_unstoppable_v0::*;
 pub use pause_campaign_v0::*;
@@ -1,3 +1,4 @@
+pub mod claim_hasher;
 pub mod claim_leaf;
 pub mod claim_tree_constants;
 pub mod constants;
@@ -6,9 +7,11 @@ pub mod instructions;
 pub mod proofs;
 pub mod state;
 
+pub use claim_hasher::*;
 pub use claim_leaf::*;
 pub use constants::{
-    CAMPAIGN_V0_SEED_PREFIX, CLAIM_RECEIPT_V0_SEED_PREFIX, COHORT_V0_SEED_PREFIX, VAULT_SEED_PREFIX,
+    CAMPAIGN_V0_SEED_PREFIX, CLAIM_RECEIPT_V0_SEED_PREFIX, COHORT_TREE_V0_SEED_PREFIX,
+    COHORT_V0_SEED_PREFIX, VAULT_SEED_PREFIX,
 };
 pub use instructions::*;
 pub use proofs::*;
@@ -102,6 +105,27 @@ pub mod prism_protocol {
         )
     }
 
+    // admin
+    pub fn initialize_cohort_v1(
+        ctx: Context<InitializeCohortV1>,
+        campaign_fingerprint: [u8; 32],
+        merkle_root: [u8; 32],
+        amount_per_entitlement: u64,
+        expected_vault_count: u8,
+        merkle_tree_arity: u16,
+        hash_scheme: ClaimHashScheme,
+    ) -> Result<()> {
+        instructions::handle_initialize_cohort_v1(
+            ctx,
+            campaign_fingerprint,
+            merkle_root,
+            amount_per_entitlement,
+            expected_vault_count,
+            merkle_tree_arity,
+            hash_scheme,
+        )
+    }
+
     // admin
     pub fn activate_cohort_v0(
         ctx: Context<ActivateCohortV0>,
@@ -181,6 +205,59 @@ pub mod prism_protocol {
         )
     }
 
+    // claimant
+    pub fn claim_tokens_v2(
+        ctx: Context<ClaimTokensV2>,
+        campaign_fingerprint: [u8; 32],
+        cohort_merkle_root: [u8; 32],
+        merkle_proof: Vec<Vec<[u8; 32]>>,
+        assigned_vault_index: u8,
+        entitlements: u64,
+    ) -> Result<()> {
+        instructions::handle_claim_tokens_v2(
+            ctx,
+            campaign_fingerprint,
+            cohort_merkle_root,
+            merkle_proof,
+            assigned_vault_index,
+            entitlements,
+        )
+    }
+
+    // relayer, signed by every claimant
+    pub fn claim_tokens_batch_v0<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimTokensBatchV0<'info>>,
+        campaign_fingerprint: [u8; 32],
+        cohort_merkle_root: [u8; 32],
+        multiproof: ClaimMultiProofV0,
+        claims: Vec<ClaimBatchEntry>,
+    ) -> Result<()> {
+        instructions::handle_claim_tokens_batch_v0(
+            ctx,
+            campaign_fingerprint,
+            cohort_merkle_root,
+            multiproof,
+            claims,
+        )
+    }
+
+    // relayer, signed by every claimant
+    pub fn claim_tokens_batch_v1<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimTokensBatchV1<'info>>,
+        campaign_fingerprint: [u8; 32],
+        cohort_merkle_root: [u8; 32],
+        multiproof: ClaimMultiProofV1,
+        claims: Vec<ClaimBatchEntry>,
+    ) -> Result<()> {
+        instructions::handle_claim_tokens_batch_v1(
+            ctx,
+            campaign_fingerprint,
+            cohort_merkle_root,
+            multiproof,
+            claims,
+        )
+    }
+
     // admin
     pub fn reclaim_tokens_v0(
         ctx: Context<ReclaimTokensV0>,
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::Hasher as SolanaHasher;
 
-use crate::{claim_tree_constants, ClaimLeaf};
+use crate::{
+    claim_tree_constants, ClaimHashScheme, ClaimHasher, ClaimLeaf, Keccak256ClaimHasher,
+    Sha256ClaimHasher,
+};
 
 /// Unified proof type that can hold either binary (V0) or 256-ary (V1) merkle proofs.
 /// This enables code reuse between claim_tokens_v0 and claim_tokens_v1 handlers.
@@ -24,11 +26,43 @@ impl ClaimProofType {
         Self::V1(ClaimProofV1::new(proof))
     }
 
-    /// Verify the proof against a root and leaf, regardless of proof type
-    pub fn verify(&self, root: &[u8; 32], leaf: &ClaimLeaf) -> bool {
+    /// Verify the proof against a root and leaf for a tree of the given arity.
+    ///
+    /// Binary proofs only verify against binary trees; wide proofs are rejected if any
+    /// level has more siblings than the arity allows.
+    pub fn verify(&self, root: &[u8; 32], leaf: &ClaimLeaf, arity: u16) -> bool {
+        self.verify_with::<Sha256ClaimHasher>(root, leaf, arity)
+    }
+
+    /// Verify like [`ClaimProofType::verify`], hashing with `H`.
+    pub fn verify_with<H: ClaimHasher>(
+        &self,
+        root: &[u8; 32],
+        leaf: &ClaimLeaf,
+        arity: u16,
+    ) -> bool {
         match self {
-            ClaimProofType::V0(proof) => proof.verify(root, leaf),
-            ClaimProofType::V1(proof) => proof.verify(root, leaf),
+            ClaimProofType::V0(proof) => {
+                arity == claim_tree_constants::BINARY_TREE_ARITY
+                    && proof.verify_with::<H>(root, leaf)
+            }
+            ClaimProofType::V1(proof) => proof.verify_with::<H>(root, leaf, arity),
+        }
+    }
+
+    /// Verify for a cohort committed to `scheme`, as the claim instructions do.
+    pub fn verify_with_scheme(
+        &self,
+        root: &[u8; 32],
+        leaf: &ClaimLeaf,
+        arity: u16,
+        scheme: ClaimHashScheme,
+    ) -> bool {
+        match scheme {
+            ClaimHashScheme::Sha256 => self.verify_with::<Sha256ClaimHasher>(root, leaf, arity),
+            ClaimHashScheme::Keccak256 => {
+                self.verify_with::<Keccak256ClaimHasher>(root, leaf, arity)
+            }
         }
     }
 
@@ -93,25 +127,13 @@ impl ClaimProofV0 {
     /// The prefix bytes ensure that leaf hashes can never equal internal node hashes,
     /// preventing attackers from forging proofs by substituting node types.
     pub fn verify(&self, root: &[u8; 32], leaf: &ClaimLeaf) -> bool {
-        let leaf_hash = leaf.to_hash();
-        let mut computed_hash = leaf_hash;
-
-        for p_elem in self.0.iter() {
-            let mut hasher = SolanaHasher::default(); // Uses SHA256 by default
-            hasher.hash(&[claim_tree_constants::INTERNAL_PREFIX]); // Internal node prefix - provides domain separation from leaf nodes (0x00)
-
-            // Correctly order H(L) and H(R) before hashing for the parent node.
-            if computed_hash <= *p_elem {
-                hasher.hash(&computed_hash);
-                hasher.hash(p_elem);
-            } else {
-                hasher.hash(p_elem);
-                hasher.hash(&computed_hash);
-            }
-            computed_hash = hasher.result().to_bytes();
-        }
+        self.verify_with::<Sha256ClaimHasher>(root, leaf)
+    }
 
-        computed_hash == *root
+    /// Verify with the same domain separation, hashing with `H`.
+    pub fn verify_with<H: ClaimHasher>(&self, root: &[u8; 32], leaf: &ClaimLeaf) -> bool {
+        // Each level orders H(L) and H(R) before hashing for the parent node.
+        prism_protocol_verify::fold_proof_v0::<H>(H::hash_leaf(leaf), &self.0) == *root
     }
 }
 
@@ -183,30 +205,31 @@ impl ClaimProofV1 {
     ///    - Result becomes the computed hash for the next level
     /// 3. Final computed hash should equal the provided root
     pub fn verify(&self, root: &[u8; 32], leaf: &ClaimLeaf) -> bool {
-        let mut computed_hash = leaf.to_hash();
-
-        // Process each level of the proof from bottom to top
-        for level_siblings in self.0.iter() {
-            // Collect all hashes at this level: current computed hash + all siblings
-            let mut level_hashes = Vec::with_capacity(level_siblings.len() + 1);
-            level_hashes.push(computed_hash);
-            level_hashes.extend_from_slice(level_siblings);
-
-            // Sort all hashes lexicographically for deterministic ordering
-            level_hashes.sort();
-
-            // Hash the sorted children to get the parent node hash
-            let mut hasher = SolanaHasher::default();
-            hasher.hash(&[claim_tree_constants::INTERNAL_PREFIX]); // Internal node prefix - provides domain separation from leaf nodes (0x00)
+        self.fold::<Sha256ClaimHasher>(leaf) == *root
+    }
 
-            for child_hash in level_hashes {
-                hasher.hash(&child_hash);
-            }
+    /// Verify the proof for a tree with at most `arity` children per internal node.
+    ///
+    /// A level with `arity` or more siblings can't come from such a tree and fails
+    /// before any hashing.
+    pub fn verify_with_arity(&self, root: &[u8; 32], leaf: &ClaimLeaf, arity: u16) -> bool {
+        self.verify_with::<Sha256ClaimHasher>(root, leaf, arity)
+    }
 
-            computed_hash = hasher.result().to_bytes();
-        }
+    /// Verify like [`ClaimProofV1::verify_with_arity`], hashing with `H`.
+    pub fn verify_with<H: ClaimHasher>(
+        &self,
+        root: &[u8; 32],
+        leaf: &ClaimLeaf,
+        arity: u16,
+    ) -> bool {
+        self.max_level_width() < arity as usize && self.fold::<H>(leaf) == *root
+    }
 
-        computed_hash == *root
+    /// Fold the proof from the leaf up, returning the computed root
+    fn fold<H: ClaimHasher>(&self, leaf: &ClaimLeaf) -> [u8; 32] {
+        // Each level sorts the computed hash with its siblings and hashes them together
+        prism_protocol_verify::fold_proof_v1::<H>(H::hash_leaf(leaf), &self.0)
     }
 }
 
@@ -223,9 +246,222 @@ impl From<Vec<Vec<[u8; 32]>>> for ClaimProofV1 {
     }
 }
 
+// ================================================================================================
+// Multiproofs
+// ================================================================================================
+
+/// Unified multiproof type for batches of leaves in binary (V0) or wide (V1) trees.
+///
+/// `claim_tokens_batch_v0` verifies binary multiproofs and `claim_tokens_batch_v1` wide ones.
+#[derive(Clone, Debug)]
+pub enum ClaimMultiProofType {
+    /// Multiproof for a binary merkle tree (V0)
+    V0(ClaimMultiProofV0),
+    /// Multiproof for a wide merkle tree (V1)
+    V1(ClaimMultiProofV1),
+}
+
+impl ClaimMultiProofType {
+    /// Verify that every leaf is in the tree with `root` and the given arity.
+    ///
+    /// Binary multiproofs only verify against binary trees, like [`ClaimProofType::verify`].
+    pub fn verify(&self, root: &[u8; 32], leaves: &[ClaimLeaf], arity: u16) -> bool {
+        self.verify_with::<Sha256ClaimHasher>(root, leaves, arity)
+    }
+
+    /// Verify like [`ClaimMultiProofType::verify`], hashing with `H`.
+    pub fn verify_with<H: ClaimHasher>(
+        &self,
+        root: &[u8; 32],
+        leaves: &[ClaimLeaf],
+        arity: u16,
+    ) -> bool {
+        match self {
+            ClaimMultiProofType::V0(proof) => {
+                arity == claim_tree_constants::BINARY_TREE_ARITY
+                    && proof.verify_with::<H>(root, leaves)
+            }
+            ClaimMultiProofType::V1(proof) => proof.verify_with::<H>(root, leaves, arity),
+        }
+    }
+
+    /// Verify for a cohort committed to `scheme`.
+    pub fn verify_with_scheme(
+        &self,
+        root: &[u8; 32],
+        leaves: &[ClaimLeaf],
+        arity: u16,
+        scheme: ClaimHashScheme,
+    ) -> bool {
+        match scheme {
+            ClaimHashScheme::Sha256 => self.verify_with::<Sha256ClaimHasher>(root, leaves, arity),
+            ClaimHashScheme::Keccak256 => {
+                self.verify_with::<Keccak256ClaimHasher>(root, leaves, arity)
+            }
+        }
+    }
+
+    /// Get a descriptive name for logging
+    pub fn description(&self) -> &'static str {
+        match self {
+            ClaimMultiProofType::V0(_) => "Binary merkle multiproof",
+            ClaimMultiProofType::V1(_) => "Wide merkle multiproof",
+        }
+    }
+}
+
+/// One internal node reconstructed by a multiproof.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ClaimMultiProofNode {
+    /// Children already known from the level below (the batch's leaves, or nodes
+    /// reconstructed at the previous level), taken in order
+    pub known_children: u16,
+    /// Hashes of the node's other children
+    pub siblings: Vec<[u8; 32]>,
+}
+
+/// Multiproof for a batch of leaves in a binary (V0) merkle tree.
+///
+/// Siblings shared by several leaves' paths, and nodes reconstructed from the batch
+/// itself, are only carried once. Levels run from the leaves to the root; each level
+/// lists the parents of the known nodes in tree order. A node with a single child and
+/// no siblings is the promoted last node of an odd level and is not hashed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ClaimMultiProofV0(pub Vec<Vec<ClaimMultiProofNode>>);
+
+impl ClaimMultiProofV0 {
+    /// Create a new binary tree multiproof
+    pub fn new(levels: Vec<Vec<ClaimMultiProofNode>>) -> Self {
+        Self(levels)
+    }
+
+    /// Get proof levels as slice
+    pub fn as_slice(&self) -> &[Vec<ClaimMultiProofNode>] {
+        &self.0
+    }
+
+    /// Get total number of sibling hashes across all levels
+    pub fn total_hashes(&self) -> usize {
+        multiproof_total_hashes(&self.0)
+    }
+
+    /// Verify the multiproof for `leaves`, given in tree order.
+    ///
+    /// Uses the same sorted-pair hashing as [`ClaimProofV0::verify`]. Every leaf must be
+    /// consumed exactly once and the levels must fold into `root`.
+    pub fn verify(&self, root: &[u8; 32], leaves: &[ClaimLeaf]) -> bool {
+        self.verify_with::<Sha256ClaimHasher>(root, leaves)
+    }
+
+    /// Verify like [`ClaimMultiProofV0::verify`], hashing with `H`.
+    pub fn verify_with<H: ClaimHasher>(&self, root: &[u8; 32], leaves: &[ClaimLeaf]) -> bool {
+        fold_multiproof::<H>(&self.0, leaves, |children| match children.len() {
+            1 => Some(children[0]),
+            2 => Some(H::hash_internal(children)),
+            _ => None,
+        }) == Some(*root)
+    }
+}
+
+/// Multiproof for a batch of leaves in a wide (V1) merkle tree.
+///
+/// Same layout as [`ClaimMultiProofV0`], but every node is hashed, including nodes with
+/// a single child, as in [`ClaimProofV1::verify`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ClaimMultiProofV1(pub Vec<Vec<ClaimMultiProofNode>>);
+
+impl ClaimMultiProofV1 {
+    /// Create a new wide tree multiproof
+    pub fn new(levels: Vec<Vec<ClaimMultiProofNode>>) -> Self {
+        Self(levels)
+    }
+
+    /// Get proof levels as slice
+    pub fn as_slice(&self) -> &[Vec<ClaimMultiProofNode>] {
+        &self.0
+    }
+
+    /// Get total number of sibling hashes across all levels
+    pub fn total_hashes(&self) -> usize {
+        multiproof_total_hashes(&self.0)
+    }
+
+    /// Verify the multiproof for `leaves`, given in tree order.
+    pub fn verify(&self, root: &[u8; 32], leaves: &[ClaimLeaf]) -> bool {
+        self.verify_with_arity(root, leaves, claim_tree_constants::MAX_TREE_ARITY)
+    }
+
+    /// Verify the multiproof for a tree with at most `arity` children per internal node.
+    pub fn verify_with_arity(&self, root: &[u8; 32], leaves: &[ClaimLeaf], arity: u16) -> bool {
+        self.verify_with::<Sha256ClaimHasher>(root, leaves, arity)
+    }
+
+    /// Verify like [`ClaimMultiProofV1::verify_with_arity`], hashing with `H`.
+    pub fn verify_with<H: ClaimHasher>(
+        &self,
+        root: &[u8; 32],
+        leaves: &[ClaimLeaf],
+        arity: u16,
+    ) -> bool {
+        fold_multiproof::<H>(&self.0, leaves, |children| {
+            (children.len() <= arity as usize).then(|| H::hash_internal(children))
+        }) == Some(*root)
+    }
+}
+
+/// Fold a multiproof from the leaves up, returning the reconstructed root.
+///
+/// `hash_node` receives each node's children (known children first, then siblings)
+/// and returns `None` for a node the tree can't contain. Returns `None` for an empty
+/// batch, a node without known children, or known nodes left over or missing at any level.
+fn fold_multiproof<H: ClaimHasher>(
+    levels: &[Vec<ClaimMultiProofNode>],
+    leaves: &[ClaimLeaf],
+    hash_node: impl Fn(&mut [[u8; 32]]) -> Option<[u8; 32]>,
+) -> Option<[u8; 32]> {
+    let mut known: Vec<[u8; 32]> = leaves.iter().map(H::hash_leaf).collect();
+
+    for level in levels {
+        let mut next = Vec::with_capacity(level.len());
+        let mut consumed = 0;
+
+        for node in level {
+            let known_children = node.known_children as usize;
+            if known_children == 0 || consumed + known_children > known.len() {
+                return None;
+            }
+
+            let mut children = Vec::with_capacity(known_children + node.siblings.len());
+            children.extend_from_slice(&known[consumed..consumed + known_children]);
+            children.extend_from_slice(&node.siblings);
+            next.push(hash_node(&mut children)?);
+            consumed += known_children;
+        }
+
+        if consumed != known.len() {
+            return None;
+        }
+        known = next;
+    }
+
+    match known.as_slice() {
+        [root] => Some(*root),
+        _ => None,
+    }
+}
+
+fn multiproof_total_hashes(levels: &[Vec<ClaimMultiProofNode>]) -> usize {
+    levels
+        .iter()
+        .flatten()
+        .map(|node| node.siblings.len())
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_lang::solana_program::hash::Hasher as SolanaHasher;
 
     fn create_test_leaf() -> ClaimLeaf {
         ClaimLeaf {
@@ -408,6 +644,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_proof_verification_respects_arity() {
+        let leaf = create_test_leaf();
+        let siblings = [[1u8; 32], [2u8; 32], [3u8; 32]];
+
+        let mut level = vec![leaf.to_hash()];
+        level.extend_from_slice(&siblings);
+        level.sort();
+        let mut hasher = SolanaHasher::default();
+        hasher.hash(&[claim_tree_constants::INTERNAL_PREFIX]);
+        for hash in &level {
+            hasher.hash(hash);
+        }
+        let root = hasher.result().to_bytes();
+
+        // 3 siblings need a tree of arity 4 or more
+        let proof = ClaimProofType::from_wide(vec![siblings.to_vec()]);
+        assert!(proof.verify(&root, &leaf, 4));
+        assert!(proof.verify(&root, &leaf, claim_tree_constants::MAX_TREE_ARITY));
+        assert!(!proof.verify(&root, &leaf, 3));
+        assert!(!proof.verify(&root, &leaf, claim_tree_constants::BINARY_TREE_ARITY));
+
+        // Binary proofs only verify against binary cohorts
+        let sibling = [7u8; 32];
+        let mut pair = [leaf.to_hash(), sibling];
+        pair.sort();
+        let mut hasher = SolanaHasher::default();
+        hasher.hash(&[claim_tree_constants::INTERNAL_PREFIX]);
+        hasher.hash(&pair[0]);
+        hasher.hash(&pair[1]);
+        let root = hasher.result().to_bytes();
+
+        let proof = ClaimProofType::from_binary(vec![sibling]);
+        assert!(proof.verify(&root, &leaf, claim_tree_constants::BINARY_TREE_ARITY));
+        assert!(!proof.verify(&root, &leaf, 4));
+    }
+
+    #[test]
+    fn test_multiproof_verification() {
+        let leaves: Vec<ClaimLeaf> = (0..3).map(|_| create_test_leaf()).collect();
+        let [a, b, c] = [
+            leaves[0].to_hash(),
+            leaves[1].to_hash(),
+            leaves[2].to_hash(),
+        ];
+        let node = |known_children, siblings: Vec<[u8; 32]>| ClaimMultiProofNode {
+            known_children,
+            siblings,
+        };
+
+        // Binary tree of 3 leaves: the odd leaf `c` is promoted, root = H(H(a, b), c)
+        let ab = Sha256ClaimHasher::hash_internal(&mut [a, b]);
+        let binary_root = Sha256ClaimHasher::hash_internal(&mut [ab, c]);
+        let batch = [leaves[0].clone(), leaves[2].clone()];
+        let binary = ClaimMultiProofV0::new(vec![
+            vec![node(1, vec![b]), node(1, vec![])],
+            vec![node(2, vec![])],
+        ]);
+        assert_eq!(binary.total_hashes(), 1);
+        assert!(binary.verify(&binary_root, &batch));
+
+        // Leaves out of tree order, missing or left over don't verify
+        assert!(!binary.verify(&binary_root, &[leaves[2].clone(), leaves[0].clone()]));
+        assert!(!binary.verify(&binary_root, &batch[..1]));
+        assert!(!binary.verify(&binary_root, &leaves));
+        assert!(!binary.verify(&binary_root, &[]));
+
+        // Nodes without known children are rejected
+        let padded = ClaimMultiProofV0::new(vec![
+            vec![node(1, vec![b]), node(0, vec![c])],
+            vec![node(1, vec![])],
+        ]);
+        assert!(!padded.verify(&binary_root, &batch[..1]));
+
+        // A wide tree hashes single-child nodes: root = H(H(a, b), H(c))
+        let wide_root =
+            Sha256ClaimHasher::hash_internal(&mut [ab, Sha256ClaimHasher::hash_internal(&mut [c])]);
+        let wide = ClaimMultiProofV1::new(binary.clone().0);
+        assert!(wide.verify(&wide_root, &batch));
+        assert!(!wide.verify(&binary_root, &batch));
+        assert!(!binary.verify(&wide_root, &batch));
+
+        // The cohort arity bounds every node, and binary multiproofs need a binary cohort
+        let flat_root = Sha256ClaimHasher::hash_internal(&mut [a, b, c]);
+        let flat = ClaimMultiProofType::V1(ClaimMultiProofV1::new(vec![vec![node(2, vec![b])]]));
+        assert!(flat.verify(&flat_root, &batch, 3));
+        assert!(!flat.verify(&flat_root, &batch, claim_tree_constants::BINARY_TREE_ARITY));
+        let binary = ClaimMultiProofType::V0(binary);
+        assert!(binary.verify(
+            &binary_root,
+            &batch,
+            claim_tree_constants::BINARY_TREE_ARITY
+        ));
+        assert!(!binary.verify(&binary_root, &batch, 3));
+    }
+
     #[test]
     fn test_proof_v1_empty_levels() {
         // Test that verification works with empty levels (no siblings at some levels)
@@ -512,4 +844,39 @@ mod tests {
             "Single empty level should work"
         );
     }
+
+    #[test]
+    fn test_proof_verification_per_hash_scheme() {
+        let leaves = [create_test_leaf(), create_test_leaf(), create_test_leaf()];
+        let binary = claim_tree_constants::BINARY_TREE_ARITY;
+
+        for scheme in ClaimHashScheme::ALL {
+            let [a, b, c] = [0, 1, 2].map(|i| leaves[i].to_hash_with_scheme(scheme));
+            let ab = scheme.hash_internal(&mut [a, b]);
+
+            // Binary tree: the odd leaf `c` is promoted
+            let binary_root = scheme.hash_internal(&mut [ab, c]);
+            let proof = ClaimProofType::from_binary(vec![b, c]);
+            assert!(proof.verify_with_scheme(&binary_root, &leaves[0], binary, scheme));
+
+            // Wide tree of arity 3
+            let flat_root = scheme.hash_internal(&mut [a, b, c]);
+            let proof = ClaimProofType::from_wide(vec![vec![b, c]]);
+            assert!(proof.verify_with_scheme(&flat_root, &leaves[0], 3, scheme));
+
+            let multi =
+                ClaimMultiProofType::V1(ClaimMultiProofV1::new(vec![vec![ClaimMultiProofNode {
+                    known_children: 2,
+                    siblings: vec![b],
+                }]]));
+            let batch = [leaves[0].clone(), leaves[2].clone()];
+            assert!(multi.verify_with_scheme(&flat_root, &batch, 3, scheme));
+
+            // A proof never verifies under the other scheme
+            for other in ClaimHashScheme::ALL.into_iter().filter(|s| *s != scheme) {
+                assert!(!proof.verify_with_scheme(&flat_root, &leaves[0], 3, other));
+                assert!(!multi.verify_with_scheme(&flat_root, &batch, 3, other));
+            }
+        }
+    }
 }
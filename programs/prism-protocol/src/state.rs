@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::ClaimHashScheme;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum CampaignStatus {
     Inactive,          // Deployed but not activated
@@ -79,6 +81,24 @@ pub struct CohortV0 {
     pub bump: u8,
 }
 
+#[account] // seed [COHORT_TREE_V0_SEED_PREFIX, cohort]
+#[derive(InitSpace)]
+pub struct CohortTreeV0 {
+    /// Pubkey of the Cohort account whose merkle tree this describes.
+    pub cohort: Pubkey,
+
+    /// Maximum children per internal node of the cohort's merkle tree.
+    /// `claim_tokens_v2` rejects proofs with more than `merkle_tree_arity - 1` siblings at any level.
+    pub merkle_tree_arity: u16,
+
+    /// Hash function and leaf encoding of the cohort's merkle tree.
+    /// `claim_tokens_v2` verifies proofs with this scheme.
+    pub hash_scheme: ClaimHashScheme,
+
+    /// Bump seed for the CohortTree PDA.
+    pub bump: u8,
+}
+
 #[account] // seed [CLAIM_RECEIPT_V0_SEED_PREFIX, cohort, claimant]
 #[derive(InitSpace)]
 pub struct ClaimReceiptV0 {
@@ -0,0 +1,12 @@
+; ModuleID = 'autocfg_cd42b1a8bf6c07d1_0.f7b32f28f66926ef-cgu.0'
+source_filename = "autocfg_cd42b1a8bf6c07d1_0.f7b32f28f66926ef-cgu.0"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-i128:128-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-unknown-linux-gnu"
+
+!llvm.module.flags = !{!0, !1, !2}
+!llvm.ident = !{!3}
+
+!0 = !{i32 8, !"PIC Level", i32 2}
+!1 = !{i32 2, !"RtLibUseGOT", i32 1}
+!2 = !{i32 7, !"uwtable", i32 2}
+!3 = !{!"rustc version 1.99.1 (58cf8f9c3 2026-10-13)"}